@@ -13,9 +13,9 @@ pub use edr_chain_spec_evm::{
     result::ExecutionResult, CfgEnv, Context, Inspector, Journal, TransactionError,
 };
 pub use edr_database_components::{DatabaseComponentError, DatabaseComponents, WrapDatabaseRef};
+pub use edr_precompile::CustomPrecompile;
 use edr_primitives::{Address, HashMap};
-use edr_state_api::{DynState, StateDiff, StateError};
-pub use revm_precompile::PrecompileFn;
+use edr_state_api::{account::Account, DynState, StateDiff, StateError};
 
 /// Helper type for a chain-specific [`BlockBuilderCreationError`].
 pub type BlockBuilderCreationErrorForChainSpec<ChainSpecT, DatabaseErrorT> =
@@ -110,6 +110,9 @@ pub struct BuiltBlockAndState<HaltReasonT: HaltReasonTrait, LocalBlockT> {
     pub state_diff: StateDiff,
     /// Transaction results
     pub transaction_results: Vec<ExecutionResult<HaltReasonT>>,
+    /// The changes that each transaction made to the state, in the order of
+    /// the block's transactions
+    pub transaction_state_changes: Vec<HashMap<Address, Account>>,
 }
 
 /// A trait for building blocks.
@@ -147,7 +150,7 @@ pub trait BlockBuilder<
         evm_config: &EvmConfig,
         inputs: BlockInputs,
         overrides: HeaderOverrides<ChainSpecT::Hardfork>,
-        custom_precompiles: &'builder HashMap<Address, PrecompileFn>,
+        custom_precompiles: &'builder HashMap<Address, CustomPrecompile>,
     ) -> Result<
         Self,
         BlockBuilderCreationErrorForChainSpec<
@@ -384,33 +384,9 @@ impl PartialHeader {
             }),
             blob_gas: overrides.blob_gas.or_else(|| {
                 if evm_spec_id >= EvmSpecId::CANCUN {
-                    let excess_gas = parent.and_then(|parent| parent.blob_gas.as_ref()).map_or(
-                        // For the first (post-fork) block, both parent.blob_gas_used and
-                        // parent.excess_blob_gas are evaluated as 0.
-                        0,
-                        |BlobGas {
-                             gas_used,
-                             excess_gas,
-                         }| {
-                            let blob_params = blob_params_for_hardfork(evm_spec_id);
-
-                            let base_fee = if evm_spec_id >= EvmSpecId::OSAKA {
-                                base_fee.expect("base fee must be set for post-Osaka blocks")
-                            } else {
-                                // In pre-Osaka (EIP-4844) scenarios, the base fee parameter is not
-                                // used in excess blob gas calculation. Passing 0 is acceptable here
-                                // because `next_block_excess_blob_gas_osaka` ignores the base fee
-                                // value for these hardforks.
-                                0
-                            };
-
-                            blob_params.next_block_excess_blob_gas_osaka(
-                                *excess_gas,
-                                *gas_used,
-                                base_fee.try_into().expect("base fee is too large"),
-                            )
-                        },
-                    );
+                    let excess_gas = parent.map_or(0, |parent| {
+                        calculate_next_excess_blob_gas(parent, evm_spec_id, base_fee)
+                    });
 
                     Some(BlobGas {
                         gas_used: 0,
@@ -602,6 +578,46 @@ pub fn calculate_next_base_fee_per_gas<HardforkT: PartialOrd>(
     }
 }
 
+/// Calculates the excess blob gas of a post-Cancun block, given the parent's
+/// header and the block's base fee.
+///
+/// The base fee is only used from Osaka onwards.
+pub fn calculate_next_excess_blob_gas<HardforkT: Into<EvmSpecId>>(
+    parent: &BlockHeader,
+    hardfork: HardforkT,
+    base_fee: Option<u128>,
+) -> u64 {
+    let evm_spec_id = hardfork.into();
+
+    parent.blob_gas.as_ref().map_or(
+        // For the first (post-fork) block, both parent.blob_gas_used and
+        // parent.excess_blob_gas are evaluated as 0.
+        0,
+        |BlobGas {
+             gas_used,
+             excess_gas,
+         }| {
+            let blob_params = blob_params_for_hardfork(evm_spec_id);
+
+            let base_fee = if evm_spec_id >= EvmSpecId::OSAKA {
+                base_fee.expect("base fee must be set for post-Osaka blocks")
+            } else {
+                // In pre-Osaka (EIP-4844) scenarios, the base fee parameter is not
+                // used in excess blob gas calculation. Passing 0 is acceptable here
+                // because `next_block_excess_blob_gas_osaka` ignores the base fee
+                // value for these hardforks.
+                0
+            };
+
+            blob_params.next_block_excess_blob_gas_osaka(
+                *excess_gas,
+                *gas_used,
+                base_fee.try_into().expect("base fee is too large"),
+            )
+        },
+    )
+}
+
 /// Calculates the next base fee per blob gas for a post-Cancun block, given the
 /// parent's header.
 pub fn calculate_next_base_fee_per_blob_gas<HardforkT: Into<EvmSpecId>>(
@@ -3,11 +3,13 @@ use std::{cmp::Ordering, fmt::Debug};
 use edr_block_api::Block as _;
 use edr_block_builder_api::{
     BlockBuilder, BlockBuilderCreationError, BlockFinalizeError, BlockInputs,
-    BlockTransactionError, Blockchain, BuiltBlockAndState, PrecompileFn, WrapDatabaseRef,
+    BlockTransactionError, Blockchain, BuiltBlockAndState, CustomPrecompile, WrapDatabaseRef,
+};
+use edr_block_header::{
+    calculate_next_base_fee_per_blob_gas, BlockHeader, HeaderOverrides, PartialHeader,
 };
-use edr_block_header::{calculate_next_base_fee_per_blob_gas, HeaderOverrides, PartialHeader};
 use edr_chain_spec::{
-    ChainSpec, EvmTransactionValidationError, ExecutableTransaction, HardforkChainSpec,
+    ChainSpec, EvmSpecId, EvmTransactionValidationError, ExecutableTransaction, HardforkChainSpec,
     TransactionValidation,
 };
 use edr_chain_spec_block::BlockChainSpec;
@@ -16,9 +18,10 @@ use edr_chain_spec_evm::{
 };
 use edr_database_components::DatabaseComponents;
 use edr_mem_pool::{MemPool, OrderedTransaction};
-use edr_primitives::{Address, HashMap};
+use edr_primitives::{Address, HashMap, HashSet, B256};
 use edr_signer::SignatureError;
 use edr_state_api::{DynState, StateError};
+use edr_transaction::IsDeposit;
 use serde::{Deserialize, Serialize};
 
 /// The type of ordering to use when selecting blocks to mine.
@@ -30,6 +33,50 @@ pub enum MineOrdering {
     Priority,
 }
 
+/// Summary of a pending transaction, provided to a
+/// [`SyncMineOrderingCallback`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingTransactionSummary {
+    /// The transaction's hash
+    pub transaction_hash: B256,
+    /// The transaction's sender
+    pub caller: Address,
+    /// The transaction's nonce
+    pub nonce: u64,
+    /// The transaction's gas price or, for EIP-1559 transactions, max fee per
+    /// gas
+    pub gas_price: u128,
+    /// The transaction's max priority fee per gas, if it's an EIP-1559
+    /// transaction
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The order in which the transaction was added to the mem pool
+    pub order_id: usize,
+}
+
+/// Callback that determines the order in which pending transactions are
+/// mined, overriding the [`MineOrdering`].
+///
+/// Receives the pending transactions of the mem pool and returns their hashes
+/// in order of descending priority. Omitted transactions are ranked below all
+/// others, in insertion order. A sender's transactions are always mined in
+/// nonce order, regardless of their ranking.
+pub trait SyncMineOrderingCallback:
+    Fn(&[PendingTransactionSummary]) -> Result<Vec<B256>, MineOrderingCallbackError> + Send + Sync
+{
+}
+
+impl<F> SyncMineOrderingCallback for F where
+    F: Fn(&[PendingTransactionSummary]) -> Result<Vec<B256>, MineOrderingCallbackError>
+        + Send
+        + Sync
+{
+}
+
+/// An error that occurred while running a [`SyncMineOrderingCallback`].
+#[derive(Debug, thiserror::Error)]
+#[error("Mine ordering callback failed: {0}")]
+pub struct MineOrderingCallbackError(pub String);
+
 /// Helper type for a chain-specific [`MineBlockError`].
 pub type MineBlockErrorForChainSpec<BlockchainErrorT, ChainSpecT, StateErrorT> = MineBlockError<
     BlockchainErrorT,
@@ -69,9 +116,20 @@ pub enum MineBlockError<BlockchainErrorT, HardforkT, StateErrorT, TransactionVal
     /// on a post-merge hardfork.
     #[error("Post-merge transaction is missing prevrandao")]
     MissingPrevrandao,
+    /// An error that occurred while running the mine ordering callback.
+    #[error(transparent)]
+    MineOrderingCallback(#[from] MineOrderingCallbackError),
 }
 
 /// Mines a block using as many transactions as can fit in it.
+///
+/// The `forced_transactions` are included at the top of the block, in the
+/// provided order. Forced transactions that cannot be included are skipped,
+/// so the caller needs to check the mined block for their inclusion.
+/// Subsequently, transactions are selected from the mem pool according to the
+/// `ordering_callback`, if provided. Otherwise, the `mine_ordering` is used.
+/// The `excluded_transactions` and the subsequent transactions of their
+/// senders are not selected.
 #[allow(clippy::too_many_arguments)]
 // `DebugContext` cannot be simplified further
 #[allow(clippy::type_complexity)]
@@ -95,14 +153,18 @@ pub fn mine_block<
         ChainSpecT::SignedTransaction,
     >,
     state: Box<dyn DynState>,
+    forced_transactions: Vec<ChainSpecT::SignedTransaction>,
     mem_pool: &MemPool<ChainSpecT::SignedTransaction>,
+    excluded_transactions: &HashSet<B256>,
     evm_config: &EvmConfig,
+    block_inputs: BlockInputs,
     overrides: HeaderOverrides<ChainSpecT::Hardfork>,
     min_gas_price: u128,
     mine_ordering: MineOrdering,
+    ordering_callback: Option<&dyn SyncMineOrderingCallback>,
     reward: u128,
     mut inspector: Option<&mut InspectorT>,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
 ) -> Result<
     BuiltBlockAndState<ChainSpecT::HaltReason, ChainSpecT::LocalBlock>,
     MineBlockErrorForChainSpec<BlockchainErrorT, ChainSpecT, StateError>,
@@ -128,7 +190,6 @@ where
         >,
     >,
 {
-    let block_inputs = BlockInputs::empty(blockchain.hardfork());
     let mut block_builder = ChainSpecT::BlockBuilder::new_block_builder(
         blockchain,
         state,
@@ -138,6 +199,22 @@ where
         custom_precompiles,
     )?;
 
+    for transaction in forced_transactions {
+        let result = if let Some(inspector) = inspector.as_mut() {
+            block_builder.add_transaction_with_inspector(transaction, inspector)
+        } else {
+            block_builder.add_transaction(transaction)
+        };
+
+        // An invalid forced transaction shouldn't prevent the block from being mined
+        if let Err(error @ BlockTransactionError::Transaction(TransactionError::Database(_))) =
+            result
+        {
+            return Err(MineBlockError::BlockTransaction(error));
+        }
+    }
+
+    let base_fee = block_builder.header().base_fee;
     let mut pending_transactions = {
         type MineOrderComparator<SignedTransactionT> = dyn Fn(
                 &OrderedTransaction<SignedTransactionT>,
@@ -145,20 +222,51 @@ where
             ) -> Ordering
             + Send;
 
-        let base_fee = block_builder.header().base_fee;
         let comparator: Box<MineOrderComparator<ChainSpecT::SignedTransaction>> =
-            match mine_ordering {
-                MineOrdering::Fifo => Box::new(first_in_first_out_comparator),
-                MineOrdering::Priority => {
-                    Box::new(move |lhs, rhs| priority_comparator(lhs, rhs, base_fee))
+            if let Some(ordering_callback) = ordering_callback {
+                let summaries = mem_pool
+                    .pending_transactions()
+                    .filter(|transaction| {
+                        !excluded_transactions.contains(transaction.pending().transaction_hash())
+                    })
+                    .map(pending_transaction_summary)
+                    .collect::<Vec<_>>();
+
+                let ranking = ordering_callback(&summaries)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(rank, transaction_hash)| (transaction_hash, rank))
+                    .collect::<HashMap<_, _>>();
+
+                Box::new(move |lhs, rhs| ranked_comparator(lhs, rhs, &ranking))
+            } else {
+                match mine_ordering {
+                    MineOrdering::Fifo => Box::new(first_in_first_out_comparator),
+                    MineOrdering::Priority => {
+                        Box::new(move |lhs, rhs| priority_comparator(lhs, rhs, base_fee))
+                    }
                 }
             };
 
-        mem_pool.iter(comparator)
+        let mut pending_transactions = mem_pool.iter(comparator);
+        pending_transactions.remove_transactions(excluded_transactions);
+
+        pending_transactions
     };
 
     while let Some(transaction) = pending_transactions.next() {
-        if *transaction.gas_price() < min_gas_price {
+        // Without the base fee check, transactions that pay less than the base
+        // fee are admitted based on their priority fee, so they are selected
+        // the same way.
+        let miner_fee = if evm_config.disable_base_fee {
+            *transaction
+                .max_priority_fee_per_gas()
+                .unwrap_or_else(|| transaction.gas_price())
+        } else {
+            effective_miner_fee(&transaction, base_fee)
+        };
+
+        if miner_fee < min_gas_price {
             pending_transactions.remove_caller(transaction.caller());
             continue;
         }
@@ -174,7 +282,8 @@ where
 
             if let Err(error) = result {
                 match error {
-                    BlockTransactionError::ExceedsBlockGasLimit => {
+                    BlockTransactionError::ExceedsBlockGasLimit
+                    | BlockTransactionError::ExceedsBlockBlobGasLimit => {
                         pending_transactions.remove_caller(&caller);
                     }
                     BlockTransactionError::Transaction(TransactionError::InvalidTransaction(
@@ -198,6 +307,86 @@ where
         .map_err(MineBlockError::BlockFinalize)
 }
 
+/// Mines a block with the provided transactions, in the provided order.
+///
+/// Unlike [`mine_block`], transactions are not selected or validated against
+/// the miner's policy; any transaction that cannot be included results in an
+/// error. This is used to re-create previously mined blocks.
+#[allow(clippy::too_many_arguments)]
+// `DebugContext` cannot be simplified further
+#[allow(clippy::type_complexity)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn mine_block_with_transactions<
+    ChainSpecT: BlockChainSpec,
+    BlockchainErrorT: std::error::Error,
+    InspectorT,
+>(
+    blockchain: &dyn Blockchain<
+        ChainSpecT::Receipt,
+        ChainSpecT::Block,
+        BlockchainErrorT,
+        ChainSpecT::Hardfork,
+        ChainSpecT::LocalBlock,
+        ChainSpecT::SignedTransaction,
+    >,
+    state: Box<dyn DynState>,
+    transactions: Vec<ChainSpecT::SignedTransaction>,
+    evm_config: &EvmConfig,
+    block_inputs: BlockInputs,
+    overrides: HeaderOverrides<ChainSpecT::Hardfork>,
+    reward: u128,
+    mut inspector: Option<&mut InspectorT>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
+) -> Result<
+    BuiltBlockAndState<ChainSpecT::HaltReason, ChainSpecT::LocalBlock>,
+    MineBlockErrorForChainSpec<BlockchainErrorT, ChainSpecT, StateError>,
+>
+where
+    InspectorT: for<'inspector> Inspector<
+        ContextForChainSpec<
+            ChainSpecT,
+            ChainSpecT::BlockEnv<'inspector, PartialHeader>,
+            WrapDatabaseRef<
+                DatabaseComponents<
+                    &'inspector dyn Blockchain<
+                        ChainSpecT::Receipt,
+                        ChainSpecT::Block,
+                        BlockchainErrorT,
+                        ChainSpecT::Hardfork,
+                        ChainSpecT::LocalBlock,
+                        ChainSpecT::SignedTransaction,
+                    >,
+                    &'inspector dyn DynState,
+                >,
+            >,
+        >,
+    >,
+{
+    let mut block_builder = ChainSpecT::BlockBuilder::new_block_builder(
+        blockchain,
+        state,
+        evm_config,
+        block_inputs,
+        overrides,
+        custom_precompiles,
+    )?;
+
+    for transaction in transactions {
+        if let Some(inspector) = inspector.as_mut() {
+            block_builder.add_transaction_with_inspector(transaction, inspector)?;
+        } else {
+            block_builder.add_transaction(transaction)?;
+        }
+    }
+
+    let beneficiary = block_builder.header().beneficiary;
+    let rewards = vec![(beneficiary, reward)];
+
+    block_builder
+        .finalize_block(rewards)
+        .map_err(MineBlockError::BlockFinalize)
+}
+
 /// Helper type for a chain-specific [`MineTransactionError`].
 pub type MineTransactionErrorForChainSpec<ChainSpecT, BlockchainErrorT> = MineTransactionError<
     BlockchainErrorT,
@@ -325,16 +514,18 @@ pub fn mine_block_with_single_transaction<
     state: Box<dyn DynState>,
     transaction: ChainSpecT::SignedTransaction,
     evm_config: &EvmConfig,
+    block_inputs: BlockInputs,
     overrides: HeaderOverrides<ChainSpecT::Hardfork>,
     min_gas_price: u128,
     reward: u128,
     inspector: Option<&mut InspectorT>,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
 ) -> Result<
     BuiltBlockAndState<ChainSpecT::HaltReason, ChainSpecT::LocalBlock>,
     MineTransactionErrorForChainSpec<ChainSpecT, BlockchainErrorT>,
 >
 where
+    ChainSpecT::SignedTransaction: IsDeposit,
     InspectorT: for<'inspector> Inspector<
         ContextForChainSpec<
             ChainSpecT,
@@ -355,6 +546,70 @@ where
         >,
     >,
 {
+    let hardfork = blockchain.hardfork();
+
+    // Deposit transactions are created by the chain's sequencer, so they don't
+    // pay fees and their nonce isn't validated.
+    if !transaction.is_deposit() {
+        let parent_block = blockchain
+            .last_block()
+            .map_err(MineTransactionError::Blockchain)?;
+
+        // The base fee is still used for the block header, even if the EVM
+        // doesn't enforce it.
+        let base_fee = if evm_config.disable_base_fee {
+            None
+        } else {
+            overrides.base_fee
+        };
+
+        validate_single_transaction(
+            &transaction,
+            &*state,
+            parent_block.block_header(),
+            hardfork,
+            base_fee,
+            min_gas_price,
+        )?;
+    }
+
+    let mut block_builder = ChainSpecT::BlockBuilder::new_block_builder(
+        blockchain,
+        state,
+        evm_config,
+        block_inputs,
+        overrides,
+        custom_precompiles,
+    )?;
+
+    let beneficiary = block_builder.header().beneficiary;
+    let rewards = vec![(beneficiary, reward)];
+
+    if let Some(inspector) = inspector {
+        block_builder.add_transaction_with_inspector(transaction, inspector)?;
+    } else {
+        block_builder.add_transaction(transaction)?;
+    }
+
+    block_builder
+        .finalize_block(rewards)
+        .map_err(MineTransactionError::Finalize)
+}
+
+/// Validates that a transaction can be mined in the next block, on top of the
+/// provided parent block and state.
+fn validate_single_transaction<
+    BlockchainErrorT,
+    HardforkT: Copy + Into<EvmSpecId>,
+    TransactionValidationErrorT,
+>(
+    transaction: &impl ExecutableTransaction,
+    state: &dyn DynState,
+    parent_header: &BlockHeader,
+    hardfork: HardforkT,
+    base_fee: Option<u128>,
+    min_gas_price: u128,
+) -> Result<(), MineTransactionError<BlockchainErrorT, HardforkT, TransactionValidationErrorT>> {
     let max_priority_fee_per_gas = transaction
         .max_priority_fee_per_gas()
         .unwrap_or_else(|| transaction.gas_price());
@@ -366,7 +621,7 @@ where
         });
     }
 
-    if let Some(base_fee_per_gas) = overrides.base_fee {
+    if let Some(base_fee_per_gas) = base_fee {
         if let Some(max_fee_per_gas) = transaction.max_fee_per_gas() {
             if *max_fee_per_gas < base_fee_per_gas {
                 return Err(MineTransactionError::MaxFeePerGasTooLow {
@@ -385,15 +640,8 @@ where
         }
     }
 
-    let parent_block = blockchain
-        .last_block()
-        .map_err(MineTransactionError::Blockchain)?;
-
-    let hardfork = blockchain.hardfork();
-
     if let Some(max_fee_per_blob_gas) = transaction.max_fee_per_blob_gas() {
-        let base_fee_per_blob_gas =
-            calculate_next_base_fee_per_blob_gas(parent_block.block_header(), hardfork);
+        let base_fee_per_blob_gas = calculate_next_base_fee_per_blob_gas(parent_header, hardfork);
         if *max_fee_per_blob_gas < base_fee_per_blob_gas {
             return Err(MineTransactionError::MaxFeePerBlobGasTooLow {
                 expected: base_fee_per_blob_gas,
@@ -424,27 +672,7 @@ where
         }
     }
 
-    let mut block_builder = ChainSpecT::BlockBuilder::new_block_builder(
-        blockchain,
-        state,
-        evm_config,
-        BlockInputs::empty(hardfork),
-        overrides,
-        custom_precompiles,
-    )?;
-
-    let beneficiary = block_builder.header().beneficiary;
-    let rewards = vec![(beneficiary, reward)];
-
-    if let Some(inspector) = inspector {
-        block_builder.add_transaction_with_inspector(transaction, inspector)?;
-    } else {
-        block_builder.add_transaction(transaction)?;
-    }
-
-    block_builder
-        .finalize_block(rewards)
-        .map_err(MineTransactionError::Finalize)
+    Ok(())
 }
 
 fn effective_miner_fee(transaction: &impl ExecutableTransaction, base_fee: Option<u128>) -> u128 {
@@ -453,8 +681,10 @@ fn effective_miner_fee(transaction: &impl ExecutableTransaction, base_fee: Optio
         .max_priority_fee_per_gas()
         .unwrap_or(max_fee_per_gas);
 
+    // Transactions that pay less than the base fee can only be mined when the
+    // base fee check is disabled, in which case they don't pay the miner.
     base_fee.map_or(*max_fee_per_gas, |base_fee| {
-        max_priority_fee_per_gas.min(*max_fee_per_gas - base_fee)
+        max_priority_fee_per_gas.min(max_fee_per_gas.saturating_sub(base_fee))
     })
 }
 
@@ -485,6 +715,39 @@ fn priority_comparator<SignedTransactionT: ExecutableTransaction>(
     }
 }
 
+fn ranked_comparator<SignedTransactionT: ExecutableTransaction>(
+    lhs: &OrderedTransaction<SignedTransactionT>,
+    rhs: &OrderedTransaction<SignedTransactionT>,
+    ranking: &HashMap<B256, usize>,
+) -> Ordering {
+    let rank = |transaction: &OrderedTransaction<SignedTransactionT>| {
+        ranking
+            .get(transaction.pending().transaction_hash())
+            .copied()
+            .unwrap_or(usize::MAX)
+    };
+
+    // Unranked transactions and ties are sorted in increasing order by orderId
+    rank(lhs)
+        .cmp(&rank(rhs))
+        .then_with(|| lhs.order_id().cmp(&rhs.order_id()))
+}
+
+fn pending_transaction_summary<SignedTransactionT: ExecutableTransaction>(
+    transaction: &OrderedTransaction<SignedTransactionT>,
+) -> PendingTransactionSummary {
+    let pending = transaction.pending();
+
+    PendingTransactionSummary {
+        transaction_hash: *pending.transaction_hash(),
+        caller: *pending.caller(),
+        nonce: pending.nonce(),
+        gas_price: *pending.gas_price(),
+        max_priority_fee_per_gas: pending.max_priority_fee_per_gas().copied(),
+        order_id: transaction.order_id(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use edr_primitives::U256;
@@ -704,4 +967,97 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn ordering_remove_transactions() -> anyhow::Result<()> {
+        let sender1 = Address::random();
+        let sender2 = Address::random();
+
+        let account_with_balance = AccountInfo {
+            balance: U256::from(100_000_000u64),
+            ..AccountInfo::default()
+        };
+        let mut fixture = MemPoolTestFixture::with_accounts(&[
+            (sender1, account_with_balance.clone()),
+            (sender2, account_with_balance),
+        ]);
+
+        let transaction1 = dummy_eip155_transaction_with_price(sender1, 0, 100)?;
+        fixture.add_transaction(transaction1.clone())?;
+
+        let transaction2 = dummy_eip155_transaction_with_price(sender1, 1, 99)?;
+        fixture.add_transaction(transaction2.clone())?;
+
+        let transaction3 = dummy_eip155_transaction_with_price(sender1, 2, 98)?;
+        fixture.add_transaction(transaction3)?;
+
+        let transaction4 = dummy_eip155_transaction_with_price(sender2, 0, 97)?;
+        fixture.add_transaction(transaction4.clone())?;
+
+        let mut ordered_transactions = fixture
+            .mem_pool
+            .iter(|lhs, rhs| priority_comparator(lhs, rhs, None));
+
+        // Removing a transaction removes its sender's subsequent transactions
+        ordered_transactions
+            .remove_transactions(&HashSet::from_iter([*transaction2.transaction_hash()]));
+
+        assert_eq!(ordered_transactions.next(), Some(transaction1));
+        assert_eq!(ordered_transactions.next(), Some(transaction4));
+        assert_eq!(ordered_transactions.next(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ranked_ordering() -> anyhow::Result<()> {
+        let sender1 = Address::random();
+        let sender2 = Address::random();
+        let sender3 = Address::random();
+
+        let account_with_balance = AccountInfo {
+            balance: U256::from(100_000_000u64),
+            ..AccountInfo::default()
+        };
+        let mut fixture = MemPoolTestFixture::with_accounts(&[
+            (sender1, account_with_balance.clone()),
+            (sender2, account_with_balance.clone()),
+            (sender3, account_with_balance),
+        ]);
+
+        let transaction1 = dummy_eip155_transaction_with_price(sender1, 0, 100)?;
+        fixture.add_transaction(transaction1.clone())?;
+
+        let transaction2 = dummy_eip155_transaction_with_price(sender1, 1, 100)?;
+        fixture.add_transaction(transaction2.clone())?;
+
+        let transaction3 = dummy_eip155_transaction_with_price(sender2, 0, 100)?;
+        fixture.add_transaction(transaction3.clone())?;
+
+        let transaction4 = dummy_eip155_transaction_with_price(sender3, 0, 100)?;
+        fixture.add_transaction(transaction4.clone())?;
+
+        // Ranking the second transaction of sender1 first doesn't violate nonce order
+        // and the unranked transaction4 comes last.
+        let ranking = [
+            *transaction2.transaction_hash(),
+            *transaction3.transaction_hash(),
+            *transaction1.transaction_hash(),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(rank, transaction_hash)| (transaction_hash, rank))
+        .collect::<HashMap<_, _>>();
+
+        let mut ordered_transactions = fixture
+            .mem_pool
+            .iter(|lhs, rhs| ranked_comparator(lhs, rhs, &ranking));
+
+        assert_eq!(ordered_transactions.next(), Some(transaction3));
+        assert_eq!(ordered_transactions.next(), Some(transaction1));
+        assert_eq!(ordered_transactions.next(), Some(transaction2));
+        assert_eq!(ordered_transactions.next(), Some(transaction4));
+
+        Ok(())
+    }
 }
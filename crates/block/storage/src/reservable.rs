@@ -142,9 +142,9 @@ impl<
 }
 impl<
         BlockReceiptT: ExecutionReceipt<Log = FilterLog> + ReceiptTrait,
-        BlockT: BlockReceipts<BlockReceiptT>,
+        BlockT: Block<SignedTransactionT> + BlockReceipts<BlockReceiptT> + Clone,
         HardforkT,
-        SignedTransactionT,
+        SignedTransactionT: ExecutableTransaction,
     > ReservableSparseBlockStorage<BlockReceiptT, BlockT, HardforkT, SignedTransactionT>
 {
     /// Retrieves the logs that match the provided filter.
@@ -162,9 +162,9 @@ impl<
 
 impl<
         BlockReceiptT: ExecutionReceipt<Log = FilterLog> + ReceiptTrait,
-        BlockT: FetchBlockReceipts<BlockReceiptT>,
+        BlockT: Block<SignedTransactionT> + FetchBlockReceipts<BlockReceiptT> + Clone,
         HardforkT,
-        SignedTransactionT,
+        SignedTransactionT: ExecutableTransaction,
     > ReservableSparseBlockStorage<BlockReceiptT, BlockT, HardforkT, SignedTransactionT>
 {
     /// Tries to fetch the logs that match the provided filter.
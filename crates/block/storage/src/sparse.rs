@@ -3,7 +3,9 @@ use std::marker::PhantomData;
 use derive_where::derive_where;
 use edr_block_api::{Block, BlockReceipts, FetchBlockReceipts};
 use edr_chain_spec::ExecutableTransaction;
-use edr_primitives::{hash_map::OccupiedError, Address, HashMap, HashSet, B256, U256};
+use edr_primitives::{
+    hash_map::OccupiedError, Address, Bloom, BloomInput, HashMap, HashSet, B256, U256,
+};
 use edr_receipt::{
     log::{matches_address_filter, matches_topics_filter, FilterLog},
     ExecutionReceipt, ReceiptTrait,
@@ -11,6 +13,10 @@ use edr_receipt::{
 
 use crate::{InsertBlockError, InsertReceiptError};
 
+/// The number of consecutive blocks whose log blooms are aggregated into a
+/// single section bloom.
+const BLOOM_SECTION_SIZE: u64 = 1024;
+
 /// A storage solution for storing a subset of a blockchain's blocks in-memory.
 #[derive_where(Debug; BlockReceiptT, BlockT)]
 #[derive_where(Default)]
@@ -18,6 +24,9 @@ pub struct SparseBlockStorage<BlockReceiptT: ReceiptTrait, BlockT, SignedTransac
     hash_to_block: HashMap<B256, BlockT>,
     hash_to_total_difficulty: HashMap<B256, U256>,
     number_to_block: HashMap<u64, BlockT>,
+    /// The aggregated log blooms of sections of [`BLOOM_SECTION_SIZE`] blocks,
+    /// used to skip sections that cannot contain matching logs.
+    section_blooms: HashMap<u64, Bloom>,
     transaction_hash_to_block: HashMap<B256, BlockT>,
     transaction_hash_to_receipt: HashMap<B256, BlockReceiptT>,
     phantom: PhantomData<fn() -> SignedTransactionT>,
@@ -46,13 +55,22 @@ impl<
         let mut hash_to_total_difficulty = HashMap::default();
         hash_to_total_difficulty.insert(*block_hash, total_difficulty);
 
+        let block_header = block.block_header();
+
+        let mut section_blooms = HashMap::default();
+        section_blooms.insert(
+            block_header.number / BLOOM_SECTION_SIZE,
+            block_header.logs_bloom,
+        );
+
         let mut number_to_block = HashMap::default();
-        number_to_block.insert(block.block_header().number, block);
+        number_to_block.insert(block_header.number, block);
 
         Self {
             hash_to_block,
             hash_to_total_difficulty,
             number_to_block,
+            section_blooms,
             transaction_hash_to_block,
             transaction_hash_to_receipt: HashMap::default(),
             phantom: PhantomData,
@@ -96,6 +114,11 @@ impl<
                 .map(|transaction| (*transaction.transaction_hash(), block.clone())),
         );
 
+        self.section_blooms
+            .entry(block_header.number / BLOOM_SECTION_SIZE)
+            .or_default()
+            .accrue_bloom(&block_header.logs_bloom);
+
         // SAFETY: We have checked that the block hash and number are not in the maps,
         // so it's ok to use unchecked.
         let block = unsafe {
@@ -134,6 +157,47 @@ impl<
                 self.transaction_hash_to_receipt.remove(transaction_hash);
             }
         }
+
+        // Recompute the bloom of the section that now contains the last block
+        let last_section = block_number / BLOOM_SECTION_SIZE;
+        self.section_blooms
+            .retain(|section, _| *section < last_section);
+
+        let section_bloom = (last_section * BLOOM_SECTION_SIZE..=block_number)
+            .filter_map(|number| self.number_to_block.get(&number))
+            .fold(Bloom::default(), |mut section_bloom, block| {
+                section_bloom.accrue_bloom(&block.block_header().logs_bloom);
+                section_bloom
+            });
+
+        self.section_blooms.insert(last_section, section_bloom);
+    }
+
+    /// Retrieves the blocks in the provided range whose log bloom indicates
+    /// that they might contain logs matching the provided filter.
+    fn blocks_possibly_containing_logs<'storage>(
+        &'storage self,
+        from_block: u64,
+        to_block: u64,
+        addresses: &'storage HashSet<Address>,
+        topics_filter: &'storage [Option<Vec<B256>>],
+    ) -> impl Iterator<Item = &'storage BlockT> + 'storage {
+        (from_block / BLOOM_SECTION_SIZE..=to_block / BLOOM_SECTION_SIZE)
+            .filter(move |section| {
+                self.section_blooms
+                    .get(section)
+                    .is_some_and(|bloom| bloom_matches_filter(bloom, addresses, topics_filter))
+            })
+            .flat_map(move |section| {
+                let first_block = (section * BLOOM_SECTION_SIZE).max(from_block);
+                let last_block =
+                    (section * BLOOM_SECTION_SIZE + BLOOM_SECTION_SIZE - 1).min(to_block);
+
+                (first_block..=last_block).filter_map(|number| self.number_to_block.get(&number))
+            })
+            .filter(move |block| {
+                bloom_matches_filter(&block.block_header().logs_bloom, addresses, topics_filter)
+            })
     }
 }
 
@@ -221,11 +285,33 @@ impl<BlockReceiptT: ReceiptTrait, BlockT, SignedTransactionT>
     }
 }
 
+/// Whether the bloom indicates that it might contain logs matching the
+/// provided filter.
+fn bloom_matches_filter(
+    bloom: &Bloom,
+    addresses: &HashSet<Address>,
+    topics_filter: &[Option<Vec<B256>>],
+) -> bool {
+    let matches_addresses = addresses.is_empty()
+        || addresses
+            .iter()
+            .any(|address| bloom.contains_input(BloomInput::Raw(address.as_slice())));
+
+    matches_addresses
+        && topics_filter.iter().all(|topics| {
+            topics.as_ref().is_none_or(|topics| {
+                topics
+                    .iter()
+                    .any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice())))
+            })
+        })
+}
+
 /// Retrieves the logs that match the provided filter.
 pub fn logs<
     BlockReceiptT: ExecutionReceipt<Log = FilterLog> + ReceiptTrait,
-    BlockT: BlockReceipts<BlockReceiptT>,
-    SignedTransactionT,
+    BlockT: Block<SignedTransactionT> + BlockReceipts<BlockReceiptT> + Clone,
+    SignedTransactionT: ExecutableTransaction,
 >(
     storage: &SparseBlockStorage<BlockReceiptT, BlockT, SignedTransactionT>,
     from_block: u64,
@@ -236,17 +322,17 @@ pub fn logs<
     let mut logs = Vec::new();
     let addresses: HashSet<Address> = addresses.iter().copied().collect();
 
-    for block_number in from_block..=to_block {
-        if let Some(block) = storage.block_by_number(block_number) {
-            let receipts = block.transaction_receipts();
-            for receipt in receipts {
-                let filtered_logs = receipt.transaction_logs().iter().filter(|log| {
-                    matches_address_filter(&log.address, &addresses)
-                        && matches_topics_filter(log.topics(), topics_filter)
-                });
+    for block in
+        storage.blocks_possibly_containing_logs(from_block, to_block, &addresses, topics_filter)
+    {
+        let receipts = block.transaction_receipts();
+        for receipt in receipts {
+            let filtered_logs = receipt.transaction_logs().iter().filter(|log| {
+                matches_address_filter(&log.address, &addresses)
+                    && matches_topics_filter(log.topics(), topics_filter)
+            });
 
-                logs.extend(filtered_logs.cloned());
-            }
+            logs.extend(filtered_logs.cloned());
         }
     }
 
@@ -256,8 +342,8 @@ pub fn logs<
 /// Tries to fetch the logs that match the provided filter.
 pub fn try_fetch_logs<
     BlockReceiptT: ExecutionReceipt<Log = FilterLog> + ReceiptTrait,
-    BlockT: FetchBlockReceipts<BlockReceiptT>,
-    SignedTransactionT,
+    BlockT: Block<SignedTransactionT> + FetchBlockReceipts<BlockReceiptT> + Clone,
+    SignedTransactionT: ExecutableTransaction,
 >(
     storage: &SparseBlockStorage<BlockReceiptT, BlockT, SignedTransactionT>,
     from_block: u64,
@@ -268,19 +354,86 @@ pub fn try_fetch_logs<
     let mut logs = Vec::new();
     let addresses: HashSet<Address> = addresses.iter().copied().collect();
 
-    for block_number in from_block..=to_block {
-        if let Some(block) = storage.block_by_number(block_number) {
-            let receipts = block.fetch_transaction_receipts()?;
-            for receipt in receipts {
-                let filtered_logs = receipt.transaction_logs().iter().filter(|log| {
-                    matches_address_filter(&log.address, &addresses)
-                        && matches_topics_filter(log.topics(), topics_filter)
-                });
+    for block in
+        storage.blocks_possibly_containing_logs(from_block, to_block, &addresses, topics_filter)
+    {
+        let receipts = block.fetch_transaction_receipts()?;
+        for receipt in receipts {
+            let filtered_logs = receipt.transaction_logs().iter().filter(|log| {
+                matches_address_filter(&log.address, &addresses)
+                    && matches_topics_filter(log.topics(), topics_filter)
+            });
 
-                logs.extend(filtered_logs.cloned());
-            }
+            logs.extend(filtered_logs.cloned());
         }
     }
 
     Ok(logs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bloom_with(address: Address, topic: B256) -> Bloom {
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+        bloom.accrue(BloomInput::Raw(topic.as_slice()));
+        bloom
+    }
+
+    #[test]
+    fn bloom_matches_addresses() {
+        let address = Address::random();
+        let bloom = bloom_with(address, B256::random());
+
+        assert!(bloom_matches_filter(&bloom, &HashSet::default(), &[]));
+        assert!(bloom_matches_filter(
+            &bloom,
+            &[Address::random(), address].into_iter().collect(),
+            &[]
+        ));
+        assert!(!bloom_matches_filter(
+            &bloom,
+            &std::iter::once(Address::random()).collect(),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn bloom_matches_topics() {
+        let topic = B256::random();
+        let bloom = bloom_with(Address::random(), topic);
+        let no_addresses = HashSet::default();
+
+        // Wildcards and alternatives
+        assert!(bloom_matches_filter(
+            &bloom,
+            &no_addresses,
+            &[None, Some(vec![B256::random(), topic])]
+        ));
+        // Every position needs to match
+        assert!(!bloom_matches_filter(
+            &bloom,
+            &no_addresses,
+            &[Some(vec![topic]), Some(vec![B256::random()])]
+        ));
+        assert!(!bloom_matches_filter(
+            &bloom,
+            &no_addresses,
+            &[Some(vec![B256::random()])]
+        ));
+    }
+
+    #[test]
+    fn empty_bloom_only_matches_empty_filter() {
+        let bloom = Bloom::default();
+
+        assert!(bloom_matches_filter(&bloom, &HashSet::default(), &[None]));
+        assert!(!bloom_matches_filter(
+            &bloom,
+            &HashSet::default(),
+            &[Some(vec![B256::random()])]
+        ));
+    }
+}
@@ -472,6 +472,23 @@ impl<
             .state_at_block_number(block_number, state_overrides)
             .map_err(DynBlockchainError::new)
     }
+
+    fn state_at_block_number_from_base(
+        &self,
+        base_state: Box<dyn DynState>,
+        base_block_number: u64,
+        block_number: u64,
+        state_overrides: &BTreeMap<u64, StateOverride>,
+    ) -> Result<Option<Box<dyn DynState>>, Self::BlockchainError> {
+        self.inner
+            .state_at_block_number_from_base(
+                base_state,
+                base_block_number,
+                block_number,
+                state_overrides,
+            )
+            .map_err(DynBlockchainError::new)
+    }
 }
 
 impl<
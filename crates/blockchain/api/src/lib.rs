@@ -165,6 +165,25 @@ pub trait StateAtBlock {
         // Block number -> state overrides
         state_overrides: &BTreeMap<u64, StateOverride>,
     ) -> Result<Box<dyn DynState>, Self::BlockchainError>;
+
+    /// Retrieves the state at a given block by applying the changes of the
+    /// blocks after `base_block_number` to `base_state`, which must be the
+    /// state at `base_block_number`.
+    ///
+    /// This avoids recomputing the state from scratch. Returns `None` if the
+    /// state cannot be derived from the base state, in which case it needs to
+    /// be retrieved using [`StateAtBlock::state_at_block_number`]. By default,
+    /// states are never derived.
+    fn state_at_block_number_from_base(
+        &self,
+        _base_state: Box<dyn DynState>,
+        _base_block_number: u64,
+        _block_number: u64,
+        // Block number -> state overrides
+        _state_overrides: &BTreeMap<u64, StateOverride>,
+    ) -> Result<Option<Box<dyn DynState>>, Self::BlockchainError> {
+        Ok(None)
+    }
 }
 
 /// Trait for retrieving the total difficulty by its block hash.
@@ -1,22 +1,22 @@
 //! Utility functions for blockchain implementations
 
 use core::ops::Bound;
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use edr_block_api::Block;
 use edr_block_storage::ReservableSparseBlockStorage;
 use edr_receipt::ReceiptTrait;
-use edr_state_api::{StateCommit, StateDiff, StateOverride};
+use edr_state_api::{DynState, StateDiff, StateError, StateOverride};
 
-/// Computes the state at a given block by applying state diffs from local
-/// blocks
+/// Computes the state at a given block by applying state diffs and storage
+/// root overrides from local blocks
 pub fn compute_state_at_block<
     BlockReceiptT: Clone + ReceiptTrait,
     BlockT: Block<SignedTransactionT> + Clone,
     HardforkT: Clone,
     SignedTransactionT,
 >(
-    state: &mut dyn StateCommit,
+    state: &mut dyn DynState,
     local_storage: &ReservableSparseBlockStorage<
         BlockReceiptT,
         BlockT,
@@ -26,7 +26,7 @@ pub fn compute_state_at_block<
     first_local_block_number: u64,
     last_local_block_number: u64,
     state_overrides: &BTreeMap<u64, StateOverride>,
-) {
+) -> Result<(), StateError> {
     // If we're dealing with a local block, apply their state diffs
     let state_diffs = local_storage
         .state_diffs_until_block(last_local_block_number)
@@ -49,7 +49,76 @@ pub fn compute_state_at_block<
             .or_insert_with(|| state_override.diff.clone());
     }
 
-    for (_block_number, state_diff) in overriden_state_diffs {
+    for (block_number, state_diff) in overriden_state_diffs {
         state.commit(state_diff.into());
+
+        if let Some(state_override) = state_overrides.get(&block_number) {
+            for (address, storage_root) in &state_override.storage_roots {
+                state.set_account_storage_root(*address, *storage_root)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the state at a given block by applying the state diffs and storage
+/// root overrides of the local blocks after `base_block_number` to the state
+/// at `base_block_number`.
+pub fn compute_state_since_block<
+    BlockReceiptT: Clone + ReceiptTrait,
+    BlockT: Block<SignedTransactionT> + Clone,
+    HardforkT: Clone,
+    SignedTransactionT,
+>(
+    state: &mut dyn DynState,
+    local_storage: &ReservableSparseBlockStorage<
+        BlockReceiptT,
+        BlockT,
+        HardforkT,
+        SignedTransactionT,
+    >,
+    base_block_number: u64,
+    block_number: u64,
+    state_overrides: &BTreeMap<u64, StateOverride>,
+) -> Result<(), StateError> {
+    let state_diffs = local_storage
+        .state_diffs_until_block(block_number)
+        .unwrap_or_default();
+
+    // State diffs are ordered by block number
+    let first_diff_index = state_diffs
+        .partition_point(|(diff_block_number, _)| *diff_block_number <= base_block_number);
+
+    let mut overriden_state_diffs: BTreeMap<u64, Cow<'_, StateDiff>> = state_diffs
+        [first_diff_index..]
+        .iter()
+        .map(|(block_number, state_diff)| (*block_number, Cow::Borrowed(state_diff)))
+        .collect();
+
+    for (block_number, state_override) in state_overrides.range((
+        Bound::Excluded(&base_block_number),
+        Bound::Included(&block_number),
+    )) {
+        overriden_state_diffs
+            .entry(*block_number)
+            .and_modify(|state_diff| {
+                state_diff
+                    .to_mut()
+                    .apply_diff(state_override.diff.as_inner().clone());
+            })
+            .or_insert_with(|| Cow::Borrowed(&state_override.diff));
     }
+
+    for (block_number, state_diff) in overriden_state_diffs {
+        state.commit(state_diff.into_owned().into());
+
+        if let Some(state_override) = state_overrides.get(&block_number) {
+            for (address, storage_root) in &state_override.storage_roots {
+                state.set_account_storage_root(*address, *storage_root)?;
+            }
+        }
+    }
+
+    Ok(())
 }
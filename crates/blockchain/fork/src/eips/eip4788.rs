@@ -9,7 +9,8 @@ pub const BEACON_ROOTS_BYTECODE: Bytes = bytes!(
     "0x3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500"
 );
 
-pub(crate) fn beacon_roots_contract() -> AccountInfo {
+/// Constructs the account of the beacon roots contract.
+pub fn beacon_roots_contract() -> AccountInfo {
     let code = Bytecode::new_raw(BEACON_ROOTS_BYTECODE);
 
     AccountInfo {
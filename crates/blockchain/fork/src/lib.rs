@@ -14,9 +14,10 @@ use edr_block_storage::{
     InsertBlockAndReceiptsError, InsertBlockError, ReservableSparseBlockStorage,
 };
 use edr_blockchain_api::{
-    utils::compute_state_at_block, BlockHashByNumber, BlockchainMetadata, GetBlockchainBlock,
-    GetBlockchainLogs, InsertBlock, ReceiptByTransactionHash, ReserveBlocks, RevertToBlock,
-    StateAtBlock, TotalDifficultyByBlockHash,
+    utils::{compute_state_at_block, compute_state_since_block},
+    BlockHashByNumber, BlockchainMetadata, GetBlockchainBlock, GetBlockchainLogs, InsertBlock,
+    ReceiptByTransactionHash, ReserveBlocks, RevertToBlock, StateAtBlock,
+    TotalDifficultyByBlockHash,
 };
 use edr_blockchain_remote::{FetchRemoteBlockError, FetchRemoteReceiptError, RemoteBlockchain};
 use edr_chain_config::{ChainConfig, HardforkActivations};
@@ -36,16 +37,16 @@ use edr_rpc_eth::{
 use edr_state_api::{
     account::{Account, AccountStatus},
     irregular::IrregularState,
-    DynState, StateDiff, StateOverride,
+    DynState, StateDiff, StateError, StateOverride,
 };
-use edr_state_fork::ForkedState;
+use edr_state_fork::{BlockHashHistory, ForkedState};
 use edr_utils::{random::RandomHashGenerator, CastArcFrom, CastArcInto};
 use parking_lot::Mutex;
 use tokio::runtime;
 
 use crate::eips::{
     eip2935::{
-        add_history_storage_contract_to_state_diff, history_storage_contract,
+        add_history_storage_contract_to_state_diff, history_storage_contract, HISTORY_SERVE_WINDOW,
         HISTORY_STORAGE_ADDRESS,
     },
     eip4788::{
@@ -112,6 +113,9 @@ pub enum ForkedBlockchainError<HardforkT, RpcBlockConversionErrorT, RpcReceiptCo
     /// Rpc client error
     #[error(transparent)]
     RpcClient(#[from] RpcClientError),
+    /// An error that occurs when computing the state at a block.
+    #[error(transparent)]
+    State(#[from] StateError),
     /// Missing hardfork activation history
     #[error(
         "No known hardfork for execution on historical block {block_number} (relative to fork block number {fork_block_number}) in chain with id {chain_id}. The node was not configured with a hardfork activation history."
@@ -187,6 +191,9 @@ pub struct ForkedBlockchain<
     >,
     state_root_generator: Arc<Mutex<RandomHashGenerator>>,
     fork_block_number: u64,
+    /// Set if the history storage contract was deployed at the fork block,
+    /// in which case its storage is backfilled with the remote block hashes.
+    block_hash_history: Option<BlockHashHistory>,
     base_fee_params: BaseFeeParams<HardforkT>,
     /// The chan id of the forked blockchain is either the local chain id
     /// override or the chain id of the remote blockchain.
@@ -312,6 +319,7 @@ impl<
             .expect("Block must exist since block number is less than the latest block number.")
             .timestamp();
 
+        let mut block_hash_history = None;
         if let Some(remote_hardfork) =
             hardfork_activations
                 .as_ref()
@@ -333,6 +341,12 @@ impl<
             if remote_evm_spec_id < EvmSpecId::PRAGUE && local_evm_spec_id >= EvmSpecId::PRAGUE {
                 let state_root = state_root_generator.lock().next_value();
 
+                block_hash_history = Some(BlockHashHistory {
+                    address: HISTORY_STORAGE_ADDRESS,
+                    serve_window: HISTORY_SERVE_WINDOW,
+                    fork_block_number,
+                });
+
                 irregular_state
                     .state_override_at_block_number(fork_block_number)
                     .and_modify(|state_override| {
@@ -365,6 +379,7 @@ impl<
 
                         StateOverride {
                             diff: StateDiff::from(accounts),
+                            storage_roots: HashMap::default(),
                             state_root,
                         }
                     });
@@ -393,6 +408,7 @@ impl<
 
                         StateOverride {
                             diff: StateDiff::from(accounts),
+                            storage_roots: HashMap::default(),
                             state_root,
                         }
                     });
@@ -407,6 +423,7 @@ impl<
             chain_id: chain_id_override.unwrap_or(remote_chain_id),
             remote_chain_id,
             fork_block_number,
+            block_hash_history,
             network_id,
             hardfork,
             hardfork_activations,
@@ -1125,6 +1142,12 @@ impl<
             state_root,
         );
 
+        if block_number >= self.fork_block_number
+            && let Some(block_hash_history) = self.block_hash_history
+        {
+            state.set_block_hash_history(block_hash_history);
+        }
+
         let (first_block_number, last_block_number) =
             match block_number.cmp(&self.fork_block_number) {
                 // Only override the state at the forked block
@@ -1141,13 +1164,58 @@ impl<
             first_block_number,
             last_block_number,
             state_overrides,
-        );
+        )?;
 
         // Override the state root in case the local state was modified
         state.set_state_root(state_root);
 
         Ok(Box::new(state))
     }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn state_at_block_number_from_base(
+        &self,
+        mut base_state: Box<dyn DynState>,
+        base_block_number: u64,
+        block_number: u64,
+        state_overrides: &BTreeMap<u64, StateOverride>,
+    ) -> Result<Option<Box<dyn DynState>>, Self::BlockchainError> {
+        if block_number > self.last_block_number() {
+            return Err(ForkedBlockchainError::UnknownBlockNumber);
+        }
+
+        // States before the forked block only contain their own block's
+        // overrides, so only states of local blocks can be derived.
+        if base_block_number < self.fork_block_number || base_block_number > block_number {
+            return Ok(None);
+        }
+
+        let state_root = if let Some(state_override) = state_overrides.get(&block_number) {
+            state_override.state_root
+        } else {
+            self.block_by_number(block_number)?
+                .expect(
+                    "Block must exist since block number is less than equal the last block number.",
+                )
+                .block_header()
+                .state_root
+        };
+
+        compute_state_since_block(
+            base_state.as_mut(),
+            &self.local_storage,
+            base_block_number,
+            block_number,
+            state_overrides,
+        )?;
+
+        // Override the state root in case the local state was modified
+        if !base_state.override_state_root(state_root)? {
+            return Ok(None);
+        }
+
+        Ok(Some(base_state))
+    }
 }
 
 impl<
@@ -2,14 +2,14 @@
 
 use std::sync::Arc;
 
-use edr_blockchain_api::StateAtBlock as _;
+use edr_blockchain_api::{BlockHashByNumber as _, StateAtBlock as _};
 use edr_blockchain_fork::{
-    eips::eip2935::{HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_UNSUPPORTED_BYTECODE},
+    eips::eip2935::{HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_BYTECODE},
     ForkedBlockchainCreationError,
 };
-use edr_chain_l1::L1ChainSpec;
+use edr_chain_l1::{block::history_storage_index, L1ChainSpec};
 use edr_chain_spec_provider::{default_block_config, ProviderChainSpec as _};
-use edr_primitives::{bytes, Bytecode, Bytes};
+use edr_primitives::{Bytecode, U256};
 use edr_provider::spec::ForkedBlockchainForChainSpec;
 use edr_rpc_eth::client::EthRpcClientForChainSpec;
 use edr_state_api::irregular::IrregularState;
@@ -17,10 +17,6 @@ use edr_test_utils::env::get_alchemy_url;
 use edr_utils::random::RandomHashGenerator;
 use parking_lot::Mutex;
 
-const HISTORY_STORAGE_BYTECODE: Bytes = bytes!(
-        "0x3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500"
-    );
-
 async fn forked_blockchain(
     irregular_state: &mut IrregularState,
     block_number: u64,
@@ -111,7 +107,44 @@ async fn forked_blockchain_pre_prague_activation_with_prague() -> anyhow::Result
 
     assert_eq!(
         history_storage_code,
-        Bytecode::new_raw(HISTORY_STORAGE_UNSUPPORTED_BYTECODE)
+        Bytecode::new_raw(HISTORY_STORAGE_BYTECODE)
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn forked_blockchain_pre_prague_activation_backfills_history() -> anyhow::Result<()> {
+    const PRE_PRAGUE_BLOCK_NUMBER: u64 = 19_426_589;
+
+    let mut irregular_state = IrregularState::default();
+    let pre_prague = forked_blockchain(
+        &mut irregular_state,
+        PRE_PRAGUE_BLOCK_NUMBER,
+        edr_chain_l1::Hardfork::PRAGUE,
+    )
+    .await?;
+
+    let state = pre_prague
+        .state_at_block_number(PRE_PRAGUE_BLOCK_NUMBER, irregular_state.state_overrides())?;
+
+    for block_number in [
+        PRE_PRAGUE_BLOCK_NUMBER - 1,
+        PRE_PRAGUE_BLOCK_NUMBER - HISTORY_SERVE_WINDOW,
+    ] {
+        let block_hash = pre_prague.block_hash_by_number(block_number)?;
+
+        assert_eq!(
+            state.storage(HISTORY_STORAGE_ADDRESS, history_storage_index(block_number))?,
+            U256::from_be_bytes(block_hash.0)
+        );
+    }
+
+    // Slots beyond the serve window are empty
+    assert_eq!(
+        state.storage(HISTORY_STORAGE_ADDRESS, U256::from(HISTORY_SERVE_WINDOW))?,
+        U256::ZERO
     );
 
     Ok(())
@@ -151,9 +184,6 @@ async fn forked_blockchain_post_eip2935_deployment_with_cancun() -> anyhow::Resu
     Ok(())
 }
 
-// TODO: This test is meant to start failing once the Prague hardfork is
-// activated on mainnet. Once that happens, the bytecode should be updated to
-// `HISTORY_STORAGE_BYTECODE`.
 #[tokio::test(flavor = "multi_thread")]
 #[serial_test::serial]
 async fn forked_blockchain_post_prague() -> anyhow::Result<()> {
@@ -180,9 +210,7 @@ async fn forked_blockchain_post_prague() -> anyhow::Result<()> {
 
     assert_eq!(
         history_storage_code,
-        // TODO: Once prague has been released, this should be updated to
-        // `HISTORY_STORAGE_BYTECODE`
-        Bytecode::new_raw(HISTORY_STORAGE_UNSUPPORTED_BYTECODE)
+        Bytecode::new_raw(HISTORY_STORAGE_BYTECODE)
     );
 
     Ok(())
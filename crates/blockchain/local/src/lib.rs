@@ -7,15 +7,16 @@ use edr_block_api::{
 use edr_block_header::BlockConfig;
 use edr_block_storage::ReservableSparseBlockStorage;
 use edr_blockchain_api::{
-    utils::compute_state_at_block, BlockHashByNumber, BlockchainMetadata, GetBlockchainBlock,
-    GetBlockchainLogs, InsertBlock, ReceiptByTransactionHash, ReserveBlocks, RevertToBlock,
-    StateAtBlock, TotalDifficultyByBlockHash,
+    utils::{compute_state_at_block, compute_state_since_block},
+    BlockHashByNumber, BlockchainMetadata, GetBlockchainBlock, GetBlockchainLogs, InsertBlock,
+    ReceiptByTransactionHash, ReserveBlocks, RevertToBlock, StateAtBlock,
+    TotalDifficultyByBlockHash,
 };
 use edr_chain_spec::{EvmSpecId, ExecutableTransaction};
 use edr_eip1559::BaseFeeParams;
 use edr_primitives::{Address, HashSet, B256, U256};
 use edr_receipt::{log::FilterLog, ExecutionReceipt, ReceiptTrait};
-use edr_state_api::{DynState, StateDiff, StateOverride};
+use edr_state_api::{DynState, StateDiff, StateError, StateOverride};
 use edr_state_persistent_trie::PersistentStateTrie;
 use edr_utils::CastArcInto;
 
@@ -40,6 +41,8 @@ pub struct LocalBlockchain<BlockReceiptT: ReceiptTrait, HardforkT, LocalBlockT,
     base_fee_params: BaseFeeParams<HardforkT>,
     chain_id: u64,
     hardfork: HardforkT,
+    /// (Block number -> hardfork) activations for blocks after genesis
+    hardfork_activations: BTreeMap<u64, HardforkT>,
     min_ethash_difficulty: u64,
     storage: ReservableSparseBlockStorage<
         Arc<BlockReceiptT>,
@@ -96,9 +99,23 @@ impl<
             base_fee_params: base_fee_params.clone(),
             chain_id,
             hardfork,
+            hardfork_activations: BTreeMap::new(),
             min_ethash_difficulty,
         })
     }
+
+    /// Activates the provided hardforks at the corresponding block numbers.
+    /// Blocks before the first activation use the blockchain's hardfork.
+    ///
+    /// An activation at block zero should match the hardfork of the genesis
+    /// block, as the genesis block has already been created.
+    pub fn with_hardfork_activations(
+        mut self,
+        hardfork_activations: BTreeMap<u64, HardforkT>,
+    ) -> Self {
+        self.hardfork_activations = hardfork_activations;
+        self
+    }
 }
 
 impl<
@@ -132,6 +149,9 @@ pub enum LocalBlockchainError {
     /// An error that occurs when trying to insert an invalid local block.
     #[error(transparent)]
     InvalidNextBlock(#[from] BlockValidityError),
+    /// An error that occurs when computing the state at a block.
+    #[error(transparent)]
+    State(#[from] StateError),
     /// Block number does not exist in blockchain
     #[error("Unknown block number")]
     UnknownBlockNumber,
@@ -156,6 +176,18 @@ impl<
     }
 }
 
+impl<BlockReceiptT: ReceiptTrait, HardforkT: Clone, LocalBlockT, SignedTransactionT>
+    LocalBlockchain<BlockReceiptT, HardforkT, LocalBlockT, SignedTransactionT>
+{
+    /// Returns the hardfork that is active at the provided block number.
+    fn hardfork_at_block_number(&self, block_number: u64) -> HardforkT {
+        self.hardfork_activations
+            .range(..=block_number)
+            .next_back()
+            .map_or_else(|| self.hardfork.clone(), |(_, hardfork)| hardfork.clone())
+    }
+}
+
 impl<BlockReceiptT: ReceiptTrait, HardforkT: Clone, LocalBlockT, SignedTransactionT>
     BlockchainMetadata<HardforkT>
     for LocalBlockchain<BlockReceiptT, HardforkT, LocalBlockT, SignedTransactionT>
@@ -180,11 +212,11 @@ impl<BlockReceiptT: ReceiptTrait, HardforkT: Clone, LocalBlockT, SignedTransacti
             return Err(LocalBlockchainError::UnknownBlockNumber);
         }
 
-        Ok(self.hardfork.clone())
+        Ok(self.hardfork_at_block_number(block_number))
     }
 
     fn hardfork(&self) -> HardforkT {
-        self.hardfork.clone()
+        self.hardfork_at_block_number(self.last_block_number() + 1)
     }
 
     fn last_block_number(&self) -> u64 {
@@ -295,7 +327,8 @@ impl<
     ) -> Result<BlockAndTotalDifficulty<Arc<BlockT>, SignedTransactionT>, Self::Error> {
         let last_block = self.last_local_block()?;
 
-        validate_next_block(self.hardfork.clone(), &last_block, &block)?;
+        let hardfork = self.hardfork_at_block_number(block.block_header().number);
+        validate_next_block(hardfork, &last_block, &block)?;
 
         let previous_total_difficulty = self
             .total_difficulty_by_hash(last_block.block_hash())
@@ -358,19 +391,38 @@ impl<
             .expect("Must exist as its block is stored");
 
         let last_header = last_block.block_header();
-
-        self.storage.reserve_blocks(
-            additional,
-            interval,
-            last_header.base_fee_per_gas,
-            last_header.state_root,
-            previous_total_difficulty,
-            BlockConfig {
-                base_fee_params: &self.base_fee_params,
-                hardfork: self.hardfork.clone(),
-                min_ethash_difficulty: self.min_ethash_difficulty,
-            },
-        );
+        let last_reserved_number = last_header.number + additional.get();
+
+        // Reserved blocks are empty, so all blocks of a reservation can share a
+        // hardfork. Reservations are split at hardfork activations.
+        let mut first_number = last_header.number + 1;
+        while first_number <= last_reserved_number {
+            let last_number = self
+                .hardfork_activations
+                .range(first_number + 1..=last_reserved_number)
+                .next()
+                .map_or(last_reserved_number, |(activation_number, _)| {
+                    activation_number - 1
+                });
+
+            let additional = NonZeroU64::new(last_number - first_number + 1)
+                .expect("The last number is at least the first number");
+
+            self.storage.reserve_blocks(
+                additional,
+                interval,
+                last_header.base_fee_per_gas,
+                last_header.state_root,
+                previous_total_difficulty,
+                BlockConfig {
+                    base_fee_params: &self.base_fee_params,
+                    hardfork: self.hardfork_at_block_number(first_number),
+                    min_ethash_difficulty: self.min_ethash_difficulty,
+                },
+            );
+
+            first_number = last_number + 1;
+        }
 
         Ok(())
     }
@@ -415,10 +467,37 @@ impl<
         }
 
         let mut state = PersistentStateTrie::default();
-        compute_state_at_block(&mut state, &self.storage, 0, block_number, state_overrides);
+        compute_state_at_block(&mut state, &self.storage, 0, block_number, state_overrides)?;
 
         Ok(Box::new(state))
     }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn state_at_block_number_from_base(
+        &self,
+        mut base_state: Box<dyn DynState>,
+        base_block_number: u64,
+        block_number: u64,
+        state_overrides: &BTreeMap<u64, StateOverride>,
+    ) -> Result<Option<Box<dyn DynState>>, Self::BlockchainError> {
+        if block_number > self.last_block_number() {
+            return Err(LocalBlockchainError::UnknownBlockNumber);
+        }
+
+        if base_block_number > block_number {
+            return Ok(None);
+        }
+
+        compute_state_since_block(
+            base_state.as_mut(),
+            &self.storage,
+            base_block_number,
+            block_number,
+            state_overrides,
+        )?;
+
+        Ok(Some(base_state))
+    }
 }
 
 impl<BlockReceiptT: ReceiptTrait, HardforkT: Clone, LocalBlockT, SignedTransactionT>
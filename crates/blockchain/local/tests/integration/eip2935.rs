@@ -4,8 +4,7 @@ use edr_block_api::{GenesisBlockFactory as _, GenesisBlockOptions};
 use edr_block_header::BlockConfig;
 use edr_blockchain_api::StateAtBlock as _;
 use edr_blockchain_fork::eips::eip2935::{
-    add_history_storage_contract_to_state_diff, HISTORY_STORAGE_ADDRESS,
-    HISTORY_STORAGE_UNSUPPORTED_BYTECODE,
+    add_history_storage_contract_to_state_diff, HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_BYTECODE,
 };
 use edr_blockchain_local::LocalBlockchain;
 use edr_chain_l1::L1ChainSpec;
@@ -72,7 +71,7 @@ fn test_local_blockchain_with_history() -> anyhow::Result<()> {
 
     assert_eq!(
         history_storage_code,
-        Bytecode::new_raw(HISTORY_STORAGE_UNSUPPORTED_BYTECODE)
+        Bytecode::new_raw(HISTORY_STORAGE_BYTECODE)
     );
 
     Ok(())
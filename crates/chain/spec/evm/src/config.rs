@@ -12,6 +12,16 @@ pub struct EvmConfig {
     ///
     /// Chain ID was introduced in EIP-155.
     pub chain_id: u64,
+    /// Disables the check that the sender's balance covers the transaction's
+    /// upfront cost.
+    ///
+    /// By default, it is set to `false`.
+    pub disable_balance_check: bool,
+    /// Disables the check that the transaction's gas price - or max fee per
+    /// gas - is at least the block's base fee.
+    ///
+    /// By default, it is set to `false`.
+    pub disable_base_fee: bool,
     /// EIP-3607 rejects transactions from senders with deployed code
     ///
     /// In development, it can be desirable to simulate calls from contracts,
@@ -25,6 +35,12 @@ pub struct EvmConfig {
     /// EIP-7907) at runtime. If Some, this specific limit will be used
     /// regardless of hardfork.
     pub limit_contract_code_size: Option<usize>,
+    /// Contract initcode size limit override.
+    ///
+    /// If None, the limit will be twice the contract code size limit, as
+    /// defined by EIP-3860. If Some, this specific limit will be used
+    /// regardless of hardfork.
+    pub limit_contract_initcode_size: Option<usize>,
     /// Configures the gas limit cap for the transaction.
     ///
     /// If `None`, default value defined by spec will be used.
@@ -36,12 +52,15 @@ pub struct EvmConfig {
 impl EvmConfig {
     /// Creates a new EVM configuration with the specified chain ID.
     ///
-    /// Does not disable EIP-3607 and does not set a contract code size limit.
+    /// Does not disable any checks and does not set a contract code size limit.
     pub fn with_chain_id(chain_id: u64) -> Self {
         Self {
             chain_id,
+            disable_balance_check: false,
+            disable_base_fee: false,
             disable_eip3607: false,
             limit_contract_code_size: None,
+            limit_contract_initcode_size: None,
             transaction_gas_cap: None,
         }
     }
@@ -51,8 +70,11 @@ impl EvmConfig {
     pub fn to_cfg_env<HardforkT: Into<EvmSpecId>>(&self, hardfork: HardforkT) -> CfgEnv<HardforkT> {
         let mut cfg_env = CfgEnv::new_with_spec(hardfork);
         cfg_env.chain_id = self.chain_id;
+        cfg_env.disable_balance_check = self.disable_balance_check;
+        cfg_env.disable_base_fee = self.disable_base_fee;
         cfg_env.disable_eip3607 = self.disable_eip3607;
         cfg_env.limit_contract_code_size = self.limit_contract_code_size;
+        cfg_env.limit_contract_initcode_size = self.limit_contract_initcode_size;
         cfg_env.tx_gas_limit_cap = self.transaction_gas_cap;
 
         cfg_env
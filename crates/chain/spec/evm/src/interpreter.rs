@@ -3,7 +3,7 @@
 pub use revm_interpreter::{
     interpreter::EthInterpreter,
     interpreter_types::{InputsTr, Jumps, LoopControl},
-    return_revert, CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome, FrameInput,
-    Gas, Host, InputsImpl, InstructionResult, Interpreter, InterpreterResult, InterpreterTypes,
-    SuccessOrHalt,
+    return_revert, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
+    CreateScheme, FrameInput, Gas, Host, InputsImpl, InstructionResult, Interpreter,
+    InterpreterResult, InterpreterTypes, SuccessOrHalt,
 };
@@ -16,7 +16,7 @@ use edr_transaction::{TransactionAndBlock, TransactionType};
 /// provider.
 pub trait ProviderChainSpec: BlockChainSpec<
         Block: 'static,
-        Hardfork: 'static + Debug + PartialOrd,
+        Hardfork: 'static + Debug + Into<&'static str> + PartialOrd,
         LocalBlock: 'static
                         + FetchBlockReceipts<Arc<<Self as ReceiptChainSpec>::Receipt>, Error: Debug>,
         Receipt: 'static + TryFrom<<Self as RpcChainSpec>::RpcReceipt, Error: Send + Sync>,
@@ -39,6 +39,9 @@ pub trait RpcChainSpec: ExecutionReceiptChainSpec + RpcBlockChainSpec {
 /// Trait that provides access to common properties of an Ethereum-based RPC
 /// block.
 pub trait RpcEthBlock {
+    /// Returns the block's hash. For pending blocks, returns `None`.
+    fn hash(&self) -> Option<&B256>;
+
     /// Returns the root of the block's state trie.
     fn state_root(&self) -> &B256;
 
@@ -1,14 +1,18 @@
 use core::{fmt::Debug, marker::PhantomData};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use alloy_eips::{
+    eip2935::{HISTORY_SERVE_WINDOW, HISTORY_STORAGE_ADDRESS},
+    eip4788::BEACON_ROOTS_ADDRESS,
+};
 use edr_block_api::Block;
 use edr_block_builder_api::{
     BlockBuilder, BlockBuilderCreationError, BlockFinalizeError, BlockInputs,
     BlockTransactionError, BlockTransactionErrorForChainSpec, Blockchain, BuiltBlockAndState,
-    CfgEnv, DatabaseComponents, ExecutionResult, PrecompileFn, WrapDatabaseRef,
+    CfgEnv, CustomPrecompile, DatabaseComponents, ExecutionResult, WrapDatabaseRef,
 };
 use edr_block_header::{
-    blob_params_for_hardfork, BlobGas, BlockConfig, HeaderAndEvmSpec, HeaderOverrides,
+    blob_params_for_hardfork, BlobGas, BlockConfig, BlockHeader, HeaderAndEvmSpec, HeaderOverrides,
     PartialHeader, Withdrawal,
 };
 use edr_block_local::EthLocalBlock;
@@ -23,13 +27,15 @@ use edr_chain_spec_evm::{
 };
 use edr_chain_spec_receipt::ReceiptConstructor;
 use edr_evm::{dry_run, dry_run_with_inspector};
-use edr_primitives::{Address, Bloom, HashMap, KECCAK_NULL_RLP, U256};
+use edr_primitives::{Address, Bloom, HashMap, B256, KECCAK_EMPTY, KECCAK_NULL_RLP, U256};
 use edr_receipt::{
     log::{ExecutionLog, FilterLog},
     ExecutionReceipt, ExecutionReceiptChainSpec, MapReceiptLogs, ReceiptTrait, TransactionReceipt,
 };
 use edr_receipt_builder_api::ExecutionReceiptBuilder;
-use edr_state_api::{AccountModifierFn, DynState, StateDiff, StateError};
+use edr_state_api::{
+    account::Account, AccountModifierFn, DynState, EvmStorageSlot, StateDiff, StateError,
+};
 use edr_trie::ordered_trie_root;
 
 const MAX_BLOCK_SIZE: usize = 10_485_760; // 10 MiB
@@ -38,6 +44,25 @@ const SAFETY_MARGIN: usize = 2_097_152; // 2 MiB
 /// EIP-7934 max RLP block size
 pub const MAX_RLP_BLOCK_SIZE: usize = MAX_BLOCK_SIZE - SAFETY_MARGIN;
 
+/// EIP-4788 length of the beacon roots contract's ring buffer
+pub const BEACON_ROOTS_HISTORY_BUFFER_LENGTH: u64 = 8191;
+
+/// Returns the storage indices at which the EIP-4788 beacon roots contract
+/// stores the provided timestamp and its corresponding beacon root,
+/// respectively.
+pub fn beacon_root_storage_indices(timestamp: u64) -> (U256, U256) {
+    let timestamp_index = timestamp % BEACON_ROOTS_HISTORY_BUFFER_LENGTH;
+    let root_index = timestamp_index + BEACON_ROOTS_HISTORY_BUFFER_LENGTH;
+
+    (U256::from(timestamp_index), U256::from(root_index))
+}
+
+/// Returns the storage index at which the EIP-2935 history storage contract
+/// stores the hash of the block with the provided number.
+pub fn history_storage_index(block_number: u64) -> U256 {
+    U256::from(block_number % HISTORY_SERVE_WINDOW as u64)
+}
+
 /// A builder for constructing Ethereum blocks.
 pub struct EthBlockBuilder<
     'builder,
@@ -65,14 +90,16 @@ pub struct EthBlockBuilder<
     cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     context: EvmChainSpecT::Context,
     header: PartialHeader,
+    ommers: Vec<BlockHeader>,
     parent_gas_limit: Option<u64>,
     receipts: Vec<TransactionReceipt<ExecutionReceiptChainSpecT::ExecutionReceipt<ExecutionLog>>>,
     state: Box<dyn DynState>,
     state_diff: StateDiff,
     transactions: Vec<EvmChainSpecT::SignedTransaction>,
     transaction_results: Vec<ExecutionResult<EvmChainSpecT::HaltReason>>,
+    transaction_state_changes: Vec<HashMap<Address, Account>>,
     withdrawals: Option<Vec<Withdrawal>>,
-    custom_precompiles: &'builder HashMap<Address, PrecompileFn>,
+    custom_precompiles: &'builder HashMap<Address, CustomPrecompile>,
     _phantom: PhantomData<fn() -> (EvmChainSpecT, ExecutionReceiptBuilderT)>,
 }
 
@@ -255,11 +282,11 @@ impl<
             LocalBlockT,
             ChainSpecT::SignedTransaction,
         >,
-        state: Box<dyn DynState>,
+        mut state: Box<dyn DynState>,
         evm_config: &EvmConfig,
         inputs: BlockInputs,
         mut overrides: HeaderOverrides<ChainSpecT::Hardfork>,
-        custom_precompiles: &'builder HashMap<Address, PrecompileFn>,
+        custom_precompiles: &'builder HashMap<Address, CustomPrecompile>,
     ) -> Result<
         Self,
         BlockBuilderCreationError<
@@ -302,17 +329,46 @@ impl<
             inputs.withdrawals.as_ref(),
         );
 
+        let mut state_diff = StateDiff::default();
+        if evm_spec_id >= EvmSpecId::CANCUN
+            && let Some(parent_beacon_block_root) = header.parent_beacon_block_root
+        {
+            store_parent_beacon_block_root(
+                state.as_mut(),
+                &mut state_diff,
+                header.timestamp,
+                parent_beacon_block_root,
+            )
+            .map_err(|error| {
+                BlockBuilderCreationError::Database(DatabaseComponentError::State(error))
+            })?;
+        }
+
+        if evm_spec_id >= EvmSpecId::PRAGUE {
+            store_parent_block_hash(
+                state.as_mut(),
+                &mut state_diff,
+                header.number,
+                header.parent_hash,
+            )
+            .map_err(|error| {
+                BlockBuilderCreationError::Database(DatabaseComponentError::State(error))
+            })?;
+        }
+
         Ok(Self {
             blockchain,
             cfg,
             context,
             header,
+            ommers: inputs.ommers,
             parent_gas_limit,
             receipts: Vec::new(),
             state,
-            state_diff: StateDiff::default(),
+            state_diff,
             transactions: Vec::new(),
             transaction_results: Vec::new(),
+            transaction_state_changes: Vec::new(),
             withdrawals: inputs.withdrawals,
             custom_precompiles,
             _phantom: PhantomData,
@@ -356,7 +412,12 @@ impl<
             self.custom_precompiles,
         )?;
 
-        self.add_transaction_result(receipt_builder, transaction, transaction_result);
+        self.add_transaction_result(receipt_builder, transaction, transaction_result)
+            .map_err(|error| {
+                BlockTransactionError::Transaction(TransactionError::Database(
+                    DatabaseComponentError::State(error),
+                ))
+            })?;
 
         Ok(())
     }
@@ -418,7 +479,12 @@ impl<
         )
         .map_err(BlockTransactionError::from)?;
 
-        self.add_transaction_result(receipt_builder, transaction, transaction_result);
+        self.add_transaction_result(receipt_builder, transaction, transaction_result)
+            .map_err(|error| {
+                BlockTransactionError::Transaction(TransactionError::Database(
+                    DatabaseComponentError::State(error),
+                ))
+            })?;
 
         Ok(())
     }
@@ -427,15 +493,28 @@ impl<
         receipt_builder: ExecutionReceiptBuilderT,
         transaction: ChainSpecT::SignedTransaction,
         transaction_result: ExecutionResultAndState<ChainSpecT::HaltReason>,
-    ) {
+    ) -> Result<(), StateError> {
         let ExecutionResultAndState {
             result: transaction_result,
             state: state_diff,
         } = transaction_result;
 
-        self.state_diff.apply_diff(state_diff.clone());
+        for address in state_diff.keys() {
+            let original_info = self.state.basic(*address)?;
+            self.state_diff
+                .record_original_account_info(*address, original_info);
+        }
+
+        let conflicts = self
+            .state_diff
+            .apply_transaction_diff(self.transactions.len(), state_diff.clone());
+        debug_assert!(
+            conflicts.is_empty(),
+            "Sequentially executed transactions observe all earlier writes"
+        );
 
-        self.state.commit(state_diff);
+        self.state.commit(state_diff.clone());
+        self.transaction_state_changes.push(state_diff);
 
         self.header.gas_used += transaction_result.gas_used();
 
@@ -450,18 +529,29 @@ impl<
             &transaction_result,
             self.cfg.spec,
         );
+        let blob_gas_price = self
+            .header
+            .blob_gas
+            .as_ref()
+            .map(|BlobGas { excess_gas, .. }| {
+                blob_params_for_hardfork(self.cfg.spec.into()).calc_blob_fee(*excess_gas)
+            });
+
         let receipt = TransactionReceipt::new(
             receipt,
             &transaction,
             &transaction_result,
             self.transactions.len() as u64,
             self.header.base_fee.unwrap_or(0),
+            blob_gas_price,
             self.cfg.spec,
         );
         self.receipts.push(receipt);
 
         self.transactions.push(transaction);
         self.transaction_results.push(transaction_result);
+
+        Ok(())
     }
 }
 
@@ -522,6 +612,13 @@ impl<
     > {
         for (address, reward) in rewards {
             if reward > 0 {
+                let original_info = self
+                    .state
+                    .basic(address)
+                    .map_err(BlockFinalizeError::State)?;
+                self.state_diff
+                    .record_original_account_info(address, original_info);
+
                 let account_info = self
                     .state
                     .modify_account(
@@ -536,6 +633,32 @@ impl<
             }
         }
 
+        // EIP-4895: withdrawals are applied after all transactions
+        for withdrawal in self.withdrawals.iter().flatten() {
+            if withdrawal.amount > 0 {
+                let amount = withdrawal.amount_wei();
+                let original_info = self
+                    .state
+                    .basic(withdrawal.address)
+                    .map_err(BlockFinalizeError::State)?;
+                self.state_diff
+                    .record_original_account_info(withdrawal.address, original_info);
+
+                let account_info = self
+                    .state
+                    .modify_account(
+                        withdrawal.address,
+                        AccountModifierFn::new(Box::new(move |balance, _nonce, _code| {
+                            *balance += amount;
+                        })),
+                    )
+                    .map_err(BlockFinalizeError::State)?;
+
+                self.state_diff
+                    .apply_account_change(withdrawal.address, account_info);
+            }
+        }
+
         if let Some(gas_limit) = self.parent_gas_limit {
             self.header.gas_limit = gas_limit;
         }
@@ -566,14 +689,13 @@ impl<
                 .as_secs();
         }
 
-        // TODO: handle ommers
         let block = EthLocalBlock::new::<ExecutionReceiptChainSpecT>(
             &self.context,
             self.cfg.spec,
             self.header,
             self.transactions,
             self.receipts,
-            Vec::new(),
+            self.ommers,
             self.withdrawals,
         );
 
@@ -590,6 +712,7 @@ impl<
             state: self.state,
             state_diff: self.state_diff,
             transaction_results: self.transaction_results,
+            transaction_state_changes: self.transaction_state_changes,
         })
     }
 }
@@ -662,7 +785,7 @@ impl<
         evm_config: &EvmConfig,
         inputs: BlockInputs,
         overrides: HeaderOverrides<ChainSpecT::Hardfork>,
-        custom_precompiles: &'builder HashMap<Address, PrecompileFn>,
+        custom_precompiles: &'builder HashMap<Address, CustomPrecompile>,
     ) -> Result<
         Self,
         BlockBuilderCreationError<
@@ -743,3 +866,81 @@ impl<
         self.finalize(rewards)
     }
 }
+
+/// Performs the EIP-4788 system call, storing the parent beacon block root in
+/// the beacon roots contract.
+pub fn store_parent_beacon_block_root(
+    state: &mut dyn DynState,
+    state_diff: &mut StateDiff,
+    timestamp: u64,
+    parent_beacon_block_root: B256,
+) -> Result<(), StateError> {
+    let (timestamp_index, root_index) = beacon_root_storage_indices(timestamp);
+
+    set_system_contract_storage(
+        state,
+        state_diff,
+        BEACON_ROOTS_ADDRESS,
+        &[
+            (timestamp_index, U256::from(timestamp)),
+            (root_index, U256::from_be_bytes(parent_beacon_block_root.0)),
+        ],
+    )
+}
+
+/// Performs the EIP-2935 system call, storing the parent block hash in the
+/// history storage contract.
+pub fn store_parent_block_hash(
+    state: &mut dyn DynState,
+    state_diff: &mut StateDiff,
+    block_number: u64,
+    parent_hash: B256,
+) -> Result<(), StateError> {
+    let Some(parent_block_number) = block_number.checked_sub(1) else {
+        return Ok(());
+    };
+
+    set_system_contract_storage(
+        state,
+        state_diff,
+        HISTORY_STORAGE_ADDRESS,
+        &[(
+            history_storage_index(parent_block_number),
+            U256::from_be_bytes(parent_hash.0),
+        )],
+    )
+}
+
+/// Applies the storage changes of a system call to the contract at the
+/// provided address. The changes are skipped if the contract hasn't been
+/// deployed.
+fn set_system_contract_storage(
+    state: &mut dyn DynState,
+    state_diff: &mut StateDiff,
+    address: Address,
+    changes: &[(U256, U256)],
+) -> Result<(), StateError> {
+    let Some(mut account_info) = state.basic(address)? else {
+        return Ok(());
+    };
+
+    if account_info.code_hash == KECCAK_EMPTY {
+        return Ok(());
+    }
+
+    // The code is needed for applying the state diff to an empty state
+    account_info.code = Some(state.code_by_hash(account_info.code_hash)?);
+
+    for (index, value) in changes.iter().copied() {
+        let old_value = state.set_account_storage_slot(address, index, value)?;
+
+        state_diff.apply_storage_change(
+            address,
+            index,
+            EvmStorageSlot::new_changed(old_value, value, 0),
+            Some(account_info.clone()),
+        );
+    }
+
+    Ok(())
+}
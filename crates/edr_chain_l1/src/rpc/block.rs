@@ -111,6 +111,10 @@ impl<TransactionT> GetBlockNumber for L1RpcBlock<TransactionT> {
 }
 
 impl<TransactionT> RpcEthBlock for L1RpcBlock<TransactionT> {
+    fn hash(&self) -> Option<&B256> {
+        self.hash.as_ref()
+    }
+
     fn state_root(&self) -> &B256 {
         &self.state_root
     }
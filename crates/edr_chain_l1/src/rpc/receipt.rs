@@ -71,6 +71,22 @@ pub struct L1RpcTransactionReceipt {
         with = "alloy_serde::quantity::opt"
     )]
     pub effective_gas_price: Option<u128>,
+    /// Blob gas used by the transaction. Only present for EIP-4844
+    /// transactions.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub blob_gas_used: Option<u64>,
+    /// The price per unit of blob gas paid by the transaction. Only present
+    /// for EIP-4844 transactions.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub blob_gas_price: Option<u128>,
     /// The authorization list is a list of tuples that store the address to
     /// code which the signer desires to execute in the context of their
     /// EOA.
@@ -115,6 +131,8 @@ impl RpcTypeFrom<L1BlockReceipt<TypedEnvelope<edr_receipt::Execution<FilterLog>>
                 edr_receipt::Execution::Eip658(receipt) => Some(receipt.status),
             },
             effective_gas_price: value.inner.effective_gas_price,
+            blob_gas_used: value.inner.blob_gas_used,
+            blob_gas_price: value.inner.blob_gas_price,
             authorization_list: None,
         }
     }
@@ -151,6 +169,8 @@ impl RpcTypeFrom<L1BlockReceipt<TypedEnvelope<edr_receipt::execution::Eip658<Fil
             state_root: None,
             status: Some(value.as_execution_receipt().data().status),
             effective_gas_price: value.inner.effective_gas_price,
+            blob_gas_used: value.inner.blob_gas_used,
+            blob_gas_price: value.inner.blob_gas_price,
             authorization_list: None,
         }
     }
@@ -218,6 +238,8 @@ impl TryFrom<L1RpcTransactionReceipt>
                 contract_address: value.contract_address,
                 gas_used: value.gas_used,
                 effective_gas_price: value.effective_gas_price,
+                blob_gas_used: value.blob_gas_used,
+                blob_gas_price: value.blob_gas_price,
             },
         })
     }
@@ -276,6 +298,8 @@ impl TryFrom<L1RpcTransactionReceipt>
                 contract_address: value.contract_address,
                 gas_used: value.gas_used,
                 effective_gas_price: value.effective_gas_price,
+                blob_gas_used: value.blob_gas_used,
+                blob_gas_price: value.blob_gas_price,
             },
         })
     }
@@ -8,7 +8,7 @@ use edr_primitives::{Address, Bytes, B256, U256};
 use edr_signer::{FakeableSignature, Signature};
 pub use edr_transaction::signed::{Eip155, Eip1559, Eip2930, Eip4844, Eip7702, Legacy};
 use edr_transaction::{
-    impl_revm_transaction_trait, signed::PreOrPostEip155, IsEip155, IsEip4844, IsLegacy,
+    impl_revm_transaction_trait, signed::PreOrPostEip155, IsDeposit, IsEip155, IsEip4844, IsLegacy,
     IsSupported, SignedTransaction, TransactionMut, TransactionType, TxKind,
     INVALID_TX_TYPE_ERROR_MESSAGE,
 };
@@ -180,6 +180,12 @@ impl IsEip155 for L1SignedTransaction {
     }
 }
 
+impl IsDeposit for L1SignedTransaction {
+    fn is_deposit(&self) -> bool {
+        false
+    }
+}
+
 impl IsEip4844 for L1SignedTransaction {
     fn is_eip4844(&self) -> bool {
         matches!(self, L1SignedTransaction::Eip4844(_))
@@ -29,6 +29,11 @@ pub const SECRET_KEYS: [&str; 20] = [
 /// RPC Client are subdirectories of this directory.
 pub const CACHE_DIR: &str = "./edr-cache";
 
+/// The default directory in which checkpoints are stored by
+/// `hardhat_saveCheckpoint`, relative to the working directory when a provider
+/// is created.
+pub const CHECKPOINT_DIR: &str = "./edr-cache/checkpoints";
+
 /// Maximum concurrent requests to a remote blockchain node to avoid getting
 /// rate limited.
 pub const MAX_CONCURRENT_REQUESTS: usize = 5;
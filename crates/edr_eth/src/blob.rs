@@ -0,0 +1,31 @@
+//! Ethereum types for EIP-4844 blob sidecars
+
+use c_kzg::{Blob, Bytes48};
+use edr_primitives::B256;
+
+/// A blob of an EIP-4844 transaction that was included in a block, as
+/// returned by `eth_getBlobSidecars`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlobSidecar {
+    /// Index of the blob within the block
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub index: u64,
+    /// The blob
+    pub blob: Blob,
+    /// The KZG commitment of the blob
+    pub kzg_commitment: Bytes48,
+    /// The KZG proof of the blob
+    pub kzg_proof: Bytes48,
+    /// Hash of the block that includes the blob's transaction
+    pub block_hash: B256,
+    /// Number of the block that includes the blob's transaction
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: u64,
+    /// Hash of the transaction that carries the blob
+    pub transaction_hash: B256,
+    /// Index of the transaction within the block
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub transaction_index: u64,
+}
@@ -25,6 +25,19 @@ pub struct FeeHistoryResult {
     /// requested block percentiles.
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub reward: Option<Vec<Vec<U256>>>,
+    /// An array of block base fees per blob gas. This includes the next block
+    /// after the newest of the returned range, because this value can be
+    /// derived from the newest block. Zeroes are returned for pre-EIP-4844
+    /// blocks.
+    #[cfg_attr(feature = "serde", serde(default, with = "alloy_serde::quantity::vec"))]
+    pub base_fee_per_blob_gas: Vec<u128>,
+    /// An array of block blob gas used ratios. These are calculated as the
+    /// ratio of blob gas used and the maximum blob gas per block.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "crate::serde::deserialize_float_vec")
+    )]
+    pub blob_gas_used_ratio: Vec<f64>,
 }
 
 impl FeeHistoryResult {
@@ -36,6 +49,8 @@ impl FeeHistoryResult {
             base_fee_per_gas: Vec::default(),
             gas_used_ratio: Vec::default(),
             reward: Option::default(),
+            base_fee_per_blob_gas: Vec::default(),
+            blob_gas_used_ratio: Vec::default(),
         }
     }
 }
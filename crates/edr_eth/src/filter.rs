@@ -106,6 +106,8 @@ pub enum FilteredEvents {
     NewHeads(Vec<B256>),
     /// new pending transactions
     NewPendingTransactions(Vec<B256>),
+    /// dropped transactions
+    DroppedTransactions(Vec<B256>),
 }
 
 impl FilteredEvents {
@@ -115,6 +117,7 @@ impl FilteredEvents {
             Self::Logs(v) => Self::Logs(take(v)),
             Self::NewHeads(v) => Self::NewHeads(take(v)),
             Self::NewPendingTransactions(v) => Self::NewPendingTransactions(take(v)),
+            Self::DroppedTransactions(v) => Self::DroppedTransactions(take(v)),
         }
     }
 
@@ -124,6 +127,7 @@ impl FilteredEvents {
             Self::Logs(_) => SubscriptionType::Logs,
             Self::NewHeads(_) => SubscriptionType::NewHeads,
             Self::NewPendingTransactions(_) => SubscriptionType::NewPendingTransactions,
+            Self::DroppedTransactions(_) => SubscriptionType::DroppedTransactions,
         }
     }
 }
@@ -139,6 +143,10 @@ pub enum SubscriptionType {
     /// Induces the emission of transaction hashes that are sent to the network
     /// and marked as "pending".
     NewPendingTransactions,
+    /// Induces the emission of hashes of transactions that are removed from
+    /// the mem pool without being mined. This is not part of the Ethereum
+    /// JSON-RPC specification.
+    DroppedTransactions,
 }
 
 #[cfg(feature = "serde")]
@@ -151,6 +159,7 @@ impl serde::Serialize for SubscriptionType {
             SubscriptionType::Logs => "logs",
             SubscriptionType::NewHeads => "newHeads",
             SubscriptionType::NewPendingTransactions => "newPendingTransactions",
+            SubscriptionType::DroppedTransactions => "droppedTransactions",
         })
     }
 }
@@ -177,6 +186,7 @@ impl<'a> serde::Deserialize<'a> for SubscriptionType {
                     "logs" => Ok(SubscriptionType::Logs),
                     "newHeads" => Ok(SubscriptionType::NewHeads),
                     "newPendingTransactions" => Ok(SubscriptionType::NewPendingTransactions),
+                    "droppedTransactions" => Ok(SubscriptionType::DroppedTransactions),
                     _ => Err(serde::de::Error::custom("Invalid subscription type")),
                 }
             }
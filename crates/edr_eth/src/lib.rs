@@ -5,6 +5,7 @@
 //! Ethereum types as needed by EDR. In particular, they are based on the same
 //! primitive types as `revm`.
 
+pub mod blob;
 /// Ethereum block types
 pub mod block;
 /// Ethereum block spec
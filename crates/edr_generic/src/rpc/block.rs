@@ -107,6 +107,10 @@ impl<T> GetBlockNumber for GenericRpcBlock<T> {
 }
 
 impl<T> RpcEthBlock for GenericRpcBlock<T> {
+    fn hash(&self) -> Option<&B256> {
+        self.hash.as_ref()
+    }
+
     fn state_root(&self) -> &B256 {
         &self.state_root
     }
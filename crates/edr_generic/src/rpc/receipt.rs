@@ -83,6 +83,8 @@ impl TryFrom<GenericRpcTransactionReceipt>
                 contract_address: value.contract_address,
                 gas_used: value.gas_used,
                 effective_gas_price: value.effective_gas_price,
+                blob_gas_used: value.blob_gas_used,
+                blob_gas_price: value.blob_gas_price,
             },
         })
     }
@@ -125,6 +127,8 @@ impl RpcTypeFrom<L1BlockReceipt<TypedEnvelope<edr_receipt::Execution<FilterLog>>
                 edr_receipt::Execution::Eip658(receipt) => Some(receipt.status),
             },
             effective_gas_price: value.inner.effective_gas_price,
+            blob_gas_used: value.inner.blob_gas_used,
+            blob_gas_price: value.inner.blob_gas_price,
             authorization_list: None,
         })
     }
@@ -3,8 +3,8 @@ use edr_chain_spec::{ExecutableTransaction, TransactionValidation};
 use edr_primitives::{Address, Bytes, B256, U256};
 use edr_signer::Signature;
 use edr_transaction::{
-    impl_revm_transaction_trait, IsEip155, IsEip4844, IsLegacy, IsSupported, SignedTransaction,
-    TransactionMut, TransactionType, TxKind,
+    impl_revm_transaction_trait, IsDeposit, IsEip155, IsEip4844, IsLegacy, IsSupported,
+    SignedTransaction, TransactionMut, TransactionType, TxKind,
 };
 
 /// The type of transaction.
@@ -95,6 +95,12 @@ impl SignedTransactionWithFallbackToPostEip155 {
     }
 }
 
+impl alloy_rlp::Decodable for SignedTransactionWithFallbackToPostEip155 {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        L1SignedTransaction::decode(buf).map(Self::from)
+    }
+}
+
 impl alloy_rlp::Encodable for SignedTransactionWithFallbackToPostEip155 {
     fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
         self.inner.encode(out);
@@ -230,6 +236,12 @@ impl IsEip155 for SignedTransactionWithFallbackToPostEip155 {
     }
 }
 
+impl IsDeposit for SignedTransactionWithFallbackToPostEip155 {
+    fn is_deposit(&self) -> bool {
+        self.inner.is_deposit()
+    }
+}
+
 impl IsEip4844 for SignedTransactionWithFallbackToPostEip155 {
     fn is_eip4844(&self) -> bool {
         self.inner.is_eip4844()
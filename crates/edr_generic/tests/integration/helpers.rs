@@ -6,6 +6,7 @@ use edr_provider::{
     test_utils::create_test_config_with_fork, time::CurrentTime, ForkConfig, NoopLogger, Provider,
     ProviderSpec, SyncProviderSpec,
 };
+use edr_rpc_eth::client::TransportConfig;
 use edr_solidity::contract_decoder::ContractDecoder;
 use tokio::runtime;
 
@@ -34,7 +35,10 @@ pub(crate) fn get_chain_fork_provider<
         cache_dir: edr_defaults::CACHE_DIR.into(),
         chain_overrides,
         http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
         url,
+        verified_sources: None,
     }));
 
     config.chain_id = chain_id;
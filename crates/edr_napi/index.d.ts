@@ -179,6 +179,13 @@ export interface BaseFeeActivationByHardfork {
   /** The hardfork at which the `base_fee_params` is activated */
   hardfork: string
 }
+/** Configuration for a scheduled block gas limit change */
+export interface BlockGasLimitActivation {
+  /** The block number from which the gas limit is used */
+  blockNumber: bigint
+  /** The gas limit of each block, starting at `block_number` */
+  gasLimit: bigint
+}
 /** Specification of a chain with possible overrides. */
 export interface ChainOverride {
   /** The chain ID */
@@ -222,15 +229,117 @@ export interface ForkConfig {
   cacheDir?: string
   /** Overrides for the configuration of chains. */
   chainOverrides?: Array<ChainOverride>
+  /**
+   * The URLs of JSON-RPC endpoints that serve the same chain as `url`. They
+   * are tried in order when a request to `url` fails after exhausting its
+   * retries.
+   */
+  fallbackUrls?: Array<string>
   /** The HTTP headers to use when making requests to the JSON-RPC endpoint */
   httpHeaders?: Array<HttpHeader>
-  /** The URL of the JSON-RPC endpoint to fork from */
+  /**
+   * Whether to track the head of the remote blockchain. If enabled, the
+   * fork block advances to the most recent block that is safe from reorgs,
+   * for as long as no local blocks have been mined. Defaults to `false`.
+   */
+  live?: boolean
+  /**
+   * The policy for retries, rate limiting, and timeouts of requests to the
+   * JSON-RPC endpoints. If not provided, the default policy is used.
+   */
+  transport?: RpcTransportConfig
+  /**
+   * The URL of the JSON-RPC endpoint to fork from. Both HTTP and WebSocket
+   * (`ws://` or `wss://`) URLs are supported.
+   */
   url: string
+  /**
+   * If provided, the verified sources of forked contracts are fetched from
+   * Sourcify and Etherscan, and cached in `cacheDir`, to decode their
+   * traces.
+   */
+  verifiedSources?: VerifiedSourcesConfig
+}
+/** Configuration for fetching the verified sources of forked contracts */
+export interface VerifiedSourcesConfig {
+  /** The API key for Etherscan. If not provided, only Sourcify is queried. */
+  etherscanApiKey?: string
+  /** The URL of the Etherscan API. Defaults to the multichain V2 API. */
+  etherscanUrl?: string
+  /** The URL of the Sourcify server. Defaults to the public server. */
+  sourcifyUrl?: string
 }
 export interface HttpHeader {
   name: string
   value: string
 }
+/** Configuration for the transport of requests to remote JSON-RPC endpoints */
+export interface RpcTransportConfig {
+  /**
+   * The maximum number of retries of a transient failure, per endpoint.
+   * Defaults to 9.
+   */
+  maxRetries?: number
+  /**
+   * The interval before the first retry, which grows exponentially for
+   * subsequent retries, in milliseconds. Defaults to 1 second.
+   */
+  minRetryIntervalMs?: number
+  /**
+   * The maximum interval between retries, in milliseconds. Defaults to 32
+   * seconds.
+   */
+  maxRetryIntervalMs?: number
+  /**
+   * The maximum duration of a single request, in milliseconds. If not
+   * provided, requests don't time out.
+   */
+  requestTimeoutMs?: number
+  /**
+   * The maximum number of requests per second sent to each endpoint. If not
+   * provided, requests are not rate limited.
+   */
+  maxRequestsPerSecond?: number
+  /**
+   * Faults to inject into requests, to test how failures of remote nodes
+   * are handled. If not provided, no faults are injected.
+   */
+  faultInjection?: FaultInjectionConfig
+}
+/**
+ * Configuration for injecting faults into requests to remote JSON-RPC
+ * endpoints
+ */
+export interface FaultInjectionConfig {
+  /**
+   * The probability, between 0 and 1, that a request fails without being
+   * sent. Failed requests are retried like any other transient failure.
+   * Defaults to 0.
+   */
+  errorRate?: number
+  /**
+   * The latency that is added to every request, in milliseconds. Defaults
+   * to 0.
+   */
+  latencyMs?: number
+  /**
+   * The maximum random latency that is added to every request on top of
+   * `latencyMs`, in milliseconds. Defaults to 0.
+   */
+  latencyJitterMs?: number
+  /**
+   * The maximum number of blocks that the remote node lags behind its
+   * head. The reported block number never decreases. Blocks, transactions,
+   * receipts, and logs after the reported block number are not returned.
+   * Defaults to 0.
+   */
+  staleBlockWindow?: number
+  /**
+   * The seed used to decide which faults to inject. If not provided, a
+   * random seed is used.
+   */
+  seed?: bigint
+}
 /** Configuration for a hardfork activation */
 export interface HardforkActivation {
   /** The condition for the hardfork activation */
@@ -253,20 +362,145 @@ export const enum MineOrdering {
   /**Effective miner fee */
   Priority = 'Priority'
 }
+/**The mode that determines how block timestamps advance. */
+export const enum TimeMode {
+  /**Block timestamps follow the wall clock, shifted by an offset */
+  Drift = 'Drift',
+  /**Block timestamps only advance through explicit time changes */
+  Frozen = 'Frozen'
+}
+/**
+ * Configuration for an owned account that is imported from its secret key,
+ * with an initial balance.
+ */
+export interface ImportedAccountConfig {
+  /** The secret key of the account */
+  secretKey: string
+  /**
+   * The initial balance of the account. If not provided, the balance is
+   * taken from the genesis state.
+   */
+  balance?: bigint
+}
 /** Configuration for the provider's mempool. */
 export interface MemPoolConfig {
   order: MineOrdering
+  /**
+   * The minimum percentage by which the fees of a replacement transaction
+   * need to exceed the fees of the transaction it replaces. Defaults to
+   * 10%.
+   */
+  priceBumpPercentage?: number
+  /**
+   * The maximum number of transactions in the mem pool. When exceeded, the
+   * transactions with the lowest gas price are evicted. Unlimited if
+   * omitted or zero.
+   */
+  maxTransactions?: number
+  /**
+   * The maximum number of transactions per sender in the mem pool.
+   * Unlimited if omitted or zero.
+   */
+  maxTransactionsPerSender?: number
+  /**
+   * The number of seconds that a transaction can remain in the mem pool,
+   * before it's dropped. Unlimited if omitted or zero.
+   */
+  transactionLifetimeSeconds?: number
+  /**
+   * Whether transactions with a nonce higher than the sender's next nonce
+   * are queued while automining, instead of being rejected. Queued
+   * transactions are mined once the nonce gap is filled. Defaults to
+   * `false`.
+   */
+  queueFutureTransactions?: boolean
 }
 export interface IntervalRange {
   min: bigint
   max: bigint
 }
+/**
+ * Configuration of the JSON-RPC methods that the provider accepts. A
+ * rejected request fails with error code -32004.
+ */
+export interface MethodPermissionsConfig {
+  /** If provided, only the listed methods are accepted. */
+  allowedMethods?: Array<string>
+  /**
+   * Methods that are rejected, even if they are listed in
+   * `allowedMethods`.
+   */
+  deniedMethods?: Array<string>
+  /**
+   * Whether to reject methods that modify the blockchain, the state, the
+   * mempool, or the provider's configuration - e.g. `eth_sendTransaction`
+   * or `hardhat_setBalance` - and methods that sign with the provider's
+   * accounts. Defaults to `false`.
+   */
+  readOnly?: boolean
+}
+/**
+ * Configuration of an account that pays for the gas of the transactions of a
+ * set of senders, to simulate gasless transactions.
+ */
+export interface GasSponsorConfig {
+  /** The address of the account that pays for the gas */
+  sponsor: Uint8Array
+  /** The addresses of the senders whose transactions are sponsored */
+  senders: Array<Uint8Array>
+}
+/**The mode that determines which block states the provider retains in memory. */
+export const enum StatePruningType {
+  /**Retains the states of all blocks */
+  Archive = 'Archive',
+  /**Retains the states of the most recent blocks */
+  Prune = 'Prune'
+}
+/** Configuration for retaining the states of all blocks. */
+export interface ArchiveStatePruningConfig {
+  type: StatePruningType.Archive
+}
+/** Configuration for retaining the states of the most recent blocks. */
+export interface PruneStatePruningConfig {
+  type: StatePruningType.Prune
+  /**
+   * The number of most recent blocks whose states are retained. The states
+   * of older blocks are reconstructed from their state diffs when queried.
+   * Their recorded state diffs, blob sidecars, and indexed address
+   * transactions and token transfers are pruned.
+   */
+  retainedBlocks: number
+}
 /** Configuration for the provider's miner. */
 export interface MiningConfig {
   autoMine: boolean
   interval?: bigint | IntervalRange
   memPool: MemPoolConfig
 }
+/**
+ * Configuration for owned accounts that are derived from a BIP-39
+ * mnemonic.
+ */
+export interface MnemonicAccountsConfig {
+  /** The BIP-39 mnemonic */
+  mnemonic: string
+  /** The BIP-39 passphrase. Defaults to an empty passphrase. */
+  passphrase?: string
+  /**
+   * The BIP-32 derivation path of the accounts, excluding the account
+   * index. Defaults to `m/44'/60'/0'/0`.
+   */
+  path?: string
+  /** The index of the first derived account. Defaults to 0. */
+  initialIndex?: number
+  /** The number of accounts to derive. */
+  count: number
+  /**
+   * The initial balance of each derived account. If not provided, the
+   * balances are taken from the genesis state.
+   */
+  balance?: bigint
+}
 /** Configuration for runtime observability. */
 export interface ObservabilityConfig {
   /** If present, configures runtime observability to collect code coverage. */
@@ -278,8 +512,26 @@ export interface ObservabilityConfig {
 export interface ProviderConfig {
   /** Whether to allow blocks with the same timestamp */
   allowBlocksWithSameTimestamp: boolean
-  /** Whether to allow unlimited contract size */
+  /**
+   * Whether to allow unlimited contract size. Implies
+   * `allowUnlimitedInitCodeSize`.
+   */
   allowUnlimitedContractSize: boolean
+  /**
+   * Whether to allow deployments with init code of any size, disabling
+   * EIP-3860. Defaults to `false`.
+   */
+  allowUnlimitedInitCodeSize?: boolean
+  /**
+   * Whether to top up the balance of impersonated senders to cover the
+   * upfront cost of their transactions. Defaults to `false`.
+   */
+  autoFund?: boolean
+  /**
+   * Whether to impersonate every sender that isn't an owned account,
+   * without requiring `hardhat_impersonateAccount`. Defaults to `false`.
+   */
+  autoImpersonate?: boolean
   /** Whether to return an `Err` when `eth_call` fails */
   bailOnCallFailure: boolean
   /** Whether to return an `Err` when a `eth_sendTransaction` fails */
@@ -297,19 +549,108 @@ export interface ProviderConfig {
   baseFeeConfig?: Array<BaseFeeParamActivation>
   /** The gas limit of each block */
   blockGasLimit: bigint
+  /**
+   * Scheduled changes of the block gas limit. Each gas limit is used from
+   * its block number onwards, until it's replaced by a later activation or
+   * `evm_setBlockGasLimit`.
+   */
+  blockGasLimitSchedule?: Array<BlockGasLimitActivation>
   /** The chain ID of the blockchain */
   chainId: bigint
+  /**
+   * The directory in which checkpoints are stored by
+   * `hardhat_saveCheckpoint` and from which they're loaded by
+   * `hardhat_loadCheckpoint`. Fixtures of `hardhat_runFixture` are cached
+   * in its `fixtures` subdirectory. A relative path is resolved against the
+   * working directory when the provider is created. Defaults to
+   * `./edr-cache/checkpoints`.
+   */
+  checkpointDir?: string
   /** The address of the coinbase */
   coinbase: Uint8Array
+  /**
+   * Whether to deploy the deterministic deployment proxy at
+   * `0x4e59b44847b379578588920ca78fbf26c0b4956c` in the genesis state,
+   * unless `genesisState` overrides its account. Defaults to `false`.
+   */
+  deployDeterministicDeploymentProxy?: boolean
+  /**
+   * Whether to accept and execute transactions regardless of whether the
+   * sender's balance covers their upfront cost. Defaults to `false`.
+   */
+  disableBalanceCheck?: boolean
+  /**
+   * Whether to keep the base fee per gas of new blocks equal to their
+   * parent's, instead of adjusting it based on the parent's gas usage.
+   * Defaults to `false`.
+   */
+  disableBaseFeeAdjustment?: boolean
+  /**
+   * Whether to accept and execute transactions whose gas price - or max
+   * fee per gas - is below the base fee of the block. Defaults to `false`.
+   */
+  disableBaseFeeCheck?: boolean
+  /**
+   * Whether to accept and mine transactions whose priority fee is below
+   * the minimum gas price. Defaults to `false`.
+   */
+  disablePriorityFeeCheck?: boolean
   /**
    * The configuration for forking a blockchain. If not provided, a local
    * blockchain will be created
    */
   fork?: ForkConfig
+  /**
+   * If provided, the gas of transactions sent by the configured senders is
+   * paid by the sponsor account. Before a sponsored transaction is added
+   * to the mempool, the sponsor transfers its maximum gas cost to the
+   * sender, and the cost of unused gas is refunded once it's mined.
+   */
+  gasSponsor?: GasSponsorConfig
+  /**
+   * A Geth-style genesis JSON to initialize the local blockchain with.
+   *
+   * The accounts of its `alloc` section are added to the genesis state.
+   * Fields of accounts that `genesisState` overrides take precedence. Its
+   * chain ID, gas limit, base fee per gas, and timestamp take precedence
+   * over `chainId`, `blockGasLimit`, `initialBaseFeePerGas`, and
+   * `initialDate`, and its extra data is used for the genesis block. The
+   * hardfork is still determined by `hardfork`.
+   *
+   * Can't be used when forking.
+   */
+  genesisJson?: string
   /** The genesis state of the blockchain */
   genesisState: Array<AccountOverride>
   /** The hardfork of the blockchain */
   hardfork: string
+  /**
+   * Hardfork activations for a local blockchain. Blocks before the first
+   * activation use `hardfork`. Only block number conditions are supported.
+   *
+   * An activation at block zero determines the hardfork of the genesis
+   * block.
+   */
+  hardforkActivations?: Array<HardforkActivation>
+  /**
+   * Owned accounts that are imported from their secret keys, with their
+   * initial balances. They follow the accounts of `ownedAccounts` and
+   * `mnemonicAccounts`.
+   */
+  importedAccounts?: Array<ImportedAccountConfig>
+  /**
+   * Whether to index the transactions of mined blocks by the addresses
+   * they involve, so they can be queried with
+   * `explorer_getAddressTransactions`, `ots_searchTransactionsBefore`,
+   * and `ots_searchTransactionsAfter`. Defaults to `false`.
+   */
+  indexAddressTransactions?: boolean
+  /**
+   * Whether to index the ERC-20 and ERC-721 `Transfer` events of mined
+   * blocks, so they can be queried with `hardhat_tokenTransfers` and
+   * `hardhat_tokenBalances`. Defaults to `false`.
+   */
+  indexTokenTransfers?: boolean
   /**
    * The initial base fee per gas of the blockchain. Required for EIP-1559
    * transactions and later
@@ -324,10 +665,38 @@ export interface ProviderConfig {
    * EIP-4788
    */
   initialParentBeaconBlockRoot?: Uint8Array
+  /**
+   * The maximum number of block states that are retained for historical
+   * queries. The state of a block that is no longer retained is derived
+   * from the closest preceding retained state when queried. Defaults to
+   * 100,000 when not set.
+   */
+  maxCachedStates?: number
+  /**
+   * The maximum number of blocks that a single `eth_getLogs` request may
+   * span. Unlimited when not set.
+   */
+  maxLogsBlockRange?: bigint
+  /**
+   * The maximum number of logs that a single `eth_getLogs` request may
+   * return. When exceeded, the error's data contains the block range that
+   * can be queried instead. Unlimited when not set.
+   */
+  maxLogsResults?: bigint
+  /**
+   * Which JSON-RPC methods are accepted. All methods are accepted when not
+   * provided.
+   */
+  methodPermissions?: MethodPermissionsConfig
   /** The minimum gas price of the next block. */
   minGasPrice: bigint
   /** The configuration for the miner */
   mining: MiningConfig
+  /**
+   * Owned accounts that are derived from mnemonics. They follow the
+   * accounts of `ownedAccounts`.
+   */
+  mnemonicAccounts?: Array<MnemonicAccountsConfig>
   /** The network ID of the blockchain */
   networkId: bigint
   /** The configuration for the provider's observability */
@@ -336,6 +705,22 @@ export interface ProviderConfig {
   ownedAccounts: Array<string>
   /** Overrides for precompiles */
   precompileOverrides: Array<Precompile>
+  /**
+   * The seed of the generator of the prevrandao values of mined blocks,
+   * which makes them reproducible across runs and machines. If not
+   * provided, a fixed default seed is used.
+   */
+  prevRandaoSeed?: string
+  /**
+   * Whether to record the changes that each mined transaction makes to the
+   * state, so they can be retrieved with `hardhat_getStateDiff`. Defaults
+   * to `false`.
+   */
+  recordStateDiffs?: boolean
+  /** Which block states are retained in memory. Defaults to archive mode. */
+  statePruning?: PruneStatePruningConfig | ArchiveStatePruningConfig
+  /** How block timestamps advance over time. Defaults to `Drift`. */
+  timeMode?: TimeMode
   /**
    * Transaction gas cap, introduced in [EIP-7825].
    *
@@ -395,6 +780,23 @@ export interface DebugTraceLogItem {
   /** Map of all stored values with keys and values encoded as hex strings. */
   storage?: Record<string, string>
 }
+/** Configuration of the diagnostic logs that are emitted by EDR. */
+export interface DiagnosticsConfig {
+  /**
+   * The filter that selects which diagnostic logs are emitted, using the
+   * syntax of `RUST_LOG` directives, e.g.
+   * `edr_provider=debug,edr_rpc_client=trace`. Defaults to the value of
+   * the `RUST_LOG` environment variable.
+   *
+   * The filter can be changed at runtime using `hardhat_setLogFilter`.
+   */
+  filter?: string
+  /**
+   * The path of a file to which diagnostic logs are appended, in addition to
+   * being printed. Each line of the file contains a single JSON object.
+   */
+  jsonLogFile?: string
+}
 export interface GasReport {
   contracts: Record<string, ContractGasReport>
 }
@@ -450,6 +852,33 @@ export interface InstrumentationMetadata {
  * code.
  */
 export declare function addStatementCoverageInstrumentation(sourceCode: string, sourceId: string, solidityVersion: string, coverageLibraryPath: string): InstrumentationResult
+/** Line and branch coverage of a single source file. */
+export interface SourceLineCoverage {
+  /** The source name of the file, e.g. `contracts/Counter.sol`. */
+  sourceName: string
+  /** The executed lines of the file. */
+  lines: Array<LineHits>
+  /** The conditional jumps of the file. */
+  branches: Array<BranchHits>
+}
+/** The number of times a line was executed. */
+export interface LineHits {
+  /** The 1-based line number. */
+  line: number
+  /** The number of times the line was executed. */
+  hits: bigint
+}
+/** The number of times a conditional jump was taken or not. */
+export interface BranchHits {
+  /** The 1-based line number of the branch. */
+  line: number
+  /** The byte offset of the branch's source location. */
+  offset: number
+  /** The number of times the jump was taken. */
+  taken: bigint
+  /** The number of times the jump was not taken. */
+  notTaken: bigint
+}
 /** Ethereum execution log. */
 export interface ExecutionLog {
   address: Uint8Array
@@ -460,8 +889,44 @@ export interface LoggerConfig {
   /** Whether to enable the logger. */
   enable: boolean
   decodeConsoleLogInputsCallback: (inputs: ArrayBuffer[]) => string[]
+  /**
+   * Optional callback that is called with the ABI-encoded inputs of the
+   * `console.log` calls made during each execution, including `eth_call`,
+   * `eth_estimateGas` and failed transactions. It's called regardless of
+   * whether the logger is enabled.
+   */
+  consoleLogCallback?: (inputs: ArrayBuffer[]) => void
   printLineCallback: (message: string, replace: boolean) => void
 }
+/** Summary of a pending transaction, provided to a mine ordering callback. */
+export interface PendingTransactionSummary {
+  /** The transaction's hash */
+  transactionHash: Uint8Array
+  /** The transaction's sender */
+  caller: Uint8Array
+  /** The transaction's nonce */
+  nonce: bigint
+  /**
+   * The transaction's gas price or, for EIP-1559 transactions, max fee per
+   * gas
+   */
+  gasPrice: bigint
+  /**
+   * The transaction's max priority fee per gas, if it's an EIP-1559
+   * transaction
+   */
+  maxPriorityFeePerGas?: bigint
+  /** The order in which the transaction was added to the mem pool */
+  orderId: bigint
+}
+/**
+ * Creates a stand-in for a precompile that executes the provided bytecode at
+ * the provided address. This allows precompiles that EDR doesn't implement
+ * natively to be emulated by a contract.
+ *
+ * The address cannot be the address of a built-in precompile.
+ */
+export declare function precompileFromBytecode(address: Uint8Array, bytecode: Uint8Array): Precompile
 /**
  * [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md#specification)
  * secp256r1 precompile.
@@ -1173,6 +1638,64 @@ export interface SolidityTestResult {
   /** Gas report, if it was generated. */
   readonly gasReport?: GasReport
 }
+/**
+ * Options for creating and mining synthetic transactions with
+ * [`crate::provider::Provider::flood`].
+ */
+export interface FloodOptions {
+  /** The number of transactions to send. */
+  count: bigint
+  /** The sender of the transactions. Defaults to the first local account. */
+  from?: Uint8Array
+  /**
+   * The receiver of the transactions. Transactions without a receiver
+   * create contracts.
+   */
+  to?: Uint8Array
+  /** The value sent with each transaction. Defaults to zero. */
+  value?: bigint
+  /** The calldata sent with each transaction. Defaults to empty calldata. */
+  data?: Uint8Array
+  /**
+   * The gas limit of each transaction. Defaults to the gas estimation of
+   * the first transaction.
+   */
+  gas?: bigint
+}
+/** Throughput statistics of [`crate::provider::Provider::flood`]. */
+export interface FloodResult {
+  /** The number of mined transactions. */
+  transactions: bigint
+  /** The number of mined transactions that failed. */
+  failed: bigint
+  /** The number of mined blocks. */
+  blocks: bigint
+  /** The total gas used by the mined transactions. */
+  gasUsed: bigint
+  /**
+   * The wall-clock duration of creating and mining the transactions, in
+   * milliseconds.
+   */
+  durationMs: number
+  /** The number of mined transactions per second. */
+  transactionsPerSecond: number
+  /** The amount of gas used per second. */
+  gasPerSecond: number
+}
+/**
+ * A snapshot of a provider's metrics, including request counts and latency
+ * per JSON-RPC method, mined blocks and transactions, fork cache hits, and
+ * state size gauges.
+ */
+export interface ProviderMetrics {
+  /**
+   * The metrics, encoded in the Prometheus text exposition format. Can be
+   * served as-is from a `/metrics` endpoint.
+   */
+  prometheus: string
+  /** The metrics as a JSON object. */
+  snapshot: any
+}
 /** Configuration for subscriptions. */
 export interface SubscriptionConfig {
   /** Callback to be called when a new event is received. */
@@ -1182,6 +1705,35 @@ export interface SubscriptionEvent {
   filterId: bigint
   result: any
 }
+/**
+ * Summary of a transaction that is about to be added to the mem pool,
+ * provided to a transaction firewall callback.
+ */
+export interface TransactionFirewallRequest {
+  /** The transaction's hash */
+  transactionHash: Uint8Array
+  /** The transaction's sender */
+  caller: Uint8Array
+  /** The transaction's recipient, or `undefined` for contract creations */
+  to?: Uint8Array
+  /**
+   * The function selector of the transaction's calldata, if it's at least
+   * four bytes long
+   */
+  selector?: Uint8Array
+  /** The value transferred by the transaction */
+  value: bigint
+}
+/** The decision of a transaction firewall callback about a transaction. */
+export interface TransactionFirewallVerdict {
+  /** If provided, the transaction is rejected for this reason */
+  rejectReason?: string
+  /**
+   * Tags to assign to an accepted transaction, which can be queried with
+   * `hardhat_getTransactionTags` while the transaction is pending
+   */
+  tags?: Array<string>
+}
 export declare function linkHexStringBytecode(code: string, address: string, position: number): string
 export declare function printStackTrace(trace: SolidityStackTrace): void
 /** Represents the exit code of the EVM. */
@@ -1338,6 +1890,8 @@ export interface DirectLibraryCallErrorStackTraceEntry {
 export interface UnrecognizedCreateErrorStackTraceEntry {
   type: StackTraceEntryType.UNRECOGNIZED_CREATE_ERROR
   returnData: Uint8Array
+  /** The human-readable custom error of the return data, if it's known. */
+  customError?: string
   sourceReference?: undefined
   isInvalidOpcodeError: boolean
 }
@@ -1345,6 +1899,8 @@ export interface UnrecognizedContractErrorStackTraceEntry {
   type: StackTraceEntryType.UNRECOGNIZED_CONTRACT_ERROR
   address: Uint8Array
   returnData: Uint8Array
+  /** The human-readable custom error of the return data, if it's known. */
+  customError?: string
   sourceReference?: undefined
   isInvalidOpcodeError: boolean
 }
@@ -1434,7 +1990,7 @@ export interface Withdrawal {
 }
 export declare class EdrContext {
   /** Creates a new [`EdrContext`] instance. Should only be called once! */
-  constructor()
+  constructor(diagnosticsConfig?: DiagnosticsConfig | undefined | null)
   /** Constructs a new provider with the provided configuration. */
   createProvider(chainType: string, providerConfig: ProviderConfig, loggerConfig: LoggerConfig, subscriptionConfig: SubscriptionConfig, contractDecoder: ContractDecoder): Promise<Provider>
   /** Registers a new provider factory for the provided chain type. */
@@ -1474,10 +2030,16 @@ export declare class ContractDecoder {
 export declare class Precompile {
   /** Returns the address of the precompile. */
   get address(): Uint8Array
+  /**
+   * Returns a copy of the precompile that is located at the provided
+   * address instead. This allows chain-specific precompiles to be
+   * registered at a custom address.
+   */
+  withAddress(address: Uint8Array): Precompile
 }
 export declare class ProviderFactory { }
 export declare class Response {
-  /**Returns the response data as a JSON string or a JSON object. */
+  /**Returns the response data as a JSON string or a JSON object. The string is empty for a batch request that only consists of notifications, as it doesn't receive a response. */
   get data(): string | any
   /**Compute the error stack trace. Return the stack trace if it can be decoded, otherwise returns none. Throws if there was an error computing the stack trace. */
   stackTrace(): SolidityStackTrace | null
@@ -1496,7 +2058,67 @@ export declare class Provider {
   contractDecoder(): ContractDecoder
   /**Handles a JSON-RPC request and returns a JSON-RPC response. */
   handleRequest(request: string): Promise<Response>
+  /**
+   * Handles a `debug_traceTransaction` JSON-RPC request, streaming its
+   * struct logs in chunks instead of returning them in a single response.
+   *
+   * Recommended for transactions with large traces, as struct logs are
+   * serialized in chunks instead of as a single response. Tracing never
+   * waits for the consumer, as the provider is locked while tracing. If the
+   * consumer falls behind, chunks are buffered until tracing has finished.
+   */
+  handleStreamedTraceRequest(request: string): StructLogStream
+  /**
+   * Creates and mines synthetic transactions in-process, without
+   * serializing them individually, and returns throughput statistics.
+   *
+   * Transactions are added to the mem pool in batches that fit in a single
+   * block, after which the pending transactions are mined. Mined blocks are
+   * not logged and their traces are not collected.
+   */
+  flood(options: FloodOptions): Promise<FloodResult>
+  /** Returns a snapshot of the provider's metrics. */
+  metrics(): Promise<ProviderMetrics>
+  /**
+   * Enables or disables the collection of line and branch coverage.
+   *
+   * Executed program counters are mapped to source lines using the source
+   * maps of the contracts known to the instance's contract decoder.
+   * Disabling collection discards any coverage that was collected so far.
+   */
+  setLineCoverageEnabled(enabled: boolean): void
+  /**
+   * Returns the line and branch coverage collected since collection was
+   * enabled or since the last call to this method, and resets it.
+   */
+  takeLineCoverage(): Array<SourceLineCoverage>
   setCallOverrideCallback(callOverrideCallback: (contract_address: ArrayBuffer, data: ArrayBuffer) => Promise<CallOverrideResult | undefined>): Promise<void>
+  /**
+   * Sets a callback that determines the order in which pending transactions
+   * are mined, overriding the configured mine ordering. The callback
+   * receives the pending transactions and returns their hashes in order of
+   * descending priority. Omitted transactions are mined last, in insertion
+   * order. A sender's transactions are always mined in nonce order. If the
+   * callback throws or its promise is rejected, mining fails with an error.
+   *
+   * Pass `undefined` to restore the configured mine ordering.
+   */
+  setMineOrderingCallback(mineOrderingCallback?: ((transactions: PendingTransactionSummary[]) => Promise<Uint8Array[]>) | undefined): Promise<void>
+  /**
+   * Sets a callback that decides whether transactions are added to the mem
+   * pool, e.g. to simulate sequencer policies or sender allowlists and
+   * denylists. The callback receives a summary of each transaction before
+   * it's added to the mem pool and either rejects it or accepts it with
+   * optional tags, which can be queried with `hardhat_getTransactionTags`
+   * while the transaction is pending. If the callback throws or its
+   * promise rejects, the transaction is rejected.
+   *
+   * The callback is called while the provider handles a request, so it
+   * must not send requests to the provider, as they would never complete.
+   *
+   * Pass `undefined` to admit all transactions.
+   */
+  setTransactionFirewallCallback(transactionFirewallCallback?: ((transaction: TransactionFirewallRequest) => Promise<TransactionFirewallVerdict>) | undefined): Promise<void>
   /**
    * Set to `true` to make the traces returned with `eth_call`,
    * `eth_estimateGas`, `eth_sendRawTransaction`, `eth_sendTransaction`,
@@ -1505,6 +2127,13 @@ export declare class Provider {
    */
   setVerboseTracing(verboseTracing: boolean): Promise<void>
 }
+/** A stream of the struct logs of a traced transaction. */
+export declare class StructLogStream {
+  /**Returns the next chunk of struct logs as newline-delimited JSON, or `null` once all struct logs have been returned. */
+  next(): Promise<string | null>
+  /**Returns the JSON-encoded trace result, excluding its struct logs. Only available once the stream has been exhausted. */
+  result(): string | null
+}
 export declare class SolidityTestRunnerFactory { }
 /** See [`edr_solidity_tests::result::SuiteResult`] */
 export declare class SuiteResult {
@@ -1,7 +1,7 @@
 use std::{str::FromStr, sync::Arc};
 
 use edr_blockchain_fork::eips::{
-    eip2935::{HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_UNSUPPORTED_BYTECODE},
+    eip2935::{history_storage_contract, HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_BYTECODE},
     eip4788::{BEACON_ROOTS_ADDRESS, BEACON_ROOTS_BYTECODE},
 };
 use edr_chain_l1::L1ChainSpec;
@@ -68,10 +68,9 @@ pub fn l1_genesis_state(hardfork: SpecId) -> Vec<AccountOverride> {
     let history_storage_account_constructor = || AccountOverride {
         address: Uint8Array::with_data_copied(HISTORY_STORAGE_ADDRESS),
         balance: Some(BigInt::from(0u64)),
-        nonce: Some(BigInt::from(0u64)),
-        code: Some(Uint8Array::with_data_copied(
-            &HISTORY_STORAGE_UNSUPPORTED_BYTECODE,
-        )),
+        // EIP-2935: the contract is deployed with a nonce of one
+        nonce: Some(BigInt::from(history_storage_contract().nonce)),
+        code: Some(Uint8Array::with_data_copied(&HISTORY_STORAGE_BYTECODE)),
         storage: Some(Vec::new()),
     };
 
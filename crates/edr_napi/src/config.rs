@@ -1,6 +1,6 @@
 use core::fmt::{Debug, Display};
 use std::{
-    num::NonZeroU64,
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::PathBuf,
     time::{Duration, SystemTime},
 };
@@ -8,8 +8,12 @@ use std::{
 use edr_coverage::reporter::SyncOnCollectedCoverageCallback;
 use edr_eip1559::{BaseFeeActivation, ConstantBaseFeeParams};
 use edr_gas_report::SyncOnCollectedGasReportCallback;
-use edr_primitives::{Bytes, HashMap, HashSet};
-use edr_signer::{secret_key_from_str, SecretKey};
+use edr_primitives::{Bytes, HashMap, HashSet, U256};
+use edr_provider::GethGenesis;
+use edr_signer::{
+    public_key_to_address, secret_key_from_str, secret_keys_from_mnemonic, SecretKey,
+    DEFAULT_DERIVATION_PATH,
+};
 use napi::{
     bindgen_prelude::{BigInt, Promise, Reference, Uint8Array},
     threadsafe_function::{
@@ -21,8 +25,13 @@ use napi::{
 use napi_derive::napi;
 
 use crate::{
-    account::AccountOverride, block::BlobGas, cast::TryCast, gas_report::GasReport,
-    logger::LoggerConfig, precompile::Precompile, subscription::SubscriptionConfig,
+    account::AccountOverride,
+    block::BlobGas,
+    cast::TryCast,
+    gas_report::GasReport,
+    logger::LoggerConfig,
+    precompile::{Precompile, PrecompileImplementation},
+    subscription::SubscriptionConfig,
 };
 
 /// Configuration for EIP-1559 parameters
@@ -68,6 +77,30 @@ impl TryFrom<BaseFeeParamActivation> for (BaseFeeActivation<String>, ConstantBas
     }
 }
 
+/// Configuration for a scheduled block gas limit change
+#[napi(object)]
+pub struct BlockGasLimitActivation {
+    /// The block number from which the gas limit is used
+    pub block_number: BigInt,
+    /// The gas limit of each block, starting at `block_number`
+    pub gas_limit: BigInt,
+}
+
+impl TryFrom<BlockGasLimitActivation> for (u64, NonZeroU64) {
+    type Error = napi::Error;
+
+    fn try_from(value: BlockGasLimitActivation) -> Result<Self, Self::Error> {
+        let gas_limit = NonZeroU64::new(value.gas_limit.try_cast()?).ok_or_else(|| {
+            napi::Error::new(
+                napi::Status::GenericFailure,
+                "Block gas limit must be greater than 0",
+            )
+        })?;
+
+        Ok((value.block_number.try_cast()?, gas_limit))
+    }
+}
+
 /// Specification of a chain with possible overrides.
 #[napi(object)]
 pub struct ChainOverride {
@@ -114,10 +147,37 @@ pub struct ForkConfig {
     pub cache_dir: Option<String>,
     /// Overrides for the configuration of chains.
     pub chain_overrides: Option<Vec<ChainOverride>>,
+    /// The URLs of JSON-RPC endpoints that serve the same chain as `url`. They
+    /// are tried in order when a request to `url` fails after exhausting its
+    /// retries.
+    pub fallback_urls: Option<Vec<String>>,
     /// The HTTP headers to use when making requests to the JSON-RPC endpoint
     pub http_headers: Option<Vec<HttpHeader>>,
-    /// The URL of the JSON-RPC endpoint to fork from
+    /// Whether to track the head of the remote blockchain. If enabled, the
+    /// fork block advances to the most recent block that is safe from reorgs,
+    /// for as long as no local blocks have been mined. Defaults to `false`.
+    pub live: Option<bool>,
+    /// The policy for retries, rate limiting, and timeouts of requests to the
+    /// JSON-RPC endpoints. If not provided, the default policy is used.
+    pub transport: Option<RpcTransportConfig>,
+    /// The URL of the JSON-RPC endpoint to fork from. Both HTTP and WebSocket
+    /// (`ws://` or `wss://`) URLs are supported.
     pub url: String,
+    /// If provided, the verified sources of forked contracts are fetched from
+    /// Sourcify and Etherscan, and cached in `cacheDir`, to decode their
+    /// traces.
+    pub verified_sources: Option<VerifiedSourcesConfig>,
+}
+
+/// Configuration for fetching the verified sources of forked contracts
+#[napi(object)]
+pub struct VerifiedSourcesConfig {
+    /// The API key for Etherscan. If not provided, only Sourcify is queried.
+    pub etherscan_api_key: Option<String>,
+    /// The URL of the Etherscan API. Defaults to the multichain V2 API.
+    pub etherscan_url: Option<String>,
+    /// The URL of the Sourcify server. Defaults to the public server.
+    pub sourcify_url: Option<String>,
 }
 
 #[napi(object)]
@@ -126,6 +186,53 @@ pub struct HttpHeader {
     pub value: String,
 }
 
+/// Configuration for the transport of requests to remote JSON-RPC endpoints
+#[napi(object)]
+pub struct RpcTransportConfig {
+    /// The maximum number of retries of a transient failure, per endpoint.
+    /// Defaults to 9.
+    pub max_retries: Option<u32>,
+    /// The interval before the first retry, which grows exponentially for
+    /// subsequent retries, in milliseconds. Defaults to 1 second.
+    pub min_retry_interval_ms: Option<u32>,
+    /// The maximum interval between retries, in milliseconds. Defaults to 32
+    /// seconds.
+    pub max_retry_interval_ms: Option<u32>,
+    /// The maximum duration of a single request, in milliseconds. If not
+    /// provided, requests don't time out.
+    pub request_timeout_ms: Option<u32>,
+    /// The maximum number of requests per second sent to each endpoint. If not
+    /// provided, requests are not rate limited.
+    pub max_requests_per_second: Option<u32>,
+    /// Faults to inject into requests, to test how failures of remote nodes
+    /// are handled. If not provided, no faults are injected.
+    pub fault_injection: Option<FaultInjectionConfig>,
+}
+
+/// Configuration for injecting faults into requests to remote JSON-RPC
+/// endpoints
+#[napi(object)]
+pub struct FaultInjectionConfig {
+    /// The probability, between 0 and 1, that a request fails without being
+    /// sent. Failed requests are retried like any other transient failure.
+    /// Defaults to 0.
+    pub error_rate: Option<f64>,
+    /// The latency that is added to every request, in milliseconds. Defaults
+    /// to 0.
+    pub latency_ms: Option<u32>,
+    /// The maximum random latency that is added to every request on top of
+    /// `latencyMs`, in milliseconds. Defaults to 0.
+    pub latency_jitter_ms: Option<u32>,
+    /// The maximum number of blocks that the remote node lags behind its
+    /// head. The reported block number never decreases. Blocks, transactions,
+    /// receipts, and logs after the reported block number are not returned.
+    /// Defaults to 0.
+    pub stale_block_window: Option<u32>,
+    /// The seed used to decide which faults to inject. If not provided, a
+    /// random seed is used.
+    pub seed: Option<BigInt>,
+}
+
 /// Configuration for a hardfork activation
 #[napi(object)]
 pub struct HardforkActivation {
@@ -156,10 +263,51 @@ pub enum MineOrdering {
     Priority,
 }
 
+#[napi(string_enum)]
+#[doc = "The mode that determines how block timestamps advance."]
+pub enum TimeMode {
+    #[doc = "Block timestamps follow the wall clock, shifted by an offset"]
+    Drift,
+    #[doc = "Block timestamps only advance through explicit time changes"]
+    Frozen,
+}
+
+/// Configuration for an owned account that is imported from its secret key,
+/// with an initial balance.
+#[napi(object)]
+pub struct ImportedAccountConfig {
+    // Using JsString here as it doesn't have `Debug`, `Display` and `Serialize` implementation
+    // which prevents accidentally leaking the secret key to error messages and logs.
+    /// The secret key of the account
+    pub secret_key: JsString,
+    /// The initial balance of the account. If not provided, the balance is
+    /// taken from the genesis state.
+    pub balance: Option<BigInt>,
+}
+
 /// Configuration for the provider's mempool.
 #[napi(object)]
 pub struct MemPoolConfig {
     pub order: MineOrdering,
+    /// The minimum percentage by which the fees of a replacement transaction
+    /// need to exceed the fees of the transaction it replaces. Defaults to
+    /// 10%.
+    pub price_bump_percentage: Option<u32>,
+    /// The maximum number of transactions in the mem pool. When exceeded, the
+    /// transactions with the lowest gas price are evicted. Unlimited if
+    /// omitted or zero.
+    pub max_transactions: Option<u32>,
+    /// The maximum number of transactions per sender in the mem pool.
+    /// Unlimited if omitted or zero.
+    pub max_transactions_per_sender: Option<u32>,
+    /// The number of seconds that a transaction can remain in the mem pool,
+    /// before it's dropped. Unlimited if omitted or zero.
+    pub transaction_lifetime_seconds: Option<u32>,
+    /// Whether transactions with a nonce higher than the sender's next nonce
+    /// are queued while automining, instead of being rejected. Queued
+    /// transactions are mined once the nonce gap is filled. Defaults to
+    /// `false`.
+    pub queue_future_transactions: Option<bool>,
 }
 
 #[napi(object)]
@@ -168,6 +316,60 @@ pub struct IntervalRange {
     pub max: BigInt,
 }
 
+/// Configuration of the JSON-RPC methods that the provider accepts. A
+/// rejected request fails with error code -32004.
+#[napi(object)]
+pub struct MethodPermissionsConfig {
+    /// If provided, only the listed methods are accepted.
+    pub allowed_methods: Option<Vec<String>>,
+    /// Methods that are rejected, even if they are listed in
+    /// `allowedMethods`.
+    pub denied_methods: Option<Vec<String>>,
+    /// Whether to reject methods that modify the blockchain, the state, the
+    /// mempool, or the provider's configuration - e.g. `eth_sendTransaction`
+    /// or `hardhat_setBalance` - and methods that sign with the provider's
+    /// accounts. Defaults to `false`.
+    pub read_only: Option<bool>,
+}
+
+/// Configuration of an account that pays for the gas of the transactions of a
+/// set of senders, to simulate gasless transactions.
+#[napi(object)]
+pub struct GasSponsorConfig {
+    /// The address of the account that pays for the gas
+    pub sponsor: Uint8Array,
+    /// The addresses of the senders whose transactions are sponsored
+    pub senders: Vec<Uint8Array>,
+}
+
+#[napi(string_enum)]
+#[doc = "The mode that determines which block states the provider retains in memory."]
+pub enum StatePruningType {
+    #[doc = "Retains the states of all blocks"]
+    Archive,
+    #[doc = "Retains the states of the most recent blocks"]
+    Prune,
+}
+
+/// Configuration for retaining the states of all blocks.
+#[napi(object)]
+pub struct ArchiveStatePruningConfig {
+    #[napi(js_name = "type", ts_type = "StatePruningType.Archive")]
+    pub type_: StatePruningType,
+}
+
+/// Configuration for retaining the states of the most recent blocks.
+#[napi(object)]
+pub struct PruneStatePruningConfig {
+    #[napi(js_name = "type", ts_type = "StatePruningType.Prune")]
+    pub type_: StatePruningType,
+    /// The number of most recent blocks whose states are retained. The states
+    /// of older blocks are reconstructed from their state diffs when queried.
+    /// Their recorded state diffs, blob sidecars, and indexed address
+    /// transactions and token transfers are pruned.
+    pub retained_blocks: u32,
+}
+
 /// Configuration for the provider's miner.
 #[napi(object)]
 pub struct MiningConfig {
@@ -176,6 +378,28 @@ pub struct MiningConfig {
     pub mem_pool: MemPoolConfig,
 }
 
+/// Configuration for owned accounts that are derived from a BIP-39
+/// mnemonic.
+#[napi(object)]
+pub struct MnemonicAccountsConfig {
+    // Using JsString here as it doesn't have `Debug`, `Display` and `Serialize` implementation
+    // which prevents accidentally leaking the mnemonic to error messages and logs.
+    /// The BIP-39 mnemonic
+    pub mnemonic: JsString,
+    /// The BIP-39 passphrase. Defaults to an empty passphrase.
+    pub passphrase: Option<JsString>,
+    /// The BIP-32 derivation path of the accounts, excluding the account
+    /// index. Defaults to `m/44'/60'/0'/0`.
+    pub path: Option<String>,
+    /// The index of the first derived account. Defaults to 0.
+    pub initial_index: Option<u32>,
+    /// The number of accounts to derive.
+    pub count: u32,
+    /// The initial balance of each derived account. If not provided, the
+    /// balances are taken from the genesis state.
+    pub balance: Option<BigInt>,
+}
+
 /// Configuration for runtime observability.
 #[napi(object)]
 pub struct ObservabilityConfig {
@@ -190,8 +414,18 @@ pub struct ObservabilityConfig {
 pub struct ProviderConfig {
     /// Whether to allow blocks with the same timestamp
     pub allow_blocks_with_same_timestamp: bool,
-    /// Whether to allow unlimited contract size
+    /// Whether to allow unlimited contract size. Implies
+    /// `allowUnlimitedInitCodeSize`.
     pub allow_unlimited_contract_size: bool,
+    /// Whether to allow deployments with init code of any size, disabling
+    /// EIP-3860. Defaults to `false`.
+    pub allow_unlimited_init_code_size: Option<bool>,
+    /// Whether to top up the balance of impersonated senders to cover the
+    /// upfront cost of their transactions. Defaults to `false`.
+    pub auto_fund: Option<bool>,
+    /// Whether to impersonate every sender that isn't an owned account,
+    /// without requiring `hardhat_impersonateAccount`. Defaults to `false`.
+    pub auto_impersonate: Option<bool>,
     /// Whether to return an `Err` when `eth_call` fails
     pub bail_on_call_failure: bool,
     /// Whether to return an `Err` when a `eth_sendTransaction` fails
@@ -207,17 +441,80 @@ pub struct ProviderConfig {
     pub base_fee_config: Option<Vec<BaseFeeParamActivation>>,
     /// The gas limit of each block
     pub block_gas_limit: BigInt,
+    /// Scheduled changes of the block gas limit. Each gas limit is used from
+    /// its block number onwards, until it's replaced by a later activation or
+    /// `evm_setBlockGasLimit`.
+    pub block_gas_limit_schedule: Option<Vec<BlockGasLimitActivation>>,
     /// The chain ID of the blockchain
     pub chain_id: BigInt,
+    /// The directory in which checkpoints are stored by
+    /// `hardhat_saveCheckpoint` and from which they're loaded by
+    /// `hardhat_loadCheckpoint`. Fixtures of `hardhat_runFixture` are cached
+    /// in its `fixtures` subdirectory. A relative path is resolved against the
+    /// working directory when the provider is created. Defaults to
+    /// `./edr-cache/checkpoints`.
+    pub checkpoint_dir: Option<String>,
     /// The address of the coinbase
     pub coinbase: Uint8Array,
+    /// Whether to deploy the deterministic deployment proxy at
+    /// `0x4e59b44847b379578588920ca78fbf26c0b4956c` in the genesis state,
+    /// unless `genesisState` overrides its account. Defaults to `false`.
+    pub deploy_deterministic_deployment_proxy: Option<bool>,
+    /// Whether to accept and execute transactions regardless of whether the
+    /// sender's balance covers their upfront cost. Defaults to `false`.
+    pub disable_balance_check: Option<bool>,
+    /// Whether to keep the base fee per gas of new blocks equal to their
+    /// parent's, instead of adjusting it based on the parent's gas usage.
+    /// Defaults to `false`.
+    pub disable_base_fee_adjustment: Option<bool>,
+    /// Whether to accept and execute transactions whose gas price - or max
+    /// fee per gas - is below the base fee of the block. Defaults to `false`.
+    pub disable_base_fee_check: Option<bool>,
+    /// Whether to accept and mine transactions whose priority fee is below
+    /// the minimum gas price. Defaults to `false`.
+    pub disable_priority_fee_check: Option<bool>,
     /// The configuration for forking a blockchain. If not provided, a local
     /// blockchain will be created
     pub fork: Option<ForkConfig>,
+    /// If provided, the gas of transactions sent by the configured senders is
+    /// paid by the sponsor account. Before a sponsored transaction is added
+    /// to the mempool, the sponsor transfers its maximum gas cost to the
+    /// sender, and the cost of unused gas is refunded once it's mined.
+    pub gas_sponsor: Option<GasSponsorConfig>,
+    /// A Geth-style genesis JSON to initialize the local blockchain with.
+    ///
+    /// The accounts of its `alloc` section are added to the genesis state.
+    /// Fields of accounts that `genesisState` overrides take precedence. Its
+    /// chain ID, gas limit, base fee per gas, and timestamp take precedence
+    /// over `chainId`, `blockGasLimit`, `initialBaseFeePerGas`, and
+    /// `initialDate`, and its extra data is used for the genesis block. The
+    /// hardfork is still determined by `hardfork`.
+    ///
+    /// Can't be used when forking.
+    pub genesis_json: Option<String>,
     /// The genesis state of the blockchain
     pub genesis_state: Vec<AccountOverride>,
     /// The hardfork of the blockchain
     pub hardfork: String,
+    /// Hardfork activations for a local blockchain. Blocks before the first
+    /// activation use `hardfork`. Only block number conditions are supported.
+    ///
+    /// An activation at block zero determines the hardfork of the genesis
+    /// block.
+    pub hardfork_activations: Option<Vec<HardforkActivation>>,
+    /// Owned accounts that are imported from their secret keys, with their
+    /// initial balances. They follow the accounts of `ownedAccounts` and
+    /// `mnemonicAccounts`.
+    pub imported_accounts: Option<Vec<ImportedAccountConfig>>,
+    /// Whether to index the transactions of mined blocks by the addresses
+    /// they involve, so they can be queried with
+    /// `explorer_getAddressTransactions`, `ots_searchTransactionsBefore`,
+    /// and `ots_searchTransactionsAfter`. Defaults to `false`.
+    pub index_address_transactions: Option<bool>,
+    /// Whether to index the ERC-20 and ERC-721 `Transfer` events of mined
+    /// blocks, so they can be queried with `hardhat_tokenTransfers` and
+    /// `hardhat_tokenBalances`. Defaults to `false`.
+    pub index_token_transfers: Option<bool>,
     /// The initial base fee per gas of the blockchain. Required for EIP-1559
     /// transactions and later
     pub initial_base_fee_per_gas: Option<BigInt>,
@@ -228,10 +525,28 @@ pub struct ProviderConfig {
     /// The initial parent beacon block root of the blockchain. Required for
     /// EIP-4788
     pub initial_parent_beacon_block_root: Option<Uint8Array>,
+    /// The maximum number of block states that are retained for historical
+    /// queries. The state of a block that is no longer retained is derived
+    /// from the closest preceding retained state when queried. Defaults to
+    /// 100,000 when not set.
+    pub max_cached_states: Option<u32>,
+    /// The maximum number of blocks that a single `eth_getLogs` request may
+    /// span. Unlimited when not set.
+    pub max_logs_block_range: Option<BigInt>,
+    /// The maximum number of logs that a single `eth_getLogs` request may
+    /// return. When exceeded, the error's data contains the block range that
+    /// can be queried instead. Unlimited when not set.
+    pub max_logs_results: Option<BigInt>,
+    /// Which JSON-RPC methods are accepted. All methods are accepted when not
+    /// provided.
+    pub method_permissions: Option<MethodPermissionsConfig>,
     /// The minimum gas price of the next block.
     pub min_gas_price: BigInt,
     /// The configuration for the miner
     pub mining: MiningConfig,
+    /// Owned accounts that are derived from mnemonics. They follow the
+    /// accounts of `ownedAccounts`.
+    pub mnemonic_accounts: Option<Vec<MnemonicAccountsConfig>>,
     /// The network ID of the blockchain
     pub network_id: BigInt,
     /// The configuration for the provider's observability
@@ -242,6 +557,18 @@ pub struct ProviderConfig {
     pub owned_accounts: Vec<JsString>,
     /// Overrides for precompiles
     pub precompile_overrides: Vec<Reference<Precompile>>,
+    /// The seed of the generator of the prevrandao values of mined blocks,
+    /// which makes them reproducible across runs and machines. If not
+    /// provided, a fixed default seed is used.
+    pub prev_randao_seed: Option<String>,
+    /// Whether to record the changes that each mined transaction makes to the
+    /// state, so they can be retrieved with `hardhat_getStateDiff`. Defaults
+    /// to `false`.
+    pub record_state_diffs: Option<bool>,
+    /// Which block states are retained in memory. Defaults to archive mode.
+    pub state_pruning: Option<Either<PruneStatePruningConfig, ArchiveStatePruningConfig>>,
+    /// How block timestamps advance over time. Defaults to `Drift`.
+    pub time_mode: Option<TimeMode>,
     /// Transaction gas cap, introduced in [EIP-7825].
     ///
     /// When not set, will default to value defined by the used hardfork
@@ -327,12 +654,95 @@ impl TryFrom<ForkConfig> for edr_provider::ForkConfig<String> {
                 .collect()
         });
 
+        let mut transport = value
+            .transport
+            .map(edr_rpc_client::TransportConfig::try_from)
+            .transpose()?
+            .unwrap_or_default();
+        transport.fallback_urls = value.fallback_urls.unwrap_or_default();
+
         Ok(Self {
             block_number,
             cache_dir,
             chain_overrides: chain_overrides.unwrap_or_default(),
             http_headers,
+            live: value.live.unwrap_or(false),
+            transport,
             url: value.url,
+            verified_sources: value.verified_sources.map(
+                |VerifiedSourcesConfig {
+                     etherscan_api_key,
+                     etherscan_url,
+                     sourcify_url,
+                 }| edr_provider::VerifiedSourcesConfig {
+                    etherscan_api_key,
+                    etherscan_url,
+                    sourcify_url,
+                },
+            ),
+        })
+    }
+}
+
+impl TryFrom<RpcTransportConfig> for edr_rpc_client::TransportConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: RpcTransportConfig) -> Result<Self, Self::Error> {
+        let default = Self::default();
+
+        let max_requests_per_second = value
+            .max_requests_per_second
+            .map(|max_requests_per_second| {
+                NonZeroU32::new(max_requests_per_second).ok_or_else(|| {
+                    napi::Error::new(
+                        napi::Status::InvalidArg,
+                        "maxRequestsPerSecond must be greater than zero".to_string(),
+                    )
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            max_retries: value.max_retries.unwrap_or(default.max_retries),
+            min_retry_interval: value
+                .min_retry_interval_ms
+                .map_or(default.min_retry_interval, |interval| {
+                    Duration::from_millis(interval.into())
+                }),
+            max_retry_interval: value
+                .max_retry_interval_ms
+                .map_or(default.max_retry_interval, |interval| {
+                    Duration::from_millis(interval.into())
+                }),
+            request_timeout: value
+                .request_timeout_ms
+                .map(|timeout| Duration::from_millis(timeout.into())),
+            max_requests_per_second,
+            fault_injection: value
+                .fault_injection
+                .map(edr_rpc_client::FaultInjectionConfig::try_from)
+                .transpose()?
+                .unwrap_or_default(),
+            ..default
+        })
+    }
+}
+
+impl TryFrom<FaultInjectionConfig> for edr_rpc_client::FaultInjectionConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: FaultInjectionConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            error_rate: edr_rpc_client::ErrorRate::try_from(value.error_rate.unwrap_or_default())
+                .map_err(|error| {
+                napi::Error::new(napi::Status::InvalidArg, error.to_string())
+            })?,
+            latency: Duration::from_millis(value.latency_ms.unwrap_or_default().into()),
+            latency_jitter: Duration::from_millis(
+                value.latency_jitter_ms.unwrap_or_default().into(),
+            ),
+            stale_block_window: value.stale_block_window.unwrap_or_default().into(),
+            seed: value.seed.map(|seed| seed.try_cast()).transpose()?,
         })
     }
 }
@@ -341,6 +751,89 @@ impl From<MemPoolConfig> for edr_provider::MemPoolConfig {
     fn from(value: MemPoolConfig) -> Self {
         Self {
             order: value.order.into(),
+            policy: edr_mem_pool::MemPoolPolicy {
+                price_bump_percentage: value
+                    .price_bump_percentage
+                    .map_or(edr_mem_pool::DEFAULT_PRICE_BUMP_PERCENTAGE, u64::from),
+                max_transactions: value
+                    .max_transactions
+                    .and_then(|max| NonZeroUsize::new(max as usize)),
+                max_transactions_per_sender: value
+                    .max_transactions_per_sender
+                    .and_then(|max| NonZeroUsize::new(max as usize)),
+                transaction_lifetime: value
+                    .transaction_lifetime_seconds
+                    .filter(|seconds| *seconds > 0)
+                    .map(|seconds| Duration::from_secs(seconds.into())),
+                queue_future_transactions: value.queue_future_transactions.unwrap_or(false),
+            },
+        }
+    }
+}
+
+impl From<MethodPermissionsConfig> for edr_provider::MethodPermissionsConfig {
+    fn from(value: MethodPermissionsConfig) -> Self {
+        Self {
+            allowed_methods: value
+                .allowed_methods
+                .map(|methods| methods.into_iter().collect()),
+            denied_methods: value
+                .denied_methods
+                .map(|methods| methods.into_iter().collect())
+                .unwrap_or_default(),
+            read_only: value.read_only.unwrap_or(false),
+        }
+    }
+}
+
+impl TryFrom<GasSponsorConfig> for edr_provider::GasSponsorConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: GasSponsorConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sponsor: value.sponsor.try_cast()?,
+            senders: value
+                .senders
+                .into_iter()
+                .map(TryCast::<edr_primitives::Address>::try_cast)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<PruneStatePruningConfig> for edr_provider::StatePruningConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: PruneStatePruningConfig) -> Result<Self, Self::Error> {
+        match value.type_ {
+            // An archive configuration with a superfluous `retainedBlocks`
+            StatePruningType::Archive => Ok(Self::Archive),
+            StatePruningType::Prune => {
+                let retained_blocks =
+                    NonZeroU64::new(value.retained_blocks.into()).ok_or_else(|| {
+                        napi::Error::new(
+                            napi::Status::InvalidArg,
+                            "retainedBlocks must be greater than zero".to_string(),
+                        )
+                    })?;
+
+                Ok(Self::Prune { retained_blocks })
+            }
+        }
+    }
+}
+
+impl TryFrom<ArchiveStatePruningConfig> for edr_provider::StatePruningConfig {
+    type Error = napi::Error;
+
+    fn try_from(value: ArchiveStatePruningConfig) -> Result<Self, Self::Error> {
+        match value.type_ {
+            StatePruningType::Archive => Ok(Self::Archive),
+            // A prune configuration without `retainedBlocks`
+            StatePruningType::Prune => Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                "retainedBlocks is required when pruning states".to_string(),
+            )),
         }
     }
 }
@@ -354,6 +847,15 @@ impl From<MineOrdering> for edr_block_miner::MineOrdering {
     }
 }
 
+impl From<TimeMode> for edr_provider::time::TimeMode {
+    fn from(value: TimeMode) -> Self {
+        match value {
+            TimeMode::Drift => Self::Drift,
+            TimeMode::Frozen => Self::Frozen,
+        }
+    }
+}
+
 impl TryFrom<MiningConfig> for edr_provider::MiningConfig {
     type Error = napi::Error;
 
@@ -532,6 +1034,42 @@ impl ObservabilityConfig {
     }
 }
 
+/// Parses a secret key from a [`JsString`], without leaking its contents to
+/// error messages.
+fn secret_key_from_js_string(secret_key: JsString) -> napi::Result<SecretKey> {
+    // This is the only place in production code where it's allowed to use
+    // `DangerousSecretKeyStr`.
+    #[allow(deprecated)]
+    use edr_signer::DangerousSecretKeyStr;
+
+    static_assertions::assert_not_impl_all!(JsString: Debug, Display, serde::Serialize);
+    static_assertions::assert_not_impl_all!(JsStringUtf8: Debug, Display, serde::Serialize);
+    // `SecretKey` has `Debug` implementation, but it's opaque (only shows the
+    // type name)
+    static_assertions::assert_not_impl_any!(SecretKey: Display, serde::Serialize);
+
+    let secret_key = secret_key.into_utf8()?;
+    // This is the only place in production code where it's allowed to use
+    // `DangerousSecretKeyStr`.
+    #[allow(deprecated)]
+    let secret_key_str = DangerousSecretKeyStr(secret_key.as_str()?);
+    let secret_key: SecretKey = secret_key_from_str(secret_key_str)
+        .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error))?;
+
+    Ok(secret_key)
+}
+
+/// Sets the balance of the secret key's account in the genesis state,
+/// preserving any other overrides of the account.
+fn set_genesis_balance(
+    genesis_state: &mut HashMap<edr_primitives::Address, edr_provider::AccountOverride>,
+    secret_key: &SecretKey,
+    balance: U256,
+) {
+    let address = public_key_to_address(secret_key.public_key());
+    genesis_state.entry(address).or_default().balance = Some(balance);
+}
+
 impl ProviderConfig {
     /// Resolves the instance to a [`edr_napi_core::provider::Config`].
     pub fn resolve(
@@ -539,31 +1077,10 @@ impl ProviderConfig {
         env: &napi::Env,
         runtime: runtime::Handle,
     ) -> napi::Result<edr_napi_core::provider::Config> {
-        let owned_accounts = self
+        let mut owned_accounts = self
             .owned_accounts
             .into_iter()
-            .map(|secret_key| {
-                // This is the only place in production code where it's allowed to use
-                // `DangerousSecretKeyStr`.
-                #[allow(deprecated)]
-                use edr_signer::DangerousSecretKeyStr;
-
-                static_assertions::assert_not_impl_all!(JsString: Debug, Display, serde::Serialize);
-                static_assertions::assert_not_impl_all!(JsStringUtf8: Debug, Display, serde::Serialize);
-                // `SecretKey` has `Debug` implementation, but it's opaque (only shows the
-                // type name)
-                static_assertions::assert_not_impl_any!(SecretKey: Display, serde::Serialize);
-
-                let secret_key = secret_key.into_utf8()?;
-                // This is the only place in production code where it's allowed to use
-                // `DangerousSecretKeyStr`.
-                #[allow(deprecated)]
-                let secret_key_str = DangerousSecretKeyStr(secret_key.as_str()?);
-                let secret_key: SecretKey = secret_key_from_str(secret_key_str)
-                    .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error))?;
-
-                Ok(secret_key)
-            })
+            .map(secret_key_from_js_string)
             .collect::<napi::Result<Vec<_>>>()?;
 
         let base_fee_params: Option<Vec<(BaseFeeActivation<String>, ConstantBaseFeeParams)>> = self
@@ -571,6 +1088,21 @@ impl ProviderConfig {
             .map(|vec| vec.into_iter().map(TryInto::try_into).collect())
             .transpose()?;
 
+        let geth_genesis = self
+            .genesis_json
+            .map(|json| {
+                if self.fork.is_some() {
+                    return Err(napi::Error::new(
+                        napi::Status::InvalidArg,
+                        "A genesis JSON can't be used when forking",
+                    ));
+                }
+
+                GethGenesis::from_json(&json)
+                    .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error.to_string()))
+            })
+            .transpose()?;
+
         let block_gas_limit =
             NonZeroU64::new(self.block_gas_limit.try_cast()?).ok_or_else(|| {
                 napi::Error::new(
@@ -579,30 +1111,149 @@ impl ProviderConfig {
                 )
             })?;
 
-        let genesis_state = self
+        let block_gas_limit_schedule = self
+            .block_gas_limit_schedule
+            .unwrap_or_default()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<napi::Result<_>>()?;
+
+        let mut genesis_state = self
             .genesis_state
             .into_iter()
             .map(TryInto::try_into)
-            .collect::<napi::Result<HashMap<edr_primitives::Address, edr_provider::AccountOverride>>>()?;
+            .collect::<napi::Result<
+            HashMap<edr_primitives::Address, edr_provider::AccountOverride>,
+        >>()?;
+
+        for MnemonicAccountsConfig {
+            mnemonic,
+            passphrase,
+            path,
+            initial_index,
+            count,
+            balance,
+        } in self.mnemonic_accounts.unwrap_or_default()
+        {
+            let mnemonic = mnemonic.into_utf8()?;
+            let passphrase = passphrase.map(JsString::into_utf8).transpose()?;
+
+            let secret_keys = secret_keys_from_mnemonic(
+                mnemonic.as_str()?,
+                passphrase
+                    .as_ref()
+                    .map(JsStringUtf8::as_str)
+                    .transpose()?
+                    .unwrap_or_default(),
+                path.as_deref().unwrap_or(DEFAULT_DERIVATION_PATH),
+                initial_index.unwrap_or(0),
+                count,
+            )
+            .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error))?;
+
+            let balance: Option<U256> = balance.map(TryCast::try_cast).transpose()?;
+            for secret_key in secret_keys {
+                if let Some(balance) = balance {
+                    set_genesis_balance(&mut genesis_state, &secret_key, balance);
+                }
+
+                owned_accounts.push(secret_key);
+            }
+        }
+
+        for ImportedAccountConfig {
+            secret_key,
+            balance,
+        } in self.imported_accounts.unwrap_or_default()
+        {
+            let secret_key = secret_key_from_js_string(secret_key)?;
+
+            if let Some(balance) = balance {
+                set_genesis_balance(&mut genesis_state, &secret_key, balance.try_cast()?);
+            }
 
-        let precompile_overrides = self
-            .precompile_overrides
+            owned_accounts.push(secret_key);
+        }
+
+        let hardfork_activations = self
+            .hardfork_activations
+            .unwrap_or_default()
             .into_iter()
-            .map(|precompile| precompile.to_tuple())
-            .collect();
+            .map(
+                |HardforkActivation {
+                     condition,
+                     hardfork,
+                 }| {
+                    let block_number = match condition {
+                        Either::A(HardforkActivationByBlockNumber { block_number }) => {
+                            block_number.try_cast()?
+                        }
+                        Either::B(HardforkActivationByTimestamp { .. }) => {
+                            return Err(napi::Error::new(
+                                napi::Status::InvalidArg,
+                                "Hardfork activations by timestamp are not supported for a local blockchain",
+                            ));
+                        }
+                    };
+
+                    Ok((block_number, hardfork))
+                },
+            )
+            .collect::<napi::Result<_>>()?;
+
+        let mut precompile_overrides = HashMap::default();
+        for precompile in self.precompile_overrides {
+            match precompile.to_tuple() {
+                (address, PrecompileImplementation::Native(precompile)) => {
+                    precompile_overrides.insert(address, precompile);
+                }
+                (address, PrecompileImplementation::Bytecode(bytecode)) => {
+                    if edr_precompile::Precompiles::latest().contains(&address) {
+                        return Err(napi::Error::new(
+                            napi::Status::InvalidArg,
+                            format!(
+                                "Cannot deploy a precompile stand-in at {address}, as it's the address of a built-in precompile"
+                            ),
+                        ));
+                    }
+
+                    genesis_state.entry(address).or_default().code = Some(bytecode);
+                }
+            }
+        }
 
         Ok(edr_napi_core::provider::Config {
             allow_blocks_with_same_timestamp: self.allow_blocks_with_same_timestamp,
             allow_unlimited_contract_size: self.allow_unlimited_contract_size,
+            allow_unlimited_init_code_size: self.allow_unlimited_init_code_size.unwrap_or(false),
+            auto_fund: self.auto_fund.unwrap_or(false),
+            auto_impersonate: self.auto_impersonate.unwrap_or(false),
             bail_on_call_failure: self.bail_on_call_failure,
             bail_on_transaction_failure: self.bail_on_transaction_failure,
             base_fee_params,
             block_gas_limit,
+            block_gas_limit_schedule,
             chain_id: self.chain_id.try_cast()?,
+            checkpoint_dir: PathBuf::from(
+                self.checkpoint_dir
+                    .unwrap_or(edr_defaults::CHECKPOINT_DIR.to_owned()),
+            ),
             coinbase: self.coinbase.try_cast()?,
+            deploy_deterministic_deployment_proxy: self
+                .deploy_deterministic_deployment_proxy
+                .unwrap_or(false),
+            disable_balance_check: self.disable_balance_check.unwrap_or(false),
+            disable_base_fee_adjustment: self.disable_base_fee_adjustment.unwrap_or(false),
+            disable_base_fee_check: self.disable_base_fee_check.unwrap_or(false),
+            disable_priority_fee_check: self.disable_priority_fee_check.unwrap_or(false),
             fork: self.fork.map(TryInto::try_into).transpose()?,
+            gas_sponsor: self.gas_sponsor.map(TryInto::try_into).transpose()?,
             genesis_state,
+            geth_genesis,
             hardfork: self.hardfork,
+            hardfork_activations,
+            index_address_transactions: self.index_address_transactions.unwrap_or(false),
+            index_token_transfers: self.index_token_transfers.unwrap_or(false),
             initial_base_fee_per_gas: self
                 .initial_base_fee_per_gas
                 .map(TryCast::try_cast)
@@ -619,12 +1270,40 @@ impl ProviderConfig {
                 .initial_parent_beacon_block_root
                 .map(TryCast::try_cast)
                 .transpose()?,
+            max_cached_states: self
+                .max_cached_states
+                .map(|max_cached_states| {
+                    NonZeroUsize::new(max_cached_states as usize).ok_or_else(|| {
+                        napi::Error::new(
+                            napi::Status::InvalidArg,
+                            "maxCachedStates must be greater than zero".to_string(),
+                        )
+                    })
+                })
+                .transpose()?,
+            max_logs_block_range: self
+                .max_logs_block_range
+                .map(TryCast::try_cast)
+                .transpose()?,
+            max_logs_results: self.max_logs_results.map(TryCast::try_cast).transpose()?,
+            method_permissions: self.method_permissions.map(Into::into).unwrap_or_default(),
             mining: self.mining.try_into()?,
             min_gas_price: self.min_gas_price.try_cast()?,
             network_id: self.network_id.try_cast()?,
             observability: self.observability.resolve(env, runtime)?,
             owned_accounts,
             precompile_overrides,
+            prev_randao_seed: self.prev_randao_seed,
+            record_state_diffs: self.record_state_diffs.unwrap_or(false),
+            state_pruning: self
+                .state_pruning
+                .map(|state_pruning| match state_pruning {
+                    Either::A(prune) => prune.try_into(),
+                    Either::B(archive) => archive.try_into(),
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            time_mode: self.time_mode.map(Into::into).unwrap_or_default(),
             transaction_gas_cap: self
                 .transaction_gas_cap
                 .map(TryCast::try_cast)
@@ -15,11 +15,12 @@ use napi::{
     Env, JsFunction, JsObject,
 };
 use napi_derive::napi;
-use tracing_subscriber::{prelude::*, EnvFilter, Registry};
+use tracing_subscriber::{prelude::*, Registry};
 
 use crate::{
     config::{resolve_configs, ConfigResolution, ProviderConfig, TracingConfigWithBuffers},
     contract_decoder::ContractDecoder,
+    diagnostics::{reloadable_filter, DiagnosticsConfig, JsonLogLayer},
     logger::LoggerConfig,
     provider::{Provider, ProviderFactory},
     solidity_tests::{
@@ -41,8 +42,8 @@ pub struct EdrContext {
 impl EdrContext {
     /// Creates a new [`EdrContext`] instance. Should only be called once!
     #[napi(catch_unwind, constructor)]
-    pub fn new() -> napi::Result<Self> {
-        let context = Context::new()?;
+    pub fn new(diagnostics_config: Option<DiagnosticsConfig>) -> napi::Result<Self> {
+        let context = Context::new(diagnostics_config)?;
 
         Ok(Self {
             inner: Arc::new(AsyncMutex::new(context)),
@@ -96,6 +97,14 @@ impl EdrContext {
                 logger_config.enable,
             )));
 
+        #[cfg(feature = "scenarios")]
+        let journal_file =
+            try_or_reject_promise!(runtime.clone().block_on(crate::scenarios::journal_file(
+                chain_type.clone(),
+                provider_config.clone(),
+                logger_config.enable,
+            )));
+
         let factory = {
             // TODO: https://github.com/NomicFoundation/edr/issues/760
             // TODO: Don't block the JS event loop
@@ -121,6 +130,8 @@ impl EdrContext {
                         contract_decoder,
                         #[cfg(feature = "scenarios")]
                         scenario_file,
+                        #[cfg(feature = "scenarios")]
+                        journal_file,
                     )
                 });
 
@@ -354,14 +365,28 @@ pub struct Context {
 
 impl Context {
     /// Creates a new [`Context`] instance. Should only be called once!
-    pub fn new() -> napi::Result<Self> {
+    pub fn new(diagnostics_config: Option<DiagnosticsConfig>) -> napi::Result<Self> {
+        let DiagnosticsConfig {
+            filter,
+            json_log_file,
+        } = diagnostics_config.unwrap_or(DiagnosticsConfig {
+            filter: None,
+            json_log_file: None,
+        });
+
+        let json_layer = json_log_file
+            .as_deref()
+            .map(JsonLogLayer::with_file)
+            .transpose()?;
+
         let fmt_layer = tracing_subscriber::fmt::layer()
             .with_file(true)
             .with_line_number(true)
             .with_thread_ids(true)
             .with_target(false)
             .with_level(true)
-            .with_filter(EnvFilter::from_default_env());
+            .and_then(json_layer)
+            .with_filter(reloadable_filter(filter.as_deref())?);
 
         let subscriber = Registry::default().with(fmt_layer);
 
@@ -0,0 +1,240 @@
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    io::Write as _,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use napi_derive::napi;
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
+use tracing_subscriber::{layer, registry::LookupSpan, reload, EnvFilter, Layer};
+
+/// Configuration of the diagnostic logs that are emitted by EDR.
+#[napi(object)]
+pub struct DiagnosticsConfig {
+    /// The filter that selects which diagnostic logs are emitted, using the
+    /// syntax of `RUST_LOG` directives, e.g.
+    /// `edr_provider=debug,edr_rpc_client=trace`. Defaults to the value of
+    /// the `RUST_LOG` environment variable.
+    ///
+    /// The filter can be changed at runtime using `hardhat_setLogFilter`.
+    pub filter: Option<String>,
+    /// The path of a file to which diagnostic logs are appended, in addition to
+    /// being printed. Each line of the file contains a single JSON object.
+    pub json_log_file: Option<String>,
+}
+
+/// Creates the filter of the diagnostic logs and registers a handler with the
+/// provider to allow changing it at runtime.
+pub fn reloadable_filter<SubscriberT: Subscriber>(
+    filter: Option<&str>,
+) -> napi::Result<reload::Layer<EnvFilter, SubscriberT>> {
+    let filter = match filter {
+        Some(filter) => EnvFilter::try_new(filter).map_err(|error| {
+            napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("Invalid log filter '{filter}': {error}"),
+            )
+        })?,
+        None => EnvFilter::from_default_env(),
+    };
+
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let handler = Box::new(move |filter: &str| {
+        let filter = EnvFilter::try_new(filter).map_err(|error| error.to_string())?;
+        handle.reload(filter).map_err(|error| error.to_string())
+    });
+
+    if edr_provider::log_filter::set_log_filter_handler(handler).is_err() {
+        println!(
+            "Failed to register log filter handler. Please only initialize EdrContext once per process to avoid this error."
+        );
+    }
+
+    Ok(filter)
+}
+
+/// A layer that appends each event to a file as a JSON object, including the
+/// fields of the spans in which the event occurred.
+pub struct JsonLogLayer {
+    file: Mutex<File>,
+}
+
+impl JsonLogLayer {
+    /// Creates a new instance that appends to the file at the provided path.
+    pub fn with_file(path: &str) -> napi::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| {
+                napi::Error::new(
+                    napi::Status::GenericFailure,
+                    format!("Failed to open JSON log file '{path}' with error: {error}"),
+                )
+            })?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl<SubscriberT> Layer<SubscriberT> for JsonLogLayer
+where
+    SubscriberT: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(
+        &self,
+        attributes: &span::Attributes<'_>,
+        id: &span::Id,
+        context: layer::Context<'_, SubscriberT>,
+    ) {
+        let mut fields = JsonFields::default();
+        attributes.record(&mut fields);
+
+        if let Some(span) = context.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(
+        &self,
+        id: &span::Id,
+        values: &span::Record<'_>,
+        context: layer::Context<'_, SubscriberT>,
+    ) {
+        if let Some(span) = context.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<JsonFields>() {
+                values.record(fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, context: layer::Context<'_, SubscriberT>) {
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+
+        let spans = context
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| {
+                        let mut object = Map::new();
+                        object.insert("name".to_owned(), span.name().into());
+                        if let Some(fields) = span.extensions().get::<JsonFields>() {
+                            object.extend(fields.0.clone());
+                        }
+
+                        Value::Object(object)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64());
+
+        let metadata = event.metadata();
+        let line = serde_json::json!({
+            "timestamp": timestamp,
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": fields.0,
+            "spans": spans,
+        });
+
+        // Logging is best-effort, so write errors are ignored
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+/// The fields of an event or span, encoded as JSON values.
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl JsonFields {
+    fn insert(&mut self, field: &Field, value: Value) {
+        self.0.insert(field.name().to_owned(), value);
+    }
+}
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.insert(field, value.to_string().into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::{prelude::*, Registry};
+
+    use super::*;
+
+    #[test]
+    fn json_log_layer_writes_events_with_spans() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("logs.json");
+        let path = path.to_str().expect("Path should be valid UTF-8");
+
+        let layer = JsonLogLayer::with_file(path)?;
+        let subscriber = Registry::default().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::debug_span!("request", method = "eth_call");
+            let _entered = span.enter();
+
+            tracing::debug!(transactions = 2u64, is_mined = true, "Mined block");
+        });
+
+        let contents = std::fs::read_to_string(path)?;
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+
+        let line: Value = serde_json::from_str(lines[0])?;
+        assert_eq!(line["level"], "DEBUG");
+        assert_eq!(line["fields"]["message"], "Mined block");
+        assert_eq!(line["fields"]["transactions"], 2);
+        assert_eq!(line["fields"]["is_mined"], true);
+        assert_eq!(
+            line["spans"],
+            serde_json::json!([{ "name": "request", "method": "eth_call" }])
+        );
+
+        Ok(())
+    }
+}
@@ -20,13 +20,19 @@ pub mod context;
 /// Types for decoding smart contract data.
 pub mod contract_decoder;
 mod debug_trace;
+/// Types for configuring EDR's diagnostic logs.
+pub mod diagnostics;
 pub mod gas_report;
 /// Types and functions related to code coverage instrumentation.
 pub mod instrument;
+/// Types for source-map-based line and branch coverage.
+pub mod line_coverage;
 /// Types for EVM execution logs.
 pub mod log;
 /// Types for an RPC request logger.
 pub mod logger;
+/// Types for overriding the order in which transactions are mined.
+pub mod mine_ordering;
 /// Types for mocking provider behavior.
 #[cfg(feature = "test-mock")]
 pub mod mock;
@@ -46,5 +52,7 @@ pub mod solidity_tests;
 pub mod subscription;
 /// Types for EVM traces.
 pub mod trace;
+/// Types for deciding whether transactions are added to the mem pool.
+pub mod transaction_firewall;
 /// Types related to Ethereum withdrawals.
 pub mod withdrawal;
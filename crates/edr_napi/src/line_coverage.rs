@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+
+use edr_chain_spec::EvmHaltReason;
+use edr_solidity::contract_decoder::{ContractDecoder, NestedTraceDecoder as _};
+use edr_tracing::Trace;
+use napi::bindgen_prelude::BigInt;
+use napi_derive::napi;
+
+/// Line and branch coverage of a single source file.
+#[napi(object)]
+pub struct SourceLineCoverage {
+    /// The source name of the file, e.g. `contracts/Counter.sol`.
+    pub source_name: String,
+    /// The executed lines of the file.
+    pub lines: Vec<LineHits>,
+    /// The conditional jumps of the file.
+    pub branches: Vec<BranchHits>,
+}
+
+/// The number of times a line was executed.
+#[napi(object)]
+pub struct LineHits {
+    /// The 1-based line number.
+    pub line: u32,
+    /// The number of times the line was executed.
+    pub hits: BigInt,
+}
+
+/// The number of times a conditional jump was taken or not.
+#[napi(object)]
+pub struct BranchHits {
+    /// The 1-based line number of the branch.
+    pub line: u32,
+    /// The byte offset of the branch's source location.
+    pub offset: u32,
+    /// The number of times the jump was taken.
+    pub taken: BigInt,
+    /// The number of times the jump was not taken.
+    pub not_taken: BigInt,
+}
+
+impl From<(String, edr_solidity::line_coverage::FileCoverage)> for SourceLineCoverage {
+    fn from((source_name, coverage): (String, edr_solidity::line_coverage::FileCoverage)) -> Self {
+        Self {
+            source_name,
+            lines: coverage
+                .lines
+                .into_iter()
+                .map(|(line, hits)| LineHits {
+                    line,
+                    hits: BigInt::from(hits),
+                })
+                .collect(),
+            branches: coverage
+                .branches
+                .into_iter()
+                .map(|(offset, branch)| BranchHits {
+                    line: branch.line,
+                    offset,
+                    taken: BigInt::from(branch.taken),
+                    not_taken: BigInt::from(branch.not_taken),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Shared line coverage state of a provider. `None` if line coverage
+/// collection is disabled.
+pub(crate) type SharedLineCoverage = Arc<Mutex<Option<edr_solidity::line_coverage::LineCoverage>>>;
+
+/// Records the line coverage of the provided traces, if line coverage
+/// collection is enabled.
+///
+/// Coverage is best-effort, so failures are logged instead of failing the
+/// request that produced the traces.
+pub(crate) fn record_line_coverage(
+    line_coverage: &SharedLineCoverage,
+    contract_decoder: &ContractDecoder,
+    traces: &[Arc<Trace<EvmHaltReason>>],
+) {
+    let Ok(mut line_coverage) = line_coverage.lock() else {
+        tracing::warn!("Failed to record line coverage, as its lock was poisoned");
+        return;
+    };
+
+    let Some(line_coverage) = line_coverage.as_mut() else {
+        return;
+    };
+
+    for trace in traces {
+        let nested_trace = match edr_solidity::nested_tracer::convert_trace_messages_to_nested_trace(
+            trace.as_ref().clone(),
+        ) {
+            Ok(Some(nested_trace)) => nested_trace,
+            Ok(None) => continue,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to record line coverage of trace");
+                continue;
+            }
+        };
+
+        match contract_decoder.try_to_decode_nested_trace(nested_trace) {
+            Ok(decoded_trace) => line_coverage.record_trace(&decoded_trace),
+            Err(error) => tracing::warn!(%error, "Failed to record line coverage of trace"),
+        }
+    }
+}
@@ -16,6 +16,12 @@ pub struct LoggerConfig {
     pub enable: bool,
     #[napi(ts_type = "(inputs: ArrayBuffer[]) => string[]")]
     pub decode_console_log_inputs_callback: JsFunction,
+    /// Optional callback that is called with the ABI-encoded inputs of the
+    /// `console.log` calls made during each execution, including `eth_call`,
+    /// `eth_estimateGas` and failed transactions. It's called regardless of
+    /// whether the logger is enabled.
+    #[napi(ts_type = "(inputs: ArrayBuffer[]) => void")]
+    pub console_log_callback: Option<JsFunction>,
     #[napi(ts_type = "(message: string, replace: boolean) => void")]
     pub print_line_callback: JsFunction,
 }
@@ -70,6 +76,47 @@ impl LoggerConfig {
                 .expect("Receive can only fail if the channel is closed")
         });
 
+        let console_log_fn = self
+            .console_log_callback
+            .map(|console_log_callback| {
+                let mut console_log_callback: ThreadsafeFunction<_, ErrorStrategy::Fatal> =
+                    console_log_callback.create_threadsafe_function(
+                        0,
+                        |ctx: ThreadSafeCallContext<Vec<Bytes>>| {
+                            let inputs = ctx
+                                .env
+                                .create_array_with_length(ctx.value.len())
+                                .and_then(|mut inputs| {
+                                    for (idx, input) in ctx.value.into_iter().enumerate() {
+                                        ctx.env
+                                            .create_arraybuffer_with_data(input.to_vec())
+                                            .and_then(|input| {
+                                                inputs.set_element(idx as u32, input.into_raw())
+                                            })?;
+                                    }
+
+                                    Ok(inputs)
+                                })?;
+
+                            Ok(vec![inputs])
+                        },
+                    )?;
+
+                // Maintain a weak reference to the function to avoid blocking the event loop
+                // from exiting.
+                console_log_callback.unref(env)?;
+
+                let console_log_fn: Arc<dyn edr_napi_core::logger::ConsoleLogFn> =
+                    Arc::new(move |console_log_inputs| {
+                        let status = console_log_callback
+                            .call(console_log_inputs, ThreadsafeFunctionCallMode::NonBlocking);
+                        debug_assert_eq!(status, Status::Ok);
+                    });
+
+                Ok::<_, napi::Error>(console_log_fn)
+            })
+            .transpose()?;
+
         let mut print_line_callback: ThreadsafeFunction<_, ErrorStrategy::Fatal> = self
             .print_line_callback
             .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(String, bool)>| {
@@ -114,6 +161,7 @@ impl LoggerConfig {
         Ok(edr_napi_core::logger::Config {
             enable: self.enable,
             decode_console_log_inputs_fn,
+            console_log_fn,
             print_line_fn,
         })
     }
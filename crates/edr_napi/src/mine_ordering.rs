@@ -0,0 +1,131 @@
+use std::sync::mpsc::channel;
+
+use edr_primitives::B256;
+use edr_provider::MineOrderingCallbackError;
+use napi::{
+    bindgen_prelude::{BigInt, Promise, Uint8Array},
+    threadsafe_function::{
+        ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+    },
+    tokio::runtime,
+    Env, JsFunction, Status,
+};
+use napi_derive::napi;
+
+/// Summary of a pending transaction, provided to a mine ordering callback.
+#[napi(object)]
+pub struct PendingTransactionSummary {
+    /// The transaction's hash
+    pub transaction_hash: Uint8Array,
+    /// The transaction's sender
+    pub caller: Uint8Array,
+    /// The transaction's nonce
+    pub nonce: BigInt,
+    /// The transaction's gas price or, for EIP-1559 transactions, max fee per
+    /// gas
+    pub gas_price: BigInt,
+    /// The transaction's max priority fee per gas, if it's an EIP-1559
+    /// transaction
+    pub max_priority_fee_per_gas: Option<BigInt>,
+    /// The order in which the transaction was added to the mem pool
+    pub order_id: BigInt,
+}
+
+impl From<&edr_provider::PendingTransactionSummary> for PendingTransactionSummary {
+    fn from(value: &edr_provider::PendingTransactionSummary) -> Self {
+        Self {
+            transaction_hash: Uint8Array::with_data_copied(value.transaction_hash),
+            caller: Uint8Array::with_data_copied(value.caller),
+            nonce: BigInt::from(value.nonce),
+            gas_price: BigInt::from(value.gas_price),
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas.map(BigInt::from),
+            order_id: BigInt::from(value.order_id as u64),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MineOrderingCallback {
+    mine_ordering_callback_fn:
+        ThreadsafeFunction<Vec<PendingTransactionSummary>, ErrorStrategy::Fatal>,
+    runtime: runtime::Handle,
+}
+
+impl MineOrderingCallback {
+    pub fn new(
+        env: &Env,
+        mine_ordering_callback: JsFunction,
+        runtime: runtime::Handle,
+    ) -> napi::Result<Self> {
+        let mut mine_ordering_callback_fn = mine_ordering_callback.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<Vec<PendingTransactionSummary>>| Ok(vec![ctx.value]),
+        )?;
+
+        // Maintain a weak reference to the function to avoid blocking the event loop
+        // from exiting.
+        mine_ordering_callback_fn.unref(env)?;
+
+        Ok(Self {
+            mine_ordering_callback_fn,
+            runtime,
+        })
+    }
+
+    pub fn order(
+        &self,
+        transactions: &[edr_provider::PendingTransactionSummary],
+    ) -> Result<Vec<B256>, MineOrderingCallbackError> {
+        let (sender, receiver) = channel();
+
+        let runtime = self.runtime.clone();
+        let status = self.mine_ordering_callback_fn.call_with_return_value(
+            transactions
+                .iter()
+                .map(PendingTransactionSummary::from)
+                .collect(),
+            ThreadsafeFunctionCallMode::Blocking,
+            move |result: Promise<Vec<Uint8Array>>| {
+                runtime.spawn(async move {
+                    let result = result.await.and_then(|transaction_hashes| {
+                        transaction_hashes
+                            .into_iter()
+                            .map(|transaction_hash| {
+                                B256::try_from(transaction_hash.as_ref()).map_err(|_error| {
+                                    napi::Error::new(
+                                        Status::InvalidArg,
+                                        "Transaction hashes must be 32 bytes long",
+                                    )
+                                })
+                            })
+                            .collect::<napi::Result<Vec<_>>>()
+                    });
+
+                    sender.send(result).map_err(|_error| {
+                        napi::Error::new(
+                            Status::GenericFailure,
+                            "Failed to send result from mine_ordering_callback",
+                        )
+                    })
+                });
+                Ok(())
+            },
+        );
+
+        if status != Status::Ok {
+            return Err(MineOrderingCallbackError(format!(
+                "Failed to call mine_ordering_callback: {status:?}"
+            )));
+        }
+
+        // The sender is dropped without a result if the callback throws
+        receiver
+            .recv()
+            .map_err(|_error| {
+                MineOrderingCallbackError(
+                    "mine_ordering_callback didn't return a result".to_string(),
+                )
+            })?
+            .map_err(|error| MineOrderingCallbackError(error.reason))
+    }
+}
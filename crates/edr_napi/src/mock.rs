@@ -40,12 +40,39 @@ impl SyncProvider for MockProvider {
             .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))
     }
 
+    fn handle_streamed_trace_request(
+        &self,
+        _request: String,
+        _struct_log_stream: edr_provider::StructLogStream,
+    ) -> napi::Result<String> {
+        Err(napi::Error::new(
+            napi::Status::GenericFailure,
+            "Mock providers do not support streamed traces",
+        ))
+    }
+
+    fn metrics(&self) -> edr_provider::ProviderMetrics {
+        edr_provider::ProviderMetrics::default()
+    }
+
     fn set_call_override_callback(
         &self,
         _call_override_callback: Arc<dyn edr_provider::SyncCallOverride>,
     ) {
     }
 
+    fn set_mine_ordering_callback(
+        &self,
+        _mine_ordering_callback: Option<Arc<dyn edr_provider::SyncMineOrderingCallback>>,
+    ) {
+    }
+
+    fn set_transaction_firewall(
+        &self,
+        _transaction_firewall: Option<Arc<dyn edr_provider::SyncTransactionFirewall>>,
+    ) {
+    }
+
     fn set_verbose_tracing(&self, _enabled: bool) {}
 }
 
@@ -64,6 +91,8 @@ impl EdrContext {
             Arc::new(ContractDecoder::default()),
             #[cfg(feature = "scenarios")]
             None,
+            #[cfg(feature = "scenarios")]
+            None,
         );
 
         Ok(provider)
@@ -123,6 +123,8 @@ pub fn create_provider_with_mock_timer(
                 contract_decoder,
                 #[cfg(feature = "scenarios")]
                 None,
+                #[cfg(feature = "scenarios")]
+                None,
             ))
         };
 
@@ -1,35 +1,43 @@
-use edr_precompile::PrecompileFn;
-use edr_primitives::Address;
+use edr_precompile::{CustomPrecompile, PrecompileFn};
+use edr_primitives::{Address, Bytecode};
 use napi::bindgen_prelude::Uint8Array;
 use napi_derive::napi;
 
+use crate::cast::TryCast as _;
+
 #[napi]
 #[derive(Clone)]
 pub struct Precompile {
     address: Address,
-    precompile_fn: PrecompileFn,
+    implementation: PrecompileImplementation,
+}
+
+/// The implementation of a [`Precompile`].
+#[derive(Clone, Debug)]
+pub enum PrecompileImplementation {
+    /// A native precompile.
+    Native(CustomPrecompile),
+    /// A stand-in contract that is deployed at the precompile's address.
+    Bytecode(Bytecode),
 }
 
 impl Precompile {
     pub fn new(address: Address, precompile_fn: PrecompileFn) -> Self {
         Self {
             address,
-            precompile_fn,
+            implementation: PrecompileImplementation::Native(CustomPrecompile::from(precompile_fn)),
         }
     }
 
-    /// Returns the address and precompile function as a tuple.
-    pub fn to_tuple(&self) -> (Address, PrecompileFn) {
-        (self.address, self.precompile_fn)
+    /// Returns the address and implementation as a tuple.
+    pub fn to_tuple(&self) -> (Address, PrecompileImplementation) {
+        (self.address, self.implementation.clone())
     }
 }
 
 impl From<edr_precompile::Precompile> for Precompile {
     fn from(value: edr_precompile::Precompile) -> Self {
-        Self {
-            address: *value.address(),
-            precompile_fn: value.into_precompile(),
-        }
+        Self::new(*value.address(), value.into_precompile())
     }
 }
 
@@ -40,6 +48,33 @@ impl Precompile {
     pub fn address(&self) -> Uint8Array {
         Uint8Array::with_data_copied(self.address)
     }
+
+    /// Returns a copy of the precompile that is located at the provided
+    /// address instead. This allows chain-specific precompiles to be
+    /// registered at a custom address.
+    #[napi(catch_unwind)]
+    pub fn with_address(&self, address: Uint8Array) -> napi::Result<Precompile> {
+        Ok(Self {
+            address: address.try_cast()?,
+            implementation: self.implementation.clone(),
+        })
+    }
+}
+
+/// Creates a stand-in for a precompile that executes the provided bytecode at
+/// the provided address. This allows precompiles that EDR doesn't implement
+/// natively to be emulated by a contract.
+///
+/// The address cannot be the address of a built-in precompile.
+#[napi(catch_unwind)]
+pub fn precompile_from_bytecode(
+    address: Uint8Array,
+    bytecode: Uint8Array,
+) -> napi::Result<Precompile> {
+    Ok(Precompile {
+        address: address.try_cast()?,
+        implementation: PrecompileImplementation::Bytecode(bytecode.try_cast()?),
+    })
 }
 
 /// [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md#specification)
@@ -1,26 +1,55 @@
 /// Types related to provider factories.
 pub mod factory;
+mod flood;
+mod metrics;
 mod response;
+mod struct_log_stream;
 
-use std::sync::Arc;
+use std::sync::{mpsc as std_mpsc, Arc};
 
 use edr_napi_core::provider::SyncProvider;
 use edr_solidity::compiler::create_models_and_decode_bytecodes;
-use napi::{tokio::runtime, Env, JsFunction, JsObject, Status};
+use napi::{
+    tokio::{
+        runtime,
+        sync::mpsc::{self, error::TrySendError},
+    },
+    Env, JsFunction, JsObject, Status,
+};
 use napi_derive::napi;
 
-pub use self::factory::ProviderFactory;
-use self::response::Response;
-use crate::{call_override::CallOverrideCallback, contract_decoder::ContractDecoder};
+pub use self::{
+    factory::ProviderFactory,
+    flood::{FloodOptions, FloodResult},
+    metrics::ProviderMetrics,
+    struct_log_stream::StructLogStream,
+};
+use self::{response::Response, struct_log_stream::encode_struct_logs};
+use crate::{
+    call_override::CallOverrideCallback,
+    contract_decoder::ContractDecoder,
+    line_coverage::{record_line_coverage, SharedLineCoverage, SourceLineCoverage},
+    mine_ordering::MineOrderingCallback,
+    transaction_firewall::TransactionFirewallCallback,
+};
+
+/// The maximum number of struct logs per chunk of a [`StructLogStream`].
+const STRUCT_LOG_CHUNK_SIZE: usize = 1_000;
+/// The maximum number of chunks of a [`StructLogStream`] that can be queued for
+/// the consumer. Additional chunks are buffered until tracing has finished.
+const STRUCT_LOG_STREAM_CAPACITY: usize = 16;
 
 /// A JSON-RPC provider for Ethereum.
 #[napi]
 pub struct Provider {
     contract_decoder: Arc<edr_solidity::contract_decoder::ContractDecoder>,
+    line_coverage: SharedLineCoverage,
     provider: Arc<dyn SyncProvider>,
     runtime: runtime::Handle,
     #[cfg(feature = "scenarios")]
     scenario_file: Option<napi::tokio::sync::Mutex<napi::tokio::fs::File>>,
+    #[cfg(feature = "scenarios")]
+    journal_file: Option<napi::tokio::sync::Mutex<napi::tokio::fs::File>>,
 }
 
 impl Provider {
@@ -32,13 +61,19 @@ impl Provider {
         #[cfg(feature = "scenarios")] scenario_file: Option<
             napi::tokio::sync::Mutex<napi::tokio::fs::File>,
         >,
+        #[cfg(feature = "scenarios")] journal_file: Option<
+            napi::tokio::sync::Mutex<napi::tokio::fs::File>,
+        >,
     ) -> Self {
         Self {
             contract_decoder,
+            line_coverage: SharedLineCoverage::default(),
             provider,
             runtime,
             #[cfg(feature = "scenarios")]
             scenario_file,
+            #[cfg(feature = "scenarios")]
+            journal_file,
         }
     }
 }
@@ -102,13 +137,174 @@ impl Provider {
             crate::scenarios::write_request(scenario_file, &request).await?;
         }
 
+        #[cfg(feature = "scenarios")]
+        let journal_request = self
+            .journal_file
+            .as_ref()
+            .map(|_| (crate::scenarios::timestamp_millis(), request.clone()));
+
         let contract_decoder = Arc::clone(&self.contract_decoder);
+        let line_coverage = Arc::clone(&self.line_coverage);
+
+        let response = self
+            .runtime
+            .spawn_blocking(move || {
+                let response = provider.handle_request(request, Arc::clone(&contract_decoder))?;
+                record_line_coverage(&line_coverage, &contract_decoder, &response.traces);
+
+                Ok::<_, napi::Error>(response)
+            })
+            .await
+            .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))??;
+
+        #[cfg(feature = "scenarios")]
+        if let (Some(journal_file), Some((timestamp, request))) =
+            (&self.journal_file, journal_request)
+        {
+            crate::scenarios::write_journal_entry(journal_file, timestamp, request, &response.data)
+                .await?;
+        }
+
+        Ok(Response::from(response))
+    }
+
+    /// Handles a `debug_traceTransaction` JSON-RPC request, streaming its
+    /// struct logs in chunks instead of returning them in a single response.
+    ///
+    /// Recommended for transactions with large traces, as struct logs are
+    /// serialized in chunks instead of as a single response. Tracing never
+    /// waits for the consumer, as the provider is locked while tracing. If the
+    /// consumer falls behind, chunks are buffered until tracing has finished.
+    #[napi(catch_unwind)]
+    pub fn handle_streamed_trace_request(&self, request: String) -> StructLogStream {
+        let (sender, receiver) = mpsc::channel(STRUCT_LOG_STREAM_CAPACITY);
+        let result = Arc::default();
+
+        let provider = self.provider.clone();
+        let stream_result = Arc::clone(&result);
+        self.runtime.spawn_blocking(move || {
+            let (overflow_sender, overflow_receiver) = std_mpsc::channel();
+
+            let chunk_sender = sender.clone();
+            let mut is_overflowing = false;
+            let struct_log_stream =
+                edr_provider::StructLogStream::new(STRUCT_LOG_CHUNK_SIZE, move |struct_logs| {
+                    let chunk = encode_struct_logs(&struct_logs);
+
+                    // Once a chunk has overflowed, all subsequent chunks are buffered to
+                    // preserve their order.
+                    if is_overflowing {
+                        let _result = overflow_sender.send(chunk);
+                        return;
+                    }
+
+                    match chunk_sender.try_send(chunk) {
+                        Ok(()) => {}
+                        Err(TrySendError::Full(chunk)) => {
+                            is_overflowing = true;
+                            let _result = overflow_sender.send(chunk);
+                        }
+                        // The stream was dropped, so the chunk can be discarded.
+                        Err(TrySendError::Closed(_chunk)) => {}
+                    }
+                });
+
+            let response = provider.handle_streamed_trace_request(request, struct_log_stream);
+
+            // The provider is no longer locked, so it's safe to wait for the consumer to
+            // receive the buffered chunks. Sending only fails if the stream was dropped,
+            // in which case the chunks can be discarded.
+            for chunk in overflow_receiver.try_iter() {
+                if sender.blocking_send(chunk).is_err() {
+                    return;
+                }
+            }
+
+            match response {
+                Ok(response) => {
+                    if let Ok(mut result) = stream_result.lock() {
+                        *result = Some(response);
+                    }
+                }
+                Err(error) => {
+                    let _result = sender.blocking_send(Err(error));
+                }
+            }
+        });
+
+        StructLogStream::new(receiver, result)
+    }
+
+    /// Creates and mines synthetic transactions in-process, without
+    /// serializing them individually, and returns throughput statistics.
+    ///
+    /// Transactions are added to the mem pool in batches that fit in a single
+    /// block, after which the pending transactions are mined. Mined blocks are
+    /// not logged and their traces are not collected.
+    #[napi(catch_unwind)]
+    pub async fn flood(&self, options: FloodOptions) -> napi::Result<FloodResult> {
+        let options = options.try_into()?;
+        let provider = self.provider.clone();
+
+        self.runtime
+            .spawn_blocking(move || provider.flood(options))
+            .await
+            .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))?
+            .map(FloodResult::from)
+    }
+
+    /// Returns a snapshot of the provider's metrics.
+    #[napi(catch_unwind)]
+    pub async fn metrics(&self) -> napi::Result<ProviderMetrics> {
+        let provider = self.provider.clone();
 
         self.runtime
-            .spawn_blocking(move || provider.handle_request(request, contract_decoder))
+            .spawn_blocking(move || provider.metrics())
             .await
             .map_err(|error| napi::Error::new(Status::GenericFailure, error.to_string()))?
-            .map(Response::from)
+            .try_into()
+    }
+
+    /// Enables or disables the collection of line and branch coverage.
+    ///
+    /// Executed program counters are mapped to source lines using the source
+    /// maps of the contracts known to the instance's contract decoder.
+    /// Disabling collection discards any coverage that was collected so far.
+    #[napi(catch_unwind)]
+    pub fn set_line_coverage_enabled(&self, enabled: bool) -> napi::Result<()> {
+        let mut line_coverage = self
+            .line_coverage
+            .lock()
+            .map_err(|_error| napi::Error::from_reason("Line coverage lock was poisoned"))?;
+
+        if !enabled {
+            *line_coverage = None;
+        } else if line_coverage.is_none() {
+            *line_coverage = Some(edr_solidity::line_coverage::LineCoverage::default());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the line and branch coverage collected since collection was
+    /// enabled or since the last call to this method, and resets it.
+    #[napi(catch_unwind)]
+    pub fn take_line_coverage(&self) -> napi::Result<Vec<SourceLineCoverage>> {
+        let mut line_coverage = self
+            .line_coverage
+            .lock()
+            .map_err(|_error| napi::Error::from_reason("Line coverage lock was poisoned"))?;
+
+        let line_coverage = line_coverage
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default();
+
+        Ok(line_coverage
+            .into_files()
+            .into_iter()
+            .map(SourceLineCoverage::from)
+            .collect())
     }
 
     #[napi(catch_unwind, ts_return_type = "Promise<void>")]
@@ -144,6 +340,102 @@ impl Provider {
         Ok(promise)
     }
 
+    /// Sets a callback that determines the order in which pending transactions
+    /// are mined, overriding the configured mine ordering. The callback
+    /// receives the pending transactions and returns their hashes in order of
+    /// descending priority. Omitted transactions are mined last, in insertion
+    /// order. A sender's transactions are always mined in nonce order. If the
+    /// callback throws or its promise is rejected, mining fails with an error.
+    ///
+    /// Pass `undefined` to restore the configured mine ordering.
+    #[napi(catch_unwind, ts_return_type = "Promise<void>")]
+    pub fn set_mine_ordering_callback(
+        &self,
+        env: Env,
+        #[napi(
+            ts_arg_type = "((transactions: PendingTransactionSummary[]) => Promise<Uint8Array[]>) | undefined"
+        )]
+        mine_ordering_callback: Option<JsFunction>,
+    ) -> napi::Result<JsObject> {
+        let (deferred, promise) = env.create_deferred()?;
+
+        let mine_ordering_callback = match mine_ordering_callback
+            .map(|callback| MineOrderingCallback::new(&env, callback, self.runtime.clone()))
+            .transpose()
+        {
+            Ok(callback) => callback,
+            Err(error) => {
+                deferred.reject(error);
+                return Ok(promise);
+            }
+        };
+
+        let mine_ordering_callback = mine_ordering_callback.map(|callback| {
+            Arc::new(move |transactions: &[edr_provider::PendingTransactionSummary]| {
+                callback.order(transactions)
+            }) as Arc<dyn edr_provider::SyncMineOrderingCallback>
+        });
+
+        let provider = self.provider.clone();
+        self.runtime.spawn_blocking(move || {
+            provider.set_mine_ordering_callback(mine_ordering_callback);
+
+            deferred.resolve(|_env| Ok(()));
+        });
+
+        Ok(promise)
+    }
+
+    /// Sets a callback that decides whether transactions are added to the mem
+    /// pool, e.g. to simulate sequencer policies or sender allowlists and
+    /// denylists. The callback receives a summary of each transaction before
+    /// it's added to the mem pool and either rejects it or accepts it with
+    /// optional tags, which can be queried with `hardhat_getTransactionTags`
+    /// while the transaction is pending. If the callback throws or its
+    /// promise rejects, the transaction is rejected.
+    ///
+    /// The callback is called while the provider handles a request, so it
+    /// must not send requests to the provider, as they would never complete.
+    ///
+    /// Pass `undefined` to admit all transactions.
+    #[napi(catch_unwind, ts_return_type = "Promise<void>")]
+    pub fn set_transaction_firewall_callback(
+        &self,
+        env: Env,
+        #[napi(
+            ts_arg_type = "((transaction: TransactionFirewallRequest) => Promise<TransactionFirewallVerdict>) | undefined"
+        )]
+        transaction_firewall_callback: Option<JsFunction>,
+    ) -> napi::Result<JsObject> {
+        let (deferred, promise) = env.create_deferred()?;
+
+        let transaction_firewall_callback = match transaction_firewall_callback
+            .map(|callback| TransactionFirewallCallback::new(&env, callback, self.runtime.clone()))
+            .transpose()
+        {
+            Ok(callback) => callback,
+            Err(error) => {
+                deferred.reject(error);
+                return Ok(promise);
+            }
+        };
+
+        let transaction_firewall = transaction_firewall_callback.map(|callback| {
+            Arc::new(move |request: &edr_provider::TransactionFirewallRequest| {
+                callback.check(request)
+            }) as Arc<dyn edr_provider::SyncTransactionFirewall>
+        });
+
+        let provider = self.provider.clone();
+        self.runtime.spawn_blocking(move || {
+            provider.set_transaction_firewall(transaction_firewall);
+
+            deferred.resolve(|_env| Ok(()));
+        });
+
+        Ok(promise)
+    }
+
     /// Set to `true` to make the traces returned with `eth_call`,
     /// `eth_estimateGas`, `eth_sendRawTransaction`, `eth_sendTransaction`,
     /// `evm_mine`, `hardhat_mine` include the full stack and memory. Set to
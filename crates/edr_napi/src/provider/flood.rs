@@ -0,0 +1,86 @@
+use edr_primitives::U64;
+use napi::bindgen_prelude::{BigInt, Uint8Array};
+use napi_derive::napi;
+
+use crate::cast::TryCast;
+
+/// Options for creating and mining synthetic transactions with
+/// [`crate::provider::Provider::flood`].
+#[napi(object)]
+pub struct FloodOptions {
+    /// The number of transactions to send.
+    pub count: BigInt,
+    /// The sender of the transactions. Defaults to the first local account.
+    pub from: Option<Uint8Array>,
+    /// The receiver of the transactions. Transactions without a receiver
+    /// create contracts.
+    pub to: Option<Uint8Array>,
+    /// The value sent with each transaction. Defaults to zero.
+    pub value: Option<BigInt>,
+    /// The calldata sent with each transaction. Defaults to empty calldata.
+    pub data: Option<Uint8Array>,
+    /// The gas limit of each transaction. Defaults to the gas estimation of
+    /// the first transaction.
+    pub gas: Option<BigInt>,
+}
+
+impl TryFrom<FloodOptions> for edr_provider::hardhat_rpc_types::FloodOptions {
+    type Error = napi::Error;
+
+    fn try_from(value: FloodOptions) -> Result<Self, Self::Error> {
+        let FloodOptions {
+            count,
+            from,
+            to,
+            value,
+            data,
+            gas,
+        } = value;
+
+        let count: u64 = count.try_cast()?;
+        let gas: Option<u64> = gas.map(TryCast::try_cast).transpose()?;
+
+        Ok(Self {
+            count: U64::from(count),
+            from: from.map(TryCast::try_cast).transpose()?,
+            to: to.map(TryCast::try_cast).transpose()?,
+            value: value.map(TryCast::try_cast).transpose()?,
+            data: data.try_cast()?,
+            gas: gas.map(U64::from),
+        })
+    }
+}
+
+/// Throughput statistics of [`crate::provider::Provider::flood`].
+#[napi(object)]
+pub struct FloodResult {
+    /// The number of mined transactions.
+    pub transactions: BigInt,
+    /// The number of mined transactions that failed.
+    pub failed: BigInt,
+    /// The number of mined blocks.
+    pub blocks: BigInt,
+    /// The total gas used by the mined transactions.
+    pub gas_used: BigInt,
+    /// The wall-clock duration of creating and mining the transactions, in
+    /// milliseconds.
+    pub duration_ms: f64,
+    /// The number of mined transactions per second.
+    pub transactions_per_second: f64,
+    /// The amount of gas used per second.
+    pub gas_per_second: f64,
+}
+
+impl From<edr_provider::hardhat_rpc_types::FloodResult> for FloodResult {
+    fn from(value: edr_provider::hardhat_rpc_types::FloodResult) -> Self {
+        Self {
+            transactions: BigInt::from(value.transactions),
+            failed: BigInt::from(value.failed),
+            blocks: BigInt::from(value.blocks),
+            gas_used: BigInt::from(value.gas_used),
+            duration_ms: value.duration_ms,
+            transactions_per_second: value.transactions_per_second,
+            gas_per_second: value.gas_per_second,
+        }
+    }
+}
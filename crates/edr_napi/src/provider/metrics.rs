@@ -0,0 +1,27 @@
+use napi_derive::napi;
+
+/// A snapshot of a provider's metrics, including request counts and latency
+/// per JSON-RPC method, mined blocks and transactions, fork cache hits, and
+/// state size gauges.
+#[napi(object)]
+pub struct ProviderMetrics {
+    /// The metrics, encoded in the Prometheus text exposition format. Can be
+    /// served as-is from a `/metrics` endpoint.
+    pub prometheus: String,
+    /// The metrics as a JSON object.
+    pub snapshot: serde_json::Value,
+}
+
+impl TryFrom<edr_provider::ProviderMetrics> for ProviderMetrics {
+    type Error = napi::Error;
+
+    fn try_from(metrics: edr_provider::ProviderMetrics) -> Result<Self, Self::Error> {
+        let snapshot = serde_json::to_value(&metrics)
+            .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))?;
+
+        Ok(Self {
+            prometheus: metrics.to_prometheus(),
+            snapshot,
+        })
+    }
+}
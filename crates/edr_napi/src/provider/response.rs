@@ -22,7 +22,7 @@ impl From<edr_napi_core::spec::Response<EvmHaltReason>> for Response {
 
 #[napi]
 impl Response {
-    #[doc = "Returns the response data as a JSON string or a JSON object."]
+    #[doc = "Returns the response data as a JSON string or a JSON object. The string is empty for a batch request that only consists of notifications, as it doesn't receive a response."]
     #[napi(catch_unwind, getter)]
     pub fn data(&self) -> Either<String, serde_json::Value> {
         self.inner.data.clone()
@@ -0,0 +1,65 @@
+use std::sync::{Arc, Mutex};
+
+use napi::tokio::sync::{mpsc, Mutex as AsyncMutex};
+use napi_derive::napi;
+
+/// A stream of the struct logs of a traced transaction.
+#[napi]
+pub struct StructLogStream {
+    receiver: AsyncMutex<mpsc::Receiver<napi::Result<String>>>,
+    result: Arc<Mutex<Option<String>>>,
+}
+
+impl StructLogStream {
+    /// Constructs a new instance.
+    pub fn new(
+        receiver: mpsc::Receiver<napi::Result<String>>,
+        result: Arc<Mutex<Option<String>>>,
+    ) -> Self {
+        Self {
+            receiver: AsyncMutex::new(receiver),
+            result,
+        }
+    }
+}
+
+#[napi]
+impl StructLogStream {
+    #[doc = "Returns the next chunk of struct logs as newline-delimited JSON, or `null` once all struct logs have been returned."]
+    #[napi(catch_unwind)]
+    pub async fn next(&self) -> napi::Result<Option<String>> {
+        self.receiver.lock().await.recv().await.transpose()
+    }
+
+    #[doc = "Returns the JSON-encoded trace result, excluding its struct logs. Only available once the stream has been exhausted."]
+    #[napi(catch_unwind)]
+    pub fn result(&self) -> napi::Result<Option<String>> {
+        let result = self
+            .result
+            .lock()
+            .map_err(|_error| napi::Error::from_reason("Trace result lock was poisoned"))?;
+
+        Ok(result.clone())
+    }
+}
+
+/// Encodes struct logs as newline-delimited JSON.
+pub(crate) fn encode_struct_logs(
+    struct_logs: &[edr_provider::DebugTraceLogItem],
+) -> napi::Result<String> {
+    struct_logs
+        .iter()
+        .try_fold(String::new(), |mut chunk, struct_log| {
+            let line = serde_json::to_string(struct_log)?;
+            chunk.push_str(&line);
+            chunk.push('\n');
+
+            Ok(chunk)
+        })
+        .map_err(|error: serde_json::Error| {
+            napi::Error::new(
+                napi::Status::Unknown,
+                format!("Failed to serialize struct logs due to: {error}"),
+            )
+        })
+}
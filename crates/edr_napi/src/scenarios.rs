@@ -1,10 +1,15 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use edr_scenarios::ScenarioConfig;
-use napi::tokio::{fs::File, io::AsyncWriteExt, sync::Mutex};
+use edr_napi_core::spec::ResponseData;
+use edr_scenarios::{JournalEntry, JournalHeader, ScenarioConfig};
+use napi::{
+    tokio::{fs::File, io::AsyncWriteExt, sync::Mutex},
+    Either,
+};
 use rand::{distributions::Alphanumeric, Rng};
 
 const SCENARIO_FILE_PREFIX: &str = "EDR_SCENARIO_PREFIX";
+const JOURNAL_FILE_PREFIX: &str = "EDR_JOURNAL_PREFIX";
 
 /// Creates a scenario file with the provided configuration.
 pub async fn scenario_file(
@@ -13,28 +18,13 @@ pub async fn scenario_file(
     logger_enabled: bool,
 ) -> napi::Result<Option<Mutex<File>>> {
     if let Ok(scenario_prefix) = std::env::var(SCENARIO_FILE_PREFIX) {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
-        let suffix = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(4)
-            .map(char::from)
-            .collect::<String>();
-
-        let mut scenario_file =
-            File::create(format!("{scenario_prefix}_{timestamp}_{suffix}.json")).await?;
-
         let config = ScenarioConfig {
             chain_type: Some(chain_type),
             logger_enabled,
             provider_config: provider_config.try_into()?,
         };
-        let mut line = serde_json::to_string(&config)?;
-        line.push('\n');
-        scenario_file.write_all(line.as_bytes()).await?;
 
+        let scenario_file = create_file(&scenario_prefix, &config).await?;
         Ok(Some(Mutex::new(scenario_file)))
     } else {
         Ok(None)
@@ -51,3 +41,87 @@ pub async fn write_request(scenario_file: &Mutex<File>, request: &str) -> napi::
     }
     Ok(())
 }
+
+/// Creates a request journal with the provided configuration.
+pub async fn journal_file(
+    chain_type: String,
+    provider_config: edr_napi_core::provider::Config,
+    logger_enabled: bool,
+) -> napi::Result<Option<Mutex<File>>> {
+    if let Ok(journal_prefix) = std::env::var(JOURNAL_FILE_PREFIX) {
+        let header = JournalHeader {
+            timestamp: timestamp_millis(),
+            config: ScenarioConfig {
+                chain_type: Some(chain_type),
+                logger_enabled,
+                provider_config: provider_config.try_into()?,
+            },
+        };
+
+        let journal_file = create_file(&journal_prefix, &header).await?;
+        Ok(Some(Mutex::new(journal_file)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Writes a JSON-RPC request and its response to the request journal.
+pub async fn write_journal_entry(
+    journal_file: &Mutex<File>,
+    timestamp: u64,
+    request: String,
+    response: &ResponseData,
+) -> napi::Result<()> {
+    let response = match response {
+        // A batch of notifications doesn't receive a response
+        Either::A(response) if response.is_empty() => serde_json::Value::Null,
+        Either::A(response) => serde_json::from_str(response)?,
+        Either::B(response) => response.clone(),
+    };
+
+    let entry = JournalEntry {
+        timestamp,
+        request,
+        response,
+    };
+
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    {
+        let mut journal_file = journal_file.lock().await;
+        journal_file.write_all(line.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+pub fn timestamp_millis() -> u64 {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis();
+
+    u64::try_from(millis).expect("Timestamp fits into u64")
+}
+
+/// Creates a uniquely named file with the provided prefix and writes the
+/// provided header as its first line.
+async fn create_file(prefix: &str, header: &impl serde::Serialize) -> napi::Result<File> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs();
+    let suffix = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(4)
+        .map(char::from)
+        .collect::<String>();
+
+    let mut file = File::create(format!("{prefix}_{timestamp}_{suffix}.json")).await?;
+
+    let mut line = serde_json::to_string(header)?;
+    line.push('\n');
+    file.write_all(line.as_bytes()).await?;
+
+    Ok(file)
+}
@@ -71,6 +71,8 @@ impl From<&BeforeMessage> for TracingMessage {
             caller,
             to,
             is_static_call,
+            scheme: _scheme,
+            create_scheme: _create_scheme,
             gas_limit,
             data,
             value,
@@ -178,14 +180,18 @@ impl RawTrace {
         self.inner
             .messages
             .iter()
-            .map(|message| match message {
+            .filter_map(|message| match message {
                 edr_tracing::TraceMessage::Before(message) => {
-                    Either3::A(TracingMessage::from(message))
+                    Some(Either3::A(TracingMessage::from(message)))
                 }
-                edr_tracing::TraceMessage::Step(step) => Either3::B(TracingStep::new(step)),
-                edr_tracing::TraceMessage::After(message) => Either3::C(TracingMessageResult {
-                    execution_result: ExecutionResult::from(message),
-                }),
+                edr_tracing::TraceMessage::Step(step) => Some(Either3::B(TracingStep::new(step))),
+                edr_tracing::TraceMessage::After(message) => {
+                    Some(Either3::C(TracingMessageResult {
+                        execution_result: ExecutionResult::from(message),
+                    }))
+                }
+                // Self-destructs are not exposed in raw traces
+                edr_tracing::TraceMessage::SelfDestruct(_) => None,
             })
             .collect()
     }
@@ -450,6 +450,8 @@ pub struct UnrecognizedCreateErrorStackTraceEntry {
     pub type_: StackTraceEntryTypeConst<{ StackTraceEntryType::UNRECOGNIZED_CREATE_ERROR as u8 }>,
     #[serde(serialize_with = "serialize_uint8array_to_hex")]
     pub return_data: Uint8Array,
+    /// The human-readable custom error of the return data, if it's known.
+    pub custom_error: Option<String>,
     pub source_reference: Option<Undefined>,
     pub is_invalid_opcode_error: bool,
 }
@@ -472,6 +474,8 @@ pub struct UnrecognizedContractErrorStackTraceEntry {
     pub address: Uint8Array,
     #[serde(serialize_with = "serialize_uint8array_to_hex")]
     pub return_data: Uint8Array,
+    /// The human-readable custom error of the return data, if it's known.
+    pub custom_error: Option<String>,
     pub source_reference: Option<Undefined>,
     pub is_invalid_opcode_error: bool,
 }
@@ -777,10 +781,12 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
             }
             StackTraceEntry::UnrecognizedCreateError {
                 return_data,
+                custom_error,
                 is_invalid_opcode_error,
             } => UnrecognizedCreateErrorStackTraceEntry {
                 type_: StackTraceEntryTypeConst,
                 return_data: return_data.into(),
+                custom_error,
                 is_invalid_opcode_error,
                 source_reference: None,
             }
@@ -788,11 +794,13 @@ impl TryCast<SolidityStackTraceEntry> for edr_solidity::solidity_stack_trace::St
             StackTraceEntry::UnrecognizedContractError {
                 address,
                 return_data,
+                custom_error,
                 is_invalid_opcode_error,
             } => UnrecognizedContractErrorStackTraceEntry {
                 type_: StackTraceEntryTypeConst,
                 address: Uint8Array::with_data_copied(address),
                 return_data: return_data.into(),
+                custom_error,
                 is_invalid_opcode_error,
                 source_reference: None,
             }
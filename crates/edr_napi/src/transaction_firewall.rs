@@ -0,0 +1,139 @@
+use std::sync::mpsc::channel;
+
+use napi::{
+    bindgen_prelude::{BigInt, Promise, Uint8Array},
+    threadsafe_function::{
+        ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+    },
+    tokio::runtime,
+    Env, JsFunction, Status,
+};
+use napi_derive::napi;
+
+use crate::trace::u256_to_bigint;
+
+/// Summary of a transaction that is about to be added to the mem pool,
+/// provided to a transaction firewall callback.
+#[napi(object)]
+pub struct TransactionFirewallRequest {
+    /// The transaction's hash
+    pub transaction_hash: Uint8Array,
+    /// The transaction's sender
+    pub caller: Uint8Array,
+    /// The transaction's recipient, or `undefined` for contract creations
+    pub to: Option<Uint8Array>,
+    /// The function selector of the transaction's calldata, if it's at least
+    /// four bytes long
+    pub selector: Option<Uint8Array>,
+    /// The value transferred by the transaction
+    pub value: BigInt,
+}
+
+impl From<&edr_provider::TransactionFirewallRequest> for TransactionFirewallRequest {
+    fn from(value: &edr_provider::TransactionFirewallRequest) -> Self {
+        Self {
+            transaction_hash: Uint8Array::with_data_copied(value.transaction_hash),
+            caller: Uint8Array::with_data_copied(value.caller),
+            to: value.to.map(Uint8Array::with_data_copied),
+            selector: value.selector.map(Uint8Array::with_data_copied),
+            value: u256_to_bigint(&value.value),
+        }
+    }
+}
+
+/// The decision of a transaction firewall callback about a transaction.
+#[napi(object)]
+pub struct TransactionFirewallVerdict {
+    /// If provided, the transaction is rejected for this reason
+    pub reject_reason: Option<String>,
+    /// Tags to assign to an accepted transaction, which can be queried with
+    /// `hardhat_getTransactionTags` while the transaction is pending
+    pub tags: Option<Vec<String>>,
+}
+
+impl From<TransactionFirewallVerdict> for edr_provider::TransactionFirewallVerdict {
+    fn from(value: TransactionFirewallVerdict) -> Self {
+        match value.reject_reason {
+            Some(reason) => Self::Reject { reason },
+            None => Self::Accept {
+                tags: value.tags.unwrap_or_default(),
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TransactionFirewallCallback {
+    transaction_firewall_fn: ThreadsafeFunction<TransactionFirewallRequest, ErrorStrategy::Fatal>,
+    runtime: runtime::Handle,
+}
+
+impl TransactionFirewallCallback {
+    pub fn new(
+        env: &Env,
+        transaction_firewall: JsFunction,
+        runtime: runtime::Handle,
+    ) -> napi::Result<Self> {
+        let mut transaction_firewall_fn = transaction_firewall.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<TransactionFirewallRequest>| Ok(vec![ctx.value]),
+        )?;
+
+        // Maintain a weak reference to the function to avoid blocking the event loop
+        // from exiting.
+        transaction_firewall_fn.unref(env)?;
+
+        Ok(Self {
+            transaction_firewall_fn,
+            runtime,
+        })
+    }
+
+    /// Calls the JavaScript callback to decide about the provided
+    /// transaction. If the callback throws or its promise rejects, the
+    /// transaction is rejected.
+    pub fn check(
+        &self,
+        request: &edr_provider::TransactionFirewallRequest,
+    ) -> edr_provider::TransactionFirewallVerdict {
+        let (sender, receiver) = channel();
+
+        let runtime = self.runtime.clone();
+        let status = self.transaction_firewall_fn.call_with_return_value(
+            TransactionFirewallRequest::from(request),
+            ThreadsafeFunctionCallMode::Blocking,
+            move |result: Promise<TransactionFirewallVerdict>| {
+                runtime.spawn(async move {
+                    let result = result.await;
+                    sender.send(result).map_err(|_error| {
+                        napi::Error::new(
+                            Status::GenericFailure,
+                            "Failed to send result from transaction_firewall_callback",
+                        )
+                    })
+                });
+                Ok(())
+            },
+        );
+
+        let result = if status == Status::Ok {
+            // The sender is dropped without sending a result if the callback
+            // throws synchronously.
+            receiver.recv().unwrap_or_else(|_error| {
+                Err(napi::Error::new(
+                    Status::GenericFailure,
+                    "The callback didn't return a promise",
+                ))
+            })
+        } else {
+            Err(napi::Error::new(status, "Failed to call the callback"))
+        };
+
+        match result {
+            Ok(verdict) => verdict.into(),
+            Err(error) => edr_provider::TransactionFirewallVerdict::Reject {
+                reason: format!("Transaction firewall callback failed: {error}"),
+            },
+        }
+    }
+}
@@ -0,0 +1,174 @@
+import { toBytes } from "@nomicfoundation/ethereumjs-util";
+import { assert } from "chai";
+import * as fs from "fs";
+import {
+  AccountOverride,
+  ContractDecoder,
+  GENERIC_CHAIN_TYPE,
+  genericChainProviderFactory,
+  l1GenesisState,
+  l1HardforkFromString,
+  MineOrdering,
+  Provider,
+  SHANGHAI,
+  SubscriptionEvent,
+  TracingConfigWithBuffers,
+} from "..";
+import { deployContract, getContext, sendTransaction } from "./helpers";
+
+const genesisState: AccountOverride[] = [
+  {
+    address: toBytes("0xbe862ad9abfe6f22bcb087716c7d89a26051f74c"),
+    balance: 1000n * 10n ** 18n,
+  },
+  {
+    address: toBytes("0x94a48723b9b46b19c72e3091838d0522618b9363"),
+    balance: 1000n * 10n ** 18n,
+  },
+];
+
+// Contract build info in edr/crates/edr_napi/data/artifacts/default/GasReport.json
+const contractBuildInfo: Buffer = fs.readFileSync(
+  `${__dirname}/data/artifacts/default/GasReport.json`
+);
+
+const SOURCE_NAME = "project/contracts/MyLibrary.sol";
+// The line of `return a + 100;`
+const RETURN_LINE = 6;
+// plus100(1)
+const PLUS_100_CALLDATA =
+  "0x68ba353b0000000000000000000000000000000000000000000000000000000000000001";
+
+const providerConfig = {
+  allowBlocksWithSameTimestamp: false,
+  allowUnlimitedContractSize: true,
+  bailOnCallFailure: false,
+  bailOnTransactionFailure: false,
+  blockGasLimit: 6_000_000n,
+  chainId: 123n,
+  chainOverrides: [],
+  coinbase: Uint8Array.from(
+    Buffer.from("0000000000000000000000000000000000000000", "hex")
+  ),
+  genesisState,
+  hardfork: SHANGHAI,
+  initialBlobGas: {
+    gasUsed: 0n,
+    excessGas: 0n,
+  },
+  initialParentBeaconBlockRoot: Uint8Array.from(
+    Buffer.from(
+      "0000000000000000000000000000000000000000000000000000000000000000",
+      "hex"
+    )
+  ),
+  minGasPrice: 0n,
+  mining: {
+    autoMine: true,
+    memPool: {
+      order: MineOrdering.Priority,
+    },
+  },
+  networkId: 123n,
+  observability: {},
+  ownedAccounts: [
+    "0xe331b6d69882b4cb4ea581d88e0b604039a3de5967688d3dcffdd2270c0fd109",
+    "0xe331b6d69882b4cb4ea581d88e0b604039a3de5967688d3dcffdd2270c0fd10a",
+  ],
+  precompileOverrides: [],
+};
+
+const loggerConfig = {
+  enable: false,
+  decodeConsoleLogInputsCallback: (_inputs: ArrayBuffer[]): string[] => {
+    return [];
+  },
+  printLineCallback: (_message: string, _replace: boolean) => {},
+};
+
+const tracingConfig: TracingConfigWithBuffers = {
+  buildInfos: [Uint8Array.from(contractBuildInfo)],
+  ignoreContracts: true,
+};
+
+describe("Line coverage", function () {
+  const context = getContext();
+  before(async () => {
+    await context.registerProviderFactory(
+      GENERIC_CHAIN_TYPE,
+      genericChainProviderFactory()
+    );
+  });
+
+  const exampleBuildInfo = JSON.parse(contractBuildInfo.toString());
+  const bytecode =
+    exampleBuildInfo.output.contracts[SOURCE_NAME].MyLibrary.evm.bytecode
+      .object;
+
+  let provider: Provider;
+
+  beforeEach(async function () {
+    provider = await context.createProvider(
+      GENERIC_CHAIN_TYPE,
+      {
+        ...providerConfig,
+        genesisState: providerConfig.genesisState.concat(
+          l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
+        ),
+      },
+      loggerConfig,
+      {
+        subscriptionCallback: (_event: SubscriptionEvent) => {},
+      },
+      ContractDecoder.withContracts(tracingConfig)
+    );
+  });
+
+  function returnLineHits(): bigint | undefined {
+    const coverage = provider.takeLineCoverage();
+    const file = coverage.find((file) => file.sourceName === SOURCE_NAME);
+
+    return file?.lines.find((line) => line.line === RETURN_LINE)?.hits;
+  }
+
+  it("is not collected by default", async function () {
+    const address = await deployContract(provider, `0x${bytecode}`);
+    await sendTransaction(provider, {
+      to: address,
+      gas: 1_000_000,
+      data: PLUS_100_CALLDATA,
+    });
+
+    assert.lengthOf(provider.takeLineCoverage(), 0);
+  });
+
+  it("collects and resets executed lines", async function () {
+    provider.setLineCoverageEnabled(true);
+
+    const address = await deployContract(provider, `0x${bytecode}`);
+    // Deploying a library doesn't execute its functions
+    assert.isUndefined(returnLineHits());
+
+    for (let i = 0; i < 2; i++) {
+      await sendTransaction(provider, {
+        to: address,
+        gas: 1_000_000,
+        data: PLUS_100_CALLDATA,
+      });
+    }
+
+    assert.strictEqual(returnLineHits(), 2n);
+    // Taking the coverage resets it
+    assert.isUndefined(returnLineHits());
+
+    provider.setLineCoverageEnabled(false);
+
+    await sendTransaction(provider, {
+      to: address,
+      gas: 1_000_000,
+      data: PLUS_100_CALLDATA,
+    });
+
+    assert.isUndefined(returnLineHits());
+  });
+});
@@ -1,6 +1,9 @@
 import { toBytes } from "@nomicfoundation/ethereumjs-util";
 import { assert } from "chai";
 import chalk, { Chalk } from "chalk";
+import fs from "fs";
+import os from "os";
+import path from "path";
 import {
   AccountOverride,
   ContractDecoder,
@@ -203,6 +206,20 @@ async function setAutomine(
   );
 }
 
+async function setLoggingOptions(
+  provider: Provider,
+  options: object
+): Promise<void> {
+  await provider.handleRequest(
+    JSON.stringify({
+      id: 1,
+      jsonrpc: "2.0",
+      method: "hardhat_setLoggingOptions",
+      params: [options],
+    })
+  );
+}
+
 const genesisState: AccountOverride[] = [
   {
     address: toBytes("0xbe862ad9abfe6f22bcb087716c7d89a26051f74c"),
@@ -255,6 +272,47 @@ const providerConfig = {
   precompileOverrides: [],
 };
 
+async function createLoggingProvider(
+  logger: FakeModulesLogger,
+  mockTimer: MockTime
+): Promise<Provider> {
+  const printLineFn = logger.printLineFn();
+  const replaceLastLineFn = logger.replaceLastLineFn();
+
+  const loggerConfig = {
+    enable: true,
+    decodeConsoleLogInputsCallback: (inputs: ArrayBuffer[]): string[] => {
+      return ConsoleLogger.getDecodedLogs(
+        inputs.map((input) => {
+          return Buffer.from(input);
+        })
+      );
+    },
+    printLineCallback: (message: string, replace: boolean): void => {
+      if (replace) {
+        replaceLastLineFn(message);
+      } else {
+        printLineFn(message);
+      }
+    },
+  };
+
+  return createProviderWithMockTimer(
+    {
+      ...providerConfig,
+      genesisState: providerConfig.genesisState.concat(
+        l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
+      ),
+    },
+    loggerConfig,
+    {
+      subscriptionCallback: (_event: SubscriptionEvent) => {},
+    },
+    new ContractDecoder(),
+    mockTimer
+  );
+}
+
 describe("Provider logs", function () {
   const mockTimer = MockTime.now();
 
@@ -264,42 +322,7 @@ describe("Provider logs", function () {
     let provider: Provider;
     beforeEach(async function () {
       logger = new FakeModulesLogger();
-
-      const printLineFn = logger.printLineFn();
-      const replaceLastLineFn = logger.replaceLastLineFn();
-
-      const loggerConfig = {
-        enable: true,
-        decodeConsoleLogInputsCallback: (inputs: ArrayBuffer[]): string[] => {
-          return ConsoleLogger.getDecodedLogs(
-            inputs.map((input) => {
-              return Buffer.from(input);
-            })
-          );
-        },
-        printLineCallback: (message: string, replace: boolean): void => {
-          if (replace) {
-            replaceLastLineFn(message);
-          } else {
-            printLineFn(message);
-          }
-        },
-      };
-
-      provider = await createProviderWithMockTimer(
-        {
-          ...providerConfig,
-          genesisState: providerConfig.genesisState.concat(
-            l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
-          ),
-        },
-        loggerConfig,
-        {
-          subscriptionCallback: (_event: SubscriptionEvent) => {},
-        },
-        new ContractDecoder(),
-        mockTimer
-      );
+      provider = await createLoggingProvider(logger, mockTimer);
 
       gasPrice = await getGasPrice(provider);
 
@@ -437,4 +460,54 @@ describe("Provider logs", function () {
           }
     });
   });
+
+  describe("Logging options", function () {
+    let logger: FakeModulesLogger;
+    let provider: Provider;
+    beforeEach(async function () {
+      logger = new FakeModulesLogger();
+      provider = await createLoggingProvider(logger, mockTimer);
+    });
+
+    it("should not print methods with silent verbosity", async function () {
+      await setLoggingOptions(provider, {
+        methodVerbosity: { eth_blockNumber: "silent" },
+      });
+      logger.reset();
+
+      await getBlockNumber(provider);
+      await getGasPrice(provider);
+
+      assert.lengthOf(logger.lines, 1);
+      assert.equal(logger.lines[0], ansiColor("eth_gasPrice", chalk.green));
+    });
+
+    it("should only print the mined block with summary verbosity", async function () {
+      const gasPrice = await getGasPrice(provider);
+      await setLoggingOptions(provider, { verbosity: "summary" });
+      await sendTransaction(provider, { gasPrice });
+
+      logger.reset();
+
+      await intervalMine(logger, mockTimer);
+
+      assert.lengthOf(logger.lines, 1);
+      assert.match(logger.lines[0], /^Mined block #\d+$/);
+    });
+
+    it("should append logs without colors to the output file", async function () {
+      const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "edr-logs-"));
+      const outputFile = path.join(outputDir, "edr.log");
+
+      await setLoggingOptions(provider, { outputFile });
+      logger.reset();
+
+      await getBlockNumber(provider);
+
+      assert.lengthOf(logger.lines, 0);
+      assert.equal(fs.readFileSync(outputFile, "utf8"), "eth_blockNumber\n");
+
+      fs.rmSync(outputDir, { recursive: true });
+    });
+  });
 });
@@ -14,11 +14,14 @@ import {
   l1HardforkToString,
   MineOrdering,
   SubscriptionEvent,
+  Precompile,
+  precompileFromBytecode,
   precompileP256Verify,
   OP_CHAIN_TYPE,
   opProviderFactory,
   opHardforkToString,
   OpHardfork,
+  Provider,
 } from "..";
 import {
   collectMessages,
@@ -447,6 +450,186 @@ describe("Provider", () => {
       assert.lengthOf(rawTraces, 1);
     });
 
+    it("should stream struct logs of debug_traceTransaction", async function () {
+      const provider = await context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        {
+          ...providerConfig,
+          genesisState: providerConfig.genesisState.concat(
+            l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
+          ),
+        },
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+
+      const sendTxResponse = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "eth_sendTransaction",
+          params: [
+            {
+              from: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+              // PUSH1 0x42
+              // PUSH0
+              // MSTORE
+              // PUSH1 0x20
+              // PUSH0
+              // RETURN
+              data: "0x60425f5260205ff3",
+              gas: "0x" + 1_000_000n.toString(16),
+            },
+          ],
+        })
+      );
+
+      let responseData;
+
+      if (typeof sendTxResponse.data === "string") {
+        responseData = JSON.parse(sendTxResponse.data);
+      } else {
+        responseData = sendTxResponse.data;
+      }
+
+      const txHash = responseData.result;
+
+      const stream = provider.handleStreamedTraceRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "debug_traceTransaction",
+          params: [txHash],
+        })
+      );
+
+      const structLogs = [];
+      let chunk = await stream.next();
+      while (chunk !== null) {
+        for (const line of chunk.split("\n")) {
+          if (line.length > 0) {
+            structLogs.push(JSON.parse(line));
+          }
+        }
+
+        chunk = await stream.next();
+      }
+
+      assert.deepEqual(
+        structLogs.map((structLog) => structLog.opName),
+        ["PUSH1", "PUSH0", "MSTORE", "PUSH1", "PUSH0", "RETURN"]
+      );
+
+      const result = JSON.parse(stream.result()!);
+      assert.isTrue(result.pass);
+      assert.lengthOf(result.structLogs, 0);
+    });
+
+    it("should handle requests while streamed struct logs aren't consumed", async function () {
+      const provider = await context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        {
+          ...providerConfig,
+          genesisState: providerConfig.genesisState.concat(
+            l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
+          ),
+        },
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+
+      const sendTxResponse = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "eth_sendTransaction",
+          params: [
+            {
+              from: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+              // PUSH2 0x2000
+              // JUMPDEST
+              // PUSH1 0x01
+              // SWAP1
+              // SUB
+              // DUP1
+              // PUSH1 0x03
+              // JUMPI
+              // STOP
+              data: "0x6120005b600190038060035700",
+              gas: "0x" + 1_000_000n.toString(16),
+            },
+          ],
+        })
+      );
+
+      let responseData;
+
+      if (typeof sendTxResponse.data === "string") {
+        responseData = JSON.parse(sendTxResponse.data);
+      } else {
+        responseData = sendTxResponse.data;
+      }
+
+      const txHash = responseData.result;
+
+      const stream = provider.handleStreamedTraceRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "debug_traceTransaction",
+          params: [txHash],
+        })
+      );
+
+      // The trace has more chunks than the stream can queue, so this would
+      // deadlock if tracing waited for the consumer.
+      const blockNumberResponse = await provider.handleRequest(
+        JSON.stringify({
+          id: 2,
+          jsonrpc: "2.0",
+          method: "eth_blockNumber",
+          params: [],
+        })
+      );
+
+      let blockNumberData;
+
+      if (typeof blockNumberResponse.data === "string") {
+        blockNumberData = JSON.parse(blockNumberResponse.data);
+      } else {
+        blockNumberData = blockNumberResponse.data;
+      }
+
+      assert.equal(blockNumberData.result, "0x1");
+
+      let numStructLogs = 0;
+      let lastStructLog;
+      let chunk = await stream.next();
+      while (chunk !== null) {
+        for (const line of chunk.split("\n")) {
+          if (line.length > 0) {
+            numStructLogs += 1;
+            lastStructLog = JSON.parse(line);
+          }
+        }
+
+        chunk = await stream.next();
+      }
+
+      // PUSH2, 0x2000 iterations of the loop's 7 instructions, and STOP
+      assert.equal(numStructLogs, 2 + 0x2000 * 7);
+      assert.equal(lastStructLog.opName, "STOP");
+
+      const result = JSON.parse(stream.result()!);
+      assert.isTrue(result.pass);
+    });
+
     it("should have tracing information when debug_traceCall is used", async function () {
       const provider = await context.createProvider(
         GENERIC_CHAIN_TYPE,
@@ -489,6 +672,46 @@ describe("Provider", () => {
     });
   });
 
+  it("should have tracing information when eth_estimateGas fails", async function () {
+    const provider = await context.createProvider(
+      GENERIC_CHAIN_TYPE,
+      {
+        ...providerConfig,
+        genesisState: providerConfig.genesisState.concat(
+          l1GenesisState(l1HardforkFromString(providerConfig.hardfork))
+        ),
+      },
+      loggerConfig,
+      {
+        subscriptionCallback: (_event: SubscriptionEvent) => {},
+      },
+      new ContractDecoder()
+    );
+
+    const estimateGasResponse = await provider.handleRequest(
+      JSON.stringify({
+        id: 1,
+        jsonrpc: "2.0",
+        method: "eth_estimateGas",
+        params: [
+          {
+            from: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            // PUSH0
+            // PUSH0
+            // REVERT
+            data: "0x5f5ffd",
+          },
+        ],
+      })
+    );
+
+    const responseData = JSON.parse(estimateGasResponse.data);
+    assert.isDefined(responseData.error);
+
+    assert.lengthOf(estimateGasResponse.traces, 1);
+    assert.isNotNull(estimateGasResponse.stackTrace());
+  });
+
   async function deployAndTestCustomPrecompile(enabled: boolean) {
     // Contract code in edr/data/contracts/CustomPrecompile.sol
     const contractArtifact = loadContract(
@@ -576,6 +799,75 @@ describe("Provider", () => {
     return JSON.parse(precompileTransactionReceiptResponse.data).result;
   }
 
+  it("derives and imports accounts with balances", async function () {
+    const provider = await context.createProvider(
+      GENERIC_CHAIN_TYPE,
+      {
+        ...providerConfig,
+        mnemonicAccounts: [
+          {
+            mnemonic:
+              "test test test test test test test test test test test junk",
+            initialIndex: 1,
+            count: 2,
+            balance: 10n ** 18n,
+          },
+        ],
+        importedAccounts: [
+          {
+            secretKey:
+              "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+            balance: 2n * 10n ** 18n,
+          },
+        ],
+      },
+      loggerConfig,
+      {
+        subscriptionCallback: (_event: SubscriptionEvent) => {},
+      },
+      new ContractDecoder()
+    );
+
+    const accountsResponse = await provider.handleRequest(
+      JSON.stringify({
+        id: 1,
+        jsonrpc: "2.0",
+        method: "eth_accounts",
+        params: [],
+      })
+    );
+    const accounts: string[] = JSON.parse(accountsResponse.data).result;
+
+    assert.deepEqual(accounts, [
+      "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+      "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+      "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+      "0x90f79bf6eb2c4f870365e785982e1f101e93b906",
+    ]);
+
+    const expectedBalances = [
+      1000n * 10n ** 18n,
+      10n ** 18n,
+      10n ** 18n,
+      2n * 10n ** 18n,
+    ];
+    for (const [index, account] of accounts.entries()) {
+      const balanceResponse = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "eth_getBalance",
+          params: [account, "latest"],
+        })
+      );
+
+      assert.strictEqual(
+        BigInt(JSON.parse(balanceResponse.data).result),
+        expectedBalances[index]
+      );
+    }
+  });
+
   it("custom precompile enabled", async function () {
     const precompileReceipt = await deployAndTestCustomPrecompile(true);
     assert.strictEqual(precompileReceipt.status, "0x1");
@@ -586,6 +878,98 @@ describe("Provider", () => {
     assert.strictEqual(precompileReceipt.status, "0x0");
   });
 
+  describe("custom precompiles", function () {
+    // Test vector from
+    // https://github.com/maticnetwork/bor/blob/bade7f57df5c09ae060c15fc66aed488c526149e/core/vm/testdata/precompiles/p256Verify.json
+    const P256_VERIFY_CALLDATA =
+      "0x4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4da73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d604aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff37618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e";
+
+    const CUSTOM_ADDRESS = "0x0000000000000000000000000000000000001234";
+
+    async function createProviderWithPrecompiles(
+      precompileOverrides: Precompile[]
+    ): Promise<Provider> {
+      return context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        {
+          ...providerConfig,
+          precompileOverrides,
+        },
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+    }
+
+    async function call(
+      provider: Provider,
+      to: string,
+      data: string
+    ): Promise<string> {
+      const response = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "eth_call",
+          params: [{ to, data }, "latest"],
+        })
+      );
+
+      return JSON.parse(response.data).result;
+    }
+
+    it("executes a native precompile at a custom address", async function () {
+      const precompile = precompileP256Verify().withAddress(
+        toBytes(CUSTOM_ADDRESS)
+      );
+
+      // The original precompile is unaffected
+      assert.deepEqual(
+        Buffer.from(precompileP256Verify().address),
+        Buffer.from(toBytes("0x0000000000000000000000000000000000000100"))
+      );
+
+      const provider = await createProviderWithPrecompiles([precompile]);
+
+      const result = await call(provider, CUSTOM_ADDRESS, P256_VERIFY_CALLDATA);
+      assert.strictEqual(
+        result,
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+      );
+    });
+
+    it("executes a bytecode-backed precompile stand-in", async function () {
+      // PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+      const precompile = precompileFromBytecode(
+        toBytes(CUSTOM_ADDRESS),
+        toBytes("0x602a60005260206000f3")
+      );
+
+      const provider = await createProviderWithPrecompiles([precompile]);
+
+      const result = await call(provider, CUSTOM_ADDRESS, "0x");
+      assert.strictEqual(
+        result,
+        "0x000000000000000000000000000000000000000000000000000000000000002a"
+      );
+    });
+
+    it("rejects precompile stand-ins at built-in precompile addresses", async function () {
+      // ecrecover
+      const precompile = precompileFromBytecode(
+        toBytes("0x0000000000000000000000000000000000000001"),
+        toBytes("0x602a60005260206000f3")
+      );
+
+      await assert.isRejected(
+        createProviderWithPrecompiles([precompile]),
+        "built-in precompile"
+      );
+    });
+  });
+
   it("allows baseFeeConfig configuration", async function () {
     const provider = await context.createProvider(
       OP_CHAIN_TYPE,
@@ -656,6 +1040,130 @@ describe("Provider", () => {
     assert.equal(250, dataView.getUint8(denominatorLeastSignificantByte));
     assert.equal(6, dataView.getUint8(elasticityLeastSignificantByte));
   });
+
+  describe("transaction firewall", function () {
+    const sender = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const recipient = "0x000000000000000000000000000000000000dead";
+
+    async function sendTransaction(provider: Provider): Promise<any> {
+      const response = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "eth_sendTransaction",
+          params: [{ from: sender, to: recipient }],
+        })
+      );
+
+      return JSON.parse(response.data);
+    }
+
+    it("should tag accepted transactions", async function () {
+      const provider = await context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        {
+          ...providerConfig,
+          mining: { ...providerConfig.mining, autoMine: false },
+        },
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+
+      await provider.setTransactionFirewallCallback(async (transaction) => {
+        assert.deepEqual(transaction.to, toBytes(recipient));
+        return { tags: ["checked"] };
+      });
+
+      const response = await sendTransaction(provider);
+      const tagsResponse = await provider.handleRequest(
+        JSON.stringify({
+          id: 1,
+          jsonrpc: "2.0",
+          method: "hardhat_getTransactionTags",
+          params: [response.result],
+        })
+      );
+
+      assert.deepEqual(JSON.parse(tagsResponse.data).result, ["checked"]);
+    });
+
+    it("should reject transactions with the provided reason", async function () {
+      const provider = await context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        providerConfig,
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+
+      await provider.setTransactionFirewallCallback(async (_transaction) => {
+        return { rejectReason: "sanctioned recipient" };
+      });
+
+      const response = await sendTransaction(provider);
+      assert.include(response.error.message, "sanctioned recipient");
+    });
+
+    it("should reject transactions if the callback rejects", async function () {
+      const provider = await context.createProvider(
+        GENERIC_CHAIN_TYPE,
+        providerConfig,
+        loggerConfig,
+        {
+          subscriptionCallback: (_event: SubscriptionEvent) => {},
+        },
+        new ContractDecoder()
+      );
+
+      await provider.setTransactionFirewallCallback(async (_transaction) => {
+        throw new Error("firewall unavailable");
+      });
+
+      const response = await sendTransaction(provider);
+      assert.include(response.error.message, "firewall unavailable");
+    });
+  });
+
+  it("floods the provider with synthetic transactions", async function () {
+    const provider = await context.createProvider(
+      GENERIC_CHAIN_TYPE,
+      providerConfig,
+      loggerConfig,
+      {
+        subscriptionCallback: (_event: SubscriptionEvent) => {},
+      },
+      new ContractDecoder()
+    );
+
+    const recipient = toBytes("0x000000000000000000000000000000000000dead");
+    const result = await provider.flood({
+      count: 100n,
+      to: recipient,
+      value: 1n,
+    });
+
+    assert.strictEqual(result.transactions, 100n);
+    assert.strictEqual(result.failed, 0n);
+    assert.strictEqual(result.blocks, 1n);
+    assert.strictEqual(result.gasUsed, 100n * 21_000n);
+    assert.isAbove(result.transactionsPerSecond, 0);
+
+    const response = await provider.handleRequest(
+      JSON.stringify({
+        id: 1,
+        jsonrpc: "2.0",
+        method: "eth_getBalance",
+        params: ["0x000000000000000000000000000000000000dead", "latest"],
+      })
+    );
+
+    assert.strictEqual(JSON.parse(response.data).result, "0x64");
+  });
 });
 
 function assertEqualMemory(
@@ -1,5 +1,13 @@
 use core::fmt::Debug;
-use std::{fmt::Display, marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{File, OpenOptions},
+    io::Write as _,
+    marker::PhantomData,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 use ansi_term::{Color, Style};
 use derive_where::derive_where;
@@ -9,8 +17,10 @@ use edr_chain_spec_evm::result::{ExecutionResult, Output};
 use edr_precompile::{PrecompileSpecId, Precompiles};
 use edr_primitives::{Bytecode, Bytes, B256, U256};
 use edr_provider::{
-    time::TimeSinceEpoch, CallResult, DebugMineBlockResult, DebugMineBlockResultForChainSpec,
-    EstimateGasFailure, ProviderError, ProviderErrorForChainSpec, ProviderSpec, TransactionFailure,
+    hardhat_rpc_types::{LogVerbosity, LoggingOptions},
+    time::TimeSinceEpoch,
+    CallResult, DebugMineBlockResult, DebugMineBlockResultForChainSpec, EstimateGasFailure,
+    ProviderError, ProviderErrorForChainSpec, ProviderSpec, TransactionFailure,
 };
 use edr_solidity::contract_decoder::{ContractAndFunctionName, ContractDecoder};
 use edr_tracing::{AfterMessage, Trace, TraceMessage};
@@ -21,6 +31,12 @@ pub trait DecodeConsoleLogInputsFn: Fn(Vec<Bytes>) -> Vec<String> + Send + Sync
 
 impl<FnT> DecodeConsoleLogInputsFn for FnT where FnT: Fn(Vec<Bytes>) -> Vec<String> + Send + Sync {}
 
+/// Trait for a function that receives the ABI-encoded inputs of `console.log`
+/// calls.
+pub trait ConsoleLogFn: Fn(Vec<Bytes>) + Send + Sync {}
+
+impl<FnT> ConsoleLogFn for FnT where FnT: Fn(Vec<Bytes>) + Send + Sync {}
+
 /// Trait for a function that prints a line or replaces the last printed line.
 pub trait PrintLineFn: Fn(String, bool) -> Result<(), LoggerError> + Send + Sync {}
 
@@ -31,6 +47,9 @@ pub struct Config {
     /// Whether to enable the logger.
     pub enable: bool,
     pub decode_console_log_inputs_fn: Arc<dyn DecodeConsoleLogInputsFn>,
+    /// Optional function that is called with the `console.log` inputs of each
+    /// execution, regardless of whether the logger is enabled.
+    pub console_log_fn: Option<Arc<dyn ConsoleLogFn>>,
     pub print_line_fn: Arc<dyn PrintLineFn>,
 }
 
@@ -84,6 +103,13 @@ enum LogLine {
 pub enum LoggerError {
     #[error("Failed to print line")]
     PrintLine,
+    #[error("Failed to open log output file '{path}': {source}")]
+    OpenOutputFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to write to log output file: {0}")]
+    WriteOutputFile(std::io::Error),
 }
 
 #[derive_where(Clone)]
@@ -97,6 +123,17 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Logger<Ch
             collector: LogCollector::new(config, contract_decoder)?,
         })
     }
+
+    /// Forwards the `console.log` inputs to the console log function, if any.
+    fn emit_console_logs(&self, console_log_inputs: &[Bytes]) {
+        if console_log_inputs.is_empty() {
+            return;
+        }
+
+        if let Some(console_log_fn) = &self.collector.config.console_log_fn {
+            console_log_fn(console_log_inputs.to_vec());
+        }
+    }
 }
 
 impl<ChainSpecT, TimerT> edr_provider::Logger<ChainSpecT, TimerT> for Logger<ChainSpecT, TimerT>
@@ -112,12 +149,22 @@ where
         self.collector.config.enable = is_enabled;
     }
 
+    fn set_options(
+        &mut self,
+        options: LoggingOptions,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.collector.set_options(options)?;
+
+        Ok(())
+    }
+
     fn log_call(
         &mut self,
         hardfork: ChainSpecT::Hardfork,
         transaction: &ChainSpecT::SignedTransaction,
         result: &CallResult<ChainSpecT::HaltReason>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.emit_console_logs(&result.console_log_inputs);
         self.collector.log_call(hardfork, transaction, result)?;
 
         Ok(())
@@ -129,6 +176,7 @@ where
         transaction: &ChainSpecT::SignedTransaction,
         failure: &EstimateGasFailure<ChainSpecT::HaltReason>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.emit_console_logs(&failure.console_log_inputs);
         self.collector
             .log_estimate_gas(hardfork, transaction, failure)?;
 
@@ -140,6 +188,7 @@ where
         hardfork: ChainSpecT::Hardfork,
         mining_result: &DebugMineBlockResultForChainSpec<ChainSpecT>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.emit_console_logs(&mining_result.console_log_inputs);
         self.collector
             .log_interval_mined(hardfork, mining_result)
             .map_err(Box::new)?;
@@ -152,6 +201,9 @@ where
         hardfork: ChainSpecT::Hardfork,
         mining_results: &[DebugMineBlockResultForChainSpec<ChainSpecT>],
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for mining_result in mining_results {
+            self.emit_console_logs(&mining_result.console_log_inputs);
+        }
         self.collector.log_mined_blocks(hardfork, mining_results)?;
 
         Ok(())
@@ -163,6 +215,9 @@ where
         transaction: &ChainSpecT::SignedTransaction,
         mining_results: &[DebugMineBlockResultForChainSpec<ChainSpecT>],
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for mining_result in mining_results {
+            self.emit_console_logs(&mining_result.console_log_inputs);
+        }
         self.collector
             .log_send_transaction(hardfork, transaction, mining_results)?;
 
@@ -174,6 +229,16 @@ where
         method: &str,
         error: Option<&ProviderErrorForChainSpec<ChainSpecT>>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let verbosity = self.collector.verbosity_for_method(method);
+        match verbosity {
+            LogVerbosity::Silent => {
+                self.collector.logs.clear();
+                return Ok(());
+            }
+            LogVerbosity::Summary => self.collector.logs.clear(),
+            LogVerbosity::Full => {}
+        }
+
         if let Some(error) = error {
             self.collector.state = LoggingState::Empty;
 
@@ -184,7 +249,11 @@ where
                 self.collector.print::<false>(Color::Red.paint(method))?;
                 self.collector.print_logs()?;
 
-                if !matches!(error, ProviderError::TransactionFailed(_)) {
+                // Transaction failures are part of the detailed logs, unless those were
+                // omitted.
+                if verbosity == LogVerbosity::Summary
+                    || !matches!(error, ProviderError::TransactionFailed(_))
+                {
                     self.collector.print_empty_line()?;
 
                     let error_message = error.to_string();
@@ -229,6 +298,12 @@ struct LogCollector<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceE
     logs: Vec<LogLine>,
     state: LoggingState,
     title_length: usize,
+    /// The verbosity of methods without a method-specific verbosity.
+    verbosity: LogVerbosity,
+    method_verbosity: HashMap<String, LogVerbosity>,
+    /// If set, lines are appended to this file instead of being passed to the
+    /// print line function.
+    output_file: Option<Arc<Mutex<File>>>,
     phantom: PhantomData<fn() -> (ChainSpecT, TimerT)>,
 }
 
@@ -243,10 +318,53 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
             logs: Vec::new(),
             state: LoggingState::default(),
             title_length: 0,
+            verbosity: LogVerbosity::default(),
+            method_verbosity: HashMap::new(),
+            output_file: None,
             phantom: PhantomData,
         })
     }
 
+    pub fn set_options(&mut self, options: LoggingOptions) -> Result<(), LoggerError> {
+        let LoggingOptions {
+            verbosity,
+            method_verbosity,
+            output_file,
+        } = options;
+
+        // Open the file first, to leave the options unchanged upon failure.
+        if let Some(path) = output_file {
+            self.output_file = if path.as_os_str().is_empty() {
+                None
+            } else {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map_err(|source| LoggerError::OpenOutputFile { path, source })?;
+
+                Some(Arc::new(Mutex::new(file)))
+            };
+        }
+
+        if let Some(verbosity) = verbosity {
+            self.verbosity = verbosity;
+        }
+
+        if let Some(method_verbosity) = method_verbosity {
+            self.method_verbosity = method_verbosity;
+        }
+
+        Ok(())
+    }
+
+    fn verbosity_for_method(&self, method: &str) -> LogVerbosity {
+        self.method_verbosity
+            .get(method)
+            .copied()
+            .unwrap_or(self.verbosity)
+    }
+
     pub fn log_call(
         &mut self,
         hardfork: ChainSpecT::Hardfork,
@@ -279,7 +397,7 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
                 TimerT,
             >(execution_result, None, trace)
             {
-                logger.log_transaction_failure(&transaction_failure);
+                logger.log_transaction_failure(transaction_failure);
             }
 
             Ok(())
@@ -321,8 +439,10 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
 
     fn log_transaction_failure(
         &mut self,
-        failure: &edr_provider::TransactionFailure<ChainSpecT::HaltReason>,
+        failure: edr_provider::TransactionFailure<ChainSpecT::HaltReason>,
     ) {
+        let failure = failure.with_custom_error(&self.contract_decoder);
+
         let is_revert_error = matches!(
             failure.reason,
             edr_provider::TransactionFailureReason::Revert(_)
@@ -374,6 +494,12 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
         hardfork: ChainSpecT::Hardfork,
         mining_result: &DebugMineBlockResultForChainSpec<ChainSpecT>,
     ) -> Result<(), LoggerError> {
+        // Interval mining isn't triggered by a method, so only the default
+        // verbosity applies.
+        if self.verbosity == LogVerbosity::Silent {
+            return Ok(());
+        }
+
         let block_header = mining_result.block.block_header();
         let block_number = block_header.number;
 
@@ -400,6 +526,10 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
                     empty_blocks_range_start.unwrap_or(block_header.number),
                 ),
             };
+        } else if self.verbosity == LogVerbosity::Summary {
+            self.state = LoggingState::Empty;
+
+            self.print::<false>(format!("Mined block #{block_number}"))?;
         } else {
             self.log_interval_mined_block(hardfork, mining_result)?;
 
@@ -662,7 +792,7 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
             >(result, Some(transaction_hash), trace);
 
             if let Some(transaction_failure) = transaction_failure {
-                logger.log_transaction_failure(&transaction_failure);
+                logger.log_transaction_failure(transaction_failure);
             }
 
             Ok(())
@@ -1077,7 +1207,7 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
             );
 
             if let Some(transaction_failure) = transaction_failure {
-                logger.log_transaction_failure(&transaction_failure);
+                logger.log_transaction_failure(transaction_failure);
             }
 
             Ok(())
@@ -1090,7 +1220,15 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
         }
 
         let formatted = self.format(message);
-        (self.config.print_line_fn)(formatted, REPLACE)
+        if let Some(output_file) = &self.output_file {
+            // Files are append-only, so replaced lines are appended as well. A
+            // panic while writing can at most have left a partial line behind.
+            let mut output_file = output_file.lock().unwrap_or_else(PoisonError::into_inner);
+            writeln!(output_file, "{}", strip_ansi_escapes::strip_str(&formatted))
+                .map_err(LoggerError::WriteOutputFile)
+        } else {
+            (self.config.print_line_fn)(formatted, REPLACE)
+        }
     }
 
     fn print_empty_line(&mut self) -> Result<(), LoggerError> {
@@ -4,7 +4,12 @@ mod factory;
 use std::{str::FromStr as _, sync::Arc};
 
 use edr_chain_spec::EvmHaltReason;
-use edr_provider::{time::TimeSinceEpoch, InvalidRequestReason, SyncCallOverride};
+use edr_provider::{
+    hardhat_rpc_types::{FloodOptions, FloodResult},
+    time::TimeSinceEpoch,
+    InvalidRequestReason, MethodInvocation, ProviderMetrics, ProviderRequest, StructLogStream,
+    SyncCallOverride, SyncMineOrderingCallback, SyncTransactionFirewall,
+};
 use edr_rpc_client::jsonrpc;
 use edr_solidity::contract_decoder::ContractDecoder;
 
@@ -21,12 +26,43 @@ pub trait SyncProvider: Send + Sync {
         contract_decoder: Arc<ContractDecoder>,
     ) -> napi::Result<Response<EvmHaltReason>>;
 
+    /// Blocking method to handle a `debug_traceTransaction` request, streaming
+    /// the struct logs to the provided stream instead of including them in
+    /// the response. Returns the JSON-encoded trace result.
+    fn handle_streamed_trace_request(
+        &self,
+        request: String,
+        struct_log_stream: StructLogStream,
+    ) -> napi::Result<String>;
+
+    /// Blocking method to create and mine synthetic transactions, without
+    /// serializing them individually.
+    fn flood(&self, options: FloodOptions) -> napi::Result<FloodResult>;
+
+    /// Blocking method to retrieve a snapshot of the provider's metrics.
+    fn metrics(&self) -> ProviderMetrics;
+
     /// Set to `true` to make the traces returned with `eth_call`,
     /// `eth_estimateGas`, `eth_sendRawTransaction`, `eth_sendTransaction`,
     /// `evm_mine`, `hardhat_mine` include the full stack and memory. Set to
     /// `false` to disable this.
     fn set_call_override_callback(&self, call_override_callback: Arc<dyn SyncCallOverride>);
 
+    /// Sets the callback that determines the order in which pending
+    /// transactions are mined. Set to `None` to restore the configured mine
+    /// ordering.
+    fn set_mine_ordering_callback(
+        &self,
+        mine_ordering_callback: Option<Arc<dyn SyncMineOrderingCallback>>,
+    );
+
+    /// Sets the callback that decides whether transactions are added to the
+    /// mem pool. Set to `None` to admit all transactions.
+    fn set_transaction_firewall(
+        &self,
+        transaction_firewall: Option<Arc<dyn SyncTransactionFirewall>>,
+    );
+
     /// Set the verbose tracing flag to the provided value.
     fn set_verbose_tracing(&self, enabled: bool);
 }
@@ -39,7 +75,7 @@ impl<ChainSpecT: SyncNapiSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> SyncProvi
         request: String,
         contract_decoder: Arc<ContractDecoder>,
     ) -> napi::Result<Response<EvmHaltReason>> {
-        let request = match serde_json::from_str(&request) {
+        let request: ProviderRequest<ChainSpecT> = match serde_json::from_str(&request) {
             Ok(request) => request,
             Err(error) => {
                 let message = error.to_string();
@@ -81,15 +117,85 @@ impl<ChainSpecT: SyncNapiSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> SyncProvi
             }
         };
 
+        // A batch that only consists of notifications doesn't receive a response
+        let is_notification_batch = matches!(
+            &request,
+            ProviderRequest::Batch(requests) if requests.iter().all(|request| request.id.is_none())
+        );
+
         let response = edr_provider::Provider::handle_request(self, request);
+        let is_empty_response = is_notification_batch && response.is_ok();
+
+        ChainSpecT::cast_response(response, contract_decoder).map(|mut response| {
+            if is_empty_response {
+                response.data = napi::Either::A(String::new());
+            }
 
-        ChainSpecT::cast_response(response, contract_decoder)
+            response
+        })
+    }
+
+    fn handle_streamed_trace_request(
+        &self,
+        request: String,
+        struct_log_stream: StructLogStream,
+    ) -> napi::Result<String> {
+        let request: ProviderRequest<ChainSpecT> = serde_json::from_str(&request)
+            .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error.to_string()))?;
+
+        let ProviderRequest::Single(request) = request else {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                "Batch requests cannot be streamed",
+            ));
+        };
+
+        let MethodInvocation::DebugTraceTransaction(transaction_hash, config) = *request else {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                "Only `debug_traceTransaction` requests can be streamed",
+            ));
+        };
+
+        let result = self
+            .handle_streamed_debug_trace_transaction(transaction_hash, config, struct_log_stream)
+            .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))?;
+
+        serde_json::to_string(&result).map_err(|error| {
+            napi::Error::new(
+                napi::Status::Unknown,
+                format!("Failed to serialize response due to: {error}"),
+            )
+        })
+    }
+
+    fn flood(&self, options: FloodOptions) -> napi::Result<FloodResult> {
+        self.flood(options)
+            .map_err(|error| napi::Error::new(napi::Status::GenericFailure, error.to_string()))
+    }
+
+    fn metrics(&self) -> ProviderMetrics {
+        self.metrics()
     }
 
     fn set_call_override_callback(&self, call_override_callback: Arc<dyn SyncCallOverride>) {
         self.set_call_override_callback(Some(call_override_callback));
     }
 
+    fn set_mine_ordering_callback(
+        &self,
+        mine_ordering_callback: Option<Arc<dyn SyncMineOrderingCallback>>,
+    ) {
+        self.set_mine_ordering_callback(mine_ordering_callback);
+    }
+
+    fn set_transaction_firewall(
+        &self,
+        transaction_firewall: Option<Arc<dyn SyncTransactionFirewall>>,
+    ) {
+        self.set_transaction_firewall(transaction_firewall);
+    }
+
     fn set_verbose_tracing(&self, enabled: bool) {
         self.set_verbose_tracing(enabled);
     }
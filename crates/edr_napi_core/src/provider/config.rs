@@ -1,13 +1,16 @@
-use core::num::NonZeroU64;
-use std::{str::FromStr, time::SystemTime};
+use core::num::{NonZeroU64, NonZeroUsize};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr, time::SystemTime};
 
 use edr_block_header::BlobGas;
 use edr_chain_config::{ChainOverride, HardforkActivation, HardforkActivations};
 use edr_chain_spec::EvmSpecId;
 use edr_eip1559::{BaseFeeActivation, BaseFeeParams, ConstantBaseFeeParams, DynamicBaseFeeParams};
-use edr_precompile::PrecompileFn;
+use edr_precompile::CustomPrecompile;
 use edr_primitives::{Address, ChainId, HashMap, UnknownHardfork, B256};
-use edr_provider::{config, observability::ObservabilityConfig, AccountOverride, ForkConfig};
+use edr_provider::{
+    config, observability::ObservabilityConfig, time::TimeMode, AccountOverride, ForkConfig,
+    GethGenesis,
+};
 use edr_signer::SecretKey;
 
 /// Chain-agnostic configuration for a provider.
@@ -15,28 +18,84 @@ use edr_signer::SecretKey;
 pub struct Config {
     pub allow_blocks_with_same_timestamp: bool,
     pub allow_unlimited_contract_size: bool,
+    /// Whether to allow deployments with init code of any size.
+    pub allow_unlimited_init_code_size: bool,
+    /// Whether to top up the balance of impersonated senders to cover the
+    /// upfront cost of their transactions.
+    pub auto_fund: bool,
+    /// Whether to impersonate every sender that isn't an owned account.
+    pub auto_impersonate: bool,
     /// Whether to return an `Err` when `eth_call` fails
     pub bail_on_call_failure: bool,
     /// Whether to return an `Err` when a `eth_sendTransaction` fails
     pub bail_on_transaction_failure: bool,
     pub base_fee_params: Option<Vec<(BaseFeeActivation<String>, ConstantBaseFeeParams)>>,
     pub block_gas_limit: NonZeroU64,
+    /// (Block number -> block gas limit) changes applied when mining new
+    /// blocks.
+    pub block_gas_limit_schedule: BTreeMap<u64, NonZeroU64>,
     pub chain_id: ChainId,
+    /// The directory in which checkpoints are stored.
+    pub checkpoint_dir: PathBuf,
     pub coinbase: Address,
+    /// Whether to deploy the deterministic deployment proxy at genesis.
+    pub deploy_deterministic_deployment_proxy: bool,
+    /// Whether to skip the check that the sender's balance covers the upfront
+    /// cost of a transaction.
+    pub disable_balance_check: bool,
+    /// Whether to keep the base fee per gas of new blocks equal to their
+    /// parent's.
+    pub disable_base_fee_adjustment: bool,
+    /// Whether to skip the check that a transaction pays at least the base
+    /// fee.
+    pub disable_base_fee_check: bool,
+    /// Whether to skip the check that a transaction's priority fee is at
+    /// least the minimum gas price.
+    pub disable_priority_fee_check: bool,
     pub fork: Option<ForkConfig<String>>,
+    /// The account that pays for the gas of the transactions of a set of
+    /// senders, if any.
+    pub gas_sponsor: Option<config::GasSponsor>,
     pub genesis_state: HashMap<Address, AccountOverride>,
+    /// A Geth-style genesis that is applied on top of the rest of the
+    /// configuration.
+    pub geth_genesis: Option<GethGenesis>,
     pub hardfork: String,
+    /// (Block number -> hardfork) activations for a local blockchain.
+    pub hardfork_activations: BTreeMap<u64, String>,
+    /// Whether to index the transactions of mined blocks by address.
+    pub index_address_transactions: bool,
+    /// Whether to index the token transfers of mined blocks.
+    pub index_token_transfers: bool,
     pub initial_base_fee_per_gas: Option<u128>,
     pub initial_blob_gas: Option<BlobGas>,
     pub initial_date: Option<SystemTime>,
     pub initial_parent_beacon_block_root: Option<B256>,
+    /// The maximum number of block states that are retained for historical
+    /// queries.
+    pub max_cached_states: Option<NonZeroUsize>,
+    /// The maximum number of blocks that a single `eth_getLogs` request may
+    /// span.
+    pub max_logs_block_range: Option<u64>,
+    /// The maximum number of logs that a single `eth_getLogs` request may
+    /// return.
+    pub max_logs_results: Option<u64>,
+    /// Which JSON-RPC methods are accepted.
+    pub method_permissions: config::MethodPermissions,
     pub min_gas_price: u128,
     pub mining: config::Mining,
     pub network_id: u64,
     pub observability: ObservabilityConfig,
     /// Secret keys of owned accounts.
     pub owned_accounts: Vec<SecretKey>,
-    pub precompile_overrides: HashMap<Address, PrecompileFn>,
+    pub precompile_overrides: HashMap<Address, CustomPrecompile>,
+    /// The seed of the generator of the prevrandao values of mined blocks.
+    pub prev_randao_seed: Option<String>,
+    /// Whether to record the state diff of each mined transaction.
+    pub record_state_diffs: bool,
+    pub state_pruning: config::StatePruning,
+    /// How block timestamps advance over time.
+    pub time_mode: TimeMode,
     /// Transaction gas cap, introduced in [EIP-7825].
     ///
     /// When not set, will default to value defined by the used hardfork
@@ -134,36 +193,73 @@ where
                     cache_dir: fork.cache_dir,
                     chain_overrides,
                     http_headers: fork.http_headers,
+                    live: fork.live,
+                    transport: fork.transport,
                     url: fork.url,
+                    verified_sources: fork.verified_sources,
                 })
             })
             .transpose()?;
 
         let hardfork = parse_hardfork(value.hardfork)?;
+        let hardfork_activations = value
+            .hardfork_activations
+            .into_iter()
+            .map(|(block_number, hardfork)| Ok((block_number, parse_hardfork(hardfork)?)))
+            .collect::<napi::Result<_>>()?;
 
-        Ok(Self {
+        let mut config = Self {
             allow_blocks_with_same_timestamp: value.allow_blocks_with_same_timestamp,
             allow_unlimited_contract_size: value.allow_unlimited_contract_size,
+            allow_unlimited_init_code_size: value.allow_unlimited_init_code_size,
+            auto_fund: value.auto_fund,
+            auto_impersonate: value.auto_impersonate,
             bail_on_call_failure: value.bail_on_call_failure,
             bail_on_transaction_failure: value.bail_on_transaction_failure,
             base_fee_params,
             block_gas_limit: value.block_gas_limit,
+            block_gas_limit_schedule: value.block_gas_limit_schedule,
             chain_id: value.chain_id,
+            checkpoint_dir: value.checkpoint_dir,
             coinbase: value.coinbase,
+            deploy_deterministic_deployment_proxy: value.deploy_deterministic_deployment_proxy,
+            disable_balance_check: value.disable_balance_check,
+            disable_base_fee_adjustment: value.disable_base_fee_adjustment,
+            disable_base_fee_check: value.disable_base_fee_check,
+            disable_priority_fee_check: value.disable_priority_fee_check,
             fork,
+            gas_sponsor: value.gas_sponsor,
+            genesis_extra_data: None,
             genesis_state: value.genesis_state,
             hardfork,
+            hardfork_activations,
+            index_address_transactions: value.index_address_transactions,
+            index_token_transfers: value.index_token_transfers,
             initial_base_fee_per_gas: value.initial_base_fee_per_gas,
             initial_blob_gas: value.initial_blob_gas,
             initial_date: value.initial_date,
             initial_parent_beacon_block_root: value.initial_parent_beacon_block_root,
+            max_cached_states: value.max_cached_states,
+            max_logs_block_range: value.max_logs_block_range,
+            max_logs_results: value.max_logs_results,
+            method_permissions: value.method_permissions,
             min_gas_price: value.min_gas_price,
             mining: value.mining,
             network_id: value.network_id,
             observability: value.observability,
             owned_accounts: value.owned_accounts,
             precompile_overrides: value.precompile_overrides,
+            prev_randao_seed: value.prev_randao_seed,
+            record_state_diffs: value.record_state_diffs,
+            state_pruning: value.state_pruning,
+            time_mode: value.time_mode,
             transaction_gas_cap: value.transaction_gas_cap,
-        })
+        };
+
+        if let Some(geth_genesis) = value.geth_genesis {
+            geth_genesis.apply_to(&mut config);
+        }
+
+        Ok(config)
     }
 }
@@ -17,7 +17,8 @@ pub type ResponseData = Either<String, serde_json::Value>;
 pub struct Response<HaltReasonT: HaltReasonTrait> {
     // N-API is known to be slow when marshalling `serde_json::Value`s, so we try to return a
     // `String`. If the object is too large to be represented as a `String`, we return a `Buffer`
-    // instead.
+    // instead. An empty `String` indicates that there is no response, which is the case for a
+    // batch request that only consists of notifications.
     pub data: ResponseData,
     /// When a transaction fails to execute, the provider returns a trace of the
     /// transaction.
@@ -80,29 +81,27 @@ impl<TimerT: Clone + TimeSinceEpoch> SyncNapiSpec<TimerT> for L1ChainSpec {
         // We can take the solidity trace as it won't be used for anything else
         let solidity_trace: Option<Arc<Trace<EvmHaltReason>>> =
             response.as_mut().err().and_then(|error| {
-                if let edr_provider::ProviderError::TransactionFailed(failure) = error {
-                    if matches!(
-                        failure.failure.reason,
-                        edr_provider::TransactionFailureReason::OutOfGas(_)
-                    ) {
-                        None
-                    } else {
-                        Some(Arc::new(std::mem::take(
-                            &mut failure.failure.solidity_trace,
-                        )))
-                    }
-                } else {
+                // Both transaction failures and `eth_estimateGas` failures carry a trace
+                let failure = error.as_transaction_failure_mut()?;
+                if matches!(
+                    failure.failure.reason,
+                    edr_provider::TransactionFailureReason::OutOfGas(_)
+                ) {
                     None
+                } else {
+                    Some(Arc::new(std::mem::take(
+                        &mut failure.failure.solidity_trace,
+                    )))
                 }
             });
 
         // We can take the traces as they won't be used for anything else
         let traces = match &mut response {
             Ok(response) => std::mem::take(&mut response.traces),
-            Err(edr_provider::ProviderError::TransactionFailed(failure)) => {
-                std::mem::take(&mut failure.traces)
-            }
-            Err(_) => Vec::new(),
+            Err(error) => error
+                .as_transaction_failure_mut()
+                .map(|failure| std::mem::take(&mut failure.traces))
+                .unwrap_or_default(),
         };
 
         let response = jsonrpc::ResponseData::from(response.map(|response| response.result));
@@ -132,29 +131,27 @@ impl<TimerT: Clone + TimeSinceEpoch> SyncNapiSpec<TimerT> for GenericChainSpec {
         // We can take the solidity trace as it won't be used for anything else
         let solidity_trace: Option<Arc<Trace<EvmHaltReason>>> =
             response.as_mut().err().and_then(|error| {
-                if let edr_provider::ProviderError::TransactionFailed(failure) = error {
-                    if matches!(
-                        failure.failure.reason,
-                        edr_provider::TransactionFailureReason::OutOfGas(_)
-                    ) {
-                        None
-                    } else {
-                        Some(Arc::new(std::mem::take(
-                            &mut failure.failure.solidity_trace,
-                        )))
-                    }
-                } else {
+                // Both transaction failures and `eth_estimateGas` failures carry a trace
+                let failure = error.as_transaction_failure_mut()?;
+                if matches!(
+                    failure.failure.reason,
+                    edr_provider::TransactionFailureReason::OutOfGas(_)
+                ) {
                     None
+                } else {
+                    Some(Arc::new(std::mem::take(
+                        &mut failure.failure.solidity_trace,
+                    )))
                 }
             });
 
         // We can take the traces as they won't be used for anything else
         let traces = match &mut response {
             Ok(response) => std::mem::take(&mut response.traces),
-            Err(edr_provider::ProviderError::TransactionFailed(failure)) => {
-                std::mem::take(&mut failure.traces)
-            }
-            Err(_) => Vec::new(),
+            Err(error) => error
+                .as_transaction_failure_mut()
+                .map(|failure| std::mem::take(&mut failure.traces))
+                .unwrap_or_default(),
         };
 
         let response = jsonrpc::ResponseData::from(response.map(|response| response.result));
@@ -74,6 +74,7 @@ pub enum SubscriptionEventData {
     /// A function that converts a [`BlockAndTotalDifficulty`] to a JS value.
     NewHeads(Box<DynJsValueConstructor>),
     NewPendingTransactions(B256),
+    DroppedTransactions(B256),
 }
 
 impl SubscriptionEventData {
@@ -101,6 +102,9 @@ impl SubscriptionEventData {
             edr_provider::SubscriptionEventData::NewPendingTransactions(fixed_bytes) => {
                 Self::NewPendingTransactions(fixed_bytes)
             }
+            edr_provider::SubscriptionEventData::DroppedTransactions(transaction_hash) => {
+                Self::DroppedTransactions(transaction_hash)
+            }
         }
     }
 }
@@ -127,7 +131,8 @@ impl Callback {
                     SubscriptionEventData::NewHeads(block_to_js_value_fn) => {
                         block_to_js_value_fn(&ctx.env)
                     }
-                    SubscriptionEventData::NewPendingTransactions(tx_hash) => {
+                    SubscriptionEventData::NewPendingTransactions(tx_hash)
+                    | SubscriptionEventData::DroppedTransactions(tx_hash) => {
                         ctx.env.to_js_value(&tx_hash)
                     }
                 }?;
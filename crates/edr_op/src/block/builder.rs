@@ -2,7 +2,8 @@ use core::fmt::Debug;
 
 use edr_block_builder_api::{
     BlockBuilder, BlockBuilderCreationError, BlockFinalizeError, BlockInputs,
-    BlockTransactionError, BuiltBlockAndState, DatabaseComponents, PrecompileFn, WrapDatabaseRef,
+    BlockTransactionError, BuiltBlockAndState, CustomPrecompile, DatabaseComponents,
+    WrapDatabaseRef,
 };
 use edr_block_header::{overridden_block_number, HeaderOverrides, PartialHeader};
 use edr_chain_l1::block::EthBlockBuilder;
@@ -58,7 +59,7 @@ impl<'builder, BlockchainErrorT: std::error::Error>
         evm_config: &EvmConfig,
         mut inputs: BlockInputs,
         mut overrides: HeaderOverrides<Hardfork>,
-        custom_precompiles: &'builder HashMap<Address, PrecompileFn>,
+        custom_precompiles: &'builder HashMap<Address, CustomPrecompile>,
     ) -> Result<
         Self,
         BlockBuilderCreationError<
@@ -79,8 +80,9 @@ impl<'builder, BlockchainErrorT: std::error::Error>
         // and avoid passing them as input.
         if hardfork >= Hardfork::CANYON {
             // `EthBlockBuilder` expects `inputs.withdrawals.is_some()` despite OP not
-            // supporting withdrawals.
-            inputs.withdrawals = Some(Vec::new());
+            // supporting withdrawals. Withdrawals that were provided by the caller are
+            // kept.
+            inputs.withdrawals.get_or_insert_with(Vec::new);
         }
 
         overrides.withdrawals_root = overrides.withdrawals_root.map_or_else(
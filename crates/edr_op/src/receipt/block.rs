@@ -142,15 +142,13 @@ fn to_rpc_l1_block_info(
     transaction: &OpSignedTransaction,
     transaction_receipt: &TransactionReceipt<TypedEnvelope<OpExecutionReceipt<FilterLog>>>,
 ) -> Option<op_alloy_rpc_types::L1BlockInfo> {
-    if matches!(
-        transaction_receipt.inner,
-        TypedEnvelope::Legacy(_) | TypedEnvelope::Deposit(_)
-    ) {
+    // Like every other non-deposit transaction, legacy transactions pay an L1
+    // data fee.
+    if matches!(transaction_receipt.inner, TypedEnvelope::Deposit(_)) {
         None
     } else {
-        let enveloped_tx = transaction
-            .enveloped_tx()
-            .expect("Non-deposit transactions must return an enveloped transaction");
+        // Non-deposit transactions always have an enveloped transaction
+        let enveloped_tx = transaction.enveloped_tx()?;
 
         let mut l1_block_info = l1_block_info.clone();
         let l1_fee = l1_block_info.calculate_tx_l1_cost(enveloped_tx, hardfork);
@@ -160,10 +158,7 @@ fn to_rpc_l1_block_info(
 
             (Some(l1_fee_scalar / 1_000_000f64), None)
         } else {
-            let l1_base_fee_scalar = l1_block_info
-                .l1_base_fee_scalar
-                .try_into()
-                .expect("L1 base fee scalar cannot be larger than u128::max");
+            let l1_base_fee_scalar = l1_block_info.l1_base_fee_scalar.saturating_to();
 
             (None, Some(l1_base_fee_scalar))
         };
@@ -172,45 +167,25 @@ fn to_rpc_l1_block_info(
             .data_gas(enveloped_tx, hardfork)
             .saturating_add(l1_block_info.l1_fee_overhead.unwrap_or_default());
 
+        // Values that don't fit in a `u128` are saturated
         let l1_block_info = op_alloy_rpc_types::L1BlockInfo {
-            l1_gas_price: Some(
-                l1_block_info
-                    .l1_base_fee
-                    .try_into()
-                    .expect("L1 gas price cannot be larger than u128::max"),
-            ),
-            l1_gas_used: Some(
-                l1_gas_used
-                    .try_into()
-                    .expect("L1 gas used cannot be larger than u128::max"),
-            ),
-            l1_fee: Some(
-                l1_fee
-                    .try_into()
-                    .expect("L1 fee cannot be larger than u128::max"),
-            ),
+            l1_gas_price: Some(l1_block_info.l1_base_fee.saturating_to()),
+            l1_gas_used: Some(l1_gas_used.saturating_to()),
+            l1_fee: Some(l1_fee.saturating_to()),
             l1_fee_scalar,
             l1_base_fee_scalar,
-            l1_blob_base_fee: l1_block_info.l1_blob_base_fee.map(|scalar| {
-                scalar
-                    .try_into()
-                    .expect("L1 blob base fee cannot be larger than u128::max")
-            }),
-            l1_blob_base_fee_scalar: l1_block_info.l1_blob_base_fee_scalar.map(|scalar| {
-                scalar
-                    .try_into()
-                    .expect("L1 blob base fee scalar cannot be larger than u128::max")
-            }),
-            operator_fee_scalar: l1_block_info.operator_fee_scalar.map(|scalar| {
-                scalar
-                    .try_into()
-                    .expect("Operator fee scalar cannot be larger than u128::max")
-            }),
-            operator_fee_constant: l1_block_info.operator_fee_constant.map(|scalar| {
-                scalar
-                    .try_into()
-                    .expect("Operator fee constant cannot be larger than u128::max")
-            }),
+            l1_blob_base_fee: l1_block_info
+                .l1_blob_base_fee
+                .map(|scalar| scalar.saturating_to()),
+            l1_blob_base_fee_scalar: l1_block_info
+                .l1_blob_base_fee_scalar
+                .map(|scalar| scalar.saturating_to()),
+            operator_fee_scalar: l1_block_info
+                .operator_fee_scalar
+                .map(|scalar| scalar.saturating_to()),
+            operator_fee_constant: l1_block_info
+                .operator_fee_constant
+                .map(|scalar| scalar.saturating_to()),
         };
 
         Some(l1_block_info)
@@ -111,7 +111,7 @@ impl TryFrom<OpRpcBlockReceipt> for OpBlockReceipt {
                     return Err(ConversionError::MissingStateRootOrStatus);
                 };
 
-                (execution, None)
+                (execution, Some(value.l1_block_info))
             }
             transaction::OpTransactionType::Eip1559
             | transaction::OpTransactionType::Eip2930
@@ -156,6 +156,8 @@ impl TryFrom<OpRpcBlockReceipt> for OpBlockReceipt {
                 contract_address: value.contract_address,
                 gas_used: value.gas_used,
                 effective_gas_price: value.effective_gas_price,
+                blob_gas_used: None,
+                blob_gas_price: None,
             },
         };
 
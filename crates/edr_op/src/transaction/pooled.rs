@@ -1,9 +1,11 @@
 use edr_chain_spec::ExecutableTransaction;
 use edr_primitives::{Address, Bytes, B256, U256};
-use edr_provider::spec::HardforkValidationData;
+use edr_provider::spec::{HardforkValidationData, PooledBlobSidecar};
 pub use edr_transaction::pooled::{Eip155, Eip1559, Eip2930, Eip4844, Eip7702, Legacy};
 use edr_transaction::{
-    pooled::eip4844::Blob, signed::PreOrPostEip155, IsEip155, TxKind, INVALID_TX_TYPE_ERROR_MESSAGE,
+    pooled::eip4844::{Blob, BlobTransactionSidecar},
+    signed::PreOrPostEip155,
+    IsEip155, TxKind, INVALID_TX_TYPE_ERROR_MESSAGE,
 };
 
 use crate::transaction::signed::OpSignedTransaction;
@@ -177,6 +179,15 @@ impl HardforkValidationData for OpPooledTransaction {
     }
 }
 
+impl PooledBlobSidecar for OpPooledTransaction {
+    fn blob_sidecar(&self) -> Option<BlobTransactionSidecar> {
+        match self {
+            OpPooledTransaction::Eip4844(tx) => Some(tx.to_sidecar()),
+            _ => None,
+        }
+    }
+}
+
 impl IsEip155 for OpPooledTransaction {
     fn is_eip155(&self) -> bool {
         matches!(self, OpPooledTransaction::PostEip155Legacy(_))
@@ -10,8 +10,8 @@ use edr_primitives::{Address, Bytes, B256, U256};
 use edr_signer::{FakeableSignature, Signature};
 pub use edr_transaction::signed::{Eip155, Eip1559, Eip2930, Eip4844, Eip7702, Legacy};
 use edr_transaction::{
-    impl_revm_transaction_trait, IsEip4844, IsLegacy, IsSupported, MaybeSignedTransaction,
-    TransactionMut, TransactionType, TxKind, INVALID_TX_TYPE_ERROR_MESSAGE,
+    impl_revm_transaction_trait, IsDeposit, IsEip4844, IsLegacy, IsSupported,
+    MaybeSignedTransaction, TransactionMut, TransactionType, TxKind, INVALID_TX_TYPE_ERROR_MESSAGE,
 };
 
 pub use self::deposit::Deposit;
@@ -148,6 +148,12 @@ impl From<Legacy> for OpSignedTransaction {
     }
 }
 
+impl IsDeposit for OpSignedTransaction {
+    fn is_deposit(&self) -> bool {
+        matches!(self, OpSignedTransaction::Deposit(_))
+    }
+}
+
 impl IsEip4844 for OpSignedTransaction {
     fn is_eip4844(&self) -> bool {
         matches!(self, OpSignedTransaction::Eip4844(_))
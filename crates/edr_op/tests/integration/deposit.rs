@@ -0,0 +1,82 @@
+use std::sync::OnceLock;
+
+use edr_op::{
+    transaction::{pooled::OpPooledTransaction, signed::Deposit},
+    OpChainSpec,
+};
+use edr_primitives::{address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider_for_chain_spec, create_test_config, request},
+    MethodInvocation, Provider,
+};
+use edr_transaction::TxKind;
+
+const MINT: u128 = 1_000_000_000_000_000_000;
+const VALUE: u64 = 1_000;
+
+fn create_provider() -> anyhow::Result<Provider<OpChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = edr_op::Hardfork::ISTHMUS;
+
+    create_provider_for_chain_spec(config)
+}
+
+fn deposit_transaction() -> Deposit {
+    Deposit {
+        source_hash: B256::repeat_byte(0x01),
+        from: address!("0x0000000000000000000000000000000000000d3b"),
+        to: TxKind::Call(address!("0x0000000000000000000000000000000000000bee")),
+        mint: MINT,
+        value: U256::from(VALUE),
+        gas_limit: 100_000,
+        is_system_tx: false,
+        data: Bytes::new(),
+        hash: OnceLock::new(),
+        rlp_encoding: OnceLock::new(),
+    }
+}
+
+fn send_raw_transaction(
+    provider: &Provider<OpChainSpec>,
+    transaction: OpPooledTransaction,
+) -> anyhow::Result<B256> {
+    let raw_transaction = alloy_rlp::encode(transaction);
+
+    request(
+        provider,
+        MethodInvocation::SendRawTransaction(raw_transaction.into()),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deposit_mints_and_pays_no_fees() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    // Deposits should be mined immediately, regardless of auto-mining.
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let deposit = deposit_transaction();
+    let sender = deposit.from;
+    let recipient = *deposit.to.to().expect("deposit is a call");
+
+    let transaction_hash = send_raw_transaction(&provider, OpPooledTransaction::Deposit(deposit))?;
+
+    let sender_balance: U256 = request(&provider, MethodInvocation::GetBalance(sender, None))?;
+    assert_eq!(sender_balance, U256::from(MINT - u128::from(VALUE)));
+
+    let recipient_balance: U256 =
+        request(&provider, MethodInvocation::GetBalance(recipient, None))?;
+    assert_eq!(recipient_balance, U256::from(VALUE));
+
+    let receipt: edr_op::rpc::OpRpcBlockReceipt = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+
+    assert_eq!(receipt.transaction_type, Some(Deposit::TYPE));
+    assert_eq!(receipt.status, Some(true));
+    assert_eq!(receipt.deposit_nonce, Some(0));
+    assert_eq!(receipt.deposit_receipt_version, Some(1));
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::sync::OnceLock;
+
+use edr_chain_l1::rpc::TransactionRequest;
+use edr_defaults::SECRET_KEYS;
+use edr_eth::PreEip1898BlockSpec;
+use edr_op::{
+    transaction::{pooled::OpPooledTransaction, signed::Deposit},
+    OpChainSpec,
+};
+use edr_primitives::{address, Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider_for_chain_spec, create_test_config, request},
+    MethodInvocation, Provider,
+};
+use edr_test_utils::secret_key::secret_key_to_address;
+use edr_transaction::TxKind;
+
+const RECIPIENT: Address = address!("0x0000000000000000000000000000000000000bee");
+
+fn create_provider() -> anyhow::Result<Provider<OpChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = edr_op::Hardfork::ISTHMUS;
+
+    create_provider_for_chain_spec(config)
+}
+
+fn deposit(source_hash_byte: u8) -> Deposit {
+    Deposit {
+        source_hash: B256::repeat_byte(source_hash_byte),
+        from: address!("0x0000000000000000000000000000000000000d3b"),
+        to: TxKind::Call(RECIPIENT),
+        mint: 1_000_000_000_000_000_000,
+        value: U256::from(1_000),
+        gas_limit: 100_000,
+        is_system_tx: false,
+        data: Bytes::new(),
+        hash: OnceLock::new(),
+        rlp_encoding: OnceLock::new(),
+    }
+}
+
+fn encode_deposit(deposit: Deposit) -> Bytes {
+    alloy_rlp::encode(OpPooledTransaction::Deposit(deposit)).into()
+}
+
+fn raw_deposit(source_hash_byte: u8) -> Bytes {
+    encode_deposit(deposit(source_hash_byte))
+}
+
+fn enqueue_deposit(provider: &Provider<OpChainSpec>, raw_deposit: Bytes) -> anyhow::Result<B256> {
+    request(provider, MethodInvocation::EnqueueDeposit(raw_deposit))
+}
+
+fn latest_block_transactions(provider: &Provider<OpChainSpec>) -> anyhow::Result<Vec<B256>> {
+    let block: serde_json::Value = request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )?;
+
+    Ok(serde_json::from_value(block["transactions"].clone())?)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn enqueued_deposits_are_included_at_top_of_next_block() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let first_deposit_hash = enqueue_deposit(&provider, raw_deposit(0x01))?;
+    let second_deposit_hash = enqueue_deposit(&provider, raw_deposit(0x02))?;
+
+    // Enqueued deposits are not mined until the next block
+    let receipt: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(first_deposit_hash),
+    )?;
+    assert!(receipt.is_none());
+
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: secret_key_to_address(SECRET_KEYS[0])?,
+            to: Some(RECIPIENT),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    assert_eq!(
+        latest_block_transactions(&provider)?,
+        vec![first_deposit_hash, second_deposit_hash, transaction_hash]
+    );
+
+    // Deposits are only included once
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+    assert!(latest_block_transactions(&provider)?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn enqueued_deposits_are_reverted_with_snapshot() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    enqueue_deposit(&provider, raw_deposit(0x01))?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+    assert!(latest_block_transactions(&provider)?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn non_deposit_transactions_cannot_be_enqueued() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let raw_transaction: Bytes = request(
+        &provider,
+        MethodInvocation::SignTransaction(TransactionRequest {
+            from: secret_key_to_address(SECRET_KEYS[0])?,
+            to: Some(RECIPIENT),
+            gas: Some(21_000),
+            nonce: Some(0),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    assert!(enqueue_deposit(&provider, raw_transaction).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mined_deposits_cannot_be_enqueued_again() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    enqueue_deposit(&provider, raw_deposit(0x01))?;
+    assert!(enqueue_deposit(&provider, raw_deposit(0x01)).is_err());
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+    assert!(enqueue_deposit(&provider, raw_deposit(0x01)).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deposits_exceeding_block_gas_limit_cannot_be_enqueued() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let mut deposit = deposit(0x01);
+    deposit.gas_limit = u64::MAX;
+
+    assert!(enqueue_deposit(&provider, encode_deposit(deposit)).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_deposit_does_not_prevent_mining() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    // System transactions are invalid after the Regolith hardfork
+    let mut invalid_deposit = deposit(0x01);
+    invalid_deposit.is_system_tx = true;
+
+    let invalid_deposit_hash = enqueue_deposit(&provider, encode_deposit(invalid_deposit))?;
+    let valid_deposit_hash = enqueue_deposit(&provider, raw_deposit(0x02))?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+    assert_eq!(
+        latest_block_transactions(&provider)?,
+        vec![valid_deposit_hash]
+    );
+
+    let receipt: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(invalid_deposit_hash),
+    )?;
+    assert!(receipt.is_none());
+
+    // The invalid deposit was dropped from the queue
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+    assert!(latest_block_transactions(&provider)?.is_empty());
+
+    Ok(())
+}
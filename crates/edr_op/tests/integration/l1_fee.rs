@@ -0,0 +1,112 @@
+use edr_chain_l1::rpc::TransactionRequest;
+use edr_defaults::SECRET_KEYS;
+use edr_op::{predeploys::L1_BLOCK_PREDEPLOY_ADDRESS, rpc::OpRpcBlockReceipt, OpChainSpec};
+use edr_primitives::{address, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider_for_chain_spec, create_test_config, request},
+    MethodInvocation, Provider,
+};
+use edr_test_utils::secret_key::secret_key_to_address;
+
+const L1_BASE_FEE_STORAGE_INDEX: u64 = 1;
+const L1_FEE_SCALARS_STORAGE_INDEX: u64 = 3;
+
+const L1_BASE_FEE: u128 = 1_000_000_000;
+const L1_BASE_FEE_SCALAR: u32 = 1_000_000;
+
+fn create_provider() -> anyhow::Result<Provider<OpChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = edr_op::Hardfork::ISTHMUS;
+
+    let provider = create_provider_for_chain_spec(config)?;
+
+    set_storage(
+        &provider,
+        L1_BASE_FEE_STORAGE_INDEX,
+        U256::from(L1_BASE_FEE),
+    )?;
+
+    // The base fee scalar occupies bytes 16..20 of the packed fee scalars slot
+    let mut fee_scalars = [0u8; 32];
+    fee_scalars[16..20].copy_from_slice(&L1_BASE_FEE_SCALAR.to_be_bytes());
+    set_storage(
+        &provider,
+        L1_FEE_SCALARS_STORAGE_INDEX,
+        U256::from_be_bytes(fee_scalars),
+    )?;
+
+    Ok(provider)
+}
+
+fn set_storage(provider: &Provider<OpChainSpec>, index: u64, value: U256) -> anyhow::Result<()> {
+    request::<bool, _, _>(
+        provider,
+        MethodInvocation::SetStorageAt(L1_BLOCK_PREDEPLOY_ADDRESS, U256::from(index), value),
+    )?;
+
+    Ok(())
+}
+
+fn send_transaction(
+    provider: &Provider<OpChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<OpRpcBlockReceipt> {
+    let transaction_hash: B256 = request(provider, MethodInvocation::SendTransaction(transaction))?;
+
+    request(
+        provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )
+}
+
+fn assert_l1_fee_fields(receipt: &OpRpcBlockReceipt) {
+    let l1_block_info = &receipt.l1_block_info;
+    assert_eq!(l1_block_info.l1_gas_price, Some(L1_BASE_FEE));
+    assert_eq!(
+        l1_block_info.l1_base_fee_scalar,
+        Some(u128::from(L1_BASE_FEE_SCALAR))
+    );
+    assert!(l1_block_info
+        .l1_gas_used
+        .is_some_and(|gas_used| gas_used > 0));
+    assert!(l1_block_info.l1_fee.is_some_and(|fee| fee > 0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn receipts_of_legacy_transactions_include_l1_fee() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let receipt = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: secret_key_to_address(SECRET_KEYS[0])?,
+            to: Some(address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")),
+            gas_price: Some(10_000_000_000),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    assert_eq!(receipt.transaction_type, Some(0));
+    assert_l1_fee_fields(&receipt);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn receipts_of_eip1559_transactions_include_l1_fee() -> anyhow::Result<()> {
+    let provider = create_provider()?;
+
+    let receipt = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: secret_key_to_address(SECRET_KEYS[0])?,
+            to: Some(address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    assert_eq!(receipt.transaction_type, Some(2));
+    assert_l1_fee_fields(&receipt);
+
+    Ok(())
+}
@@ -1,10 +1,14 @@
+mod deposit;
+mod deposit_queue;
 mod dynamic_base_fee_params;
 mod full_block;
 mod hardfork_activation;
 mod holocene;
 mod isthmus_operator_fee;
+mod l1_fee;
 mod provider;
 mod rpc;
+mod withdrawals;
 
 #[cfg(feature = "test-remote")]
 mod op {
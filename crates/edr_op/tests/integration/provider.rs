@@ -13,6 +13,7 @@ use edr_provider::{
     time::CurrentTime,
     ForkConfig, MethodInvocation, NoopLogger, Provider, ProviderRequest,
 };
+use edr_rpc_eth::client::TransportConfig;
 use edr_solidity::contract_decoder::ContractDecoder;
 use tokio::runtime;
 
@@ -31,7 +32,10 @@ async fn sepolia_call_with_remote_chain_id() -> anyhow::Result<()> {
         cache_dir: edr_defaults::CACHE_DIR.into(),
         chain_overrides: HashMap::default(),
         http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
         url: op::sepolia_url(),
+        verified_sources: None,
     }));
 
     // Set a different chain ID than the forked chain ID
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use edr_block_header::Withdrawal;
+use edr_op::OpChainSpec;
+use edr_primitives::{Address, U256};
+use edr_provider::{
+    test_utils::create_test_config, time::CurrentTime, MethodInvocation, NoopLogger, Provider,
+    ProviderRequest,
+};
+use edr_solidity::contract_decoder::ContractDecoder;
+use tokio::runtime;
+
+const WITHDRAWAL_AMOUNT_GWEI: u64 = 1_000_000_000;
+
+fn request<T: serde::de::DeserializeOwned>(
+    provider: &Provider<OpChainSpec>,
+    method: MethodInvocation<OpChainSpec>,
+) -> anyhow::Result<T> {
+    let result = provider.handle_request(ProviderRequest::with_single(method))?;
+
+    Ok(serde_json::from_value(result.result)?)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_withdrawals_are_credited() -> anyhow::Result<()> {
+    let mut config = create_test_config::<edr_op::Hardfork>();
+    config.hardfork = edr_op::Hardfork::ISTHMUS;
+
+    let logger = Box::new(NoopLogger::<OpChainSpec>::default());
+    let subscriber = Box::new(|_event| {});
+    let provider = Provider::new(
+        runtime::Handle::current(),
+        logger,
+        subscriber,
+        config,
+        Arc::<ContractDecoder>::default(),
+        CurrentTime,
+    )?;
+
+    let recipient = Address::random();
+    request::<bool>(
+        &provider,
+        MethodInvocation::SetNextBlockWithdrawals(vec![Withdrawal {
+            index: 0,
+            validator_index: 1,
+            address: recipient,
+            amount: WITHDRAWAL_AMOUNT_GWEI,
+        }]),
+    )?;
+
+    request::<String>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let balance: U256 = request(&provider, MethodInvocation::GetBalance(recipient, None))?;
+    assert_eq!(
+        balance,
+        U256::from(WITHDRAWAL_AMOUNT_GWEI) * U256::from(1_000_000_000u64)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,321 @@
+//! Index of the transactions in locally mined blocks that involve each
+//! address, used by `explorer_getAddressTransactions` and the Otterscan
+//! transaction searches.
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use edr_chain_spec::{ExecutableTransaction, HaltReasonTrait};
+use edr_primitives::{Address, HashMap, HashSet};
+use edr_tracing::{Trace, TraceMessage};
+use edr_transaction::TxKind;
+
+use crate::requests::explorer::{AddressRelation, AddressTransaction, AddressTransactionsPage};
+
+#[derive(Debug, Default)]
+pub(crate) struct AddressIndex {
+    /// Transactions that involve each address, in the order in which they
+    /// were mined.
+    transactions: HashMap<Address, Vec<AddressTransaction>>,
+    /// The addresses that are involved in the transactions of each block.
+    block_addresses: BTreeMap<u64, HashSet<Address>>,
+    /// The number of transactions in pruned blocks that involved each
+    /// address.
+    pruned_counts: HashMap<Address, PrunedTransactionCounts>,
+    /// The number of the most recently indexed block.
+    last_block_number: Option<u64>,
+}
+
+/// The number of pruned transactions that involved an address, in total and
+/// per relation, along with the numbers of the blocks that contained them.
+#[derive(Clone, Debug, Default)]
+struct PrunedTransactionCounts {
+    total: u64,
+    sent: u64,
+    received: u64,
+    internal: u64,
+    block_numbers: Option<RangeInclusive<u64>>,
+}
+
+impl PrunedTransactionCounts {
+    fn add(&mut self, transaction: &AddressTransaction) {
+        self.block_numbers = Some(self.block_numbers.as_ref().map_or(
+            transaction.block_number..=transaction.block_number,
+            |block_numbers| *block_numbers.start()..=transaction.block_number,
+        ));
+
+        self.total += 1;
+        self.sent += u64::from(transaction.sent);
+        self.received += u64::from(transaction.received);
+        self.internal += u64::from(transaction.internal);
+    }
+
+    fn count(&self, relation: Option<AddressRelation>) -> u64 {
+        match relation {
+            None => self.total,
+            Some(AddressRelation::Sent) => self.sent,
+            Some(AddressRelation::Received) => self.received,
+            Some(AddressRelation::Internal) => self.internal,
+        }
+    }
+}
+
+impl AddressIndex {
+    /// Indexes the transactions of a newly mined block, using the call trees
+    /// that were collected while mining it.
+    ///
+    /// Any previously indexed blocks with the same or a higher number have
+    /// been removed from the blockchain - e.g. by `evm_revert` - so their
+    /// transactions are removed from the index.
+    pub fn insert_block<HaltReasonT: HaltReasonTrait>(
+        &mut self,
+        block_number: u64,
+        transactions: &[impl ExecutableTransaction],
+        transaction_traces: &[Trace<HaltReasonT>],
+    ) {
+        if self
+            .last_block_number
+            .is_some_and(|last_block_number| last_block_number >= block_number)
+        {
+            self.transactions.retain(|_address, transactions| {
+                transactions.retain(|transaction| transaction.block_number < block_number);
+                !transactions.is_empty()
+            });
+            self.block_addresses.split_off(&block_number);
+        }
+        self.last_block_number = Some(block_number);
+
+        // Transactions that were rejected while building the block also have
+        // traces, so each transaction is matched with the next trace of its
+        // top-level call.
+        let mut remaining_traces = transaction_traces;
+        let mut block_addresses = HashSet::default();
+
+        for (transaction_index, transaction) in transactions.iter().enumerate() {
+            let trace = remaining_traces
+                .iter()
+                .position(|trace| is_trace_of(trace, transaction))
+                .map(|position| {
+                    let trace = &remaining_traces[position];
+                    remaining_traces = &remaining_traces[position + 1..];
+                    trace
+                });
+
+            let mut relations: HashMap<Address, AddressTransaction> = HashMap::default();
+            let mut relate = |address: Address, relation: AddressRelation| {
+                let entry = relations
+                    .entry(address)
+                    .or_insert_with(|| AddressTransaction {
+                        block_number,
+                        transaction_hash: *transaction.transaction_hash(),
+                        transaction_index: transaction_index as u64,
+                        sent: false,
+                        received: false,
+                        internal: false,
+                    });
+
+                match relation {
+                    AddressRelation::Sent => entry.sent = true,
+                    AddressRelation::Received => entry.received = true,
+                    AddressRelation::Internal => entry.internal = true,
+                }
+            };
+
+            relate(*transaction.caller(), AddressRelation::Sent);
+            if let TxKind::Call(to) = transaction.kind() {
+                relate(to, AddressRelation::Received);
+            }
+
+            if let Some(trace) = trace {
+                relate_call_tree(trace, &mut relate);
+            }
+
+            for (address, transaction) in relations {
+                block_addresses.insert(address);
+                self.transactions
+                    .entry(address)
+                    .or_default()
+                    .push(transaction);
+            }
+        }
+
+        self.block_addresses.insert(block_number, block_addresses);
+    }
+
+    /// Removes the transactions in blocks before the provided block number.
+    pub fn prune_blocks_before(&mut self, block_number: u64) {
+        let retained_block_addresses = self.block_addresses.split_off(&block_number);
+        let pruned_block_addresses =
+            std::mem::replace(&mut self.block_addresses, retained_block_addresses);
+
+        for address in pruned_block_addresses.into_values().flatten() {
+            let Some(transactions) = self.transactions.get_mut(&address) else {
+                continue;
+            };
+
+            let first_retained_index =
+                transactions.partition_point(|transaction| transaction.block_number < block_number);
+
+            let pruned_counts = self.pruned_counts.entry(address).or_default();
+            for transaction in transactions.drain(..first_retained_index) {
+                pruned_counts.add(&transaction);
+            }
+
+            if transactions.is_empty() {
+                self.transactions.remove(&address);
+            }
+        }
+    }
+
+    /// Returns the transactions that involve the provided address, in the
+    /// order in which they were mined, along with the numbers of the first and
+    /// last pruned blocks that contained transactions that involved it.
+    /// Transactions in blocks after `last_block_number` are ignored.
+    pub fn retained_transactions(
+        &self,
+        address: &Address,
+        last_block_number: u64,
+    ) -> (&[AddressTransaction], Option<RangeInclusive<u64>>) {
+        let transactions = self.transactions_up_to(address, last_block_number);
+        let pruned_block_numbers = self
+            .pruned_counts
+            .get(address)
+            .and_then(|pruned_counts| pruned_counts.block_numbers.clone());
+
+        (transactions, pruned_block_numbers)
+    }
+
+    /// Returns a page of the transactions that involve the provided address,
+    /// from the most to the least recent. Transactions in blocks after
+    /// `last_block_number` are ignored.
+    ///
+    /// Returns `None` if the page includes transactions in pruned blocks.
+    pub fn transactions(
+        &self,
+        address: &Address,
+        relation: Option<AddressRelation>,
+        last_block_number: u64,
+        page: u64,
+        page_size: u64,
+    ) -> Option<AddressTransactionsPage> {
+        let transactions = self.transactions_up_to(address, last_block_number);
+
+        let matching = move || {
+            transactions.iter().rev().filter(move |transaction| {
+                relation.is_none_or(|relation| transaction.has_relation(relation))
+            })
+        };
+
+        let retained = if relation.is_some() {
+            matching().count()
+        } else {
+            transactions.len()
+        } as u64;
+
+        // Pruned transactions precede all retained transactions
+        let pruned = self
+            .pruned_counts
+            .get(address)
+            .map_or(0, |pruned_counts| pruned_counts.count(relation));
+        let total = retained + pruned;
+
+        let first_index = page.saturating_mul(page_size);
+        if first_index.saturating_add(page_size) > retained && first_index < total {
+            return None;
+        }
+
+        let transactions = matching()
+            .skip(usize::try_from(first_index).unwrap_or(usize::MAX))
+            .take(usize::try_from(page_size).unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+
+        Some(AddressTransactionsPage {
+            transactions,
+            total,
+        })
+    }
+
+    /// Returns the transactions that involve the provided address in blocks up
+    /// to and including `last_block_number`.
+    fn transactions_up_to(
+        &self,
+        address: &Address,
+        last_block_number: u64,
+    ) -> &[AddressTransaction] {
+        self.transactions
+            .get(address)
+            .map_or(&[][..], |transactions| {
+                let end = transactions
+                    .partition_point(|transaction| transaction.block_number <= last_block_number);
+
+                &transactions[..end]
+            })
+    }
+}
+
+/// Whether the provided trace's top-level call is that of the provided
+/// transaction.
+fn is_trace_of<HaltReasonT: HaltReasonTrait>(
+    trace: &Trace<HaltReasonT>,
+    transaction: &impl ExecutableTransaction,
+) -> bool {
+    let Some(TraceMessage::Before(message)) = trace.messages.first() else {
+        return false;
+    };
+
+    let to = match transaction.kind() {
+        TxKind::Call(to) => Some(to),
+        TxKind::Create => None,
+    };
+
+    message.caller == *transaction.caller()
+        && message.to == to
+        && message.value == *transaction.value()
+        && message.data == *transaction.data()
+}
+
+/// Relates the addresses that are involved in a transaction's call tree to the
+/// transaction.
+fn relate_call_tree<HaltReasonT: HaltReasonTrait>(
+    trace: &Trace<HaltReasonT>,
+    relate: &mut impl FnMut(Address, AddressRelation),
+) {
+    let mut depth = 0usize;
+
+    for message in &trace.messages {
+        match message {
+            TraceMessage::Before(message) => {
+                // The transaction's own call is related as sent or received
+                if depth > 0 {
+                    relate(message.caller, AddressRelation::Internal);
+                    if let Some(to) = message.to {
+                        relate(to, AddressRelation::Internal);
+                    }
+                    if let Some(code_address) = message.code_address {
+                        relate(code_address, AddressRelation::Internal);
+                    }
+                }
+
+                depth += 1;
+            }
+            TraceMessage::After(message) => {
+                depth = depth.saturating_sub(1);
+
+                if let Some(contract_address) = message.contract_address {
+                    let relation = if depth == 0 {
+                        AddressRelation::Received
+                    } else {
+                        AddressRelation::Internal
+                    };
+
+                    relate(contract_address, relation);
+                }
+            }
+            TraceMessage::SelfDestruct(message) => {
+                relate(message.contract, AddressRelation::Internal);
+                relate(message.target, AddressRelation::Internal);
+            }
+            TraceMessage::Step(_) => {}
+        }
+    }
+}
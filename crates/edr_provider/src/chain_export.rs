@@ -0,0 +1,222 @@
+//! Versioned binary format of the chains exported by `hardhat_exportChain` and
+//! imported by `hardhat_importChain`.
+//!
+//! An export consists of [`CHAIN_EXPORT_MAGIC`], followed by a single version
+//! byte and the gzip-compressed JSON encoding of a [`ChainExport`].
+
+use std::io::Read as _;
+
+use edr_primitives::{Address, Bytes, B256};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::requests::hardhat::rpc_types::StateDump;
+
+/// The magic bytes that prefix every chain export.
+pub const CHAIN_EXPORT_MAGIC: &[u8; 8] = b"EDRCHAIN";
+
+/// The version of the chain export format that is produced by this crate.
+pub const CHAIN_EXPORT_VERSION: u8 = 1;
+
+/// The maximum size of a decompressed chain export, which protects against
+/// exports that decompress to an excessive size.
+pub const MAX_DECOMPRESSED_CHAIN_EXPORT_SIZE: u64 = 1 << 30;
+
+/// A local chain, consisting of the blocks that were mined on top of a base
+/// block - i.e. the genesis block or the fork block - and the resulting state.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainExport<BlockT, ReceiptT> {
+    /// The chain ID of the exported chain.
+    pub chain_id: u64,
+    /// The number of the block on top of which the local blocks were mined.
+    pub base_block_number: u64,
+    /// The hash of the block on top of which the local blocks were mined.
+    pub base_block_hash: B256,
+    /// The local modifications of the base block's state, e.g. using
+    /// `hardhat_setBalance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_state_overrides: Option<StateDump>,
+    /// The local blocks, in ascending order.
+    pub blocks: Vec<ExportedBlock<BlockT, ReceiptT>>,
+    /// The state after the last block, including local modifications.
+    pub state: StateDump,
+}
+
+/// A block of a [`ChainExport`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedBlock<BlockT, ReceiptT> {
+    /// The JSON-RPC block, including its transactions.
+    pub block: BlockT,
+    /// The JSON-RPC receipts of the block's transactions.
+    pub receipts: Vec<ReceiptT>,
+    /// The local modifications of the block's state, e.g. using
+    /// `hardhat_setBalance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateDump>,
+}
+
+impl<BlockT: Serialize, ReceiptT: Serialize> ChainExport<BlockT, ReceiptT> {
+    /// Encodes the chain in the versioned binary format.
+    pub fn encode(&self) -> Result<Bytes, serde_json::Error> {
+        let mut encoded = CHAIN_EXPORT_MAGIC.to_vec();
+        encoded.push(CHAIN_EXPORT_VERSION);
+
+        let mut encoder = GzEncoder::new(encoded, Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+
+        let encoded = encoder.finish().expect("Writing to a vector cannot fail");
+
+        Ok(Bytes::from(encoded))
+    }
+}
+
+impl<BlockT: DeserializeOwned, ReceiptT: DeserializeOwned> ChainExport<BlockT, ReceiptT> {
+    /// Decodes a chain from the versioned binary format.
+    pub fn decode(encoded: &[u8]) -> Result<Self, ChainImportError> {
+        Self::decode_with_max_size(encoded, MAX_DECOMPRESSED_CHAIN_EXPORT_SIZE)
+    }
+
+    fn decode_with_max_size(encoded: &[u8], max_size: u64) -> Result<Self, ChainImportError> {
+        let encoded = encoded
+            .strip_prefix(CHAIN_EXPORT_MAGIC.as_slice())
+            .ok_or(ChainImportError::InvalidMagic)?;
+
+        let (&version, encoded) = encoded
+            .split_first()
+            .ok_or(ChainImportError::InvalidMagic)?;
+
+        if version != CHAIN_EXPORT_VERSION {
+            return Err(ChainImportError::UnsupportedVersion { version });
+        }
+
+        // Reading one byte past the maximum size detects oversized exports
+        // without decompressing them entirely.
+        let mut json = Vec::new();
+        GzDecoder::new(encoded)
+            .take(max_size.saturating_add(1))
+            .read_to_end(&mut json)
+            .map_err(ChainImportError::Decompression)?;
+
+        if json.len() as u64 > max_size {
+            return Err(ChainImportError::DecompressedSizeExceeded { max_size });
+        }
+
+        serde_json::from_slice(&json).map_err(ChainImportError::Deserialization)
+    }
+}
+
+/// An error that occurred while importing a chain.
+#[derive(Debug, thiserror::Error)]
+pub enum ChainImportError {
+    /// The base block of the export doesn't match the provider's.
+    #[error(
+        "The chain was exported on top of block {expected_number} ({expected_hash}), but the provider's base block is {actual_number} ({actual_hash})"
+    )]
+    BaseBlockMismatch {
+        /// The number of the exported base block.
+        expected_number: u64,
+        /// The hash of the exported base block.
+        expected_hash: B256,
+        /// The number of the provider's base block.
+        actual_number: u64,
+        /// The hash of the provider's base block.
+        actual_hash: B256,
+    },
+    /// A re-mined block doesn't match the exported block.
+    #[error("Imported block {block_number} has hash {actual}, but the exported block has hash {expected}")]
+    BlockMismatch {
+        /// The number of the block.
+        block_number: u64,
+        /// The hash of the exported block.
+        expected: B256,
+        /// The hash of the imported block.
+        actual: B256,
+    },
+    /// The chain ID of the export doesn't match the provider's.
+    #[error(
+        "The chain was exported with chain ID {expected}, but the provider has chain ID {actual}"
+    )]
+    ChainIdMismatch {
+        /// The chain ID of the export.
+        expected: u64,
+        /// The chain ID of the provider.
+        actual: u64,
+    },
+    /// The export decompresses to more than the maximum size.
+    #[error("The decompressed chain export exceeds the maximum size of {max_size} bytes")]
+    DecompressedSizeExceeded {
+        /// The maximum size in bytes.
+        max_size: u64,
+    },
+    /// The export could not be decompressed.
+    #[error("Failed to decompress chain export: {0}")]
+    Decompression(std::io::Error),
+    /// The export could not be deserialized.
+    #[error("Failed to deserialize chain export: {0}")]
+    Deserialization(serde_json::Error),
+    /// An exported block could not be converted.
+    #[error("Exported block {block_number} is invalid: {message}")]
+    InvalidBlock {
+        /// The number of the block.
+        block_number: u64,
+        /// The conversion error message.
+        message: String,
+    },
+    /// The export doesn't start with the magic bytes.
+    #[error("The provided data is not a chain export")]
+    InvalidMagic,
+    /// The provider already has local blocks.
+    #[error(
+        "Cannot import a chain after local blocks have been mined. The base block is {base_block_number}, but the latest block is {latest_block_number}"
+    )]
+    LocalBlocksExist {
+        /// The number of the base block.
+        base_block_number: u64,
+        /// The number of the latest block.
+        latest_block_number: u64,
+    },
+    /// The state after importing the blocks doesn't match the exported state.
+    #[error("The imported state of account {address} doesn't match the exported state")]
+    StateMismatch {
+        /// The address of the mismatching account.
+        address: Address,
+    },
+    /// The export was created with an unsupported version of the format.
+    #[error("Unsupported chain export version {version}. Expected version {CHAIN_EXPORT_VERSION}")]
+    UnsupportedVersion {
+        /// The version of the export.
+        version: u8,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use serde::de::IgnoredAny;
+
+    use super::*;
+
+    #[test]
+    fn decode_rejects_oversized_export() -> anyhow::Result<()> {
+        const MAX_SIZE: u64 = 1024;
+
+        let mut encoded = CHAIN_EXPORT_MAGIC.to_vec();
+        encoded.push(CHAIN_EXPORT_VERSION);
+
+        let mut encoder = GzEncoder::new(encoded, Compression::default());
+        encoder.write_all(&[b' '; 2 * MAX_SIZE as usize])?;
+        let encoded = encoder.finish()?;
+
+        let result =
+            ChainExport::<IgnoredAny, IgnoredAny>::decode_with_max_size(&encoded, MAX_SIZE);
+        assert!(matches!(
+            result,
+            Err(ChainImportError::DecompressedSizeExceeded { max_size: MAX_SIZE })
+        ));
+
+        Ok(())
+    }
+}
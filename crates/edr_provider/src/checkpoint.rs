@@ -0,0 +1,115 @@
+//! Named checkpoints that are stored as files in the configured checkpoint
+//! directory by `hardhat_saveCheckpoint` and restored by
+//! `hardhat_loadCheckpoint`.
+//!
+//! A checkpoint contains the chain in the format of `hardhat_exportChain`, so
+//! it can be restored by any provider with the same base block - e.g. in a
+//! separate process.
+
+use std::{
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+};
+
+use tempfile::NamedTempFile;
+
+/// The extension of checkpoint files.
+const CHECKPOINT_EXTENSION: &str = "edrchain";
+
+/// An error that occurred while saving or loading a checkpoint.
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    /// The checkpoint name contains characters other than ASCII
+    /// alphanumerics, `-`, `_`, and `.`, or starts with a `.`.
+    #[error(
+        "Invalid checkpoint name '{name}'. Only ASCII letters, digits, '-', '_', and '.' are allowed, and the name cannot start with '.'."
+    )]
+    InvalidName { name: String },
+    /// An I/O error occurred while accessing the checkpoint file.
+    #[error("Failed to access checkpoint file {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    /// No checkpoint with the provided name exists.
+    #[error("Checkpoint '{name}' does not exist at {path:?}")]
+    NotFound { name: String, path: PathBuf },
+}
+
+/// Writes the provided encoded chain to the checkpoint with the provided name,
+/// replacing any existing checkpoint with the same name.
+///
+/// The file is written to a uniquely named temporary file first, so concurrent
+/// readers never observe a partially written checkpoint and concurrent writers
+/// don't interfere with each other.
+pub(crate) fn write_checkpoint(
+    checkpoint_dir: &Path,
+    name: &str,
+    chain: &[u8],
+) -> Result<(), CheckpointError> {
+    let path = checkpoint_path(checkpoint_dir, name)?;
+
+    fs::create_dir_all(checkpoint_dir).map_err(|source| CheckpointError::Io {
+        path: checkpoint_dir.to_path_buf(),
+        source,
+    })?;
+
+    // The temporary file is removed if it isn't persisted
+    let temp_file = NamedTempFile::new_in(checkpoint_dir)
+        .and_then(|mut temp_file| {
+            temp_file.write_all(chain)?;
+            Ok(temp_file)
+        })
+        .map_err(|source| CheckpointError::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+    temp_file
+        .persist(&path)
+        .map_err(|error| CheckpointError::Io {
+            path,
+            source: error.error,
+        })?;
+
+    Ok(())
+}
+
+/// Reads the encoded chain of the checkpoint with the provided name.
+pub(crate) fn read_checkpoint(
+    checkpoint_dir: &Path,
+    name: &str,
+) -> Result<Vec<u8>, CheckpointError> {
+    let path = checkpoint_path(checkpoint_dir, name)?;
+
+    fs::read(&path).map_err(|source| {
+        if source.kind() == io::ErrorKind::NotFound {
+            CheckpointError::NotFound {
+                name: name.to_owned(),
+                path,
+            }
+        } else {
+            CheckpointError::Io { path, source }
+        }
+    })
+}
+
+/// Returns the path of the checkpoint file with the provided name, ensuring
+/// that it's located inside the checkpoint directory.
+fn checkpoint_path(checkpoint_dir: &Path, name: &str) -> Result<PathBuf, CheckpointError> {
+    let is_valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if !is_valid {
+        return Err(CheckpointError::InvalidName {
+            name: name.to_owned(),
+        });
+    }
+
+    Ok(checkpoint_dir.join(format!("{name}.{CHECKPOINT_EXTENSION}")))
+}
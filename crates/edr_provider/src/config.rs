@@ -1,17 +1,25 @@
-use std::{num::NonZeroU64, path::PathBuf, time::SystemTime};
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::SystemTime,
+};
 
 use edr_block_header::BlobGas;
 use edr_block_miner::MineOrdering;
 use edr_chain_config::ChainOverride;
 use edr_eip1559::BaseFeeParams;
-use edr_precompile::PrecompileFn;
-use edr_primitives::{Address, Bytecode, ChainId, HashMap, B256, U256};
+use edr_mem_pool::MemPoolPolicy;
+use edr_precompile::CustomPrecompile;
+use edr_primitives::{Address, Bytecode, Bytes, ChainId, HashMap, HashSet, B256, U256};
+use edr_rpc_eth::client::TransportConfig;
 use edr_state_api::EvmStorage;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     observability::ObservabilityConfig, requests::IntervalConfig as IntervalConfigRequest,
+    time::TimeMode,
 };
 
 /// Specification of overrides for an account and its storage.
@@ -41,7 +49,33 @@ pub struct Fork<HardforkT> {
     pub cache_dir: PathBuf,
     pub chain_overrides: HashMap<ChainId, ChainOverride<HardforkT>>,
     pub http_headers: Option<std::collections::HashMap<String, String>>,
+    /// Whether to track the head of the remote blockchain. If enabled, the
+    /// fork block advances to the most recent block that is safe from reorgs,
+    /// for as long as no local blocks have been mined.
+    #[serde(default)]
+    pub live: bool,
+    /// Policy for retries, rate limiting, timeouts, and fallback URLs of
+    /// requests to the remote node.
+    #[serde(default)]
+    pub transport: TransportConfig,
     pub url: String,
+    /// If present, the verified sources of forked contracts are fetched to
+    /// decode their traces.
+    #[serde(default)]
+    pub verified_sources: Option<VerifiedSources>,
+}
+
+/// Configuration for fetching the verified sources of forked contracts from
+/// Sourcify and Etherscan.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedSources {
+    /// The API key for Etherscan. If not provided, only Sourcify is queried.
+    pub etherscan_api_key: Option<String>,
+    /// The URL of the Etherscan API. Defaults to the multichain V2 API.
+    pub etherscan_url: Option<String>,
+    /// The URL of the Sourcify server. Defaults to the public server.
+    pub sourcify_url: Option<String>,
 }
 
 /// Configuration for interval mining.
@@ -92,11 +126,30 @@ impl TryInto<Option<Interval>> for IntervalConfigRequest {
     }
 }
 
+/// Configuration of an account that pays for the gas of the transactions of a
+/// set of senders, to simulate gasless transactions.
+///
+/// Before a sponsored transaction is added to the mempool, the sponsor
+/// transfers the transaction's maximum gas cost to the sender. Once the
+/// transaction has been mined, the cost of unused gas is refunded to the
+/// sponsor.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasSponsor {
+    /// The account that pays for the gas.
+    pub sponsor: Address,
+    /// The senders whose transactions are sponsored.
+    pub senders: HashSet<Address>,
+}
+
 /// Configuration for the provider's mempool.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MemPool {
     pub order: MineOrdering,
+    /// Policy for replacing, limiting, and expiring transactions
+    #[serde(default)]
+    pub policy: MemPoolPolicy,
 }
 
 /// Configuration for the provider's miner.
@@ -108,32 +161,193 @@ pub struct Mining {
     pub mem_pool: MemPool,
 }
 
+/// Configuration of which block states the provider retains in memory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum StatePruning {
+    /// Retains the states of all blocks, up to `max_cached_states`.
+    #[default]
+    Archive,
+    /// Retains the states of the most recent `retained_blocks` blocks. The
+    /// states of older blocks are reconstructed from their state diffs when
+    /// queried and only the few most recently reconstructed states are
+    /// cached.
+    ///
+    /// The recorded state diffs, blob sidecars, indexed address transactions,
+    /// and indexed token transfers of older blocks are pruned as well, so
+    /// queries for them fail. Blocks themselves are retained, as their state
+    /// diffs are needed to reconstruct states.
+    Prune { retained_blocks: NonZeroU64 },
+}
+
+impl StatePruning {
+    /// Returns the number of the first block whose state is retained, given
+    /// the number of the last block.
+    pub fn first_retained_block_number(&self, last_block_number: u64) -> u64 {
+        match self {
+            StatePruning::Archive => 0,
+            StatePruning::Prune { retained_blocks } => {
+                (last_block_number + 1).saturating_sub(retained_blocks.get())
+            }
+        }
+    }
+
+    /// Returns whether the state of the block with the provided number is
+    /// retained, given the number of the last block.
+    pub fn is_retained(&self, block_number: u64, last_block_number: u64) -> bool {
+        block_number >= self.first_retained_block_number(last_block_number)
+    }
+}
+
+/// Configuration of the JSON-RPC methods that the provider accepts.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodPermissions {
+    /// If set, only the listed methods are accepted.
+    #[serde(default)]
+    pub allowed_methods: Option<HashSet<String>>,
+    /// Methods that are rejected, even if they are listed in
+    /// `allowed_methods`.
+    #[serde(default)]
+    pub denied_methods: HashSet<String>,
+    /// Whether to reject methods that modify the blockchain, the state, the
+    /// mempool, or the provider's configuration - e.g. `eth_sendTransaction`
+    /// or `hardhat_setBalance` - and methods that sign with the provider's
+    /// accounts.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl MethodPermissions {
+    /// Returns whether the method with the provided name is permitted.
+    /// `is_read_only_method` indicates whether the method leaves the
+    /// provider's state unmodified.
+    pub fn is_permitted(&self, method_name: &str, is_read_only_method: bool) -> bool {
+        if self.read_only && !is_read_only_method {
+            return false;
+        }
+
+        if self.denied_methods.contains(method_name) {
+            return false;
+        }
+
+        self.allowed_methods
+            .as_ref()
+            .is_none_or(|allowed_methods| allowed_methods.contains(method_name))
+    }
+}
+
 /// Configuration for the provider
 #[derive(Clone, Debug)]
 pub struct Provider<HardforkT> {
     pub allow_blocks_with_same_timestamp: bool,
+    /// Whether to allow contracts of any size to be deployed, disabling
+    /// EIP-170. Implies `allow_unlimited_init_code_size`.
     pub allow_unlimited_contract_size: bool,
+    /// Whether to allow deployments with init code of any size, disabling
+    /// EIP-3860.
+    pub allow_unlimited_init_code_size: bool,
+    /// Whether to top up the balance of impersonated senders to cover the
+    /// upfront cost of their transactions.
+    pub auto_fund: bool,
+    /// Whether to impersonate every sender that isn't an owned account,
+    /// without requiring `hardhat_impersonateAccount`.
+    pub auto_impersonate: bool,
     /// Whether to return an `Err` when `eth_call` fails
     pub bail_on_call_failure: bool,
     /// Whether to return an `Err` when a `eth_sendTransaction` fails
     pub bail_on_transaction_failure: bool,
     pub base_fee_params: Option<BaseFeeParams<HardforkT>>,
     pub block_gas_limit: NonZeroU64,
+    /// (Block number -> block gas limit) changes that are applied when mining
+    /// new blocks. A scheduled gas limit is used from its block number
+    /// onwards, until it's replaced by a later entry or
+    /// `evm_setBlockGasLimit`.
+    ///
+    /// An entry at or before the latest block at startup, i.e. the genesis
+    /// block or the fork block, replaces `block_gas_limit`. An entry at block
+    /// zero is used as the gas limit of a local genesis block.
+    pub block_gas_limit_schedule: BTreeMap<u64, NonZeroU64>,
     pub chain_id: ChainId,
+    /// The directory in which checkpoints are stored by
+    /// `hardhat_saveCheckpoint` and fixtures are cached by
+    /// `hardhat_runFixture`. A relative path is resolved against the working
+    /// directory when the provider is created.
+    pub checkpoint_dir: PathBuf,
     pub coinbase: Address,
+    /// Whether to deploy the deterministic deployment proxy at
+    /// [`DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS`](crate::DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS)
+    /// in the genesis state, unless `genesis_state` overrides its account.
+    pub deploy_deterministic_deployment_proxy: bool,
+    /// Whether to accept and execute transactions regardless of whether the
+    /// sender's balance covers their upfront cost.
+    pub disable_balance_check: bool,
+    /// Whether to keep the base fee per gas of new blocks equal to their
+    /// parent's, instead of adjusting it based on the parent's gas usage.
+    pub disable_base_fee_adjustment: bool,
+    /// Whether to accept and execute transactions whose gas price - or max fee
+    /// per gas - is below the base fee of the block.
+    pub disable_base_fee_check: bool,
+    /// Whether to accept and mine transactions whose priority fee is below
+    /// `min_gas_price`.
+    pub disable_priority_fee_check: bool,
     pub fork: Option<Fork<HardforkT>>,
+    /// If set, the gas of transactions sent by the configured senders is paid
+    /// by the sponsor account.
+    pub gas_sponsor: Option<GasSponsor>,
+    /// The extra data of the genesis block of a local blockchain. Defaults to
+    /// empty.
+    pub genesis_extra_data: Option<Bytes>,
     pub genesis_state: HashMap<Address, AccountOverride>,
     pub hardfork: HardforkT,
+    /// (Block number -> hardfork) activations for a local blockchain. Blocks
+    /// before the first activation use `hardfork`.
+    ///
+    /// An activation at block zero determines the hardfork of the genesis
+    /// block.
+    pub hardfork_activations: BTreeMap<u64, HardforkT>,
+    /// Whether to index the transactions of mined blocks by the addresses
+    /// they involve, so they can be queried with
+    /// `explorer_getAddressTransactions`, `ots_searchTransactionsBefore`, and
+    /// `ots_searchTransactionsAfter`.
+    pub index_address_transactions: bool,
+    /// Whether to index the ERC-20 and ERC-721 `Transfer` events of mined
+    /// blocks, so they can be queried with `hardhat_tokenTransfers` and
+    /// `hardhat_tokenBalances`.
+    pub index_token_transfers: bool,
     pub initial_base_fee_per_gas: Option<u128>,
     pub initial_blob_gas: Option<BlobGas>,
     pub initial_date: Option<SystemTime>,
     pub initial_parent_beacon_block_root: Option<B256>,
+    /// The maximum number of block states that are retained for historical
+    /// queries. The state of a block that is no longer retained is derived
+    /// from the closest preceding retained state when queried. Defaults to
+    /// 100,000 when not set.
+    pub max_cached_states: Option<NonZeroUsize>,
+    /// The maximum number of blocks that a single `eth_getLogs` request may
+    /// span. Unlimited when not set.
+    pub max_logs_block_range: Option<u64>,
+    /// The maximum number of logs that a single `eth_getLogs` request may
+    /// return. Unlimited when not set.
+    pub max_logs_results: Option<u64>,
+    /// Which JSON-RPC methods are accepted.
+    pub method_permissions: MethodPermissions,
     pub min_gas_price: u128,
     pub mining: Mining,
     pub network_id: u64,
     pub observability: ObservabilityConfig,
     pub owned_accounts: Vec<k256::SecretKey>,
-    pub precompile_overrides: HashMap<Address, PrecompileFn>,
+    pub precompile_overrides: HashMap<Address, CustomPrecompile>,
+    /// The seed of the generator of the prevrandao values of mined blocks.
+    /// When not set, a fixed default seed is used.
+    pub prev_randao_seed: Option<String>,
+    /// Whether to record the changes that each mined transaction makes to the
+    /// state, so they can be retrieved with `hardhat_getStateDiff`.
+    pub record_state_diffs: bool,
+    /// Which block states are retained in memory.
+    pub state_pruning: StatePruning,
+    /// How block timestamps advance over time.
+    pub time_mode: TimeMode,
     /// Transaction gas cap, introduced in [EIP-7825].
     ///
     /// When not set, will default to value defined by the used hardfork
@@ -146,6 +360,7 @@ impl Default for MemPool {
     fn default() -> Self {
         Self {
             order: MineOrdering::Priority,
+            policy: MemPoolPolicy::default(),
         }
     }
 }
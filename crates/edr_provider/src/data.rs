@@ -1,11 +1,16 @@
+mod bundle;
 mod call;
 mod gas;
+mod inclusion;
+mod storage_layout;
 
 use std::{
     cmp::{self, Ordering},
-    collections::BTreeMap,
+    collections::{BTreeMap, VecDeque},
     fmt::Debug,
     num::{NonZeroU64, NonZeroUsize},
+    ops::RangeInclusive,
+    path::Path,
     sync::Arc,
     time::{Duration, Instant, UNIX_EPOCH},
 };
@@ -13,20 +18,30 @@ use std::{
 use alloy_dyn_abi::eip712::TypedData;
 use alloy_eips::eip7825;
 use edr_block_api::{
-    Block, BlockAndTotalDifficulty, FetchBlockReceipts as _, GenesisBlockFactory,
+    Block, BlockAndTotalDifficulty, EthBlockData, FetchBlockReceipts as _, GenesisBlockFactory,
     GenesisBlockOptions,
 };
-use edr_block_builder_api::BuiltBlockAndState;
+use edr_block_builder_api::{BlockInputs, BuiltBlockAndState};
 use edr_block_header::{
-    calculate_next_base_fee_per_blob_gas, BlockConfig, BlockHeader, HeaderOverrides,
+    calculate_next_base_fee_per_blob_gas, BlockConfig, BlockHeader, HeaderOverrides, Withdrawal,
+};
+use edr_block_miner::{
+    mine_block, mine_block_with_single_transaction, mine_block_with_transactions, MineOrdering,
+    PendingTransactionSummary, SyncMineOrderingCallback,
 };
-use edr_block_miner::{mine_block, mine_block_with_single_transaction};
 use edr_blockchain_api::{
     r#dyn::{DynBlockchain, DynBlockchainError},
     BlockHashByNumber, BlockchainMetadata as _, GetBlockchainBlock as _, StateAtBlock as _,
 };
-use edr_blockchain_fork::ForkedBlockchainCreationError as ForkedCreationError;
+use edr_blockchain_fork::{
+    eips::{
+        eip2935::{history_storage_contract, HISTORY_STORAGE_ADDRESS},
+        eip4788::{beacon_roots_contract, BEACON_ROOTS_ADDRESS},
+    },
+    ForkedBlockchainCreationError as ForkedCreationError,
+};
 use edr_chain_config::ChainConfig;
+use edr_chain_l1::block::beacon_root_storage_indices;
 use edr_chain_spec::{
     BlockEnvConstructor as _, ChainSpec, EvmSpecId, ExecutableTransaction, HaltReasonTrait,
     TransactionValidation,
@@ -36,74 +51,111 @@ use edr_chain_spec_evm::{config::EvmConfig, result::ExecutionResult, CfgEnv};
 use edr_chain_spec_provider::ProviderChainSpec;
 use edr_eip1559::BaseFeeParams;
 use edr_eth::{
-    block::miner_reward,
+    blob::BlobSidecar,
+    block::{largest_safe_block_number, miner_reward, LargestSafeBlockNumberArgs},
     fee_history::FeeHistoryResult,
     filter::{FilteredEvents, LogOutput, SubscriptionType},
     reward_percentile::RewardPercentile,
     BlockSpec, BlockTag, Eip1898BlockSpec,
 };
 use edr_gas_report::{GasReport, SyncOnCollectedGasReportCallback};
-use edr_mem_pool::{account_next_nonce, MemPool, OrderedTransaction};
-use edr_precompile::PrecompileFn;
-use edr_primitives::{Address, Bytecode, Bytes, HashMap, HashSet, B256, KECCAK_EMPTY, U256};
+use edr_mem_pool::{account_next_nonce, MemPool, MemPoolPolicy, OrderedTransaction};
+use edr_precompile::CustomPrecompile;
+use edr_primitives::{
+    keccak256, Address, Bytecode, Bytes, HashMap, HashSet, B256, B64, KECCAK_EMPTY, U256, U64,
+};
 use edr_receipt::{log::FilterLog, ExecutionReceipt, ReceiptTrait as _};
 use edr_rpc_eth::client::{EthRpcClient, EthRpcClientForChainSpec, HeaderMap};
 use edr_runtime::{
     inspector::DualInspector,
-    overrides::{StateOverrides, StateRefOverrider},
+    overrides::{AccountOverride, StateOverrides, StateRefOverrider},
     transaction,
 };
 use edr_signer::{
-    public_key_to_address, FakeSign as _, RecoveryMessage, Sign as _, SignatureWithRecoveryId,
+    public_key_to_address, FakeSign as _, RecoveryMessage, Sign as _, SignatureError,
+    SignatureWithRecoveryId, SignatureWithYParity,
 };
 use edr_solidity::contract_decoder::ContractDecoder;
 use edr_state_api::{
     account::{Account, AccountInfo, AccountStatus},
     irregular::IrregularState,
-    AccountModifierFn, DynState, EvmStorageSlot, StateDiff, StateOverride,
+    AccountModifierFn, DynState, EvmStorageSlot, StateCommit as _, StateDiff, StateError,
+    StateOverride,
 };
-use edr_tracing::Trace;
+use edr_tracing::{Trace, TraceCollector};
 use edr_transaction::{
-    request::TransactionRequestAndSender, BlockDataForTransaction, IsEip4844, IsSupported as _,
-    TransactionAndBlock, TransactionMut, TransactionType, TxKind,
+    pooled::eip4844::BlobTransactionSidecar, request::TransactionRequestAndSender,
+    BlockDataForTransaction, IsDeposit as _, IsEip4844, IsSupported as _, TransactionAndBlock,
+    TransactionMut, TransactionType, TxKind,
 };
 use edr_utils::{random::RandomHashGenerator, CastArcInto};
-use gas::gas_used_ratio;
+use gas::{
+    base_fee_per_blob_gas, blob_gas_used_ratio, gas_used_ratio, next_block_base_fee_per_blob_gas,
+};
 use indexmap::IndexMap;
 use itertools::izip;
 use lru::LruCache;
-use rpds::HashTrieMapSync;
+use rpds::{HashTrieMapSync, RedBlackTreeMapSync};
 use tokio::runtime;
 
 use edr_state_api::{State, StateDebug};
 
 use crate::{
-    data::gas::{compute_rewards, BinarySearchEstimationArgs, CheckGasLimitArgs},
+    address_index::AddressIndex,
+    chain_export::ChainImportError,
+    data::{
+        bundle::{simulate_bundle, SimulateBundleArgs},
+        gas::{compute_rewards, BinarySearchEstimationArgs, CheckGasLimitArgs, TouchedStateCache},
+        inclusion::{inclusion_action, shuffled_ranking},
+    },
     debug_mine::{
         DebugMineBlockResult, DebugMineBlockResultAndState, DebugMineBlockResultForChainSpec,
     },
     debug_trace::{
         debug_trace_transaction, execution_result_to_debug_result, DebugTraceConfig,
-        DebugTraceResultWithTraces, TracerEip3155,
+        DebugTraceResultWithTraces, StructLogStream, TracerEip3155,
     },
+    deterministic_deployment::{
+        deterministic_deployment_proxy, DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS,
+    },
+    erc4337::{UserOperationEntities, UserOperationTracer, UserOperationViolation},
     error::{
         CreationError, CreationErrorForChainSpec, EstimateGasFailure, ProviderErrorForChainSpec,
         TransactionFailure, TransactionFailureWithTraces,
     },
     filter::{bloom_contains_log_filter, filter_logs, Filter, FilterData, LogFilter},
+    gas_profile::gas_profile_transaction,
+    keystore::{Keystore, DEFAULT_UNLOCK_DURATION},
     logger::SyncLogger,
+    metrics::{MetricsRecorder, ProviderMetrics, StateGauges},
     mock::SyncCallOverride,
     observability::{EvmObserver, EvmObserverConfig, ObservabilityConfig},
-    pending::BlockchainWithPending,
-    requests::hardhat::rpc_types::{ForkMetadata, StateAccount, StateDump},
+    parity_trace::{trace_transactions, transaction_traces},
+    pending::{BlockchainWithPending, PendingBlock},
+    raw_block::{ensure_header_field_matches, RawBlock, RawBlockImportError},
+    replay::{
+        replay_block, replay_transaction, transaction_state_diffs, ReplayedTransactionWithTraces,
+    },
+    requests::{
+        explorer::{AddressRelation, AddressTransaction, AddressTransactionsPage},
+        hardhat::rpc_types::{
+            AccountDiff, BundleSimulation, FeeChecksConfig, ForkMetadata, GasProfile,
+            InclusionAction, InclusionPolicy, NextBlockHeaderOverrides, ReplayedBlock,
+            StateAccount, StateDump, StorageLayout, TokenTransfer,
+        },
+        trace::LocalizedTrace,
+    },
     snapshot::Snapshot,
     spec::{
         ForkedBlockchainForChainSpec, LocalBlockchainForChainSpec, ProviderSpec,
         SyncBlockchainForChainSpec, SyncProviderSpec, TransactionAndBlockForChainSpec,
     },
-    time::{CurrentTime, TimeSinceEpoch},
-    MiningConfig, ProviderConfig, ProviderError, SubscriptionEvent, SubscriptionEventData,
-    SyncSubscriberCallback,
+    time::{BlockClock, CurrentTime, TimeSinceEpoch},
+    token_index::{TokenHoldings, TokenTransferIndex},
+    verified_sources::{VerifiedSourceFetcher, VerifiedSourceLookup, VerifiedSourceTracker},
+    GasSponsorConfig, MiningConfig, ProviderConfig, ProviderError, StatePruningConfig,
+    SubscriptionEvent, SubscriptionEventData, SyncSubscriberCallback, SyncTransactionFirewall,
+    TransactionFirewallRequest, TransactionFirewallVerdict,
 };
 
 const DEFAULT_INITIAL_BASE_FEE_PER_GAS: u128 = 1_000_000_000;
@@ -112,6 +164,14 @@ const DEFAULT_MAX_CACHED_STATES: usize = 100_000;
 const EDR_UNSAFE_SKIP_UNSUPPORTED_TRANSACTION_TYPES: &str =
     "__EDR_UNSAFE_SKIP_UNSUPPORTED_TRANSACTION_TYPES";
 const DEFAULT_SKIP_UNSUPPORTED_TRANSACTION_TYPES: bool = false;
+/// The maximum number of reconstructed states of pruned blocks that are cached.
+const MAX_RECONSTRUCTED_STATES: usize = 4;
+/// The maximum size of a block header's extra data, in bytes.
+pub(crate) const MAX_EXTRA_DATA_SIZE: usize = 32;
+/// The bound divisor of the gas limit, used to limit its change between blocks.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+/// The minimum gas limit of a block.
+const MIN_GAS_LIMIT: u64 = 5000;
 
 /// The result of executing an `eth_call`.
 #[derive(Clone, Debug)]
@@ -121,6 +181,28 @@ pub struct CallResult<HaltReasonT: HaltReasonTrait> {
     pub trace: Trace<HaltReasonT>,
 }
 
+/// A transaction of a simulated bundle.
+#[derive(Clone, Debug)]
+pub enum BundleTransaction<SignedTransactionT> {
+    /// A signed transaction, which is fully validated.
+    Signed(SignedTransactionT),
+    /// A fake-signed transaction, which is executed like an `eth_call`.
+    Unsigned(SignedTransactionT),
+}
+
+/// Overrides of the block context in which a bundle is simulated. Fields that
+/// aren't overridden are derived from the block on top of whose state the
+/// bundle is simulated.
+#[derive(Clone, Debug, Default)]
+pub struct BundleBlockOverrides {
+    /// The block number.
+    pub number: Option<u64>,
+    /// The block timestamp.
+    pub timestamp: Option<u64>,
+    /// The beneficiary of the block's fees.
+    pub coinbase: Option<Address>,
+}
+
 #[derive(Clone)]
 pub struct EstimateGasResult<HaltReasonT: HaltReasonTrait> {
     pub estimation: u64,
@@ -200,38 +282,104 @@ pub struct ProviderData<
     /// Whether to return an `Err` when a `eth_sendTransaction` fails
     bail_on_transaction_failure: bool,
     blockchain: Box<dyn SyncBlockchainForChainSpec<ChainSpecT>>,
+    // Blob sidecars of locally sent EIP-4844 transactions, keyed by transaction
+    // hash. A persistent map is used, as the sidecars are part of snapshots.
+    blob_sidecars: HashTrieMapSync<B256, BlobTransactionSidecar>,
     pub irregular_state: IrregularState,
     mem_pool: MemPool<ChainSpecT::SignedTransaction>,
     mining_config: MiningConfig,
+    // Overrides the mem pool's mine ordering, if set.
+    mine_ordering_callback: Option<Arc<dyn SyncMineOrderingCallback>>,
+    // Decides whether transactions are added to the mem pool, if set.
+    transaction_firewall: Option<Arc<dyn SyncTransactionFirewall>>,
+    // Tags assigned by the transaction firewall to pending and mined
+    // transactions, keyed by transaction hash. The tags of mined transactions
+    // are removed with their blocks, or when their blocks are pruned.
+    transaction_tags: HashMap<B256, Vec<String>>,
+    // Simulates adverse network conditions by delaying, dropping, or reordering
+    // transactions.
+    inclusion_policy: InclusionPolicy,
+    // The first block numbers in which transactions that were delayed by the
+    // inclusion policy can be mined, keyed by transaction hash.
+    delayed_transactions: HashMap<B256, u64>,
+    // Deposit transactions that are force-included at the top of the next
+    // block, in order of insertion.
+    queued_deposits: Vec<ChainSpecT::SignedTransaction>,
+    // Storage layouts of contracts, used to read state variables by name.
+    storage_layouts: HashMap<Address, Arc<StorageLayout>>,
     network_id: u64,
     observability: ObservabilityConfig,
-    precompile_overrides: HashMap<Address, PrecompileFn>,
+    precompile_overrides: HashMap<Address, CustomPrecompile>,
     beneficiary: Address,
+    block_gas_limit_schedule: BTreeMap<u64, NonZeroU64>,
+    /// The user-provided transaction gas cap. If `None`, the cap of the
+    /// hardfork of the next block applies.
+    transaction_gas_cap_override: Option<u64>,
     min_gas_price: u128,
     parent_beacon_block_root_generator: RandomHashGenerator,
     prev_randao_generator: RandomHashGenerator,
+    block_clock: BlockClock,
     block_time_offset_seconds: i64,
     fork_metadata: Option<ForkMetadata>,
+    // Used for re-creating the blockchain when updating the fork.
+    initial_config: ProviderConfig<ChainSpecT::Hardfork>,
     // Must be set if the provider is created with a fork config.
     // Hack to get around the type erasure with the dyn blockchain trait.
     rpc_client: Option<Arc<EthRpcClientForChainSpec<ChainSpecT>>>,
     instance_id: B256,
     is_auto_mining: bool,
     next_block_base_fee_per_gas: Option<u128>,
+    next_block_difficulty: Option<U256>,
+    // Keeps the base fee per gas of new blocks equal to their parent's.
+    disable_base_fee_adjustment: bool,
+    // Accepts transactions that pay less than the base fee.
+    disable_base_fee_check: bool,
+    // Accepts transactions whose priority fee is below the minimum gas price.
+    disable_priority_fee_check: bool,
+    max_logs_block_range: Option<u64>,
+    max_logs_results: Option<u64>,
+    // Whether to record the state diff of each mined transaction.
+    record_state_diffs: bool,
+    // State diffs of mined transactions and the number of the block that
+    // includes them, keyed by transaction hash.
+    state_diffs: HashMap<B256, (u64, BTreeMap<Address, AccountDiff>)>,
+    // Transactions of locally mined blocks, indexed by the addresses they involve.
+    address_index: AddressIndex,
+    index_address_transactions: bool,
+    // Whether to index the token transfers of mined blocks.
+    index_token_transfers: bool,
+    // Token transfers of locally mined blocks.
+    token_transfers: TokenTransferIndex,
+    next_block_header_overrides: Option<NextBlockHeaderOverrides>,
+    next_block_state_root: Option<B256>,
+    next_block_withdrawals: Option<Vec<Withdrawal>>,
     base_fee_params: Option<BaseFeeParams<ChainSpecT::Hardfork>>,
     next_block_timestamp: Option<u64>,
     next_snapshot_id: u64,
     snapshots: BTreeMap<u64, Snapshot<ChainSpecT::SignedTransaction>>,
     allow_blocks_with_same_timestamp: bool,
     allow_unlimited_contract_size: bool,
+    allow_unlimited_init_code_size: bool,
     // Skip unsupported transaction types in `debugTraceTransaction` instead of throwing an error
     skip_unsupported_transaction_types: bool,
     // IndexMap to preserve account order for logging.
     local_accounts: IndexMap<Address, k256::SecretKey>,
+    // Accounts managed through the `personal` namespace
+    keystore: Keystore,
     filters: HashMap<U256, Filter>,
     last_filter_id: U256,
     logger: Box<dyn SyncLogger<ChainSpecT, TimerT>>,
+    metrics: MetricsRecorder,
     impersonated_accounts: HashSet<Address>,
+    // Whether to fake-sign transactions of all senders that aren't local accounts
+    auto_impersonate: bool,
+    // Whether to top up the balance of impersonated senders to cover their transactions
+    auto_fund: bool,
+    // The account that pays for the gas of the transactions of a set of senders, if any.
+    gas_sponsor: Option<GasSponsorConfig>,
+    // Sponsored transactions that haven't been mined yet, keyed by transaction hash, with
+    // their senders and the gas costs that were prepaid by the sponsor.
+    sponsored_transactions: HashMap<B256, (Address, U256)>,
     subscriber_callback:
         Box<dyn SyncSubscriberCallback<ChainSpecT::Block, ChainSpecT::SignedTransaction>>,
     timer: TimerT,
@@ -239,8 +387,23 @@ pub struct ProviderData<
     // self to get.
     block_state_cache: LruCache<StateId, Arc<Box<dyn DynState>>>,
     current_state_id: StateId,
-    block_number_to_state_id: HashTrieMapSync<u64, StateId>,
+    block_number_to_state_id: RedBlackTreeMapSync<u64, StateId>,
+    state_pruning: StatePruningConfig,
+    // The IDs of the cached, reconstructed states of pruned blocks, from the least to the most
+    // recently reconstructed.
+    reconstructed_state_ids: VecDeque<StateId>,
+    // The number of the first locally mined block.
+    first_local_block_number: u64,
+    // The number of the first block whose recorded state diffs, blob sidecars, and indexed
+    // address transactions and token transfers haven't been pruned.
+    first_unpruned_block_number: u64,
+    // The pending block mined from the mem pool, cached until it is invalidated or
+    // the chain head or next block timestamp changes.
+    pending_block: Option<PendingBlock<<ChainSpecT as GenesisBlockFactory>::LocalBlock>>,
     contract_decoder: Arc<ContractDecoder>,
+    // Tracks the forked contracts whose verified sources need to be fetched, if
+    // enabled.
+    verified_sources: Option<VerifiedSourceTracker>,
 }
 
 impl<ChainSpecT, TimerT> ProviderData<ChainSpecT, TimerT>
@@ -249,7 +412,17 @@ where
     TimerT: Clone + TimeSinceEpoch,
 {
     pub fn accounts(&self) -> impl Iterator<Item = &Address> {
-        self.local_accounts.keys()
+        self.local_accounts.keys().chain(self.keystore.addresses())
+    }
+
+    /// Adds a filter for dropped transactions to the provider.
+    pub fn add_dropped_transaction_filter<const IS_SUBSCRIPTION: bool>(&mut self) -> U256 {
+        let filter_id = self.next_filter_id();
+        self.filters.insert(
+            filter_id,
+            Filter::new_dropped_transaction_filter(IS_SUBSCRIPTION),
+        );
+        filter_id
     }
 
     /// Adds a filter for new pending transactions to the provider.
@@ -262,8 +435,10 @@ where
         filter_id
     }
 
+    /// Whether deployments with init code of any size are allowed. This is
+    /// implied by allowing unlimited contract sizes.
     pub fn allow_unlimited_initcode_size(&self) -> bool {
-        self.allow_unlimited_contract_size
+        self.allow_unlimited_contract_size || self.allow_unlimited_init_code_size
     }
 
     /// Whether the provider is configured to bail on call failures.
@@ -290,6 +465,21 @@ where
         &self.contract_decoder
     }
 
+    /// Returns a lookup of the verified sources of the forked contracts
+    /// called in the traces that the contract decoder doesn't recognize yet,
+    /// if enabled.
+    ///
+    /// The lookup performs HTTP requests, so it should be registered after
+    /// releasing the provider's data.
+    pub fn verified_source_lookup(
+        &mut self,
+        traces: &[Trace<ChainSpecT::HaltReason>],
+    ) -> Option<VerifiedSourceLookup> {
+        self.verified_sources
+            .as_mut()
+            .and_then(|tracker| tracker.lookup(&self.contract_decoder, traces))
+    }
+
     /// Returns the default caller.
     pub fn default_caller(&self) -> Address {
         self.local_accounts
@@ -304,14 +494,64 @@ where
         self.fork_metadata.as_ref()
     }
 
+    /// Returns the URL of the remote node's JSON-RPC endpoint, if forking.
+    pub fn fork_url(&self) -> Option<&str> {
+        self.initial_config
+            .fork
+            .as_ref()
+            .map(|fork_config| fork_config.url.as_str())
+    }
+
+    /// Returns the directory in which checkpoints are stored.
+    pub fn checkpoint_dir(&self) -> &Path {
+        &self.initial_config.checkpoint_dir
+    }
+
+    /// Returns the order in which pending transactions are mined, unless
+    /// overridden by a mine ordering callback.
+    pub fn mine_ordering(&self) -> MineOrdering {
+        self.mining_config.mem_pool.order
+    }
+
+    /// Returns the client for the remote node, if forking.
+    pub fn rpc_client(&self) -> Option<&Arc<EthRpcClientForChainSpec<ChainSpecT>>> {
+        self.rpc_client.as_ref()
+    }
+
     pub fn get_filter_changes(&mut self, filter_id: &U256) -> Option<FilteredEvents> {
+        self.remove_expired_filters();
+
         self.filters.get_mut(filter_id).map(Filter::take_events)
     }
 
+    /// Imports the provided raw secret key into the keystore, protected by the
+    /// provided passphrase.
+    pub fn import_raw_key(
+        &mut self,
+        secret_key: B256,
+        passphrase: &str,
+    ) -> Result<Address, ProviderErrorForChainSpec<ChainSpecT>> {
+        let secret_key = k256::SecretKey::from_slice(secret_key.as_slice())
+            .map_err(SignatureError::EllipticCurveError)?;
+
+        Ok(self.keystore.import(secret_key, passphrase)?)
+    }
+
     pub fn impersonate_account(&mut self, address: Address) {
         self.impersonated_accounts.insert(address);
     }
 
+    /// Returns whether transactions of the provided address are fake-signed.
+    /// This is the case for explicitly impersonated accounts and, when
+    /// auto-impersonation is enabled, for all accounts that aren't local.
+    ///
+    /// Impersonated accounts can also be contract accounts, as the provider
+    /// doesn't reject transactions from senders with code (EIP-3607).
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        self.impersonated_accounts.contains(address)
+            || (self.auto_impersonate && self.secret_key(address).is_none())
+    }
+
     pub fn increase_block_time(&mut self, increment: u64) -> i64 {
         self.block_time_offset_seconds += i64::try_from(increment).expect("increment too large");
         self.block_time_offset_seconds
@@ -335,6 +575,41 @@ where
         &self.mining_config
     }
 
+    /// Generates a new account in the keystore, protected by the provided
+    /// passphrase.
+    pub fn new_account(&mut self, passphrase: &str) -> Address {
+        self.keystore.new_account(passphrase)
+    }
+
+    /// Returns a snapshot of the recorded metrics and the current state
+    /// gauges.
+    pub fn metrics(&self) -> ProviderMetrics {
+        let state = StateGauges {
+            block_number: self.blockchain.last_block_number(),
+            pending_transactions: self.mem_pool.pending_transactions().count() as u64,
+            queued_transactions: self.mem_pool.future_transactions().count() as u64,
+            cached_states: self.block_state_cache.len() as u64,
+        };
+
+        ProviderMetrics::new(
+            &self.metrics,
+            self.rpc_client
+                .as_ref()
+                .map(|rpc_client| rpc_client.cache_stats()),
+            state,
+        )
+    }
+
+    /// Records a handled request of the provided method.
+    pub(crate) fn record_request(
+        &mut self,
+        method_name: &'static str,
+        duration: Duration,
+        is_error: bool,
+    ) {
+        self.metrics.record_request(method_name, duration, is_error);
+    }
+
     /// Returns the instance's network ID.
     pub fn network_id(&self) -> String {
         self.network_id.to_string()
@@ -344,6 +619,28 @@ where
         self.mem_pool.transactions()
     }
 
+    /// Returns the mem pool transactions that can be included in the next
+    /// block, grouped by sender in order of insertion.
+    pub fn executable_transactions(&self) -> impl Iterator<Item = &ChainSpecT::SignedTransaction> {
+        self.mem_pool
+            .pending_transactions()
+            .map(OrderedTransaction::pending)
+    }
+
+    /// Returns the deposit transactions that will be force-included at the
+    /// top of the next block, in order of inclusion.
+    pub fn queued_deposits(&self) -> impl Iterator<Item = &ChainSpecT::SignedTransaction> {
+        self.queued_deposits.iter()
+    }
+
+    /// Returns the mem pool transactions whose nonces are too high to be
+    /// included in the next block, grouped by sender in order of insertion.
+    pub fn queued_transactions(&self) -> impl Iterator<Item = &ChainSpecT::SignedTransaction> {
+        self.mem_pool
+            .future_transactions()
+            .map(OrderedTransaction::pending)
+    }
+
     pub fn remove_filter(&mut self, filter_id: &U256) -> bool {
         self.remove_filter_impl::</* IS_SUBSCRIPTION */ false>(filter_id)
     }
@@ -352,13 +649,55 @@ where
         self.remove_filter_impl::</* IS_SUBSCRIPTION */ true>(filter_id)
     }
 
+    /// Runs the provided function and, if it fails, reverts the chain, the mem
+    /// pool, and the irregular state to how they were before it was run.
+    pub fn revert_on_error<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, ProviderErrorForChainSpec<ChainSpecT>>,
+    ) -> Result<T, ProviderErrorForChainSpec<ChainSpecT>> {
+        let snapshot_id = self.make_snapshot();
+
+        let result = f(self);
+        if result.is_err() {
+            // Ignore potential failure of reverting, as returning the original error is
+            // more important
+            let _result = self.revert_to_snapshot(snapshot_id);
+        } else {
+            self.snapshots.remove(&snapshot_id);
+        }
+
+        result
+    }
+
     /// Removes the transaction with the provided hash from the mem pool, if it
     /// exists.
     pub fn remove_pending_transaction(
         &mut self,
         transaction_hash: &B256,
-    ) -> Option<OrderedTransaction<ChainSpecT::SignedTransaction>> {
-        self.mem_pool.remove_transaction(transaction_hash)
+    ) -> Result<
+        Option<OrderedTransaction<ChainSpecT::SignedTransaction>>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let removed = self.mem_pool.remove_transaction(transaction_hash);
+        if let Some(removed) = &removed {
+            self.handle_dropped_transactions(std::slice::from_ref(removed))?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Removes all transactions from the mem pool.
+    ///
+    /// Returns the number of removed transactions.
+    pub fn remove_all_pending_transactions(
+        &mut self,
+    ) -> Result<usize, ProviderErrorForChainSpec<ChainSpecT>> {
+        let removed = self.mem_pool.remove_all_transactions();
+        let num_removed = removed.len();
+
+        self.handle_dropped_transactions(&removed)?;
+
+        Ok(num_removed)
     }
 
     /// Retrieves the runtime handle.
@@ -371,8 +710,59 @@ where
         self.is_auto_mining = enabled;
     }
 
+    /// Invalidates the cached pending block, e.g. because the mem pool or the
+    /// mining configuration changed.
+    pub fn invalidate_pending_block(&mut self) {
+        self.pending_block = None;
+    }
+
     pub fn set_call_override_callback(&mut self, call_override: Option<Arc<dyn SyncCallOverride>>) {
         self.observability.call_override = call_override;
+        // The pending block was built using the previous call override
+        self.invalidate_pending_block();
+    }
+
+    /// Sets the callback that determines the order in which pending
+    /// transactions are mined. When set, it overrides the configured mine
+    /// ordering.
+    pub fn set_mine_ordering_callback(
+        &mut self,
+        mine_ordering_callback: Option<Arc<dyn SyncMineOrderingCallback>>,
+    ) {
+        self.mine_ordering_callback = mine_ordering_callback;
+        // The pending block was built using the previous mine ordering
+        self.invalidate_pending_block();
+    }
+
+    /// Sets the callback that decides whether transactions are added to the
+    /// mem pool. Set to `None` to admit all transactions.
+    pub fn set_transaction_firewall(
+        &mut self,
+        transaction_firewall: Option<Arc<dyn SyncTransactionFirewall>>,
+    ) {
+        self.transaction_firewall = transaction_firewall;
+    }
+
+    /// Returns the tags that the transaction firewall assigned to the
+    /// transaction with the provided hash.
+    pub fn transaction_tags(&self, transaction_hash: &B256) -> &[String] {
+        self.transaction_tags
+            .get(transaction_hash)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Sets the policy that simulates adverse network conditions, replacing
+    /// the previous policy. Transactions that were delayed by the previous
+    /// policy can be mined immediately.
+    pub fn set_inclusion_policy(&mut self, policy: InclusionPolicy) {
+        self.inclusion_policy = policy;
+        self.delayed_transactions.clear();
+    }
+
+    /// Registers the storage layout of the contract at the provided address,
+    /// replacing any previously registered layout.
+    pub fn set_storage_layout(&mut self, address: Address, layout: StorageLayout) {
+        self.storage_layouts.insert(address, Arc::new(layout));
     }
 
     /// Sets the coinbase.
@@ -380,6 +770,10 @@ where
         self.beneficiary = coinbase;
     }
 
+    pub fn set_auto_impersonate(&mut self, enabled: bool) {
+        self.auto_impersonate = enabled;
+    }
+
     pub fn set_verbose_tracing(&mut self, verbose_tracing: bool) {
         self.observability.verbose_raw_tracing = verbose_tracing;
     }
@@ -393,7 +787,36 @@ where
         self.mem_pool.transaction_gas_cap()
     }
 
+    /// Unlocks the keystore account with the provided address for the
+    /// provided number of seconds. Zero seconds unlock the account
+    /// indefinitely.
+    pub fn unlock_account(
+        &mut self,
+        address: &Address,
+        passphrase: &str,
+        duration_seconds: Option<u64>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let duration = duration_seconds.map_or(DEFAULT_UNLOCK_DURATION, Duration::from_secs);
+
+        Ok(self.keystore.unlock(address, passphrase, duration)?)
+    }
+
+    /// Returns the secret key of a local account or an unlocked keystore
+    /// account.
+    fn secret_key(&self, address: &Address) -> Option<&k256::SecretKey> {
+        self.local_accounts
+            .get(address)
+            .or_else(|| self.keystore.unlocked_secret_key(address))
+    }
+
     fn add_state_to_cache(&mut self, state: Box<dyn DynState>, block_number: u64) -> StateId {
+        // A superseded state of the same block is no longer retained
+        if self.state_pruning != StatePruningConfig::Archive
+            && let Some(superseded_state_id) = self.block_number_to_state_id.get(&block_number)
+        {
+            self.block_state_cache.pop(superseded_state_id);
+        }
+
         let state_id = self.current_state_id.increment();
         self.block_state_cache.push(state_id, Arc::new(state));
         self.block_number_to_state_id
@@ -426,6 +849,69 @@ where
         }
     }
 
+    /// Notifies subscribers to `FilterData::DroppedTransactions` about the
+    /// transaction with the provided hash, which was removed from the mem pool
+    /// without being mined.
+    fn notify_subscribers_about_dropped_transaction(&mut self, transaction_hash: &B256) {
+        for (filter_id, filter) in self.filters.iter_mut() {
+            if let FilterData::DroppedTransactions(events) = &mut filter.data {
+                if filter.is_subscription {
+                    (self.subscriber_callback)(SubscriptionEvent {
+                        filter_id: *filter_id,
+                        result: SubscriptionEventData::DroppedTransactions(*transaction_hash),
+                    });
+                } else {
+                    events.push(*transaction_hash);
+                }
+            }
+        }
+    }
+
+    /// Removes the transactions that exceeded the mem pool's transaction
+    /// lifetime, notifying subscribers about them.
+    fn remove_expired_pending_transactions(
+        &mut self,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let removed = self
+            .mem_pool
+            .remove_expired_transactions(self.timer.since_epoch());
+
+        for transaction in &removed {
+            log::debug!(
+                "Dropped transaction {} from the mem pool, as it exceeded the transaction lifetime",
+                transaction.pending().transaction_hash()
+            );
+        }
+
+        self.handle_dropped_transactions(&removed)
+    }
+
+    /// Handles transactions that were dropped from the mem pool without being
+    /// mined: forgets their side information, notifies subscribers, and
+    /// refunds sponsored gas costs.
+    ///
+    /// All paths that drop transactions from the mem pool should go through
+    /// this function.
+    fn handle_dropped_transactions(
+        &mut self,
+        removed: &[OrderedTransaction<ChainSpecT::SignedTransaction>],
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let transaction_hashes = removed
+            .iter()
+            .map(|transaction| *transaction.pending().transaction_hash())
+            .collect::<Vec<_>>();
+
+        for transaction_hash in &transaction_hashes {
+            self.blob_sidecars.remove_mut(transaction_hash);
+            self.delayed_transactions.remove(transaction_hash);
+            self.transaction_tags.remove(transaction_hash);
+
+            self.notify_subscribers_about_dropped_transaction(transaction_hash);
+        }
+
+        self.cancel_transaction_sponsorships(&transaction_hashes)
+    }
+
     /// Notifies subscribers to `FilterData::Logs` and `FilterData::NewHeads`
     /// about the mined block.
     fn notify_subscribers_about_mined_block(
@@ -469,16 +955,107 @@ where
                         block_hashes.push(*block.block_hash());
                     }
                 }
-                FilterData::NewPendingTransactions(_) => (),
+                FilterData::NewPendingTransactions(_) | FilterData::DroppedTransactions(_) => (),
             }
         }
 
-        // Remove outdated filters
-        self.filters.retain(|_, filter| !filter.has_expired());
+        self.remove_expired_filters();
+
+        Ok(())
+    }
+
+    /// Notifies filters about the blocks that will be removed when reverting
+    /// to the block with the provided number.
+    ///
+    /// Events of removed blocks that haven't been delivered yet are discarded.
+    /// Logs that were already delivered are delivered again with `removed` set
+    /// to `true`.
+    fn notify_filters_about_reverted_blocks(
+        &mut self,
+        block_number: u64,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let last_block_number = self.blockchain.last_block_number();
+        if block_number >= last_block_number {
+            return Ok(());
+        }
+
+        let mut removed_block_hashes = HashSet::default();
+        for removed_block_number in block_number + 1..=last_block_number {
+            if let Some(block) = self.blockchain.block_by_number(removed_block_number)? {
+                removed_block_hashes.insert(*block.block_hash());
+            }
+        }
+
+        for (filter_id, filter) in self.filters.iter_mut() {
+            match &mut filter.data {
+                FilterData::Logs { criteria, logs } => {
+                    let from_block = criteria.from_block.max(block_number + 1);
+                    let to_block = criteria.to_block.map_or(last_block_number, |to_block| {
+                        to_block.min(last_block_number)
+                    });
+
+                    if from_block > to_block {
+                        continue;
+                    }
+
+                    let mut undelivered_logs = HashSet::default();
+                    logs.retain(|log| {
+                        let is_removed = log
+                            .block_hash
+                            .is_some_and(|block_hash| removed_block_hashes.contains(&block_hash));
+
+                        if is_removed {
+                            undelivered_logs.insert((log.block_hash, log.log_index));
+                        }
+
+                        !is_removed
+                    });
+
+                    let mut removed_logs: Vec<_> = self
+                        .blockchain
+                        .logs(
+                            from_block,
+                            to_block,
+                            &criteria.addresses,
+                            &criteria.normalized_topics,
+                        )?
+                        .iter()
+                        .map(LogOutput::from)
+                        .filter(|log| !undelivered_logs.contains(&(log.block_hash, log.log_index)))
+                        .map(|log| LogOutput {
+                            removed: true,
+                            ..log
+                        })
+                        .collect();
+
+                    if removed_logs.is_empty() {
+                        continue;
+                    }
+
+                    if filter.is_subscription {
+                        (self.subscriber_callback)(SubscriptionEvent {
+                            filter_id: *filter_id,
+                            result: SubscriptionEventData::Logs(removed_logs),
+                        });
+                    } else {
+                        logs.append(&mut removed_logs);
+                    }
+                }
+                FilterData::NewHeads(block_hashes) => {
+                    block_hashes.retain(|block_hash| !removed_block_hashes.contains(block_hash));
+                }
+                FilterData::NewPendingTransactions(_) | FilterData::DroppedTransactions(_) => (),
+            }
+        }
 
         Ok(())
     }
 
+    /// Removes filters that haven't been polled within their deadline.
+    fn remove_expired_filters(&mut self) {
+        self.filters.retain(|_, filter| !filter.has_expired());
+    }
+
     fn remove_filter_impl<const IS_SUBSCRIPTION: bool>(&mut self, filter_id: &U256) -> bool {
         if let Some(filter) = self.filters.get(filter_id) {
             filter.is_subscription == IS_SUBSCRIPTION && self.filters.remove(filter_id).is_some()
@@ -515,29 +1092,62 @@ where
         &mut self,
         filter_id: &U256,
     ) -> Result<Option<Vec<LogOutput>>, ProviderErrorForChainSpec<ChainSpecT>> {
-        self.filters
-            .get_mut(filter_id)
-            .map(|filter| {
-                if let Some(events) = filter.take_log_events() {
-                    Ok(events)
-                } else {
-                    Err(ProviderError::InvalidFilterSubscriptionType {
-                        filter_id: *filter_id,
-                        expected: SubscriptionType::Logs,
-                        actual: filter.data.subscription_type(),
-                    })
-                }
-            })
-            .transpose()
+        self.remove_expired_filters();
+
+        let Some(filter) = self.filters.get_mut(filter_id) else {
+            return Ok(None);
+        };
+
+        filter.refresh_deadline();
+
+        let FilterData::Logs { criteria, .. } = &filter.data else {
+            return Err(ProviderError::InvalidFilterSubscriptionType {
+                filter_id: *filter_id,
+                expected: SubscriptionType::Logs,
+                actual: filter.data.subscription_type(),
+            });
+        };
+
+        // Unlike `eth_getFilterChanges`, this returns all logs matching the filter
+        // criteria, without affecting the filter's pending changes.
+        let logs = self
+            .blockchain
+            .logs(
+                criteria.from_block,
+                criteria
+                    .to_block
+                    .unwrap_or(self.blockchain.last_block_number()),
+                &criteria.addresses,
+                &criteria.normalized_topics,
+            )?
+            .iter()
+            .map(LogOutput::from)
+            .collect();
+
+        Ok(Some(logs))
     }
 
-    pub fn revert_to_snapshot(&mut self, snapshot_id: u64) -> bool {
+    pub fn revert_to_snapshot(
+        &mut self,
+        snapshot_id: u64,
+    ) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+        // Filters are notified before any state is modified, such that a failure
+        // leaves the provider and its snapshots untouched.
+        if let Some(block_number) = self
+            .snapshots
+            .get(&snapshot_id)
+            .map(|snapshot| snapshot.block_number)
+        {
+            self.notify_filters_about_reverted_blocks(block_number)?;
+        }
+
         // Ensure that, if the snapshot exists, we also remove all subsequent snapshots,
         // as they can only be used once in Ganache.
         let mut removed_snapshots = self.snapshots.split_off(&snapshot_id);
 
         if let Some(snapshot) = removed_snapshots.remove(&snapshot_id) {
             let Snapshot {
+                blob_sidecars,
                 block_number,
                 block_number_to_state_id,
                 block_time_offset_seconds,
@@ -545,35 +1155,56 @@ where
                 irregular_state,
                 mem_pool,
                 next_block_base_fee_per_gas,
+                next_block_difficulty,
+                next_block_header_overrides,
+                next_block_state_root,
                 next_block_timestamp,
+                next_block_withdrawals,
                 parent_beacon_block_root_generator,
                 prev_randao_generator,
+                queued_deposits,
+                sponsored_transactions,
                 time,
+                transaction_tags,
             } = snapshot;
 
+            self.blob_sidecars = blob_sidecars;
             self.block_number_to_state_id = block_number_to_state_id;
 
             // We compute a new offset such that:
             // now + new_offset == snapshot_date + old_offset
-            let duration_since_snapshot = Instant::now().duration_since(time);
+            let seconds_since_snapshot = self.block_clock.elapsed_since(time);
             self.block_time_offset_seconds = block_time_offset_seconds
-                + i64::try_from(duration_since_snapshot.as_secs()).expect("duration too large");
+                + i64::try_from(seconds_since_snapshot).expect("duration too large");
 
             self.beneficiary = coinbase;
             self.blockchain
                 .revert_to_block(block_number)
                 .expect("Snapshotted block should exist");
+            self.prune_state_diffs();
 
             self.irregular_state = irregular_state;
+
+            // Fee checks aren't part of the snapshot, so retain the current setting
+            let disable_balance_check = self.mem_pool.is_balance_check_disabled();
             self.mem_pool = mem_pool;
+            self.mem_pool
+                .set_balance_check_disabled(disable_balance_check);
             self.next_block_base_fee_per_gas = next_block_base_fee_per_gas;
+            self.next_block_difficulty = next_block_difficulty;
+            self.next_block_header_overrides = next_block_header_overrides;
+            self.next_block_state_root = next_block_state_root;
             self.next_block_timestamp = next_block_timestamp;
+            self.next_block_withdrawals = next_block_withdrawals;
             self.parent_beacon_block_root_generator = parent_beacon_block_root_generator;
+            self.queued_deposits = queued_deposits;
             self.prev_randao_generator = prev_randao_generator;
+            self.sponsored_transactions = sponsored_transactions;
+            self.transaction_tags = transaction_tags;
 
-            true
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 
@@ -582,7 +1213,7 @@ where
         address: &Address,
         message: Bytes,
     ) -> Result<SignatureWithRecoveryId, ProviderErrorForChainSpec<ChainSpecT>> {
-        match self.local_accounts.get(address) {
+        match self.secret_key(address) {
             Some(secret_key) => Ok(SignatureWithRecoveryId::new(&message[..], secret_key)?),
             None => Err(ProviderError::UnknownAddress { address: *address }),
         }
@@ -593,7 +1224,7 @@ where
         address: &Address,
         message: &TypedData,
     ) -> Result<SignatureWithRecoveryId, ProviderErrorForChainSpec<ChainSpecT>> {
-        match self.local_accounts.get(address) {
+        match self.secret_key(address) {
             Some(secret_key) => {
                 let hash = message.eip712_signing_hash()?;
                 Ok(SignatureWithRecoveryId::new(
@@ -604,6 +1235,21 @@ where
             None => Err(ProviderError::UnknownAddress { address: *address }),
         }
     }
+
+    pub fn sign_authorization(
+        &self,
+        address: &Address,
+        authorization: edr_eip7702::Authorization,
+    ) -> Result<edr_eip7702::SignedAuthorization, ProviderErrorForChainSpec<ChainSpecT>> {
+        match self.secret_key(address) {
+            Some(secret_key) => {
+                let signature =
+                    SignatureWithYParity::with_message(authorization.signature_hash(), secret_key)?;
+                Ok(authorization.into_signed(signature.into_inner()))
+            }
+            None => Err(ProviderError::UnknownAddress { address: *address }),
+        }
+    }
 }
 
 impl<ChainSpecT, TimerT> ProviderData<ChainSpecT, TimerT>
@@ -630,11 +1276,15 @@ where
             prev_randao_generator,
             block_time_offset_seconds,
             next_block_base_fee_per_gas,
-        } = create_blockchain_and_state(runtime_handle.clone(), &config, &timer)?;
+        } = create_blockchain_and_state(runtime_handle.clone(), &config, &timer, None)?;
 
-        let max_cached_states = get_max_cached_states_from_env::<ChainSpecT, TimerT>()?;
+        let max_cached_states = if let Some(max_cached_states) = config.max_cached_states {
+            max_cached_states
+        } else {
+            get_max_cached_states_from_env::<ChainSpecT, TimerT>()?
+        };
         let mut block_state_cache = LruCache::new(max_cached_states);
-        let mut block_number_to_state_id = HashTrieMapSync::default();
+        let mut block_number_to_state_id = RedBlackTreeMapSync::default();
 
         let current_state_id = StateId::default();
         block_state_cache.push(current_state_id, Arc::new(state));
@@ -643,7 +1293,13 @@ where
         let allow_blocks_with_same_timestamp = config.allow_blocks_with_same_timestamp;
         let allow_unlimited_contract_size = config.allow_unlimited_contract_size;
         let beneficiary = config.coinbase;
-        let block_gas_limit = config.block_gas_limit;
+        // A scheduled gas limit at or before the latest block, e.g. at the genesis
+        // block, replaces the configured gas limit.
+        let block_gas_limit = config
+            .block_gas_limit_schedule
+            .range(..=blockchain.last_block_number())
+            .next_back()
+            .map_or(config.block_gas_limit, |(_, gas_limit)| *gas_limit);
         let is_auto_mining = config.mining.auto_mine;
         let min_gas_price = config.min_gas_price;
 
@@ -659,6 +1315,17 @@ where
 
         let observability = config.observability.clone();
 
+        // Resolving the checkpoint directory upfront ensures that later changes
+        // of the working directory don't affect it.
+        let mut initial_config = config.clone();
+        initial_config.checkpoint_dir =
+            std::path::absolute(&config.checkpoint_dir).map_err(|source| {
+                CreationError::InvalidCheckpointDir {
+                    path: config.checkpoint_dir.clone(),
+                    source,
+                }
+            })?;
+
         let skip_unsupported_transaction_types = get_skip_unsupported_transaction_types_from_env();
 
         let parent_beacon_block_root_generator = if let Some(initial_parent_beacon_block_root) =
@@ -669,13 +1336,28 @@ where
             RandomHashGenerator::with_seed("randomParentBeaconBlockRootSeed")
         };
 
-        let transaction_gas_cap = if let Some(cap) = config.transaction_gas_cap {
-            Some(cap)
-        } else if config.hardfork.into() >= EvmSpecId::OSAKA {
-            Some(eip7825::MAX_TX_GAS_LIMIT_OSAKA)
-        } else {
-            None
-        };
+        let verified_sources = config.fork.as_ref().and_then(|fork_config| {
+            let verified_sources = fork_config.verified_sources.clone()?;
+            let chain_id = fork_metadata.as_ref()?.chain_id;
+
+            match VerifiedSourceFetcher::new(verified_sources, &fork_config.cache_dir, chain_id) {
+                Ok(fetcher) => Some(VerifiedSourceTracker::new(fetcher)),
+                Err(error) => {
+                    log::warn!("Failed to create the verified source fetcher: {error}");
+                    None
+                }
+            }
+        });
+
+        let transaction_gas_cap =
+            transaction_gas_cap(config.transaction_gas_cap, blockchain.hardfork());
+
+        let mut mem_pool = MemPool::with_policy(
+            block_gas_limit,
+            transaction_gas_cap,
+            config.mining.mem_pool.policy.clone(),
+        );
+        mem_pool.set_balance_check_disabled(config.disable_balance_check);
 
         Ok(Self {
             runtime_handle,
@@ -683,40 +1365,80 @@ where
             bail_on_transaction_failure: config.bail_on_transaction_failure,
             base_fee_params: config.base_fee_params,
             blockchain,
+            blob_sidecars: HashTrieMapSync::default(),
             irregular_state,
-            mem_pool: MemPool::new(block_gas_limit, transaction_gas_cap),
+            mem_pool,
             mining_config: config.mining,
+            mine_ordering_callback: None,
+            transaction_firewall: None,
+            transaction_tags: HashMap::default(),
+            inclusion_policy: InclusionPolicy::default(),
+            delayed_transactions: HashMap::default(),
+            queued_deposits: Vec::new(),
+            storage_layouts: HashMap::default(),
             network_id: config.network_id,
             observability,
             precompile_overrides: config.precompile_overrides,
             beneficiary,
+            block_gas_limit_schedule: config.block_gas_limit_schedule,
+            transaction_gas_cap_override: config.transaction_gas_cap,
             min_gas_price,
             parent_beacon_block_root_generator,
             prev_randao_generator,
+            block_clock: BlockClock::new(config.time_mode, &timer),
             block_time_offset_seconds,
             fork_metadata,
+            initial_config,
             rpc_client,
             instance_id: B256::random(),
             is_auto_mining,
             next_block_base_fee_per_gas,
+            next_block_difficulty: None,
+            disable_base_fee_adjustment: config.disable_base_fee_adjustment,
+            disable_base_fee_check: config.disable_base_fee_check,
+            disable_priority_fee_check: config.disable_priority_fee_check,
+            max_logs_block_range: config.max_logs_block_range,
+            max_logs_results: config.max_logs_results,
+            record_state_diffs: config.record_state_diffs,
+            state_diffs: HashMap::default(),
+            address_index: AddressIndex::default(),
+            index_address_transactions: config.index_address_transactions,
+            index_token_transfers: config.index_token_transfers,
+            token_transfers: TokenTransferIndex::default(),
+            next_block_header_overrides: None,
+            next_block_state_root: None,
             next_block_timestamp: None,
+            next_block_withdrawals: None,
             // Start with 1 to mimic Ganache
             next_snapshot_id: 1,
             snapshots: BTreeMap::new(),
             allow_blocks_with_same_timestamp,
             allow_unlimited_contract_size,
+            allow_unlimited_init_code_size: config.allow_unlimited_init_code_size,
             skip_unsupported_transaction_types,
             local_accounts,
+            keystore: Keystore::default(),
             filters: HashMap::default(),
             last_filter_id: U256::ZERO,
             logger,
+            metrics: MetricsRecorder::default(),
             impersonated_accounts: HashSet::default(),
+            auto_impersonate: config.auto_impersonate,
+            auto_fund: config.auto_fund,
+            gas_sponsor: config.gas_sponsor,
+            sponsored_transactions: HashMap::default(),
             subscriber_callback,
             timer,
             block_state_cache,
             current_state_id,
+            reconstructed_state_ids: VecDeque::new(),
+            first_local_block_number: blockchain.last_block_number() + 1,
+            first_unpruned_block_number: blockchain.last_block_number() + 1,
             block_number_to_state_id,
+            state_pruning: config.state_pruning,
+            pending_block: None,
             contract_decoder,
+            verified_sources,
         })
     }
 
@@ -856,6 +1578,18 @@ where
             .map_err(ProviderError::Blockchain)
     }
 
+    /// Returns the block with the provided number, if it exists.
+    // `SyncBlock` cannot be simplified further
+    #[allow(clippy::type_complexity)]
+    pub fn block_by_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<Arc<ChainSpecT::Block>>, ProviderErrorForChainSpec<ChainSpecT>> {
+        self.blockchain
+            .block_by_number(block_number)
+            .map_err(ProviderError::Blockchain)
+    }
+
     pub fn gas_price(&self) -> Result<u128, ProviderErrorForChainSpec<ChainSpecT>> {
         const PRE_EIP_1559_GAS_PRICE: u128 = 8_000_000_000;
         const SUGGESTED_PRIORITY_FEE_PER_GAS: u128 = 1_000_000_000;
@@ -868,33 +1602,135 @@ where
         }
     }
 
+    /// Retrieves the logs matching the provided filter, subject to the
+    /// configured block range and result limits.
     pub fn logs(
         &self,
         filter: LogFilter,
     ) -> Result<Vec<FilterLog>, ProviderErrorForChainSpec<ChainSpecT>> {
-        self.blockchain
-            .logs(
-                filter.from_block,
-                filter
-                    .to_block
-                    .unwrap_or(self.blockchain.last_block_number()),
-                &filter.addresses,
-                &filter.normalized_topics,
+        let from_block = filter.from_block;
+        let to_block = filter
+            .to_block
+            .unwrap_or(self.blockchain.last_block_number());
+
+        if let Some(max_block_range) = self.max_logs_block_range
+            && to_block.saturating_sub(from_block).saturating_add(1) > max_block_range
+        {
+            return Err(ProviderError::LogsBlockRangeExceeded {
+                from_block,
+                to_block,
+                max_block_range,
+            });
+        }
+
+        if let Some(max_results) = self.max_logs_results {
+            return self.logs_within_limit(from_block, to_block, &filter, max_results);
+        }
+
+        self.blockchain
+            .logs(
+                from_block,
+                to_block,
+                &filter.addresses,
+                &filter.normalized_topics,
             )
             .map_err(ProviderError::Blockchain)
     }
 
+    /// Retrieves the logs matching the provided filter, stopping the scan
+    /// once more than `max_results` logs were found.
+    ///
+    /// Local blocks are scanned in ranges of doubling size, so only a
+    /// logarithmic number of scans - each of which skips blocks using their
+    /// log blooms - is needed. The remote blocks of a fork are retrieved in a
+    /// single request.
+    fn logs_within_limit(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        filter: &LogFilter,
+        max_results: u64,
+    ) -> Result<Vec<FilterLog>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let first_local_block_number = self
+            .fork_metadata()
+            .map_or(0, |fork_metadata| fork_metadata.fork_block_number + 1);
+        let last_block_number = to_block.min(self.blockchain.last_block_number());
+        let max_results_len = usize::try_from(max_results).unwrap_or(usize::MAX);
+
+        let mut logs = Vec::new();
+        let mut next_block_number = from_block;
+        let mut local_range_size = 1u64;
+        while next_block_number <= last_block_number {
+            let scan_to_block_number = if next_block_number < first_local_block_number {
+                last_block_number.min(first_local_block_number - 1)
+            } else {
+                let scan_to_block_number =
+                    last_block_number.min(next_block_number.saturating_add(local_range_size - 1));
+                local_range_size = local_range_size.saturating_mul(2);
+
+                scan_to_block_number
+            };
+
+            logs.extend(
+                self.blockchain
+                    .logs(
+                        next_block_number,
+                        scan_to_block_number,
+                        &filter.addresses,
+                        &filter.normalized_topics,
+                    )
+                    .map_err(ProviderError::Blockchain)?,
+            );
+
+            if let Some(first_excess_log) = logs.get(max_results_len) {
+                // Suggest the largest range whose logs all fit within the
+                // limit. If the first block alone exceeds the limit, it can't
+                // be split.
+                let to_block = first_excess_log
+                    .block_number
+                    .saturating_sub(1)
+                    .max(from_block);
+
+                return Err(ProviderError::LogsResultsExceeded {
+                    from_block,
+                    to_block,
+                    max_results,
+                });
+            }
+
+            next_block_number = scan_to_block_number + 1;
+        }
+
+        Ok(logs)
+    }
+
     pub fn set_account_storage_slot(
         &mut self,
         address: Address,
         index: U256,
         value: U256,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        self.set_account_storage_slots(address, [(index, value)])
+    }
+
+    /// Sets the provided storage slots of the account at the specified
+    /// address. Either all or none of the slots are set.
+    fn set_account_storage_slots(
+        &mut self,
+        address: Address,
+        slots: impl IntoIterator<Item = (U256, U256)>,
     ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
         // We clone to automatically revert in case of subsequent errors.
         let mut modified_state = (*self.current_state()?).clone();
-        let old_value = modified_state.set_account_storage_slot(address, index, value)?;
+        let changed_slots = slots
+            .into_iter()
+            .map(|(index, value)| {
+                let old_value = modified_state.set_account_storage_slot(address, index, value)?;
+
+                Ok((index, EvmStorageSlot::new_changed(old_value, value, 0)))
+            })
+            .collect::<Result<Vec<_>, StateError>>()?;
 
-        let slot = EvmStorageSlot::new_changed(old_value, value, 0);
         let account_info = modified_state.basic(address).and_then(|mut account_info| {
             // Retrieve the code if it's not empty. This is needed for the irregular state.
             if let Some(account_info) = &mut account_info
@@ -908,12 +1744,70 @@ where
 
         let state_root = modified_state.state_root()?;
 
+        let block_number = self.blockchain.last_block_number();
+        let state_override = self
+            .irregular_state
+            .state_override_at_block_number(block_number)
+            .or_insert_with(|| StateOverride::with_state_root(state_root));
+
+        for (index, slot) in changed_slots {
+            state_override
+                .diff
+                .apply_storage_change(address, index, slot, account_info.clone());
+        }
+
+        self.add_state_to_cache(modified_state, block_number);
+
+        Ok(())
+    }
+
+    /// Stores the provided beacon root for the specified timestamp in the
+    /// EIP-4788 beacon roots contract.
+    pub fn set_beacon_root(
+        &mut self,
+        timestamp: u64,
+        root: B256,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let hardfork = self.hardfork();
+        if hardfork.into() < EvmSpecId::CANCUN {
+            return Err(ProviderError::SetBeaconRootUnsupported { hardfork });
+        }
+
+        let (timestamp_index, root_index) = beacon_root_storage_indices(timestamp);
+        self.set_account_storage_slot(
+            BEACON_ROOTS_ADDRESS,
+            timestamp_index,
+            U256::from(timestamp),
+        )?;
+        self.set_account_storage_slot(
+            BEACON_ROOTS_ADDRESS,
+            root_index,
+            U256::from_be_bytes(root.0),
+        )?;
+
+        Ok(())
+    }
+
+    /// Overrides the storage root of the account at the specified address,
+    /// which is used when calculating the state root. Pass `None` to remove
+    /// the override.
+    pub fn set_account_storage_root(
+        &mut self,
+        address: Address,
+        storage_root: Option<B256>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        // We clone to automatically revert in case of subsequent errors.
+        let mut modified_state = (*self.current_state()?).clone();
+        modified_state.set_account_storage_root(address, storage_root)?;
+
+        let state_root = modified_state.state_root()?;
+
         let block_number = self.blockchain.last_block_number();
         self.irregular_state
             .state_override_at_block_number(block_number)
             .or_insert_with(|| StateOverride::with_state_root(state_root))
-            .diff
-            .apply_storage_change(address, index, slot, account_info);
+            .storage_roots
+            .insert(address, storage_root);
 
         self.add_state_to_cache(modified_state, block_number);
 
@@ -926,6 +1820,7 @@ where
         balance: U256,
     ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
         let mut modified_state = (*self.current_state()?).clone();
+        let original_info = modified_state.basic(address)?;
         let account_info = modified_state.modify_account(
             address,
             AccountModifierFn::new(Box::new(move |account_balance, _, _| {
@@ -935,29 +1830,315 @@ where
 
         let state_root = modified_state.state_root()?;
 
-        self.mem_pool.update(&modified_state)?;
+        let removed = self.mem_pool.update(&modified_state)?;
 
         let block_number = self.blockchain.last_block_number();
-        self.irregular_state
+        let diff = &mut self
+            .irregular_state
             .state_override_at_block_number(block_number)
             .or_insert_with(|| StateOverride::with_state_root(state_root))
-            .diff
-            .apply_account_change(address, account_info.clone());
+            .diff;
+
+        diff.record_original_account_info(address, original_info);
+        diff.apply_account_change(address, account_info.clone());
+
+        self.add_state_to_cache(modified_state, block_number);
+
+        self.handle_dropped_transactions(&removed)
+    }
+
+    /// Tops up the balance of the transaction's sender to cover its upfront
+    /// cost, if auto-funding is enabled and the sender is impersonated.
+    ///
+    /// Returns the sender's previous balance if it was topped up.
+    pub fn top_up_impersonated_sender(
+        &mut self,
+        transaction: &ChainSpecT::SignedTransaction,
+    ) -> Result<Option<U256>, ProviderErrorForChainSpec<ChainSpecT>> {
+        self.top_up_impersonated_account(
+            *transaction.caller(),
+            edr_transaction::upfront_cost(transaction),
+        )
+    }
+
+    /// Tops up the balance of the account to the required balance, if
+    /// auto-funding is enabled and the account is impersonated.
+    ///
+    /// Returns the account's previous balance if it was topped up.
+    pub fn top_up_impersonated_account(
+        &mut self,
+        address: Address,
+        required_balance: U256,
+    ) -> Result<Option<U256>, ProviderErrorForChainSpec<ChainSpecT>> {
+        if !self.auto_fund || !self.is_impersonated(&address) {
+            return Ok(None);
+        }
+
+        let balance = self
+            .current_state()?
+            .basic(address)?
+            .map_or(U256::ZERO, |account| account.balance);
+
+        if balance >= required_balance {
+            return Ok(None);
+        }
+
+        self.set_balance(address, required_balance)?;
+
+        Ok(Some(balance))
+    }
+
+    /// Transfers the maximum gas cost of the transaction from the gas sponsor
+    /// to the transaction's sender, if the sender is sponsored.
+    pub fn sponsor_transaction(
+        &mut self,
+        transaction: &ChainSpecT::SignedTransaction,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let sender = *transaction.caller();
+        let Some(sponsor) = self.gas_sponsor_of(&sender) else {
+            return Ok(());
+        };
+
+        let gas_cost =
+            edr_transaction::upfront_cost(transaction).saturating_sub(*transaction.value());
+        if gas_cost.is_zero() {
+            return Ok(());
+        }
+
+        let sponsor_balance = self.current_balance(sponsor)?;
+        if sponsor_balance < gas_cost {
+            return Err(ProviderError::GasSponsorInsufficientFunds {
+                sponsor,
+                balance: sponsor_balance,
+                required: gas_cost,
+            });
+        }
+
+        self.transfer_gas_costs(vec![(sponsor, sender, gas_cost)])?;
+        self.sponsored_transactions
+            .insert(*transaction.transaction_hash(), (sender, gas_cost));
+
+        Ok(())
+    }
+
+    /// Refunds the full prepaid gas cost of a sponsored transaction that
+    /// wasn't added to the mempool.
+    pub fn cancel_transaction_sponsorship(
+        &mut self,
+        transaction_hash: &B256,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        self.cancel_transaction_sponsorships(std::iter::once(transaction_hash))
+    }
+
+    /// Refunds the full prepaid gas costs of sponsored transactions that were
+    /// removed from the mempool without being mined.
+    fn cancel_transaction_sponsorships<'hash>(
+        &mut self,
+        transaction_hashes: impl IntoIterator<Item = &'hash B256>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let refunds = transaction_hashes
+            .into_iter()
+            .filter_map(|transaction_hash| self.sponsored_transactions.remove(transaction_hash))
+            .collect::<Vec<_>>();
+
+        self.refund_gas_sponsors(refunds)
+    }
+
+    /// Refunds the cost of unused gas of the sponsored transactions in the
+    /// provided block to the gas sponsor.
+    fn refund_gas_sponsor_for_block(
+        &mut self,
+        block: &ChainSpecT::Block,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let receipts = block
+            .fetch_transaction_receipts()
+            .map_err(ProviderError::FetchReceipt)?;
+
+        let refunds = receipts
+            .iter()
+            .filter_map(|receipt| {
+                let (sender, prepaid_gas_cost) = self
+                    .sponsored_transactions
+                    .remove(receipt.transaction_hash())?;
+
+                let gas_cost =
+                    receipt
+                        .effective_gas_price()
+                        .map_or(prepaid_gas_cost, |effective_gas_price| {
+                            U256::from(receipt.gas_used()) * U256::from(*effective_gas_price)
+                        });
+
+                Some((sender, prepaid_gas_cost.saturating_sub(gas_cost)))
+            })
+            .collect::<Vec<_>>();
+
+        self.refund_gas_sponsors(refunds)
+    }
+
+    fn refund_gas_sponsors(
+        &mut self,
+        refunds: Vec<(Address, U256)>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let Some(sponsor) = self
+            .gas_sponsor
+            .as_ref()
+            .map(|gas_sponsor| gas_sponsor.sponsor)
+        else {
+            return Ok(());
+        };
+
+        let transfers = refunds
+            .into_iter()
+            .map(|(sender, amount)| (sender, sponsor, amount))
+            .collect();
+
+        self.transfer_gas_costs(transfers)
+    }
+
+    /// Applies the provided `(from, to, amount)` transfers as a single
+    /// irregular state change at the last block.
+    ///
+    /// Gas costs are only known once the transactions have executed, so -
+    /// like `hardhat_setBalance` - they are recorded as an irregular state
+    /// override instead of being part of a block's state transition. This
+    /// ensures that states recomputed from the blockchain include them.
+    fn transfer_gas_costs(
+        &mut self,
+        transfers: Vec<(Address, Address, U256)>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let mut modified_state = (*self.current_state()?).clone();
+
+        let mut original_infos = Vec::new();
+        let mut account_changes = Vec::new();
+        for (from, to, amount) in transfers {
+            // The sender might have spent part of the prepaid gas cost in the meantime
+            let from_balance = modified_state
+                .basic(from)?
+                .map_or(U256::ZERO, |account| account.balance);
+
+            let amount = amount.min(from_balance);
+            if amount.is_zero() {
+                continue;
+            }
+
+            original_infos.push((from, modified_state.basic(from)?));
+            original_infos.push((to, modified_state.basic(to)?));
+
+            let from_info = modified_state.modify_account(
+                from,
+                AccountModifierFn::new(Box::new(move |balance, _, _| {
+                    *balance -= amount;
+                })),
+            )?;
+            account_changes.push((from, from_info));
+
+            let to_info = modified_state.modify_account(
+                to,
+                AccountModifierFn::new(Box::new(move |balance, _, _| {
+                    *balance = balance.saturating_add(amount);
+                })),
+            )?;
+            account_changes.push((to, to_info));
+        }
+
+        if account_changes.is_empty() {
+            return Ok(());
+        }
+
+        let state_root = modified_state.state_root()?;
+
+        let removed = self.mem_pool.update(&modified_state)?;
+
+        let block_number = self.blockchain.last_block_number();
+        let state_override = self
+            .irregular_state
+            .state_override_at_block_number(block_number)
+            .or_insert_with(|| StateOverride::with_state_root(state_root));
+
+        state_override.state_root = state_root;
+        for (address, original_info) in original_infos {
+            state_override
+                .diff
+                .record_original_account_info(address, original_info);
+        }
+        for (address, account_info) in account_changes {
+            state_override
+                .diff
+                .apply_account_change(address, account_info);
+        }
 
         self.add_state_to_cache(modified_state, block_number);
 
+        self.handle_dropped_transactions(&removed)
+    }
+
+    fn gas_sponsor_of(&self, sender: &Address) -> Option<Address> {
+        self.gas_sponsor
+            .as_ref()
+            .filter(|gas_sponsor| gas_sponsor.senders.contains(sender))
+            .map(|gas_sponsor| gas_sponsor.sponsor)
+    }
+
+    fn current_balance(
+        &mut self,
+        address: Address,
+    ) -> Result<U256, ProviderErrorForChainSpec<ChainSpecT>> {
+        Ok(self
+            .current_state()?
+            .basic(address)?
+            .map_or(U256::ZERO, |account| account.balance))
+    }
+
+    /// Applies the last scheduled block gas limit within the provided range of
+    /// block numbers, if any.
+    fn apply_block_gas_limit_schedule(
+        &mut self,
+        block_numbers: RangeInclusive<u64>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        if let Some(gas_limit) = self
+            .block_gas_limit_schedule
+            .range(block_numbers)
+            .next_back()
+            .map(|(_, gas_limit)| *gas_limit)
+        {
+            self.set_block_gas_limit(gas_limit)?;
+        }
+
         Ok(())
     }
 
+    /// Applies the transaction gas cap of the hardfork of the next block,
+    /// unless the user provided a transaction gas cap.
+    fn apply_transaction_gas_cap(&mut self) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let cap = transaction_gas_cap(
+            self.transaction_gas_cap_override,
+            self.blockchain.hardfork(),
+        );
+        if cap == self.mem_pool.transaction_gas_cap() {
+            return Ok(());
+        }
+
+        let state = self.current_state()?;
+        let removed = self
+            .mem_pool
+            .set_transaction_gas_cap(&*state, cap)
+            .map_err(ProviderError::State)?;
+
+        self.handle_dropped_transactions(&removed)
+    }
+
     /// Sets the gas limit used for mining new blocks.
     pub fn set_block_gas_limit(
         &mut self,
         gas_limit: NonZeroU64,
     ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
         let state = self.current_state()?;
-        self.mem_pool
+        let removed = self
+            .mem_pool
             .set_block_gas_limit(&*state, gas_limit)
-            .map_err(ProviderError::State)
+            .map_err(ProviderError::State)?;
+
+        self.handle_dropped_transactions(&removed)
     }
 
     pub fn set_code(
@@ -970,6 +2151,7 @@ where
 
         // We clone to automatically revert in case of subsequent errors.
         let mut modified_state = (*self.current_state()?).clone();
+        let original_info = modified_state.basic(address)?;
         let mut account_info = modified_state.modify_account(
             address,
             AccountModifierFn::new(Box::new(move |_, _, account_code| {
@@ -984,28 +2166,81 @@ where
         let state_root = modified_state.state_root()?;
 
         let block_number = self.blockchain.last_block_number();
-        self.irregular_state
+        let diff = &mut self
+            .irregular_state
             .state_override_at_block_number(block_number)
             .or_insert_with(|| StateOverride::with_state_root(state_root))
-            .diff
-            .apply_account_change(address, account_info.clone());
+            .diff;
+
+        diff.record_original_account_info(address, original_info);
+        diff.apply_account_change(address, account_info.clone());
+
+        // Overridden code doesn't match the verified source of a forked contract
+        if let Some(verified_sources) = self.verified_sources.as_mut() {
+            verified_sources.add_local_contract(address);
+        }
 
         self.add_state_to_cache(modified_state, block_number);
 
         Ok(())
     }
 
-    pub fn set_min_gas_price(
+    /// Sets the mem pool's policy, dropping any transactions that exceed its
+    /// limits.
+    pub fn set_mem_pool_policy(
         &mut self,
-        min_gas_price: u128,
+        policy: MemPoolPolicy,
     ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
-        if self.evm_spec_id() >= EvmSpecId::LONDON {
-            return Err(ProviderError::SetMinGasPriceUnsupported);
+        self.mining_config.mem_pool.policy = policy.clone();
+
+        let removed = self.mem_pool.set_policy(policy, self.timer.since_epoch());
+        for transaction in &removed {
+            log::debug!(
+                "Dropped transaction {} from the mem pool, as it exceeds the mem pool's policy",
+                transaction.pending().transaction_hash()
+            );
         }
 
+        self.handle_dropped_transactions(&removed)
+    }
+
+    /// Sets whether contracts of any size can be deployed.
+    pub fn set_allow_unlimited_contract_size(&mut self, allow: bool) {
+        self.allow_unlimited_contract_size = allow;
+    }
+
+    /// Sets whether deployments with init code of any size are allowed.
+    pub fn set_allow_unlimited_init_code_size(&mut self, allow: bool) {
+        self.allow_unlimited_init_code_size = allow;
+    }
+
+    /// Sets the minimum gas price. When EIP-1559 is active, this is the
+    /// minimum priority fee per gas.
+    pub fn set_min_gas_price(&mut self, min_gas_price: u128) {
         self.min_gas_price = min_gas_price;
+    }
 
-        Ok(())
+    /// Sets which fee and balance checks are disabled. Omitted checks retain
+    /// their current setting.
+    pub fn set_fee_checks(&mut self, config: FeeChecksConfig) {
+        let FeeChecksConfig {
+            disable_balance_check,
+            disable_base_fee_check,
+            disable_priority_fee_check,
+        } = config;
+
+        if let Some(disable_balance_check) = disable_balance_check {
+            self.mem_pool
+                .set_balance_check_disabled(disable_balance_check);
+        }
+
+        if let Some(disable_base_fee_check) = disable_base_fee_check {
+            self.disable_base_fee_check = disable_base_fee_check;
+        }
+
+        if let Some(disable_priority_fee_check) = disable_priority_fee_check {
+            self.disable_priority_fee_check = disable_priority_fee_check;
+        }
     }
 
     /// Sets the next block's base fee per gas.
@@ -1023,46 +2258,227 @@ where
         Ok(())
     }
 
-    /// Set the next block timestamp.
-    pub fn set_next_block_timestamp(
+    /// Enqueues a deposit transaction to be force-included at the top of the
+    /// next block, after previously enqueued deposits. Returns the
+    /// transaction's hash.
+    pub fn enqueue_deposit(
         &mut self,
-        timestamp: u64,
-    ) -> Result<u64, ProviderErrorForChainSpec<ChainSpecT>> {
-        let latest_block = self.blockchain.last_block()?;
-        let latest_block_header = latest_block.block_header();
+        transaction: ChainSpecT::SignedTransaction,
+    ) -> Result<B256, ProviderErrorForChainSpec<ChainSpecT>> {
+        if !transaction.is_deposit() {
+            return Err(ProviderError::InvalidInput(
+                "Only deposit transactions can be enqueued".to_string(),
+            ));
+        }
 
-        match timestamp.cmp(&latest_block_header.timestamp) {
-            Ordering::Less => Err(ProviderError::TimestampLowerThanPrevious {
-                proposed: timestamp,
-                previous: latest_block_header.timestamp,
-            }),
-            Ordering::Equal if !self.allow_blocks_with_same_timestamp => {
-                Err(ProviderError::TimestampEqualsPrevious {
-                    proposed: timestamp,
-                })
-            }
-            Ordering::Equal | Ordering::Greater => {
-                self.next_block_timestamp = Some(timestamp);
-                Ok(timestamp)
-            }
+        let transaction_hash = *transaction.transaction_hash();
+        if self
+            .queued_deposits
+            .iter()
+            .any(|queued| *queued.transaction_hash() == transaction_hash)
+        {
+            return Err(ProviderError::InvalidInput(format!(
+                "Deposit transaction {transaction_hash} is already enqueued"
+            )));
         }
+
+        if self
+            .blockchain
+            .block_by_transaction_hash(&transaction_hash)?
+            .is_some()
+        {
+            return Err(ProviderError::InvalidInput(format!(
+                "Deposit transaction {transaction_hash} was already mined"
+            )));
+        }
+
+        // All queued deposits need to fit in the next block
+        let queued_gas = self
+            .queued_deposits
+            .iter()
+            .map(ExecutableTransaction::gas_limit)
+            .sum::<u64>();
+
+        let block_gas_limit = self.block_gas_limit();
+        if queued_gas.saturating_add(transaction.gas_limit()) > block_gas_limit {
+            return Err(ProviderError::InvalidInput(format!(
+                "Deposit transaction {transaction_hash} with gas limit {} doesn't fit in the next block, which has {} gas remaining for deposits",
+                transaction.gas_limit(),
+                block_gas_limit.saturating_sub(queued_gas)
+            )));
+        }
+
+        self.queued_deposits.push(transaction);
+        self.notify_subscribers_about_pending_transaction(&transaction_hash);
+
+        Ok(transaction_hash)
     }
 
-    /// Sets the next block's prevrandao.
-    pub fn set_next_prev_randao(
+    /// Sets overrides for fields of the next block's header.
+    pub fn set_next_block_header_overrides(
         &mut self,
-        prev_randao: B256,
+        overrides: NextBlockHeaderOverrides,
     ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
-        let hardfork = self.hardfork();
-        if hardfork.into() < EvmSpecId::MERGE {
-            return Err(ProviderError::SetNextPrevRandaoUnsupported { hardfork });
+        if let Some(extra_data) = &overrides.extra_data
+            && extra_data.len() > MAX_EXTRA_DATA_SIZE
+        {
+            return Err(ProviderError::InvalidInput(format!(
+                "Extra data must be at most {MAX_EXTRA_DATA_SIZE} bytes long, but got {} bytes",
+                extra_data.len()
+            )));
+        }
+
+        if overrides.gas_limit == Some(U64::ZERO) {
+            return Err(ProviderError::InvalidInput(
+                "Gas limit must be a positive integer".to_string(),
+            ));
         }
 
-        self.prev_randao_generator.set_next(prev_randao);
+        self.next_block_header_overrides = Some(overrides);
 
         Ok(())
     }
 
+    /// Sets the next block's state root, overriding the root of the state
+    /// that results from mining it.
+    pub fn set_next_block_state_root(&mut self, state_root: B256) {
+        self.next_block_state_root = Some(state_root);
+    }
+
+    /// Sets the withdrawals to include in the next block. Withdrawn amounts
+    /// are credited to their recipients when the block is mined.
+    pub fn set_next_block_withdrawals(
+        &mut self,
+        withdrawals: Vec<Withdrawal>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let hardfork = self.hardfork();
+        if hardfork.into() < EvmSpecId::SHANGHAI {
+            return Err(ProviderError::SetNextBlockWithdrawalsUnsupported { hardfork });
+        }
+
+        self.next_block_withdrawals = Some(withdrawals);
+
+        Ok(())
+    }
+
+    /// Stores the blob sidecar of an EIP-4844 transaction, such that it can be
+    /// retrieved once the transaction has been mined.
+    pub fn add_blob_sidecar(&mut self, transaction_hash: B256, sidecar: BlobTransactionSidecar) {
+        self.blob_sidecars.insert_mut(transaction_hash, sidecar);
+    }
+
+    /// Retrieves the blob sidecars of the transactions in the block with the
+    /// provided hash, if the block exists.
+    ///
+    /// Only blobs of transactions that were sent to this provider are
+    /// available.
+    pub fn blob_sidecars_by_block_hash(
+        &self,
+        block_hash: &B256,
+    ) -> Result<Option<Vec<BlobSidecar>>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let Some(block) = self.blockchain.block_by_hash(block_hash)? else {
+            return Ok(None);
+        };
+
+        let block_number = block.block_header().number;
+
+        let mut blob_sidecars = Vec::new();
+        for (transaction_index, transaction) in block.transactions().iter().enumerate() {
+            let transaction_hash = transaction.transaction_hash();
+            let Some(sidecar) = self.blob_sidecars.get(transaction_hash) else {
+                continue;
+            };
+
+            for (blob, kzg_commitment, kzg_proof) in
+                izip!(&sidecar.blobs, &sidecar.commitments, &sidecar.proofs)
+            {
+                blob_sidecars.push(BlobSidecar {
+                    index: blob_sidecars.len() as u64,
+                    blob: *blob,
+                    kzg_commitment: *kzg_commitment,
+                    kzg_proof: *kzg_proof,
+                    block_hash: *block_hash,
+                    block_number,
+                    transaction_hash: *transaction_hash,
+                    transaction_index: transaction_index as u64,
+                });
+            }
+        }
+
+        Ok(Some(blob_sidecars))
+    }
+
+    /// Set the next block timestamp.
+    pub fn set_next_block_timestamp(
+        &mut self,
+        timestamp: u64,
+    ) -> Result<u64, ProviderErrorForChainSpec<ChainSpecT>> {
+        let latest_block = self.blockchain.last_block()?;
+        let latest_block_header = latest_block.block_header();
+
+        match timestamp.cmp(&latest_block_header.timestamp) {
+            Ordering::Less => Err(ProviderError::TimestampLowerThanPrevious {
+                proposed: timestamp,
+                previous: latest_block_header.timestamp,
+            }),
+            Ordering::Equal if !self.allow_blocks_with_same_timestamp => {
+                Err(ProviderError::TimestampEqualsPrevious {
+                    proposed: timestamp,
+                })
+            }
+            Ordering::Equal | Ordering::Greater => {
+                self.next_block_timestamp = Some(timestamp);
+                Ok(timestamp)
+            }
+        }
+    }
+
+    /// Sets the clock's current time, such that subsequent blocks use it as
+    /// their base timestamp. Returns the new offset of the clock, in seconds.
+    pub fn set_time(
+        &mut self,
+        timestamp: u64,
+    ) -> Result<i64, ProviderErrorForChainSpec<ChainSpecT>> {
+        let latest_block = self.blockchain.last_block()?;
+        let latest_block_timestamp = latest_block.block_header().timestamp;
+
+        if timestamp < latest_block_timestamp {
+            return Err(ProviderError::TimestampLowerThanPrevious {
+                proposed: timestamp,
+                previous: latest_block_timestamp,
+            });
+        }
+
+        let timestamp_too_large =
+            || ProviderError::InvalidInput(format!("Timestamp {timestamp} is too large"));
+
+        let current_timestamp = i128::from(self.block_clock.now(&self.timer));
+        self.block_time_offset_seconds = (i128::from(timestamp) - current_timestamp)
+            .try_into()
+            .map_err(|_error| timestamp_too_large())?;
+
+        // The new time supersedes a previously set timestamp for the next block
+        self.next_block_timestamp.take();
+
+        Ok(self.block_time_offset_seconds)
+    }
+
+    /// Sets the next block's prevrandao. Before the merge, this sets the next
+    /// block's difficulty instead.
+    pub fn set_next_prev_randao(&mut self, prev_randao: B256) {
+        if self.evm_spec_id() >= EvmSpecId::MERGE {
+            self.prev_randao_generator.set_next(prev_randao);
+        } else {
+            self.next_block_difficulty = Some(U256::from_be_bytes(prev_randao.0));
+        }
+    }
+
+    /// Restarts the generator of prevrandao values with the provided seed, so
+    /// the prevrandao values of subsequently mined blocks are reproducible.
+    pub fn set_prev_randao_seed(&mut self, seed: &str) {
+        self.prev_randao_generator = RandomHashGenerator::with_seed(seed);
+    }
+
     pub fn set_nonce(
         &mut self,
         address: Address,
@@ -1086,6 +2502,7 @@ where
 
         // We clone to automatically revert in case of subsequent errors.
         let mut modified_state = (*self.current_state()?).clone();
+        let original_info = modified_state.basic(address)?;
         let account_info = modified_state.modify_account(
             address,
             AccountModifierFn::new(Box::new(move |_, account_nonce, _| *account_nonce = nonce)),
@@ -1093,63 +2510,43 @@ where
 
         let state_root = modified_state.state_root()?;
 
-        self.mem_pool.update(&modified_state)?;
+        let removed = self.mem_pool.update(&modified_state)?;
 
         let block_number = self.last_block_number();
-        self.irregular_state
+        let diff = &mut self
+            .irregular_state
             .state_override_at_block_number(block_number)
             .or_insert_with(|| StateOverride::with_state_root(state_root))
-            .diff
-            .apply_account_change(address, account_info.clone());
+            .diff;
+
+        diff.record_original_account_info(address, original_info);
+        diff.apply_account_change(address, account_info.clone());
 
         self.add_state_to_cache(modified_state, block_number);
 
-        Ok(())
+        self.handle_dropped_transactions(&removed)
     }
 
     /// Dumps the current state of all accounts in Anvil-compatible format.
     pub fn dump_state(&mut self) -> Result<StateDump, ProviderErrorForChainSpec<ChainSpecT>> {
         let state = self.current_state()?;
 
-        // Use the serialize method to get all accounts, then convert to Anvil format
-        let serialized = state.serialize();
-
-        // Parse the serialized state - it's a BTreeMap<Address, StateAccount> format
-        #[derive(serde::Deserialize)]
-        struct SerializedAccount {
-            balance: U256,
-            code_hash: B256,
-            nonce: u64,
-            storage: std::collections::BTreeMap<B256, U256>,
-            #[allow(dead_code)]
-            storage_root: B256,
-        }
-
-        let serialized_state: std::collections::BTreeMap<Address, SerializedAccount> =
-            serde_json::from_str(&serialized).map_err(ProviderError::Serialization)?;
-
         let mut dump = StateDump::new();
 
-        for (address, account) in serialized_state {
-            // Look up the code by hash
-            let code = if account.code_hash != KECCAK_EMPTY {
-                let bytecode = state.code_by_hash(account.code_hash)?;
+        for (address, account_info) in state.iter_accounts()? {
+            let code = if account_info.code_hash != KECCAK_EMPTY {
+                let bytecode = state.code_by_hash(account_info.code_hash)?;
                 bytecode.original_bytes()
             } else {
                 Bytes::new()
             };
 
-            // Convert storage from BTreeMap<B256, U256> to HashMap<U256, U256>
-            let storage: HashMap<U256, U256> = account
-                .storage
-                .into_iter()
-                .map(|(k, v)| (U256::from_be_bytes(k.0), v))
-                .collect();
+            let storage: HashMap<U256, U256> = state.iter_storage(&address)?.collect();
 
             let state_account = StateAccount {
-                balance: account.balance,
+                balance: account_info.balance,
                 code,
-                nonce: U256::from(account.nonce),
+                nonce: U256::from(account_info.nonce),
                 storage,
             };
 
@@ -1159,6 +2556,39 @@ where
         Ok(dump)
     }
 
+    /// Returns the local modifications of the state of the block with the
+    /// provided number, e.g. using `hardhat_setBalance`, in Anvil-compatible
+    /// format.
+    pub fn state_overrides_dump(&self, block_number: u64) -> Option<StateDump> {
+        self.irregular_state
+            .state_overrides()
+            .get(&block_number)
+            .map(|state_override| {
+                let mut dump = StateDump::new();
+
+                for (address, account) in state_override.diff.as_inner() {
+                    let state_account = StateAccount {
+                        balance: account.info.balance,
+                        code: account
+                            .info
+                            .code
+                            .as_ref()
+                            .map_or_else(Bytes::new, Bytecode::original_bytes),
+                        nonce: U256::from(account.info.nonce),
+                        storage: account
+                            .storage
+                            .iter()
+                            .map(|(index, slot)| (*index, slot.present_value))
+                            .collect(),
+                    };
+
+                    dump.add_account(*address, state_account);
+                }
+
+                dump
+            })
+    }
+
     /// Loads state from an Anvil-compatible state dump, merging with existing state.
     pub fn load_state(
         &mut self,
@@ -1195,30 +2625,172 @@ where
         Ok(())
     }
 
+    /// Re-bases the local state onto a new fork block of the remote
+    /// blockchain, returning the metadata of the updated fork.
+    ///
+    /// If no block number is provided, the most recent block that is safe from
+    /// reorgs is used. Local modifications of the fork block's state, e.g.
+    /// using `hardhat_setBalance`, are carried over to the new fork block.
+    /// Pending transactions are re-validated and snapshots are discarded.
+    ///
+    /// Fails if the provider is not forking or if local blocks have been mined
+    /// since forking.
+    pub fn update_fork(
+        &mut self,
+        block_number: Option<u64>,
+    ) -> Result<ForkMetadata, ProviderErrorForChainSpec<ChainSpecT>> {
+        let (Some(fork_metadata), Some(rpc_client)) =
+            (self.fork_metadata.clone(), self.rpc_client.clone())
+        else {
+            return Err(ProviderError::UpdateForkUnsupported);
+        };
+
+        let latest_block_number = self.blockchain.last_block_number();
+        if latest_block_number != fork_metadata.fork_block_number {
+            return Err(ProviderError::UpdateForkAfterLocalBlocks {
+                fork_block_number: fork_metadata.fork_block_number,
+                latest_block_number,
+            });
+        }
+
+        let fork_block_number = if let Some(block_number) = block_number {
+            block_number
+        } else {
+            let remote_block_number = tokio::task::block_in_place(|| {
+                self.runtime_handle.block_on(rpc_client.block_number())
+            })?;
+
+            largest_safe_block_number(LargestSafeBlockNumberArgs {
+                chain_id: fork_metadata.chain_id,
+                latest_block_number: remote_block_number,
+            })
+            .unwrap_or(remote_block_number)
+        };
+
+        if fork_block_number == fork_metadata.fork_block_number {
+            return Ok(fork_metadata);
+        }
+
+        let mut config = self.initial_config.clone();
+        if let Some(fork_config) = config.fork.as_mut() {
+            fork_config.block_number = Some(fork_block_number);
+        }
+
+        let BlockchainAndState {
+            blockchain,
+            fork_metadata: new_fork_metadata,
+            rpc_client,
+            state: _,
+            mut irregular_state,
+            prev_randao_generator: _,
+            block_time_offset_seconds,
+            next_block_base_fee_per_gas,
+        } = create_blockchain_and_state(
+            self.runtime_handle.clone(),
+            &config,
+            &self.timer,
+            Some(rpc_client),
+        )?;
+
+        // Carry over the local modifications of the previous fork block's state
+        if let Some(local_override) = self
+            .irregular_state
+            .state_overrides()
+            .get(&fork_metadata.fork_block_number)
+        {
+            let local_diff = local_override.diff.as_inner().clone();
+
+            // The state root of the previous fork block's state doesn't apply to
+            // the new fork block, so it's recomputed.
+            let mut state = blockchain
+                .state_at_block_number(fork_block_number, irregular_state.state_overrides())?;
+
+            state.commit(local_diff.clone());
+            for (address, storage_root) in &local_override.storage_roots {
+                state.set_account_storage_root(*address, *storage_root)?;
+            }
+
+            let state_root = state.state_root()?;
+
+            irregular_state
+                .state_override_at_block_number(fork_block_number)
+                .and_modify(|state_override| {
+                    state_override.diff.apply_diff(local_diff.clone());
+                    state_override
+                        .storage_roots
+                        .extend(local_override.storage_roots.clone());
+                    state_override.state_root = state_root;
+                })
+                .or_insert_with(|| StateOverride {
+                    diff: StateDiff::from(local_diff),
+                    storage_roots: local_override.storage_roots.clone(),
+                    state_root,
+                });
+        }
+
+        let new_fork_metadata = new_fork_metadata.expect("Forked blockchain must have metadata");
+
+        self.blockchain = blockchain;
+        self.irregular_state = irregular_state;
+        self.fork_metadata = Some(new_fork_metadata.clone());
+        self.rpc_client = rpc_client;
+        // The new offset is relative to the timer's current time
+        self.block_clock = BlockClock::new(self.block_clock.mode(), &self.timer);
+        self.block_time_offset_seconds = block_time_offset_seconds;
+        self.next_block_base_fee_per_gas = next_block_base_fee_per_gas;
+
+        // Snapshots and cached states refer to the previous fork block
+        self.snapshots.clear();
+        self.block_state_cache.clear();
+        self.block_number_to_state_id = RedBlackTreeMapSync::default();
+        self.reconstructed_state_ids.clear();
+        self.first_local_block_number = self.blockchain.last_block_number() + 1;
+        self.first_unpruned_block_number = self.blockchain.last_block_number() + 1;
+
+        let state = self.current_state()?;
+        let removed = self
+            .mem_pool
+            .update(&*state)
+            .map_err(ProviderError::MemPoolUpdate)?;
+
+        self.handle_dropped_transactions(&removed)?;
+
+        Ok(new_fork_metadata)
+    }
+
     pub fn sign_transaction_request(
         &self,
         transaction_request: TransactionRequestAndSender<ChainSpecT::TransactionRequest>,
     ) -> Result<ChainSpecT::SignedTransaction, ProviderErrorForChainSpec<ChainSpecT>> {
-        let TransactionRequestAndSender { request, sender } = transaction_request;
+        if self.is_impersonated(&transaction_request.sender) {
+            let TransactionRequestAndSender { request, sender } = transaction_request;
 
-        if self.impersonated_accounts.contains(&sender) {
             let signed_transaction = request.fake_sign(sender);
             transaction::validate(signed_transaction, self.evm_spec_id())
                 .map_err(ProviderError::TransactionCreationError)
         } else {
-            let secret_key = self
-                .local_accounts
-                .get(&sender)
-                .ok_or(ProviderError::UnknownAddress { address: sender })?;
+            self.sign_transaction_request_with_secret_key(transaction_request)
+        }
+    }
+
+    /// Signs the transaction request with the secret key of its sender, which
+    /// needs to be a local account or an unlocked keystore account.
+    pub fn sign_transaction_request_with_secret_key(
+        &self,
+        transaction_request: TransactionRequestAndSender<ChainSpecT::TransactionRequest>,
+    ) -> Result<ChainSpecT::SignedTransaction, ProviderErrorForChainSpec<ChainSpecT>> {
+        let TransactionRequestAndSender { request, sender } = transaction_request;
 
-            // SAFETY: We know the secret key belongs to the sender, as we retrieved it from
-            // `local_accounts`.
-            let signed_transaction =
-                unsafe { request.sign_for_sender_unchecked(secret_key, sender) }?;
+        let secret_key = self
+            .secret_key(&sender)
+            .ok_or(ProviderError::UnknownAddress { address: sender })?;
 
-            transaction::validate(signed_transaction, self.evm_spec_id())
-                .map_err(ProviderError::TransactionCreationError)
-        }
+        // SAFETY: We know the secret key belongs to the sender, as we retrieved it by
+        // the sender's address.
+        let signed_transaction = unsafe { request.sign_for_sender_unchecked(secret_key, sender) }?;
+
+        transaction::validate(signed_transaction, self.evm_spec_id())
+            .map_err(ProviderError::TransactionCreationError)
     }
 
     pub fn total_difficulty_by_hash(
@@ -1286,15 +2858,59 @@ where
 
     /// Tries to add a transaction to the mempool and notifies subscribers about
     /// it.
-    fn add_pending_transaction(
+    pub(crate) fn add_pending_transaction(
         &mut self,
         transaction: ChainSpecT::SignedTransaction,
     ) -> Result<B256, ProviderErrorForChainSpec<ChainSpecT>> {
         let transaction_hash = *transaction.transaction_hash();
 
+        self.remove_expired_pending_transactions()?;
+
+        let tags = match self
+            .transaction_firewall
+            .as_deref()
+            .map(|transaction_firewall| {
+                transaction_firewall(&TransactionFirewallRequest::new(&transaction))
+            }) {
+            None => Vec::new(),
+            Some(TransactionFirewallVerdict::Accept { tags }) => tags,
+            Some(TransactionFirewallVerdict::Reject { reason }) => {
+                return Err(ProviderError::TransactionRejected {
+                    transaction_hash,
+                    reason,
+                });
+            }
+        };
+
+        let sender = *transaction.caller();
+        let nonce = transaction.nonce();
+
         let state = self.current_state()?;
         // Handles validation
-        self.mem_pool.add_transaction(&*state, transaction)?;
+        let removed =
+            self.mem_pool
+                .add_transaction(&*state, transaction, self.timer.since_epoch())?;
+
+        for removed_transaction in &removed {
+            let removed_transaction = removed_transaction.pending();
+            if *removed_transaction.caller() == sender && removed_transaction.nonce() == nonce {
+                log::debug!(
+                    "Replaced transaction {} in the mem pool with {transaction_hash}",
+                    removed_transaction.transaction_hash()
+                );
+            } else {
+                log::debug!(
+                    "Evicted transaction {} from the mem pool to make room for {transaction_hash}",
+                    removed_transaction.transaction_hash()
+                );
+            }
+        }
+
+        self.handle_dropped_transactions(&removed)?;
+
+        if !tags.is_empty() {
+            self.transaction_tags.insert(transaction_hash, tags);
+        }
 
         self.notify_subscribers_about_pending_transaction(&transaction_hash);
 
@@ -1338,16 +2954,33 @@ where
         Ok(block_number)
     }
 
+    /// The minimum priority fee per gas that transactions need to pay to be
+    /// mined, taking into account whether the priority fee check is disabled.
+    fn effective_min_gas_price(&self) -> u128 {
+        if self.disable_priority_fee_check {
+            0
+        } else {
+            self.min_gas_price
+        }
+    }
+
     /// Creates an [`EvmConfig`] with the provided chain id
     fn create_evm_config(&self, chain_id: u64) -> EvmConfig {
         EvmConfig {
             chain_id,
+            disable_balance_check: self.mem_pool.is_balance_check_disabled(),
+            disable_base_fee: self.disable_base_fee_check,
             disable_eip3607: true,
             limit_contract_code_size: if self.allow_unlimited_contract_size {
                 Some(usize::MAX)
             } else {
                 None
             },
+            limit_contract_initcode_size: if self.allow_unlimited_initcode_size() {
+                Some(usize::MAX)
+            } else {
+                None
+            },
             transaction_gas_cap: self.mem_pool.transaction_gas_cap(),
         }
     }
@@ -1427,10 +3060,43 @@ where
             }
         };
 
-        let state = self
-            .blockchain
-            .state_at_block_number(block_number, self.irregular_state.state_overrides())?;
+        // Derive the state from the closest preceding cached state, if possible, to
+        // avoid recomputing it from scratch.
+        let derived_state = if let Some((base_block_number, base_state)) =
+            self.closest_cached_state_before(block_number)
+        {
+            self.blockchain.state_at_block_number_from_base(
+                (*base_state).clone(),
+                base_block_number,
+                block_number,
+                self.irregular_state.state_overrides(),
+            )?
+        } else {
+            None
+        };
+
+        let state = if let Some(state) = derived_state {
+            state
+        } else {
+            self.blockchain
+                .state_at_block_number(block_number, self.irregular_state.state_overrides())?
+        };
+
         let state_id = self.add_state_to_cache(state, block_number);
+
+        // Only the most recently reconstructed states of pruned blocks are cached
+        if !self
+            .state_pruning
+            .is_retained(block_number, self.last_block_number())
+        {
+            self.reconstructed_state_ids.push_back(state_id);
+            if self.reconstructed_state_ids.len() > MAX_RECONSTRUCTED_STATES
+                && let Some(evicted_state_id) = self.reconstructed_state_ids.pop_front()
+            {
+                self.block_state_cache.pop(&evicted_state_id);
+            }
+        }
+
         Ok(self
             .block_state_cache
             .get(&state_id)
@@ -1440,6 +3106,72 @@ where
             .clone())
     }
 
+    /// Removes the cached states of blocks that are no longer retained
+    /// according to the state pruning configuration, as well as their
+    /// recorded state diffs, blob sidecars, transaction tags, and indexed
+    /// address transactions and token transfers.
+    fn prune_states(&mut self) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let first_retained_block_number = self
+            .state_pruning
+            .first_retained_block_number(self.last_block_number());
+
+        // Reconstructed states of pruned blocks are evicted separately
+        let pruned_states = self
+            .block_number_to_state_id
+            .range(..first_retained_block_number)
+            .filter(|(_block_number, state_id)| !self.reconstructed_state_ids.contains(state_id))
+            .map(|(block_number, state_id)| (*block_number, *state_id))
+            .collect::<Vec<_>>();
+
+        for (block_number, state_id) in pruned_states {
+            self.block_number_to_state_id.remove_mut(&block_number);
+            self.block_state_cache.pop(&state_id);
+        }
+
+        // After reverting, blocks that replace pruned blocks are pruned once
+        // they are no longer retained.
+        let pruned_block_numbers = self.first_unpruned_block_number..first_retained_block_number;
+        self.first_unpruned_block_number = first_retained_block_number;
+
+        for block_number in pruned_block_numbers {
+            let Some(block) = self.blockchain.block_by_number(block_number)? else {
+                continue;
+            };
+
+            for transaction in block.transactions() {
+                let transaction_hash = transaction.transaction_hash();
+
+                self.state_diffs.remove(transaction_hash);
+                self.blob_sidecars.remove_mut(transaction_hash);
+                self.transaction_tags.remove(transaction_hash);
+            }
+        }
+
+        self.address_index
+            .prune_blocks_before(first_retained_block_number);
+        self.token_transfers
+            .prune_blocks_before(first_retained_block_number);
+
+        Ok(())
+    }
+
+    /// Retrieves the cached state of the closest block before the block with
+    /// the provided number, if any.
+    fn closest_cached_state_before(
+        &self,
+        block_number: u64,
+    ) -> Option<(u64, Arc<Box<dyn DynState>>)> {
+        // States that were evicted from the cache are skipped
+        self.block_number_to_state_id
+            .range(..block_number)
+            .rev()
+            .find_map(|(cached_block_number, state_id)| {
+                self.block_state_cache
+                    .peek(state_id)
+                    .map(|state| (*cached_block_number, state.clone()))
+            })
+    }
+
     fn mine_and_commit_block_impl(
         &mut self,
         mine_fn: impl FnOnce(
@@ -1460,16 +3192,71 @@ where
         let (block_timestamp, new_offset) = self.next_block_timestamp(options.timestamp)?;
         options.timestamp = Some(block_timestamp);
 
+        let block_number = self.blockchain.last_block_number() + 1;
+        self.apply_block_gas_limit_schedule(block_number..=block_number)?;
+        self.apply_transaction_gas_cap()?;
+
+        // Expired transactions shouldn't be mined
+        self.remove_expired_pending_transactions()?;
+
         let result = self.mine_block(mine_fn, options)?;
 
+        // State diffs are only used for debugging, so failing to record them
+        // shouldn't prevent the block from being mined.
+        if self.record_state_diffs
+            && let Err(error) = self.record_transaction_state_diffs(&result)
+        {
+            log::warn!("Failed to record the state diffs of mined transactions: {error}");
+        }
+
         let block_and_total_difficulty = self
             .blockchain
             .insert_block(result.block, result.state_diff)
             .map_err(ProviderError::Blockchain)?;
 
-        self.mem_pool
+        if self.index_address_transactions {
+            self.address_index.insert_block(
+                block_and_total_difficulty.block.block_header().number,
+                block_and_total_difficulty.block.transactions(),
+                &result.transaction_traces,
+            );
+        }
+
+        if let Some(verified_sources) = self.verified_sources.as_mut() {
+            verified_sources.add_local_contracts(&result.transaction_traces);
+        }
+
+        if self.index_token_transfers {
+            let block = &block_and_total_difficulty.block;
+            let receipts = block
+                .fetch_transaction_receipts()
+                .map_err(ProviderError::FetchReceipt)?;
+
+            self.token_transfers.insert_block(
+                block.block_header().number,
+                receipts.iter().flat_map(ExecutionReceipt::transaction_logs),
+            );
+        }
+
+        let included_transactions = block_and_total_difficulty
+            .block
+            .transactions()
+            .iter()
+            .map(|transaction| *transaction.transaction_hash())
+            .collect::<HashSet<_>>();
+
+        // Besides the mined transactions, this removes transactions that were
+        // invalidated by the block; e.g. because their sender can no longer
+        // afford them.
+        let dropped_transactions = self
+            .mem_pool
             .update(&result.state)
-            .map_err(ProviderError::MemPoolUpdate)?;
+            .map_err(ProviderError::MemPoolUpdate)?
+            .into_iter()
+            .filter(|transaction| {
+                !included_transactions.contains(transaction.pending().transaction_hash())
+            })
+            .collect::<Vec<_>>();
 
         if let Some(new_offset) = new_offset {
             self.block_time_offset_seconds = new_offset;
@@ -1478,18 +3265,67 @@ where
         // Reset the next block base fee per gas upon successful execution
         self.next_block_base_fee_per_gas.take();
 
+        // Reset the next block difficulty
+        self.next_block_difficulty.take();
+
+        // Reset the next block header overrides
+        self.next_block_header_overrides.take();
+
+        // Reset the next block state root
+        self.next_block_state_root.take();
+
         // Reset next block time stamp
         self.next_block_timestamp.take();
 
+        // Reset the next block withdrawals
+        self.next_block_withdrawals.take();
+
         self.parent_beacon_block_root_generator.generate_next();
         self.prev_randao_generator.generate_next();
 
         self.notify_subscribers_about_mined_block(&block_and_total_difficulty)?;
 
+        let header = block_and_total_difficulty.block.block_header();
+        tracing::debug!(
+            number = header.number,
+            transactions = result.transaction_results.len(),
+            gas_used = header.gas_used,
+            "Mined block"
+        );
+
+        self.metrics
+            .record_mined_block(result.transaction_results.len());
+
+        // All queued deposits were attempted. Those that weren't included in the
+        // mined block are invalid, so they are dropped instead of blocking
+        // subsequent blocks.
+        if !self.queued_deposits.is_empty() {
+            for transaction in self.queued_deposits.drain(..) {
+                let transaction_hash = transaction.transaction_hash();
+                if !included_transactions.contains(transaction_hash) {
+                    log::warn!(
+                        "Dropped queued deposit transaction {transaction_hash}, as it couldn't be included in block {}",
+                        block_and_total_difficulty.block.block_header().number
+                    );
+                }
+            }
+        }
+
         self.add_state_to_cache(
             result.state,
             block_and_total_difficulty.block.block_header().number,
         );
+        self.prune_states()?;
+
+        if !self.sponsored_transactions.is_empty() {
+            self.refund_gas_sponsor_for_block(&block_and_total_difficulty.block)?;
+        }
+
+        self.handle_dropped_transactions(&dropped_transactions)?;
+
+        // The next block might activate a hardfork with a different transaction gas
+        // cap
+        self.apply_transaction_gas_cap()?;
 
         Ok(DebugMineBlockResult::new(
             block_and_total_difficulty.block,
@@ -1499,20 +3335,65 @@ where
         ))
     }
 
-    /// Mines a block using the provided options. If an option has not been
-    /// specified, it will be set using the provider's configuration values.
-    fn mine_block(
+    /// Records the changes that each transaction of the provided mined block
+    /// made to the state, on top of the current state. Must be called before
+    /// the block is inserted into the blockchain.
+    fn record_transaction_state_diffs(
         &mut self,
-        mine_fn: impl FnOnce(
-            &mut ProviderData<ChainSpecT, TimerT>,
-            &EvmConfig,
-            HeaderOverrides<ChainSpecT::Hardfork>,
-            &mut EvmObserver<ChainSpecT::HaltReason>,
-        ) -> Result<
-            BuiltBlockAndState<
-                ChainSpecT::HaltReason,
-                <ChainSpecT as GenesisBlockFactory>::LocalBlock,
-            >,
+        result: &DebugMineBlockResultAndState<
+            ChainSpecT::HaltReason,
+            <ChainSpecT as GenesisBlockFactory>::LocalBlock,
+        >,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        if result.transaction_state_changes.is_empty() {
+            return Ok(());
+        }
+
+        let state = self.current_state()?;
+        let state_diffs = transaction_state_diffs(
+            (*state).clone(),
+            result.transaction_state_changes.iter().cloned(),
+        )?;
+
+        let block_number = result.block.block_header().number;
+        let transaction_hashes = result
+            .block
+            .transactions()
+            .iter()
+            .map(|transaction| *transaction.transaction_hash());
+
+        self.state_diffs.extend(
+            transaction_hashes
+                .zip(state_diffs)
+                .map(|(transaction_hash, diff)| (transaction_hash, (block_number, diff))),
+        );
+
+        Ok(())
+    }
+
+    /// Removes the state diffs of transactions in blocks after the latest
+    /// block, e.g. after reverting to a snapshot.
+    fn prune_state_diffs(&mut self) {
+        let last_block_number = self.blockchain.last_block_number();
+
+        self.state_diffs
+            .retain(|_transaction_hash, (block_number, _diff)| *block_number <= last_block_number);
+    }
+
+    /// Mines a block using the provided options. If an option has not been
+    /// specified, it will be set using the provider's configuration values.
+    fn mine_block(
+        &mut self,
+        mine_fn: impl FnOnce(
+            &mut ProviderData<ChainSpecT, TimerT>,
+            &EvmConfig,
+            HeaderOverrides<ChainSpecT::Hardfork>,
+            &mut EvmObserver<ChainSpecT::HaltReason>,
+        ) -> Result<
+            BuiltBlockAndState<
+                ChainSpecT::HaltReason,
+                <ChainSpecT as GenesisBlockFactory>::LocalBlock,
+            >,
             ProviderErrorForChainSpec<ChainSpecT>,
         >,
         mut options: HeaderOverrides<ChainSpecT::Hardfork>,
@@ -1524,6 +3405,19 @@ where
         ProviderErrorForChainSpec<ChainSpecT>,
     > {
         options.base_fee = options.base_fee.or(self.next_block_base_fee_per_gas);
+        if self.disable_base_fee_adjustment && options.base_fee.is_none() {
+            options.base_fee = self.next_block_base_fee_per_gas()?;
+        }
+        options.difficulty = options.difficulty.or(self.next_block_difficulty);
+        options.state_root = options.state_root.or(self.next_block_state_root);
+        if let Some(overrides) = &self.next_block_header_overrides {
+            options.beneficiary = options.beneficiary.or(overrides.coinbase);
+            options.extra_data = options.extra_data.or_else(|| overrides.extra_data.clone());
+            options.gas_limit = options
+                .gas_limit
+                .or(overrides.gas_limit.map(|gas_limit| gas_limit.to()));
+            options.mix_hash = options.mix_hash.or(overrides.mix_hash);
+        }
         options.beneficiary = Some(options.beneficiary.unwrap_or(self.beneficiary));
         options.gas_limit = Some(options.gas_limit.unwrap_or_else(|| self.block_gas_limit()));
 
@@ -1586,6 +3480,21 @@ where
         ))
     }
 
+    /// Returns the inputs for the next block, including any scheduled
+    /// withdrawals.
+    fn next_block_inputs(&self) -> BlockInputs {
+        let mut inputs = BlockInputs::empty(self.blockchain.hardfork());
+
+        // Withdrawals can only be included post-Shanghai
+        if let Some(withdrawals) = inputs.withdrawals.as_mut()
+            && let Some(next_block_withdrawals) = &self.next_block_withdrawals
+        {
+            withdrawals.clone_from(next_block_withdrawals);
+        }
+
+        inputs
+    }
+
     /// Get the timestamp for the next block.
     /// Ported from <https://github.com/NomicFoundation/hardhat/blob/b84baf2d9f5d3ea897c06e0ecd5e7084780d8b6c/packages/hardhat-core/src/internal/hardhat-network/provider/node.ts#L1942>
     fn next_block_timestamp(
@@ -1596,7 +3505,7 @@ where
         let latest_block_header = latest_block.block_header();
 
         let current_timestamp =
-            i64::try_from(self.timer.since_epoch()).expect("timestamp too large");
+            i64::try_from(self.block_clock.now(&self.timer)).expect("timestamp too large");
 
         let (mut block_timestamp, mut new_offset) = if let Some(timestamp) = timestamp {
             timestamp.checked_sub(latest_block_header.timestamp).ok_or(
@@ -1646,6 +3555,8 @@ where
                 });
             }
             Ordering::Equal => (),
+            // Queued until the nonce gap is filled
+            Ordering::Greater if self.mem_pool.policy().queue_future_transactions => (),
             Ordering::Greater => {
                 return Err(ProviderError::AutoMineNonceTooHigh {
                     expected: next_nonce,
@@ -1658,13 +3569,18 @@ where
             .max_priority_fee_per_gas()
             .unwrap_or_else(|| transaction.gas_price());
 
-        if *max_priority_fee_per_gas < self.min_gas_price {
+        let min_gas_price = self.effective_min_gas_price();
+        if *max_priority_fee_per_gas < min_gas_price {
             return Err(ProviderError::AutoMinePriorityFeeTooLow {
-                expected: self.min_gas_price,
+                expected: min_gas_price,
                 actual: *max_priority_fee_per_gas,
             });
         }
 
+        if self.disable_base_fee_check {
+            return Ok(());
+        }
+
         if let Some(next_block_base_fee) = self.next_block_base_fee_per_gas()? {
             if let Some(max_fee_per_gas) = transaction.max_fee_per_gas() {
                 if *max_fee_per_gas < next_block_base_fee {
@@ -1741,12 +3657,115 @@ where
         self.blockchain.last_block_number()
     }
 
+    /// Returns a page of the transactions in locally mined blocks that involve
+    /// the provided address, from the most to the least recent.
+    ///
+    /// Returns an error if the page includes transactions of pruned blocks.
+    pub fn address_transactions(
+        &self,
+        address: &Address,
+        relation: Option<AddressRelation>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<AddressTransactionsPage, ProviderErrorForChainSpec<ChainSpecT>> {
+        self.ensure_address_transactions_indexed()?;
+
+        self.address_index
+            .transactions(
+                address,
+                relation,
+                self.blockchain.last_block_number(),
+                page,
+                page_size,
+            )
+            .ok_or_else(|| {
+                ProviderError::InvalidInput(format!(
+                    "Page {page} of the transactions of {address} is unavailable, as it includes transactions of pruned blocks."
+                ))
+            })
+    }
+
+    /// Returns the transactions in locally mined blocks that involve the
+    /// provided address, in the order in which they were mined, along with the
+    /// numbers of the first and last pruned blocks that contained transactions
+    /// that involved it.
+    pub fn retained_address_transactions(
+        &self,
+        address: &Address,
+    ) -> Result<
+        (&[AddressTransaction], Option<RangeInclusive<u64>>),
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        self.ensure_address_transactions_indexed()?;
+
+        Ok(self
+            .address_index
+            .retained_transactions(address, self.blockchain.last_block_number()))
+    }
+
+    fn ensure_address_transactions_indexed(
+        &self,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        if self.index_address_transactions {
+            Ok(())
+        } else {
+            Err(ProviderError::InvalidInput(
+                "Address transaction indexing is disabled. Enable it with `indexAddressTransactions`."
+                    .to_string(),
+            ))
+        }
+    }
+
+    /// Returns the token transfers in locally mined blocks up to and including
+    /// the provided block number, in the order in which they occurred.
+    /// Transfers of pruned blocks are omitted.
+    pub fn token_transfers(
+        &self,
+        last_block_number: u64,
+    ) -> Result<&[TokenTransfer], ProviderErrorForChainSpec<ChainSpecT>> {
+        if !self.index_token_transfers {
+            return Err(ProviderError::InvalidInput(
+                "Token transfer indexing is disabled. Enable it with `indexTokenTransfers`."
+                    .to_string(),
+            ));
+        }
+
+        Ok(self
+            .token_transfers
+            .transfers(last_block_number.min(self.blockchain.last_block_number())))
+    }
+
+    /// Returns the token holdings of the provided address that result from the
+    /// indexed transfers up to and including the provided block number.
+    pub(crate) fn token_holdings(
+        &self,
+        address: &Address,
+        block_number: u64,
+    ) -> Result<TokenHoldings, ProviderErrorForChainSpec<ChainSpecT>> {
+        if !self.index_token_transfers {
+            return Err(ProviderError::InvalidInput(
+                "Token transfer indexing is disabled. Enable it with `indexTokenTransfers`."
+                    .to_string(),
+            ));
+        }
+
+        let block_number = block_number.min(self.blockchain.last_block_number());
+        self.token_transfers
+            .holdings(address, block_number)
+            .ok_or_else(|| {
+                ProviderError::InvalidInput(format!(
+                    "The token balances at block {block_number} are unavailable, as the transfers of earlier blocks were pruned."
+                ))
+            })
+    }
+
     /// Makes a snapshot of the instance's state and returns the snapshot ID.
     pub fn make_snapshot(&mut self) -> u64 {
         let id = self.next_snapshot_id;
         self.next_snapshot_id += 1;
 
         let snapshot = Snapshot {
+            blob_sidecars: self.blob_sidecars.clone(),
             block_number: self.blockchain.last_block_number(),
             block_number_to_state_id: self.block_number_to_state_id.clone(),
             block_time_offset_seconds: self.block_time_offset_seconds,
@@ -1754,10 +3773,17 @@ where
             irregular_state: self.irregular_state.clone(),
             mem_pool: self.mem_pool.clone(),
             next_block_base_fee_per_gas: self.next_block_base_fee_per_gas,
+            next_block_difficulty: self.next_block_difficulty,
+            next_block_header_overrides: self.next_block_header_overrides.clone(),
+            next_block_state_root: self.next_block_state_root,
             next_block_timestamp: self.next_block_timestamp,
+            next_block_withdrawals: self.next_block_withdrawals.clone(),
             parent_beacon_block_root_generator: self.parent_beacon_block_root_generator.clone(),
             prev_randao_generator: self.prev_randao_generator.clone(),
+            queued_deposits: self.queued_deposits.clone(),
+            sponsored_transactions: self.sponsored_transactions.clone(),
             time: Instant::now(),
+            transaction_tags: self.transaction_tags.clone(),
         };
         self.snapshots.insert(id, snapshot);
 
@@ -1806,6 +3832,12 @@ where
     }
 
     fn calculate_next_block_base_fee(&self, header: &BlockHeader) -> u128 {
+        if self.disable_base_fee_adjustment
+            && let Some(base_fee_per_gas) = header.base_fee_per_gas
+        {
+            return base_fee_per_gas;
+        }
+
         ChainSpecT::next_base_fee_per_gas(
             header,
             self.hardfork(),
@@ -1874,7 +3906,7 @@ where
 
             let EvmObserver {
                 code_coverage,
-                console_logger: _console_logger,
+                console_logger,
                 mocker: _mocker,
                 trace_collector,
             } = evm_observer;
@@ -1885,13 +3917,51 @@ where
                     .map_err(ProviderError::OnCollectedCoverageCallback)?;
             }
 
-            let debug_result =
-                execution_result_to_debug_result(result, trace_collector, eip3155_tracer);
+            let debug_result = execution_result_to_debug_result(
+                result,
+                trace_collector,
+                eip3155_tracer,
+                console_logger.into_encoded_messages(),
+            );
 
             Ok(debug_result)
         })?
     }
 
+    /// Runs the provided call to `EntryPoint.handleOps`, recording violations
+    /// of the ERC-4337 validation rules by the user operation's entities.
+    pub fn validate_user_operation(
+        &mut self,
+        transaction: ChainSpecT::SignedTransaction,
+        block_spec: &BlockSpec,
+        entities: UserOperationEntities,
+    ) -> Result<
+        (
+            ExecutionResult<ChainSpecT::HaltReason>,
+            Vec<UserOperationViolation>,
+        ),
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
+
+        let mut tracer = UserOperationTracer::new(entities);
+        let custom_precompiles = self.precompile_overrides.clone();
+
+        self.execute_in_block_context(Some(block_spec), move |blockchain, block, state| {
+            let result = call::run_call::<ChainSpecT, _, _, _>(
+                blockchain,
+                block.block_header(),
+                state.as_ref(),
+                cfg_env,
+                transaction,
+                &custom_precompiles,
+                &mut tracer,
+            )?;
+
+            Ok((result, tracer.into_violations()))
+        })?
+    }
+
     // Matches Hardhat implementation
     pub fn fee_history(
         &mut self,
@@ -1920,7 +3990,7 @@ where
         let last_block_number = newest_block_number + 1;
 
         let pending_block = if last_block_number >= pending_block_number {
-            let DebugMineBlockResultAndState { block, .. } = self.mine_pending_block()?;
+            let PendingBlock { block, .. } = self.pending_block()?;
             Some(block)
         } else {
             None
@@ -1958,6 +4028,8 @@ where
                 base_fee_per_gas,
                 gas_used_ratio,
                 reward: remote_reward,
+                base_fee_per_blob_gas,
+                blob_gas_used_ratio,
             } = tokio::task::block_in_place(|| {
                 self.runtime_handle.block_on(rpc_client.fee_history(
                     remote_block_count,
@@ -1968,6 +4040,8 @@ where
 
             result.base_fee_per_gas = base_fee_per_gas;
             result.gas_used_ratio = gas_used_ratio;
+            result.base_fee_per_blob_gas = base_fee_per_blob_gas;
+            result.blob_gas_used_ratio = blob_gas_used_ratio;
             if let Some(reward) = opt_reward.as_mut()
                 && let Some(remote_reward) = remote_reward
             {
@@ -1995,14 +4069,21 @@ where
                     .expect("Block must exist as i is at most the last block number");
 
                 let header = block.block_header();
+                let evm_spec_id = self.blockchain.spec_at_block_number(block_number)?.into();
                 result
                     .base_fee_per_gas
                     .push(header.base_fee_per_gas.unwrap_or(0));
+                result
+                    .base_fee_per_blob_gas
+                    .push(base_fee_per_blob_gas(header, evm_spec_id));
 
                 if block_number < last_block_number {
                     result
                         .gas_used_ratio
                         .push(gas_used_ratio(header.gas_used, header.gas_limit));
+                    result
+                        .blob_gas_used_ratio
+                        .push(blob_gas_used_ratio(header, evm_spec_id));
 
                     if let Some(reward) = opt_reward.as_mut() {
                         reward.push(compute_rewards::<ChainSpecT>(block.as_ref(), &percentiles)?);
@@ -2014,12 +4095,19 @@ where
                     .expect("We checked that EIP-1559 is active");
                 result.base_fee_per_gas.push(next_block_base_fee_per_gas);
 
+                let block = pending_block.as_ref().expect("We mined the pending block");
+                let header = block.block_header();
+                result
+                    .base_fee_per_blob_gas
+                    .push(base_fee_per_blob_gas(header, self.evm_spec_id()));
+
                 if block_number < last_block_number {
-                    let block = pending_block.as_ref().expect("We mined the pending block");
-                    let header = block.block_header();
                     result
                         .gas_used_ratio
                         .push(gas_used_ratio(header.gas_used, header.gas_limit));
+                    result
+                        .blob_gas_used_ratio
+                        .push(blob_gas_used_ratio(header, self.evm_spec_id()));
 
                     if let Some(reward) = opt_reward.as_mut() {
                         // We don't compute this for the pending block, as there's no
@@ -2029,9 +4117,16 @@ where
                 }
             } else if block_number == pending_block_number + 1 {
                 let block = pending_block.as_ref().expect("We mined the pending block");
+                let next_block_base_fee_per_gas =
+                    self.calculate_next_block_base_fee(block.block_header());
+                result.base_fee_per_gas.push(next_block_base_fee_per_gas);
                 result
-                    .base_fee_per_gas
-                    .push(self.calculate_next_block_base_fee(block.block_header()));
+                    .base_fee_per_blob_gas
+                    .push(next_block_base_fee_per_blob_gas(
+                        block.block_header(),
+                        self.evm_spec_id(),
+                        next_block_base_fee_per_gas,
+                    ));
             }
         }
 
@@ -2075,6 +4170,76 @@ where
         )?
     }
 
+    /// Reads and decodes the value of a state variable of the contract at the
+    /// provided address, using its registered storage layout.
+    ///
+    /// The `keys` are used, in order, to index the mappings and arrays that are
+    /// encountered while resolving the variable.
+    pub fn read_storage_variable(
+        &mut self,
+        address: Address,
+        variable: &str,
+        keys: &[String],
+        block_spec: Option<&BlockSpec>,
+    ) -> Result<serde_json::Value, ProviderErrorForChainSpec<ChainSpecT>> {
+        let layout = self.storage_layout(address)?;
+
+        self.execute_in_block_context::<Result<serde_json::Value, ProviderErrorForChainSpec<ChainSpecT>>>(
+            block_spec,
+            move |_blockchain, _block, state| {
+                let value =
+                    storage_layout::read_storage_variable(&layout, variable, keys, &mut |index| {
+                        state.storage(address, index)
+                    })?;
+
+                Ok(value)
+            },
+        )?
+    }
+
+    /// Encodes and writes the value of a state variable of the contract at the
+    /// provided address, using its registered storage layout.
+    ///
+    /// The variable is resolved like in [`Self::read_storage_variable`].
+    pub fn set_storage_variable(
+        &mut self,
+        address: Address,
+        variable: &str,
+        keys: &[String],
+        value: &serde_json::Value,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let layout = self.storage_layout(address)?;
+
+        let writes = self
+            .execute_in_block_context::<Result<_, ProviderErrorForChainSpec<ChainSpecT>>>(
+                None,
+                move |_blockchain, _block, state| {
+                    let writes = storage_layout::write_storage_variable(
+                        &layout,
+                        variable,
+                        keys,
+                        value,
+                        &mut |index| state.storage(address, index),
+                    )?;
+
+                    Ok(writes)
+                },
+            )??;
+
+        self.set_account_storage_slots(address, writes)
+    }
+
+    fn storage_layout(
+        &self,
+        address: Address,
+    ) -> Result<Arc<StorageLayout>, ProviderErrorForChainSpec<ChainSpecT>> {
+        self.storage_layouts.get(&address).cloned().ok_or_else(|| {
+            ProviderError::InvalidInput(format!(
+                "No storage layout was registered for address {address}"
+            ))
+        })
+    }
+
     pub fn get_transaction_count(
         &mut self,
         address: Address,
@@ -2187,8 +4352,14 @@ where
         } else {
             let current_state = (*self.current_state()?).clone();
 
+            // Reserved blocks use the current block gas limit, so only the last
+            // scheduled change among them carries over to the next block.
+            let first_reserved_block_number = self.blockchain.last_block_number() + 1;
             self.blockchain
                 .reserve_blocks(remaining_blocks - 1, interval)?;
+            self.apply_block_gas_limit_schedule(
+                first_reserved_block_number..=self.blockchain.last_block_number(),
+            )?;
 
             // Ensure there is a cache entry for the last reserved block, to avoid
             // recomputation
@@ -2206,6 +4377,31 @@ where
         Ok(mined_blocks)
     }
 
+    /// Mines blocks until the mempool no longer contains pending transactions.
+    ///
+    /// Stops early if a block couldn't include any of the pending
+    /// transactions, e.g. because their fees are too low.
+    pub fn mine_pending_transactions(
+        &mut self,
+    ) -> Result<
+        Vec<DebugMineBlockResultForChainSpec<ChainSpecT>>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let mut mining_results = Vec::new();
+        while self.mem_pool.has_pending_transactions() {
+            let result = self.mine_and_commit_block(self.header_overrides())?;
+            let is_empty = result.transaction_results.is_empty();
+
+            mining_results.push(result);
+
+            if is_empty {
+                break;
+            }
+        }
+
+        Ok(mining_results)
+    }
+
     /// Mines a pending block, without modifying any values.
     pub fn mine_pending_block(
         &mut self,
@@ -2225,6 +4421,39 @@ where
         )
     }
 
+    /// Retrieves the pending block, mined from the mem pool, and the state
+    /// after executing it.
+    ///
+    /// The pending block is cached until it's invalidated using
+    /// [`Self::invalidate_pending_block`] or the chain head or the next block's
+    /// timestamp changes.
+    pub(crate) fn pending_block(
+        &mut self,
+    ) -> Result<
+        PendingBlock<<ChainSpecT as GenesisBlockFactory>::LocalBlock>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let (block_timestamp, _new_offset) = self.next_block_timestamp(None)?;
+        let last_block_hash = *self.blockchain.last_block()?.block_hash();
+
+        if let Some(pending_block) = self.pending_block.as_ref() {
+            let header = pending_block.block.block_header();
+            if header.parent_hash == last_block_hash && header.timestamp == block_timestamp {
+                return Ok(pending_block.clone());
+            }
+        }
+
+        let result = self.mine_pending_block()?;
+        let pending_block = PendingBlock {
+            block: Arc::new(result.block),
+            state: Arc::new(result.state),
+        };
+
+        self.pending_block = Some(pending_block.clone());
+
+        Ok(pending_block)
+    }
+
     pub fn nonce(
         &mut self,
         address: &Address,
@@ -2254,76 +4483,626 @@ where
             )
     }
 
-    pub fn run_call(
+    /// Simulates a chain reorganisation by removing the last `depth` blocks and
+    /// mining `new_blocks` replacement blocks.
+    ///
+    /// Transactions of the removed blocks are returned to the mem pool, unless
+    /// their hash is included in `dropped_transactions`. Subscribers are
+    /// notified about the removed logs.
+    pub fn reorg(
         &mut self,
-        transaction: ChainSpecT::SignedTransaction,
-        block_spec: &BlockSpec,
-        state_overrides: &StateOverrides,
-    ) -> Result<CallResult<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>> {
-        struct GasReportArgs {
-            callback: Box<dyn SyncOnCollectedGasReportCallback>,
-            kind: TxKind,
-            input: Bytes,
+        depth: u64,
+        new_blocks: u64,
+        dropped_transactions: &HashSet<B256>,
+    ) -> Result<
+        Vec<DebugMineBlockResultForChainSpec<ChainSpecT>>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let last_block_number = self.blockchain.last_block_number();
+        // Remote and genesis blocks cannot be removed
+        let first_local_block_number = self
+            .fork_metadata
+            .as_ref()
+            .map_or(0, |fork_metadata| fork_metadata.fork_block_number);
+
+        let max_depth = last_block_number - first_local_block_number;
+        if depth == 0 || depth > max_depth {
+            return Err(ProviderError::InvalidReorgDepth { depth, max_depth });
         }
 
-        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
+        let block_number = last_block_number - depth;
 
-        let custom_precompiles = self.precompile_overrides.clone();
-        let mut evm_observer = EvmObserver::new(EvmObserverConfig::from(&self.observability));
+        let mut reverted_transactions = Vec::new();
+        for removed_block_number in block_number + 1..=last_block_number {
+            let block = self
+                .blockchain
+                .block_by_number(removed_block_number)?
+                .expect("Local blocks should be available");
+
+            reverted_transactions.extend(
+                block
+                    .transactions()
+                    .iter()
+                    .filter(|transaction| {
+                        !dropped_transactions.contains(transaction.transaction_hash())
+                    })
+                    .cloned(),
+            );
+        }
 
-        let gas_report_args =
-            self.observability
-                .on_collected_gas_report_fn
-                .as_ref()
-                .map(|callback| GasReportArgs {
-                    callback: callback.clone(),
-                    kind: transaction.kind(),
-                    input: transaction.data().clone(),
-                });
+        self.remove_blocks_after(block_number)?;
 
-        let contract_decoder = Arc::clone(&self.contract_decoder);
+        for transaction in reverted_transactions {
+            // Transactions that are no longer valid are dropped, like in a real
+            // reorganisation.
+            match self.add_pending_transaction(transaction) {
+                Ok(_) | Err(ProviderError::MemPoolAddTransaction(_)) => (),
+                Err(error) => return Err(error),
+            }
+        }
 
-        self.execute_in_block_context(Some(block_spec), |blockchain, block, state| {
-            let state_overrider = StateRefOverrider::new(state_overrides, state.as_ref());
+        (0..new_blocks)
+            .map(|_| self.mine_and_commit_block(self.header_overrides()))
+            .collect()
+    }
 
-            let execution_result = call::run_call::<ChainSpecT, _, _, _>(
-                blockchain,
-                block.block_header(),
-                state_overrider,
-                cfg_env,
-                transaction,
-                &custom_precompiles,
-                &mut evm_observer,
-            )?;
+    /// Rewinds the local chain to the block with the provided number, removing
+    /// all subsequent blocks. Transactions of the removed blocks are not added
+    /// back to the mem pool.
+    ///
+    /// Fails if the block number is lower than the first local block number,
+    /// i.e. the fork block number, or higher than the latest block number.
+    pub fn set_head(
+        &mut self,
+        block_number: u64,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let latest_block_number = self.blockchain.last_block_number();
+        // Remote and genesis blocks cannot be removed
+        let first_local_block_number = self
+            .fork_metadata
+            .as_ref()
+            .map_or(0, |fork_metadata| fork_metadata.fork_block_number);
 
-            let EvmObserver {
-                code_coverage,
-                console_logger,
-                mocker: _mocker,
-                trace_collector,
-            } = evm_observer;
+        if block_number < first_local_block_number || block_number > latest_block_number {
+            return Err(ProviderError::InvalidHeadBlockNumber {
+                block_number,
+                first_local_block_number,
+                latest_block_number,
+            });
+        }
 
-            if let Some(code_coverage) = code_coverage {
-                code_coverage
-                    .report()
-                    .map_err(ProviderError::OnCollectedCoverageCallback)?;
-            }
+        if block_number < latest_block_number {
+            self.remove_blocks_after(block_number)?;
+        }
 
-            if let Some(GasReportArgs {
-                callback,
-                kind,
-                input,
-            }) = gas_report_args
-            {
-                let gas_report = GasReport::new(
-                    state,
-                    contract_decoder.as_ref(),
-                    &execution_result,
-                    kind,
-                    input,
-                )?;
+        Ok(())
+    }
 
-                callback(gas_report).map_err(ProviderError::OnCollectedGasReportCallback)?;
+    /// Removes all blocks after the block with the provided number, along with
+    /// their cached states, irregular state, and snapshots.
+    fn remove_blocks_after(
+        &mut self,
+        block_number: u64,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let last_block_number = self.blockchain.last_block_number();
+
+        // Transactions of removed blocks are not added back to the mem pool, so
+        // their blob sidecars and tags are no longer needed. Transactions that
+        // are re-added by a reorg are tagged again.
+        if !self.blob_sidecars.is_empty() || !self.transaction_tags.is_empty() {
+            for removed_block_number in block_number + 1..=last_block_number {
+                let Some(block) = self.blockchain.block_by_number(removed_block_number)? else {
+                    continue;
+                };
+
+                for transaction in block.transactions() {
+                    let transaction_hash = transaction.transaction_hash();
+
+                    self.blob_sidecars.remove_mut(transaction_hash);
+                    self.transaction_tags.remove(transaction_hash);
+                }
+            }
+        }
+
+        self.notify_filters_about_reverted_blocks(block_number)?;
+        self.blockchain.revert_to_block(block_number)?;
+        self.prune_state_diffs();
+
+        // Cached states and irregular state of removed blocks are no longer valid
+        for removed_block_number in block_number + 1..=last_block_number {
+            self.block_number_to_state_id
+                .remove_mut(&removed_block_number);
+        }
+        self.irregular_state
+            .remove_state_overrides_after(block_number);
+
+        // Snapshots of removed blocks can no longer be reverted to
+        self.snapshots
+            .retain(|_, snapshot| snapshot.block_number <= block_number);
+
+        let state = self.current_state()?;
+        self.mem_pool
+            .update(&*state)
+            .map_err(ProviderError::MemPoolUpdate)?;
+
+        self.apply_transaction_gas_cap()
+    }
+
+    /// Imports the blocks of an exported chain on top of the base block,
+    /// applying the local modifications of each block's state after it is
+    /// mined. Afterwards, the resulting state is compared with the exported
+    /// state.
+    ///
+    /// If any of the blocks doesn't match the exported block, all changes are
+    /// reverted.
+    pub fn import_chain(
+        &mut self,
+        base_state_overrides: Option<StateDump>,
+        blocks: Vec<(
+            EthBlockData<ChainSpecT::SignedTransaction>,
+            Option<StateDump>,
+        )>,
+        expected_state: StateDump,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let snapshot_id = self.make_snapshot();
+
+        let result = self.import_chain_impl(base_state_overrides, blocks, expected_state);
+        if result.is_err() {
+            // Ignore potential failure of reverting, as returning the original error is
+            // more important
+            let _result = self.revert_to_snapshot(snapshot_id);
+        } else {
+            self.snapshots.remove(&snapshot_id);
+        }
+
+        result
+    }
+
+    fn import_chain_impl(
+        &mut self,
+        base_state_overrides: Option<StateDump>,
+        blocks: Vec<(
+            EthBlockData<ChainSpecT::SignedTransaction>,
+            Option<StateDump>,
+        )>,
+        expected_state: StateDump,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        if let Some(state_overrides) = base_state_overrides {
+            self.load_state(state_overrides)?;
+        }
+
+        for (block, state_overrides) in blocks {
+            let block_number = block.header.number;
+            let expected = block.hash;
+
+            let actual = *self.import_block(block)?.block_hash();
+            if actual != expected {
+                return Err(ChainImportError::BlockMismatch {
+                    block_number,
+                    expected,
+                    actual,
+                }
+                .into());
+            }
+
+            if let Some(state_overrides) = state_overrides {
+                self.load_state(state_overrides)?;
+            }
+        }
+
+        let state = self.current_state()?;
+        for (address, account) in expected_state.accounts {
+            let account_info = state.basic(address)?.unwrap_or_default();
+            let code = if account_info.code_hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else {
+                state.code_by_hash(account_info.code_hash)?.original_bytes()
+            };
+
+            let mut matches = account_info.balance == account.balance
+                && U256::from(account_info.nonce) == account.nonce
+                && code == account.code;
+
+            for (index, value) in account.storage {
+                matches &= state.storage(address, index)? == value;
+            }
+
+            if !matches {
+                return Err(ChainImportError::StateMismatch { address }.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Mines a block with the transactions and header fields of a previously
+    /// exported block, returning the mined block.
+    ///
+    /// In fork mode, the exported state root is reused, as the state root of
+    /// forked state cannot be computed locally.
+    fn import_block(
+        &mut self,
+        block: EthBlockData<ChainSpecT::SignedTransaction>,
+    ) -> Result<Arc<ChainSpecT::Block>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let EthBlockData {
+            header,
+            transactions,
+            withdrawals,
+            ..
+        } = block;
+
+        let state_root = self.fork_metadata.is_some().then_some(header.state_root);
+        let options = HeaderOverrides {
+            beneficiary: Some(header.beneficiary),
+            state_root,
+            difficulty: Some(header.difficulty),
+            gas_limit: Some(header.gas_limit),
+            timestamp: Some(header.timestamp),
+            extra_data: Some(header.extra_data),
+            mix_hash: Some(header.mix_hash),
+            nonce: Some(header.nonce),
+            base_fee: header.base_fee_per_gas,
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            ..self.header_overrides()
+        };
+
+        let block_inputs = BlockInputs {
+            ommers: Vec::new(),
+            withdrawals,
+        };
+
+        self.mine_imported_block(options, block_inputs, transactions)
+    }
+
+    /// Imports RLP-encoded blocks, e.g. exported by another Ethereum client,
+    /// on top of the latest block. Each block is validated against its parent
+    /// and executed, after which the resulting header is compared with the
+    /// imported header.
+    ///
+    /// Proof-of-work seals are not verified and ommer rewards are not applied.
+    ///
+    /// If any of the blocks is invalid, all changes are reverted.
+    pub fn import_raw_blocks(
+        &mut self,
+        blocks: Vec<RawBlock<ChainSpecT::SignedTransaction>>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let snapshot_id = self.make_snapshot();
+
+        let result = blocks
+            .into_iter()
+            .try_for_each(|block| self.import_raw_block(block));
+
+        if result.is_err() {
+            // Ignore potential failure of reverting, as returning the original error is
+            // more important
+            let _result = self.revert_to_snapshot(snapshot_id);
+        } else {
+            self.snapshots.remove(&snapshot_id);
+        }
+
+        result
+    }
+
+    fn import_raw_block(
+        &mut self,
+        block: RawBlock<ChainSpecT::SignedTransaction>,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let RawBlock {
+            header,
+            transactions,
+            ommers,
+            withdrawals,
+        } = block;
+
+        self.validate_raw_block_header(&header, &ommers)?;
+
+        // The state root of forked state cannot be computed locally
+        let state_root = self.fork_metadata.is_some().then_some(header.state_root);
+        let options = HeaderOverrides {
+            beneficiary: Some(header.beneficiary),
+            state_root,
+            difficulty: Some(header.difficulty),
+            gas_limit: Some(header.gas_limit),
+            timestamp: Some(header.timestamp),
+            extra_data: Some(header.extra_data.clone()),
+            mix_hash: Some(header.mix_hash),
+            nonce: Some(header.nonce),
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            ..self.header_overrides()
+        };
+
+        let block_inputs = BlockInputs {
+            ommers,
+            withdrawals,
+        };
+
+        let block = self.mine_imported_block(options, block_inputs, transactions)?;
+        let executed = block.block_header();
+
+        let block_number = header.number;
+        ensure_header_field_matches(
+            block_number,
+            "transactions root",
+            &header.transactions_root,
+            &executed.transactions_root,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "ommers hash",
+            &header.ommers_hash,
+            &executed.ommers_hash,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "withdrawals root",
+            &header.withdrawals_root,
+            &executed.withdrawals_root,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "base fee per gas",
+            &header.base_fee_per_gas,
+            &executed.base_fee_per_gas,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "gas used",
+            &header.gas_used,
+            &executed.gas_used,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "logs bloom",
+            &header.logs_bloom,
+            &executed.logs_bloom,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "receipts root",
+            &header.receipts_root,
+            &executed.receipts_root,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "blob gas",
+            &header.blob_gas,
+            &executed.blob_gas,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "state root",
+            &header.state_root,
+            &executed.state_root,
+        )?;
+        ensure_header_field_matches(
+            block_number,
+            "requests hash",
+            &header.requests_hash,
+            &executed.requests_hash,
+        )?;
+        ensure_header_field_matches(block_number, "hash", &header.hash(), block.block_hash())?;
+
+        Ok(())
+    }
+
+    /// Validates the header of an imported block against its parent, i.e. the
+    /// latest block.
+    fn validate_raw_block_header(
+        &self,
+        header: &BlockHeader,
+        ommers: &[BlockHeader],
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        let parent = self.blockchain.last_block()?;
+        let parent_header = parent.block_header();
+
+        let expected = parent_header.number + 1;
+        if header.number != expected {
+            return Err(RawBlockImportError::UnexpectedBlockNumber {
+                expected,
+                actual: header.number,
+            }
+            .into());
+        }
+
+        if header.parent_hash != *parent.block_hash() {
+            return Err(RawBlockImportError::UnknownParent {
+                block_number: header.number,
+                parent_hash: header.parent_hash,
+                latest_block_hash: *parent.block_hash(),
+            }
+            .into());
+        }
+
+        let invalid_header = |reason: String| RawBlockImportError::InvalidHeader {
+            block_number: header.number,
+            reason,
+        };
+
+        if header.timestamp <= parent_header.timestamp {
+            return Err(invalid_header(format!(
+                "timestamp {} is not greater than the parent's timestamp {}",
+                header.timestamp, parent_header.timestamp
+            ))
+            .into());
+        }
+
+        if header.extra_data.len() > MAX_EXTRA_DATA_SIZE {
+            return Err(invalid_header(format!(
+                "extra data of {} bytes exceeds the maximum of {MAX_EXTRA_DATA_SIZE} bytes",
+                header.extra_data.len()
+            ))
+            .into());
+        }
+
+        if header.gas_used > header.gas_limit {
+            return Err(invalid_header(format!(
+                "gas used {} exceeds the gas limit {}",
+                header.gas_used, header.gas_limit
+            ))
+            .into());
+        }
+
+        // The gas limit may change by less than 1/1024 of the parent's gas limit
+        let max_gas_limit_delta = parent_header.gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+        if header.gas_limit.abs_diff(parent_header.gas_limit) >= max_gas_limit_delta
+            || header.gas_limit < MIN_GAS_LIMIT
+        {
+            return Err(invalid_header(format!(
+                "gas limit {} is not within the bounds of the parent's gas limit {}",
+                header.gas_limit, parent_header.gas_limit
+            ))
+            .into());
+        }
+
+        // The block is executed with the provider's hardfork, so it needs to
+        // match the hardfork that the block belongs to.
+        let evm_spec_id: EvmSpecId = self.raw_block_hardfork(header).into();
+        if evm_spec_id != self.evm_spec_id() {
+            return Err(invalid_header(format!(
+                "block belongs to hardfork {evm_spec_id:?}, but would be executed with hardfork {:?}",
+                self.evm_spec_id()
+            ))
+            .into());
+        }
+
+        if evm_spec_id >= EvmSpecId::MERGE {
+            if header.difficulty != U256::ZERO {
+                return Err(invalid_header(format!(
+                    "difficulty {} must be zero after the merge",
+                    header.difficulty
+                ))
+                .into());
+            }
+
+            if header.nonce != B64::ZERO {
+                return Err(
+                    invalid_header("nonce must be zero after the merge".to_string()).into(),
+                );
+            }
+
+            if !ommers.is_empty() {
+                return Err(
+                    invalid_header("ommers are not allowed after the merge".to_string()).into(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hardfork of an imported block, determined by its number and
+    /// timestamp if the provider's chain has known hardfork activations.
+    /// Otherwise, the hardfork of the next block is used.
+    fn raw_block_hardfork(&self, header: &BlockHeader) -> ChainSpecT::Hardfork {
+        ChainSpecT::chain_configs()
+            .get(&self.blockchain.chain_id())
+            .and_then(|chain_config| {
+                chain_config
+                    .hardfork_activations
+                    .hardfork_at_block(header.number, header.timestamp)
+            })
+            .unwrap_or_else(|| self.blockchain.hardfork())
+    }
+
+    /// Mines and commits a block with the provided transactions and header
+    /// overrides, returning the mined block.
+    fn mine_imported_block(
+        &mut self,
+        options: HeaderOverrides<ChainSpecT::Hardfork>,
+        block_inputs: BlockInputs,
+        transactions: Vec<ChainSpecT::SignedTransaction>,
+    ) -> Result<Arc<ChainSpecT::Block>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let result = self.mine_and_commit_block_impl(
+            move |provider, config, options, evm_observer| {
+                provider.mine_block_with_transactions(
+                    config,
+                    options,
+                    block_inputs,
+                    transactions,
+                    evm_observer,
+                )
+            },
+            options,
+        )?;
+
+        Ok(result.block)
+    }
+
+    pub fn run_call(
+        &mut self,
+        transaction: ChainSpecT::SignedTransaction,
+        block_spec: &BlockSpec,
+        state_overrides: &StateOverrides,
+    ) -> Result<CallResult<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>> {
+        struct GasReportArgs {
+            callback: Box<dyn SyncOnCollectedGasReportCallback>,
+            kind: TxKind,
+            input: Bytes,
+        }
+
+        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
+
+        let custom_precompiles = self.precompile_overrides.clone();
+        let mut evm_observer = EvmObserver::new(EvmObserverConfig::from(&self.observability));
+
+        let gas_report_args =
+            self.observability
+                .on_collected_gas_report_fn
+                .as_ref()
+                .map(|callback| GasReportArgs {
+                    callback: callback.clone(),
+                    kind: transaction.kind(),
+                    input: transaction.data().clone(),
+                });
+
+        let contract_decoder = Arc::clone(&self.contract_decoder);
+        let gas_sponsor = self.gas_sponsor_of(transaction.caller());
+
+        self.execute_in_block_context(Some(block_spec), |blockchain, block, state| {
+            let state_overrider = StateRefOverrider::new(state_overrides, state.as_ref());
+
+            let gas_sponsor_overrides =
+                gas_sponsor_call_overrides(gas_sponsor, &state_overrider, &transaction)?;
+            let state_overrider = StateRefOverrider::new(&gas_sponsor_overrides, state_overrider);
+
+            let execution_result = call::run_call::<ChainSpecT, _, _, _>(
+                blockchain,
+                block.block_header(),
+                state_overrider,
+                cfg_env,
+                transaction,
+                &custom_precompiles,
+                &mut evm_observer,
+            )?;
+
+            let EvmObserver {
+                code_coverage,
+                console_logger,
+                mocker: _mocker,
+                trace_collector,
+            } = evm_observer;
+
+            if let Some(code_coverage) = code_coverage {
+                code_coverage
+                    .report()
+                    .map_err(ProviderError::OnCollectedCoverageCallback)?;
+            }
+
+            if let Some(GasReportArgs {
+                callback,
+                kind,
+                input,
+            }) = gas_report_args
+            {
+                let gas_report = GasReport::new(
+                    state,
+                    contract_decoder.as_ref(),
+                    &execution_result,
+                    kind,
+                    input,
+                )?;
+
+                callback(gas_report).map_err(ProviderError::OnCollectedGasReportCallback)?;
             }
 
             let mut traces = trace_collector.into_traces();
@@ -2338,6 +5117,68 @@ where
         })?
     }
 
+    /// Simulates the transactions of a bundle in order on top of the state of
+    /// the block with the provided block spec, without committing any changes.
+    ///
+    /// The bundle is executed in the context of a child of that block, whose
+    /// number, timestamp, and coinbase can be overridden. By default, the
+    /// child's timestamp is one second after that of its parent.
+    pub fn simulate_bundle(
+        &mut self,
+        transactions: Vec<BundleTransaction<ChainSpecT::SignedTransaction>>,
+        block_spec: &BlockSpec,
+        block_overrides: BundleBlockOverrides,
+    ) -> Result<BundleSimulation, ProviderErrorForChainSpec<ChainSpecT>> {
+        if transactions.is_empty() {
+            return Err(ProviderError::InvalidInput(
+                "A bundle must contain at least one transaction".to_string(),
+            ));
+        }
+
+        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
+
+        let custom_precompiles = self.precompile_overrides.clone();
+        let contract_decoder = Arc::clone(&self.contract_decoder);
+
+        let base_fee_params = self.blockchain.base_fee_params().clone();
+        let disable_base_fee_adjustment = self.disable_base_fee_adjustment;
+
+        self.execute_in_block_context(Some(block_spec), |blockchain, block, state| {
+            let parent_header = block.block_header();
+
+            let mut header = parent_header.clone();
+            header.parent_hash = *block.block_hash();
+            header.number = block_overrides
+                .number
+                .unwrap_or_else(|| parent_header.number + 1);
+            header.timestamp = block_overrides
+                .timestamp
+                .unwrap_or_else(|| parent_header.timestamp + 1);
+            if let Some(coinbase) = block_overrides.coinbase {
+                header.beneficiary = coinbase;
+            }
+
+            header.base_fee_per_gas = parent_header.base_fee_per_gas.map(|base_fee_per_gas| {
+                if disable_base_fee_adjustment {
+                    base_fee_per_gas
+                } else {
+                    ChainSpecT::next_base_fee_per_gas(parent_header, cfg_env.spec, &base_fee_params)
+                }
+            });
+
+            simulate_bundle::<ChainSpecT>(SimulateBundleArgs {
+                blockchain,
+                header: &header,
+                state_block_number: parent_header.number,
+                state: state.clone(),
+                cfg_env,
+                transactions,
+                custom_precompiles: &custom_precompiles,
+                contract_decoder: &contract_decoder,
+            })
+        })?
+    }
+
     fn execute_in_block_context<T>(
         &mut self,
         block_spec: Option<&BlockSpec>,
@@ -2362,13 +5203,16 @@ where
             Ok(function(&*self.blockchain, &block, &contextual_state))
         } else {
             // Block spec is pending
-            let result = self.mine_pending_block()?;
+            let PendingBlock {
+                block: pending_block,
+                state,
+            } = self.pending_block()?;
 
-            let blockchain = BlockchainWithPending::new(&*self.blockchain, result.block);
+            let blockchain = BlockchainWithPending::new(&*self.blockchain, pending_block);
 
             let block: Arc<ChainSpecT::Block> = blockchain.last_block().clone().cast_arc_into();
 
-            Ok(function(&blockchain, &block, &result.state))
+            Ok(function(&blockchain, &block, &state))
         }
     }
 
@@ -2384,14 +5228,39 @@ where
         let reward = miner_reward(self.blockchain.hardfork().into()).unwrap_or(0);
         let state_to_be_modified = (*self.current_state()?).clone();
 
+        // Forget delayed transactions that are no longer in the mem pool
+        self.delayed_transactions.retain(|transaction_hash, _| {
+            self.mem_pool
+                .transaction_by_hash(transaction_hash)
+                .is_some()
+        });
+
+        let block_number = self.blockchain.last_block_number() + 1;
+        let delayed_transactions = self.delayed_transactions_at(block_number);
+
+        let seed = self.inclusion_policy.seed.to::<u64>();
+        let shuffle_callback = move |transactions: &[PendingTransactionSummary]| {
+            shuffled_ranking(seed, block_number, transactions)
+        };
+        let ordering_callback: Option<&dyn SyncMineOrderingCallback> =
+            if self.inclusion_policy.shuffle {
+                Some(&shuffle_callback)
+            } else {
+                self.mine_ordering_callback.as_deref()
+            };
+
         let result = mine_block::<ChainSpecT, _, _>(
             self.blockchain.as_ref(),
             state_to_be_modified,
+            self.queued_deposits.clone(),
             &self.mem_pool,
+            &delayed_transactions,
             evm_config,
+            self.next_block_inputs(),
             options,
-            self.min_gas_price,
+            self.effective_min_gas_price(),
             self.mining_config.mem_pool.order,
+            ordering_callback,
             reward,
             Some(evm_observer),
             &self.precompile_overrides,
@@ -2400,10 +5269,69 @@ where
         Ok(result)
     }
 
-    /// Mines a block with the provided transaction.
-    fn mine_block_with_single_transaction(
+    /// Retrieves the hashes of the transactions that the inclusion policy
+    /// delays past the provided block number.
+    fn delayed_transactions_at(&self, block_number: u64) -> HashSet<B256> {
+        self.delayed_transactions
+            .iter()
+            .filter(|(_, first_block_number)| **first_block_number > block_number)
+            .map(|(transaction_hash, _)| *transaction_hash)
+            .collect()
+    }
+
+    /// Whether the sender of the provided transaction has an earlier
+    /// transaction that the inclusion policy delays past the next block.
+    fn is_behind_delayed_transaction(&self, transaction: &ChainSpecT::SignedTransaction) -> bool {
+        self.delayed_transactions_at(self.blockchain.last_block_number() + 1)
+            .iter()
+            .filter_map(|transaction_hash| self.mem_pool.transaction_by_hash(transaction_hash))
+            .any(|delayed| {
+                delayed.pending().caller() == transaction.caller()
+                    && delayed.pending().nonce() < transaction.nonce()
+            })
+    }
+
+    /// Mines blocks while the mem pool has pending transactions that can be
+    /// included in the next block, if auto-mining is enabled. This mines the
+    /// transactions whose inclusion delay expired.
+    pub fn mine_includable_transactions(
         &mut self,
-        evm_config: &EvmConfig,
+    ) -> Result<
+        Vec<DebugMineBlockResultForChainSpec<ChainSpecT>>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let mut mining_results = Vec::new();
+        if !self.is_auto_mining {
+            return Ok(mining_results);
+        }
+
+        while self.has_includable_transactions(self.blockchain.last_block_number() + 1) {
+            mining_results.push(self.mine_and_commit_block(self.header_overrides())?);
+        }
+
+        Ok(mining_results)
+    }
+
+    /// Whether the mem pool has pending transactions that can be included in
+    /// the provided block number.
+    fn has_includable_transactions(&self, block_number: u64) -> bool {
+        let delayed_transactions = self.delayed_transactions_at(block_number);
+
+        // Delaying a transaction also delays the sender's subsequent transactions,
+        // so only the first pending transaction of each sender is relevant.
+        let mut senders = HashSet::default();
+        self.mem_pool
+            .pending_transactions()
+            .filter(|transaction| senders.insert(*transaction.pending().caller()))
+            .any(|transaction| {
+                !delayed_transactions.contains(transaction.pending().transaction_hash())
+            })
+    }
+
+    /// Mines a block with the provided transaction.
+    fn mine_block_with_single_transaction(
+        &mut self,
+        evm_config: &EvmConfig,
         options: HeaderOverrides<ChainSpecT::Hardfork>,
         transaction: ChainSpecT::SignedTransaction,
         evm_observer: &mut EvmObserver<ChainSpecT::HaltReason>,
@@ -2419,8 +5347,39 @@ where
             state_to_be_modified,
             transaction,
             evm_config,
+            self.next_block_inputs(),
+            options,
+            self.effective_min_gas_price(),
+            reward,
+            Some(evm_observer),
+            &self.precompile_overrides,
+        )?;
+
+        Ok(result)
+    }
+
+    /// Mines a block with the provided transactions, in the provided order.
+    fn mine_block_with_transactions(
+        &mut self,
+        evm_config: &EvmConfig,
+        options: HeaderOverrides<ChainSpecT::Hardfork>,
+        block_inputs: BlockInputs,
+        transactions: Vec<ChainSpecT::SignedTransaction>,
+        evm_observer: &mut EvmObserver<ChainSpecT::HaltReason>,
+    ) -> Result<
+        BuiltBlockAndState<ChainSpecT::HaltReason, <ChainSpecT as GenesisBlockFactory>::LocalBlock>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let reward = miner_reward(self.blockchain.hardfork().into()).unwrap_or(0);
+        let state_to_be_modified = (*self.current_state()?).clone();
+
+        let result = mine_block_with_transactions::<ChainSpecT, _, _>(
+            self.blockchain.as_ref(),
+            state_to_be_modified,
+            transactions,
+            evm_config,
+            block_inputs,
             options,
-            self.min_gas_price,
             reward,
             Some(evm_observer),
             &self.precompile_overrides,
@@ -2445,15 +5404,10 @@ where
         transaction: ChainSpecT::SignedTransaction,
     ) -> Result<SendTransactionResultForChainSpec<ChainSpecT>, ProviderErrorForChainSpec<ChainSpecT>>
     {
-        if transaction.transaction_type().is_eip4844() {
-            if !self.is_auto_mining || edr_mem_pool::has_transactions(&self.mem_pool) {
-                return Err(ProviderError::BlobMemPoolUnsupported);
-            }
-
+        if transaction.is_deposit() {
+            // Deposit transactions are created by the sequencer and pay no fees, so
+            // they bypass the mempool and are immediately mined in their own block.
             let transaction_hash = *transaction.transaction_hash();
-
-            // Despite not adding the transaction to the mempool, we still notify
-            // subscribers
             self.notify_subscribers_about_pending_transaction(&transaction_hash);
 
             let result = self.mine_and_commit_block_impl(
@@ -2474,13 +5428,55 @@ where
             });
         }
 
+        if let Some(action) = inclusion_action(
+            &self.inclusion_policy,
+            transaction.transaction_hash(),
+            transaction.caller(),
+        )
+        .cloned()
+        {
+            let transaction_hash = match action {
+                InclusionAction::Delay { blocks } => {
+                    let transaction_hash = self.add_pending_transaction(transaction)?;
+
+                    let first_block_number = (self.blockchain.last_block_number() + 1)
+                        .saturating_add(blocks.saturating_to());
+
+                    self.delayed_transactions
+                        .insert(transaction_hash, first_block_number);
+
+                    transaction_hash
+                }
+                // Dropped transactions never reach the mem pool, so they can't
+                // replace or evict other transactions.
+                InclusionAction::Drop => *transaction.transaction_hash(),
+            };
+
+            // Transactions affected by the inclusion policy aren't automined, but other
+            // transactions whose delay expired are
+            let mining_results = self.mine_includable_transactions()?;
+
+            return Ok(SendTransactionResult {
+                transaction_hash,
+                mining_results,
+            });
+        }
+
         let snapshot_id = if self.is_auto_mining {
-            // This check guarantees that the sent transaction is a pending transaction,
-            // meaning it can either be mined immediately or as part of a sequence of
-            // transactions.
+            // Unless future transactions are queued, this check guarantees that the sent
+            // transaction is a pending transaction, meaning it can either be mined
+            // immediately or as part of a sequence of transactions.
             self.validate_auto_mine_transaction(&transaction)?;
 
-            Some(self.make_snapshot())
+            let is_future_transaction =
+                transaction.nonce() > self.account_next_nonce(transaction.caller())?;
+
+            // Future transactions are mined once they're promoted to pending
+            // transactions, when the nonce gap is filled. Likewise, transactions
+            // queued behind a delayed transaction of the same sender are mined once
+            // the delay expires.
+            (!is_future_transaction && !self.is_behind_delayed_transaction(&transaction))
+                .then(|| self.make_snapshot())
         } else {
             None
         };
@@ -2489,7 +5485,7 @@ where
             .add_pending_transaction(transaction)
             .inspect_err(|_error| {
                 if let Some(snapshot_id) = snapshot_id {
-                    self.revert_to_snapshot(snapshot_id);
+                    let _result = self.revert_to_snapshot(snapshot_id);
                 }
             })?;
 
@@ -2513,7 +5509,7 @@ where
                         let result = self
                             .mine_and_commit_block(self.header_overrides())
                             .inspect_err(|_error| {
-                                self.revert_to_snapshot(snapshot_id);
+                                let _result = self.revert_to_snapshot(snapshot_id);
                             })?;
 
                         let mined_transaction = result.has_transaction(&transaction_hash);
@@ -2530,11 +5526,12 @@ where
                     //   that became pending
                     // - the mine ordering is "priority" and the sent transaction has a higher
                     //   miner's tip than other pending transactions.
-                    while self.mem_pool.has_pending_transactions() {
+                    while self.has_includable_transactions(self.blockchain.last_block_number() + 1)
+                    {
                         let result = self
                             .mine_and_commit_block(self.header_overrides())
                             .inspect_err(|_error| {
-                                self.revert_to_snapshot(snapshot_id);
+                                let _result = self.revert_to_snapshot(snapshot_id);
                             })?;
 
                         mining_results.push(result);
@@ -2567,6 +5564,7 @@ where
         &mut self,
         transaction_hash: &B256,
         trace_config: DebugTraceConfig,
+        struct_log_stream: Option<StructLogStream>,
     ) -> Result<
         DebugTraceResultWithTraces<ChainSpecT::HaltReason>,
         ProviderErrorForChainSpec<ChainSpecT>,
@@ -2604,397 +5602,854 @@ where
                     transactions,
                     transaction_hash,
                     observer_config,
+                    struct_log_stream,
                 )
                 .map_err(ProviderError::DebugTrace)
             },
         )?
     }
 
-    /// Filters out transactions with unsupported types and returns the
-    /// remaining transactions, if skipping is allowed. Otherwise returns
-    /// an error.
-    fn filter_unsupported_transaction_types(
-        &self,
-        transactions: &[ChainSpecT::SignedTransaction],
+    /// Replays the transaction with the provided hash and aggregates its gas
+    /// usage per call frame, internal function, and source line.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub fn gas_profile_transaction(
+        &mut self,
         transaction_hash: &B256,
-    ) -> Result<Vec<ChainSpecT::SignedTransaction>, ProviderErrorForChainSpec<ChainSpecT>> {
-        transactions
-            .iter()
-            .filter_map(|transaction| {
-                if transaction.is_supported_transaction() {
-                    Some(Ok(transaction.clone()))
-                } else if *transaction.transaction_hash() == *transaction_hash {
-                    Some(Err(
-                        ProviderError::UnsupportedTransactionTypeForDebugTrace {
-                            transaction_hash: *transaction_hash,
-                            unsupported_transaction_type: transaction.transaction_type().into(),
-                        },
-                    ))
-                } else if self.skip_unsupported_transaction_types {
-                    None
-                } else {
-                    Some(Err(ProviderError::UnsupportedTransactionTypeInDebugTrace {
-                        requested_transaction_hash: *transaction_hash,
-                        unsupported_transaction_hash: *transaction.transaction_hash(),
-                        unsupported_transaction_type: transaction.transaction_type().into(),
-                    }))
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()
-    }
-}
+    ) -> Result<GasProfile, ProviderErrorForChainSpec<ChainSpecT>> {
+        let block = self
+            .blockchain
+            .block_by_transaction_hash(transaction_hash)?
+            .ok_or_else(|| ProviderError::InvalidTransactionHash(*transaction_hash))?;
 
-impl<ChainSpecT, TimerT> ProviderData<ChainSpecT, TimerT>
-where
-    ChainSpecT: SyncProviderSpec<
-        TimerT,
-        SignedTransaction: Default
-                               + TransactionMut
-                               + TransactionValidation<ValidationError: PartialEq>,
-    >,
-    TimerT: Clone + TimeSinceEpoch,
-{
-    /// Estimate the gas cost of a transaction. Matches Hardhat behavior.
-    pub fn estimate_gas(
-        &mut self,
-        transaction: ChainSpecT::SignedTransaction,
-        block_spec: &BlockSpec,
-    ) -> Result<EstimateGasResult<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
-    {
-        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
-        // Minimum gas cost that is required for transaction to be included in
-        // a block
-        let minimum_cost =
-            transaction::calculate_initial_tx_gas_for_tx(&transaction, self.evm_spec_id())
-                .initial_gas;
+        let header = block.block_header();
 
-        let custom_precompiles = self.precompile_overrides.clone();
-        let mut evm_observer = EvmObserver::new(EvmObserverConfig::from(&self.observability));
+        let cfg_env = self.create_evm_config_at_block_spec(&BlockSpec::Number(header.number))?;
 
-        self.execute_in_block_context(Some(block_spec), |blockchain, block, state| {
-            let header = block.block_header();
+        let transactions =
+            self.filter_unsupported_transaction_types(block.transactions(), transaction_hash)?;
 
-            // Measure the gas used by the transaction with optional limit from call request
-            // defaulting to block limit. Report errors from initial call as if from
-            // `eth_call`.
-            let result = call::run_call::<'_, ChainSpecT, _, _, _>(
-                blockchain,
-                header,
-                state,
-                cfg_env.clone(),
-                transaction.clone(),
-                &custom_precompiles,
-                &mut evm_observer,
-            )?;
+        let prev_block_number = block.block_header().number - 1;
+        let prev_block_spec = Some(BlockSpec::Number(prev_block_number));
+        let contract_decoder = Arc::clone(&self.contract_decoder);
 
-            let EvmObserver {
-                code_coverage,
-                console_logger,
-                mocker: _mocker,
-                mut trace_collector,
-            } = evm_observer;
+        self.execute_in_block_context(
+            prev_block_spec.as_ref(),
+            |blockchain, _prev_block, state| {
+                let block_env = ChainSpecT::BlockEnv::new_block_env(header, cfg_env.spec);
 
-            if let Some(code_coverage) = code_coverage {
-                code_coverage
-                    .report()
-                    .map_err(ProviderError::OnCollectedCoverageCallback)?;
-            }
+                gas_profile_transaction::<ChainSpecT>(
+                    blockchain,
+                    state.clone(),
+                    cfg_env,
+                    block_env,
+                    transactions,
+                    transaction_hash,
+                    &contract_decoder,
+                )
+                .map_err(ProviderError::DebugTrace)
+            },
+        )?
+    }
 
-            let mut initial_estimation = match result {
-                ExecutionResult::Success { gas_used, .. } => Ok(gas_used),
-                ExecutionResult::Revert { output, .. } => Err(TransactionFailure::revert(
-                    output,
-                    None,
-                    trace_collector
-                        .traces()
-                        .first()
-                        .expect("Must have a trace")
-                        .clone(),
-                )),
-                ExecutionResult::Halt { reason, .. } => Err(TransactionFailure::halt(
-                    ChainSpecT::cast_halt_reason(reason),
-                    None,
-                    trace_collector
-                        .traces()
-                        .first()
-                        .expect("Must have a trace")
-                        .clone(),
-                )),
-            }
-            .map_err(|failure| {
-                Box::new(EstimateGasFailure {
-                    console_log_inputs: console_logger.into_encoded_messages(),
-                    transaction_failure: TransactionFailureWithTraces {
-                        traces: vec![failure.solidity_trace.clone()],
-                        failure,
-                    },
-                })
-            })?;
+    /// Replays the transactions of the block with the provided number on top
+    /// of its parent's state and compares the results with the block's
+    /// receipts and post-state. In fork mode, this is used to verify the
+    /// execution of remote blocks.
+    pub fn replay_block(
+        &mut self,
+        block_number: u64,
+    ) -> Result<ReplayedBlock, ProviderErrorForChainSpec<ChainSpecT>> {
+        if block_number == 0 {
+            return Err(ProviderError::InvalidInput(
+                "The genesis block cannot be replayed".to_string(),
+            ));
+        }
 
-            // Ensure that the initial estimation is at least the minimum cost + 1.
-            if initial_estimation <= minimum_cost {
-                initial_estimation = minimum_cost + 1;
-            }
+        let block = self
+            .block_by_block_spec(&BlockSpec::Number(block_number))?
+            .expect("Block numbers cannot be pending");
 
-            // Test if the transaction would be successful with the initial estimation
-            let success = gas::check_gas_limit::<ChainSpecT>(CheckGasLimitArgs {
-                blockchain,
-                header,
-                state,
-                cfg_env: cfg_env.clone(),
-                transaction: transaction.clone(),
-                gas_limit: initial_estimation,
-                custom_precompiles: &custom_precompiles,
-                trace_collector: &mut trace_collector,
-            })?;
+        if let Some(transaction) = block
+            .transactions()
+            .iter()
+            .find(|transaction| !transaction.is_supported_transaction())
+        {
+            return Err(ProviderError::UnsupportedTransactionTypeInBlockReplay {
+                block_number,
+                transaction_hash: *transaction.transaction_hash(),
+                unsupported_transaction_type: transaction.transaction_type().into(),
+            });
+        }
 
-            // Return the initial estimation if it was successful
-            if success {
-                return Ok(EstimateGasResult {
-                    estimation: initial_estimation,
-                    traces: trace_collector.into_traces(),
-                });
-            }
+        let receipts = block
+            .fetch_transaction_receipts()
+            .map_err(ProviderError::FetchReceipt)?;
 
-            // Correct the initial estimation if the transaction failed with the actually
-            // used gas limit. This can happen if the execution logic is based
-            // on the available gas.
-            let estimation =
-                gas::binary_search_estimation::<ChainSpecT>(BinarySearchEstimationArgs {
-                    blockchain,
-                    header,
-                    state,
-                    cfg_env: cfg_env.clone(),
-                    transaction,
-                    lower_bound: initial_estimation,
-                    upper_bound: header.gas_limit,
-                    custom_precompiles: &custom_precompiles,
-                    trace_collector: &mut trace_collector,
-                })?;
+        let header = block.block_header();
+        let cfg_env = self.create_evm_config_at_block_spec(&BlockSpec::Number(block_number))?;
+        let reward = miner_reward(cfg_env.spec.into()).unwrap_or(0);
 
-            let traces = trace_collector.into_traces();
-            Ok(EstimateGasResult { estimation, traces })
-        })?
-    }
-}
+        let prev_state = self.get_or_compute_state(block_number - 1)?;
+        let post_state = self.get_or_compute_state(block_number)?;
 
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-#[repr(transparent)]
-pub(crate) struct StateId(u64);
+        let block_env = ChainSpecT::BlockEnv::new_block_env(header, cfg_env.spec);
 
-impl StateId {
-    /// Increment the current state id and return the incremented id.
-    fn increment(&mut self) -> Self {
-        self.0 += 1;
-        *self
+        replay_block::<ChainSpecT>(
+            &*self.blockchain,
+            (*prev_state).clone(),
+            &**post_state,
+            cfg_env,
+            block_env,
+            header,
+            block.transactions().to_vec(),
+            &receipts,
+            block.withdrawals(),
+            reward,
+            // Forked state roots are generated, so they cannot be compared
+            self.fork_metadata.is_none(),
+        )
+        .map_err(ProviderError::DebugTrace)
     }
-}
 
-fn block_time_offset_seconds<ChainSpecT: ProviderChainSpec, TimerT: TimeSinceEpoch>(
-    config: &ProviderConfig<ChainSpecT::Hardfork>,
-    timer: &TimerT,
-) -> Result<i64, CreationErrorForChainSpec<ChainSpecT>> {
-    config.initial_date.map_or(Ok(0), |initial_date| {
-        let initial_timestamp = i64::try_from(
-            initial_date
-                .duration_since(UNIX_EPOCH)
-                .map_err(|_e| CreationError::InvalidInitialDate(initial_date))?
-                .as_secs(),
-        )
-        .expect("initial date must be representable as i64");
+    /// Replays the transaction with the provided hash on top of the state
+    /// produced by the block's prior transactions, returning its receipt,
+    /// trace, and state diff. In fork mode, this also works for transactions
+    /// that were mined before the fork block.
+    pub fn replay_transaction(
+        &mut self,
+        transaction_hash: &B256,
+    ) -> Result<
+        ReplayedTransactionWithTraces<ChainSpecT::HaltReason>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let block = self
+            .blockchain
+            .block_by_transaction_hash(transaction_hash)?
+            .ok_or_else(|| ProviderError::InvalidTransactionHash(*transaction_hash))?;
 
-        let current_timestamp = i64::try_from(timer.since_epoch())
-            .expect("Current timestamp must be representable as i64");
+        let header = block.block_header();
 
-        Ok(initial_timestamp - current_timestamp)
-    })
-}
+        let cfg_env = self.create_evm_config_at_block_spec(&BlockSpec::Number(header.number))?;
 
-struct BlockchainAndState<ChainSpecT: BlockChainSpec> {
-    blockchain: Box<dyn SyncBlockchainForChainSpec<ChainSpecT>>,
-    fork_metadata: Option<ForkMetadata>,
-    rpc_client: Option<Arc<EthRpcClientForChainSpec<ChainSpecT>>>,
-    state: Box<dyn DynState>,
-    irregular_state: IrregularState,
-    prev_randao_generator: RandomHashGenerator,
-    block_time_offset_seconds: i64,
-    next_block_base_fee_per_gas: Option<u128>,
-}
+        let transactions =
+            self.filter_unsupported_transaction_types(block.transactions(), transaction_hash)?;
 
-fn create_blockchain_and_state<
-    ChainSpecT: SyncProviderSpec<TimerT>,
-    TimerT: Clone + TimeSinceEpoch,
->(
-    runtime: runtime::Handle,
-    config: &ProviderConfig<ChainSpecT::Hardfork>,
-    timer: &TimerT,
-) -> Result<BlockchainAndState<ChainSpecT>, CreationErrorForChainSpec<ChainSpecT>> {
-    let mut prev_randao_generator = RandomHashGenerator::with_seed(edr_defaults::MIX_HASH_SEED);
+        let prev_block_number = block.block_header().number - 1;
+        let prev_block_spec = Some(BlockSpec::Number(prev_block_number));
+        let observer_config = EvmObserverConfig {
+            call_override: None,
+            ..EvmObserverConfig::from(&self.observability)
+        };
 
-    if let Some(fork_config) = &config.fork {
-        let state_root_generator = Arc::new(parking_lot::Mutex::new(
-            RandomHashGenerator::with_seed(edr_defaults::STATE_ROOT_HASH_SEED),
-        ));
+        self.execute_in_block_context(
+            prev_block_spec.as_ref(),
+            |blockchain, _prev_block, state| {
+                let block_env = ChainSpecT::BlockEnv::new_block_env(header, cfg_env.spec);
 
-        let http_headers = fork_config
-            .http_headers
-            .as_ref()
-            .map(|headers| HeaderMap::try_from(headers).map_err(CreationError::InvalidHttpHeaders))
-            .transpose()?;
+                replay_transaction::<ChainSpecT>(
+                    blockchain,
+                    state.clone(),
+                    cfg_env,
+                    block_env,
+                    transactions,
+                    transaction_hash,
+                    observer_config,
+                )
+                .map_err(ProviderError::DebugTrace)
+            },
+        )?
+    }
 
-        let rpc_client = Arc::new(EthRpcClient::<
-            ChainSpecT,
-            ChainSpecT::RpcReceipt,
-            ChainSpecT::RpcTransaction,
-        >::new(
-            &fork_config.url,
-            fork_config.cache_dir.clone(),
-            http_headers.clone(),
-        )?);
+    /// Retrieves the changes that the mined transaction with the provided
+    /// hash made to each account it modified, if they were recorded.
+    ///
+    /// Returns an error if state diff recording is disabled or the
+    /// transaction's block was pruned.
+    pub fn state_diff(
+        &self,
+        transaction_hash: &B256,
+    ) -> Result<Option<BTreeMap<Address, AccountDiff>>, ProviderErrorForChainSpec<ChainSpecT>> {
+        if !self.record_state_diffs {
+            return Err(ProviderError::InvalidInput(
+                "State diff recording is disabled. Enable it with `recordStateDiffs`.".to_string(),
+            ));
+        }
 
-        let mut chain_configs = ChainSpecT::chain_configs().clone();
-        for (chain_id, chain_override) in fork_config.chain_overrides.iter() {
-            chain_configs
-                .entry(*chain_id)
-                .and_modify(|chain_config| chain_config.apply_override(chain_override))
-                .or_insert_with(|| ChainConfig {
-                    name: chain_override.name.clone(),
-                    hardfork_activations: chain_override
-                        .hardfork_activation_overrides
-                        .clone()
-                        .unwrap_or_default(),
-                    base_fee_params: config
-                        .base_fee_params
-                        .clone()
-                        .unwrap_or_else(|| ChainSpecT::default_base_fee_params().clone()),
-                });
+        if let Some((_block_number, diff)) = self.state_diffs.get(transaction_hash) {
+            return Ok(Some(diff.clone()));
         }
 
-        let block_config = BlockConfig {
-            base_fee_params: ChainSpecT::default_base_fee_params(),
-            hardfork: config.hardfork,
-            min_ethash_difficulty: ChainSpecT::MIN_ETHASH_DIFFICULTY,
-        };
+        if let Some(block) = self
+            .blockchain
+            .block_by_transaction_hash(transaction_hash)?
+        {
+            let block_number = block.block_header().number;
+            if (self.first_local_block_number..self.first_unpruned_block_number)
+                .contains(&block_number)
+            {
+                return Err(ProviderError::InvalidInput(format!(
+                    "The state diff of transaction {transaction_hash} is unavailable, as block {block_number} was pruned."
+                )));
+            }
+        }
 
-        let (blockchain, mut irregular_state) = tokio::task::block_in_place(
-            || -> Result<_, ForkedCreationError<ChainSpecT::Hardfork>> {
-                let mut irregular_state = IrregularState::default();
-                let blockchain =
-                    runtime.block_on(ForkedBlockchainForChainSpec::<ChainSpecT>::new(
-                        block_config,
-                        runtime.clone(),
-                        rpc_client.clone(),
-                        &mut irregular_state,
-                        state_root_generator.clone(),
-                        &chain_configs,
-                        fork_config.block_number,
-                        Some(config.chain_id),
-                    ))?;
+        Ok(None)
+    }
 
-                Ok((blockchain, irregular_state))
-            },
-        )?;
+    /// Traces the transactions of the block with the provided number, in the
+    /// format of OpenEthereum's `trace_block`. Block rewards are not traced.
+    pub fn trace_block(
+        &mut self,
+        block_number: u64,
+    ) -> Result<Vec<LocalizedTrace>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let block = self
+            .block_by_block_spec(&BlockSpec::Number(block_number))?
+            .expect("Block numbers cannot be pending");
 
-        let fork_block_number = blockchain.last_block_number();
+        if block.transactions().is_empty() {
+            return Ok(Vec::new());
+        }
 
-        if !config.genesis_state.is_empty() {
-            let genesis_addresses = config.genesis_state.keys().cloned().collect::<Vec<_>>();
-            let genesis_account_infos = tokio::task::block_in_place(|| {
-                runtime.block_on(rpc_client.get_account_infos(
-                    &genesis_addresses,
-                    Some(BlockSpec::Number(fork_block_number)),
-                ))
-            })?;
+        if let Some(transaction) = block
+            .transactions()
+            .iter()
+            .find(|transaction| !transaction.is_supported_transaction())
+        {
+            return Err(ProviderError::UnsupportedTransactionTypeInBlockReplay {
+                block_number,
+                transaction_hash: *transaction.transaction_hash(),
+                unsupported_transaction_type: transaction.transaction_type().into(),
+            });
+        }
 
-            let genesis_state: HashMap<Address, Account> = config
-                .genesis_state
-                .iter()
-                .zip(genesis_account_infos)
-                .map(|((address, account_override), remote_account)| {
-                    let (code, code_hash) = account_override.code.as_ref().map_or(
-                        (remote_account.code, remote_account.code_hash),
-                        |code| {
-                            let code_hash = code.hash_slow();
-                            (Some(code.clone()), code_hash)
-                        },
-                    );
+        let transactions = block.transactions().to_vec();
+        self.trace_transactions_in_block(&block, transactions, None)
+    }
 
-                    let info = AccountInfo {
-                        balance: account_override.balance.unwrap_or(remote_account.balance),
-                        nonce: account_override.nonce.unwrap_or(remote_account.nonce),
-                        code_hash,
-                        code,
-                    };
+    /// Traces the transaction with the provided hash, in the format of
+    /// OpenEthereum's `trace_transaction`. Returns `None` if the transaction
+    /// hasn't been mined.
+    pub fn trace_transaction(
+        &mut self,
+        transaction_hash: &B256,
+    ) -> Result<Option<Vec<LocalizedTrace>>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let Some(block) = self
+            .blockchain
+            .block_by_transaction_hash(transaction_hash)?
+        else {
+            return Ok(None);
+        };
 
-                    // TODO: Add support for overriding the storage
-                    // TODO: https://github.com/NomicFoundation/edr/issues/911
-                    if account_override.storage.is_some() {
-                        return Err(ForkedCreationError::StorageOverridesUnsupported);
-                    }
+        let transactions =
+            self.filter_unsupported_transaction_types(block.transactions(), transaction_hash)?;
 
-                    let account = Account {
-                        info,
-                        // TODO: Add support for overriding the storage
-                        // TODO: https://github.com/NomicFoundation/edr/issues/911
-                        storage: HashMap::default(),
-                        status: AccountStatus::Created | AccountStatus::Touched,
-                        transaction_id: 0,
-                    };
+        self.trace_transactions_in_block(&block, transactions, Some(transaction_hash))
+            .map(Some)
+    }
 
-                    Ok((*address, account))
-                })
-                .collect::<Result<_, _>>()?;
+    /// Replays the transaction with the provided hash and returns its call
+    /// tree. Returns `None` if the transaction hasn't been mined.
+    pub fn transaction_call_tree(
+        &mut self,
+        transaction_hash: &B256,
+    ) -> Result<Option<Vec<Trace<ChainSpecT::HaltReason>>>, ProviderErrorForChainSpec<ChainSpecT>>
+    {
+        let Some(block) = self
+            .blockchain
+            .block_by_transaction_hash(transaction_hash)?
+        else {
+            return Ok(None);
+        };
 
-            irregular_state
-                .state_override_at_block_number(fork_block_number)
-                .and_modify(|state_override| {
-                    // No need to update the state_root, as it could only have been created by the
-                    // `ForkedBlockchain` constructor.
-                    state_override.diff.apply_diff(genesis_state.clone());
-                })
-                .or_insert_with(|| {
-                    let state_root = state_root_generator.lock().next_value();
+        let transactions =
+            self.filter_unsupported_transaction_types(block.transactions(), transaction_hash)?;
 
-                    StateOverride {
-                        diff: StateDiff::from(genesis_state),
-                        state_root,
-                    }
-                });
-        }
+        let call_trees = self.call_trees_in_block(&block, transactions, Some(transaction_hash))?;
 
-        let state = blockchain
-            .state_at_block_number(fork_block_number, irregular_state.state_overrides())
-            .expect("Fork state must exist");
+        Ok(call_trees.into_iter().next().map(|(_, traces)| traces))
+    }
 
-        let block_time_offset_seconds = {
-            let fork_block_timestamp = UNIX_EPOCH
-                + Duration::from_secs(
-                    blockchain
-                        .last_block()
-                        .map_err(DynBlockchainError::new)?
-                        .block_header()
-                        .timestamp,
-                );
+    /// Executes the provided transactions of a block on top of its parent's
+    /// state and collects their call trees. If a transaction hash is
+    /// provided, only that transaction is traced.
+    fn call_trees_in_block(
+        &mut self,
+        block: &Arc<ChainSpecT::Block>,
+        transactions: Vec<ChainSpecT::SignedTransaction>,
+        transaction_hash: Option<&B256>,
+    ) -> Result<
+        Vec<(B256, Vec<Trace<ChainSpecT::HaltReason>>)>,
+        ProviderErrorForChainSpec<ChainSpecT>,
+    > {
+        let header = block.block_header();
+        let block_number = header.number;
 
-            let elapsed = match timer.since(fork_block_timestamp) {
-                Ok(elapsed) => -i128::from(elapsed),
-                Err(forward_drift) => i128::from(forward_drift.duration().as_secs()),
-            };
+        let cfg_env = self.create_evm_config_at_block_spec(&BlockSpec::Number(block_number))?;
+        let prev_block_spec = Some(BlockSpec::Number(block_number - 1));
 
-            elapsed
-                .try_into()
-                .expect("Elapsed time since fork block must be representable as i64")
-        };
+        let custom_precompiles = self.precompile_overrides.clone();
 
-        let next_block_base_fee_per_gas = if config.hardfork.into() >= EvmSpecId::LONDON {
-            if let Some(base_fee) = config.initial_base_fee_per_gas {
-                Some(base_fee)
-            } else {
-                let previous_base_fee = blockchain
-                    .last_block()
-                    .map_err(DynBlockchainError::new)?
-                    .block_header()
-                    .base_fee_per_gas;
+        self.execute_in_block_context(
+            prev_block_spec.as_ref(),
+            |blockchain, _prev_block, state| {
+                let block_env = ChainSpecT::BlockEnv::new_block_env(header, cfg_env.spec);
+
+                trace_transactions::<ChainSpecT>(
+                    blockchain,
+                    state.clone(),
+                    cfg_env,
+                    block_env,
+                    transactions,
+                    transaction_hash,
+                    &custom_precompiles,
+                )
+                .map_err(ProviderError::DebugTrace)
+            },
+        )?
+    }
+
+    /// Executes the provided transactions of a block on top of its parent's
+    /// state and converts their call trees into traces. If a transaction hash
+    /// is provided, only that transaction is traced.
+    fn trace_transactions_in_block(
+        &mut self,
+        block: &Arc<ChainSpecT::Block>,
+        transactions: Vec<ChainSpecT::SignedTransaction>,
+        transaction_hash: Option<&B256>,
+    ) -> Result<Vec<LocalizedTrace>, ProviderErrorForChainSpec<ChainSpecT>> {
+        let block_hash = *block.block_hash();
+        let block_number = block.block_header().number;
+
+        let traces = self.call_trees_in_block(block, transactions, transaction_hash)?;
+
+        let transaction_positions: HashMap<B256, u64> = block
+            .transactions()
+            .iter()
+            .enumerate()
+            .map(|(position, transaction)| (*transaction.transaction_hash(), position as u64))
+            .collect();
+
+        Ok(traces
+            .into_iter()
+            .flat_map(|(transaction_hash, traces)| {
+                let transaction_position = transaction_positions[&transaction_hash];
+
+                transaction_traces(&traces, ChainSpecT::cast_halt_reason)
+                    .into_iter()
+                    .map(move |trace| LocalizedTrace {
+                        trace,
+                        block_hash,
+                        block_number,
+                        transaction_hash,
+                        transaction_position,
+                    })
+            })
+            .collect())
+    }
+
+    /// Filters out transactions with unsupported types and returns the
+    /// remaining transactions, if skipping is allowed. Otherwise returns
+    /// an error.
+    fn filter_unsupported_transaction_types(
+        &self,
+        transactions: &[ChainSpecT::SignedTransaction],
+        transaction_hash: &B256,
+    ) -> Result<Vec<ChainSpecT::SignedTransaction>, ProviderErrorForChainSpec<ChainSpecT>> {
+        transactions
+            .iter()
+            .filter_map(|transaction| {
+                if transaction.is_supported_transaction() {
+                    Some(Ok(transaction.clone()))
+                } else if *transaction.transaction_hash() == *transaction_hash {
+                    Some(Err(
+                        ProviderError::UnsupportedTransactionTypeForDebugTrace {
+                            transaction_hash: *transaction_hash,
+                            unsupported_transaction_type: transaction.transaction_type().into(),
+                        },
+                    ))
+                } else if self.skip_unsupported_transaction_types {
+                    None
+                } else {
+                    Some(Err(ProviderError::UnsupportedTransactionTypeInDebugTrace {
+                        requested_transaction_hash: *transaction_hash,
+                        unsupported_transaction_hash: *transaction.transaction_hash(),
+                        unsupported_transaction_type: transaction.transaction_type().into(),
+                    }))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }
+}
+
+impl<ChainSpecT, TimerT> ProviderData<ChainSpecT, TimerT>
+where
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionMut
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+{
+    /// Estimate the gas cost of a transaction. Matches Hardhat behavior.
+    pub fn estimate_gas(
+        &mut self,
+        transaction: ChainSpecT::SignedTransaction,
+        block_spec: &BlockSpec,
+    ) -> Result<EstimateGasResult<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
+    {
+        let cfg_env = self.create_evm_config_at_block_spec(block_spec)?;
+        // Minimum gas cost that is required for transaction to be included in
+        // a block
+        let minimum_cost =
+            transaction::calculate_initial_tx_gas_for_tx(&transaction, self.evm_spec_id())
+                .initial_gas;
+
+        let custom_precompiles = self.precompile_overrides.clone();
+        let mut evm_observer = EvmObserver::new(EvmObserverConfig::from(&self.observability));
+        let contract_decoder = Arc::clone(&self.contract_decoder);
+        let gas_sponsor = self.gas_sponsor_of(transaction.caller());
+
+        self.execute_in_block_context(Some(block_spec), |blockchain, block, state| {
+            let header = block.block_header();
+
+            let gas_sponsor_overrides =
+                gas_sponsor_call_overrides(gas_sponsor, state.as_ref(), &transaction)?;
+            let state = &TouchedStateCache::new(StateRefOverrider::new(
+                &gas_sponsor_overrides,
+                state.as_ref(),
+            ));
+
+            // Measure the gas used by the transaction with optional limit from call request
+            // defaulting to block limit. Report errors from initial call as if from
+            // `eth_call`.
+            let result = call::run_call::<'_, ChainSpecT, _, _, _>(
+                blockchain,
+                header,
+                state,
+                cfg_env.clone(),
+                transaction.clone(),
+                &custom_precompiles,
+                &mut evm_observer,
+            )?;
+
+            let EvmObserver {
+                code_coverage,
+                console_logger,
+                mocker: _mocker,
+                mut trace_collector,
+            } = evm_observer;
+
+            if let Some(code_coverage) = code_coverage {
+                code_coverage
+                    .report()
+                    .map_err(ProviderError::OnCollectedCoverageCallback)?;
+            }
+
+            let (mut initial_estimation, gas_refunded) = match result {
+                ExecutionResult::Success {
+                    gas_used,
+                    gas_refunded,
+                    ..
+                } => Ok((gas_used, gas_refunded)),
+                ExecutionResult::Revert { output, .. } => Err(TransactionFailure::revert(
+                    output,
+                    None,
+                    trace_collector
+                        .traces()
+                        .first()
+                        .expect("Must have a trace")
+                        .clone(),
+                )
+                .with_custom_error(&contract_decoder)),
+                ExecutionResult::Halt { reason, .. } => Err(TransactionFailure::halt(
+                    ChainSpecT::cast_halt_reason(reason),
+                    None,
+                    trace_collector
+                        .traces()
+                        .first()
+                        .expect("Must have a trace")
+                        .clone(),
+                )),
+            }
+            .map_err(|failure| {
+                Box::new(EstimateGasFailure {
+                    console_log_inputs: console_logger.into_encoded_messages(),
+                    transaction_failure: TransactionFailureWithTraces {
+                        traces: vec![failure.solidity_trace.clone()],
+                        failure,
+                    },
+                })
+            })?;
+
+            // Ensure that the initial estimation is at least the minimum cost + 1.
+            if initial_estimation <= minimum_cost {
+                initial_estimation = minimum_cost + 1;
+            }
+
+            // Test if the transaction would be successful with the initial estimation
+            let success = gas::check_gas_limit::<ChainSpecT>(CheckGasLimitArgs {
+                blockchain,
+                header,
+                state,
+                cfg_env: cfg_env.clone(),
+                transaction: transaction.clone(),
+                gas_limit: initial_estimation,
+                custom_precompiles: &custom_precompiles,
+                trace_collector: &mut trace_collector,
+            })?;
+
+            // Return the initial estimation if it was successful
+            if success {
+                return Ok(EstimateGasResult {
+                    estimation: initial_estimation,
+                    traces: trace_collector.into_traces(),
+                });
+            }
+
+            // Correct the initial estimation if the transaction failed with the actually
+            // used gas limit. This can happen if the execution logic is based
+            // on the available gas, e.g. due to the 63/64 rule of EIP-150 or gas refunds.
+            //
+            // Most transactions succeed with an optimistic gas limit that accounts for
+            // these, which narrows the range of the binary search.
+            let optimistic_estimation = cmp::min(
+                gas::optimistic_gas_limit(initial_estimation, gas_refunded),
+                header.gas_limit,
+            );
+
+            // The optimistic estimation only narrows the range of the binary search, so
+            // its trace isn't reported.
+            let upper_bound = if optimistic_estimation > initial_estimation
+                && gas::check_gas_limit::<ChainSpecT>(CheckGasLimitArgs {
+                    blockchain,
+                    header,
+                    state,
+                    cfg_env: cfg_env.clone(),
+                    transaction: transaction.clone(),
+                    gas_limit: optimistic_estimation,
+                    custom_precompiles: &custom_precompiles,
+                    trace_collector: &mut TraceCollector::new(false),
+                })? {
+                optimistic_estimation
+            } else {
+                header.gas_limit
+            };
+
+            let estimation =
+                gas::binary_search_estimation::<ChainSpecT>(BinarySearchEstimationArgs {
+                    blockchain,
+                    header,
+                    state,
+                    cfg_env: cfg_env.clone(),
+                    transaction,
+                    lower_bound: initial_estimation,
+                    upper_bound,
+                    custom_precompiles: &custom_precompiles,
+                    trace_collector: &mut trace_collector,
+                })?;
+
+            let traces = trace_collector.into_traces();
+            Ok(EstimateGasResult { estimation, traces })
+        })?
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub(crate) struct StateId(u64);
+
+impl StateId {
+    /// Increment the current state id and return the incremented id.
+    fn increment(&mut self) -> Self {
+        self.0 += 1;
+        *self
+    }
+}
+
+/// Returns state overrides that prefund the gas cost of a call from a sender
+/// sponsored by the provided sponsor, mirroring what
+/// [`ProviderData::sponsor_transaction`] does for transactions.
+fn gas_sponsor_call_overrides<StateT: State + ?Sized>(
+    sponsor: Option<Address>,
+    state: &StateT,
+    transaction: &impl ExecutableTransaction,
+) -> Result<StateOverrides, StateT::Error> {
+    let sender = *transaction.caller();
+    let Some(sponsor) = sponsor.filter(|sponsor| *sponsor != sender) else {
+        return Ok(StateOverrides::default());
+    };
+
+    let sponsor_balance = state
+        .basic(sponsor)?
+        .map_or(U256::ZERO, |account| account.balance);
+
+    let gas_cost = edr_transaction::upfront_cost(transaction)
+        .saturating_sub(*transaction.value())
+        .min(sponsor_balance);
+
+    if gas_cost.is_zero() {
+        return Ok(StateOverrides::default());
+    }
+
+    let sender_balance = state
+        .basic(sender)?
+        .map_or(U256::ZERO, |account| account.balance);
+
+    let balance_override = |balance| AccountOverride {
+        balance: Some(balance),
+        nonce: None,
+        code: None,
+        storage: None,
+    };
+
+    Ok(StateOverrides::new(
+        [
+            (sponsor, balance_override(sponsor_balance - gas_cost)),
+            (
+                sender,
+                balance_override(sender_balance.saturating_add(gas_cost)),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    ))
+}
+
+/// Returns the transaction gas cap for blocks of the provided hardfork,
+/// preferring the user-provided cap.
+fn transaction_gas_cap(cap_override: Option<u64>, hardfork: impl Into<EvmSpecId>) -> Option<u64> {
+    cap_override.or_else(|| {
+        (hardfork.into() >= EvmSpecId::OSAKA).then_some(eip7825::MAX_TX_GAS_LIMIT_OSAKA)
+    })
+}
+
+fn block_time_offset_seconds<ChainSpecT: ProviderChainSpec, TimerT: TimeSinceEpoch>(
+    config: &ProviderConfig<ChainSpecT::Hardfork>,
+    timer: &TimerT,
+) -> Result<i64, CreationErrorForChainSpec<ChainSpecT>> {
+    config.initial_date.map_or(Ok(0), |initial_date| {
+        let initial_timestamp = i64::try_from(
+            initial_date
+                .duration_since(UNIX_EPOCH)
+                .map_err(|_e| CreationError::InvalidInitialDate(initial_date))?
+                .as_secs(),
+        )
+        .expect("initial date must be representable as i64");
+
+        let current_timestamp = i64::try_from(timer.since_epoch())
+            .expect("Current timestamp must be representable as i64");
+
+        Ok(initial_timestamp - current_timestamp)
+    })
+}
+
+struct BlockchainAndState<ChainSpecT: BlockChainSpec> {
+    blockchain: Box<dyn SyncBlockchainForChainSpec<ChainSpecT>>,
+    fork_metadata: Option<ForkMetadata>,
+    rpc_client: Option<Arc<EthRpcClientForChainSpec<ChainSpecT>>>,
+    state: Box<dyn DynState>,
+    irregular_state: IrregularState,
+    prev_randao_generator: RandomHashGenerator,
+    block_time_offset_seconds: i64,
+    next_block_base_fee_per_gas: Option<u128>,
+}
+
+fn create_blockchain_and_state<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    runtime: runtime::Handle,
+    config: &ProviderConfig<ChainSpecT::Hardfork>,
+    timer: &TimerT,
+    // An existing client for the remote node, which is reused when forking.
+    rpc_client: Option<Arc<EthRpcClientForChainSpec<ChainSpecT>>>,
+) -> Result<BlockchainAndState<ChainSpecT>, CreationErrorForChainSpec<ChainSpecT>> {
+    let mut prev_randao_generator = RandomHashGenerator::with_seed(
+        config
+            .prev_randao_seed
+            .as_deref()
+            .unwrap_or(edr_defaults::MIX_HASH_SEED),
+    );
+
+    let mut genesis_overrides = config.genesis_state.clone();
+    if config.deploy_deterministic_deployment_proxy {
+        genesis_overrides
+            .entry(DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS)
+            .or_insert_with(deterministic_deployment_proxy);
+    }
+
+    if let Some(fork_config) = &config.fork {
+        let state_root_generator = Arc::new(parking_lot::Mutex::new(
+            RandomHashGenerator::with_seed(edr_defaults::STATE_ROOT_HASH_SEED),
+        ));
+
+        let rpc_client = if let Some(rpc_client) = rpc_client {
+            rpc_client
+        } else {
+            let http_headers = fork_config
+                .http_headers
+                .as_ref()
+                .map(|headers| {
+                    HeaderMap::try_from(headers).map_err(CreationError::InvalidHttpHeaders)
+                })
+                .transpose()?;
+
+            Arc::new(EthRpcClient::<
+                ChainSpecT,
+                ChainSpecT::RpcReceipt,
+                ChainSpecT::RpcTransaction,
+            >::with_transport_config(
+                &fork_config.url,
+                fork_config.cache_dir.clone(),
+                http_headers,
+                fork_config.transport.clone(),
+            )?)
+        };
+
+        let mut chain_configs = ChainSpecT::chain_configs().clone();
+        for (chain_id, chain_override) in fork_config.chain_overrides.iter() {
+            chain_configs
+                .entry(*chain_id)
+                .and_modify(|chain_config| chain_config.apply_override(chain_override))
+                .or_insert_with(|| ChainConfig {
+                    name: chain_override.name.clone(),
+                    hardfork_activations: chain_override
+                        .hardfork_activation_overrides
+                        .clone()
+                        .unwrap_or_default(),
+                    base_fee_params: config
+                        .base_fee_params
+                        .clone()
+                        .unwrap_or_else(|| ChainSpecT::default_base_fee_params().clone()),
+                });
+        }
+
+        let block_config = BlockConfig {
+            base_fee_params: ChainSpecT::default_base_fee_params(),
+            hardfork: config.hardfork,
+            min_ethash_difficulty: ChainSpecT::MIN_ETHASH_DIFFICULTY,
+        };
+
+        let (blockchain, mut irregular_state) = tokio::task::block_in_place(
+            || -> Result<_, ForkedCreationError<ChainSpecT::Hardfork>> {
+                let mut irregular_state = IrregularState::default();
+                let blockchain =
+                    runtime.block_on(ForkedBlockchainForChainSpec::<ChainSpecT>::new(
+                        block_config,
+                        runtime.clone(),
+                        rpc_client.clone(),
+                        &mut irregular_state,
+                        state_root_generator.clone(),
+                        &chain_configs,
+                        fork_config.block_number,
+                        Some(config.chain_id),
+                    ))?;
+
+                Ok((blockchain, irregular_state))
+            },
+        )?;
+
+        let fork_block_number = blockchain.last_block_number();
+
+        if !genesis_overrides.is_empty() {
+            let genesis_addresses = genesis_overrides.keys().cloned().collect::<Vec<_>>();
+            let genesis_account_infos = tokio::task::block_in_place(|| {
+                runtime.block_on(rpc_client.get_account_infos(
+                    &genesis_addresses,
+                    Some(BlockSpec::Number(fork_block_number)),
+                ))
+            })?;
+
+            let genesis_state: HashMap<Address, Account> = genesis_overrides
+                .iter()
+                .zip(genesis_account_infos)
+                .map(|((address, account_override), remote_account)| {
+                    let (code, code_hash) = account_override.code.as_ref().map_or(
+                        (remote_account.code, remote_account.code_hash),
+                        |code| {
+                            let code_hash = code.hash_slow();
+                            (Some(code.clone()), code_hash)
+                        },
+                    );
+
+                    let info = AccountInfo {
+                        balance: account_override.balance.unwrap_or(remote_account.balance),
+                        nonce: account_override.nonce.unwrap_or(remote_account.nonce),
+                        code_hash,
+                        code,
+                    };
+
+                    // TODO: Add support for overriding the storage
+                    // TODO: https://github.com/NomicFoundation/edr/issues/911
+                    if account_override.storage.is_some() {
+                        return Err(ForkedCreationError::StorageOverridesUnsupported);
+                    }
+
+                    let account = Account {
+                        info,
+                        // TODO: Add support for overriding the storage
+                        // TODO: https://github.com/NomicFoundation/edr/issues/911
+                        storage: HashMap::default(),
+                        status: AccountStatus::Created | AccountStatus::Touched,
+                        transaction_id: 0,
+                    };
+
+                    Ok((*address, account))
+                })
+                .collect::<Result<_, _>>()?;
+
+            irregular_state
+                .state_override_at_block_number(fork_block_number)
+                .and_modify(|state_override| {
+                    // No need to update the state_root, as it could only have been created by the
+                    // `ForkedBlockchain` constructor.
+                    state_override.diff.apply_diff(genesis_state.clone());
+                })
+                .or_insert_with(|| {
+                    let state_root = state_root_generator.lock().next_value();
+
+                    StateOverride {
+                        diff: StateDiff::from(genesis_state),
+                        storage_roots: HashMap::default(),
+                        state_root,
+                    }
+                });
+        }
+
+        let state = blockchain
+            .state_at_block_number(fork_block_number, irregular_state.state_overrides())
+            .expect("Fork state must exist");
+
+        let block_time_offset_seconds = {
+            let fork_block_timestamp = UNIX_EPOCH
+                + Duration::from_secs(
+                    blockchain
+                        .last_block()
+                        .map_err(DynBlockchainError::new)?
+                        .block_header()
+                        .timestamp,
+                );
+
+            let elapsed = match timer.since(fork_block_timestamp) {
+                Ok(elapsed) => -i128::from(elapsed),
+                Err(forward_drift) => i128::from(forward_drift.duration().as_secs()),
+            };
+
+            elapsed
+                .try_into()
+                .expect("Elapsed time since fork block must be representable as i64")
+        };
+
+        let next_block_base_fee_per_gas = if config.hardfork.into() >= EvmSpecId::LONDON {
+            if let Some(base_fee) = config.initial_base_fee_per_gas {
+                Some(base_fee)
+            } else {
+                let previous_base_fee = blockchain
+                    .last_block()
+                    .map_err(DynBlockchainError::new)?
+                    .block_header()
+                    .base_fee_per_gas;
 
                 if previous_base_fee.is_none() {
                     Some(DEFAULT_INITIAL_BASE_FEE_PER_GAS)
@@ -3015,6 +6470,7 @@ fn create_blockchain_and_state<
                     .map_err(DynBlockchainError::new)?
                     .expect("Fork block must exist")
                     .block_hash(),
+                fork_url_hash: keccak256(fork_config.url.as_bytes()),
             }),
             rpc_client: Some(rpc_client),
             blockchain: Box::new(DynBlockchain::new(blockchain)),
@@ -3025,253 +6481,848 @@ fn create_blockchain_and_state<
             next_block_base_fee_per_gas,
         })
     } else {
-        let mix_hash = if config.hardfork.into() >= EvmSpecId::MERGE {
+        // An activation at block zero determines the hardfork of the genesis block
+        let genesis_hardfork = config
+            .hardfork_activations
+            .get(&0)
+            .copied()
+            .unwrap_or(config.hardfork);
+
+        let mix_hash = if genesis_hardfork.into() >= EvmSpecId::MERGE {
             Some(prev_randao_generator.generate_next())
         } else {
             None
         };
 
-        let genesis_state: HashMap<Address, Account> = config
-            .genesis_state
-            .iter()
-            .map(|(address, account_override)| {
-                let code_hash = account_override
-                    .code
-                    .as_ref()
-                    .map_or(KECCAK_EMPTY, Bytecode::hash_slow);
+        let mut genesis_state: HashMap<Address, Account> = genesis_overrides
+            .iter()
+            .map(|(address, account_override)| {
+                let code_hash = account_override
+                    .code
+                    .as_ref()
+                    .map_or(KECCAK_EMPTY, Bytecode::hash_slow);
+
+                let info = AccountInfo {
+                    balance: account_override.balance.unwrap_or(U256::ZERO),
+                    nonce: account_override.nonce.unwrap_or(0),
+                    code_hash,
+                    code: account_override.code.clone(),
+                };
+
+                let account = Account {
+                    info,
+                    storage: account_override
+                        .storage
+                        .clone()
+                        .unwrap_or(HashMap::default()),
+                    status: AccountStatus::Created | AccountStatus::Touched,
+                    transaction_id: 0,
+                };
+
+                (*address, account)
+            })
+            .collect();
+
+        // EIP-4788 and EIP-2935: system contracts are deployed in genesis, unless
+        // they were explicitly overridden. Like on mainnet, they're also deployed
+        // ahead of a hardfork that is activated after genesis.
+        let evm_spec_id = config.hardfork_activations.values().fold(
+            config.hardfork.into(),
+            |evm_spec_id: EvmSpecId, hardfork| evm_spec_id.max((*hardfork).into()),
+        );
+        let system_contracts = [
+            (
+                EvmSpecId::CANCUN,
+                BEACON_ROOTS_ADDRESS,
+                beacon_roots_contract as fn() -> AccountInfo,
+            ),
+            (
+                EvmSpecId::PRAGUE,
+                HISTORY_STORAGE_ADDRESS,
+                history_storage_contract,
+            ),
+        ];
+        for (activation, address, contract) in system_contracts {
+            if evm_spec_id >= activation {
+                genesis_state.entry(address).or_insert_with(|| Account {
+                    info: contract(),
+                    storage: HashMap::default(),
+                    status: AccountStatus::Created | AccountStatus::Touched,
+                    transaction_id: 0,
+                });
+            }
+        }
+
+        let base_fee_params = config.base_fee_params.as_ref().unwrap_or_else(|| {
+            ChainSpecT::chain_configs()
+                .get(&config.chain_id)
+                .map_or_else(ChainSpecT::default_base_fee_params, |chain_config| {
+                    &chain_config.base_fee_params
+                })
+        });
+
+        let block_config = BlockConfig {
+            base_fee_params,
+            hardfork: genesis_hardfork,
+            min_ethash_difficulty: ChainSpecT::MIN_ETHASH_DIFFICULTY,
+        };
+
+        let genesis_diff = StateDiff::from(genesis_state);
+        let genesis_block = ChainSpecT::genesis_block(
+            genesis_diff.clone(),
+            block_config.clone(),
+            GenesisBlockOptions {
+                extra_data: config.genesis_extra_data.clone(),
+                withdrawals_root: None,
+                gas_limit: Some(
+                    config
+                        .block_gas_limit_schedule
+                        .get(&0)
+                        .unwrap_or(&config.block_gas_limit)
+                        .get(),
+                ),
+                timestamp: config.initial_date.map(|d| {
+                    d.duration_since(UNIX_EPOCH)
+                        .expect("initial date must be after UNIX epoch")
+                        .as_secs()
+                }),
+                mix_hash,
+                base_fee: config.initial_base_fee_per_gas,
+                base_fee_params: config.base_fee_params.clone(),
+                blob_gas: config.initial_blob_gas.clone(),
+            },
+        )
+        .map_err(CreationError::LocalBlockchainCreation)?;
+
+        let blockchain = LocalBlockchainForChainSpec::<ChainSpecT>::new(
+            genesis_block,
+            genesis_diff,
+            config.chain_id,
+            block_config,
+        )
+        .map_err(CreationError::InvalidGenesisBlock)?
+        .with_hardfork_activations(config.hardfork_activations.clone());
+
+        let irregular_state = IrregularState::default();
+        let state = blockchain
+            .state_at_block_number(0, irregular_state.state_overrides())
+            .expect("Genesis state must exist");
+
+        let block_time_offset_seconds =
+            block_time_offset_seconds::<ChainSpecT, TimerT>(config, timer)?;
+
+        Ok(BlockchainAndState {
+            fork_metadata: None,
+            rpc_client: None,
+            blockchain: Box::new(DynBlockchain::new(blockchain)),
+            state,
+            irregular_state,
+            block_time_offset_seconds,
+            prev_randao_generator,
+            // For local blockchain the initial base fee per gas config option is incorporated as
+            // part of the genesis block.
+            next_block_base_fee_per_gas: None,
+        })
+    }
+}
+
+fn get_skip_unsupported_transaction_types_from_env() -> bool {
+    std::env::var(EDR_UNSAFE_SKIP_UNSUPPORTED_TRANSACTION_TYPES)
+        .map_or(DEFAULT_SKIP_UNSUPPORTED_TRANSACTION_TYPES, |s| s == "true")
+}
+
+fn get_max_cached_states_from_env<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>() -> Result<NonZeroUsize, CreationErrorForChainSpec<ChainSpecT>> {
+    std::env::var(EDR_MAX_CACHED_STATES_ENV_VAR).map_or_else(
+        |err| match err {
+            std::env::VarError::NotPresent => {
+                Ok(NonZeroUsize::new(DEFAULT_MAX_CACHED_STATES).expect("constant is non-zero"))
+            }
+            std::env::VarError::NotUnicode(s) => Err(CreationError::InvalidMaxCachedStates(s)),
+        },
+        |s| {
+            s.parse()
+                .map_err(|_err| CreationError::InvalidMaxCachedStates(s.into()))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+    use edr_block_miner::{MineOrderingCallbackError, PendingTransactionSummary};
+    use edr_chain_l1::L1ChainSpec;
+    use edr_mem_pool::MemPoolAddTransactionError;
+    use edr_primitives::{address, hex};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        console_log::tests::{deploy_console_log_contract, ConsoleLogTransaction},
+        logger::NoopLogger,
+        requests::hardhat::rpc_types::ValueChange,
+        test_utils::{create_test_config, one_ether, ProviderTestFixture},
+        MemPoolConfig, MiningConfig, ProviderConfig,
+    };
+
+    #[test]
+    fn test_local_account_balance() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let account = *fixture
+            .provider_data
+            .local_accounts
+            .keys()
+            .next()
+            .expect("there are local accounts");
+
+        let last_block_number = fixture.provider_data.last_block_number();
+        let block_spec = BlockSpec::Number(last_block_number);
+
+        let balance = fixture.provider_data.balance(account, Some(&block_spec))?;
+
+        assert_eq!(balance, one_ether());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dump_state_includes_storage_indices() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let address = Address::random();
+        fixture
+            .provider_data
+            .set_account_storage_slot(address, U256::from(1), U256::from(42))?;
+
+        let dump = fixture.provider_data.dump_state()?;
+        let account = dump
+            .accounts
+            .get(&address)
+            .expect("account should be dumped");
+
+        assert_eq!(account.storage.len(), 1);
+        assert_eq!(account.storage.get(&U256::from(1)), Some(&U256::from(42)));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "test-remote")]
+    #[test]
+    fn dump_state_overlays_local_storage_on_fetched_storage_forked() -> anyhow::Result<()> {
+        const WETH_ADDRESS: Address = address!("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_forked(None)?;
+
+        // Slots 0 and 1 store the token's name and symbol
+        let name = fixture
+            .provider_data
+            .get_storage_at(WETH_ADDRESS, U256::ZERO, None)?;
+        assert_ne!(name, U256::ZERO);
+
+        let symbol = fixture
+            .provider_data
+            .get_storage_at(WETH_ADDRESS, U256::from(1), None)?;
+        assert_ne!(symbol, U256::ZERO);
+
+        fixture
+            .provider_data
+            .set_account_storage_slot(WETH_ADDRESS, U256::from(1), U256::ZERO)?;
+        fixture.provider_data.set_account_storage_slot(
+            WETH_ADDRESS,
+            U256::from(100),
+            U256::from(42),
+        )?;
+
+        let dump = fixture.provider_data.dump_state()?;
+        let account = dump
+            .accounts
+            .get(&WETH_ADDRESS)
+            .expect("account should be dumped");
+
+        assert!(!account.code.is_empty());
+        assert_eq!(account.storage.get(&U256::ZERO), Some(&name));
+        assert_eq!(account.storage.get(&U256::from(1)), None);
+        assert_eq!(account.storage.get(&U256::from(100)), Some(&U256::from(42)));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "test-remote")]
+    #[test]
+    fn test_local_account_balance_forked() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_forked(None)?;
+
+        let account = *fixture
+            .provider_data
+            .local_accounts
+            .keys()
+            .next()
+            .expect("there are local accounts");
+
+        let last_block_number = fixture.provider_data.last_block_number();
+        let block_spec = BlockSpec::Number(last_block_number);
+
+        let balance = fixture.provider_data.balance(account, Some(&block_spec))?;
+
+        assert_eq!(balance, one_ether());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_transaction_request() -> anyhow::Result<()> {
+        let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
+        let recovered_address = transaction.caller();
+
+        assert!(fixture
+            .provider_data
+            .local_accounts
+            .contains_key(recovered_address));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_transaction_request_impersonated_account() -> anyhow::Result<()> {
+        let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let transaction = fixture.impersonated_dummy_transaction()?;
+
+        assert_eq!(transaction.caller(), &fixture.impersonated_account);
+
+        Ok(())
+    }
+
+    fn test_add_pending_transaction(
+        fixture: &mut ProviderTestFixture<L1ChainSpec>,
+        transaction: edr_chain_l1::L1SignedTransaction,
+    ) -> anyhow::Result<()> {
+        let filter_id = fixture
+            .provider_data
+            .add_pending_transaction_filter::<false>();
+
+        let transaction_hash = fixture.provider_data.add_pending_transaction(transaction)?;
+
+        assert!(fixture
+            .provider_data
+            .mem_pool
+            .transaction_by_hash(&transaction_hash)
+            .is_some());
+
+        match fixture
+            .provider_data
+            .get_filter_changes(&filter_id)
+            .unwrap()
+        {
+            FilteredEvents::NewPendingTransactions(hashes) => {
+                assert!(hashes.contains(&transaction_hash));
+            }
+            _ => panic!("expected pending transaction"),
+        };
+
+        assert!(fixture.provider_data.mem_pool.has_pending_transactions());
 
-                let info = AccountInfo {
-                    balance: account_override.balance.unwrap_or(U256::ZERO),
-                    nonce: account_override.nonce.unwrap_or(0),
-                    code_hash,
-                    code: account_override.code.clone(),
-                };
+        Ok(())
+    }
 
-                let account = Account {
-                    info,
-                    storage: account_override
-                        .storage
-                        .clone()
-                        .unwrap_or(HashMap::default()),
-                    status: AccountStatus::Created | AccountStatus::Touched,
-                    transaction_id: 0,
-                };
+    #[test]
+    fn block_filter_discards_reverted_blocks() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
 
-                (*address, account)
+        let filter_id = fixture.provider_data.add_block_filter::<false>()?;
+        // Consume the hash of the block that was latest when the filter was installed
+        fixture.provider_data.get_filter_changes(&filter_id);
+
+        let snapshot_id = fixture.provider_data.make_snapshot();
+        fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert!(fixture.provider_data.revert_to_snapshot(snapshot_id)?);
+
+        match fixture.provider_data.get_filter_changes(&filter_id) {
+            Some(FilteredEvents::NewHeads(block_hashes)) => assert!(block_hashes.is_empty()),
+            _ => panic!("expected new heads"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn polling_filters_expire_on_inactivity() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let filter_id = fixture.provider_data.add_block_filter::<false>()?;
+        let subscription_id = fixture.provider_data.add_block_filter::<true>()?;
+
+        for filter in fixture.provider_data.filters.values_mut() {
+            filter.deadline = Instant::now();
+        }
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(fixture
+            .provider_data
+            .get_filter_changes(&filter_id)
+            .is_none());
+        // Subscriptions push their events, so they don't expire
+        assert!(fixture.provider_data.filters.contains_key(&subscription_id));
+
+        Ok(())
+    }
+
+    #[test]
+    fn polling_filter_deadline_is_refreshed() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let filter_id = fixture.provider_data.add_block_filter::<false>()?;
+        let deadline = Instant::now() + Duration::from_secs(1);
+        fixture
+            .provider_data
+            .filters
+            .get_mut(&filter_id)
+            .expect("filter was just added")
+            .deadline = deadline;
+
+        assert!(fixture
+            .provider_data
+            .get_filter_changes(&filter_id)
+            .is_some());
+        assert!(fixture.provider_data.filters[&filter_id].deadline > deadline);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reorg_reincludes_transactions_that_are_not_dropped() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let initial_block_number = fixture.provider_data.last_block_number();
+
+        let transaction1 = fixture.signed_dummy_transaction(0, None)?;
+        let transaction2 = fixture.signed_dummy_transaction(1, None)?;
+        let transaction_hash1 = fixture
+            .provider_data
+            .send_transaction(transaction1)?
+            .transaction_hash;
+        let transaction_hash2 = fixture
+            .provider_data
+            .send_transaction(transaction2)?
+            .transaction_hash;
+
+        let replaced_block_hash = *fixture.provider_data.last_block()?.block_hash();
+
+        let result = fixture.provider_data.reorg(3, 1, &HashSet::default());
+        assert!(matches!(
+            result,
+            Err(ProviderError::InvalidReorgDepth {
+                depth: 3,
+                max_depth: 2
             })
-            .collect();
+        ));
 
-        let base_fee_params = config.base_fee_params.as_ref().unwrap_or_else(|| {
-            ChainSpecT::chain_configs()
-                .get(&config.chain_id)
-                .map_or_else(ChainSpecT::default_base_fee_params, |chain_config| {
-                    &chain_config.base_fee_params
+        let mined_blocks =
+            fixture
+                .provider_data
+                .reorg(2, 1, &[transaction_hash1].into_iter().collect())?;
+        assert_eq!(mined_blocks.len(), 1);
+
+        let new_block = fixture.provider_data.last_block()?;
+        assert_eq!(
+            fixture.provider_data.last_block_number(),
+            initial_block_number + 1
+        );
+        assert_ne!(*new_block.block_hash(), replaced_block_hash);
+        assert_eq!(
+            new_block
+                .transactions()
+                .iter()
+                .map(|transaction| *transaction.transaction_hash())
+                .collect::<Vec<_>>(),
+            vec![transaction_hash2]
+        );
+
+        assert!(fixture
+            .provider_data
+            .transaction_receipt(&transaction_hash1)?
+            .is_none());
+        assert_eq!(fixture.provider_data.pending_transactions().count(), 0);
+
+        Ok(())
+    }
+
+    /// Returns the provided blocks of the provider's chain as raw blocks.
+    fn raw_blocks(
+        provider_data: &ProviderData<L1ChainSpec>,
+        block_numbers: RangeInclusive<u64>,
+    ) -> anyhow::Result<Vec<RawBlock<edr_chain_l1::L1SignedTransaction>>> {
+        block_numbers
+            .map(|block_number| {
+                let block = provider_data
+                    .block_by_block_spec(&BlockSpec::Number(block_number))?
+                    .context("block not found")?;
+
+                Ok(RawBlock {
+                    header: block.block_header().clone(),
+                    transactions: block.transactions().to_vec(),
+                    ommers: Vec::new(),
+                    withdrawals: block.withdrawals().map(<[Withdrawal]>::to_vec),
                 })
-        });
+            })
+            .collect()
+    }
+
+    /// Creates a provider with the same genesis block as the fixture's
+    /// provider.
+    fn create_importing_provider_data(
+        fixture: &ProviderTestFixture<L1ChainSpec>,
+    ) -> anyhow::Result<ProviderData<L1ChainSpec>> {
+        let provider_data = ProviderData::new(
+            fixture.provider_data.runtime().clone(),
+            Box::<NoopLogger<L1ChainSpec, CurrentTime>>::default(),
+            Box::new(|_| ()),
+            fixture.config.clone(),
+            Arc::<ContractDecoder>::default(),
+            CurrentTime,
+        )?;
 
-        let block_config = BlockConfig {
-            base_fee_params,
-            hardfork: config.hardfork,
-            min_ethash_difficulty: ChainSpecT::MIN_ETHASH_DIFFICULTY,
-        };
+        Ok(provider_data)
+    }
 
-        let genesis_diff = StateDiff::from(genesis_state);
-        let genesis_block = ChainSpecT::genesis_block(
-            genesis_diff.clone(),
-            block_config.clone(),
-            GenesisBlockOptions {
-                extra_data: None,
-                withdrawals_root: None,
-                gas_limit: Some(config.block_gas_limit.get()),
-                timestamp: config.initial_date.map(|d| {
-                    d.duration_since(UNIX_EPOCH)
-                        .expect("initial date must be after UNIX epoch")
-                        .as_secs()
-                }),
-                mix_hash,
-                base_fee: config.initial_base_fee_per_gas,
-                base_fee_params: config.base_fee_params.clone(),
-                blob_gas: config.initial_blob_gas.clone(),
-            },
-        )
-        .map_err(CreationError::LocalBlockchainCreation)?;
+    #[test]
+    fn set_head_rewinds_local_blocks() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let initial_block_number = fixture.provider_data.last_block_number();
 
-        let blockchain = LocalBlockchainForChainSpec::<ChainSpecT>::new(
-            genesis_block,
-            genesis_diff,
-            config.chain_id,
-            block_config,
-        )
-        .map_err(CreationError::InvalidGenesisBlock)?;
+        let transaction1 = fixture.signed_dummy_transaction(0, None)?;
+        let transaction2 = fixture.signed_dummy_transaction(1, None)?;
+        fixture.provider_data.send_transaction(transaction1)?;
+        let transaction_hash2 = fixture
+            .provider_data
+            .send_transaction(transaction2)?
+            .transaction_hash;
 
-        let irregular_state = IrregularState::default();
-        let state = blockchain
-            .state_at_block_number(0, irregular_state.state_overrides())
-            .expect("Genesis state must exist");
+        let result = fixture.provider_data.set_head(initial_block_number + 3);
+        assert!(matches!(
+            result,
+            Err(ProviderError::InvalidHeadBlockNumber {
+                block_number,
+                first_local_block_number: 0,
+                latest_block_number,
+            }) if block_number == initial_block_number + 3
+                && latest_block_number == initial_block_number + 2
+        ));
 
-        let block_time_offset_seconds =
-            block_time_offset_seconds::<ChainSpecT, TimerT>(config, timer)?;
+        fixture.provider_data.set_head(initial_block_number + 1)?;
+        assert_eq!(
+            fixture.provider_data.last_block_number(),
+            initial_block_number + 1
+        );
+        assert!(fixture
+            .provider_data
+            .transaction_receipt(&transaction_hash2)?
+            .is_none());
 
-        Ok(BlockchainAndState {
-            fork_metadata: None,
-            rpc_client: None,
-            blockchain: Box::new(DynBlockchain::new(blockchain)),
-            state,
-            irregular_state,
-            block_time_offset_seconds,
-            prev_randao_generator,
-            // For local blockchain the initial base fee per gas config option is incorporated as
-            // part of the genesis block.
-            next_block_base_fee_per_gas: None,
-        })
+        Ok(())
     }
-}
 
-fn get_skip_unsupported_transaction_types_from_env() -> bool {
-    std::env::var(EDR_UNSAFE_SKIP_UNSUPPORTED_TRANSACTION_TYPES)
-        .map_or(DEFAULT_SKIP_UNSUPPORTED_TRANSACTION_TYPES, |s| s == "true")
-}
+    #[test]
+    fn import_raw_blocks_into_separate_chain() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let initial_block_number = fixture.provider_data.last_block_number();
 
-fn get_max_cached_states_from_env<
-    ChainSpecT: ProviderSpec<TimerT>,
-    TimerT: Clone + TimeSinceEpoch,
->() -> Result<NonZeroUsize, CreationErrorForChainSpec<ChainSpecT>> {
-    std::env::var(EDR_MAX_CACHED_STATES_ENV_VAR).map_or_else(
-        |err| match err {
-            std::env::VarError::NotPresent => {
-                Ok(NonZeroUsize::new(DEFAULT_MAX_CACHED_STATES).expect("constant is non-zero"))
-            }
-            std::env::VarError::NotUnicode(s) => Err(CreationError::InvalidMaxCachedStates(s)),
-        },
-        |s| {
-            s.parse()
-                .map_err(|_err| CreationError::InvalidMaxCachedStates(s.into()))
-        },
-    )
-}
+        let transaction1 = fixture.signed_dummy_transaction(0, None)?;
+        let transaction2 = fixture.signed_dummy_transaction(1, None)?;
+        let transaction_hash1 = fixture
+            .provider_data
+            .send_transaction(transaction1)?
+            .transaction_hash;
+        let transaction_hash2 = fixture
+            .provider_data
+            .send_transaction(transaction2)?
+            .transaction_hash;
 
-#[cfg(test)]
-mod tests {
-    use anyhow::Context;
-    use edr_block_miner::MineOrdering;
-    use edr_chain_l1::L1ChainSpec;
-    use edr_primitives::hex;
-    use serde_json::json;
+        let blocks = raw_blocks(
+            &fixture.provider_data,
+            initial_block_number + 1..=initial_block_number + 2,
+        )?;
+        let encoded = blocks
+            .iter()
+            .flat_map(alloy_rlp::encode)
+            .collect::<Vec<_>>();
 
-    use super::*;
-    use crate::{
-        console_log::tests::{deploy_console_log_contract, ConsoleLogTransaction},
-        test_utils::{create_test_config, one_ether, ProviderTestFixture},
-        MemPoolConfig, MiningConfig, ProviderConfig,
-    };
+        let mut importer = create_importing_provider_data(&fixture)?;
+        importer.import_raw_blocks(RawBlock::decode_sequence(&encoded)?)?;
+
+        assert_eq!(importer.last_block_number(), initial_block_number + 2);
+        assert_eq!(
+            importer.last_block()?.block_hash(),
+            fixture.provider_data.last_block()?.block_hash()
+        );
+
+        // The transactions were executed by the importing provider
+        assert!(importer.transaction_receipt(&transaction_hash1)?.is_some());
+        assert!(importer.transaction_receipt(&transaction_hash2)?.is_some());
+
+        let sender = fixture.nth_local_account(0)?;
+        assert_eq!(
+            importer.balance(sender, None)?,
+            fixture.provider_data.balance(sender, None)?
+        );
+        assert_eq!(
+            importer.balance(Address::ZERO, None)?,
+            fixture.provider_data.balance(Address::ZERO, None)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_raw_blocks_rejects_proof_of_work_after_merge() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        assert!(fixture.provider_data.evm_spec_id() >= EvmSpecId::MERGE);
+
+        let initial_block_number = fixture.provider_data.last_block_number();
+
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
+        fixture.provider_data.send_transaction(transaction)?;
+
+        let mut blocks = raw_blocks(
+            &fixture.provider_data,
+            initial_block_number + 1..=initial_block_number + 1,
+        )?;
+        blocks[0].header.difficulty = U256::from(1);
+
+        let mut importer = create_importing_provider_data(&fixture)?;
+
+        let result = importer.import_raw_blocks(blocks);
+        assert!(
+            matches!(
+                result,
+                Err(ProviderError::RawBlockImport(
+                    RawBlockImportError::InvalidHeader { block_number, .. }
+                )) if block_number == initial_block_number + 1
+            ),
+            "{result:?}"
+        );
+        assert_eq!(importer.last_block_number(), initial_block_number);
+
+        Ok(())
+    }
 
     #[test]
-    fn test_local_account_balance() -> anyhow::Result<()> {
+    fn import_raw_blocks_reverts_on_mismatch() -> anyhow::Result<()> {
         let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let initial_block_number = fixture.provider_data.last_block_number();
 
-        let account = *fixture
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
+        fixture.provider_data.send_transaction(transaction)?;
+        fixture
             .provider_data
-            .local_accounts
-            .keys()
-            .next()
-            .expect("there are local accounts");
+            .mine_and_commit_block(fixture.provider_data.header_overrides())?;
 
-        let last_block_number = fixture.provider_data.last_block_number();
-        let block_spec = BlockSpec::Number(last_block_number);
+        let mut blocks = raw_blocks(
+            &fixture.provider_data,
+            initial_block_number + 1..=initial_block_number + 2,
+        )?;
 
-        let balance = fixture.provider_data.balance(account, Some(&block_spec))?;
+        blocks[1].header.gas_used += 1;
 
-        assert_eq!(balance, one_ether());
+        fixture.provider_data.set_head(initial_block_number)?;
+
+        let result = fixture.provider_data.import_raw_blocks(blocks);
+        assert!(matches!(
+            result,
+            Err(ProviderError::RawBlockImport(
+                RawBlockImportError::HeaderMismatch {
+                    block_number,
+                    field: "gas used",
+                    ..
+                }
+            )) if block_number == initial_block_number + 2
+        ));
+
+        // The first block was imported successfully, but is reverted
+        assert_eq!(
+            fixture.provider_data.last_block_number(),
+            initial_block_number
+        );
 
         Ok(())
     }
 
-    #[cfg(feature = "test-remote")]
     #[test]
-    fn test_local_account_balance_forked() -> anyhow::Result<()> {
-        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_forked(None)?;
+    fn add_pending_transaction() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
 
-        let account = *fixture
+        test_add_pending_transaction(&mut fixture, transaction)
+    }
+
+    #[test]
+    fn set_mem_pool_policy_limits_sender_transactions() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        fixture.provider_data.set_auto_mining(false);
+
+        let transaction1 = fixture.signed_dummy_transaction(0, Some(0))?;
+        let transaction2 = fixture.signed_dummy_transaction(0, Some(1))?;
+        fixture
             .provider_data
-            .local_accounts
-            .keys()
-            .next()
-            .expect("there are local accounts");
+            .send_transaction(transaction1.clone())?;
+        fixture.provider_data.send_transaction(transaction2)?;
 
-        let last_block_number = fixture.provider_data.last_block_number();
-        let block_spec = BlockSpec::Number(last_block_number);
+        fixture.provider_data.set_mem_pool_policy(MemPoolPolicy {
+            max_transactions_per_sender: NonZeroUsize::new(1),
+            ..MemPoolPolicy::default()
+        })?;
 
-        let balance = fixture.provider_data.balance(account, Some(&block_spec))?;
+        let pending_transactions = fixture
+            .provider_data
+            .pending_transactions()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(pending_transactions, vec![transaction1]);
 
-        assert_eq!(balance, one_ether());
+        let transaction3 = fixture.signed_dummy_transaction(0, Some(1))?;
+        let result = fixture.provider_data.send_transaction(transaction3);
+        assert!(matches!(
+            result,
+            Err(ProviderError::MemPoolAddTransaction(
+                MemPoolAddTransactionError::SenderTransactionLimitReached { .. }
+            ))
+        ));
 
         Ok(())
     }
 
     #[test]
-    fn test_sign_transaction_request() -> anyhow::Result<()> {
-        let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
-
-        let transaction = fixture.signed_dummy_transaction(0, None)?;
-        let recovered_address = transaction.caller();
+    fn auto_mine_rejects_future_transactions_by_default() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
 
-        assert!(fixture
-            .provider_data
-            .local_accounts
-            .contains_key(recovered_address));
+        let transaction = fixture.signed_dummy_transaction(0, Some(1))?;
+        let result = fixture.provider_data.send_transaction(transaction);
+        assert!(matches!(
+            result,
+            Err(ProviderError::AutoMineNonceTooHigh {
+                expected: 0,
+                actual: 1
+            })
+        ));
 
         Ok(())
     }
 
     #[test]
-    fn test_sign_transaction_request_impersonated_account() -> anyhow::Result<()> {
-        let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+    fn auto_mine_queues_future_transactions_until_nonce_gap_is_filled() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        fixture.provider_data.set_mem_pool_policy(MemPoolPolicy {
+            queue_future_transactions: true,
+            ..MemPoolPolicy::default()
+        })?;
 
-        let transaction = fixture.impersonated_dummy_transaction()?;
+        let transaction2 = fixture.signed_dummy_transaction(0, Some(2))?;
+        let result = fixture
+            .provider_data
+            .send_transaction(transaction2.clone())?;
+        assert!(result.mining_results.is_empty());
 
-        assert_eq!(transaction.caller(), &fixture.impersonated_account);
+        let transaction1 = fixture.signed_dummy_transaction(0, Some(1))?;
+        let result = fixture
+            .provider_data
+            .send_transaction(transaction1.clone())?;
+        assert!(result.mining_results.is_empty());
+
+        assert_eq!(fixture.provider_data.executable_transactions().count(), 0);
+        assert_eq!(fixture.provider_data.queued_transactions().count(), 2);
+
+        // Filling the nonce gap promotes and mines the queued transactions
+        let transaction0 = fixture.signed_dummy_transaction(0, Some(0))?;
+        let result = fixture
+            .provider_data
+            .send_transaction(transaction0.clone())?;
+
+        let mined_transactions = result
+            .mining_results
+            .iter()
+            .flat_map(|result| result.block.transactions().iter().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mined_transactions,
+            vec![transaction0, transaction1, transaction2]
+        );
+
+        assert_eq!(fixture.provider_data.queued_transactions().count(), 0);
+        assert_eq!(fixture.provider_data.pending_transactions().count(), 0);
 
         Ok(())
     }
 
-    fn test_add_pending_transaction(
-        fixture: &mut ProviderTestFixture<L1ChainSpec>,
-        transaction: edr_chain_l1::L1SignedTransaction,
-    ) -> anyhow::Result<()> {
-        let filter_id = fixture
-            .provider_data
-            .add_pending_transaction_filter::<false>();
+    #[test]
+    fn mine_ordering_callback_overrides_mine_ordering() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        fixture.provider_data.set_auto_mining(false);
 
-        let transaction_hash = fixture.provider_data.add_pending_transaction(transaction)?;
+        let transaction1 = fixture.signed_dummy_transaction(0, Some(0))?;
+        let transaction2 = fixture.signed_dummy_transaction(1, Some(0))?;
+        let transaction3 = fixture.signed_dummy_transaction(0, Some(1))?;
+        for transaction in [&transaction1, &transaction2, &transaction3] {
+            fixture
+                .provider_data
+                .send_transaction(transaction.clone())?;
+        }
 
-        assert!(fixture
+        // Reverse the insertion order. The sender's nonce order is preserved.
+        fixture
             .provider_data
-            .mem_pool
-            .transaction_by_hash(&transaction_hash)
-            .is_some());
+            .set_mine_ordering_callback(Some(Arc::new(
+                |transactions: &[PendingTransactionSummary]| {
+                    Ok(transactions
+                        .iter()
+                        .rev()
+                        .map(|transaction| transaction.transaction_hash)
+                        .collect())
+                },
+            )));
 
-        match fixture
+        let result = fixture
             .provider_data
-            .get_filter_changes(&filter_id)
-            .unwrap()
-        {
-            FilteredEvents::NewPendingTransactions(hashes) => {
-                assert!(hashes.contains(&transaction_hash));
-            }
-            _ => panic!("expected pending transaction"),
-        };
+            .mine_and_commit_block(HeaderOverrides::default())?;
 
-        assert!(fixture.provider_data.mem_pool.has_pending_transactions());
+        assert_eq!(
+            result.block.transactions(),
+            &[transaction2, transaction1, transaction3]
+        );
 
         Ok(())
     }
 
     #[test]
-    fn add_pending_transaction() -> anyhow::Result<()> {
+    fn failing_mine_ordering_callback_fails_mining() -> anyhow::Result<()> {
         let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        fixture.provider_data.set_auto_mining(false);
+
         let transaction = fixture.signed_dummy_transaction(0, None)?;
+        fixture.provider_data.send_transaction(transaction)?;
 
-        test_add_pending_transaction(&mut fixture, transaction)
+        fixture
+            .provider_data
+            .set_mine_ordering_callback(Some(Arc::new(
+                |_transactions: &[PendingTransactionSummary]| {
+                    Err(MineOrderingCallbackError("Callback threw".to_string()))
+                },
+            )));
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default());
+        assert!(result.is_err());
+
+        // The transaction remains pending
+        assert_eq!(fixture.provider_data.pending_transactions().count(), 1);
+
+        Ok(())
     }
 
     #[test]
@@ -3399,60 +7450,296 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "test-remote")]
+    #[test]
+    fn update_fork_preserves_local_modifications() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_forked(None)?;
+
+        let fork_block_number = fixture
+            .provider_data
+            .fork_metadata()
+            .expect("fork metadata should exist")
+            .fork_block_number;
+
+        let address = Address::random();
+        fixture
+            .provider_data
+            .set_balance(address, U256::from(1_000u64))?;
+
+        let target_block_number = fork_block_number - 10;
+        let fork_metadata = fixture
+            .provider_data
+            .update_fork(Some(target_block_number))?;
+
+        assert_eq!(fork_metadata.fork_block_number, target_block_number);
+        assert_eq!(
+            fixture.provider_data.last_block_number(),
+            target_block_number
+        );
+        assert_eq!(
+            fixture.provider_data.balance(address, None)?,
+            U256::from(1_000u64)
+        );
+
+        // The state root is recomputed for the modified state of the new fork block
+        let state_root = fixture.provider_data.current_state()?.state_root()?;
+        let remote_state_root = fixture
+            .provider_data
+            .blockchain
+            .block_by_number(target_block_number)?
+            .expect("fork block should exist")
+            .block_header()
+            .state_root;
+        assert_ne!(state_root, remote_state_root);
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_fork_unsupported_in_local_mode() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let result = fixture.provider_data.update_fork(None);
+        assert!(matches!(result, Err(ProviderError::UpdateForkUnsupported)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn console_log_mine_block() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let ConsoleLogTransaction {
+            transaction,
+            expected_call_data,
+        } = deploy_console_log_contract(&mut fixture.provider_data)?;
+
+        let signed_transaction = fixture
+            .provider_data
+            .sign_transaction_request(transaction)?;
+
+        fixture.provider_data.set_auto_mining(false);
+        fixture.provider_data.send_transaction(signed_transaction)?;
+        let (block_timestamp, _) = fixture.provider_data.next_block_timestamp(None)?;
+        let prevrandao = fixture.provider_data.prev_randao_generator.next_value();
+        let result = fixture.provider_data.mine_block(
+            ProviderData::mine_block_with_mem_pool,
+            HeaderOverrides {
+                mix_hash: Some(prevrandao),
+                ..fixture
+                    .provider_data
+                    .header_overrides_with_timestamp(block_timestamp)
+            },
+        )?;
+
+        let console_log_inputs = result.console_log_inputs;
+        assert_eq!(console_log_inputs.len(), 1);
+        assert_eq!(console_log_inputs[0], expected_call_data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn console_log_run_call() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let ConsoleLogTransaction {
+            transaction,
+            expected_call_data,
+        } = deploy_console_log_contract(&mut fixture.provider_data)?;
+
+        let pending_transaction = fixture
+            .provider_data
+            .sign_transaction_request(transaction)?;
+
+        let result = fixture.provider_data.run_call(
+            pending_transaction,
+            &BlockSpec::latest(),
+            &StateOverrides::default(),
+        )?;
+
+        let console_log_inputs = result.console_log_inputs;
+        assert_eq!(console_log_inputs.len(), 1);
+        assert_eq!(console_log_inputs[0], expected_call_data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_gas_of_call_that_depends_on_available_gas() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        // PUSH1 0x01 PUSH1 0x00 SSTORE STOP
+        let callee = Address::random();
+        fixture
+            .provider_data
+            .set_code(callee, Bytes::from(hex::decode("600160005500")?))?;
+
+        // Calls the callee with all available gas, of which 1/64th is retained, and
+        // reverts if the call fails:
+        // PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 PUSH20 <callee> GAS
+        // CALL PUSH1 0x28 JUMPI PUSH1 0x00 DUP1 REVERT JUMPDEST STOP
+        let mut caller_code = hex::decode("60006000600060006000")?;
+        caller_code.push(0x73);
+        caller_code.extend_from_slice(callee.as_slice());
+        caller_code.extend_from_slice(&hex::decode("5af1602857600080fd5b00")?);
+
+        let caller = Address::random();
+        fixture
+            .provider_data
+            .set_code(caller, Bytes::from(caller_code))?;
+
+        let sender = fixture.nth_local_account(0)?;
+        let create_transaction = |fixture: &ProviderTestFixture<L1ChainSpec>, gas_limit| {
+            fixture
+                .provider_data
+                .sign_transaction_request(TransactionRequestAndSender {
+                    request: edr_chain_l1::L1TransactionRequest::Eip155(
+                        edr_chain_l1::request::Eip155 {
+                            kind: TxKind::Call(caller),
+                            gas_limit,
+                            gas_price: 42_000_000_000_u128,
+                            value: U256::ZERO,
+                            input: Bytes::default(),
+                            nonce: 0,
+                            chain_id: fixture.config.chain_id,
+                        },
+                    ),
+                    sender,
+                })
+        };
+
+        let transaction = create_transaction(&fixture, 1_000_000)?;
+        let gas_used = fixture
+            .provider_data
+            .run_call(
+                transaction.clone(),
+                &BlockSpec::pending(),
+                &StateOverrides::default(),
+            )?
+            .execution_result
+            .gas_used();
+
+        let result = fixture
+            .provider_data
+            .estimate_gas(transaction, &BlockSpec::pending())?;
+
+        // The retained gas needs to be available, so the estimation exceeds the gas
+        // used, but it doesn't exceed the optimistic gas limit.
+        assert!(result.estimation > gas_used);
+        assert!(result.estimation <= gas::optimistic_gas_limit(gas_used, 0));
+
+        let transaction = create_transaction(&fixture, result.estimation)?;
+        let call_result = fixture.provider_data.run_call(
+            transaction,
+            &BlockSpec::pending(),
+            &StateOverrides::default(),
+        )?;
+        assert!(call_result.execution_result.is_success());
+
+        Ok(())
+    }
+
     #[test]
-    fn console_log_mine_block() -> anyhow::Result<()> {
+    fn console_log_debug_trace_call() -> anyhow::Result<()> {
         let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
         let ConsoleLogTransaction {
             transaction,
             expected_call_data,
         } = deploy_console_log_contract(&mut fixture.provider_data)?;
 
-        let signed_transaction = fixture
+        let pending_transaction = fixture
             .provider_data
             .sign_transaction_request(transaction)?;
 
-        fixture.provider_data.set_auto_mining(false);
-        fixture.provider_data.send_transaction(signed_transaction)?;
-        let (block_timestamp, _) = fixture.provider_data.next_block_timestamp(None)?;
-        let prevrandao = fixture.provider_data.prev_randao_generator.next_value();
-        let result = fixture.provider_data.mine_block(
-            ProviderData::mine_block_with_mem_pool,
-            HeaderOverrides {
-                mix_hash: Some(prevrandao),
-                ..fixture
-                    .provider_data
-                    .header_overrides_with_timestamp(block_timestamp)
+        let result = fixture.provider_data.debug_trace_call(
+            pending_transaction.clone(),
+            &BlockSpec::latest(),
+            DebugTraceConfig::default(),
+        )?;
+        assert_eq!(result.result.console_logs, None);
+
+        let result = fixture.provider_data.debug_trace_call(
+            pending_transaction,
+            &BlockSpec::latest(),
+            DebugTraceConfig {
+                enable_console_logs: true,
+                ..DebugTraceConfig::default()
             },
         )?;
+        assert_eq!(result.result.console_logs, Some(vec![expected_call_data]));
 
-        let console_log_inputs = result.console_log_inputs;
-        assert_eq!(console_log_inputs.len(), 1);
-        assert_eq!(console_log_inputs[0], expected_call_data);
+        Ok(())
+    }
+
+    #[test]
+    fn debug_trace_call_limit() -> anyhow::Result<()> {
+        const LIMIT: u64 = 5;
+
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let ConsoleLogTransaction { transaction, .. } =
+            deploy_console_log_contract(&mut fixture.provider_data)?;
+
+        let pending_transaction = fixture
+            .provider_data
+            .sign_transaction_request(transaction)?;
+
+        let unlimited = fixture.provider_data.debug_trace_call(
+            pending_transaction.clone(),
+            &BlockSpec::latest(),
+            DebugTraceConfig::default(),
+        )?;
+        assert!(unlimited.result.logs.len() as u64 > LIMIT);
+
+        let limited = fixture.provider_data.debug_trace_call(
+            pending_transaction,
+            &BlockSpec::latest(),
+            DebugTraceConfig {
+                limit: Some(LIMIT),
+                ..DebugTraceConfig::default()
+            },
+        )?;
+        assert_eq!(limited.result.logs.len() as u64, LIMIT);
+        assert_eq!(limited.result.gas_used, unlimited.result.gas_used);
+
+        for (limited, unlimited) in limited.result.logs.iter().zip(&unlimited.result.logs) {
+            assert_eq!(limited.pc, unlimited.pc);
+            assert_eq!(limited.memory, unlimited.memory);
+        }
 
         Ok(())
     }
 
     #[test]
-    fn console_log_run_call() -> anyhow::Result<()> {
+    fn debug_trace_call_shares_unchanged_memory() -> anyhow::Result<()> {
         let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
-        let ConsoleLogTransaction {
-            transaction,
-            expected_call_data,
-        } = deploy_console_log_contract(&mut fixture.provider_data)?;
+
+        let ConsoleLogTransaction { transaction, .. } =
+            deploy_console_log_contract(&mut fixture.provider_data)?;
 
         let pending_transaction = fixture
             .provider_data
             .sign_transaction_request(transaction)?;
 
-        let result = fixture.provider_data.run_call(
+        let result = fixture.provider_data.debug_trace_call(
             pending_transaction,
             &BlockSpec::latest(),
-            &StateOverrides::default(),
+            DebugTraceConfig::default(),
         )?;
 
-        let console_log_inputs = result.console_log_inputs;
-        assert_eq!(console_log_inputs.len(), 1);
-        assert_eq!(console_log_inputs[0], expected_call_data);
+        let memories = result
+            .result
+            .logs
+            .iter()
+            .map(|log| log.memory.clone().expect("memory is enabled"))
+            .collect::<Vec<_>>();
+
+        let num_shared = memories
+            .windows(2)
+            .filter(|pair| Arc::ptr_eq(&pair[0], &pair[1]))
+            .count();
+
+        // Most opcodes don't modify the memory, so their struct logs share it
+        assert!(num_shared > memories.len() / 2);
 
         Ok(())
     }
@@ -3708,6 +7995,7 @@ mod tests {
             mining: MiningConfig {
                 mem_pool: MemPoolConfig {
                     order: MineOrdering::Fifo,
+                    ..MemPoolConfig::default()
                 },
                 ..default_config.mining
             },
@@ -3855,6 +8143,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mine_and_commit_blocks_increments_timestamps() -> anyhow::Result<()> {
+        const NUM_MINED_BLOCKS: u64 = 10;
+        const INTERVAL: u64 = 12;
+
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let mined_blocks = fixture
+            .provider_data
+            .mine_and_commit_blocks(NUM_MINED_BLOCKS, INTERVAL)?;
+
+        let first_block = &mined_blocks.first().context("block should exist")?.block;
+        let first_number = first_block.block_header().number;
+        let first_timestamp = first_block.block_header().timestamp;
+
+        // Reserved blocks are instantiated lazily, with interpolated timestamps
+        for offset in [1, NUM_MINED_BLOCKS / 2, NUM_MINED_BLOCKS - 1] {
+            let block = fixture
+                .provider_data
+                .block_by_block_spec(&BlockSpec::Number(first_number + offset))?
+                .context("block should exist")?;
+
+            assert_eq!(
+                block.block_header().timestamp,
+                first_timestamp + offset * INTERVAL
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn mine_and_commit_blocks_works_with_snapshots() -> anyhow::Result<()> {
         const NUM_MINED_BLOCKS: u64 = 10;
@@ -3886,7 +8205,7 @@ mod tests {
             original_block_number + NUM_MINED_BLOCKS
         );
 
-        let reverted = fixture.provider_data.revert_to_snapshot(snapshot_id);
+        let reverted = fixture.provider_data.revert_to_snapshot(snapshot_id)?;
         assert!(reverted);
 
         assert_eq!(
@@ -3909,7 +8228,7 @@ mod tests {
 
         fixture.provider_data.mine_and_commit_blocks(1, 1)?;
 
-        let reverted = fixture.provider_data.revert_to_snapshot(snapshot_id);
+        let reverted = fixture.provider_data.revert_to_snapshot(snapshot_id)?;
         assert!(reverted);
 
         assert_eq!(
@@ -3993,6 +8312,299 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_account_storage_root_persists_across_state_recomputation() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let address = fixture.impersonated_account;
+        let storage_root = B256::random();
+        fixture
+            .provider_data
+            .set_account_storage_root(address, Some(storage_root))?;
+
+        let state = fixture.provider_data.current_state()?;
+        assert_eq!(state.account_storage_root(&address)?, Some(storage_root));
+
+        let block_number = fixture.provider_data.last_block_number();
+        let calculated_state = fixture.provider_data.blockchain.state_at_block_number(
+            block_number,
+            fixture.provider_data.irregular_state.state_overrides(),
+        )?;
+
+        assert_eq!(
+            calculated_state.account_storage_root(&address)?,
+            Some(storage_root)
+        );
+        assert_eq!(calculated_state.state_root()?, state.state_root()?);
+
+        Ok(())
+    }
+
+    fn assert_state_is_derived_from_closest_cached_state(
+        mut fixture: ProviderTestFixture<L1ChainSpec>,
+    ) -> anyhow::Result<()> {
+        for nonce in 0..3 {
+            let transaction = fixture.signed_dummy_transaction(0, Some(nonce))?;
+            fixture.provider_data.add_pending_transaction(transaction)?;
+            fixture
+                .provider_data
+                .mine_and_commit_block(HeaderOverrides::default())?;
+        }
+
+        // Evict the states of the last two blocks
+        let block_number = fixture.provider_data.last_block_number();
+        fixture
+            .provider_data
+            .block_number_to_state_id
+            .remove_mut(&block_number);
+        fixture
+            .provider_data
+            .block_number_to_state_id
+            .remove_mut(&(block_number - 1));
+
+        let base_state = fixture
+            .provider_data
+            .closest_cached_state_before(block_number)
+            .map(|(base_block_number, _)| base_block_number);
+        assert_eq!(base_state, Some(block_number - 2));
+
+        let derived_state = fixture.provider_data.get_or_compute_state(block_number)?;
+        let calculated_state = fixture.provider_data.blockchain.state_at_block_number(
+            block_number,
+            fixture.provider_data.irregular_state.state_overrides(),
+        )?;
+
+        assert_eq!(derived_state.state_root()?, calculated_state.state_root()?);
+
+        let sender = fixture.nth_local_account(0)?;
+        assert_eq!(
+            derived_state.basic(sender)?,
+            calculated_state.basic(sender)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_is_derived_from_closest_cached_state() -> anyhow::Result<()> {
+        let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        assert_state_is_derived_from_closest_cached_state(fixture)
+    }
+
+    #[cfg(feature = "test-remote")]
+    #[test]
+    fn state_is_derived_from_closest_cached_state_forked() -> anyhow::Result<()> {
+        let fixture = ProviderTestFixture::<L1ChainSpec>::new_forked(None)?;
+
+        assert_state_is_derived_from_closest_cached_state(fixture)
+    }
+
+    #[test]
+    fn set_next_block_state_root() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let state_root = B256::random();
+        fixture.provider_data.set_next_block_state_root(state_root);
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert_eq!(result.block.block_header().state_root, state_root);
+
+        // The override only applies to the next block
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert_ne!(result.block.block_header().state_root, state_root);
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_min_gas_price_after_london() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        // The dummy transaction has a gas price of 42 gwei
+        fixture.provider_data.set_min_gas_price(50_000_000_000);
+
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
+        fixture
+            .provider_data
+            .add_pending_transaction(transaction.clone())?;
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert!(result.block.transactions().is_empty());
+
+        let pending_transactions = fixture
+            .provider_data
+            .pending_transactions()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(pending_transactions, vec![transaction]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_next_prev_randao_before_merge() -> anyhow::Result<()> {
+        let default_config = create_test_config();
+        let config = ProviderConfig {
+            hardfork: edr_chain_l1::Hardfork::LONDON,
+            ..default_config
+        };
+
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .thread_name("provider-data-test")
+            .build()?;
+
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new(runtime, config)?;
+
+        let difficulty = U256::from(1_234_567);
+        fixture
+            .provider_data
+            .set_next_prev_randao(B256::from(difficulty));
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert_eq!(result.block.block_header().difficulty, difficulty);
+
+        // The override only applies to the next block
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert_ne!(result.block.block_header().difficulty, difficulty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay_block_matches_mined_block() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let first_transaction = fixture.signed_dummy_transaction(0, Some(0))?;
+        let second_transaction = fixture.signed_dummy_transaction(0, Some(1))?;
+
+        fixture
+            .provider_data
+            .add_pending_transaction(first_transaction)?;
+        fixture
+            .provider_data
+            .add_pending_transaction(second_transaction)?;
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        let block_number = result.block.block_header().number;
+
+        let replayed_block = fixture.provider_data.replay_block(block_number)?;
+        assert!(replayed_block.matches);
+        assert_eq!(replayed_block.block_number, block_number);
+        assert!(replayed_block.gas_used.is_none());
+        assert!(replayed_block.state_root.is_none());
+        assert!(replayed_block.receipt_count.is_none());
+        assert!(replayed_block.transactions.is_empty());
+        assert!(replayed_block.accounts.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay_block_reports_state_root_mismatch() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let transaction = fixture.signed_dummy_transaction(0, None)?;
+        fixture.provider_data.add_pending_transaction(transaction)?;
+
+        let state_root = B256::random();
+        fixture.provider_data.set_next_block_state_root(state_root);
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        let block_number = result.block.block_header().number;
+
+        let replayed_block = fixture.provider_data.replay_block(block_number)?;
+        assert!(!replayed_block.matches);
+        assert!(replayed_block.gas_used.is_none());
+        assert!(replayed_block.receipt_count.is_none());
+        assert!(replayed_block.transactions.is_empty());
+
+        let mismatch = replayed_block
+            .state_root
+            .expect("state root should mismatch");
+        assert_eq!(mismatch.expected, state_root);
+        assert_eq!(
+            mismatch.actual,
+            fixture.provider_data.current_state()?.state_root()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay_block_genesis() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+
+        let result = fixture.provider_data.replay_block(0);
+        assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay_transaction_with_prior_transaction_in_block() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        let sender = fixture.nth_local_account(0)?;
+
+        let first_transaction = fixture.signed_dummy_transaction(0, Some(0))?;
+        let second_transaction = fixture.signed_dummy_transaction(0, Some(1))?;
+        let transaction_hash = *second_transaction.transaction_hash();
+
+        fixture
+            .provider_data
+            .add_pending_transaction(first_transaction)?;
+        fixture
+            .provider_data
+            .add_pending_transaction(second_transaction)?;
+
+        let result = fixture
+            .provider_data
+            .mine_and_commit_block(HeaderOverrides::default())?;
+        assert_eq!(result.block.transactions().len(), 2);
+
+        let ReplayedTransactionWithTraces { result, .. } = fixture
+            .provider_data
+            .replay_transaction(&transaction_hash)?;
+
+        assert!(result.receipt.status);
+        assert_eq!(result.receipt.gas_used, 21_000);
+        assert!(result.trace.pass);
+
+        // The state includes the changes of the prior transaction
+        let sender_diff = result
+            .state_diff
+            .get(&sender)
+            .expect("sender should be modified");
+        assert_eq!(sender_diff.nonce, ValueChange::new(1, 2));
+
+        let recipient_diff = result
+            .state_diff
+            .get(&Address::ZERO)
+            .expect("recipient should be modified");
+        assert_eq!(
+            recipient_diff.balance,
+            ValueChange::new(U256::from(1), U256::from(2))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn transaction_by_invalid_hash() -> anyhow::Result<()> {
         let fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
@@ -4125,6 +8737,7 @@ mod tests {
         use edr_chain_l1::L1ChainSpec;
         use edr_chain_spec::EvmTransactionValidationError;
         use edr_chain_spec_evm::TransactionError;
+        use edr_rpc_eth::client::TransportConfig;
         use edr_test_block_replay::impl_full_block_tests;
         use edr_test_utils::env::get_alchemy_url;
 
@@ -4190,7 +8803,10 @@ mod tests {
                 cache_dir: edr_defaults::CACHE_DIR.into(),
                 chain_overrides: HashMap::default(),
                 http_headers: None,
+                live: false,
+                transport: TransportConfig::default(),
                 url: get_alchemy_url(),
+                verified_sources: None,
             }));
 
             let config = ProviderConfig {
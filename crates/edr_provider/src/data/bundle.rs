@@ -0,0 +1,192 @@
+use alloy_sol_types::{ContractError, GenericContractError, SolInterface as _};
+use edr_block_header::BlockHeader;
+use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
+use edr_chain_spec::{BlockEnvConstructor as _, ExecutableTransaction as _};
+use edr_chain_spec_evm::{
+    result::{ExecutionResult, ExecutionResultAndState, Output},
+    BlockEnvTrait as _, CfgEnv,
+};
+use edr_chain_spec_provider::ProviderChainSpec;
+use edr_evm::{dry_run, guaranteed_dry_run};
+use edr_precompile::CustomPrecompile;
+use edr_primitives::{keccak256, Address, Bytes, HashMap, U256};
+use edr_solidity::contract_decoder::ContractDecoder;
+use edr_state_api::{DynState, State as _, StateCommit as _, StateError};
+
+use crate::{
+    data::{call::BlockEnvWithZeroBaseFee, BundleTransaction},
+    error::ProviderErrorForChainSpec,
+    requests::hardhat::rpc_types::{BundleSimulation, BundleTransactionResult, ReplayLog},
+};
+
+pub(super) struct SimulateBundleArgs<'a, HardforkT, SignedTransactionT> {
+    pub blockchain: &'a dyn BlockHashByNumber<Error = DynBlockchainError>,
+    /// The header of the block in which the bundle is executed.
+    pub header: &'a BlockHeader,
+    /// The number of the block on top of whose state the bundle is executed.
+    pub state_block_number: u64,
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    pub state: Box<dyn DynState>,
+    pub cfg_env: CfgEnv<HardforkT>,
+    pub transactions: Vec<BundleTransaction<SignedTransactionT>>,
+    pub custom_precompiles: &'a HashMap<Address, CustomPrecompile>,
+    pub contract_decoder: &'a ContractDecoder,
+}
+
+/// Executes the transactions of a bundle in order, each on top of the state
+/// produced by its predecessors, without committing the resulting state.
+///
+/// Signed transactions are fully validated, whereas unsigned transactions are
+/// executed like an `eth_call`, i.e. without nonce and balance checks and with
+/// a base fee of zero.
+pub(super) fn simulate_bundle<ChainSpecT: ProviderChainSpec>(
+    args: SimulateBundleArgs<'_, ChainSpecT::Hardfork, ChainSpecT::SignedTransaction>,
+) -> Result<BundleSimulation, ProviderErrorForChainSpec<ChainSpecT>> {
+    let SimulateBundleArgs {
+        blockchain,
+        header,
+        state_block_number,
+        mut state,
+        cfg_env,
+        transactions,
+        custom_precompiles,
+        contract_decoder,
+    } = args;
+
+    let block_env = ChainSpecT::BlockEnv::new_block_env(header, cfg_env.spec);
+    let coinbase = block_env.beneficiary();
+
+    let mut transaction_hashes = Vec::with_capacity(transactions.len() * 32);
+    let mut results = Vec::with_capacity(transactions.len());
+    for transaction in transactions {
+        let coinbase_balance_before = balance(state.as_ref(), coinbase)?;
+
+        let (transaction, base_fee, result_and_state) = match transaction {
+            BundleTransaction::Signed(transaction) => {
+                let result_and_state = dry_run::<ChainSpecT, _, _, _>(
+                    blockchain,
+                    state.as_ref(),
+                    cfg_env.clone(),
+                    transaction.clone(),
+                    &block_env,
+                    custom_precompiles,
+                )?;
+
+                (transaction, block_env.basefee(), result_and_state)
+            }
+            BundleTransaction::Unsigned(transaction) => {
+                let result_and_state = guaranteed_dry_run::<ChainSpecT, _, _, _>(
+                    blockchain,
+                    state.as_ref(),
+                    cfg_env.clone(),
+                    transaction.clone(),
+                    BlockEnvWithZeroBaseFee::new(&block_env),
+                    custom_precompiles,
+                )?;
+
+                (transaction, 0, result_and_state)
+            }
+        };
+
+        let ExecutionResultAndState {
+            result,
+            state: changes,
+        } = result_and_state;
+
+        state.commit(changes);
+
+        let coinbase_diff =
+            balance(state.as_ref(), coinbase)?.saturating_sub(coinbase_balance_before);
+
+        let gas_used = result.gas_used();
+        let gas_price = transaction
+            .effective_gas_price(base_fee.into())
+            .unwrap_or(*transaction.gas_price());
+        let gas_fees = U256::from(gas_price.saturating_sub(base_fee.into())) * U256::from(gas_used);
+
+        let logs = result
+            .logs()
+            .iter()
+            .map(|log| ReplayLog {
+                address: log.address,
+                topics: log.topics().to_vec(),
+                data: log.data.data.clone(),
+            })
+            .collect();
+
+        let (value, error, revert) = match result {
+            ExecutionResult::Success { output, .. } => {
+                let value = match output {
+                    Output::Call(return_data) | Output::Create(return_data, _) => return_data,
+                };
+
+                (value, None, None)
+            }
+            ExecutionResult::Revert { output, .. } => {
+                let revert = revert_reason(&output, contract_decoder);
+                (output, Some("execution reverted".to_string()), revert)
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                (Bytes::new(), Some(format!("{reason:?}")), None)
+            }
+        };
+
+        let transaction_hash = *transaction.transaction_hash();
+        transaction_hashes.extend_from_slice(transaction_hash.as_slice());
+
+        results.push(BundleTransactionResult {
+            tx_hash: transaction_hash,
+            from_address: *transaction.caller(),
+            to_address: transaction.kind().to().copied(),
+            gas_used,
+            gas_price: U256::from(gas_price),
+            gas_fees,
+            coinbase_diff,
+            eth_sent_to_coinbase: coinbase_diff.saturating_sub(gas_fees),
+            value,
+            logs,
+            error,
+            revert,
+        });
+    }
+
+    let total_gas_used: u64 = results.iter().map(|result| result.gas_used).sum();
+    let coinbase_diff = results
+        .iter()
+        .fold(U256::ZERO, |sum, result| sum + result.coinbase_diff);
+    let gas_fees = results
+        .iter()
+        .fold(U256::ZERO, |sum, result| sum + result.gas_fees);
+    let bundle_gas_price = if total_gas_used > 0 {
+        coinbase_diff / U256::from(total_gas_used)
+    } else {
+        U256::ZERO
+    };
+
+    Ok(BundleSimulation {
+        bundle_hash: keccak256(&transaction_hashes),
+        bundle_gas_price,
+        coinbase_diff,
+        eth_sent_to_coinbase: coinbase_diff.saturating_sub(gas_fees),
+        gas_fees,
+        state_block_number,
+        total_gas_used,
+        results,
+    })
+}
+
+fn balance(state: &dyn DynState, address: Address) -> Result<U256, StateError> {
+    Ok(state
+        .basic(address)?
+        .map_or(U256::ZERO, |account_info| account_info.balance))
+}
+
+/// Decodes the reason string, panic code, or registered custom error of a
+/// revert.
+fn revert_reason(output: &Bytes, contract_decoder: &ContractDecoder) -> Option<String> {
+    match GenericContractError::abi_decode(output.as_ref()) {
+        Ok(ContractError::Revert(revert)) => Some(revert.reason),
+        Ok(ContractError::Panic(panic)) => Some(format!("panic code {:#x}", panic.code)),
+        Ok(ContractError::CustomError(_)) | Err(_) => contract_decoder.decode_custom_error(output),
+    }
+}
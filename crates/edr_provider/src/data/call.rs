@@ -7,7 +7,7 @@ use edr_chain_spec_evm::{
 use edr_chain_spec_provider::ProviderChainSpec;
 use edr_database_components::{DatabaseComponents, WrapDatabaseRef};
 use edr_evm::guaranteed_dry_run_with_inspector;
-use edr_precompile::PrecompileFn;
+use edr_precompile::CustomPrecompile;
 use edr_primitives::{Address, HashMap, B256, U256};
 use edr_state_api::{State, StateError};
 
@@ -17,6 +17,12 @@ pub(super) struct BlockEnvWithZeroBaseFee<BlockEnvT: BlockEnvTrait> {
     inner: BlockEnvT,
 }
 
+impl<BlockEnvT: BlockEnvTrait> BlockEnvWithZeroBaseFee<BlockEnvT> {
+    pub fn new(inner: BlockEnvT) -> Self {
+        Self { inner }
+    }
+}
+
 impl<BlockEnvT: BlockEnvTrait> BlockEnvTrait for BlockEnvWithZeroBaseFee<BlockEnvT> {
     fn number(&self) -> U256 {
         self.inner.number()
@@ -59,7 +65,7 @@ pub(super) fn run_call<'call, ChainSpecT, BlockchainT, InspectorT, StateT>(
     state: StateT,
     cfg_env: CfgEnv<ChainSpecT::Hardfork>,
     transaction: ChainSpecT::SignedTransaction,
-    custom_precompiles: &'call HashMap<Address, PrecompileFn>,
+    custom_precompiles: &'call HashMap<Address, CustomPrecompile>,
     inspector: &'call mut InspectorT,
 ) -> Result<ExecutionResult<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
 where
@@ -1,31 +1,96 @@
-use core::cmp;
+use core::{cell::RefCell, cmp};
 
 use edr_block_api::{Block as _, FetchBlockReceipts};
-use edr_block_header::BlockHeader;
+use edr_block_header::{
+    blob_params_for_hardfork, calculate_next_excess_blob_gas, BlobGas, BlockHeader,
+};
 use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
-use edr_chain_spec::{ExecutableTransaction as _, HaltReasonTrait};
+use edr_chain_spec::{EvmSpecId, ExecutableTransaction as _, HaltReasonTrait};
 use edr_chain_spec_evm::{result::ExecutionResult, CfgEnv};
 use edr_chain_spec_provider::ProviderChainSpec;
 use edr_eth::reward_percentile::RewardPercentile;
-use edr_precompile::PrecompileFn;
-use edr_primitives::{Address, HashMap, U256};
+use edr_precompile::CustomPrecompile;
+use edr_primitives::{Address, Bytecode, HashMap, B256, U256};
 use edr_receipt::ReceiptTrait as _;
-use edr_state_api::DynState;
+use edr_state_api::{account::AccountInfo, State, StateError};
 use edr_tracing::TraceCollector;
 use edr_transaction::TransactionMut;
 use itertools::Itertools;
 
 use crate::{data::call, error::ProviderErrorForChainSpec, ProviderError};
 
+/// A state that caches the accounts, code, and storage slots that are touched
+/// while estimating gas. Every iteration of the estimation executes the same
+/// transaction, so this avoids repeatedly querying the underlying state, which
+/// can be expensive for forked or historical states.
+pub(super) struct TouchedStateCache<StateT> {
+    state: StateT,
+    accounts: RefCell<HashMap<Address, Option<AccountInfo>>>,
+    code: RefCell<HashMap<B256, Bytecode>>,
+    storage: RefCell<HashMap<(Address, U256), U256>>,
+}
+
+impl<StateT> TouchedStateCache<StateT> {
+    /// Creates a new instance that caches the touched state of the provided
+    /// state.
+    pub fn new(state: StateT) -> Self {
+        Self {
+            state,
+            accounts: RefCell::default(),
+            code: RefCell::default(),
+            storage: RefCell::default(),
+        }
+    }
+}
+
+impl<StateT: State<Error = StateError>> State for TouchedStateCache<StateT> {
+    type Error = StateError;
+
+    fn basic(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account_info) = self.accounts.borrow().get(&address) {
+            return Ok(account_info.clone());
+        }
+
+        let account_info = self.state.basic(address)?;
+        self.accounts
+            .borrow_mut()
+            .insert(address, account_info.clone());
+
+        Ok(account_info)
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.code.borrow().get(&code_hash) {
+            return Ok(code.clone());
+        }
+
+        let code = self.state.code_by_hash(code_hash)?;
+        self.code.borrow_mut().insert(code_hash, code.clone());
+
+        Ok(code)
+    }
+
+    fn storage(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.storage.borrow().get(&(address, index)) {
+            return Ok(*value);
+        }
+
+        let value = self.state.storage(address, index)?;
+        self.storage.borrow_mut().insert((address, index), value);
+
+        Ok(value)
+    }
+}
+
 pub(super) struct CheckGasLimitArgs<'a, HaltReasonT: HaltReasonTrait, HardforkT, SignedTransactionT>
 {
     pub blockchain: &'a dyn BlockHashByNumber<Error = DynBlockchainError>,
     pub header: &'a BlockHeader,
-    pub state: &'a dyn DynState,
+    pub state: &'a dyn State<Error = StateError>,
     pub cfg_env: CfgEnv<HardforkT>,
     pub transaction: SignedTransactionT,
     pub gas_limit: u64,
-    pub custom_precompiles: &'a HashMap<Address, PrecompileFn>,
+    pub custom_precompiles: &'a HashMap<Address, CustomPrecompile>,
     pub trace_collector: &'a mut TraceCollector<HaltReasonT>,
 }
 
@@ -66,6 +131,21 @@ pub(super) fn check_gas_limit<ChainSpecT: ProviderChainSpec<SignedTransaction: T
     Ok(matches!(result, ExecutionResult::Success { .. }))
 }
 
+/// Computes an optimistic gas limit for a transaction, based on the gas used
+/// and refunded when executing it with a higher gas limit. Ported from Geth.
+///
+/// Accounts for the gas that needs to be available for refunds, the call
+/// stipend, and the gas that is retained by the 63/64 rule of EIP-150.
+pub(super) fn optimistic_gas_limit(gas_used: u64, gas_refunded: u64) -> u64 {
+    const CALL_STIPEND: u64 = 2_300;
+
+    gas_used
+        .saturating_add(gas_refunded)
+        .saturating_add(CALL_STIPEND)
+        .saturating_mul(64)
+        / 63
+}
+
 pub(super) struct BinarySearchEstimationArgs<
     'a,
     HaltReasonT: HaltReasonTrait,
@@ -74,12 +154,12 @@ pub(super) struct BinarySearchEstimationArgs<
 > {
     pub blockchain: &'a dyn BlockHashByNumber<Error = DynBlockchainError>,
     pub header: &'a BlockHeader,
-    pub state: &'a dyn DynState,
+    pub state: &'a dyn State<Error = StateError>,
     pub cfg_env: CfgEnv<HardforkT>,
     pub transaction: SignedTransactionT,
     pub lower_bound: u64,
     pub upper_bound: u64,
-    pub custom_precompiles: &'a HashMap<Address, PrecompileFn>,
+    pub custom_precompiles: &'a HashMap<Address, CustomPrecompile>,
     pub trace_collector: &'a mut TraceCollector<HaltReasonT>,
 }
 
@@ -230,3 +310,93 @@ pub(super) fn gas_used_ratio(gas_used: u64, gas_limit: u64) -> f64 {
     const FLOATS_PRECISION: f64 = 100_000.0;
     gas_used as f64 * FLOATS_PRECISION / gas_limit as f64 / FLOATS_PRECISION
 }
+
+/// Calculates the base fee per blob gas of a block, given its header. Returns
+/// zero for pre-EIP-4844 blocks.
+pub(super) fn base_fee_per_blob_gas(header: &BlockHeader, evm_spec_id: EvmSpecId) -> u128 {
+    header
+        .blob_gas
+        .as_ref()
+        .map_or(0, |BlobGas { excess_gas, .. }| {
+            blob_params_for_hardfork(evm_spec_id).calc_blob_fee(*excess_gas)
+        })
+}
+
+/// Calculates the base fee per blob gas of the block following the block with
+/// the provided header. Returns zero for pre-EIP-4844 hardforks.
+pub(super) fn next_block_base_fee_per_blob_gas(
+    parent: &BlockHeader,
+    evm_spec_id: EvmSpecId,
+    next_block_base_fee_per_gas: u128,
+) -> u128 {
+    if evm_spec_id < EvmSpecId::CANCUN {
+        return 0;
+    }
+
+    let excess_gas =
+        calculate_next_excess_blob_gas(parent, evm_spec_id, Some(next_block_base_fee_per_gas));
+
+    blob_params_for_hardfork(evm_spec_id).calc_blob_fee(excess_gas)
+}
+
+/// Calculates the ratio of blob gas used and the maximum blob gas per block.
+/// Returns zero for pre-EIP-4844 blocks.
+pub(super) fn blob_gas_used_ratio(header: &BlockHeader, evm_spec_id: EvmSpecId) -> f64 {
+    header
+        .blob_gas
+        .as_ref()
+        .map_or(0.0, |BlobGas { gas_used, .. }| {
+            let max_blob_gas = blob_params_for_hardfork(evm_spec_id).max_blob_gas_per_block();
+            gas_used_ratio(*gas_used, max_blob_gas)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingState {
+        num_queries: Cell<usize>,
+    }
+
+    impl State for CountingState {
+        type Error = StateError;
+
+        fn basic(&self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.num_queries.set(self.num_queries.get() + 1);
+            Ok(None)
+        }
+
+        fn code_by_hash(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.num_queries.set(self.num_queries.get() + 1);
+            Ok(Bytecode::default())
+        }
+
+        fn storage(&self, _address: Address, _index: U256) -> Result<U256, Self::Error> {
+            self.num_queries.set(self.num_queries.get() + 1);
+            Ok(U256::from(1))
+        }
+    }
+
+    #[test]
+    fn touched_state_cache_queries_state_once() -> anyhow::Result<()> {
+        let state = CountingState::default();
+        let cache = TouchedStateCache::new(&state);
+
+        let address = Address::random();
+        for _ in 0..3 {
+            assert_eq!(cache.basic(address)?, None);
+            assert_eq!(cache.code_by_hash(B256::ZERO)?, Bytecode::default());
+            assert_eq!(cache.storage(address, U256::ZERO)?, U256::from(1));
+        }
+        assert_eq!(state.num_queries.get(), 3);
+
+        cache.storage(address, U256::from(1))?;
+        assert_eq!(state.num_queries.get(), 4);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use edr_block_miner::PendingTransactionSummary;
+use edr_primitives::{keccak256, Address, B256};
+
+use crate::requests::hardhat::rpc_types::{InclusionAction, InclusionPolicy};
+
+/// Determines the action that the inclusion policy takes for a transaction, if
+/// any.
+pub(super) fn inclusion_action<'policy>(
+    policy: &'policy InclusionPolicy,
+    transaction_hash: &B256,
+    sender: &Address,
+) -> Option<&'policy InclusionAction> {
+    let seed = policy.seed.to::<u64>();
+
+    policy
+        .rules
+        .iter()
+        .enumerate()
+        .find(|(index, rule)| {
+            let matches_hash = rule
+                .transaction_hashes
+                .as_ref()
+                .is_none_or(|hashes| hashes.contains(transaction_hash));
+
+            let matches_sender = rule
+                .senders
+                .as_ref()
+                .is_none_or(|senders| senders.contains(sender));
+
+            matches_hash
+                && matches_sender
+                && rule.probability.is_none_or(|probability| {
+                    sample(seed, *index as u64, transaction_hash) < probability
+                })
+        })
+        .map(|(_index, rule)| &rule.action)
+}
+
+/// Ranks the pending transactions in a pseudo-random order, which differs per
+/// block.
+pub(super) fn shuffled_ranking(
+    seed: u64,
+    block_number: u64,
+    transactions: &[PendingTransactionSummary],
+) -> Vec<B256> {
+    let mut ranking = transactions
+        .iter()
+        .map(|transaction| {
+            (
+                sample(seed, block_number, &transaction.transaction_hash),
+                transaction.transaction_hash,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ranking.sort_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+
+    ranking
+        .into_iter()
+        .map(|(_sample, transaction_hash)| transaction_hash)
+        .collect()
+}
+
+/// Deterministically derives a pseudo-random value in the range `[0, 1)` from
+/// the provided inputs.
+fn sample(seed: u64, salt: u64, transaction_hash: &B256) -> f64 {
+    let mut preimage = Vec::with_capacity(48);
+    preimage.extend_from_slice(&seed.to_be_bytes());
+    preimage.extend_from_slice(&salt.to_be_bytes());
+    preimage.extend_from_slice(transaction_hash.as_slice());
+
+    let hash = keccak256(preimage);
+    let value = u64::from_be_bytes(hash[..8].try_into().expect("hash has 32 bytes"));
+
+    // Use the 53 most significant bits, which fit the mantissa of an f64
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_primitives::U64;
+
+    use super::*;
+    use crate::requests::hardhat::rpc_types::InclusionRule;
+
+    fn rule(probability: Option<f64>, action: InclusionAction) -> InclusionRule {
+        InclusionRule {
+            transaction_hashes: None,
+            senders: None,
+            probability,
+            action,
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_applies() {
+        let sender = Address::random();
+        let transaction_hash = B256::random();
+
+        let policy = InclusionPolicy {
+            rules: vec![
+                InclusionRule {
+                    senders: Some(vec![Address::random()]),
+                    ..rule(None, InclusionAction::Drop)
+                },
+                InclusionRule {
+                    transaction_hashes: Some(vec![transaction_hash]),
+                    ..rule(
+                        None,
+                        InclusionAction::Delay {
+                            blocks: U64::from(2),
+                        },
+                    )
+                },
+                rule(None, InclusionAction::Drop),
+            ],
+            ..InclusionPolicy::default()
+        };
+
+        assert_eq!(
+            inclusion_action(&policy, &transaction_hash, &sender),
+            Some(&InclusionAction::Delay {
+                blocks: U64::from(2)
+            })
+        );
+        assert_eq!(
+            inclusion_action(&policy, &B256::random(), &sender),
+            Some(&InclusionAction::Drop)
+        );
+    }
+
+    #[test]
+    fn probability_is_deterministic() {
+        let sender = Address::random();
+        let transaction_hashes = (0..1_000).map(|_| B256::random()).collect::<Vec<_>>();
+
+        let policy = InclusionPolicy {
+            rules: vec![rule(Some(0.5), InclusionAction::Drop)],
+            seed: U64::from(7),
+            ..InclusionPolicy::default()
+        };
+
+        let dropped = transaction_hashes
+            .iter()
+            .filter(|hash| inclusion_action(&policy, hash, &sender).is_some())
+            .collect::<Vec<_>>();
+
+        assert!((400..600).contains(&dropped.len()));
+
+        let dropped_again = transaction_hashes
+            .iter()
+            .filter(|hash| inclusion_action(&policy, hash, &sender).is_some())
+            .collect::<Vec<_>>();
+
+        assert_eq!(dropped, dropped_again);
+    }
+}
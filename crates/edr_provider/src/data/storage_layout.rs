@@ -0,0 +1,1269 @@
+use core::fmt::Debug;
+use std::{collections::BTreeMap, str::FromStr as _};
+
+use edr_chain_spec::HaltReasonTrait;
+use edr_primitives::{hex, keccak256, Address, U256};
+use edr_state_api::StateError;
+
+use crate::{
+    requests::hardhat::rpc_types::{StorageEncoding, StorageLayout, StorageLayoutType},
+    ProviderError,
+};
+
+/// The maximum number of array elements that are decoded at once, including
+/// the elements of nested arrays. Larger arrays have to be read element by
+/// element.
+const MAX_DECODED_ARRAY_LENGTH: u64 = 1024;
+
+/// The maximum length of a `bytes` or `string` value that is decoded.
+const MAX_DECODED_BYTES_LENGTH: u64 = 64 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum StorageVariableError {
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error(transparent)]
+    State(#[from] StateError),
+}
+
+impl<
+        FetchReceiptErrorT,
+        GenesisBlockCreationErrorT,
+        HaltReasonT: HaltReasonTrait,
+        HardforkT: Debug,
+        TransactionValidationErrorT,
+    > From<StorageVariableError>
+    for ProviderError<
+        FetchReceiptErrorT,
+        GenesisBlockCreationErrorT,
+        HaltReasonT,
+        HardforkT,
+        TransactionValidationErrorT,
+    >
+{
+    fn from(value: StorageVariableError) -> Self {
+        match value {
+            StorageVariableError::InvalidInput(message) => ProviderError::InvalidInput(message),
+            StorageVariableError::State(error) => ProviderError::State(error),
+        }
+    }
+}
+
+/// The location of a value in storage.
+#[derive(Clone, Copy, Debug)]
+struct Location {
+    slot: U256,
+    /// The offset in bytes from the least significant byte of the slot.
+    offset: u64,
+}
+
+/// Reads and decodes the value of the state variable with the provided name,
+/// using the provided storage layout.
+///
+/// The `variable` consists of the name of a state variable, optionally
+/// followed by struct members separated by dots, e.g. `positions.owner`. The
+/// `keys` are consumed in order for every mapping or array that is
+/// encountered while resolving the variable, whereas the members are consumed
+/// for every struct.
+pub(super) fn read_storage_variable(
+    layout: &StorageLayout,
+    variable: &str,
+    keys: &[String],
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<serde_json::Value, StorageVariableError> {
+    let (type_id, location) = resolve_variable(layout, variable, keys, read_slot)?;
+
+    let mut remaining_elements = MAX_DECODED_ARRAY_LENGTH;
+    decode_value(
+        layout,
+        type_id,
+        location,
+        &mut remaining_elements,
+        read_slot,
+    )
+}
+
+/// Encodes the value of the state variable with the provided name, using the
+/// provided storage layout, and returns the storage slots that need to be
+/// written.
+///
+/// The `variable` and `keys` are resolved like in [`read_storage_variable`].
+/// Values that are packed into a slot together with other values only
+/// overwrite their own bytes.
+pub(super) fn write_storage_variable(
+    layout: &StorageLayout,
+    variable: &str,
+    keys: &[String],
+    value: &serde_json::Value,
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<BTreeMap<U256, U256>, StorageVariableError> {
+    let (type_id, location) = resolve_variable(layout, variable, keys, read_slot)?;
+
+    let mut writer = SlotWriter {
+        read_slot,
+        writes: BTreeMap::new(),
+    };
+    encode_value(layout, type_id, location, value, &mut writer)?;
+
+    Ok(writer.writes)
+}
+
+/// Buffers writes to storage slots, such that subsequent reads of a slot
+/// observe earlier writes.
+struct SlotWriter<'reader> {
+    read_slot: &'reader mut dyn FnMut(U256) -> Result<U256, StateError>,
+    writes: BTreeMap<U256, U256>,
+}
+
+impl SlotWriter<'_> {
+    fn read(&mut self, slot: U256) -> Result<U256, StateError> {
+        if let Some(value) = self.writes.get(&slot) {
+            Ok(*value)
+        } else {
+            (self.read_slot)(slot)
+        }
+    }
+
+    fn write(&mut self, slot: U256, value: U256) {
+        self.writes.insert(slot, value);
+    }
+}
+
+/// Resolves the type and location of the state variable with the provided
+/// name, consuming struct members and keys.
+fn resolve_variable<'layout>(
+    layout: &'layout StorageLayout,
+    variable: &str,
+    keys: &[String],
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<(&'layout str, Location), StorageVariableError> {
+    let mut members = variable.split('.');
+    let name = members.next().expect("Split always yields an item");
+
+    let entry = layout
+        .storage
+        .iter()
+        .find(|entry| entry.label == name)
+        .ok_or_else(|| invalid_input(format!("Unknown storage variable '{name}'")))?;
+
+    let mut type_id = entry.type_id.as_str();
+    let mut location = Location {
+        slot: parse_decimal(&entry.slot)?,
+        offset: entry.offset,
+    };
+
+    let mut keys = keys.iter();
+    loop {
+        let ty = layout_type(layout, type_id)?;
+        match ty.encoding {
+            StorageEncoding::Mapping => {
+                let Some(key) = keys.next() else {
+                    break;
+                };
+
+                let key_type = layout_type(layout, related_type(ty, &ty.key, "key")?)?;
+                let mut preimage = encode_mapping_key(key_type, key)?;
+                preimage.extend_from_slice(&location.slot.to_be_bytes::<32>());
+
+                location = Location {
+                    slot: U256::from_be_bytes(keccak256(preimage).0),
+                    offset: 0,
+                };
+                type_id = related_type(ty, &ty.value, "value")?;
+            }
+            StorageEncoding::DynamicArray | StorageEncoding::Inplace if ty.base.is_some() => {
+                let Some(key) = keys.next() else {
+                    break;
+                };
+
+                let index = parse_uint(key)?;
+                let length = array_length(ty, location, read_slot)?;
+                if index >= length {
+                    return Err(invalid_input(format!(
+                        "Index {index} is out of bounds for '{}' with length {length}",
+                        ty.label
+                    )));
+                }
+
+                let base_id = related_type(ty, &ty.base, "base")?;
+                let base = layout_type(layout, base_id)?;
+                location = element_location(array_start(ty, location), index, base)?;
+                type_id = base_id;
+            }
+            StorageEncoding::Inplace if ty.members.is_some() => {
+                let Some(member_name) = members.next() else {
+                    break;
+                };
+
+                let member = ty
+                    .members
+                    .iter()
+                    .flatten()
+                    .find(|member| member.label == member_name)
+                    .ok_or_else(|| {
+                        invalid_input(format!("Unknown member '{member_name}' of '{}'", ty.label))
+                    })?;
+
+                location = Location {
+                    slot: location.slot.wrapping_add(parse_decimal(&member.slot)?),
+                    offset: member.offset,
+                };
+                type_id = &member.type_id;
+            }
+            _ => break,
+        }
+    }
+
+    if let Some(member_name) = members.next() {
+        return Err(invalid_input(format!(
+            "Cannot access member '{member_name}' of '{}'",
+            layout_type(layout, type_id)?.label
+        )));
+    }
+
+    if keys.next().is_some() {
+        return Err(invalid_input(format!(
+            "Too many keys were provided for '{variable}'"
+        )));
+    }
+
+    Ok((type_id, location))
+}
+
+/// Decodes the value of the provided type at the provided location.
+///
+/// The `remaining_elements` are shared between nested arrays, to bound the
+/// total number of decoded elements.
+fn decode_value(
+    layout: &StorageLayout,
+    type_id: &str,
+    location: Location,
+    remaining_elements: &mut u64,
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<serde_json::Value, StorageVariableError> {
+    let ty = layout_type(layout, type_id)?;
+
+    match ty.encoding {
+        StorageEncoding::Mapping => Err(invalid_input(format!(
+            "A key is required to read '{}'",
+            ty.label
+        ))),
+        StorageEncoding::Bytes => decode_bytes(ty, location, read_slot),
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace if ty.base.is_some() => {
+            let length = array_length(ty, location, read_slot)?;
+            if length > U256::from(*remaining_elements) {
+                return Err(invalid_input(format!(
+                    "'{}' has {length} elements, which is too many to decode at once. Provide an index instead",
+                    ty.label
+                )));
+            }
+            let length = length.to::<u64>();
+            *remaining_elements -= length;
+
+            let base_id = related_type(ty, &ty.base, "base")?;
+            let base = layout_type(layout, base_id)?;
+            let start = array_start(ty, location);
+
+            let elements = (0..length)
+                .map(|index| {
+                    let location = element_location(start, U256::from(index), base)?;
+                    decode_value(layout, base_id, location, remaining_elements, read_slot)
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(serde_json::Value::Array(elements))
+        }
+        StorageEncoding::Inplace if ty.members.is_some() => {
+            let members = ty
+                .members
+                .iter()
+                .flatten()
+                .map(|member| {
+                    let location = Location {
+                        slot: location.slot.wrapping_add(parse_decimal(&member.slot)?),
+                        offset: member.offset,
+                    };
+
+                    let value = decode_value(
+                        layout,
+                        &member.type_id,
+                        location,
+                        remaining_elements,
+                        read_slot,
+                    )?;
+                    Ok((member.label.clone(), value))
+                })
+                .collect::<Result<_, StorageVariableError>>()?;
+
+            Ok(serde_json::Value::Object(members))
+        }
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace => {
+            let size = type_size(ty)?;
+            if size == 0 || size > 32 {
+                return Err(invalid_input(format!(
+                    "Unsupported type '{}' of {size} bytes",
+                    ty.label
+                )));
+            }
+
+            let value = (read_slot(location.slot)? >> (location.offset * 8)) & value_mask(size);
+
+            decode_elementary_value(&ty.label, size, value)
+        }
+    }
+}
+
+/// Decodes a value type that is stored in a single slot.
+fn decode_elementary_value(
+    label: &str,
+    size: u64,
+    value: U256,
+) -> Result<serde_json::Value, StorageVariableError> {
+    let value = if label == "bool" {
+        serde_json::Value::Bool(!value.is_zero())
+    } else if label.starts_with("address") || label.starts_with("contract ") {
+        let address = Address::from_word(value.to_be_bytes::<32>().into());
+        serde_json::Value::String(address.to_checksum(None))
+    } else if let Some(bytes) = label
+        .strip_prefix("bytes")
+        .filter(|length| length.parse::<u8>().is_ok())
+    {
+        if bytes.parse::<u64>().ok() != Some(size) {
+            return Err(invalid_input(format!(
+                "Type '{label}' has an invalid size of {size} bytes"
+            )));
+        }
+
+        let bytes = value.to_be_bytes::<32>();
+        serde_json::Value::String(hex::encode_prefixed(&bytes[32 - size as usize..]))
+    } else if label.starts_with("int") {
+        let sign_bit = U256::from(1) << (size * 8 - 1);
+        let value = if value & sign_bit == U256::ZERO {
+            value
+        } else {
+            // Sign-extend
+            value | !((sign_bit << 1).wrapping_sub(U256::from(1)))
+        };
+
+        let decimal = if value.bit(255) {
+            format!("-{}", value.wrapping_neg())
+        } else {
+            value.to_string()
+        };
+
+        serde_json::Value::String(decimal)
+    } else {
+        serde_json::Value::String(value.to_string())
+    };
+
+    Ok(value)
+}
+
+/// Decodes a `string` or `bytes` value.
+fn decode_bytes(
+    ty: &StorageLayoutType,
+    location: Location,
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<serde_json::Value, StorageVariableError> {
+    let value = read_slot(location.slot)?;
+
+    let bytes = if value.bit(0) {
+        // Long values store `length * 2 + 1` in the slot and their data starting at
+        // the hash of the slot
+        let length = (value - U256::from(1)) >> 1;
+        if length > U256::from(MAX_DECODED_BYTES_LENGTH) {
+            return Err(invalid_input(format!(
+                "'{}' has a length of {length} bytes, which exceeds the maximum of {MAX_DECODED_BYTES_LENGTH} bytes",
+                ty.label
+            )));
+        }
+        let length = length.to::<u64>();
+
+        let start = data_start(location.slot);
+        let mut bytes = Vec::with_capacity(length as usize);
+        for index in 0..length.div_ceil(32) {
+            bytes.extend_from_slice(
+                &read_slot(start.wrapping_add(U256::from(index)))?.to_be_bytes::<32>(),
+            );
+        }
+
+        bytes.truncate(length as usize);
+        bytes
+    } else {
+        // Short values store `length * 2` in the lowest byte and their data in the
+        // highest bytes of the slot
+        let bytes = value.to_be_bytes::<32>();
+        let length = usize::from(bytes[31] / 2);
+        if length > 31 {
+            return Err(invalid_input(format!(
+                "'{}' has an invalid short length of {length} bytes",
+                ty.label
+            )));
+        }
+
+        bytes[..length].to_vec()
+    };
+
+    let value = if ty.label == "string" {
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        hex::encode_prefixed(bytes)
+    };
+
+    Ok(serde_json::Value::String(value))
+}
+
+fn encode_value(
+    layout: &StorageLayout,
+    type_id: &str,
+    location: Location,
+    value: &serde_json::Value,
+    writer: &mut SlotWriter<'_>,
+) -> Result<(), StorageVariableError> {
+    let ty = layout_type(layout, type_id)?;
+
+    match ty.encoding {
+        StorageEncoding::Mapping => Err(invalid_input(format!(
+            "A key is required to write '{}'",
+            ty.label
+        ))),
+        StorageEncoding::Bytes => encode_bytes(ty, location, value, writer),
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace if ty.base.is_some() => {
+            let serde_json::Value::Array(elements) = value else {
+                return Err(invalid_value(ty, value));
+            };
+
+            if elements.len() as u64 > MAX_DECODED_ARRAY_LENGTH {
+                return Err(invalid_input(format!(
+                    "{} elements are too many to encode at once. Provide an index instead",
+                    elements.len()
+                )));
+            }
+
+            let length = U256::from(elements.len());
+            if ty.encoding == StorageEncoding::DynamicArray {
+                // Elements beyond the new length are cleared, like Solidity does
+                let old_length = writer.read(location.slot)?;
+                if old_length > length {
+                    clear_array_elements(layout, ty, location, length, old_length, writer)?;
+                }
+
+                writer.write(location.slot, length);
+            } else if array_length(ty, location, &mut |slot| writer.read(slot))? != length {
+                return Err(invalid_value(ty, value));
+            }
+
+            let base_id = related_type(ty, &ty.base, "base")?;
+            let base = layout_type(layout, base_id)?;
+            let start = array_start(ty, location);
+
+            for (index, element) in elements.iter().enumerate() {
+                let location = element_location(start, U256::from(index), base)?;
+                encode_value(layout, base_id, location, element, writer)?;
+            }
+
+            Ok(())
+        }
+        StorageEncoding::Inplace if ty.members.is_some() => {
+            let serde_json::Value::Object(values) = value else {
+                return Err(invalid_value(ty, value));
+            };
+
+            // Members that are not provided are left unchanged
+            for (member_name, value) in values {
+                let member = ty
+                    .members
+                    .iter()
+                    .flatten()
+                    .find(|member| member.label == *member_name)
+                    .ok_or_else(|| {
+                        invalid_input(format!("Unknown member '{member_name}' of '{}'", ty.label))
+                    })?;
+
+                let location = Location {
+                    slot: location.slot.wrapping_add(parse_decimal(&member.slot)?),
+                    offset: member.offset,
+                };
+                encode_value(layout, &member.type_id, location, value, writer)?;
+            }
+
+            Ok(())
+        }
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace => {
+            let size = type_size(ty)?;
+            if size == 0 || size > 32 {
+                return Err(invalid_input(format!(
+                    "Unsupported type '{}' of {size} bytes",
+                    ty.label
+                )));
+            }
+
+            let encoded = encode_elementary_value(ty, size, value)?;
+
+            let shift = location.offset * 8;
+            let mask = value_mask(size) << shift;
+            let word = writer.read(location.slot)?;
+            writer.write(location.slot, (word & !mask) | (encoded << shift));
+
+            Ok(())
+        }
+    }
+}
+
+/// Encodes a value type, checking that it fits into the type's number of
+/// bytes.
+fn encode_elementary_value(
+    ty: &StorageLayoutType,
+    size: u64,
+    value: &serde_json::Value,
+) -> Result<U256, StorageVariableError> {
+    let label = ty.label.as_str();
+
+    if label == "bool" {
+        let value = value.as_bool().ok_or_else(|| invalid_value(ty, value))?;
+        Ok(U256::from(u8::from(value)))
+    } else if label.starts_with("address") || label.starts_with("contract ") {
+        let address = value
+            .as_str()
+            .and_then(|address| Address::from_str(address).ok())
+            .ok_or_else(|| invalid_value(ty, value))?;
+
+        Ok(U256::from_be_bytes(address.into_word().0))
+    } else if label
+        .strip_prefix("bytes")
+        .is_some_and(|length| length.parse::<u8>().is_ok())
+    {
+        let bytes = value
+            .as_str()
+            .and_then(|bytes| hex::decode(bytes).ok())
+            .filter(|bytes| bytes.len() as u64 == size)
+            .ok_or_else(|| invalid_value(ty, value))?;
+
+        Ok(U256::from_be_slice(&bytes))
+    } else {
+        let text = match value {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Number(number) => number.to_string(),
+            _ => return Err(invalid_value(ty, value)),
+        };
+
+        let (is_negative, magnitude) = match text.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, text.as_str()),
+        };
+        let magnitude = U256::from_str(magnitude).map_err(|_error| invalid_value(ty, value))?;
+
+        if label.starts_with("int") {
+            let limit = U256::from(1) << (size * 8 - 1);
+            if is_negative && magnitude <= limit {
+                Ok(magnitude.wrapping_neg() & value_mask(size))
+            } else if !is_negative && magnitude < limit {
+                Ok(magnitude)
+            } else {
+                Err(invalid_value(ty, value))
+            }
+        } else if is_negative || magnitude > value_mask(size) {
+            Err(invalid_value(ty, value))
+        } else {
+            Ok(magnitude)
+        }
+    }
+}
+
+/// Encodes a `string` or `bytes` value.
+fn encode_bytes(
+    ty: &StorageLayoutType,
+    location: Location,
+    value: &serde_json::Value,
+    writer: &mut SlotWriter<'_>,
+) -> Result<(), StorageVariableError> {
+    let bytes = match value.as_str() {
+        Some(value) if ty.label == "string" => value.as_bytes().to_vec(),
+        Some(bytes) => hex::decode(bytes).map_err(|_error| invalid_value(ty, value))?,
+        None => return Err(invalid_value(ty, value)),
+    };
+
+    // Data slots of a previous long value that are no longer used are cleared
+    let retained_slots = if bytes.len() < 32 {
+        0
+    } else {
+        bytes.len().div_ceil(32) as u64
+    };
+    clear_bytes_data(location.slot, retained_slots, writer)?;
+
+    if bytes.len() < 32 {
+        let mut word = [0u8; 32];
+        word[..bytes.len()].copy_from_slice(&bytes);
+        word[31] = (bytes.len() * 2) as u8;
+
+        writer.write(location.slot, U256::from_be_bytes(word));
+    } else {
+        writer.write(location.slot, U256::from(bytes.len() * 2 + 1));
+
+        let start = data_start(location.slot);
+        for (index, chunk) in bytes.chunks(32).enumerate() {
+            let mut word = [0u8; 32];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            writer.write(
+                start.wrapping_add(U256::from(index)),
+                U256::from_be_bytes(word),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resets the value at the provided location to zero, like Solidity's
+/// `delete`. Mappings are left unchanged.
+fn clear_value(
+    layout: &StorageLayout,
+    type_id: &str,
+    location: Location,
+    writer: &mut SlotWriter<'_>,
+) -> Result<(), StorageVariableError> {
+    let ty = layout_type(layout, type_id)?;
+
+    match ty.encoding {
+        StorageEncoding::Mapping => Ok(()),
+        StorageEncoding::Bytes => {
+            clear_bytes_data(location.slot, 0, writer)?;
+            writer.write(location.slot, U256::ZERO);
+
+            Ok(())
+        }
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace if ty.base.is_some() => {
+            let length = array_length(ty, location, &mut |slot| writer.read(slot))?;
+            clear_array_elements(layout, ty, location, U256::ZERO, length, writer)?;
+
+            if ty.encoding == StorageEncoding::DynamicArray {
+                writer.write(location.slot, U256::ZERO);
+            }
+
+            Ok(())
+        }
+        StorageEncoding::Inplace if ty.members.is_some() => {
+            for member in ty.members.iter().flatten() {
+                let location = Location {
+                    slot: location.slot.wrapping_add(parse_decimal(&member.slot)?),
+                    offset: member.offset,
+                };
+                clear_value(layout, &member.type_id, location, writer)?;
+            }
+
+            Ok(())
+        }
+        StorageEncoding::DynamicArray | StorageEncoding::Inplace => {
+            let mask = value_mask(type_size(ty)?) << (location.offset * 8);
+            let word = writer.read(location.slot)?;
+            writer.write(location.slot, word & !mask);
+
+            Ok(())
+        }
+    }
+}
+
+/// Clears the elements of an array in the range `from..to`.
+fn clear_array_elements(
+    layout: &StorageLayout,
+    ty: &StorageLayoutType,
+    location: Location,
+    from: U256,
+    to: U256,
+    writer: &mut SlotWriter<'_>,
+) -> Result<(), StorageVariableError> {
+    if to.saturating_sub(from) > U256::from(MAX_DECODED_ARRAY_LENGTH) {
+        return Err(invalid_input(format!(
+            "{} elements are too many to clear at once",
+            to - from
+        )));
+    }
+
+    let base_id = related_type(ty, &ty.base, "base")?;
+    let base = layout_type(layout, base_id)?;
+    let start = array_start(ty, location);
+
+    let mut index = from;
+    while index < to {
+        let location = element_location(start, index, base)?;
+        clear_value(layout, base_id, location, writer)?;
+
+        index += U256::from(1);
+    }
+
+    Ok(())
+}
+
+/// Clears the data slots of a long `string` or `bytes` value stored at the
+/// provided slot, except for the first `retained_slots`.
+fn clear_bytes_data(
+    slot: U256,
+    retained_slots: u64,
+    writer: &mut SlotWriter<'_>,
+) -> Result<(), StorageVariableError> {
+    let word = writer.read(slot)?;
+
+    // Short values are stored in the slot itself, with the lowest bit unset
+    if !word.bit(0) {
+        return Ok(());
+    }
+
+    let length = word >> 1;
+    if length > U256::from(MAX_DECODED_BYTES_LENGTH) {
+        return Err(invalid_input(format!(
+            "The stored value has a length of {length} bytes, which exceeds the maximum of {MAX_DECODED_BYTES_LENGTH} bytes"
+        )));
+    }
+    let length = length.to::<u64>();
+
+    let start = data_start(slot);
+    for index in retained_slots..length.div_ceil(32) {
+        writer.write(start.wrapping_add(U256::from(index)), U256::ZERO);
+    }
+
+    Ok(())
+}
+
+/// Encodes a mapping key for hashing, as specified by Solidity.
+fn encode_mapping_key(
+    key_type: &StorageLayoutType,
+    key: &str,
+) -> Result<Vec<u8>, StorageVariableError> {
+    let invalid_key = || invalid_input(format!("Invalid key '{key}' of type '{}'", key_type.label));
+
+    if key_type.encoding == StorageEncoding::Bytes {
+        // Keys of type `string` and `bytes` are hashed without padding
+        return if key_type.label == "string" {
+            Ok(key.as_bytes().to_vec())
+        } else {
+            hex::decode(key).map_err(|_error| invalid_key())
+        };
+    }
+
+    let label = key_type.label.as_str();
+    let word = if label.starts_with("address") || label.starts_with("contract ") {
+        Address::from_str(key)
+            .map_err(|_error| invalid_key())?
+            .into_word()
+            .0
+    } else if label == "bool" {
+        let value = match key {
+            "true" => 1u8,
+            "false" => 0u8,
+            _ => return Err(invalid_key()),
+        };
+
+        U256::from(value).to_be_bytes::<32>()
+    } else if let Some(length) = label
+        .strip_prefix("bytes")
+        .and_then(|length| length.parse::<usize>().ok())
+    {
+        let bytes = hex::decode(key).map_err(|_error| invalid_key())?;
+        if length > 32 || bytes.len() != length {
+            return Err(invalid_key());
+        }
+
+        let mut word = [0u8; 32];
+        word[..length].copy_from_slice(&bytes);
+        word
+    } else if label.starts_with("int") {
+        let value = if let Some(magnitude) = key.strip_prefix('-') {
+            U256::from_str(magnitude)
+                .map_err(|_error| invalid_key())?
+                .wrapping_neg()
+        } else {
+            U256::from_str(key).map_err(|_error| invalid_key())?
+        };
+
+        value.to_be_bytes::<32>()
+    } else {
+        U256::from_str(key)
+            .map_err(|_error| invalid_key())?
+            .to_be_bytes::<32>()
+    };
+
+    Ok(word.to_vec())
+}
+
+/// Returns the length of a dynamic or static array.
+fn array_length(
+    ty: &StorageLayoutType,
+    location: Location,
+    read_slot: &mut dyn FnMut(U256) -> Result<U256, StateError>,
+) -> Result<U256, StorageVariableError> {
+    if ty.encoding == StorageEncoding::DynamicArray {
+        Ok(read_slot(location.slot)?)
+    } else {
+        // The label of a static array ends with its length, e.g. `uint256[3]`
+        ty.label
+            .strip_suffix(']')
+            .and_then(|label| label.rsplit_once('['))
+            .and_then(|(_, length)| U256::from_str(length).ok())
+            .ok_or_else(|| invalid_input(format!("Invalid array type '{}'", ty.label)))
+    }
+}
+
+/// Returns the slot of the first element of an array.
+fn array_start(ty: &StorageLayoutType, location: Location) -> U256 {
+    if ty.encoding == StorageEncoding::DynamicArray {
+        data_start(location.slot)
+    } else {
+        location.slot
+    }
+}
+
+/// Returns the location of the array element with the provided index. Elements
+/// that fit into a slot are packed.
+fn element_location(
+    start: U256,
+    index: U256,
+    element_type: &StorageLayoutType,
+) -> Result<Location, StorageVariableError> {
+    let size = type_size(element_type)?;
+
+    Ok(if size <= 32 {
+        let elements_per_slot = U256::from(32 / size);
+        Location {
+            slot: start.wrapping_add(index / elements_per_slot),
+            offset: (index % elements_per_slot).to::<u64>() * size,
+        }
+    } else {
+        Location {
+            slot: start.wrapping_add(index.wrapping_mul(U256::from(size.div_ceil(32)))),
+            offset: 0,
+        }
+    })
+}
+
+/// Returns the slot at which the data of a dynamic array or long `string` or
+/// `bytes` value starts.
+fn data_start(slot: U256) -> U256 {
+    U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0)
+}
+
+/// Returns a mask of the lowest `size` bytes.
+fn value_mask(size: u64) -> U256 {
+    if size >= 32 {
+        U256::MAX
+    } else {
+        (U256::from(1) << (size * 8)) - U256::from(1)
+    }
+}
+
+fn layout_type<'layout>(
+    layout: &'layout StorageLayout,
+    type_id: &str,
+) -> Result<&'layout StorageLayoutType, StorageVariableError> {
+    layout
+        .types
+        .get(type_id)
+        .ok_or_else(|| invalid_input(format!("Unknown type '{type_id}' in storage layout")))
+}
+
+fn related_type<'layout>(
+    ty: &'layout StorageLayoutType,
+    related: &'layout Option<String>,
+    kind: &str,
+) -> Result<&'layout str, StorageVariableError> {
+    related
+        .as_deref()
+        .ok_or_else(|| invalid_input(format!("Missing {kind} type of '{}'", ty.label)))
+}
+
+fn type_size(ty: &StorageLayoutType) -> Result<u64, StorageVariableError> {
+    ty.number_of_bytes
+        .parse::<u64>()
+        .ok()
+        .filter(|size| *size > 0)
+        .ok_or_else(|| {
+            invalid_input(format!(
+                "Invalid number of bytes '{}' of '{}'",
+                ty.number_of_bytes, ty.label
+            ))
+        })
+}
+
+fn parse_decimal(value: &str) -> Result<U256, StorageVariableError> {
+    U256::from_str_radix(value, 10)
+        .map_err(|_error| invalid_input(format!("Invalid storage slot '{value}'")))
+}
+
+fn parse_uint(value: &str) -> Result<U256, StorageVariableError> {
+    U256::from_str(value).map_err(|_error| invalid_input(format!("Invalid index '{value}'")))
+}
+
+fn invalid_input(message: String) -> StorageVariableError {
+    StorageVariableError::InvalidInput(message)
+}
+
+fn invalid_value(ty: &StorageLayoutType, value: &serde_json::Value) -> StorageVariableError {
+    invalid_input(format!("Invalid value {value} for type '{}'", ty.label))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn layout() -> anyhow::Result<StorageLayout> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "storage": [
+                { "astId": 1, "contract": "Vault", "label": "small", "offset": 0, "slot": "0", "type": "t_uint8" },
+                { "astId": 2, "contract": "Vault", "label": "delta", "offset": 1, "slot": "0", "type": "t_int16" },
+                { "astId": 3, "contract": "Vault", "label": "owner", "offset": 3, "slot": "0", "type": "t_address" },
+                { "astId": 4, "contract": "Vault", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)" },
+                { "astId": 5, "contract": "Vault", "label": "values", "offset": 0, "slot": "2", "type": "t_array(t_uint128)dyn_storage" },
+                { "astId": 6, "contract": "Vault", "label": "name", "offset": 0, "slot": "3", "type": "t_string_storage" },
+                { "astId": 7, "contract": "Vault", "label": "positions", "offset": 0, "slot": "4", "type": "t_mapping(t_uint256,t_struct(Position)1_storage)" },
+                { "astId": 10, "contract": "Vault", "label": "grid", "offset": 0, "slot": "5", "type": "t_array(t_array(t_uint256)64_storage)64_storage" }
+            ],
+            "types": {
+                "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+                "t_array(t_array(t_uint256)64_storage)64_storage": { "encoding": "inplace", "label": "uint256[64][64]", "numberOfBytes": "131072", "base": "t_array(t_uint256)64_storage" },
+                "t_array(t_uint128)dyn_storage": { "encoding": "dynamic_array", "label": "uint128[]", "numberOfBytes": "32", "base": "t_uint128" },
+                "t_array(t_uint256)64_storage": { "encoding": "inplace", "label": "uint256[64]", "numberOfBytes": "2048", "base": "t_uint256" },
+                "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+                "t_int16": { "encoding": "inplace", "label": "int16", "numberOfBytes": "2" },
+                "t_mapping(t_address,t_uint256)": { "encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)", "numberOfBytes": "32", "value": "t_uint256" },
+                "t_mapping(t_uint256,t_struct(Position)1_storage)": { "encoding": "mapping", "key": "t_uint256", "label": "mapping(uint256 => struct Vault.Position)", "numberOfBytes": "32", "value": "t_struct(Position)1_storage" },
+                "t_string_storage": { "encoding": "bytes", "label": "string", "numberOfBytes": "32" },
+                "t_struct(Position)1_storage": {
+                    "encoding": "inplace",
+                    "label": "struct Vault.Position",
+                    "numberOfBytes": "64",
+                    "members": [
+                        { "astId": 8, "contract": "Vault", "label": "amount", "offset": 0, "slot": "0", "type": "t_uint256" },
+                        { "astId": 9, "contract": "Vault", "label": "open", "offset": 0, "slot": "1", "type": "t_bool" }
+                    ]
+                },
+                "t_uint128": { "encoding": "inplace", "label": "uint128", "numberOfBytes": "16" },
+                "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+                "t_uint8": { "encoding": "inplace", "label": "uint8", "numberOfBytes": "1" }
+            }
+        }))?)
+    }
+
+    fn slot_hash(preimage: &[u8]) -> U256 {
+        U256::from_be_bytes(keccak256(preimage).0)
+    }
+
+    fn mapping_slot(key: [u8; 32], slot: u64) -> U256 {
+        let mut preimage = key.to_vec();
+        preimage.extend_from_slice(&U256::from(slot).to_be_bytes::<32>());
+        slot_hash(&preimage)
+    }
+
+    struct Fixture {
+        layout: StorageLayout,
+        storage: HashMap<U256, U256>,
+    }
+
+    impl Fixture {
+        fn read(
+            &self,
+            variable: &str,
+            keys: &[&str],
+        ) -> Result<serde_json::Value, StorageVariableError> {
+            let keys = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+            read_storage_variable(&self.layout, variable, &keys, &mut |slot| {
+                Ok(self.storage.get(&slot).copied().unwrap_or_default())
+            })
+        }
+
+        fn write(
+            &mut self,
+            variable: &str,
+            keys: &[&str],
+            value: serde_json::Value,
+        ) -> Result<(), StorageVariableError> {
+            let keys = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let writes =
+                write_storage_variable(&self.layout, variable, &keys, &value, &mut |slot| {
+                    Ok(self.storage.get(&slot).copied().unwrap_or_default())
+                })?;
+
+            self.storage.extend(writes);
+
+            Ok(())
+        }
+    }
+
+    fn fixture() -> anyhow::Result<(Fixture, Address)> {
+        let owner = Address::repeat_byte(0x11);
+
+        let mut storage = HashMap::new();
+
+        // small = 7, delta = -2, owner
+        let packed = U256::from(7)
+            | (U256::from(0xfffeu16) << 8)
+            | (U256::from_be_bytes(owner.into_word().0) << 24);
+        storage.insert(U256::ZERO, packed);
+
+        // balances[owner] = 100
+        storage.insert(mapping_slot(owner.into_word().0, 1), U256::from(100));
+
+        // values = [1, 2, 3]
+        storage.insert(U256::from(2), U256::from(3));
+        let values_start = slot_hash(&U256::from(2).to_be_bytes::<32>());
+        storage.insert(values_start, U256::from(1) | (U256::from(2) << 128));
+        storage.insert(values_start + U256::from(1), U256::from(3));
+
+        // name = "vault"
+        let mut name = [0u8; 32];
+        name[..5].copy_from_slice(b"vault");
+        name[31] = 10;
+        storage.insert(U256::from(3), U256::from_be_bytes(name));
+
+        // positions[5] = { amount: 42, open: true }
+        let position_slot = mapping_slot(U256::from(5).to_be_bytes::<32>(), 4);
+        storage.insert(position_slot, U256::from(42));
+        storage.insert(position_slot + U256::from(1), U256::from(1));
+
+        Ok((
+            Fixture {
+                layout: layout()?,
+                storage,
+            },
+            owner,
+        ))
+    }
+
+    #[test]
+    fn reads_packed_value_types() -> anyhow::Result<()> {
+        let (fixture, owner) = fixture()?;
+
+        assert_eq!(fixture.read("small", &[])?, serde_json::json!("7"));
+        assert_eq!(fixture.read("delta", &[])?, serde_json::json!("-2"));
+        assert_eq!(
+            fixture.read("owner", &[])?,
+            serde_json::json!(owner.to_checksum(None))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reads_mappings_arrays_and_structs() -> anyhow::Result<()> {
+        let (fixture, owner) = fixture()?;
+
+        assert_eq!(
+            fixture.read("balances", &[&owner.to_string()])?,
+            serde_json::json!("100")
+        );
+        assert_eq!(
+            fixture.read("balances", &["0x0000000000000000000000000000000000000001"])?,
+            serde_json::json!("0")
+        );
+
+        assert_eq!(fixture.read("values", &["1"])?, serde_json::json!("2"));
+        assert_eq!(
+            fixture.read("values", &[])?,
+            serde_json::json!(["1", "2", "3"])
+        );
+
+        assert_eq!(fixture.read("name", &[])?, serde_json::json!("vault"));
+
+        assert_eq!(
+            fixture.read("positions.amount", &["5"])?,
+            serde_json::json!("42")
+        );
+        assert_eq!(
+            fixture.read("positions", &["5"])?,
+            serde_json::json!({ "amount": "42", "open": true })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_paths() -> anyhow::Result<()> {
+        let (fixture, _owner) = fixture()?;
+
+        assert!(fixture.read("unknown", &[]).is_err());
+        assert!(fixture.read("balances", &[]).is_err());
+        assert!(fixture.read("values", &["3"]).is_err());
+        assert!(fixture.read("small", &["1"]).is_err());
+        assert!(fixture.read("positions.unknown", &["5"]).is_err());
+        assert!(fixture.read("small.member", &[]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_values_that_are_too_large_to_decode() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+
+        // A long string with a length of `u64::MAX` bytes
+        fixture
+            .storage
+            .insert(U256::from(3), (U256::from(u64::MAX) << 1) | U256::from(1));
+        assert!(fixture.read("name", &[]).is_err());
+
+        // The nested arrays have 64 * 64 elements in total
+        assert!(fixture.read("grid", &[]).is_err());
+        assert_eq!(
+            fixture.read("grid", &["1"])?,
+            serde_json::json!(vec!["0"; 64])
+        );
+        assert_eq!(fixture.read("grid", &["1", "2"])?, serde_json::json!("0"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_short_lengths() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+
+        // A short string with a length of 33 bytes
+        let mut name = [0u8; 32];
+        name[31] = 66;
+        fixture
+            .storage
+            .insert(U256::from(3), U256::from_be_bytes(name));
+        assert!(matches!(
+            fixture.read("name", &[]),
+            Err(StorageVariableError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn writes_packed_values_without_overwriting_neighbours() -> anyhow::Result<()> {
+        let (mut fixture, owner) = fixture()?;
+
+        fixture.write("delta", &[], serde_json::json!(-300))?;
+        assert_eq!(fixture.read("delta", &[])?, serde_json::json!("-300"));
+        assert_eq!(fixture.read("small", &[])?, serde_json::json!("7"));
+        assert_eq!(
+            fixture.read("owner", &[])?,
+            serde_json::json!(owner.to_checksum(None))
+        );
+
+        fixture.write("small", &[], serde_json::json!("255"))?;
+        assert_eq!(fixture.read("small", &[])?, serde_json::json!("255"));
+        assert_eq!(fixture.read("delta", &[])?, serde_json::json!("-300"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn writes_mappings_arrays_strings_and_structs() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+        let holder = Address::repeat_byte(0x22).to_string();
+
+        fixture.write("balances", &[&holder], serde_json::json!("12345"))?;
+        assert_eq!(
+            fixture.read("balances", &[&holder])?,
+            serde_json::json!("12345")
+        );
+
+        fixture.write("values", &[], serde_json::json!(["4", "5", "6", "7", "8"]))?;
+        assert_eq!(
+            fixture.read("values", &[])?,
+            serde_json::json!(["4", "5", "6", "7", "8"])
+        );
+
+        fixture.write("values", &["2"], serde_json::json!("9"))?;
+        assert_eq!(fixture.read("values", &["1"])?, serde_json::json!("5"));
+        assert_eq!(fixture.read("values", &["2"])?, serde_json::json!("9"));
+
+        let long_name = "a name that does not fit into a single storage slot";
+        fixture.write("name", &[], serde_json::json!(long_name))?;
+        assert_eq!(fixture.read("name", &[])?, serde_json::json!(long_name));
+
+        fixture.write("name", &[], serde_json::json!("short"))?;
+        assert_eq!(fixture.read("name", &[])?, serde_json::json!("short"));
+
+        fixture.write("positions.open", &["5"], serde_json::json!(false))?;
+        assert_eq!(
+            fixture.read("positions", &["5"])?,
+            serde_json::json!({ "amount": "42", "open": false })
+        );
+
+        fixture.write(
+            "positions",
+            &["6"],
+            serde_json::json!({ "amount": "1", "open": true }),
+        )?;
+        assert_eq!(
+            fixture.read("positions", &["6"])?,
+            serde_json::json!({ "amount": "1", "open": true })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn shrinking_writes_clear_unused_slots() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+        let slot = |fixture: &Fixture, slot: U256| fixture.storage.get(&slot).copied();
+
+        let values_start = slot_hash(&U256::from(2).to_be_bytes::<32>());
+        fixture.write("values", &[], serde_json::json!(["9"]))?;
+        assert_eq!(fixture.read("values", &[])?, serde_json::json!(["9"]));
+        assert_eq!(slot(&fixture, values_start), Some(U256::from(9)));
+        assert_eq!(
+            slot(&fixture, values_start + U256::from(1)),
+            Some(U256::ZERO)
+        );
+
+        let name_start = slot_hash(&U256::from(3).to_be_bytes::<32>());
+        let long_name = "a name that does not fit into two storage slots, as it is long";
+        fixture.write("name", &[], serde_json::json!(long_name))?;
+        assert_ne!(slot(&fixture, name_start + U256::from(1)), Some(U256::ZERO));
+
+        fixture.write("name", &[], serde_json::json!("short"))?;
+        assert_eq!(slot(&fixture, name_start), Some(U256::ZERO));
+        assert_eq!(slot(&fixture, name_start + U256::from(1)), Some(U256::ZERO));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_clearing_values_that_are_too_long() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+
+        // A long string with a length of close to 2^255 bytes
+        fixture.storage.insert(U256::from(3), U256::MAX);
+        assert!(matches!(
+            fixture.write("name", &[], serde_json::json!("short")),
+            Err(StorageVariableError::InvalidInput(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_values_of_wrong_type() -> anyhow::Result<()> {
+        let (mut fixture, _owner) = fixture()?;
+
+        assert!(fixture.write("small", &[], serde_json::json!(256)).is_err());
+        assert!(fixture.write("small", &[], serde_json::json!(-1)).is_err());
+        assert!(fixture
+            .write("delta", &[], serde_json::json!(32768))
+            .is_err());
+        assert!(fixture
+            .write("delta", &[], serde_json::json!(-32769))
+            .is_err());
+        assert!(fixture
+            .write("owner", &[], serde_json::json!("0x1234"))
+            .is_err());
+        assert!(fixture
+            .write("balances", &[], serde_json::json!("1"))
+            .is_err());
+        assert!(fixture
+            .write("values", &[], serde_json::json!("1"))
+            .is_err());
+        assert!(fixture
+            .write("positions", &["5"], serde_json::json!({ "unknown": "1" }))
+            .is_err());
+
+        fixture.write("delta", &[], serde_json::json!(-32768))?;
+        assert_eq!(fixture.read("delta", &[])?, serde_json::json!("-32768"));
+
+        Ok(())
+    }
+}
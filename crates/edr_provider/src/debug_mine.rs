@@ -6,8 +6,8 @@ use edr_block_builder_api::BuiltBlockAndState;
 use edr_chain_spec::{ChainSpec, ExecutableTransaction, HaltReasonTrait};
 use edr_chain_spec_block::BlockChainSpec;
 use edr_chain_spec_evm::result::ExecutionResult;
-use edr_primitives::{Bytes, B256};
-use edr_state_api::{DynState, StateDiff};
+use edr_primitives::{Address, Bytes, HashMap, B256};
+use edr_state_api::{account::Account, DynState, StateDiff};
 use edr_tracing::Trace;
 
 /// The result of mining a block, including the state, in debug mode. This
@@ -21,6 +21,8 @@ pub struct DebugMineBlockResultAndState<HaltReasonT: HaltReasonTrait, LocalBlock
     pub state_diff: StateDiff,
     /// Transaction results
     pub transaction_results: Vec<ExecutionResult<HaltReasonT>>,
+    /// The changes that each transaction made to the state
+    pub transaction_state_changes: Vec<HashMap<Address, Account>>,
     /// Transaction traces
     pub transaction_traces: Vec<Trace<HaltReasonT>>,
     /// Encoded `console.log` call inputs
@@ -42,6 +44,7 @@ impl<HaltReasonT: HaltReasonTrait, LocalBlockT>
             state: result.state,
             state_diff: result.state_diff,
             transaction_results: result.transaction_results,
+            transaction_state_changes: result.transaction_state_changes,
             transaction_traces,
             console_log_inputs: console_log_decoded_messages,
         }
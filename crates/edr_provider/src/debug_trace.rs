@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt::Debug};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 use edr_block_header::BlockHeader;
 use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
@@ -43,6 +43,7 @@ pub fn debug_trace_transaction<'header, ChainSpecT: BlockChainSpec>(
     transactions: Vec<ChainSpecT::SignedTransaction>,
     transaction_hash: &B256,
     observer_config: EvmObserverConfig,
+    struct_log_stream: Option<StructLogStream>,
 ) -> Result<
     DebugTraceResultWithTraces<ChainSpecT::HaltReason>,
     DebugTraceErrorForChainSpec<ChainSpecT>,
@@ -59,6 +60,8 @@ pub fn debug_trace_transaction<'header, ChainSpecT: BlockChainSpec>(
     for transaction in transactions {
         if transaction.transaction_hash() == transaction_hash {
             let mut eip3155_tracer = TracerEip3155::new(trace_config);
+            eip3155_tracer.stream = struct_log_stream;
+
             let mut evm_observer = EvmObserver::new(observer_config);
 
             let ExecutionResultAndState { result, .. } =
@@ -74,7 +77,7 @@ pub fn debug_trace_transaction<'header, ChainSpecT: BlockChainSpec>(
 
             let EvmObserver {
                 code_coverage,
-                console_logger: _console_logger,
+                console_logger,
                 mocker: _mocker,
                 trace_collector,
             } = evm_observer;
@@ -89,6 +92,7 @@ pub fn debug_trace_transaction<'header, ChainSpecT: BlockChainSpec>(
                 result,
                 trace_collector,
                 eip3155_tracer,
+                console_logger.into_encoded_messages(),
             ));
         } else {
             run::<ChainSpecT, _, _, _>(
@@ -113,8 +117,15 @@ pub fn execution_result_to_debug_result<HaltReasonT: HaltReasonTrait>(
     execution_result: ExecutionResult<HaltReasonT>,
     raw_tracer: TraceCollector<HaltReasonT>,
     eip3155_tracer: TracerEip3155,
+    console_log_inputs: Vec<Bytes>,
 ) -> DebugTraceResultWithTraces<HaltReasonT> {
     let traces = raw_tracer.into_traces();
+    let console_logs = eip3155_tracer
+        .config
+        .enable_console_logs
+        .then_some(console_log_inputs);
+
+    let logs = eip3155_tracer.into_logs();
 
     let result = match execution_result {
         ExecutionResult::Success {
@@ -123,19 +134,22 @@ pub fn execution_result_to_debug_result<HaltReasonT: HaltReasonTrait>(
             pass: true,
             gas_used,
             output: Some(output.into_data()),
-            logs: eip3155_tracer.logs,
+            logs,
+            console_logs,
         },
         ExecutionResult::Revert { gas_used, output } => DebugTraceResult {
             pass: false,
             gas_used,
             output: Some(output),
-            logs: eip3155_tracer.logs,
+            logs,
+            console_logs,
         },
         ExecutionResult::Halt { gas_used, .. } => DebugTraceResult {
             pass: false,
             gas_used,
             output: None,
-            logs: eip3155_tracer.logs,
+            logs,
+            console_logs,
         },
     };
 
@@ -151,6 +165,10 @@ pub struct DebugTraceConfig {
     pub disable_memory: bool,
     /// Disable stack trace.
     pub disable_stack: bool,
+    /// Include the ABI-encoded `console.log` inputs in the result.
+    pub enable_console_logs: bool,
+    /// The maximum number of struct logs to capture. Unlimited if `None`.
+    pub limit: Option<u64>,
 }
 
 /// Helper type for a chain-specific [`DebugTraceError`].
@@ -180,6 +198,9 @@ pub enum DebugTraceError<TransactionValidationErrorT> {
     /// An error occurred while invoking a `SyncOnCollectedCoverageCallback`.
     #[error(transparent)]
     OnCollectedCoverageCallback(Box<dyn std::error::Error + Send + Sync>),
+    /// State error.
+    #[error(transparent)]
+    State(#[from] StateError),
     /// Transaction error.
     #[error(transparent)]
     TransactionError(
@@ -204,6 +225,10 @@ pub struct DebugTraceResult {
     /// The EIP-3155 debug logs.
     #[serde(rename = "structLogs")]
     pub logs: Vec<DebugTraceLogItem>,
+    /// The ABI-encoded inputs of `console.log` calls made during execution.
+    /// Only present if requested in the trace config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub console_logs: Option<Vec<Bytes>>,
 }
 
 /// Result of a `debug_traceTransaction` call with traces.
@@ -242,22 +267,60 @@ pub struct DebugTraceLogItem {
     /// Description of an error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
-    /// Array of all allocated values as hex strings.
+    /// Array of all allocated values as hex strings. Consecutive struct logs
+    /// with the same memory share the allocation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub memory: Option<Vec<String>>,
+    pub memory: Option<Arc<[String]>>,
     /// Map of all stored values with keys and values encoded as hex strings.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub storage: Option<HashMap<String, String>>,
 }
 
+/// Receives the struct logs of a trace in chunks, while the transaction is
+/// being traced. This avoids materializing giant traces in memory.
+pub struct StructLogStream {
+    chunk_size: usize,
+    on_chunk: Box<dyn FnMut(Vec<DebugTraceLogItem>) + Send>,
+}
+
+impl StructLogStream {
+    /// Constructs a new instance that calls `on_chunk` with at most
+    /// `chunk_size` struct logs at a time.
+    pub fn new(
+        chunk_size: usize,
+        on_chunk: impl FnMut(Vec<DebugTraceLogItem>) + Send + 'static,
+    ) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            on_chunk: Box::new(on_chunk),
+        }
+    }
+}
+
+impl Debug for StructLogStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StructLogStream")
+            .field("chunk_size", &self.chunk_size)
+            .finish_non_exhaustive()
+    }
+}
+
 /// An EIP-3155 compatible EVM tracer.
 #[derive(Debug)]
 pub struct TracerEip3155 {
     config: DebugTraceConfig,
     logs: Vec<DebugTraceLogItem>,
+    /// The total number of captured struct logs, including streamed ones.
+    num_logs: u64,
+    /// If present, struct logs are streamed instead of collected.
+    stream: Option<StructLogStream>,
     contract_address: Address,
     gas_remaining: u64,
-    memory: Vec<u8>,
+    /// The hex-encoded memory of the current call frame, which is only
+    /// re-encoded when it might have changed. It's shared with the struct
+    /// logs that were captured since.
+    memory: Arc<[String]>,
+    memory_is_stale: bool,
     mem_size: usize,
     opcode: u8,
     pc: usize,
@@ -272,12 +335,15 @@ impl TracerEip3155 {
         Self {
             config,
             logs: Vec::default(),
+            num_logs: 0,
+            stream: None,
             contract_address: Address::default(),
             stack: Vec::new(),
             pc: 0,
             opcode: 0,
             gas_remaining: 0,
-            memory: Vec::default(),
+            memory: Arc::default(),
+            memory_is_stale: true,
             mem_size: 0,
             storage: HashMap::default(),
         }
@@ -289,9 +355,64 @@ impl TracerEip3155 {
         } else {
             result.gas.remaining()
         };
+
+        // Execution returns to the parent's memory, which was modified by the return
+        // data
+        self.memory_is_stale = true;
+    }
+
+    /// Converts the tracer into the collected struct logs. If the struct logs
+    /// are being streamed, the remaining ones are flushed and an empty vector
+    /// is returned.
+    fn into_logs(mut self) -> Vec<DebugTraceLogItem> {
+        if let Some(stream) = self.stream.as_mut().filter(|_| !self.logs.is_empty()) {
+            (stream.on_chunk)(std::mem::take(&mut self.logs));
+        }
+
+        self.logs
+    }
+
+    fn is_limit_reached(&self) -> bool {
+        self.config
+            .limit
+            .is_some_and(|limit| self.num_logs >= limit)
+    }
+
+    fn push_log(&mut self, log_item: DebugTraceLogItem) {
+        self.logs.push(log_item);
+        self.num_logs += 1;
+
+        let Some(stream) = &mut self.stream else {
+            return;
+        };
+
+        if self.logs.len() >= stream.chunk_size {
+            (stream.on_chunk)(std::mem::take(&mut self.logs));
+        }
     }
 }
 
+/// Whether the opcode can modify the memory of the current call frame or
+/// switch to the memory of a new call frame.
+fn may_change_memory(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        opcode::MSTORE
+            | opcode::MSTORE8
+            | opcode::MCOPY
+            | opcode::CALLDATACOPY
+            | opcode::CODECOPY
+            | opcode::EXTCODECOPY
+            | opcode::RETURNDATACOPY
+            | opcode::CALL
+            | opcode::CALLCODE
+            | opcode::DELEGATECALL
+            | opcode::STATICCALL
+            | opcode::CREATE
+            | opcode::CREATE2
+    )
+}
+
 impl<ContextT: ContextTrait<Journal: JournalExt>> Inspector<ContextT> for TracerEip3155 {
     fn call_end(
         &mut self,
@@ -312,6 +433,10 @@ impl<ContextT: ContextTrait<Journal: JournalExt>> Inspector<ContextT> for Tracer
     }
 
     fn step(&mut self, interpreter: &mut Interpreter<EthInterpreter>, _context: &mut ContextT) {
+        if self.is_limit_reached() {
+            return;
+        }
+
         self.contract_address = interpreter.input.target_address();
         self.gas_remaining = interpreter.gas.remaining();
 
@@ -319,18 +444,30 @@ impl<ContextT: ContextTrait<Journal: JournalExt>> Inspector<ContextT> for Tracer
             self.stack.clone_from(interpreter.stack.data());
         }
 
-        let shared_memory = &interpreter.memory;
-        if !self.config.disable_memory {
-            self.memory = shared_memory.context_memory().to_vec();
+        let context_memory = interpreter.memory.context_memory();
+        // Memory expansion only appends zeros, but still needs to be reflected
+        if !self.config.disable_memory
+            && (self.memory_is_stale || context_memory.len() != self.mem_size)
+        {
+            self.memory = context_memory.chunks(32).map(hex::encode).collect();
+            self.memory_is_stale = false;
         }
 
-        self.mem_size = shared_memory.context_memory().len();
+        self.mem_size = context_memory.len();
 
         self.opcode = interpreter.bytecode.opcode();
         self.pc = interpreter.bytecode.pc();
     }
 
     fn step_end(&mut self, interpreter: &mut Interpreter<EthInterpreter>, context: &mut ContextT) {
+        if self.is_limit_reached() {
+            return;
+        }
+
+        if may_change_memory(self.opcode) {
+            self.memory_is_stale = true;
+        }
+
         let journal = context.journal();
         let depth = journal.depth() as u64;
 
@@ -348,7 +485,7 @@ impl<ContextT: ContextTrait<Journal: JournalExt>> Inspector<ContextT> for Tracer
         let memory = if self.config.disable_memory {
             None
         } else {
-            Some(self.memory.chunks(32).map(hex::encode).collect())
+            Some(self.memory.clone())
         };
 
         let storage = if self.config.disable_storage {
@@ -410,6 +547,6 @@ impl<ContextT: ContextTrait<Journal: JournalExt>> Inspector<ContextT> for Tracer
             memory,
             storage,
         };
-        self.logs.push(log_item);
+        self.push_log(log_item);
     }
 }
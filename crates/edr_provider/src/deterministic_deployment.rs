@@ -0,0 +1,33 @@
+//! The deterministic deployment proxy, which deploys contracts with `CREATE2`
+//! so their addresses only depend on the salt and init code.
+//!
+//! See <https://github.com/Arachnid/deterministic-deployment-proxy>.
+
+use edr_primitives::{address, bytes, Address, Bytecode, Bytes};
+
+use crate::AccountOverride;
+
+/// The address of the deterministic deployment proxy, which is the same on
+/// every chain that it has been deployed to.
+pub const DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS: Address =
+    address!("0x4e59b44847b379578588920ca78fbf26c0b4956c");
+
+/// The runtime bytecode of the deterministic deployment proxy.
+///
+/// The proxy expects calldata consisting of a 32-byte salt followed by the
+/// init code, and returns the address of the created contract.
+pub const DETERMINISTIC_DEPLOYMENT_PROXY_BYTECODE: Bytes = bytes!(
+    "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3"
+);
+
+/// Constructs the genesis account override of the deterministic deployment
+/// proxy.
+///
+/// Only the code is overridden, so the balance and nonce of an existing
+/// account are retained when forking.
+pub(crate) fn deterministic_deployment_proxy() -> AccountOverride {
+    AccountOverride {
+        code: Some(Bytecode::new_raw(DETERMINISTIC_DEPLOYMENT_PROXY_BYTECODE)),
+        ..AccountOverride::default()
+    }
+}
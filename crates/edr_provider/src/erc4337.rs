@@ -0,0 +1,544 @@
+//! Local simulation of ERC-4337 user operation validation, enforcing the
+//! validation rules that bundlers apply before accepting a user operation
+//! into their mempool (ERC-7562).
+
+use alloy_sol_types::{SolCall as _, SolError as _};
+use edr_chain_spec_evm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EthInterpreter, InputsTr as _,
+        Interpreter,
+    },
+    ContextTrait, Inspector,
+};
+use edr_primitives::{
+    bytecode::opcode::{self, OpCode},
+    keccak256, Address, Bytes, B256, U256,
+};
+
+use crate::requests::hardhat::rpc_types::UserOperation;
+
+alloy_sol_types::sol! {
+    struct PackedUserOperation {
+        address sender;
+        uint256 nonce;
+        bytes initCode;
+        bytes callData;
+        bytes32 accountGasLimits;
+        uint256 preVerificationGas;
+        bytes32 gasFees;
+        bytes paymasterAndData;
+        bytes signature;
+    }
+
+    function handleOps(PackedUserOperation[] ops, address beneficiary);
+    function validateUserOp(PackedUserOperation userOp, bytes32 userOpHash, uint256 missingAccountFunds);
+    function validatePaymasterUserOp(PackedUserOperation userOp, bytes32 userOpHash, uint256 maxCost);
+
+    error FailedOp(uint256 opIndex, string reason);
+    error FailedOpWithRevert(uint256 opIndex, string reason, bytes inner);
+}
+
+/// The number of storage slots following a `keccak256(address || x)` slot
+/// that are considered associated with the address, allowing for structs
+/// stored in mappings.
+const ASSOCIATED_SLOT_RANGE: u64 = 128;
+
+/// An entity that participates in the validation of a user operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UserOperationEntity {
+    /// The smart contract account that sends the user operation.
+    Account,
+    /// The factory that deploys the account.
+    Factory,
+    /// The paymaster that sponsors the user operation.
+    Paymaster,
+}
+
+impl std::fmt::Display for UserOperationEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserOperationEntity::Account => write!(f, "account"),
+            UserOperationEntity::Factory => write!(f, "factory"),
+            UserOperationEntity::Paymaster => write!(f, "paymaster"),
+        }
+    }
+}
+
+/// A validation rule that was violated during the validation of a user
+/// operation.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "rule", rename_all = "camelCase")]
+pub enum UserOperationRule {
+    /// An opcode that is banned during validation was executed.
+    BannedOpcode { opcode: String },
+    /// A contract was created other than the account by its factory.
+    Create { opcode: String },
+    /// The `GAS` opcode was executed without being followed by a call.
+    GasNotFollowedByCall,
+    /// Storage was accessed that isn't associated with the account.
+    #[serde(rename_all = "camelCase")]
+    StorageAccess { address: Address, slot: U256 },
+}
+
+/// A violation of a validation rule by one of the entities of a user
+/// operation.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationViolation {
+    pub entity: UserOperationEntity,
+    pub entity_address: Address,
+    #[serde(flatten)]
+    pub rule: UserOperationRule,
+}
+
+impl std::fmt::Display for UserOperationViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            entity,
+            entity_address,
+            rule,
+        } = self;
+
+        match rule {
+            UserOperationRule::BannedOpcode { opcode } => {
+                write!(f, "{entity} {entity_address} uses banned opcode {opcode}")
+            }
+            UserOperationRule::Create { opcode } => {
+                write!(f, "{entity} {entity_address} uses unallowed {opcode}")
+            }
+            UserOperationRule::GasNotFollowedByCall => write!(
+                f,
+                "{entity} {entity_address} uses GAS without immediately calling"
+            ),
+            UserOperationRule::StorageAccess { address, slot } => write!(
+                f,
+                "{entity} {entity_address} accesses unassociated storage slot {slot:#x} of {address}"
+            ),
+        }
+    }
+}
+
+/// The addresses of the entities of a user operation.
+#[derive(Clone, Debug)]
+pub struct UserOperationEntities {
+    pub entry_point: Address,
+    pub sender: Address,
+    pub factory: Option<Address>,
+    pub paymaster: Option<Address>,
+}
+
+impl UserOperationEntities {
+    /// Constructs the entities of the provided user operation.
+    pub fn new(user_operation: &UserOperation, entry_point: Address) -> Self {
+        Self {
+            entry_point,
+            sender: user_operation.sender,
+            factory: user_operation.factory,
+            paymaster: user_operation.paymaster,
+        }
+    }
+
+    fn address(&self, entity: UserOperationEntity) -> Address {
+        match entity {
+            UserOperationEntity::Account => Some(self.sender),
+            UserOperationEntity::Factory => self.factory,
+            UserOperationEntity::Paymaster => self.paymaster,
+        }
+        .expect("entity frames are only entered for known entities")
+    }
+}
+
+/// Encodes a call to `EntryPoint.handleOps` for the provided user operation,
+/// using the EntryPoint v0.7 packed format.
+pub fn encode_handle_ops(user_operation: &UserOperation, beneficiary: Address) -> Bytes {
+    handleOpsCall {
+        ops: vec![pack_user_operation(user_operation)],
+        beneficiary,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the reason of a `FailedOp` or `FailedOpWithRevert` revert by the
+/// EntryPoint.
+pub fn decode_failed_op(output: &[u8]) -> Option<String> {
+    if let Ok(FailedOp { reason, .. }) = FailedOp::abi_decode(output) {
+        Some(reason)
+    } else if let Ok(FailedOpWithRevert { reason, .. }) = FailedOpWithRevert::abi_decode(output) {
+        Some(reason)
+    } else {
+        None
+    }
+}
+
+fn pack_user_operation(user_operation: &UserOperation) -> PackedUserOperation {
+    fn concat_u128(high: u128, low: u128) -> B256 {
+        let mut packed = B256::ZERO;
+        packed[..16].copy_from_slice(&high.to_be_bytes());
+        packed[16..].copy_from_slice(&low.to_be_bytes());
+        packed
+    }
+
+    let init_code = user_operation
+        .factory
+        .map(|factory| {
+            let factory_data = user_operation.factory_data.clone().unwrap_or_default();
+            [factory.as_slice(), factory_data.as_ref()].concat()
+        })
+        .unwrap_or_default();
+
+    let paymaster_and_data = user_operation
+        .paymaster
+        .map(|paymaster| {
+            let verification_gas_limit = user_operation
+                .paymaster_verification_gas_limit
+                .unwrap_or_default()
+                .to::<u128>();
+            let post_op_gas_limit = user_operation
+                .paymaster_post_op_gas_limit
+                .unwrap_or_default()
+                .to::<u128>();
+            let paymaster_data = user_operation.paymaster_data.clone().unwrap_or_default();
+
+            [
+                paymaster.as_slice(),
+                &verification_gas_limit.to_be_bytes(),
+                &post_op_gas_limit.to_be_bytes(),
+                paymaster_data.as_ref(),
+            ]
+            .concat()
+        })
+        .unwrap_or_default();
+
+    PackedUserOperation {
+        sender: user_operation.sender,
+        nonce: user_operation.nonce,
+        initCode: init_code.into(),
+        callData: user_operation.call_data.clone(),
+        accountGasLimits: concat_u128(
+            user_operation.verification_gas_limit.to(),
+            user_operation.call_gas_limit.to(),
+        ),
+        preVerificationGas: user_operation.pre_verification_gas,
+        gasFees: concat_u128(
+            user_operation.max_priority_fee_per_gas.to(),
+            user_operation.max_fee_per_gas.to(),
+        ),
+        paymasterAndData: paymaster_and_data.into(),
+        signature: user_operation.signature.clone(),
+    }
+}
+
+/// Whether the opcode is banned for all entities during validation.
+fn is_banned_opcode(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        opcode::GASPRICE
+            | opcode::GASLIMIT
+            | opcode::DIFFICULTY
+            | opcode::TIMESTAMP
+            | opcode::BASEFEE
+            | opcode::BLOCKHASH
+            | opcode::NUMBER
+            | opcode::ORIGIN
+            | opcode::COINBASE
+            | opcode::SELFDESTRUCT
+            | opcode::BALANCE
+            | opcode::SELFBALANCE
+            | opcode::BLOBHASH
+            | opcode::BLOBBASEFEE
+            | opcode::INVALID
+    )
+}
+
+fn is_call_opcode(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL
+    )
+}
+
+fn opcode_name(opcode: u8) -> String {
+    OpCode::new(opcode).map_or_else(|| format!("0x{opcode:x}"), |opcode| opcode.to_string())
+}
+
+/// Inspector that records violations of the validation rules by the entities
+/// of a user operation, while the EntryPoint executes `handleOps`.
+///
+/// Only the rules that apply to unstaked entities are enforced.
+#[derive(Debug)]
+pub struct UserOperationTracer {
+    entities: UserOperationEntities,
+    /// The validating entity of each active call frame, if any.
+    frames: Vec<Option<UserOperationEntity>>,
+    /// Base slots of mappings keyed by the sender, derived from the
+    /// `KECCAK256` preimages encountered during validation.
+    sender_slots: Vec<U256>,
+    /// Whether the previous step of a validating entity was a `GAS` opcode.
+    is_gas_pending: bool,
+    has_created_sender: bool,
+    violations: Vec<UserOperationViolation>,
+}
+
+impl UserOperationTracer {
+    /// Constructs a new instance for the provided entities.
+    pub fn new(entities: UserOperationEntities) -> Self {
+        Self {
+            entities,
+            frames: Vec::new(),
+            sender_slots: Vec::new(),
+            is_gas_pending: false,
+            has_created_sender: false,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Returns the recorded violations.
+    pub fn into_violations(self) -> Vec<UserOperationViolation> {
+        self.violations
+    }
+
+    fn active_entity(&self) -> Option<UserOperationEntity> {
+        self.frames.last().copied().flatten()
+    }
+
+    /// Determines which entity, if any, is validating in a new call frame.
+    fn entity_for_call(&self, target: Address, input: &[u8]) -> Option<UserOperationEntity> {
+        if let Some(entity) = self.active_entity() {
+            // Nested frames are subject to the rules of the calling entity
+            return Some(entity);
+        }
+
+        let selector = input.get(..4);
+        if target == self.entities.sender
+            && selector == Some(validateUserOpCall::SELECTOR.as_slice())
+        {
+            Some(UserOperationEntity::Account)
+        } else if Some(target) == self.entities.paymaster
+            && selector == Some(validatePaymasterUserOpCall::SELECTOR.as_slice())
+        {
+            Some(UserOperationEntity::Paymaster)
+        } else if Some(target) == self.entities.factory {
+            Some(UserOperationEntity::Factory)
+        } else {
+            None
+        }
+    }
+
+    fn is_sender_slot(&self, slot: U256) -> bool {
+        slot == U256::from_be_slice(self.entities.sender.as_slice())
+            || self.sender_slots.iter().any(|base| {
+                slot.checked_sub(*base)
+                    .is_some_and(|offset| offset < U256::from(ASSOCIATED_SLOT_RANGE))
+            })
+    }
+
+    fn violate(&mut self, entity: UserOperationEntity, rule: UserOperationRule) {
+        self.violations.push(UserOperationViolation {
+            entity,
+            entity_address: self.entities.address(entity),
+            rule,
+        });
+    }
+}
+
+impl<ContextT: ContextTrait> Inspector<ContextT, EthInterpreter> for UserOperationTracer {
+    fn step(&mut self, interpreter: &mut Interpreter<EthInterpreter>, _context: &mut ContextT) {
+        let Some(entity) = self.active_entity() else {
+            return;
+        };
+
+        let opcode = interpreter.bytecode.opcode();
+
+        if std::mem::take(&mut self.is_gas_pending) && !is_call_opcode(opcode) {
+            self.violate(entity, UserOperationRule::GasNotFollowedByCall);
+        }
+
+        let stack = interpreter.stack.data();
+        match opcode {
+            opcode::GAS => self.is_gas_pending = true,
+            opcode::CREATE => self.violate(
+                entity,
+                UserOperationRule::Create {
+                    opcode: opcode_name(opcode),
+                },
+            ),
+            opcode::CREATE2 => {
+                // Only the factory may create a single contract: the sender
+                if entity == UserOperationEntity::Factory && !self.has_created_sender {
+                    self.has_created_sender = true;
+                } else {
+                    self.violate(
+                        entity,
+                        UserOperationRule::Create {
+                            opcode: opcode_name(opcode),
+                        },
+                    );
+                }
+            }
+            opcode::KECCAK256 => {
+                if let [.., size, offset] = stack.as_slice()
+                    && let (Ok(offset), Ok(size)) =
+                        (usize::try_from(*offset), usize::try_from(*size))
+                {
+                    let memory = interpreter.memory.context_memory();
+                    if size >= 32
+                        && offset.saturating_add(size) <= memory.len()
+                        && memory[offset..offset + 12].iter().all(|byte| *byte == 0)
+                        && memory[offset + 12..offset + 32] == *self.entities.sender.as_slice()
+                    {
+                        let preimage = &memory[offset..offset + size];
+                        self.sender_slots
+                            .push(U256::from_be_bytes(keccak256(preimage).0));
+                    }
+                }
+            }
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Some(slot) = stack.last() {
+                    let address = interpreter.input.target_address();
+                    let is_allowed = address == self.entities.sender
+                        || address == self.entities.entry_point
+                        || self.is_sender_slot(*slot);
+
+                    if !is_allowed {
+                        self.violate(
+                            entity,
+                            UserOperationRule::StorageAccess {
+                                address,
+                                slot: *slot,
+                            },
+                        );
+                    }
+                }
+            }
+            _ if is_banned_opcode(opcode) => self.violate(
+                entity,
+                UserOperationRule::BannedOpcode {
+                    opcode: opcode_name(opcode),
+                },
+            ),
+            _ => (),
+        }
+    }
+
+    fn call(&mut self, context: &mut ContextT, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let input = inputs.input.bytes(context);
+        let entity = self.entity_for_call(inputs.target_address, &input);
+        self.frames.push(entity);
+
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &CallInputs,
+        _outcome: &mut CallOutcome,
+    ) {
+        self.frames.pop();
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frames.push(self.active_entity());
+
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frames.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_primitives::{address, U128};
+
+    use super::*;
+
+    fn user_operation() -> UserOperation {
+        UserOperation {
+            sender: address!("0x1111111111111111111111111111111111111111"),
+            nonce: U256::from(1),
+            factory: None,
+            factory_data: None,
+            call_data: Bytes::from_static(&[0xab]),
+            call_gas_limit: U128::from(2),
+            verification_gas_limit: U128::from(3),
+            pre_verification_gas: U256::from(4),
+            max_fee_per_gas: U128::from(5),
+            max_priority_fee_per_gas: U128::from(6),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::from_static(&[0xcd]),
+        }
+    }
+
+    #[test]
+    fn pack_gas_fields() {
+        let packed = pack_user_operation(&user_operation());
+
+        assert!(packed.initCode.is_empty());
+        assert!(packed.paymasterAndData.is_empty());
+
+        assert_eq!(
+            U256::from_be_bytes(packed.accountGasLimits.0),
+            (U256::from(3) << 128) | U256::from(2)
+        );
+        assert_eq!(
+            U256::from_be_bytes(packed.gasFees.0),
+            (U256::from(6) << 128) | U256::from(5)
+        );
+    }
+
+    #[test]
+    fn pack_factory_and_paymaster() {
+        let factory = address!("0x2222222222222222222222222222222222222222");
+        let paymaster = address!("0x3333333333333333333333333333333333333333");
+
+        let packed = pack_user_operation(&UserOperation {
+            factory: Some(factory),
+            factory_data: Some(Bytes::from_static(&[0x01, 0x02])),
+            paymaster: Some(paymaster),
+            paymaster_verification_gas_limit: Some(U128::from(7)),
+            paymaster_post_op_gas_limit: Some(U128::from(8)),
+            paymaster_data: Some(Bytes::from_static(&[0x03])),
+            ..user_operation()
+        });
+
+        assert_eq!(&packed.initCode[..20], factory.as_slice());
+        assert_eq!(&packed.initCode[20..], &[0x01, 0x02]);
+
+        assert_eq!(packed.paymasterAndData.len(), 20 + 16 + 16 + 1);
+        assert_eq!(&packed.paymasterAndData[..20], paymaster.as_slice());
+        assert_eq!(packed.paymasterAndData[35], 7);
+        assert_eq!(packed.paymasterAndData[51], 8);
+        assert_eq!(packed.paymasterAndData[52], 0x03);
+    }
+
+    #[test]
+    fn decode_failed_op_reason() {
+        let output = FailedOp {
+            opIndex: U256::ZERO,
+            reason: "AA21 didn't pay prefund".to_owned(),
+        }
+        .abi_encode();
+
+        assert_eq!(
+            decode_failed_op(&output).as_deref(),
+            Some("AA21 didn't pay prefund")
+        );
+        assert_eq!(decode_failed_op(&[0xde, 0xad]), None);
+    }
+}
@@ -3,7 +3,7 @@
 #![allow(clippy::trait_duplication_in_bounds)]
 
 use core::fmt::Debug;
-use std::{ffi::OsString, num::TryFromIntError, time::SystemTime};
+use std::{ffi::OsString, num::TryFromIntError, path::PathBuf, time::SystemTime};
 
 use alloy_sol_types::{ContractError, SolInterface};
 use edr_block_api::GenesisBlockFactory;
@@ -23,14 +23,16 @@ use edr_primitives::{hex, Address, Bytes, B256, U256};
 use edr_rpc_eth::{client::RpcClientError, error::HttpError, jsonrpc};
 use edr_runtime::{overrides::AccountOverrideConversionError, transaction};
 use edr_signer::SignatureError;
-use edr_solidity::contract_decoder::ContractDecoderError;
+use edr_solidity::contract_decoder::{ContractDecoder, ContractDecoderError};
 use edr_state_api::StateError;
 use edr_tracing::Trace;
 use serde::Serialize;
 
 use crate::{
-    config::IntervalConfigConversionError, debug_trace::DebugTraceError, time::TimeSinceEpoch,
-    ProviderSpec,
+    chain_export::ChainImportError, checkpoint::CheckpointError,
+    config::IntervalConfigConversionError, debug_trace::DebugTraceError,
+    erc4337::UserOperationViolation, keystore::KeystoreError, log_filter::LogFilterError,
+    raw_block::RawBlockImportError, time::TimeSinceEpoch, ProviderSpec,
 };
 
 /// Helper type for a chain-specific [`CreationError`].
@@ -50,6 +52,13 @@ pub enum CreationError<GenesisBlockCreationErrorT, HardforkT> {
     /// An error that occurred while constructing a forked blockchain.
     #[error(transparent)]
     ForkedBlockchainCreation(#[from] ForkedCreationError<HardforkT>),
+    /// The checkpoint directory could not be resolved to an absolute path.
+    #[error("Invalid checkpoint directory {path:?}: {source}")]
+    InvalidCheckpointDir {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
     /// Invalid genesis block.
     #[error(transparent)]
     InvalidGenesisBlock(InvalidGenesisBlock),
@@ -122,13 +131,15 @@ pub enum ProviderError<
         "Nonce too low. Expected nonce to be {expected} but got {actual}. Note that transactions can't be queued when automining."
     )]
     AutoMineNonceTooLow { expected: u64, actual: u64 },
-    #[error(
-        "An EIP-4844 (shard blob) transaction was received while auto-mine was disabled or the mempool contained transactions, but Hardhat doesn't have support for them yet. See https://github.com/NomicFoundation/hardhat/issues/5024"
-    )]
-    BlobMemPoolUnsupported,
     /// Blockchain error
     #[error(transparent)]
     Blockchain(#[from] DynBlockchainError),
+    /// An error occurred while importing a chain.
+    #[error(transparent)]
+    ChainImport(#[from] ChainImportError),
+    /// An error occurred while saving or loading a checkpoint.
+    #[error(transparent)]
+    Checkpoint(#[from] CheckpointError),
     #[error(transparent)]
     Creation(#[from] CreationError<GenesisBlockCreationErrorT, HardforkT>),
     #[error(transparent)]
@@ -155,6 +166,15 @@ pub enum ProviderError<
     /// Failed to fetch transaction receipt
     #[error(transparent)]
     FetchReceipt(FetchReceiptErrorT),
+    /// The gas sponsor can't pay for the gas of a sponsored transaction.
+    #[error(
+        "Gas sponsor {sponsor} has insufficient funds to pay for the transaction's gas. Balance: {balance}, required: {required}"
+    )]
+    GasSponsorInsufficientFunds {
+        sponsor: Address,
+        balance: U256,
+        required: U256,
+    },
     #[error("{0}")]
     InvalidArgument(String),
     /// Block number or hash doesn't exist in blockchain
@@ -183,6 +203,13 @@ pub enum ProviderError<
     /// The EIP-155 transaction was signed with another chain ID
     #[error("Trying to send an incompatible EIP-155 transaction, signed for another chain.")]
     InvalidEip155TransactionChainId,
+    /// The declared `EIP712Domain` type doesn't match the fields of the
+    /// provided domain, so the domain separator would differ from the one
+    /// computed by wallets.
+    #[error(
+        "The declared EIP712Domain type '{declared}' doesn't match the provided domain, which requires '{expected}'"
+    )]
+    InvalidEip712Domain { declared: String, expected: String },
     /// Invalid filter subscription type
     #[error(
         "Subscription {filter_id} is not a {expected:?} subscription, but a {actual:?} subscription"
@@ -192,8 +219,28 @@ pub enum ProviderError<
         expected: SubscriptionType,
         actual: SubscriptionType,
     },
+    /// Invalid forkchoice state for `engine_forkchoiceUpdatedV3`
+    #[error("Invalid forkchoice state: {0}")]
+    InvalidForkchoiceState(String),
+    /// Invalid block number for `debug_setHead`
+    #[error(
+        "Cannot set the head to block {block_number}. The block number must be between {first_local_block_number} and {latest_block_number}."
+    )]
+    InvalidHeadBlockNumber {
+        block_number: u64,
+        first_local_block_number: u64,
+        latest_block_number: u64,
+    },
     #[error("{0}")]
     InvalidInput(String),
+    /// Invalid payload attributes for `engine_forkchoiceUpdatedV3`
+    #[error("Invalid payload attributes: {0}")]
+    InvalidPayloadAttributes(String),
+    /// Invalid reorganisation depth
+    #[error(
+        "Cannot reorganise {depth} blocks. The depth must be between 1 and the number of local blocks ({max_depth})."
+    )]
+    InvalidReorgDepth { depth: u64, max_depth: u64 },
     /// Transaction hash doesn't exist on the blockchain.
     #[error("Transaction hash '{0}' doesn't exist on the blockchain.")]
     InvalidTransactionHash(B256),
@@ -205,15 +252,44 @@ pub enum ProviderError<
     InvalidTransactionInput(String),
     #[error("Invalid transaction type {0}.")]
     InvalidTransactionType(u8),
+    /// An error occurred while managing keystore accounts.
+    #[error(transparent)]
+    Keystore(#[from] KeystoreError),
+    /// An error occurred while changing the log filter.
+    #[error(transparent)]
+    LogFilter(#[from] LogFilterError),
     /// An error occurred while logging.
     #[error("Failed to log: {0}")]
     Logger(Box<dyn std::error::Error + Send + Sync>),
+    /// The block range of an `eth_getLogs` request exceeds the configured
+    /// maximum.
+    #[error(
+        "Query exceeds max block range {max_block_range}. Requested block range [{from_block:#x}, {to_block:#x}]."
+    )]
+    LogsBlockRangeExceeded {
+        from_block: u64,
+        to_block: u64,
+        max_block_range: u64,
+    },
+    /// An `eth_getLogs` request matched more logs than the configured maximum.
+    /// The provided block range is the one that can be queried to continue.
+    #[error(
+        "Query returned more than {max_results} results. Try with this block range [{from_block:#x}, {to_block:#x}]."
+    )]
+    LogsResultsExceeded {
+        from_block: u64,
+        to_block: u64,
+        max_results: u64,
+    },
     /// An error occurred while adding a pending transaction to the mem pool.
     #[error(transparent)]
     MemPoolAddTransaction(#[from] MemPoolAddTransactionError<StateError>),
     /// An error occurred while updating the mem pool.
     #[error(transparent)]
     MemPoolUpdate(StateError),
+    /// The method is not permitted by the provider's method permissions.
+    #[error("Method {method} is not permitted by the provider's configuration")]
+    MethodNotPermitted { method: String },
     /// An error occurred while mining a block.
     #[error(transparent)]
     MineBlock(
@@ -231,6 +307,9 @@ pub enum ProviderError<
     /// An error occurred while invoking a `SyncOnCollectedGasReportCallback`.
     #[error(transparent)]
     OnCollectedGasReportCallback(Box<dyn std::error::Error + Send + Sync>),
+    /// An error occurred while importing RLP-encoded blocks.
+    #[error(transparent)]
+    RawBlockImport(#[from] RawBlockImportError),
     /// Rpc client error
     #[error(transparent)]
     RpcClientError(#[from] RpcClientError),
@@ -246,10 +325,6 @@ pub enum ProviderError<
             TransactionValidationErrorT,
         >,
     ),
-    /// The `hardhat_setMinGasPrice` method is not supported when EIP-1559 is
-    /// active.
-    #[error("hardhat_setMinGasPrice is not supported when EIP-1559 is active")]
-    SetMinGasPriceUnsupported,
     /// Serialization error
     #[error("Failed to serialize response: {0}")]
     Serialization(serde_json::Error),
@@ -258,6 +333,10 @@ pub enum ProviderError<
     /// Cannot set account nonce when the mem pool is not empty
     #[error("Cannot set account nonce when the transaction pool is not empty")]
     SetAccountNonceWithPendingTransactions,
+    /// The `hardhat_setBeaconRoot` method is not supported due to an older
+    /// hardfork.
+    #[error("hardhat_setBeaconRoot is disabled because EIP-4788 is not active")]
+    SetBeaconRootUnsupported { hardfork: HardforkT },
     /// `evm_setBlockGasLimit` was called with a gas limit of zero.
     #[error("Block gas limit must be greater than 0")]
     SetBlockGasLimitMustBeGreaterThanZero,
@@ -268,12 +347,10 @@ pub enum ProviderError<
     /// an older hardfork.
     #[error("hardhat_setNextBlockBaseFeePerGas is disabled because EIP-1559 is not active")]
     SetNextBlockBaseFeePerGasUnsupported { hardfork: HardforkT },
-    /// The `hardhat_setPrevRandao` method is not supported due to an older
-    /// hardfork.
-    #[error(
-        "hardhat_setPrevRandao is only available in post-merge hardforks, the current hardfork is {hardfork:?}"
-    )]
-    SetNextPrevRandaoUnsupported { hardfork: HardforkT },
+    /// The `hardhat_setNextBlockWithdrawals` method is not supported due to
+    /// an older hardfork.
+    #[error("hardhat_setNextBlockWithdrawals is disabled because EIP-4895 is not active")]
+    SetNextBlockWithdrawalsUnsupported { hardfork: HardforkT },
     /// An error occurred while recovering a signature.
     #[error(transparent)]
     Signature(#[from] SignatureError),
@@ -298,6 +375,12 @@ pub enum ProviderError<
     /// [`crate::config::Provider::bail_on_call_failure`] was enabled
     #[error(transparent)]
     TransactionFailed(Box<TransactionFailureWithTraces<HaltReasonT>>),
+    /// The transaction firewall rejected the transaction.
+    #[error("Transaction {transaction_hash} was rejected by the transaction firewall: {reason}")]
+    TransactionRejected {
+        transaction_hash: B256,
+        reason: String,
+    },
     /// Failed to convert an integer type
     #[error("Could not convert the integer argument, due to: {0}")]
     TryFromIntError(#[from] TryFromIntError),
@@ -340,6 +423,21 @@ pub enum ProviderError<
         "The transaction contains EIP-7702 parameters, but they are not supported by the current hardfork: {current_hardfork:?}. Use the Prague hardfork (or later)."
     )]
     UnsupportedEip7702Parameters { current_hardfork: EvmSpecId },
+    /// An Engine API method doesn't support the current hardfork.
+    #[error("Unsupported fork: {method} only supports the {supported:?} hardfork, but the current hardfork is {actual:?}")]
+    UnsupportedFork {
+        method: String,
+        actual: EvmSpecId,
+        supported: EvmSpecId,
+    },
+    #[error(
+        "Cannot replay block {block_number}, because it includes transaction '{transaction_hash:?}' with unsupported type '{unsupported_transaction_type}'"
+    )]
+    UnsupportedTransactionTypeInBlockReplay {
+        block_number: u64,
+        transaction_hash: B256,
+        unsupported_transaction_type: u8,
+    },
     #[error(
         "Cannot perform debug tracing on transaction '{requested_transaction_hash:?}', because its block includes transaction '{unsupported_transaction_hash:?}' with unsupported type '{unsupported_transaction_type}'"
     )]
@@ -357,6 +455,29 @@ pub enum ProviderError<
     },
     #[error("{method_name} - Method not supported")]
     UnsupportedMethod { method_name: String },
+    /// The fork cannot be updated, because local blocks have been mined since
+    /// forking.
+    #[error(
+        "Cannot update the fork after mining local blocks. The fork block number is {fork_block_number}, but the latest block number is {latest_block_number}."
+    )]
+    UpdateForkAfterLocalBlocks {
+        fork_block_number: u64,
+        latest_block_number: u64,
+    },
+    /// `hardhat_updateFork` was called while not forking.
+    #[error("hardhat_updateFork is only available when forking a remote blockchain")]
+    UpdateForkUnsupported,
+    /// The EntryPoint rejected the user operation during validation.
+    #[error("User operation rejected: {reason}")]
+    UserOperationRejected { reason: String },
+    /// The user operation violates the ERC-4337 validation rules.
+    #[error(
+        "User operation violates validation rules: {}",
+        .violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    UserOperationRuleViolations {
+        violations: Vec<UserOperationViolation>,
+    },
 }
 
 impl<
@@ -384,6 +505,20 @@ impl<
             _ => None,
         }
     }
+
+    /// Returns a mutable reference to the transaction failure if the error
+    /// contains one.
+    pub fn as_transaction_failure_mut(
+        &mut self,
+    ) -> Option<&mut TransactionFailureWithTraces<HaltReasonT>> {
+        match self {
+            ProviderError::EstimateGasTransactionFailure(transaction_failure) => {
+                Some(&mut transaction_failure.transaction_failure)
+            }
+            ProviderError::TransactionFailed(transaction_failure) => Some(transaction_failure),
+            _ => None,
+        }
+    }
 }
 
 impl<
@@ -437,6 +572,15 @@ impl<
         const INVALID_INPUT: i16 = -32000;
         const INTERNAL_ERROR: i16 = -32603;
         const INVALID_PARAMS: i16 = -32602;
+        const LIMIT_EXCEEDED: i16 = -32005;
+        const METHOD_NOT_SUPPORTED: i16 = -32004;
+        // Engine API error codes
+        const INVALID_FORKCHOICE_STATE: i16 = -38002;
+        const INVALID_PAYLOAD_ATTRIBUTES: i16 = -38003;
+        const UNSUPPORTED_FORK: i16 = -38005;
+        // ERC-4337 bundler error codes
+        const USER_OPERATION_REJECTED: i16 = -32500;
+        const USER_OPERATION_RULE_VIOLATIONS: i16 = -32502;
 
         #[allow(clippy::match_same_arms)]
         let code = match &value {
@@ -447,8 +591,10 @@ impl<
             ProviderError::AutoMineNonceTooHigh { .. } => INVALID_INPUT,
             ProviderError::AutoMineNonceTooLow { .. } => INVALID_INPUT,
             ProviderError::AutoMinePriorityFeeTooLow { .. } => INVALID_INPUT,
-            ProviderError::BlobMemPoolUnsupported => INVALID_INPUT,
             ProviderError::Blockchain(_) => INVALID_INPUT,
+            ProviderError::ChainImport(_) => INVALID_INPUT,
+            ProviderError::Checkpoint(CheckpointError::Io { .. }) => INTERNAL_ERROR,
+            ProviderError::Checkpoint(_) => INVALID_PARAMS,
             ProviderError::Creation(_) => INVALID_INPUT,
             ProviderError::DebugTrace(_) => INTERNAL_ERROR,
             ProviderError::Eip4844CallRequestUnsupported => INVALID_INPUT,
@@ -459,36 +605,49 @@ impl<
             ProviderError::Eip712Error(_) => INVALID_INPUT,
             ProviderError::EstimateGasTransactionFailure(_) => INVALID_INPUT,
             ProviderError::FetchReceipt(_) => INTERNAL_ERROR,
+            ProviderError::GasSponsorInsufficientFunds { .. } => INVALID_INPUT,
             ProviderError::InvalidArgument(_) => INVALID_PARAMS,
             ProviderError::InvalidBlockNumberOrHash { .. } => INVALID_INPUT,
             ProviderError::InvalidBlockTag { .. } => INVALID_PARAMS,
             ProviderError::InvalidChainId { .. } => INVALID_PARAMS,
             ProviderError::InvalidDropTransactionHash(_) => INVALID_PARAMS,
             ProviderError::InvalidEip155TransactionChainId => INVALID_PARAMS,
+            ProviderError::InvalidEip712Domain { .. } => INVALID_INPUT,
             ProviderError::InvalidFilterSubscriptionType { .. } => INVALID_PARAMS,
+            ProviderError::InvalidForkchoiceState(_) => INVALID_FORKCHOICE_STATE,
+            ProviderError::InvalidHeadBlockNumber { .. } => INVALID_PARAMS,
             ProviderError::InvalidInput(_) => INVALID_INPUT,
+            ProviderError::InvalidPayloadAttributes(_) => INVALID_PAYLOAD_ATTRIBUTES,
+            ProviderError::InvalidReorgDepth { .. } => INVALID_PARAMS,
             ProviderError::InvalidTransactionHash { .. } => INVALID_PARAMS,
             ProviderError::InvalidTransactionIndex(_) => INVALID_PARAMS,
             ProviderError::InvalidTransactionInput(_) => INVALID_INPUT,
             ProviderError::InvalidTransactionType(_) => INVALID_PARAMS,
+            ProviderError::Keystore(_) => INVALID_INPUT,
             ProviderError::Logger(_) => INTERNAL_ERROR,
+            ProviderError::LogFilter(LogFilterError::InvalidFilter { .. }) => INVALID_PARAMS,
+            ProviderError::LogFilter(LogFilterError::Unsupported) => INVALID_INPUT,
+            ProviderError::LogsBlockRangeExceeded { .. } => LIMIT_EXCEEDED,
+            ProviderError::LogsResultsExceeded { .. } => LIMIT_EXCEEDED,
             ProviderError::MemPoolAddTransaction(_) => INVALID_INPUT,
             ProviderError::MemPoolUpdate(_) => INVALID_INPUT,
+            ProviderError::MethodNotPermitted { .. } => METHOD_NOT_SUPPORTED,
             ProviderError::MineBlock(_) => INVALID_INPUT,
             ProviderError::MineTransaction(_) => INVALID_INPUT,
             ProviderError::OnCollectedCoverageCallback(_) => INTERNAL_ERROR,
             ProviderError::OnCollectedGasReportCallback(_) => INTERNAL_ERROR,
+            ProviderError::RawBlockImport(_) => INVALID_INPUT,
             ProviderError::RpcClientError(_) => INTERNAL_ERROR,
             ProviderError::RpcVersion(_) => INVALID_INPUT,
             ProviderError::RunTransaction(_) => INVALID_INPUT,
             ProviderError::Serialization(_) => INVALID_INPUT,
             ProviderError::SetAccountNonceLowerThanCurrent { .. } => INVALID_INPUT,
             ProviderError::SetAccountNonceWithPendingTransactions => INTERNAL_ERROR,
+            ProviderError::SetBeaconRootUnsupported { .. } => INVALID_INPUT,
             ProviderError::SetBlockGasLimitMustBeGreaterThanZero => INVALID_INPUT,
             ProviderError::SetIntervalMiningConfigInvalid(_) => INVALID_PARAMS,
-            ProviderError::SetMinGasPriceUnsupported => INVALID_INPUT,
             ProviderError::SetNextBlockBaseFeePerGasUnsupported { .. } => INVALID_INPUT,
-            ProviderError::SetNextPrevRandaoUnsupported { .. } => INVALID_INPUT,
+            ProviderError::SetNextBlockWithdrawalsUnsupported { .. } => INVALID_INPUT,
             ProviderError::Signature(_) => INVALID_PARAMS,
             ProviderError::SolcDecoding(_) => INVALID_INPUT,
             ProviderError::State(_) => INVALID_INPUT,
@@ -496,6 +655,7 @@ impl<
             ProviderError::TimestampEqualsPrevious { .. } => INVALID_INPUT,
             ProviderError::TransactionFailed(_) => INVALID_INPUT,
             ProviderError::TransactionCreationError(_) => INVALID_INPUT,
+            ProviderError::TransactionRejected { .. } => INVALID_INPUT,
             ProviderError::TryFromIntError(_) => INVALID_INPUT,
             ProviderError::Unimplemented(_) => INVALID_INPUT,
             ProviderError::UnknownAddress { .. } => INVALID_INPUT,
@@ -504,14 +664,38 @@ impl<
             ProviderError::UnsupportedEIP1559Parameters { .. } => INVALID_PARAMS,
             ProviderError::UnsupportedEIP4844Parameters { .. } => INVALID_PARAMS,
             ProviderError::UnsupportedEip7702Parameters { .. } => INVALID_PARAMS,
+            ProviderError::UnsupportedFork { .. } => UNSUPPORTED_FORK,
             ProviderError::UnsupportedMethod { .. } => -32004,
+            ProviderError::UnsupportedTransactionTypeInBlockReplay { .. } => INVALID_INPUT,
             ProviderError::UnsupportedTransactionTypeInDebugTrace { .. } => INVALID_INPUT,
             ProviderError::UnsupportedTransactionTypeForDebugTrace { .. } => INVALID_INPUT,
+            ProviderError::UpdateForkAfterLocalBlocks { .. } => INVALID_INPUT,
+            ProviderError::UpdateForkUnsupported => INVALID_INPUT,
+            ProviderError::UserOperationRejected { .. } => USER_OPERATION_REJECTED,
+            ProviderError::UserOperationRuleViolations { .. } => USER_OPERATION_RULE_VIOLATIONS,
         };
 
-        let data = value.as_transaction_failure().map(|transaction_failure| {
-            serde_json::to_value(&transaction_failure.failure).expect("transaction_failure to json")
-        });
+        let data = if let ProviderError::LogsResultsExceeded {
+            from_block,
+            to_block,
+            ..
+        } = &value
+        {
+            // Continuation cursor for clients to retry with a smaller block range
+            Some(serde_json::json!({
+                "fromBlock": format!("{from_block:#x}"),
+                "toBlock": format!("{to_block:#x}"),
+            }))
+        } else if let ProviderError::UserOperationRuleViolations { violations } = &value {
+            Some(serde_json::json!({
+                "violations": violations,
+            }))
+        } else {
+            value.as_transaction_failure().map(|transaction_failure| {
+                serde_json::to_value(&transaction_failure.failure)
+                    .expect("transaction_failure to json")
+            })
+        };
 
         let message = value.to_string();
 
@@ -554,6 +738,19 @@ impl<HaltReasonT: HaltReasonTrait> std::fmt::Display for TransactionFailureWithT
 pub struct TransactionFailure<HaltReasonT: HaltReasonTrait> {
     pub reason: TransactionFailureReason<HaltReasonT>,
     pub data: String,
+    /// The reason string of an `Error(string)` revert.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_string: Option<String>,
+    /// The panic code of a `Panic(uint256)` revert.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panic_code: Option<U256>,
+    /// The 4-byte selector of the revert output, if it contains one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_selector: Option<Bytes>,
+    /// The human-readable custom error of the revert output, if the contract
+    /// decoder knows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_error: Option<String>,
     #[serde(skip)]
     pub solidity_trace: Trace<HaltReasonT>,
     pub transaction_hash: Option<B256>,
@@ -591,6 +788,10 @@ impl<HaltReasonT: HaltReasonTrait> TransactionFailure<HaltReasonT> {
         Self {
             reason,
             data: "0x".to_string(),
+            reason_string: None,
+            panic_code: None,
+            error_selector: None,
+            custom_error: None,
             solidity_trace,
             transaction_hash: tx_hash,
         }
@@ -602,13 +803,40 @@ impl<HaltReasonT: HaltReasonTrait> TransactionFailure<HaltReasonT> {
         solidity_trace: Trace<HaltReasonT>,
     ) -> Self {
         let data = format!("0x{}", hex::encode(output.as_ref()));
+
+        let (reason_string, panic_code) =
+            match alloy_sol_types::GenericContractError::abi_decode(output.as_ref()) {
+                Ok(ContractError::Revert(revert)) => (Some(revert.reason), None),
+                Ok(ContractError::Panic(panic)) => (None, Some(panic.code)),
+                Ok(ContractError::CustomError(_)) | Err(_) => (None, None),
+            };
+
+        let error_selector = (output.len() >= 4).then(|| output.slice(..4));
+
         Self {
             reason: TransactionFailureReason::Revert(output),
             data,
+            reason_string,
+            panic_code,
+            error_selector,
+            custom_error: None,
             solidity_trace,
             transaction_hash,
         }
     }
+
+    /// Decodes the revert output into a human-readable custom error, if the
+    /// contract decoder knows it.
+    pub fn with_custom_error(mut self, contract_decoder: &ContractDecoder) -> Self {
+        if let TransactionFailureReason::Revert(output) = &self.reason
+            && self.reason_string.is_none()
+            && self.panic_code.is_none()
+        {
+            self.custom_error = contract_decoder.decode_custom_error(output);
+        }
+
+        self
+    }
 }
 
 impl<HaltReasonT: HaltReasonTrait> std::fmt::Display for TransactionFailure<HaltReasonT> {
@@ -628,7 +856,16 @@ impl<HaltReasonT: HaltReasonTrait> std::fmt::Display for TransactionFailure<Halt
                 )
             }
             TransactionFailureReason::OutOfGas(_error) => write!(f, "Transaction ran out of gas"),
-            TransactionFailureReason::Revert(output) => write!(f, "{}", revert_error(output)),
+            TransactionFailureReason::Revert(output) => {
+                if let Some(custom_error) = &self.custom_error {
+                    write!(
+                        f,
+                        "VM Exception while processing transaction: reverted with custom error '{custom_error}'"
+                    )
+                } else {
+                    write!(f, "{}", revert_error(output))
+                }
+            }
         }
     }
 }
@@ -700,3 +937,66 @@ fn panic_code_to_error_reason(error_code: u64) -> &'static str {
         _ => "Unknown panic code",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::{Panic, Revert, SolError as _};
+    use edr_chain_spec::EvmHaltReason;
+    use serde_json::json;
+
+    use super::*;
+
+    fn revert_data(output: Vec<u8>) -> serde_json::Value {
+        let failure = TransactionFailure::<EvmHaltReason>::revert(
+            Bytes::from(output),
+            None,
+            Trace::default(),
+        );
+
+        serde_json::to_value(&failure).expect("failed to serialize transaction failure")
+    }
+
+    #[test]
+    fn revert_data_includes_reason_string() {
+        let output = Revert {
+            reason: "insufficient funds".to_string(),
+        }
+        .abi_encode();
+        let data = revert_data(output.clone());
+
+        assert_eq!(data["data"], json!(format!("0x{}", hex::encode(&output))));
+        assert_eq!(data["reasonString"], json!("insufficient funds"));
+        assert_eq!(data["errorSelector"], json!("0x08c379a0"));
+        assert!(data.get("panicCode").is_none());
+    }
+
+    #[test]
+    fn revert_data_includes_panic_code() {
+        let output = Panic {
+            code: U256::from(0x11),
+        }
+        .abi_encode();
+        let data = revert_data(output);
+
+        assert_eq!(data["panicCode"], json!("0x11"));
+        assert_eq!(data["errorSelector"], json!("0x4e487b71"));
+        assert!(data.get("reasonString").is_none());
+    }
+
+    #[test]
+    fn revert_data_includes_custom_error_selector() {
+        let data = revert_data(vec![0xde, 0xad, 0xbe, 0xef, 0x01]);
+
+        assert_eq!(data["errorSelector"], json!("0xdeadbeef"));
+        assert!(data.get("reasonString").is_none());
+        assert!(data.get("panicCode").is_none());
+    }
+
+    #[test]
+    fn revert_data_without_output() {
+        let data = revert_data(Vec::new());
+
+        assert_eq!(data["data"], json!("0x"));
+        assert!(data.get("errorSelector").is_none());
+    }
+}
@@ -39,6 +39,11 @@ impl Filter {
         )
     }
 
+    /// Constructs a new dropped transaction filter.
+    pub fn new_dropped_transaction_filter(is_subscription: bool) -> Self {
+        Self::new_filter(FilterData::DroppedTransactions(Vec::new()), is_subscription)
+    }
+
     fn new_filter(data: FilterData, is_subscription: bool) -> Self {
         Self {
             deadline: new_filter_deadline(),
@@ -47,26 +52,21 @@ impl Filter {
         }
     }
 
-    /// Whether the filter has expired.
+    /// Whether the filter has expired due to inactivity. Subscriptions push
+    /// their events, so they never expire.
     pub fn has_expired(&self) -> bool {
-        Instant::now() > self.deadline
+        !self.is_subscription && Instant::now() > self.deadline
     }
 
-    /// Take events from the filter
-    pub fn take_events(&mut self) -> FilteredEvents {
+    /// Extends the filter's deadline, as it's being polled.
+    pub fn refresh_deadline(&mut self) {
         self.deadline = new_filter_deadline();
-        self.data.take_events()
     }
 
-    /// Take log events from the filter if the filter is a log events filter.
-    pub fn take_log_events(&mut self) -> Option<Vec<LogOutput>> {
-        match &mut self.data {
-            FilterData::Logs { logs, .. } => {
-                self.deadline = new_filter_deadline();
-                Some(std::mem::take(logs))
-            }
-            _ => None,
-        }
+    /// Take events from the filter
+    pub fn take_events(&mut self) -> FilteredEvents {
+        self.refresh_deadline();
+        self.data.take_events()
     }
 }
 
@@ -82,6 +82,8 @@ pub enum FilterData {
     NewHeads(Vec<B256>),
     /// new pending transactions
     NewPendingTransactions(Vec<B256>),
+    /// transactions that were removed from the mem pool without being mined
+    DroppedTransactions(Vec<B256>),
 }
 
 impl FilterData {
@@ -91,6 +93,7 @@ impl FilterData {
             Self::Logs { logs, .. } => FilteredEvents::Logs(mem::take(logs)),
             Self::NewHeads(v) => FilteredEvents::NewHeads(mem::take(v)),
             Self::NewPendingTransactions(v) => FilteredEvents::NewPendingTransactions(mem::take(v)),
+            Self::DroppedTransactions(v) => FilteredEvents::DroppedTransactions(mem::take(v)),
         }
     }
 
@@ -100,6 +103,7 @@ impl FilterData {
             Self::Logs { .. } => SubscriptionType::Logs,
             Self::NewHeads(_) => SubscriptionType::NewHeads,
             Self::NewPendingTransactions(_) => SubscriptionType::NewPendingTransactions,
+            Self::DroppedTransactions(_) => SubscriptionType::DroppedTransactions,
         }
     }
 }
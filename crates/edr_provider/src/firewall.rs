@@ -0,0 +1,72 @@
+use edr_chain_spec::ExecutableTransaction;
+use edr_primitives::{Address, B256, U256};
+use edr_transaction::TxKind;
+
+/// Summary of a transaction that is about to be added to the mem pool,
+/// provided to a [`SyncTransactionFirewall`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionFirewallRequest {
+    /// The transaction's hash
+    pub transaction_hash: B256,
+    /// The transaction's sender
+    pub caller: Address,
+    /// The transaction's recipient, or `None` for contract creations
+    pub to: Option<Address>,
+    /// The function selector of the transaction's calldata, if it's at least
+    /// four bytes long
+    pub selector: Option<[u8; 4]>,
+    /// The value transferred by the transaction
+    pub value: U256,
+}
+
+impl TransactionFirewallRequest {
+    /// Summarizes the provided transaction.
+    pub fn new(transaction: &impl ExecutableTransaction) -> Self {
+        let to = match transaction.kind() {
+            TxKind::Call(to) => Some(to),
+            TxKind::Create => None,
+        };
+
+        let selector = transaction
+            .data()
+            .get(..4)
+            .map(|selector| selector.try_into().expect("slice has length 4"));
+
+        Self {
+            transaction_hash: *transaction.transaction_hash(),
+            caller: *transaction.caller(),
+            to,
+            selector,
+            value: *transaction.value(),
+        }
+    }
+}
+
+/// The decision of a [`SyncTransactionFirewall`] about a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionFirewallVerdict {
+    /// Add the transaction to the mem pool, labelled with the provided tags.
+    /// Tags can be queried with `hardhat_getTransactionTags` while the
+    /// transaction is pending.
+    Accept { tags: Vec<String> },
+    /// Reject the transaction for the provided reason.
+    Reject { reason: String },
+}
+
+/// Callback that decides whether transactions are added to the mem pool, e.g.
+/// to simulate sequencer policies or sender allowlists and denylists.
+///
+/// Invoked before a transaction is added to the mem pool. Rejected
+/// transactions fail with [`crate::ProviderError::TransactionRejected`].
+///
+/// The callback is invoked while the provider's data is locked, so it must not
+/// send requests to the provider; doing so deadlocks.
+pub trait SyncTransactionFirewall:
+    Fn(&TransactionFirewallRequest) -> TransactionFirewallVerdict + Send + Sync
+{
+}
+
+impl<F> SyncTransactionFirewall for F where
+    F: Fn(&TransactionFirewallRequest) -> TransactionFirewallVerdict + Send + Sync
+{
+}
@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+
+use edr_block_header::BlockHeader;
+use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
+use edr_chain_spec::ExecutableTransaction as _;
+use edr_chain_spec_block::BlockChainSpec;
+use edr_chain_spec_evm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EthInterpreter, InputsTr as _,
+        Interpreter, InterpreterResult,
+    },
+    result::ExecutionResultAndState,
+    BlockEnvTrait as _, CfgEnv, ContextTrait, Inspector,
+};
+use edr_evm::{dry_run_with_inspector, run};
+use edr_primitives::{Bytes, B256};
+use edr_solidity::contract_decoder::ContractDecoder;
+use edr_state_api::DynState;
+
+use crate::{
+    debug_trace::{DebugTraceError, DebugTraceErrorForChainSpec},
+    requests::hardhat::rpc_types::{
+        FrameGasProfile, FunctionGasProfile, GasProfile, LineGasProfile,
+    },
+};
+
+/// Replays the transactions of a block up to and including the transaction
+/// with the provided hash, profiling the gas usage of the latter.
+pub fn gas_profile_transaction<'header, ChainSpecT: BlockChainSpec>(
+    blockchain: &dyn BlockHashByNumber<Error = DynBlockchainError>,
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    mut state: Box<dyn DynState>,
+    evm_config: CfgEnv<ChainSpecT::Hardfork>,
+    block: ChainSpecT::BlockEnv<'header, BlockHeader>,
+    transactions: Vec<ChainSpecT::SignedTransaction>,
+    transaction_hash: &B256,
+    contract_decoder: &ContractDecoder,
+) -> Result<GasProfile, DebugTraceErrorForChainSpec<ChainSpecT>> {
+    let block_number = block.number();
+    for transaction in transactions {
+        if transaction.transaction_hash() == transaction_hash {
+            let mut profiler = GasProfiler::default();
+
+            let ExecutionResultAndState { result, .. } =
+                dry_run_with_inspector::<ChainSpecT, _, _, _, _>(
+                    blockchain,
+                    state.as_ref(),
+                    evm_config,
+                    transaction,
+                    &block,
+                    &edr_primitives::HashMap::default(),
+                    &mut profiler,
+                )?;
+
+            return Ok(build_gas_profile(
+                result.gas_used(),
+                profiler.into_frames(),
+                contract_decoder,
+            ));
+        } else {
+            run::<ChainSpecT, _, _, _>(
+                blockchain,
+                state.as_mut(),
+                evm_config.clone(),
+                transaction,
+                &block,
+                &edr_primitives::HashMap::default(),
+            )?;
+        }
+    }
+
+    Err(DebugTraceError::InvalidTransactionHash {
+        transaction_hash: *transaction_hash,
+        block_number,
+    })
+}
+
+/// The gas usage of a single call frame.
+#[derive(Debug)]
+struct FrameGas {
+    depth: usize,
+    /// The executed code; the init code for creates.
+    code: Bytes,
+    /// The call data; `None` for creates.
+    calldata: Option<Bytes>,
+    /// The gas used by the frame, including its child frames.
+    gas_used: u64,
+    /// The gas used by the frame's own instructions, keyed by program counter.
+    gas_by_pc: BTreeMap<u32, u64>,
+}
+
+#[derive(Debug)]
+struct PendingStep {
+    pc: u32,
+    gas_remaining: u64,
+}
+
+#[derive(Debug)]
+struct ActiveFrame {
+    index: usize,
+    pending_step: Option<PendingStep>,
+    /// Gas used by child frames since the last step, which should not be
+    /// attributed to the call instruction itself.
+    child_gas_used: u64,
+}
+
+/// Inspector that attributes gas usage to the executed program counters of
+/// each call frame.
+#[derive(Debug, Default)]
+struct GasProfiler {
+    frames: Vec<FrameGas>,
+    active_frames: Vec<ActiveFrame>,
+}
+
+impl GasProfiler {
+    fn into_frames(self) -> Vec<FrameGas> {
+        self.frames
+    }
+
+    fn start_frame(&mut self, calldata: Option<Bytes>) {
+        let index = self.frames.len();
+
+        self.frames.push(FrameGas {
+            depth: self.active_frames.len(),
+            code: Bytes::new(),
+            calldata,
+            gas_used: 0,
+            gas_by_pc: BTreeMap::new(),
+        });
+        self.active_frames.push(ActiveFrame {
+            index,
+            pending_step: None,
+            child_gas_used: 0,
+        });
+    }
+
+    fn end_frame(&mut self, result: &InterpreterResult) {
+        self.settle_pending_step(result.gas.remaining());
+
+        if let Some(frame) = self.active_frames.pop() {
+            let gas_used = result.gas.spent();
+            self.frames[frame.index].gas_used = gas_used;
+
+            if let Some(parent) = self.active_frames.last_mut() {
+                parent.child_gas_used += gas_used;
+            }
+        }
+    }
+
+    /// Attributes the gas used since the previous step of the active frame to
+    /// that step's program counter.
+    fn settle_pending_step(&mut self, gas_remaining: u64) {
+        let Some(frame) = self.active_frames.last_mut() else {
+            return;
+        };
+
+        let Some(PendingStep {
+            pc,
+            gas_remaining: gas_remaining_before,
+        }) = frame.pending_step.take()
+        else {
+            return;
+        };
+
+        let gas_cost = gas_remaining_before
+            .saturating_sub(gas_remaining)
+            .saturating_sub(std::mem::take(&mut frame.child_gas_used));
+
+        *self.frames[frame.index].gas_by_pc.entry(pc).or_default() += gas_cost;
+    }
+}
+
+impl<ContextT: ContextTrait> Inspector<ContextT, EthInterpreter> for GasProfiler {
+    fn initialize_interp(
+        &mut self,
+        interpreter: &mut Interpreter<EthInterpreter>,
+        _context: &mut ContextT,
+    ) {
+        if let Some(frame) = self.active_frames.last() {
+            self.frames[frame.index].code = interpreter.bytecode.original_bytes();
+        }
+    }
+
+    fn step(&mut self, interpreter: &mut Interpreter<EthInterpreter>, _context: &mut ContextT) {
+        let gas_remaining = interpreter.gas.remaining();
+        self.settle_pending_step(gas_remaining);
+
+        if let Some(frame) = self.active_frames.last_mut() {
+            frame.pending_step = Some(PendingStep {
+                pc: interpreter
+                    .bytecode
+                    .pc()
+                    .try_into()
+                    .expect("Program Counter should fit inside u32"),
+                gas_remaining,
+            });
+        }
+    }
+
+    fn call(&mut self, context: &mut ContextT, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.start_frame(Some(inputs.input.bytes(context)));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &CallInputs,
+        outcome: &mut CallOutcome,
+    ) {
+        self.end_frame(&outcome.result);
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame(None);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut ContextT,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.end_frame(&outcome.result);
+    }
+}
+
+/// Maps the profiled frames through the source maps of the contracts known to
+/// the contract decoder.
+fn build_gas_profile(
+    gas_used: u64,
+    frames: Vec<FrameGas>,
+    contract_decoder: &ContractDecoder,
+) -> GasProfile {
+    let mut gas_by_function: BTreeMap<(String, Option<String>, String), u64> = BTreeMap::new();
+    let mut gas_by_line: BTreeMap<(String, u32), u64> = BTreeMap::new();
+
+    let frames = frames
+        .into_iter()
+        .map(|frame| {
+            let FrameGas {
+                depth,
+                code,
+                calldata,
+                gas_used,
+                gas_by_pc,
+            } = frame;
+
+            let contract_and_function =
+                contract_decoder.get_contract_and_function_names_for_call(&code, calldata.as_ref());
+
+            let contract_meta = contract_decoder.get_contract_metadata(&code, calldata.is_none());
+
+            let mut self_gas = 0;
+            for (pc, gas) in gas_by_pc {
+                self_gas += gas;
+
+                let Some(location) = contract_meta
+                    .as_ref()
+                    .and_then(|contract_meta| contract_meta.get_instruction(pc).ok())
+                    .and_then(|instruction| instruction.location.clone())
+                else {
+                    continue;
+                };
+
+                let Ok(file) = location.file() else {
+                    continue;
+                };
+                let source_name = file.read().source_name.clone();
+
+                if let Ok(line) = location.get_starting_line_number() {
+                    *gas_by_line.entry((source_name.clone(), line)).or_default() += gas;
+                }
+
+                if let Ok(Some(function)) = location.get_containing_function() {
+                    *gas_by_function
+                        .entry((
+                            source_name,
+                            function.contract_name.clone(),
+                            function.name.clone(),
+                        ))
+                        .or_default() += gas;
+                }
+            }
+
+            FrameGasProfile {
+                depth: depth as u64,
+                contract: contract_and_function.contract_name,
+                function: contract_and_function.function_name,
+                gas_used,
+                self_gas,
+            }
+        })
+        .collect();
+
+    let mut functions: Vec<_> = gas_by_function
+        .into_iter()
+        .map(
+            |((source_name, contract, function), gas)| FunctionGasProfile {
+                source_name,
+                contract,
+                function,
+                gas,
+            },
+        )
+        .collect();
+    // Most expensive functions first
+    functions.sort_by(|lhs, rhs| rhs.gas.cmp(&lhs.gas));
+
+    let lines = gas_by_line
+        .into_iter()
+        .map(|((source_name, line), gas)| LineGasProfile {
+            source_name,
+            line,
+            gas,
+        })
+        .collect();
+
+    GasProfile {
+        gas_used,
+        frames,
+        functions,
+        lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrecognized_frames_only_report_frame_gas() {
+        let frames = vec![FrameGas {
+            depth: 0,
+            code: Bytes::from_static(&[0x60, 0x00, 0x00]),
+            calldata: Some(Bytes::new()),
+            gas_used: 30,
+            gas_by_pc: BTreeMap::from([(0, 3), (2, 0)]),
+        }];
+
+        let profile = build_gas_profile(21_030, frames, &ContractDecoder::default());
+
+        assert_eq!(profile.gas_used, 21_030);
+        assert_eq!(profile.frames.len(), 1);
+        assert_eq!(profile.frames[0].gas_used, 30);
+        assert_eq!(profile.frames[0].self_gas, 3);
+        assert!(profile.functions.is_empty());
+        assert!(profile.lines.is_empty());
+    }
+}
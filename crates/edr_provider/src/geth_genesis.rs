@@ -0,0 +1,134 @@
+//! Ingestion of Geth-style genesis files, which allows a local blockchain to
+//! be initialized with the exact genesis state of another network.
+
+use core::num::NonZeroU64;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use alloy_genesis::{Genesis, GenesisAccount};
+use edr_primitives::{Address, Bytecode, Bytes, ChainId, HashMap, U256};
+use edr_state_api::EvmStorageSlot;
+
+use crate::{data::MAX_EXTRA_DATA_SIZE, AccountOverride, ProviderConfig};
+
+/// An error that occurred while parsing a Geth-style genesis file.
+#[derive(Debug, thiserror::Error)]
+pub enum GethGenesisError {
+    /// The JSON is malformed.
+    #[error("Invalid genesis JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    /// The extra data of the genesis block is too long.
+    #[error(
+        "The genesis extra data must be at most {MAX_EXTRA_DATA_SIZE} bytes long, but got {0} bytes"
+    )]
+    ExtraDataTooLong(usize),
+    /// The gas limit of the genesis block is zero.
+    #[error("The genesis gas limit must be greater than 0")]
+    ZeroGasLimit,
+}
+
+/// The parts of a Geth-style genesis file that are used to initialize a local
+/// blockchain.
+///
+/// The hardfork isn't derived from the file's `config` section, as its
+/// activations can't be expressed for every chain type.
+#[derive(Clone, Debug)]
+pub struct GethGenesis {
+    /// The chain ID of the `config` section.
+    pub chain_id: ChainId,
+    /// The gas limit of the genesis block.
+    pub block_gas_limit: NonZeroU64,
+    /// The base fee per gas of the genesis block, if present.
+    pub initial_base_fee_per_gas: Option<u128>,
+    /// The timestamp of the genesis block.
+    pub initial_date: SystemTime,
+    /// The extra data of the genesis block.
+    pub extra_data: Bytes,
+    /// The accounts of the `alloc` section.
+    pub genesis_state: HashMap<Address, AccountOverride>,
+}
+
+impl GethGenesis {
+    /// Parses a Geth-style genesis file.
+    pub fn from_json(json: &str) -> Result<Self, GethGenesisError> {
+        let genesis: Genesis = serde_json::from_str(json)?;
+
+        Self::try_from(genesis)
+    }
+
+    /// Applies the genesis to the provided configuration. Its chain ID, gas
+    /// limit, base fee per gas, timestamp, and extra data replace those of
+    /// the configuration.
+    ///
+    /// The accounts of the `alloc` section are added to the configuration's
+    /// genesis state. Fields of accounts that the configuration already
+    /// overrides take precedence over the `alloc` section.
+    pub fn apply_to<HardforkT>(self, config: &mut ProviderConfig<HardforkT>) {
+        config.chain_id = self.chain_id;
+        config.block_gas_limit = self.block_gas_limit;
+        if let Some(initial_base_fee_per_gas) = self.initial_base_fee_per_gas {
+            config.initial_base_fee_per_gas = Some(initial_base_fee_per_gas);
+        }
+        config.initial_date = Some(self.initial_date);
+        config.genesis_extra_data = Some(self.extra_data);
+
+        for (address, allocated) in self.genesis_state {
+            let account = config.genesis_state.entry(address).or_default();
+
+            account.balance = account.balance.or(allocated.balance);
+            account.nonce = account.nonce.or(allocated.nonce);
+            account.code = account.code.take().or(allocated.code);
+            account.storage = account.storage.take().or(allocated.storage);
+        }
+    }
+}
+
+impl TryFrom<Genesis> for GethGenesis {
+    type Error = GethGenesisError;
+
+    fn try_from(value: Genesis) -> Result<Self, Self::Error> {
+        if value.extra_data.len() > MAX_EXTRA_DATA_SIZE {
+            return Err(GethGenesisError::ExtraDataTooLong(value.extra_data.len()));
+        }
+
+        let block_gas_limit =
+            NonZeroU64::new(value.gas_limit).ok_or(GethGenesisError::ZeroGasLimit)?;
+
+        let genesis_state = value
+            .alloc
+            .into_iter()
+            .map(|(address, account)| (address, account_override(account)))
+            .collect();
+
+        Ok(Self {
+            chain_id: value.config.chain_id,
+            block_gas_limit,
+            initial_base_fee_per_gas: value.base_fee_per_gas,
+            initial_date: UNIX_EPOCH + Duration::from_secs(value.timestamp),
+            extra_data: value.extra_data,
+            genesis_state,
+        })
+    }
+}
+
+fn account_override(account: GenesisAccount) -> AccountOverride {
+    let storage = account.storage.map(|storage| {
+        storage
+            .into_iter()
+            .map(|(index, value)| {
+                let slot = EvmStorageSlot::new(U256::from_be_bytes(value.0), 0);
+
+                (U256::from_be_bytes(index.0), slot)
+            })
+            .collect()
+    });
+
+    AccountOverride {
+        balance: Some(account.balance),
+        nonce: account.nonce,
+        code: account
+            .code
+            .filter(|code| !code.is_empty())
+            .map(Bytecode::new_raw),
+        storage,
+    }
+}
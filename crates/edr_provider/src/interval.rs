@@ -2,7 +2,7 @@ use std::{marker::PhantomData, sync::Arc};
 
 use tokio::{
     runtime,
-    sync::{oneshot, Mutex},
+    sync::{oneshot, watch, Mutex},
     task::JoinHandle,
     time::Instant,
 };
@@ -22,6 +22,7 @@ pub struct IntervalMiner<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeS
 /// implementation of `Drop`.
 struct Inner<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> {
     cancellation_sender: oneshot::Sender<()>,
+    pause_sender: watch::Sender<bool>,
     background_task: JoinHandle<Result<(), ProviderErrorForChainSpec<ChainSpecT>>>,
     _phantom: PhantomData<fn() -> TimerT>,
 }
@@ -37,18 +38,29 @@ impl<
         data: Arc<Mutex<ProviderData<ChainSpecT, TimerT>>>,
     ) -> Self {
         let (cancellation_sender, cancellation_receiver) = oneshot::channel();
-        let background_task = runtime
-            .spawn(async move { interval_mining_loop(config, data, cancellation_receiver).await });
+        let (pause_sender, pause_receiver) = watch::channel(false);
+        let background_task = runtime.spawn(async move {
+            interval_mining_loop(config, data, cancellation_receiver, pause_receiver).await
+        });
 
         Self {
             inner: Some(Inner {
                 cancellation_sender,
+                pause_sender,
                 background_task,
                 _phantom: PhantomData,
             }),
             runtime,
         }
     }
+
+    /// Pauses or resumes interval mining. When resumed, the next block is
+    /// scheduled relative to the time of resumption.
+    pub fn set_paused(&self, paused: bool) {
+        if let Some(inner) = &self.inner {
+            inner.pause_sender.send_replace(paused);
+        }
+    }
 }
 
 #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
@@ -59,26 +71,41 @@ async fn interval_mining_loop<
     config: IntervalConfig,
     data: Arc<Mutex<ProviderData<ChainSpecT, TimerT>>>,
     mut cancellation_receiver: oneshot::Receiver<()>,
+    mut pause_receiver: watch::Receiver<bool>,
 ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
-    let mut now = Instant::now();
+    let mut deadline = next_deadline(Instant::now(), &config);
     loop {
-        let delay = config.generate_interval();
-        let deadline = now + std::time::Duration::from_millis(delay);
+        if *pause_receiver.borrow_and_update() {
+            tokio::select! {
+                _ = &mut cancellation_receiver => return Ok(()),
+                result = pause_receiver.changed() => {
+                    // The sender was dropped, so the interval miner is being destroyed
+                    if result.is_err() {
+                        return Ok(());
+                    }
+
+                    deadline = next_deadline(Instant::now(), &config);
+                    continue;
+                }
+            }
+        }
 
         tokio::select! {
             _ = &mut cancellation_receiver => return Ok(()),
+            // Re-evaluate the pause state when it changes
+            _ = pause_receiver.changed() => continue,
             _ = tokio::time::sleep_until(deadline) => {
                 tokio::select! {
                     // Check whether the interval miner needs to be destroyed
                     _ = &mut cancellation_receiver => return Ok(()),
                     mut data = data.lock() => {
-                        now = Instant::now();
-
                         if let Err(error) = data.interval_mine() {
                             log::error!("Unexpected error while performing interval mining: {error}");
                             return Err(error);
                         }
 
+                        deadline = next_deadline_after_mining(deadline, Instant::now(), &config);
+
                         Result::<(), ProviderErrorForChainSpec<ChainSpecT>>::Ok(())
                     }
                 }
@@ -87,6 +114,23 @@ async fn interval_mining_loop<
     }
 }
 
+fn next_deadline(previous: Instant, config: &IntervalConfig) -> Instant {
+    previous + std::time::Duration::from_millis(config.generate_interval())
+}
+
+/// Schedules the next block relative to the previous deadline, to compensate
+/// for the latency of acquiring the lock and mining. If mining fell behind by
+/// more than an interval, the missed blocks are skipped instead of mining a
+/// burst.
+fn next_deadline_after_mining(previous: Instant, now: Instant, config: &IntervalConfig) -> Instant {
+    let deadline = next_deadline(previous, config);
+    if deadline < now {
+        next_deadline(now, config)
+    } else {
+        deadline
+    }
+}
+
 impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Drop
     for IntervalMiner<ChainSpecT, TimerT>
 {
@@ -94,6 +138,7 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Drop
     fn drop(&mut self) {
         if let Some(Inner {
             cancellation_sender,
+            pause_sender: _,
             background_task: task,
             _phantom,
         }) = self.inner.take()
@@ -109,3 +154,34 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Drop
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, time::Duration};
+
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(1_000);
+
+    fn fixed_interval() -> IntervalConfig {
+        IntervalConfig::Fixed(NonZeroU64::new(1_000).expect("constant is non-zero"))
+    }
+
+    #[test]
+    fn next_deadline_ignores_mining_latency() {
+        let previous = Instant::now();
+        let now = previous + Duration::from_millis(300);
+
+        let deadline = next_deadline_after_mining(previous, now, &fixed_interval());
+        assert_eq!(deadline, previous + INTERVAL);
+    }
+
+    #[test]
+    fn next_deadline_skips_missed_blocks() {
+        let previous = Instant::now();
+        let now = previous + 3 * INTERVAL;
+
+        let deadline = next_deadline_after_mining(previous, now, &fixed_interval());
+        assert_eq!(deadline, now + INTERVAL);
+    }
+}
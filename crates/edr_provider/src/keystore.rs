@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use edr_primitives::{keccak256, Address, B256};
+use edr_signer::public_key_to_address;
+use indexmap::IndexMap;
+
+/// The duration that an account remains unlocked for, when no duration is
+/// provided to `personal_unlockAccount`.
+pub const DEFAULT_UNLOCK_DURATION: Duration = Duration::from_secs(300);
+
+/// An error that occurs when managing keystore accounts.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    /// The account already exists in the keystore.
+    #[error("Account {address} already exists")]
+    AccountAlreadyExists { address: Address },
+    /// The account doesn't exist in the keystore.
+    #[error("No key for given address or file")]
+    AccountNotFound { address: Address },
+    /// The passphrase doesn't match the account's passphrase.
+    #[error("Could not decrypt key with given password")]
+    InvalidPassphrase { address: Address },
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Unlocked {
+    Indefinitely,
+    Until(Instant),
+}
+
+#[derive(Clone)]
+struct KeystoreAccount {
+    secret_key: k256::SecretKey,
+    passphrase_hash: B256,
+    unlocked: Option<Unlocked>,
+}
+
+/// In-memory keystore of accounts managed through the `personal` namespace.
+///
+/// Accounts are locked when added and can only be used for signing after
+/// being unlocked with their passphrase.
+#[derive(Clone, Default)]
+pub struct Keystore {
+    // IndexMap to preserve account order for `eth_accounts`.
+    accounts: IndexMap<Address, KeystoreAccount>,
+}
+
+impl Keystore {
+    /// Returns the addresses of all accounts in the keystore, whether locked or
+    /// not.
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        self.accounts.keys()
+    }
+
+    /// Adds the account corresponding to the provided secret key, protected by
+    /// the provided passphrase.
+    pub fn import(
+        &mut self,
+        secret_key: k256::SecretKey,
+        passphrase: &str,
+    ) -> Result<Address, KeystoreError> {
+        let address = public_key_to_address(secret_key.public_key());
+        if self.accounts.contains_key(&address) {
+            return Err(KeystoreError::AccountAlreadyExists { address });
+        }
+
+        self.accounts.insert(
+            address,
+            KeystoreAccount {
+                secret_key,
+                passphrase_hash: keccak256(passphrase),
+                unlocked: None,
+            },
+        );
+
+        Ok(address)
+    }
+
+    /// Generates a new account, protected by the provided passphrase.
+    pub fn new_account(&mut self, passphrase: &str) -> Address {
+        // Retry in the unlikely case that the random bytes aren't a valid secret key or
+        // correspond to an existing account.
+        loop {
+            let Ok(secret_key) = k256::SecretKey::from_slice(&rand::random::<[u8; 32]>()) else {
+                continue;
+            };
+
+            if let Ok(address) = self.import(secret_key, passphrase) {
+                return address;
+            }
+        }
+    }
+
+    /// Unlocks the account with the provided address for the provided
+    /// duration. A duration of zero unlocks the account indefinitely.
+    pub fn unlock(
+        &mut self,
+        address: &Address,
+        passphrase: &str,
+        duration: Duration,
+    ) -> Result<(), KeystoreError> {
+        let account = self
+            .accounts
+            .get_mut(address)
+            .ok_or(KeystoreError::AccountNotFound { address: *address })?;
+
+        if account.passphrase_hash != keccak256(passphrase) {
+            return Err(KeystoreError::InvalidPassphrase { address: *address });
+        }
+
+        account.unlocked = Some(if duration.is_zero() {
+            Unlocked::Indefinitely
+        } else {
+            Unlocked::Until(Instant::now() + duration)
+        });
+
+        Ok(())
+    }
+
+    /// Returns the secret key of the account with the provided address, if it
+    /// exists and is currently unlocked.
+    pub fn unlocked_secret_key(&self, address: &Address) -> Option<&k256::SecretKey> {
+        let account = self.accounts.get(address)?;
+        match account.unlocked? {
+            Unlocked::Indefinitely => Some(&account.secret_key),
+            Unlocked::Until(deadline) => (Instant::now() < deadline).then_some(&account.secret_key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlock_requires_passphrase() -> anyhow::Result<()> {
+        let mut keystore = Keystore::default();
+
+        let address = keystore.new_account("passphrase");
+        assert!(keystore.unlocked_secret_key(&address).is_none());
+
+        assert!(matches!(
+            keystore.unlock(&address, "wrong", DEFAULT_UNLOCK_DURATION),
+            Err(KeystoreError::InvalidPassphrase { .. })
+        ));
+        assert!(keystore.unlocked_secret_key(&address).is_none());
+
+        keystore.unlock(&address, "passphrase", DEFAULT_UNLOCK_DURATION)?;
+        assert!(keystore.unlocked_secret_key(&address).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unlock_expires() -> anyhow::Result<()> {
+        let mut keystore = Keystore::default();
+
+        let address = keystore.new_account("passphrase");
+        keystore.unlock(&address, "passphrase", Duration::from_nanos(1))?;
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(keystore.unlocked_secret_key(&address).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_duplicate_account() {
+        let mut keystore = Keystore::default();
+
+        let address = keystore.new_account("passphrase");
+        let secret_key = keystore.accounts[&address].secret_key.clone();
+
+        assert!(matches!(
+            keystore.import(secret_key, "passphrase"),
+            Err(KeystoreError::AccountAlreadyExists { address: duplicate }) if duplicate == address
+        ));
+    }
+}
@@ -1,18 +1,34 @@
+mod address_index;
+mod chain_export;
+mod checkpoint;
 /// Types for configuring the provider.
 pub mod config;
 mod console_log;
 mod data;
 mod debug_mine;
 mod debug_trace;
+mod deterministic_deployment;
+mod erc4337;
 mod error;
 mod filter;
+mod firewall;
+mod gas_profile;
+mod geth_genesis;
 mod interval;
+mod keystore;
+mod live_fork;
+/// Types for configuring the filter of diagnostic logs at runtime.
+pub mod log_filter;
 mod logger;
+mod metrics;
 mod mock;
 /// Types for runtime observability.
 pub mod observability;
+mod parity_trace;
 mod pending;
 mod provider;
+mod raw_block;
+mod replay;
 /// Type for RPC requests.
 pub mod requests;
 mod snapshot;
@@ -24,34 +40,74 @@ mod subscribe;
 pub mod test_utils;
 /// Types for temporal operations
 pub mod time;
+mod token_index;
 mod utils;
+mod verified_sources;
 
 use core::fmt::Debug;
 
+pub use edr_block_miner::{
+    MineOrderingCallbackError, PendingTransactionSummary, SyncMineOrderingCallback,
+};
 use edr_chain_spec::{ChainSpec, HaltReasonTrait};
 use edr_primitives::HashSet;
 use edr_tracing::Trace;
 use lazy_static::lazy_static;
 
 pub use self::{
+    chain_export::{ChainImportError, CHAIN_EXPORT_MAGIC, CHAIN_EXPORT_VERSION},
+    checkpoint::CheckpointError,
     config::{
-        AccountOverride, Fork as ForkConfig, Interval as IntervalConfig, MemPool as MemPoolConfig,
-        Mining as MiningConfig, Provider as ProviderConfig,
+        AccountOverride, Fork as ForkConfig, GasSponsor as GasSponsorConfig,
+        Interval as IntervalConfig, MemPool as MemPoolConfig,
+        MethodPermissions as MethodPermissionsConfig, Mining as MiningConfig,
+        Provider as ProviderConfig, StatePruning as StatePruningConfig,
+        VerifiedSources as VerifiedSourcesConfig,
     },
-    data::{CallResult, ProviderData},
+    data::{BundleBlockOverrides, BundleTransaction, CallResult, ProviderData},
     debug_mine::{DebugMineBlockResult, DebugMineBlockResultForChainSpec},
-    debug_trace::DebugTraceError,
+    debug_trace::{DebugTraceError, DebugTraceLogItem, DebugTraceResult, StructLogStream},
+    deterministic_deployment::{
+        DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS, DETERMINISTIC_DEPLOYMENT_PROXY_BYTECODE,
+    },
+    erc4337::{UserOperationEntity, UserOperationRule, UserOperationViolation},
     error::{
         EstimateGasFailure, ProviderError, ProviderErrorForChainSpec, TransactionFailure,
         TransactionFailureReason,
     },
+    firewall::{SyncTransactionFirewall, TransactionFirewallRequest, TransactionFirewallVerdict},
+    geth_genesis::{GethGenesis, GethGenesisError},
+    keystore::KeystoreError,
     logger::{Logger, NoopLogger, SyncLogger},
+    metrics::{MethodMetrics, ProviderMetrics, StateGauges},
     mock::{CallOverrideResult, SyncCallOverride},
     provider::Provider,
+    raw_block::{RawBlock, RawBlockImportError},
     requests::{
-        eth::calculate_eip1559_fee_parameters, hardhat::rpc_types as hardhat_rpc_types,
-        IntervalConfig as IntervalConfigRequest, InvalidRequestReason, MethodInvocation,
-        ProviderRequest, Timestamp,
+        engine::{
+            ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdatedResult, PayloadAttributesV3,
+            PayloadStatus, PayloadValidity,
+        },
+        eth::{calculate_eip1559_fee_parameters, FillTransactionResult},
+        explorer::{
+            AddressRelation, AddressTransaction, AddressTransactionsOptions,
+            AddressTransactionsPage, DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE,
+            MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE,
+        },
+        hardhat::rpc_types as hardhat_rpc_types,
+        otterscan::{
+            BlockDetails, BlockIssuance, BlockWithTransactionCount, ReceiptWithTimestamp,
+            TraceEntry, TraceEntryType, TransactionSearchResult, MAX_SEARCH_PAGE_SIZE,
+            OTTERSCAN_API_LEVEL,
+        },
+        trace::{
+            CallAction, CallTraceResult, CallType, CreateAction, CreateTraceResult, LocalizedTrace,
+            SuicideAction, TraceAction, TraceFilter, TraceResult, TraceType, TransactionTrace,
+            MAX_TRACE_FILTER_BLOCK_RANGE,
+        },
+        txpool::{TransactionsBySender, TxpoolContent, TxpoolInspect, TxpoolStatus},
+        BatchRequest, IntervalConfig as IntervalConfigRequest, InvalidRequestReason,
+        MethodInvocation, ProviderRequest, Timestamp,
     },
     spec::{ProviderSpec, SyncProviderSpec},
     subscribe::*,
@@ -59,8 +115,13 @@ pub use self::{
 use crate::time::TimeSinceEpoch;
 
 lazy_static! {
-    pub static ref PRIVATE_RPC_METHODS: HashSet<&'static str> =
-        ["hardhat_setLoggingEnabled",].into_iter().collect();
+    pub static ref PRIVATE_RPC_METHODS: HashSet<&'static str> = [
+        "anvil_setLoggingEnabled",
+        "hardhat_setLoggingEnabled",
+        "hardhat_setLoggingOptions",
+    ]
+    .into_iter()
+    .collect();
 }
 
 pub type ProviderResultWithTraces<T, ChainSpecT> = Result<
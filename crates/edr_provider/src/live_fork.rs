@@ -0,0 +1,159 @@
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use edr_eth::block::block_time;
+use edr_rpc_eth::client::{EthRpcClientForChainSpec, RpcClientError};
+use tokio::{
+    runtime,
+    sync::{oneshot, Mutex},
+    task::JoinHandle,
+};
+
+use crate::{data::ProviderData, spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderSpec};
+
+/// Type for tracking the head of the remote blockchain on a separate thread,
+/// advancing the fork block as new remote blocks arrive.
+pub struct LiveFork<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> {
+    inner: Option<Inner>,
+    runtime: runtime::Handle,
+    _phantom: PhantomData<fn() -> (ChainSpecT, TimerT)>,
+}
+
+/// Inner type for tracking the remote blockchain on a separate thread,
+/// required for implementation of `Drop`.
+struct Inner {
+    cancellation_sender: oneshot::Sender<()>,
+    background_task: JoinHandle<()>,
+}
+
+impl<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>
+    LiveFork<ChainSpecT, TimerT>
+{
+    /// Constructs a new instance that tracks the remote blockchain using the
+    /// provided client.
+    ///
+    /// New blocks are received through a subscription if the remote node's
+    /// URL is a WebSocket URL. Otherwise, the remote node is polled once per
+    /// block time of the remote chain.
+    pub fn new(
+        runtime: runtime::Handle,
+        rpc_client: Arc<EthRpcClientForChainSpec<ChainSpecT>>,
+        chain_id: u64,
+        data: Arc<Mutex<ProviderData<ChainSpecT, TimerT>>>,
+    ) -> Self {
+        let (cancellation_sender, cancellation_receiver) = oneshot::channel();
+        let background_task = runtime.spawn(async move {
+            live_fork_loop(
+                rpc_client,
+                block_time(chain_id),
+                data,
+                cancellation_receiver,
+            )
+            .await;
+        });
+
+        Self {
+            inner: Some(Inner {
+                cancellation_sender,
+                background_task,
+            }),
+            runtime,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+async fn live_fork_loop<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    rpc_client: Arc<EthRpcClientForChainSpec<ChainSpecT>>,
+    poll_interval: Duration,
+    data: Arc<Mutex<ProviderData<ChainSpecT, TimerT>>>,
+    mut cancellation_receiver: oneshot::Receiver<()>,
+) {
+    loop {
+        let subscription = tokio::select! {
+            _ = &mut cancellation_receiver => return,
+            subscription = rpc_client.subscribe_new_heads() => subscription,
+        };
+
+        match subscription {
+            Ok(mut subscription) => loop {
+                tokio::select! {
+                    _ = &mut cancellation_receiver => return,
+                    notification = subscription.notifications.recv() => {
+                        if notification.is_none() {
+                            // The connection was closed, so we need to subscribe again.
+                            break;
+                        }
+
+                        if !update_fork(&data, &mut cancellation_receiver).await {
+                            return;
+                        }
+                    }
+                }
+            },
+            Err(error) => {
+                if !matches!(error, RpcClientError::SubscriptionsUnsupported) {
+                    log::warn!(
+                        "Failed to subscribe to new blocks of the remote node, falling back to polling. error: '{error}'"
+                    );
+                }
+
+                tokio::select! {
+                    _ = &mut cancellation_receiver => return,
+                    () = tokio::time::sleep(poll_interval) => {
+                        if !update_fork(&data, &mut cancellation_receiver).await {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Updates the fork to the most recent safe block of the remote blockchain,
+/// if no local blocks have been mined.
+///
+/// Returns `false` if the task was cancelled while waiting for the lock.
+async fn update_fork<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    data: &Mutex<ProviderData<ChainSpecT, TimerT>>,
+    cancellation_receiver: &mut oneshot::Receiver<()>,
+) -> bool {
+    tokio::select! {
+        // Check whether the live fork needs to be destroyed
+        _ = cancellation_receiver => false,
+        mut data = data.lock() => {
+            let is_at_fork_block = data
+                .fork_metadata()
+                .is_some_and(|fork_metadata| fork_metadata.fork_block_number == data.last_block_number());
+
+            if is_at_fork_block && let Err(error) = data.update_fork(None) {
+                log::warn!("Failed to update the fork to the latest remote block. error: '{error}'");
+            }
+
+            true
+        }
+    }
+}
+
+impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Drop
+    for LiveFork<ChainSpecT, TimerT>
+{
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn drop(&mut self) {
+        if let Some(Inner {
+            cancellation_sender,
+            background_task: task,
+        }) = self.inner.take()
+        {
+            if let Ok(()) = cancellation_sender.send(()) {
+                tokio::task::block_in_place(move || self.runtime.block_on(task))
+                    .expect("Failed to join live fork task");
+            } else {
+                log::debug!(
+                    "Failed to send cancellation signal to live fork task. The runtime must have already terminated."
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Runtime configuration of the filter that selects which diagnostic logs are
+//! recorded.
+//!
+//! The provider emits diagnostic logs using [`tracing`], but the subscriber
+//! that records them is installed by the host - e.g. the N-API bindings. A
+//! host that supports changing its filter at runtime registers a handler using
+//! [`set_log_filter_handler`], which is invoked by `hardhat_setLogFilter`.
+
+use std::sync::OnceLock;
+
+/// A handler that replaces the filter of the installed log subscriber. The
+/// filter uses the syntax of `RUST_LOG` directives, e.g.
+/// `edr_provider=debug,edr_rpc_client=trace`.
+pub type LogFilterHandler = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+static LOG_FILTER_HANDLER: OnceLock<LogFilterHandler> = OnceLock::new();
+
+/// Registers the process-wide handler for changing the log filter.
+///
+/// Only a single handler can be registered. If a handler was already
+/// registered, the provided handler is returned as an error.
+pub fn set_log_filter_handler(handler: LogFilterHandler) -> Result<(), LogFilterHandler> {
+    LOG_FILTER_HANDLER.set(handler)
+}
+
+/// Replaces the filter of the installed log subscriber.
+pub(crate) fn set_log_filter(filter: &str) -> Result<(), LogFilterError> {
+    apply_log_filter(LOG_FILTER_HANDLER.get(), filter)
+}
+
+fn apply_log_filter(
+    handler: Option<&LogFilterHandler>,
+    filter: &str,
+) -> Result<(), LogFilterError> {
+    let handler = handler.ok_or(LogFilterError::Unsupported)?;
+
+    handler(filter).map_err(|message| LogFilterError::InvalidFilter {
+        filter: filter.to_owned(),
+        message,
+    })
+}
+
+/// An error that occurred while changing the log filter.
+#[derive(Debug, thiserror::Error)]
+pub enum LogFilterError {
+    /// The filter could not be parsed.
+    #[error("Invalid log filter '{filter}': {message}")]
+    InvalidFilter {
+        /// The provided filter.
+        filter: String,
+        /// The reason why the filter is invalid.
+        message: String,
+    },
+    /// No handler for changing the log filter was registered.
+    #[error(
+        "The log filter cannot be changed, because the host did not install a configurable log subscriber"
+    )]
+    Unsupported,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn apply_log_filter_without_handler() {
+        let result = apply_log_filter(None, "edr_provider=debug");
+        assert!(matches!(result, Err(LogFilterError::Unsupported)));
+    }
+
+    #[test]
+    fn apply_log_filter_with_handler() {
+        let filters = Arc::new(Mutex::new(Vec::new()));
+        let handler_filters = Arc::clone(&filters);
+        let handler: LogFilterHandler = Box::new(move |filter: &str| {
+            if filter.contains('!') {
+                return Err("unexpected character".to_owned());
+            }
+
+            handler_filters.lock().push(filter.to_owned());
+            Ok(())
+        });
+
+        assert!(apply_log_filter(Some(&handler), "edr_provider=debug").is_ok());
+
+        let result = apply_log_filter(Some(&handler), "edr_provider=!");
+        assert!(matches!(
+            result,
+            Err(LogFilterError::InvalidFilter { filter, .. }) if filter == "edr_provider=!"
+        ));
+
+        assert_eq!(*filters.lock(), vec!["edr_provider=debug".to_owned()]);
+    }
+}
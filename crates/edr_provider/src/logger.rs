@@ -7,6 +7,7 @@ use crate::{
     data::CallResult,
     debug_mine::DebugMineBlockResultForChainSpec,
     error::EstimateGasFailure,
+    hardhat_rpc_types::LoggingOptions,
     time::{CurrentTime, TimeSinceEpoch},
     ProviderErrorForChainSpec, ProviderSpec,
 };
@@ -18,6 +19,12 @@ pub trait Logger<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoc
     /// Sets whether the logger is enabled.
     fn set_is_enabled(&mut self, is_enabled: bool);
 
+    /// Sets the options of the logger. Omitted options are left unchanged.
+    fn set_options(
+        &mut self,
+        options: LoggingOptions,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
     fn log_call(
         &mut self,
         hardfork: ChainSpecT::Hardfork,
@@ -129,6 +136,13 @@ impl<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch> Logger<Ch
 
     fn set_is_enabled(&mut self, _is_enabled: bool) {}
 
+    fn set_options(
+        &mut self,
+        _options: LoggingOptions,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
     fn print_method_logs(
         &mut self,
         _method: &str,
@@ -0,0 +1,289 @@
+//! Metrics about the requests handled by a provider and the blocks it mined,
+//! for monitoring long-running nodes.
+
+use std::{collections::BTreeMap, fmt::Write as _, time::Duration};
+
+/// Cumulative metrics of a JSON-RPC method.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodMetrics {
+    /// The number of handled requests.
+    pub requests: u64,
+    /// The number of requests that resulted in an error.
+    pub errors: u64,
+    /// The total time spent handling requests, in seconds.
+    pub duration_seconds: f64,
+}
+
+/// Counters that are recorded while the provider handles requests.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetricsRecorder {
+    methods: BTreeMap<&'static str, MethodMetrics>,
+    blocks_mined: u64,
+    transactions_mined: u64,
+}
+
+impl MetricsRecorder {
+    /// Records a handled request of the provided method.
+    pub fn record_request(
+        &mut self,
+        method_name: &'static str,
+        duration: Duration,
+        is_error: bool,
+    ) {
+        let metrics = self.methods.entry(method_name).or_default();
+
+        metrics.requests += 1;
+        metrics.duration_seconds += duration.as_secs_f64();
+        if is_error {
+            metrics.errors += 1;
+        }
+    }
+
+    /// Records a mined block with the provided number of transactions.
+    pub fn record_mined_block(&mut self, transaction_count: usize) {
+        self.blocks_mined += 1;
+        self.transactions_mined += transaction_count as u64;
+    }
+}
+
+/// Gauges of the provider's state at the time of a metrics snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateGauges {
+    /// The number of the latest block.
+    pub block_number: u64,
+    /// The number of transactions in the mem pool that can be mined in the
+    /// next block.
+    pub pending_transactions: u64,
+    /// The number of transactions in the mem pool with a nonce that is too
+    /// high to be mined in the next block.
+    pub queued_transactions: u64,
+    /// The number of block states that are cached in memory.
+    pub cached_states: u64,
+}
+
+/// A snapshot of a provider's metrics.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderMetrics {
+    /// The metrics of each JSON-RPC method that was requested, keyed by
+    /// method name.
+    pub methods: BTreeMap<String, MethodMetrics>,
+    /// The number of blocks that were mined. Blocks that were reserved by
+    /// `hardhat_mine` without being mined are not included.
+    pub blocks_mined: u64,
+    /// The number of transactions that were mined.
+    pub transactions_mined: u64,
+    /// The number of remote calls that were served from the RPC cache, if the
+    /// provider is forked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork_cache_hits: Option<u64>,
+    /// The number of remote calls that were sent to the remote node, if the
+    /// provider is forked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork_cache_misses: Option<u64>,
+    /// Gauges of the provider's state.
+    pub state: StateGauges,
+}
+
+impl ProviderMetrics {
+    pub(crate) fn new(
+        recorder: &MetricsRecorder,
+        fork_cache_stats: Option<edr_rpc_eth::client::CacheStats>,
+        state: StateGauges,
+    ) -> Self {
+        Self {
+            methods: recorder
+                .methods
+                .iter()
+                .map(|(method_name, metrics)| ((*method_name).to_owned(), metrics.clone()))
+                .collect(),
+            blocks_mined: recorder.blocks_mined,
+            transactions_mined: recorder.transactions_mined,
+            fork_cache_hits: fork_cache_stats.map(|stats| stats.hits),
+            fork_cache_misses: fork_cache_stats.map(|stats| stats.misses),
+            state,
+        }
+    }
+
+    /// Encodes the metrics in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut output = String::new();
+
+        write_header(
+            &mut output,
+            "edr_rpc_requests_total",
+            "counter",
+            "The number of handled JSON-RPC requests.",
+        );
+        for (method_name, metrics) in &self.methods {
+            write_method_sample(
+                &mut output,
+                "edr_rpc_requests_total",
+                method_name,
+                metrics.requests,
+            );
+        }
+
+        write_header(
+            &mut output,
+            "edr_rpc_request_errors_total",
+            "counter",
+            "The number of JSON-RPC requests that resulted in an error.",
+        );
+        for (method_name, metrics) in &self.methods {
+            write_method_sample(
+                &mut output,
+                "edr_rpc_request_errors_total",
+                method_name,
+                metrics.errors,
+            );
+        }
+
+        write_header(
+            &mut output,
+            "edr_rpc_request_duration_seconds",
+            "summary",
+            "The time spent handling JSON-RPC requests.",
+        );
+        for (method_name, metrics) in &self.methods {
+            write_method_sample(
+                &mut output,
+                "edr_rpc_request_duration_seconds_sum",
+                method_name,
+                metrics.duration_seconds,
+            );
+            write_method_sample(
+                &mut output,
+                "edr_rpc_request_duration_seconds_count",
+                method_name,
+                metrics.requests,
+            );
+        }
+
+        write_metric(
+            &mut output,
+            "edr_blocks_mined_total",
+            "counter",
+            "The number of mined blocks.",
+            self.blocks_mined,
+        );
+        write_metric(
+            &mut output,
+            "edr_transactions_mined_total",
+            "counter",
+            "The number of mined transactions.",
+            self.transactions_mined,
+        );
+
+        if let Some(hits) = self.fork_cache_hits {
+            write_metric(
+                &mut output,
+                "edr_fork_cache_hits_total",
+                "counter",
+                "The number of remote calls that were served from the RPC cache.",
+                hits,
+            );
+        }
+        if let Some(misses) = self.fork_cache_misses {
+            write_metric(
+                &mut output,
+                "edr_fork_cache_misses_total",
+                "counter",
+                "The number of remote calls that were sent to the remote node.",
+                misses,
+            );
+        }
+
+        write_metric(
+            &mut output,
+            "edr_block_number",
+            "gauge",
+            "The number of the latest block.",
+            self.state.block_number,
+        );
+        write_metric(
+            &mut output,
+            "edr_pending_transactions",
+            "gauge",
+            "The number of mem pool transactions that can be mined in the next block.",
+            self.state.pending_transactions,
+        );
+        write_metric(
+            &mut output,
+            "edr_queued_transactions",
+            "gauge",
+            "The number of mem pool transactions with a nonce that is too high.",
+            self.state.queued_transactions,
+        );
+        write_metric(
+            &mut output,
+            "edr_cached_states",
+            "gauge",
+            "The number of block states that are cached in memory.",
+            self.state.cached_states,
+        );
+
+        output
+    }
+}
+
+fn write_header(output: &mut String, name: &str, metric_type: &str, help: &str) {
+    writeln!(output, "# HELP {name} {help}").expect("Writing to a string cannot fail");
+    writeln!(output, "# TYPE {name} {metric_type}").expect("Writing to a string cannot fail");
+}
+
+fn write_method_sample(
+    output: &mut String,
+    name: &str,
+    method_name: &str,
+    value: impl std::fmt::Display,
+) {
+    writeln!(output, "{name}{{method=\"{method_name}\"}} {value}")
+        .expect("Writing to a string cannot fail");
+}
+
+fn write_metric(
+    output: &mut String,
+    name: &str,
+    metric_type: &str,
+    help: &str,
+    value: impl std::fmt::Display,
+) {
+    write_header(output, name, metric_type, help);
+    writeln!(output, "{name} {value}").expect("Writing to a string cannot fail");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_prometheus() {
+        let mut recorder = MetricsRecorder::default();
+        recorder.record_request("eth_call", Duration::from_millis(500), false);
+        recorder.record_request("eth_call", Duration::from_millis(250), true);
+        recorder.record_mined_block(3);
+
+        let metrics = ProviderMetrics::new(
+            &recorder,
+            None,
+            StateGauges {
+                block_number: 1,
+                pending_transactions: 2,
+                queued_transactions: 0,
+                cached_states: 2,
+            },
+        );
+
+        let output = metrics.to_prometheus();
+        assert!(output.contains("edr_rpc_requests_total{method=\"eth_call\"} 2\n"));
+        assert!(output.contains("edr_rpc_request_errors_total{method=\"eth_call\"} 1\n"));
+        assert!(output.contains("edr_rpc_request_duration_seconds_sum{method=\"eth_call\"} 0.75\n"));
+        assert!(output.contains("edr_blocks_mined_total 1\n"));
+        assert!(output.contains("edr_transactions_mined_total 3\n"));
+        assert!(output.contains("edr_pending_transactions 2\n"));
+        assert!(!output.contains("edr_fork_cache_hits_total"));
+    }
+}
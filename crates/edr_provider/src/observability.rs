@@ -13,6 +13,7 @@ use edr_chain_spec_evm::{
 use edr_coverage::{reporter::SyncOnCollectedCoverageCallback, CodeCoverageReporter};
 use edr_database_components::DatabaseComponents;
 use edr_gas_report::SyncOnCollectedGasReportCallback;
+use edr_primitives::{Address, U256};
 use edr_state_api::State;
 use edr_tracing::TraceCollector;
 use revm_inspector::JournalExt;
@@ -141,4 +142,9 @@ impl<
     fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, context: &mut ContextT) {
         self.trace_collector.step(interp, context);
     }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.trace_collector
+            .notify_self_destruct(contract, target, value);
+    }
 }
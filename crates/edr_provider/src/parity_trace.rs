@@ -0,0 +1,247 @@
+//! Tracing of transactions in the format of OpenEthereum's `trace` module,
+//! built on top of the call trees that are collected by the
+//! [`TraceCollector`].
+
+use edr_block_header::BlockHeader;
+use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
+use edr_chain_spec::{ExecutableTransaction as _, HaltReasonTrait};
+use edr_chain_spec_block::BlockChainSpec;
+use edr_chain_spec_evm::{
+    interpreter::CallScheme,
+    result::{ExecutionResult, ExecutionResultAndState, Output},
+    CfgEnv,
+};
+use edr_evm::{dry_run_with_inspector, run};
+use edr_precompile::CustomPrecompile;
+use edr_primitives::{Address, HashMap, B256};
+use edr_state_api::{DynState, StateCommit as _};
+use edr_tracing::{Trace, TraceCollector, TraceMessage};
+
+use crate::{
+    debug_trace::DebugTraceErrorForChainSpec,
+    requests::trace::{
+        CallAction, CallTraceResult, CallType, CreateAction, CreateTraceResult, SuicideAction,
+        TraceAction, TraceResult, TraceType, TransactionTrace,
+    },
+    TransactionFailureReason,
+};
+
+/// Executes the transactions of a block on top of its parent's state,
+/// collecting the call tree of each transaction.
+///
+/// If a transaction hash is provided, only that transaction is traced and
+/// execution stops after it. Otherwise, all transactions are traced.
+pub fn trace_transactions<'header, ChainSpecT: BlockChainSpec>(
+    blockchain: &dyn BlockHashByNumber<Error = DynBlockchainError>,
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    mut state: Box<dyn DynState>,
+    evm_config: CfgEnv<ChainSpecT::Hardfork>,
+    block: ChainSpecT::BlockEnv<'header, BlockHeader>,
+    transactions: Vec<ChainSpecT::SignedTransaction>,
+    transaction_hash: Option<&B256>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
+) -> Result<Vec<(B256, Vec<Trace<ChainSpecT::HaltReason>>)>, DebugTraceErrorForChainSpec<ChainSpecT>>
+{
+    let mut traces = Vec::new();
+
+    for transaction in transactions {
+        let current_hash = *transaction.transaction_hash();
+
+        if transaction_hash.is_some_and(|transaction_hash| *transaction_hash != current_hash) {
+            run::<ChainSpecT, _, _, _>(
+                blockchain,
+                state.as_mut(),
+                evm_config.clone(),
+                transaction,
+                &block,
+                custom_precompiles,
+            )?;
+
+            continue;
+        }
+
+        let mut trace_collector = TraceCollector::new(false);
+
+        let ExecutionResultAndState {
+            result: _result,
+            state: changes,
+        } = dry_run_with_inspector::<ChainSpecT, _, _, _, _>(
+            blockchain,
+            state.as_ref(),
+            evm_config.clone(),
+            transaction,
+            &block,
+            custom_precompiles,
+            &mut trace_collector,
+        )?;
+
+        state.commit(changes);
+        traces.push((current_hash, trace_collector.into_traces()));
+
+        if transaction_hash.is_some() {
+            break;
+        }
+    }
+
+    Ok(traces)
+}
+
+struct Frame {
+    index: usize,
+    trace_address: Vec<u64>,
+    subtraces: u64,
+}
+
+/// Converts the call tree of a transaction into a flat list of traces, in
+/// depth-first order.
+pub fn transaction_traces<HaltReasonT: HaltReasonTrait>(
+    traces: &[Trace<HaltReasonT>],
+    cast_halt_reason: impl Fn(HaltReasonT) -> TransactionFailureReason<HaltReasonT>,
+) -> Vec<TransactionTrace> {
+    let mut transaction_traces: Vec<TransactionTrace> = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+
+    let next_trace_address = |frames: &mut Vec<Frame>| {
+        frames.last_mut().map_or_else(Vec::new, |parent| {
+            let mut trace_address = parent.trace_address.clone();
+            trace_address.push(parent.subtraces);
+            parent.subtraces += 1;
+
+            trace_address
+        })
+    };
+
+    for message in traces.iter().flat_map(|trace| &trace.messages) {
+        match message {
+            TraceMessage::Before(message) => {
+                let trace_address = next_trace_address(&mut frames);
+
+                let (trace_type, action) = match (message.to, message.scheme) {
+                    (Some(to), Some(scheme)) => {
+                        // For delegate calls and call codes, the executed code
+                        // is that of the code address, in the context of `to`.
+                        let (call_type, from, to) = match scheme {
+                            CallScheme::Call => (CallType::Call, message.caller, to),
+                            CallScheme::StaticCall => (CallType::StaticCall, message.caller, to),
+                            CallScheme::CallCode => {
+                                (CallType::CallCode, to, message.code_address.unwrap_or(to))
+                            }
+                            CallScheme::DelegateCall => (
+                                CallType::DelegateCall,
+                                to,
+                                message.code_address.unwrap_or(to),
+                            ),
+                        };
+
+                        (
+                            TraceType::Call,
+                            TraceAction::Call(CallAction {
+                                call_type,
+                                from,
+                                gas: message.gas_limit,
+                                input: message.data.clone(),
+                                to,
+                                value: message.value,
+                            }),
+                        )
+                    }
+                    _ => (
+                        TraceType::Create,
+                        TraceAction::Create(CreateAction {
+                            from: message.caller,
+                            gas: message.gas_limit,
+                            init: message.data.clone(),
+                            value: message.value,
+                        }),
+                    ),
+                };
+
+                frames.push(Frame {
+                    index: transaction_traces.len(),
+                    trace_address: trace_address.clone(),
+                    subtraces: 0,
+                });
+
+                transaction_traces.push(TransactionTrace {
+                    action,
+                    error: None,
+                    result: None,
+                    subtraces: 0,
+                    trace_address,
+                    trace_type,
+                });
+            }
+            TraceMessage::After(message) => {
+                let Some(frame) = frames.pop() else {
+                    continue;
+                };
+
+                let trace = &mut transaction_traces[frame.index];
+                trace.subtraces = frame.subtraces;
+
+                match &message.execution_result {
+                    ExecutionResult::Success {
+                        gas_used, output, ..
+                    } => {
+                        trace.result = Some(match output {
+                            Output::Call(output) => TraceResult::Call(CallTraceResult {
+                                gas_used: *gas_used,
+                                output: output.clone(),
+                            }),
+                            Output::Create(code, address) => {
+                                TraceResult::Create(CreateTraceResult {
+                                    address: address
+                                        .or(message.contract_address)
+                                        .unwrap_or_default(),
+                                    code: code.clone(),
+                                    gas_used: *gas_used,
+                                })
+                            }
+                        });
+                    }
+                    ExecutionResult::Revert { .. } => {
+                        trace.error = Some("Reverted".to_string());
+                    }
+                    ExecutionResult::Halt { reason, .. } => {
+                        trace.error = Some(halt_error(cast_halt_reason(reason.clone())));
+                    }
+                }
+            }
+            TraceMessage::SelfDestruct(message) => {
+                let trace_address = next_trace_address(&mut frames);
+
+                transaction_traces.push(TransactionTrace {
+                    action: TraceAction::Suicide(SuicideAction {
+                        address: message.contract,
+                        refund_address: message.target,
+                        balance: message.value,
+                    }),
+                    error: None,
+                    result: None,
+                    subtraces: 0,
+                    trace_address,
+                    trace_type: TraceType::Suicide,
+                });
+            }
+            TraceMessage::Step(_) => {}
+        }
+    }
+
+    transaction_traces
+}
+
+/// Returns the error message of a halted call, matching OpenEthereum where
+/// possible.
+fn halt_error<HaltReasonT: HaltReasonTrait>(
+    reason: TransactionFailureReason<HaltReasonT>,
+) -> String {
+    match reason {
+        TransactionFailureReason::CreateContractSizeLimit => {
+            "Contract code size limit exceeded".to_string()
+        }
+        TransactionFailureReason::Inner(reason) => format!("{reason:?}"),
+        TransactionFailureReason::OpcodeNotFound => "Bad instruction".to_string(),
+        TransactionFailureReason::OutOfGas(_) => "Out of gas".to_string(),
+        TransactionFailureReason::Revert(_) => "Reverted".to_string(),
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use edr_block_api::Block;
 use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
 use edr_primitives::B256;
+use edr_state_api::DynState;
 
 /// A blockchain with a pending block.
 pub(crate) struct BlockchainWithPending<'blockchain, LocalBlockT, SignedTransactionT> {
@@ -19,11 +20,11 @@ impl<'blockchain, LocalBlockT, SignedTransactionT>
     /// block.
     pub fn new(
         blockchain: &'blockchain dyn BlockHashByNumber<Error = DynBlockchainError>,
-        pending_block: LocalBlockT,
+        pending_block: Arc<LocalBlockT>,
     ) -> Self {
         Self {
             blockchain,
-            pending_block: pending_block.into(),
+            pending_block,
             _phantom: PhantomData,
         }
     }
@@ -47,3 +48,21 @@ impl<LocalBlockT: Block<SignedTransactionT>, SignedTransactionT> BlockHashByNumb
         }
     }
 }
+
+/// A pending block that was mined from the mem pool, together with the state
+/// after executing its transactions.
+pub(crate) struct PendingBlock<LocalBlockT> {
+    /// The pending block
+    pub block: Arc<LocalBlockT>,
+    /// State after executing the pending block
+    pub state: Arc<Box<dyn DynState>>,
+}
+
+impl<LocalBlockT> Clone for PendingBlock<LocalBlockT> {
+    fn clone(&self) -> Self {
+        Self {
+            block: Arc::clone(&self.block),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
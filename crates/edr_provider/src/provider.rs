@@ -1,6 +1,9 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
+use edr_block_miner::SyncMineOrderingCallback;
 use edr_chain_spec::{HardforkChainSpec, TransactionValidation};
+use edr_primitives::B256;
+use edr_rpc_eth::jsonrpc;
 use edr_solidity::contract_decoder::ContractDecoder;
 use edr_transaction::{IsEip155, IsEip4844, TransactionMut, TransactionType};
 use parking_lot::Mutex;
@@ -8,19 +11,29 @@ use tokio::{runtime, sync::Mutex as AsyncMutex, task};
 
 use crate::{
     data::ProviderData,
+    debug_trace::{DebugTraceResult, StructLogStream},
     error::{CreationErrorForChainSpec, ProviderError, ProviderErrorForChainSpec},
     interval::IntervalMiner,
+    live_fork::LiveFork,
     logger::SyncLogger,
+    metrics::ProviderMetrics,
     mock::SyncCallOverride,
     requests::{
-        debug,
-        eth::{self, handle_set_interval_mining},
-        hardhat, MethodInvocation, ProviderRequest,
+        admin,
+        debug::{self, DebugTraceConfig},
+        engine,
+        eth::{self, handle_set_interval_mining, handle_set_interval_mining_paused},
+        explorer,
+        hardhat::{
+            self,
+            rpc_types::{FloodOptions, FloodResult},
+        },
+        otterscan, trace, txpool, BatchRequest, MethodInvocation, ProviderRequest,
     },
     spec::{ProviderSpec, SyncProviderSpec},
     time::{CurrentTime, TimeSinceEpoch},
-    to_json, to_json_with_trace, to_json_with_traces, ProviderConfig, ResponseWithTraces,
-    SyncSubscriberCallback, PRIVATE_RPC_METHODS,
+    to_json, to_json_with_trace, to_json_with_traces, MethodPermissionsConfig, ProviderConfig,
+    ResponseWithTraces, SyncSubscriberCallback, SyncTransactionFirewall, PRIVATE_RPC_METHODS,
 };
 
 /// A JSON-RPC provider for Ethereum.
@@ -60,6 +73,11 @@ pub struct Provider<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceE
     /// mutex, so it needs to internally check for cancellation/self-destruction
     /// while async-awaiting the lock to avoid a deadlock.
     interval_miner: Arc<Mutex<Option<IntervalMiner<ChainSpecT, TimerT>>>>,
+    /// Tracks the head of the remote blockchain in the background, if enabled.
+    /// Like the interval miner, it checks for cancellation while awaiting the
+    /// data mutex.
+    _live_fork: Option<LiveFork<ChainSpecT, TimerT>>,
+    method_permissions: MethodPermissionsConfig,
     runtime: runtime::Handle,
 }
 
@@ -106,6 +124,13 @@ impl<
             contract_decoder,
             timer,
         )?;
+
+        let live_fork_client = config
+            .fork
+            .as_ref()
+            .filter(|fork_config| fork_config.live)
+            .and_then(|_| Some((data.rpc_client()?.clone(), data.fork_metadata()?.chain_id)));
+
         let data = Arc::new(AsyncMutex::new(data));
 
         let interval_miner = config
@@ -116,9 +141,15 @@ impl<
 
         let interval_miner = Arc::new(Mutex::new(interval_miner));
 
+        let live_fork = live_fork_client.map(|(rpc_client, chain_id)| {
+            LiveFork::new(runtime.clone(), rpc_client, chain_id, data.clone())
+        });
+
         Ok(Self {
             data,
             interval_miner,
+            _live_fork: live_fork,
+            method_permissions: config.method_permissions,
             runtime,
         })
     }
@@ -135,6 +166,33 @@ impl<
         data.set_call_override_callback(call_override_callback);
     }
 
+    /// Sets the callback that determines the order in which pending
+    /// transactions are mined. Set to `None` to restore the configured mine
+    /// ordering.
+    pub fn set_mine_ordering_callback(
+        &self,
+        mine_ordering_callback: Option<Arc<dyn SyncMineOrderingCallback>>,
+    ) {
+        let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+        data.set_mine_ordering_callback(mine_ordering_callback);
+    }
+
+    /// Sets the callback that decides whether transactions are added to the
+    /// mem pool. Set to `None` to admit all transactions.
+    pub fn set_transaction_firewall(
+        &self,
+        transaction_firewall: Option<Arc<dyn SyncTransactionFirewall>>,
+    ) {
+        let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+        data.set_transaction_firewall(transaction_firewall);
+    }
+
+    /// Returns a snapshot of the provider's metrics.
+    pub fn metrics(&self) -> ProviderMetrics {
+        let data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+        data.metrics()
+    }
+
     pub fn set_verbose_tracing(&self, enabled: bool) {
         let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
         data.set_verbose_tracing(enabled);
@@ -159,48 +217,173 @@ impl<
         request: ProviderRequest<ChainSpecT>,
     ) -> Result<ResponseWithTraces<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
     {
-        let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+        let (response, mut data) = match request {
+            ProviderRequest::Single(request) => {
+                let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+                let response = self.handle_single_request(&mut data, *request)?;
 
-        let response = match request {
-            ProviderRequest::Single(request) => self.handle_single_request(&mut data, *request),
-            ProviderRequest::Batch(requests) => self.handle_batch_request(&mut data, requests),
-        }?;
+                (response, data)
+            }
+            ProviderRequest::Batch(requests) => {
+                let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+                let response = self.handle_batch_request(&mut data, requests)?;
+
+                (response, data)
+            }
+        };
+
+        // Look up the sources of forked contracts, to decode their traces in
+        // subsequent requests
+        let verified_source_lookup = data.verified_source_lookup(&response.traces);
+
+        // The sources are fetched over the network in the background, which
+        // shouldn't block this or other requests
+        drop(data);
+        if let Some(lookup) = verified_source_lookup {
+            lookup.register(&self.runtime);
+        }
 
         Ok(response)
     }
 
+    /// Blocking method to handle a `debug_traceTransaction` request, streaming
+    /// the struct logs to the provided stream instead of including them in
+    /// the result.
+    pub fn handle_streamed_debug_trace_transaction(
+        &self,
+        transaction_hash: B256,
+        config: Option<DebugTraceConfig>,
+        struct_log_stream: StructLogStream,
+    ) -> Result<DebugTraceResult, ProviderErrorForChainSpec<ChainSpecT>> {
+        let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+
+        self.ensure_method_permitted(&mut data, "debug_traceTransaction", true, Instant::now())?;
+
+        let (result, _traces) = debug::handle_debug_trace_transaction(
+            &mut data,
+            transaction_hash,
+            config,
+            Some(struct_log_stream),
+        )?;
+
+        Ok(result)
+    }
+
+    /// Blocking method to create and mine synthetic transactions, without
+    /// serializing them individually. See `hardhat_flood`.
+    pub fn flood(
+        &self,
+        options: FloodOptions,
+    ) -> Result<FloodResult, ProviderErrorForChainSpec<ChainSpecT>> {
+        let mut data = task::block_in_place(|| self.runtime.block_on(self.data.lock()));
+
+        self.ensure_method_permitted(&mut data, "hardhat_flood", false, Instant::now())?;
+
+        hardhat::handle_flood(&mut data, options)
+    }
+
     /// Handles a batch of JSON requests for an execution provider.
+    ///
+    /// The requests are executed in order while the lock on the provider's
+    /// data is held, so the batch is handled atomically: other requests can't
+    /// observe or modify the provider's state in between its requests.
+    /// Read-only requests aren't executed concurrently, as every request
+    /// requires exclusive access to the provider's data.
+    ///
+    /// The result is an array with a JSON-RPC response for each request that
+    /// has an id, so a failing request doesn't fail the whole batch. If the
+    /// batch only consists of notifications, no response is returned and the
+    /// result is `null`.
     fn handle_batch_request(
         &self,
         data: &mut ProviderData<ChainSpecT, TimerT>,
-        request: Vec<MethodInvocation<ChainSpecT>>,
+        requests: Vec<BatchRequest<ChainSpecT>>,
     ) -> Result<ResponseWithTraces<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
     {
-        let mut results = Vec::new();
+        let mut responses = Vec::with_capacity(requests.len());
         let mut traces = Vec::new();
 
-        for req in request {
-            let response = self.handle_single_request(data, req)?;
-            results.push(response.result);
-            traces.extend(response.traces);
+        for BatchRequest { id, method } in requests {
+            let response_data = match self.handle_single_request(data, method) {
+                Ok(response) => {
+                    traces.extend(response.traces);
+
+                    jsonrpc::ResponseData::Success {
+                        result: response.result,
+                    }
+                }
+                Err(mut error) => {
+                    if let Some(failure) = error.as_transaction_failure_mut() {
+                        traces.extend(std::mem::take(&mut failure.traces));
+                    }
+
+                    jsonrpc::ResponseData::Error {
+                        error: error.into(),
+                    }
+                }
+            };
+
+            // Notifications don't receive a response
+            if let Some(id) = id {
+                responses.push(jsonrpc::Response {
+                    jsonrpc: jsonrpc::Version::V2_0,
+                    id,
+                    data: response_data,
+                });
+            }
         }
 
-        let result = serde_json::to_value(results).map_err(ProviderError::Serialization)?;
+        let result = if responses.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::to_value(responses).map_err(ProviderError::Serialization)?
+        };
+
         Ok(ResponseWithTraces { result, traces })
     }
 
+    /// Returns an error if the provider's configuration doesn't permit the
+    /// method.
+    fn ensure_method_permitted(
+        &self,
+        data: &mut ProviderData<ChainSpecT, TimerT>,
+        method_name: &'static str,
+        is_read_only: bool,
+        start: Instant,
+    ) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+        if self
+            .method_permissions
+            .is_permitted(method_name, is_read_only)
+        {
+            return Ok(());
+        }
+
+        tracing::debug!("Rejected request that is not permitted");
+        data.record_request(method_name, start.elapsed(), true);
+
+        Err(ProviderError::MethodNotPermitted {
+            method: method_name.to_owned(),
+        })
+    }
+
     fn handle_single_request(
         &self,
         data: &mut ProviderData<ChainSpecT, TimerT>,
         request: MethodInvocation<ChainSpecT>,
     ) -> Result<ResponseWithTraces<ChainSpecT::HaltReason>, ProviderErrorForChainSpec<ChainSpecT>>
     {
+        let start = Instant::now();
+        let request_method_name = request.method_name();
+        let is_read_only = request.is_read_only();
+        let _span = tracing::debug_span!("request", method = request_method_name).entered();
+
+        self.ensure_method_permitted(data, request_method_name, is_read_only, start)?;
+
         let method_name = if data.logger_mut().is_enabled() {
-            let method_name = request.method_name();
-            if PRIVATE_RPC_METHODS.contains(method_name) {
+            if PRIVATE_RPC_METHODS.contains(request_method_name) {
                 None
             } else {
-                Some(method_name)
+                Some(request_method_name)
             }
         } else {
             None
@@ -221,6 +404,10 @@ impl<
                 eth::handle_call_request(data, request, block_spec, state_overrides)
                     .and_then(to_json_with_trace::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::CallBundle(request) => {
+                hardhat::handle_call_bundle_request(data, request)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::ChainId(()) => {
                 eth::handle_chain_id_request(data).and_then(to_json::<_, ChainSpecT, TimerT>)
             }
@@ -236,10 +423,26 @@ impl<
                 eth::handle_sign_request(data, message, address)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::PersonalImportRawKey(secret_key, passphrase) => {
+                eth::handle_import_raw_key_request(data, secret_key, passphrase)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::PersonalNewAccount(passphrase) => {
+                eth::handle_new_account_request(data, passphrase)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::PersonalUnlockAccount(address, passphrase, duration) => {
+                eth::handle_unlock_account_request(data, address, passphrase, duration)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::FeeHistory(block_count, newest_block, reward_percentiles) => {
                 eth::handle_fee_history(data, block_count, newest_block, reward_percentiles)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::FillTransaction(transaction_request) => {
+                eth::handle_fill_transaction_request(data, transaction_request)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::GasPrice(()) => {
                 eth::handle_gas_price(data).and_then(to_json::<_, ChainSpecT, TimerT>)
             }
@@ -247,6 +450,10 @@ impl<
                 eth::handle_get_balance_request(data, address, block_spec)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::GetBlobSidecars(block_hash) => {
+                eth::handle_get_blob_sidecars_request(data, block_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::GetBlockByNumber(block_spec, transaction_detail_flag) => {
                 eth::handle_get_block_by_number_request(data, block_spec, transaction_detail_flag)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -255,6 +462,10 @@ impl<
                 eth::handle_get_block_by_hash_request(data, block_hash, transaction_detail_flag)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::GetBlockReceipts(block_spec) => {
+                eth::handle_get_block_receipts_request(data, block_spec)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::GetBlockTransactionCountByHash(block_hash) => {
                 eth::handle_get_block_transaction_count_by_hash_request(data, block_hash)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -331,6 +542,10 @@ impl<
                 eth::handle_send_transaction_request(data, transaction_request)
                     .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SignTransaction(transaction_request) => {
+                eth::handle_sign_transaction_request(data, transaction_request)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SignTypedDataV4(address, message) => {
                 eth::handle_sign_typed_data_v4(data, address, message)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -371,6 +586,10 @@ impl<
                 eth::handle_revert_request(data, snapshot_id)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::EvmSetAccountStorageRoot(address, storage_root) => {
+                hardhat::handle_set_account_storage_root(data, address, storage_root)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::EvmSetAutomine(enabled) => {
                 eth::handle_set_automine_request(data, enabled)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -390,13 +609,26 @@ impl<
                 eth::handle_set_next_block_timestamp_request(data, timestamp)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::EvmSetTime(timestamp) => {
+                eth::handle_set_time_request(data, timestamp)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::EvmSnapshot(()) => {
                 eth::handle_snapshot_request(data).and_then(to_json::<_, ChainSpecT, TimerT>)
             }
 
+            // admin_* methods
+            MethodInvocation::AdminImportChain(chain) => {
+                admin::handle_import_chain(data, chain).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
             // debug_* methods
+            MethodInvocation::DebugSetHead(block_number) => {
+                debug::handle_set_head(data, block_number.to())
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::DebugTraceTransaction(transaction_hash, config) => {
-                debug::handle_debug_trace_transaction(data, transaction_hash, config)
+                debug::handle_debug_trace_transaction(data, transaction_hash, config, None)
                     .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
             }
             MethodInvocation::DebugTraceCall(call_request, block_spec, config) => {
@@ -404,17 +636,156 @@ impl<
                     .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
             }
 
+            // engine_* methods
+            MethodInvocation::EngineForkchoiceUpdatedV3(forkchoice_state, payload_attributes) => {
+                engine::handle_forkchoice_updated_v3(data, forkchoice_state, payload_attributes)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::EngineNewPayloadV3(
+                payload,
+                expected_blob_versioned_hashes,
+                parent_beacon_block_root,
+            ) => engine::handle_new_payload_v3(
+                data,
+                payload,
+                expected_blob_versioned_hashes,
+                parent_beacon_block_root,
+            )
+            .and_then(to_json::<_, ChainSpecT, TimerT>),
+
+            // explorer_* methods
+            MethodInvocation::ExplorerGetAddressTransactions(address, options) => {
+                explorer::handle_get_address_transactions(data, address, options)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
+            // ots_* methods
+            MethodInvocation::OtsGetApiLevel(()) => {
+                otterscan::handle_get_api_level::<ChainSpecT, TimerT>()
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::OtsGetBlockDetails(block_number) => {
+                otterscan::handle_get_block_details(data, block_number)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::OtsGetTransactionError(transaction_hash) => {
+                otterscan::handle_get_transaction_error(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::OtsSearchTransactionsAfter(address, block_number, page_size) => {
+                otterscan::handle_search_transactions_after(data, address, block_number, page_size)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::OtsSearchTransactionsBefore(address, block_number, page_size) => {
+                otterscan::handle_search_transactions_before(data, address, block_number, page_size)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::OtsTraceTransaction(transaction_hash) => {
+                otterscan::handle_trace_transaction(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
+            // trace_* methods
+            MethodInvocation::TraceBlock(block_spec) => trace::handle_trace_block(data, block_spec)
+                .and_then(to_json::<_, ChainSpecT, TimerT>),
+            MethodInvocation::TraceFilter(filter) => {
+                trace::handle_trace_filter(data, filter).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::TraceTransaction(transaction_hash) => {
+                trace::handle_trace_transaction(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
+            // txpool_* methods
+            MethodInvocation::TxpoolContent(()) => {
+                txpool::handle_txpool_content(data).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::TxpoolInspect(()) => {
+                txpool::handle_txpool_inspect(data).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::TxpoolStatus(()) => {
+                txpool::handle_txpool_status(data).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
+            // anvil_* methods
+            MethodInvocation::AnvilDropAllTransactions(()) => {
+                hardhat::handle_drop_all_transactions(data)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AnvilDropTransaction(transaction_hash) => {
+                hardhat::handle_anvil_drop_transaction(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AnvilMine(number_of_blocks, interval) => {
+                hardhat::handle_mine(data, number_of_blocks, interval)
+                    .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AnvilNodeInfo(()) => {
+                hardhat::handle_node_info_request(data).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AnvilSetLoggingEnabled(is_enabled) => {
+                hardhat::handle_set_logging_enabled_request(data, is_enabled)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AnvilSetNextBlockBaseFeePerGas(base_fee_per_gas) => {
+                hardhat::handle_set_next_block_base_fee_per_gas_request(data, base_fee_per_gas.to())
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+
             // hardhat_* methods
+            MethodInvocation::AddContractArtifact(artifact) => {
+                hardhat::handle_add_contract_artifact(data, artifact)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::AutoImpersonateAccount(enabled) => {
+                hardhat::handle_auto_impersonate_account_request(data, enabled)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::DropTransaction(transaction_hash) => {
                 hardhat::handle_drop_transaction(data, transaction_hash)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::EnqueueDeposit(raw_transaction) => {
+                hardhat::handle_enqueue_deposit(data, raw_transaction)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ExportChain(()) => {
+                hardhat::handle_export_chain(data).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::Flood(options) => {
+                hardhat::handle_flood(data, options).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::GetAutomine(()) => hardhat::handle_get_automine_request(data)
                 .and_then(to_json::<_, ChainSpecT, TimerT>),
+            MethodInvocation::GetGasProfile(transaction_hash) => {
+                hardhat::handle_get_gas_profile_request(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::GetNextNonce(address) => {
+                hardhat::handle_get_next_nonce_request(data, *address)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::GetStateDiff(transaction_hash) => {
+                hardhat::handle_get_state_diff_request(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::GetTransactionTags(transaction_hash) => {
+                hardhat::handle_get_transaction_tags(data, transaction_hash)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::ImpersonateAccount(address) => {
                 hardhat::handle_impersonate_account_request(data, *address)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::ImportChain(chain) => {
+                hardhat::handle_import_chain(data, chain).and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ImportTransactions(source) => {
+                hardhat::handle_import_transactions(data, source)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::LoadCheckpoint(name) => hardhat::handle_load_checkpoint(data, &name)
+                .and_then(to_json::<_, ChainSpecT, TimerT>),
             MethodInvocation::Metadata(()) => {
                 hardhat::handle_metadata_request(data).and_then(to_json::<_, ChainSpecT, TimerT>)
             }
@@ -422,10 +793,60 @@ impl<
                 hardhat::handle_mine(data, number_of_blocks, interval)
                     .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::PauseIntervalMining(()) => {
+                handle_set_interval_mining_paused(&self.interval_miner.lock(), true)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::PredictCreate2Address(deployer, salt, init_code) => {
+                hardhat::handle_predict_create2_address::<ChainSpecT, TimerT>(
+                    deployer, salt, init_code,
+                )
+                .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::PredictCreateAddress(deployer, nonce) => {
+                hardhat::handle_predict_create_address(data, deployer, nonce)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ReadStorageVariable(address, variable, keys, block_spec) => {
+                hardhat::handle_read_storage_variable(data, address, variable, keys, block_spec)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::Reorg(depth, options) => hardhat::handle_reorg(data, depth, options)
+                .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>),
+            MethodInvocation::ReplayBlock(block_number) => {
+                hardhat::handle_replay_block_request(data, block_number.to())
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ReplayTransaction(transaction_hash) => {
+                hardhat::handle_replay_transaction_request(data, transaction_hash)
+                    .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ResumeIntervalMining(()) => {
+                handle_set_interval_mining_paused(&self.interval_miner.lock(), false)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::RunFixture(transactions) => {
+                hardhat::handle_run_fixture(data, transactions)
+                    .and_then(to_json_with_traces::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SaveCheckpoint(name) => hardhat::handle_save_checkpoint(data, &name)
+                .and_then(to_json::<_, ChainSpecT, TimerT>),
+            MethodInvocation::SetAllowUnlimitedContractSize(allow) => {
+                hardhat::handle_set_allow_unlimited_contract_size(data, allow)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetAllowUnlimitedInitCodeSize(allow) => {
+                hardhat::handle_set_allow_unlimited_init_code_size(data, allow)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetBalance(address, balance) => {
                 hardhat::handle_set_balance(data, address, balance)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetBeaconRoot(timestamp, root) => {
+                hardhat::handle_set_beacon_root(data, timestamp, root)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetCode(address, code) => {
                 hardhat::handle_set_code(data, address, code)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -434,10 +855,28 @@ impl<
                 hardhat::handle_set_coinbase_request(data, coinbase)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetFeeChecks(config) => hardhat::handle_set_fee_checks(data, config)
+                .and_then(to_json::<_, ChainSpecT, TimerT>),
+            MethodInvocation::SetInclusionPolicy(policy) => {
+                hardhat::handle_set_inclusion_policy_request(data, policy)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetLogFilter(filter) => {
+                hardhat::handle_set_log_filter_request::<ChainSpecT, TimerT>(&filter)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetLoggingEnabled(is_enabled) => {
                 hardhat::handle_set_logging_enabled_request(data, is_enabled)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetLoggingOptions(options) => {
+                hardhat::handle_set_logging_options_request(data, options)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetMempoolConfig(config) => {
+                hardhat::handle_set_mempool_config(data, config)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetMinGasPrice(min_gas_price) => {
                 hardhat::handle_set_min_gas_price(data, min_gas_price.to())
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -446,6 +885,14 @@ impl<
                 hardhat::handle_set_next_block_base_fee_per_gas_request(data, base_fee_per_gas.to())
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetNextBlockHeaderOverrides(overrides) => {
+                hardhat::handle_set_next_block_header_overrides_request(data, overrides)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetNextBlockWithdrawals(withdrawals) => {
+                hardhat::handle_set_next_block_withdrawals_request(data, withdrawals)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetNonce(address, nonce) => {
                 hardhat::handle_set_nonce(data, address, nonce)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
@@ -454,14 +901,54 @@ impl<
                 hardhat::handle_set_prev_randao_request(data, prev_randao)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetPrevRandaoSeed(seed) => {
+                hardhat::handle_set_prev_randao_seed_request(data, &seed)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetStateRoot(state_root) => {
+                hardhat::handle_set_state_root(data, state_root)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::SetStorageAt(address, index, value) => {
                 hardhat::handle_set_storage_at(data, address, index, value)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::SetStorageLayout(address, layout) => {
+                hardhat::handle_set_storage_layout(data, address, layout)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SetStorageVariable(address, variable, value, keys) => {
+                hardhat::handle_set_storage_variable(data, address, variable, value, keys)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SignAuthorization(address, authorization) => {
+                hardhat::handle_sign_authorization(data, address, authorization)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::SimulateBundle(transactions, block_spec) => {
+                hardhat::handle_simulate_bundle_request(data, transactions, block_spec)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::StopImpersonatingAccount(address) => {
                 hardhat::handle_stop_impersonating_account_request(data, *address)
                     .and_then(to_json::<_, ChainSpecT, TimerT>)
             }
+            MethodInvocation::TokenBalances(address, block_spec) => {
+                hardhat::handle_token_balances(data, address, block_spec)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::TokenTransfers(filter) => {
+                hardhat::handle_token_transfers(data, filter)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::UpdateFork(block_number) => {
+                hardhat::handle_update_fork(data, block_number.map(|number| number.to()))
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
+            MethodInvocation::ValidateUserOperation(user_operation, entry_point) => {
+                hardhat::handle_validate_user_operation_request(data, user_operation, entry_point)
+                    .and_then(to_json::<_, ChainSpecT, TimerT>)
+            }
             MethodInvocation::DumpState(()) => {
                 hardhat::handle_dump_state(data).and_then(to_json::<_, ChainSpecT, TimerT>)
             }
@@ -469,6 +956,20 @@ impl<
                 .and_then(to_json::<_, ChainSpecT, TimerT>),
         };
 
+        // Any request that isn't read-only might have modified the mem pool or the
+        // configuration of the next block.
+        if !is_read_only {
+            data.invalidate_pending_block();
+        }
+
+        let duration = start.elapsed();
+        match &result {
+            Ok(_) => tracing::debug!(?duration, "Handled request"),
+            Err(error) => tracing::debug!(?duration, %error, "Request failed"),
+        }
+
+        data.record_request(request_method_name, duration, result.is_err());
+
         if let Some(method_name) = method_name {
             data.logger_mut()
                 .print_method_logs(method_name, result.as_ref().err())
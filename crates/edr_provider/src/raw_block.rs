@@ -0,0 +1,359 @@
+//! RLP-encoded blocks, as exported by other Ethereum clients (e.g. using `geth
+//! export`) and imported by `admin_importChain`.
+//!
+//! Following EIP-2718, the typed transactions of a block body are wrapped in an
+//! RLP string. For compatibility with EDR's own encoding, unwrapped typed
+//! transactions are accepted as well.
+
+use std::fmt::Debug;
+
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, EMPTY_STRING_CODE};
+use edr_block_header::{BlockHeader, Withdrawal};
+use edr_primitives::B256;
+
+/// A block consisting of its header and body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawBlock<SignedTransactionT> {
+    /// The block's header
+    pub header: BlockHeader,
+    /// The block's transactions
+    pub transactions: Vec<SignedTransactionT>,
+    /// The block's ommers' headers
+    pub ommers: Vec<BlockHeader>,
+    /// The block's withdrawals
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+impl<SignedTransactionT: Decodable> RawBlock<SignedTransactionT> {
+    /// Decodes a sequence of concatenated RLP-encoded blocks.
+    pub fn decode_sequence(mut buf: &[u8]) -> alloy_rlp::Result<Vec<Self>> {
+        let mut blocks = Vec::new();
+        while !buf.is_empty() {
+            blocks.push(Self::decode(&mut buf)?);
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<SignedTransactionT: Decodable> Decodable for RawBlock<SignedTransactionT> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = decode_list_payload(buf)?;
+
+        let header = BlockHeader::decode(&mut payload)?;
+
+        let mut transactions = Vec::new();
+        let mut transactions_payload = decode_list_payload(&mut payload)?;
+        while !transactions_payload.is_empty() {
+            transactions.push(decode_transaction(&mut transactions_payload)?);
+        }
+
+        let ommers = Vec::<BlockHeader>::decode(&mut payload)?;
+        let withdrawals = if payload.is_empty() {
+            None
+        } else {
+            Some(Vec::<Withdrawal>::decode(&mut payload)?)
+        };
+
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+
+        Ok(Self {
+            header,
+            transactions,
+            ommers,
+            withdrawals,
+        })
+    }
+}
+
+impl<SignedTransactionT: Encodable> Encodable for RawBlock<SignedTransactionT> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        let transactions = self
+            .transactions
+            .iter()
+            .map(encode_transaction)
+            .collect::<Vec<_>>();
+
+        let transactions_length = transactions.iter().map(Vec::len).sum::<usize>();
+
+        let mut payload = Vec::new();
+        self.header.encode(&mut payload);
+        Header {
+            list: true,
+            payload_length: transactions_length,
+        }
+        .encode(&mut payload);
+        for transaction in transactions {
+            payload.put_slice(&transaction);
+        }
+        self.ommers.encode(&mut payload);
+        if let Some(withdrawals) = &self.withdrawals {
+            withdrawals.encode(&mut payload);
+        }
+
+        Header {
+            list: true,
+            payload_length: payload.len(),
+        }
+        .encode(out);
+        out.put_slice(&payload);
+    }
+}
+
+/// Decodes the header of an RLP list, returning its payload and advancing the
+/// buffer past the list.
+fn decode_list_payload<'buf>(buf: &mut &'buf [u8]) -> alloy_rlp::Result<&'buf [u8]> {
+    let header = Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString);
+    }
+
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort);
+    }
+
+    let (payload, remainder) = buf.split_at(header.payload_length);
+    *buf = remainder;
+
+    Ok(payload)
+}
+
+/// Decodes a transaction of a block body, unwrapping the RLP string around
+/// typed transactions, if present.
+fn decode_transaction<SignedTransactionT: Decodable>(
+    buf: &mut &[u8],
+) -> alloy_rlp::Result<SignedTransactionT> {
+    let first = *buf.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+
+    // Legacy transactions are RLP lists and unwrapped typed transactions start
+    // with their type byte.
+    if first < EMPTY_STRING_CODE || first >= alloy_rlp::EMPTY_LIST_CODE {
+        return SignedTransactionT::decode(buf);
+    }
+
+    let mut payload = Header::decode_bytes(buf, false)?;
+    let transaction = SignedTransactionT::decode(&mut payload)?;
+    if !payload.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength);
+    }
+
+    Ok(transaction)
+}
+
+/// Encodes a transaction of a block body, wrapping typed transactions in an
+/// RLP string.
+fn encode_transaction<SignedTransactionT: Encodable>(transaction: &SignedTransactionT) -> Vec<u8> {
+    let encoded = alloy_rlp::encode(transaction);
+
+    let is_typed = encoded
+        .first()
+        .is_some_and(|first| *first < EMPTY_STRING_CODE);
+
+    if is_typed {
+        let mut wrapped =
+            Vec::with_capacity(encoded.len() + alloy_rlp::length_of_length(encoded.len()));
+        Header {
+            list: false,
+            payload_length: encoded.len(),
+        }
+        .encode(&mut wrapped);
+        wrapped.extend(encoded);
+
+        wrapped
+    } else {
+        encoded
+    }
+}
+
+/// Ensures that a header field of an imported block matches the field of the
+/// executed block.
+pub(crate) fn ensure_header_field_matches<T: Debug + PartialEq>(
+    block_number: u64,
+    field: &'static str,
+    expected: &T,
+    actual: &T,
+) -> Result<(), RawBlockImportError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(RawBlockImportError::HeaderMismatch {
+            block_number,
+            field,
+            expected: format!("{expected:?}"),
+            actual: format!("{actual:?}"),
+        })
+    }
+}
+
+/// An error that occurred while importing RLP-encoded blocks.
+#[derive(Debug, thiserror::Error)]
+pub enum RawBlockImportError {
+    /// The provided data could not be decoded.
+    #[error("Failed to decode RLP-encoded blocks: {0}")]
+    Decode(alloy_rlp::Error),
+    /// A header field of the imported block doesn't match the field of the
+    /// executed block.
+    #[error(
+        "Block {block_number} has {field} {expected}, but executing the block resulted in {actual}"
+    )]
+    HeaderMismatch {
+        /// The number of the block.
+        block_number: u64,
+        /// The name of the mismatching field.
+        field: &'static str,
+        /// The value of the field in the imported block.
+        expected: String,
+        /// The value of the field in the executed block.
+        actual: String,
+    },
+    /// The header of the block is invalid.
+    #[error("Block {block_number} has an invalid header: {reason}")]
+    InvalidHeader {
+        /// The number of the block.
+        block_number: u64,
+        /// The reason why the header is invalid.
+        reason: String,
+    },
+    /// The block's number doesn't follow the latest block.
+    #[error("Expected block number {expected}, but the imported block has number {actual}")]
+    UnexpectedBlockNumber {
+        /// The number of the next block.
+        expected: u64,
+        /// The number of the imported block.
+        actual: u64,
+    },
+    /// The block's parent is not the latest block.
+    #[error(
+        "Block {block_number} has parent hash {parent_hash}, but the latest block has hash {latest_block_hash}"
+    )]
+    UnknownParent {
+        /// The number of the block.
+        block_number: u64,
+        /// The parent hash of the block.
+        parent_hash: B256,
+        /// The hash of the latest block.
+        latest_block_hash: B256,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_chain_l1::{request, L1SignedTransaction};
+    use edr_primitives::{Address, Bytes, U256};
+    use edr_test_utils::secret_key::secret_key_from_str;
+    use edr_transaction::TxKind;
+
+    use super::*;
+
+    fn signed_transactions() -> anyhow::Result<Vec<L1SignedTransaction>> {
+        let secret_key = secret_key_from_str(edr_defaults::SECRET_KEYS[0])?;
+
+        let legacy = request::Eip155 {
+            nonce: 0,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            kind: TxKind::Call(Address::random()),
+            value: U256::from(1),
+            input: Bytes::new(),
+            chain_id: 31337,
+        }
+        .sign(&secret_key)?;
+
+        let eip1559 = request::Eip1559 {
+            chain_id: 31337,
+            nonce: 1,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 2_000_000_000,
+            gas_limit: 21_000,
+            kind: TxKind::Call(Address::random()),
+            value: U256::from(1),
+            input: Bytes::new(),
+            access_list: Vec::new(),
+        }
+        .sign(&secret_key)?;
+
+        Ok(vec![legacy.into(), eip1559.into()])
+    }
+
+    #[test]
+    fn encode_wraps_typed_transactions() -> anyhow::Result<()> {
+        let transactions = signed_transactions()?;
+
+        let legacy = encode_transaction(&transactions[0]);
+        assert_eq!(legacy, alloy_rlp::encode(&transactions[0]));
+
+        let typed = encode_transaction(&transactions[1]);
+        let payload = Header::decode_bytes(&mut typed.as_slice(), false)?;
+        assert_eq!(payload, alloy_rlp::encode(&transactions[1]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn encode_and_decode_sequence() -> anyhow::Result<()> {
+        let blocks = vec![
+            RawBlock {
+                header: BlockHeader {
+                    number: 1,
+                    ..BlockHeader::default()
+                },
+                transactions: signed_transactions()?,
+                ommers: Vec::new(),
+                withdrawals: Some(Vec::new()),
+            },
+            RawBlock {
+                header: BlockHeader {
+                    number: 2,
+                    ..BlockHeader::default()
+                },
+                transactions: Vec::new(),
+                ommers: vec![BlockHeader::default()],
+                withdrawals: None,
+            },
+        ];
+
+        let encoded = blocks
+            .iter()
+            .flat_map(alloy_rlp::encode)
+            .collect::<Vec<_>>();
+
+        let decoded = RawBlock::<L1SignedTransaction>::decode_sequence(&encoded)?;
+        assert_eq!(decoded, blocks);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_unwrapped_typed_transactions() -> anyhow::Result<()> {
+        let block = RawBlock {
+            header: BlockHeader {
+                number: 1,
+                ..BlockHeader::default()
+            },
+            transactions: signed_transactions()?,
+            ommers: Vec::new(),
+            withdrawals: None,
+        };
+
+        // EDR's own encoding of block bodies doesn't wrap typed transactions
+        let mut payload = Vec::new();
+        block.header.encode(&mut payload);
+        block.transactions.encode(&mut payload);
+        block.ommers.encode(&mut payload);
+
+        let mut encoded = Vec::new();
+        Header {
+            list: true,
+            payload_length: payload.len(),
+        }
+        .encode(&mut encoded);
+        encoded.extend(payload);
+
+        let decoded = RawBlock::<L1SignedTransaction>::decode(&mut encoded.as_slice())?;
+        assert_eq!(decoded, block);
+
+        Ok(())
+    }
+}
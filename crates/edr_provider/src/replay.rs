@@ -0,0 +1,453 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use edr_block_header::{BlockHeader, Withdrawal};
+use edr_blockchain_api::{r#dyn::DynBlockchainError, BlockHashByNumber};
+use edr_chain_l1::block::{store_parent_beacon_block_root, store_parent_block_hash};
+use edr_chain_spec::{EvmSpecId, ExecutableTransaction as _, HaltReasonTrait};
+use edr_chain_spec_block::BlockChainSpec;
+use edr_chain_spec_evm::{
+    result::{ExecutionResult, ExecutionResultAndState, Output},
+    BlockEnvTrait as _, CfgEnv,
+};
+use edr_evm::{dry_run, dry_run_with_inspector, run};
+use edr_primitives::{Address, Bytes, HashMap, B256, KECCAK_EMPTY, U256};
+use edr_receipt::{log::logs_to_bloom, ExecutionReceipt as _, ReceiptTrait as _, RootOrStatus};
+use edr_runtime::inspector::DualInspector;
+use edr_state_api::{
+    account::{Account, AccountInfo},
+    AccountModifierFn, DynState, State as _, StateCommit as _, StateDebug as _, StateDiff,
+    StateError,
+};
+use edr_tracing::Trace;
+
+use crate::{
+    debug_trace::{
+        execution_result_to_debug_result, DebugTraceConfig, DebugTraceError,
+        DebugTraceErrorForChainSpec, DebugTraceResultWithTraces, TracerEip3155,
+    },
+    observability::{EvmObserver, EvmObserverConfig},
+    requests::hardhat::rpc_types::{
+        AccountDiff, AccountMismatch, Mismatch, ReplayLog, ReplayReceipt, ReplayedBlock,
+        ReplayedTransaction, TransactionMismatch, ValueChange,
+    },
+};
+
+/// Result of a `hardhat_replayTransaction` call with traces.
+pub struct ReplayedTransactionWithTraces<HaltReasonT: HaltReasonTrait> {
+    /// The outcome of the replayed transaction.
+    pub result: ReplayedTransaction,
+    /// The raw traces of the replayed transaction.
+    pub traces: Vec<Trace<HaltReasonT>>,
+}
+
+/// Replays the transactions of a block up to and including the transaction
+/// with the provided hash, returning the receipt, trace, and state diff of
+/// the latter.
+#[allow(clippy::too_many_arguments)]
+pub fn replay_transaction<'header, ChainSpecT: BlockChainSpec>(
+    blockchain: &dyn BlockHashByNumber<Error = DynBlockchainError>,
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    mut state: Box<dyn DynState>,
+    evm_config: CfgEnv<ChainSpecT::Hardfork>,
+    block: ChainSpecT::BlockEnv<'header, BlockHeader>,
+    transactions: Vec<ChainSpecT::SignedTransaction>,
+    transaction_hash: &B256,
+    observer_config: EvmObserverConfig,
+) -> Result<
+    ReplayedTransactionWithTraces<ChainSpecT::HaltReason>,
+    DebugTraceErrorForChainSpec<ChainSpecT>,
+> {
+    let block_number = block.number();
+    for transaction in transactions {
+        if transaction.transaction_hash() == transaction_hash {
+            let mut eip3155_tracer = TracerEip3155::new(DebugTraceConfig::default());
+            let mut evm_observer = EvmObserver::new(observer_config);
+
+            let ExecutionResultAndState {
+                result,
+                state: changes,
+            } = dry_run_with_inspector::<ChainSpecT, _, _, _, _>(
+                blockchain,
+                state.as_ref(),
+                evm_config,
+                transaction,
+                &block,
+                &HashMap::default(),
+                &mut DualInspector::new(&mut eip3155_tracer, &mut evm_observer),
+            )?;
+
+            let receipt = replay_receipt(&result);
+            let state_diff = state_diff(state.as_ref(), &changes)?;
+
+            let EvmObserver {
+                code_coverage,
+                console_logger,
+                mocker: _mocker,
+                trace_collector,
+            } = evm_observer;
+
+            if let Some(code_coverage) = code_coverage {
+                code_coverage
+                    .report()
+                    .map_err(DebugTraceError::OnCollectedCoverageCallback)?;
+            }
+
+            let DebugTraceResultWithTraces {
+                result: trace,
+                traces,
+            } = execution_result_to_debug_result(
+                result,
+                trace_collector,
+                eip3155_tracer,
+                console_logger.into_encoded_messages(),
+            );
+
+            return Ok(ReplayedTransactionWithTraces {
+                result: ReplayedTransaction {
+                    receipt,
+                    trace,
+                    state_diff,
+                },
+                traces,
+            });
+        } else {
+            run::<ChainSpecT, _, _, _>(
+                blockchain,
+                state.as_mut(),
+                evm_config.clone(),
+                transaction,
+                &block,
+                &HashMap::default(),
+            )?;
+        }
+    }
+
+    Err(DebugTraceError::InvalidTransactionHash {
+        transaction_hash: *transaction_hash,
+        block_number,
+    })
+}
+
+/// Replays the transactions of a block on top of its parent's state and
+/// compares the results with the block's receipts and post-state.
+///
+/// The EIP-4788 and EIP-2935 system calls are applied before the
+/// transactions, whereas withdrawals and the block reward are applied after
+/// the transactions. Ommer rewards are not replayed.
+///
+/// The state root is only compared if `compare_state_root` is set, as the
+/// state root of forked state cannot be computed locally.
+#[allow(clippy::too_many_arguments)]
+pub fn replay_block<'header, ChainSpecT: BlockChainSpec>(
+    blockchain: &dyn BlockHashByNumber<Error = DynBlockchainError>,
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    mut state: Box<dyn DynState>,
+    post_state: &dyn DynState,
+    evm_config: CfgEnv<ChainSpecT::Hardfork>,
+    block: ChainSpecT::BlockEnv<'header, BlockHeader>,
+    header: &BlockHeader,
+    transactions: Vec<ChainSpecT::SignedTransaction>,
+    receipts: &[Arc<ChainSpecT::Receipt>],
+    withdrawals: Option<&[Withdrawal]>,
+    reward: u128,
+    compare_state_root: bool,
+) -> Result<ReplayedBlock, DebugTraceErrorForChainSpec<ChainSpecT>> {
+    let mut modified_slots: BTreeMap<Address, BTreeSet<U256>> = BTreeMap::new();
+    let mut transaction_mismatches = Vec::new();
+    let mut gas_used = 0;
+
+    let evm_spec_id: EvmSpecId = evm_config.spec.clone().into();
+    let mut system_call_diff = StateDiff::default();
+    if evm_spec_id >= EvmSpecId::CANCUN
+        && let Some(parent_beacon_block_root) = header.parent_beacon_block_root
+    {
+        store_parent_beacon_block_root(
+            state.as_mut(),
+            &mut system_call_diff,
+            header.timestamp,
+            parent_beacon_block_root,
+        )?;
+    }
+
+    if evm_spec_id >= EvmSpecId::PRAGUE {
+        store_parent_block_hash(
+            state.as_mut(),
+            &mut system_call_diff,
+            header.number,
+            header.parent_hash,
+        )?;
+    }
+
+    for (address, account) in system_call_diff.as_inner() {
+        modified_slots
+            .entry(*address)
+            .or_default()
+            .extend(account.storage.keys().copied());
+    }
+
+    let receipt_count = Mismatch::new(receipts.len(), transactions.len());
+
+    for (index, transaction) in transactions.into_iter().enumerate() {
+        let transaction_hash = *transaction.transaction_hash();
+
+        let ExecutionResultAndState {
+            result,
+            state: changes,
+        } = dry_run::<ChainSpecT, _, _, _>(
+            blockchain,
+            state.as_ref(),
+            evm_config.clone(),
+            transaction,
+            &block,
+            &HashMap::default(),
+        )?;
+
+        for (address, account) in &changes {
+            if account.is_touched() {
+                modified_slots.entry(*address).or_default().extend(
+                    account
+                        .storage
+                        .iter()
+                        .filter(|(_index, slot)| slot.original_value != slot.present_value)
+                        .map(|(index, _slot)| *index),
+                );
+            }
+        }
+
+        state.commit(changes);
+        gas_used += result.gas_used();
+
+        // Missing receipts are reported by the receipt count mismatch
+        let Some(receipt) = receipts.get(index) else {
+            continue;
+        };
+
+        let status = match receipt.root_or_status() {
+            RootOrStatus::Root(_) => None,
+            RootOrStatus::Status(status) => Mismatch::new(status, result.is_success()),
+        };
+
+        let mismatch = TransactionMismatch {
+            transaction_hash,
+            status,
+            gas_used: Mismatch::new(receipt.gas_used(), result.gas_used()),
+            logs_bloom: Mismatch::new(*receipt.logs_bloom(), logs_to_bloom(result.logs())),
+        };
+
+        if !mismatch.is_empty() {
+            transaction_mismatches.push(mismatch);
+        }
+    }
+
+    // EIP-4895: withdrawals are applied after all transactions
+    for withdrawal in withdrawals.into_iter().flatten() {
+        if withdrawal.amount > 0 {
+            let amount = withdrawal.amount_wei();
+            state.modify_account(
+                withdrawal.address,
+                AccountModifierFn::new(Box::new(move |balance, _nonce, _code| {
+                    *balance += amount;
+                })),
+            )?;
+
+            modified_slots.entry(withdrawal.address).or_default();
+        }
+    }
+
+    if reward > 0 {
+        state.modify_account(
+            header.beneficiary,
+            AccountModifierFn::new(Box::new(move |balance, _nonce, _code| {
+                *balance += U256::from(reward);
+            })),
+        )?;
+
+        modified_slots.entry(header.beneficiary).or_default();
+    }
+
+    let mut account_mismatches = BTreeMap::new();
+    for (address, slots) in modified_slots {
+        let expected = post_state.basic(address)?.unwrap_or_default();
+        let actual = state.basic(address)?.unwrap_or_default();
+
+        let code = if expected.code_hash == actual.code_hash {
+            None
+        } else {
+            Mismatch::new(
+                account_code(post_state, &expected)?,
+                account_code(state.as_ref(), &actual)?,
+            )
+        };
+
+        let storage = slots
+            .into_iter()
+            .map(|index| {
+                let mismatch = Mismatch::new(
+                    post_state.storage(address, index)?,
+                    state.storage(address, index)?,
+                );
+
+                Ok(mismatch.map(|mismatch| (index, mismatch)))
+            })
+            .filter_map(Result::transpose)
+            .collect::<Result<_, StateError>>()?;
+
+        let mismatch = AccountMismatch {
+            balance: Mismatch::new(expected.balance, actual.balance),
+            nonce: Mismatch::new(expected.nonce, actual.nonce),
+            code,
+            storage,
+        };
+
+        if !mismatch.is_empty() {
+            account_mismatches.insert(address, mismatch);
+        }
+    }
+
+    let gas_used = Mismatch::new(header.gas_used, gas_used);
+    let state_root = if compare_state_root {
+        Mismatch::new(header.state_root, state.state_root()?)
+    } else {
+        None
+    };
+
+    let matches = gas_used.is_none()
+        && state_root.is_none()
+        && receipt_count.is_none()
+        && transaction_mismatches.is_empty()
+        && account_mismatches.is_empty();
+
+    Ok(ReplayedBlock {
+        block_number: header.number,
+        matches,
+        gas_used,
+        state_root,
+        receipt_count,
+        transactions: transaction_mismatches,
+        accounts: account_mismatches,
+    })
+}
+
+/// Computes the changes that each transaction of a block made to each account
+/// it modified, from the state changes of the transactions' execution, on top
+/// of the block's parent state.
+pub fn transaction_state_diffs(
+    // Take ownership of the state so that we can apply throw-away modifications on it
+    mut state: Box<dyn DynState>,
+    transaction_state_changes: impl IntoIterator<Item = HashMap<Address, Account>>,
+) -> Result<Vec<BTreeMap<Address, AccountDiff>>, StateError> {
+    transaction_state_changes
+        .into_iter()
+        .map(|changes| {
+            let diff = state_diff(state.as_ref(), &changes)?;
+            state.commit(changes);
+
+            Ok(diff)
+        })
+        .collect()
+}
+
+fn account_code(state: &dyn DynState, account_info: &AccountInfo) -> Result<Bytes, StateError> {
+    if account_info.code_hash == KECCAK_EMPTY {
+        Ok(Bytes::new())
+    } else {
+        state
+            .code_by_hash(account_info.code_hash)
+            .map(|code| code.original_bytes())
+    }
+}
+
+fn replay_receipt<HaltReasonT: HaltReasonTrait>(
+    result: &ExecutionResult<HaltReasonT>,
+) -> ReplayReceipt {
+    let contract_address = match result {
+        ExecutionResult::Success {
+            output: Output::Create(_, address),
+            ..
+        } => *address,
+        _ => None,
+    };
+
+    let logs = result
+        .logs()
+        .iter()
+        .map(|log| ReplayLog {
+            address: log.address,
+            topics: log.topics().to_vec(),
+            data: log.data.data.clone(),
+        })
+        .collect();
+
+    ReplayReceipt {
+        status: result.is_success(),
+        gas_used: result.gas_used(),
+        logs,
+        contract_address,
+    }
+}
+
+/// Computes the changes that were made to each touched account, compared to
+/// the provided pre-state. Accounts without changes are omitted.
+fn state_diff(
+    state: &dyn DynState,
+    changes: &HashMap<Address, Account>,
+) -> Result<BTreeMap<Address, AccountDiff>, StateError> {
+    let mut state_diff = BTreeMap::new();
+
+    for (address, account) in changes {
+        if !account.is_touched() {
+            continue;
+        }
+
+        let pre_info = state.basic(*address)?.unwrap_or_default();
+        let is_destroyed = account.is_selfdestructed();
+        let post_info = if is_destroyed {
+            Default::default()
+        } else {
+            account.info.clone()
+        };
+
+        let code = if pre_info.code_hash == post_info.code_hash {
+            None
+        } else {
+            let pre_code = account_code(state, &pre_info)?;
+
+            let post_code = post_info
+                .code
+                .as_ref()
+                .map_or_else(Bytes::new, |code| code.original_bytes());
+
+            ValueChange::new(pre_code, post_code)
+        };
+
+        let storage = account
+            .storage
+            .iter()
+            .filter_map(|(index, slot)| {
+                let present_value = if is_destroyed {
+                    Default::default()
+                } else {
+                    slot.present_value
+                };
+
+                ValueChange::new(slot.original_value, present_value).map(|change| (*index, change))
+            })
+            .collect();
+
+        let diff = AccountDiff {
+            balance: ValueChange::new(pre_info.balance, post_info.balance),
+            nonce: ValueChange::new(pre_info.nonce, post_info.nonce),
+            code,
+            storage,
+        };
+
+        if !diff.is_empty() {
+            state_diff.insert(*address, diff);
+        }
+    }
+
+    Ok(state_diff)
+}
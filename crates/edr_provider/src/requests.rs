@@ -1,11 +1,23 @@
+/// Admin RPC request types
+pub(crate) mod admin;
 pub(crate) mod debug;
+/// Engine API request types
+pub(crate) mod engine;
 /// Ethereum RPC request types
 pub(crate) mod eth;
+/// Block explorer RPC request types
+pub(crate) mod explorer;
 /// Hardhat RPC request types
 pub(crate) mod hardhat;
 mod methods;
+/// Otterscan RPC request types
+pub(crate) mod otterscan;
 mod resolve;
 mod serde;
+/// Trace RPC request types
+pub(crate) mod trace;
+/// Transaction pool RPC request types
+pub(crate) mod txpool;
 /// Types and functions for validating JSON-RPC requests.
 pub mod validation;
 
@@ -17,6 +29,7 @@ use ::serde::{
 };
 use derive_where::derive_where;
 use edr_chain_spec_rpc::RpcChainSpec;
+use edr_rpc_eth::jsonrpc;
 
 pub use crate::requests::{
     methods::{IntervalConfig, MethodInvocation},
@@ -31,7 +44,7 @@ pub enum ProviderRequest<ChainSpecT: RpcChainSpec> {
     /// A single JSON-RPC request
     Single(Box<MethodInvocation<ChainSpecT>>),
     /// A batch of requests
-    Batch(Vec<MethodInvocation<ChainSpecT>>),
+    Batch(Vec<BatchRequest<ChainSpecT>>),
 }
 
 impl<ChainSpecT: RpcChainSpec> ProviderRequest<ChainSpecT> {
@@ -41,6 +54,51 @@ impl<ChainSpecT: RpcChainSpec> ProviderRequest<ChainSpecT> {
     }
 }
 
+/// JSON-RPC request that is part of a batch.
+#[derive(Serialize)]
+#[derive_where(Clone, Debug; ChainSpecT::RpcCallRequest, ChainSpecT::RpcTransactionRequest)]
+#[serde(bound = "")]
+pub struct BatchRequest<ChainSpecT: RpcChainSpec> {
+    /// The id that the response is correlated with. Notifications, which
+    /// don't have an id, don't receive a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<jsonrpc::Id>,
+    /// The method to invoke, with its parameters
+    #[serde(flatten)]
+    pub method: MethodInvocation<ChainSpecT>,
+}
+
+impl<ChainSpecT: RpcChainSpec> BatchRequest<ChainSpecT> {
+    /// Constructs a new instance with the provided id.
+    pub fn new(id: jsonrpc::Id, method: MethodInvocation<ChainSpecT>) -> Self {
+        Self {
+            id: Some(id),
+            method,
+        }
+    }
+}
+
+impl<'de, ChainSpecT: RpcChainSpec> Deserialize<'de> for BatchRequest<ChainSpecT> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut request = serde_json::Value::deserialize(deserializer)?;
+
+        let id = request
+            .as_object_mut()
+            .and_then(|request| request.remove("id"))
+            .map(Option::<jsonrpc::Id>::deserialize)
+            .transpose()
+            .map_err(de::Error::custom)?
+            .flatten();
+
+        let method = MethodInvocation::deserialize(request).map_err(de::Error::custom)?;
+
+        Ok(Self { id, method })
+    }
+}
+
 // Custom deserializer for `ProviderRequest` instead of using
 // `#[serde(untagged)]` as the latter hides custom error messages which are
 // important to propagate to users.
@@ -65,7 +123,7 @@ impl<'de, ChainSpecT: RpcChainSpec> Deserialize<'de> for ProviderRequest<ChainSp
             where
                 A: SeqAccess<'de>,
             {
-                // Forward to deserializer of `Vec<MethodInvocation>`
+                // Forward to deserializer of `Vec<BatchRequest>`
                 Ok(ProviderRequest::Batch(Deserialize::deserialize(
                     de::value::SeqAccessDeserializer::new(seq),
                 )?))
@@ -123,7 +181,20 @@ mod tests {
             }
         ]"#;
         let request: ProviderRequest<L1ChainSpec> = serde_json::from_str(json)?;
-        assert!(matches!(request, ProviderRequest::Batch(_)));
+
+        let ProviderRequest::Batch(requests) = request else {
+            anyhow::bail!("expected a batch request");
+        };
+
+        let ids = requests
+            .iter()
+            .map(|request| request.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![Some(jsonrpc::Id::Num(1)), Some(jsonrpc::Id::Num(2))]
+        );
+
         Ok(())
     }
 
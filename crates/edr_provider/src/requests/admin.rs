@@ -0,0 +1,27 @@
+use edr_chain_spec::TransactionValidation;
+use edr_primitives::Bytes;
+
+use crate::{
+    data::ProviderData,
+    raw_block::{RawBlock, RawBlockImportError},
+    spec::SyncProviderSpec,
+    time::TimeSinceEpoch,
+    ProviderErrorForChainSpec,
+};
+
+pub fn handle_import_chain<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    chain: Bytes,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let blocks = RawBlock::decode_sequence(&chain).map_err(RawBlockImportError::Decode)?;
+
+    data.import_raw_blocks(blocks)?;
+
+    Ok(true)
+}
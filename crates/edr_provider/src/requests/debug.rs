@@ -6,11 +6,11 @@ use serde::{Deserialize, Deserializer};
 
 use crate::{
     data::ProviderData,
-    debug_trace::{DebugTraceResult, DebugTraceResultWithTraces},
+    debug_trace::{DebugTraceResult, DebugTraceResultWithTraces, StructLogStream},
     requests::eth::{resolve_block_spec_for_call_request, resolve_call_request},
     spec::SyncProviderSpec,
     time::TimeSinceEpoch,
-    ProviderError, ProviderResultWithTraces,
+    ProviderError, ProviderErrorForChainSpec, ProviderResultWithTraces,
 };
 
 pub fn handle_debug_trace_transaction<
@@ -23,11 +23,13 @@ pub fn handle_debug_trace_transaction<
     data: &mut ProviderData<ChainSpecT, TimerT>,
     transaction_hash: B256,
     config: Option<DebugTraceConfig>,
+    struct_log_stream: Option<StructLogStream>,
 ) -> ProviderResultWithTraces<DebugTraceResult, ChainSpecT> {
     let DebugTraceResultWithTraces { result, traces } = data
         .debug_trace_transaction(
             &transaction_hash,
             config.map(Into::into).unwrap_or_default(),
+            struct_log_stream,
         )
         .map_err(|error| match error {
             ProviderError::InvalidTransactionHash(tx_hash) => ProviderError::InvalidInput(format!(
@@ -39,6 +41,19 @@ pub fn handle_debug_trace_transaction<
     Ok((result, traces))
 }
 
+pub fn handle_set_head<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    block_number: u64,
+) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_head(block_number)
+}
+
 pub fn handle_debug_trace_call<ChainSpecT, TimerT>(
     data: &mut ProviderData<ChainSpecT, TimerT>,
     call_request: ChainSpecT::RpcCallRequest,
@@ -83,10 +98,23 @@ pub struct DebugTraceConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub disable_memory: Option<bool>,
+    /// Enable memory trace, as supported by Geth. Takes precedence over
+    /// `disableMemory`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub enable_memory: Option<bool>,
     /// Disable stack trace.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub disable_stack: Option<bool>,
+    /// Include the ABI-encoded `console.log` inputs in the result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub enable_console_logs: Option<bool>,
+    /// The maximum number of struct logs to capture. Zero means unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub limit: Option<u64>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -119,14 +147,20 @@ impl From<DebugTraceConfig> for crate::debug_trace::DebugTraceConfig {
         let DebugTraceConfig {
             disable_storage,
             disable_memory,
+            enable_memory,
             disable_stack,
+            enable_console_logs,
+            limit,
             // Tracer argument is not supported by Hardhat
             tracer: _,
         } = value;
         Self {
             disable_storage: disable_storage.unwrap_or_default(),
-            disable_memory: disable_memory.unwrap_or_default(),
+            disable_memory: enable_memory
+                .map_or_else(|| disable_memory.unwrap_or_default(), |enable| !enable),
             disable_stack: disable_stack.unwrap_or_default(),
+            enable_console_logs: enable_console_logs.unwrap_or_default(),
+            limit: limit.filter(|limit| *limit > 0),
         }
     }
 }
@@ -0,0 +1,341 @@
+//! A minimal implementation of the [Engine API], which allows a consensus
+//! client (or a test harness) to drive the local chain like an execution
+//! client.
+//!
+//! Only Cancun (V3) payloads are supported and they can only extend the latest
+//! block; payloads with any other parent are reported as `SYNCING`. As the
+//! provider doesn't keep side chains, every known block is part
+//! of the canonical chain, so a forkchoice update that references an ancestor
+//! of the latest block leaves the chain unchanged.
+//!
+//! [Engine API]: https://github.com/ethereum/execution-apis/tree/main/src/engine
+
+use alloy_rlp::Decodable as _;
+use edr_block_api::Block as _;
+use edr_block_header::{BlobGas, BlockHeader, Withdrawal};
+use edr_chain_spec::{EvmSpecId, ExecutableTransaction as _, TransactionValidation};
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bloom, Bytes, B256, B64, KECCAK_RLP_EMPTY_ARRAY, U256};
+use edr_trie::ordered_trie_root;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::ProviderData, error::ProviderErrorForChainSpec, raw_block::RawBlock,
+    spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderError,
+};
+
+/// An execution payload, as provided to `engine_newPayloadV3`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadV3 {
+    pub parent_hash: B256,
+    pub fee_recipient: Address,
+    pub state_root: B256,
+    pub receipts_root: B256,
+    pub logs_bloom: Bloom,
+    pub prev_randao: B256,
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u128,
+    pub block_hash: B256,
+    /// EIP-2718 encoded transactions
+    pub transactions: Vec<Bytes>,
+    pub withdrawals: Vec<Withdrawal>,
+    #[serde(with = "alloy_serde::quantity")]
+    pub blob_gas_used: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub excess_blob_gas: u64,
+}
+
+/// The fork choice of a consensus client, as provided to
+/// `engine_forkchoiceUpdatedV3`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceState {
+    pub head_block_hash: B256,
+    /// The hash of the most recent safe block, or zero if unknown.
+    pub safe_block_hash: B256,
+    /// The hash of the most recent finalized block, or zero if unknown.
+    pub finalized_block_hash: B256,
+}
+
+/// Attributes for building a payload on top of the head block, as provided to
+/// `engine_forkchoiceUpdatedV3`.
+///
+/// Building payloads is not supported; blocks are mined using `evm_mine` or
+/// `hardhat_mine` instead.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributesV3 {
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    pub prev_randao: B256,
+    pub suggested_fee_recipient: Address,
+    pub withdrawals: Vec<Withdrawal>,
+    pub parent_beacon_block_root: B256,
+}
+
+/// The validity of a payload or forkchoice update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayloadValidity {
+    /// The payload was executed and added to the local chain.
+    Valid,
+    /// The payload could not be executed on top of its parent.
+    Invalid,
+    /// The parent of the payload isn't the latest block, or the head block is
+    /// unknown.
+    Syncing,
+}
+
+/// Result of `engine_newPayloadV3`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadStatus {
+    pub status: PayloadValidity,
+    /// The hash of the most recent valid block in the branch of the payload.
+    pub latest_valid_hash: Option<B256>,
+    pub validation_error: Option<String>,
+}
+
+impl PayloadStatus {
+    fn valid(latest_valid_hash: B256) -> Self {
+        Self {
+            status: PayloadValidity::Valid,
+            latest_valid_hash: Some(latest_valid_hash),
+            validation_error: None,
+        }
+    }
+
+    fn invalid(latest_valid_hash: Option<B256>, validation_error: String) -> Self {
+        Self {
+            status: PayloadValidity::Invalid,
+            latest_valid_hash,
+            validation_error: Some(validation_error),
+        }
+    }
+
+    fn syncing() -> Self {
+        Self {
+            status: PayloadValidity::Syncing,
+            latest_valid_hash: None,
+            validation_error: None,
+        }
+    }
+}
+
+/// Result of `engine_forkchoiceUpdatedV3`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceUpdatedResult {
+    pub payload_status: PayloadStatus,
+    /// Always `None`, as building payloads is not supported.
+    pub payload_id: Option<B64>,
+}
+
+pub fn handle_new_payload_v3<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    payload: ExecutionPayloadV3,
+    expected_blob_versioned_hashes: Vec<B256>,
+    parent_beacon_block_root: B256,
+) -> Result<PayloadStatus, ProviderErrorForChainSpec<ChainSpecT>> {
+    let evm_spec_id = data.evm_spec_id();
+    if evm_spec_id != EvmSpecId::CANCUN {
+        return Err(ProviderError::UnsupportedFork {
+            method: "engine_newPayloadV3".to_string(),
+            actual: evm_spec_id,
+            supported: EvmSpecId::CANCUN,
+        });
+    }
+
+    if data.block_by_hash(&payload.block_hash)?.is_some() {
+        return Ok(PayloadStatus::valid(payload.block_hash));
+    }
+
+    // Payloads can only extend the latest block, as side chains aren't kept. A
+    // payload with any other parent can't be executed until the chain catches
+    // up to its parent.
+    let latest_block_hash = *data.last_block()?.block_hash();
+    if payload.parent_hash != latest_block_hash {
+        return Ok(PayloadStatus::syncing());
+    }
+
+    let parent_hash = Some(payload.parent_hash);
+
+    let transactions =
+        match decode_transactions::<ChainSpecT::SignedTransaction>(&payload.transactions) {
+            Ok(transactions) => transactions,
+            Err(error) => {
+                return Ok(PayloadStatus::invalid(
+                    parent_hash,
+                    format!("Failed to decode transactions: {error}"),
+                ));
+            }
+        };
+
+    let blob_versioned_hashes = transactions
+        .iter()
+        .flat_map(|transaction| transaction.blob_hashes().iter().copied())
+        .collect::<Vec<_>>();
+
+    if blob_versioned_hashes != expected_blob_versioned_hashes {
+        return Ok(PayloadStatus::invalid(
+            parent_hash,
+            "Blob versioned hashes do not match the transactions of the payload".to_string(),
+        ));
+    }
+
+    let header = payload_header(&payload, parent_beacon_block_root);
+    if header.hash() != payload.block_hash {
+        return Ok(PayloadStatus::invalid(
+            parent_hash,
+            format!(
+                "Block hash {} does not match the hash of the payload's header {}",
+                payload.block_hash,
+                header.hash()
+            ),
+        ));
+    }
+
+    let block = RawBlock {
+        header,
+        transactions,
+        ommers: Vec::new(),
+        withdrawals: Some(payload.withdrawals),
+    };
+
+    match data.import_raw_blocks(vec![block]) {
+        Ok(()) => Ok(PayloadStatus::valid(payload.block_hash)),
+        Err(ProviderError::RawBlockImport(error)) => {
+            Ok(PayloadStatus::invalid(parent_hash, error.to_string()))
+        }
+        Err(error) => Err(error),
+    }
+}
+
+pub fn handle_forkchoice_updated_v3<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    forkchoice_state: ForkchoiceState,
+    payload_attributes: Option<PayloadAttributesV3>,
+) -> Result<ForkchoiceUpdatedResult, ProviderErrorForChainSpec<ChainSpecT>> {
+    let Some(head_block) = data.block_by_hash(&forkchoice_state.head_block_hash)? else {
+        return Ok(ForkchoiceUpdatedResult {
+            payload_status: PayloadStatus::syncing(),
+            payload_id: None,
+        });
+    };
+
+    let head_block_number = head_block.block_header().number;
+    for (name, block_hash) in [
+        ("safe", forkchoice_state.safe_block_hash),
+        ("finalized", forkchoice_state.finalized_block_hash),
+    ] {
+        if block_hash == B256::ZERO {
+            continue;
+        }
+
+        // A block is an ancestor of the head block if the canonical block with
+        // the same number has the same hash.
+        let block_number = data
+            .block_by_hash(&block_hash)?
+            .map(|block| block.block_header().number);
+
+        let is_ancestor = if let Some(block_number) = block_number
+            && block_number <= head_block_number
+        {
+            data.block_by_block_spec(&BlockSpec::Number(block_number))?
+                .is_some_and(|block| *block.block_hash() == block_hash)
+        } else {
+            false
+        };
+
+        if !is_ancestor {
+            return Err(ProviderError::InvalidForkchoiceState(format!(
+                "{name} block {block_hash} is not an ancestor of the head block"
+            )));
+        }
+    }
+
+    if payload_attributes.is_some() {
+        return Err(ProviderError::InvalidPayloadAttributes(
+            "Building payloads is not supported. Mine blocks using `evm_mine` or `hardhat_mine` instead.".to_string(),
+        ));
+    }
+
+    // The Engine API allows skipping forkchoice updates to ancestors of the
+    // latest block, which avoids discarding blocks, as side chains aren't kept.
+
+    Ok(ForkchoiceUpdatedResult {
+        payload_status: PayloadStatus::valid(forkchoice_state.head_block_hash),
+        payload_id: None,
+    })
+}
+
+/// Decodes the EIP-2718 encoded transactions of a payload.
+fn decode_transactions<SignedTransactionT: alloy_rlp::Decodable>(
+    transactions: &[Bytes],
+) -> alloy_rlp::Result<Vec<SignedTransactionT>> {
+    transactions
+        .iter()
+        .map(|transaction| {
+            let mut buf = transaction.as_ref();
+            let transaction = SignedTransactionT::decode(&mut buf)?;
+            if !buf.is_empty() {
+                return Err(alloy_rlp::Error::UnexpectedLength);
+            }
+
+            Ok(transaction)
+        })
+        .collect()
+}
+
+/// Constructs the header of the block corresponding to the payload.
+fn payload_header(payload: &ExecutionPayloadV3, parent_beacon_block_root: B256) -> BlockHeader {
+    BlockHeader {
+        parent_hash: payload.parent_hash,
+        ommers_hash: KECCAK_RLP_EMPTY_ARRAY,
+        beneficiary: payload.fee_recipient,
+        state_root: payload.state_root,
+        transactions_root: ordered_trie_root(&payload.transactions),
+        receipts_root: payload.receipts_root,
+        logs_bloom: payload.logs_bloom,
+        difficulty: U256::ZERO,
+        number: payload.block_number,
+        gas_limit: payload.gas_limit,
+        gas_used: payload.gas_used,
+        timestamp: payload.timestamp,
+        extra_data: payload.extra_data.clone(),
+        mix_hash: payload.prev_randao,
+        nonce: B64::ZERO,
+        base_fee_per_gas: Some(payload.base_fee_per_gas),
+        withdrawals_root: Some(ordered_trie_root(
+            payload.withdrawals.iter().map(alloy_rlp::encode),
+        )),
+        blob_gas: Some(BlobGas {
+            gas_used: payload.blob_gas_used,
+            excess_gas: payload.excess_blob_gas,
+        }),
+        parent_beacon_block_root: Some(parent_beacon_block_root),
+        requests_hash: None,
+    }
+}
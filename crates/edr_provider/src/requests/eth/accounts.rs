@@ -1,4 +1,4 @@
-use edr_primitives::Address;
+use edr_primitives::{Address, B256};
 
 use crate::{
     data::ProviderData, error::ProviderErrorForChainSpec, spec::ProviderSpec, time::TimeSinceEpoch,
@@ -11,3 +11,40 @@ pub fn handle_accounts_request<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone +
 ) -> Result<Vec<Address>, ProviderErrorForChainSpec<ChainSpecT>> {
     Ok(data.accounts().copied().collect())
 }
+
+pub fn handle_import_raw_key_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    secret_key: B256,
+    passphrase: String,
+) -> Result<Address, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.import_raw_key(secret_key, &passphrase)
+}
+
+pub fn handle_new_account_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    passphrase: String,
+) -> Result<Address, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(data.new_account(&passphrase))
+}
+
+/// `duration`: the number of seconds that the account remains unlocked for.
+/// Zero seconds unlock the account indefinitely.
+pub fn handle_unlock_account_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    passphrase: String,
+    duration: Option<u64>,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.unlock_account(&address, &passphrase, duration)?;
+
+    Ok(true)
+}
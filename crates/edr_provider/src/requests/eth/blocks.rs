@@ -1,14 +1,14 @@
 use core::fmt::Debug;
 use std::sync::Arc;
 
-use edr_block_api::Block as _;
+use edr_block_api::{Block as _, FetchBlockReceipts as _};
 use edr_block_header::Withdrawal;
 use edr_chain_l1::rpc::block::L1RpcBlock;
 use edr_chain_spec::{ExecutableTransaction as _, TransactionValidation};
 use edr_chain_spec_block::BlockChainSpec;
 use edr_chain_spec_provider::ProviderChainSpec;
 use edr_chain_spec_rpc::RpcTypeFrom as _;
-use edr_eth::{BlockSpec, PreEip1898BlockSpec};
+use edr_eth::{blob::BlobSidecar, BlockSpec, PreEip1898BlockSpec};
 use edr_primitives::{B256, U256, U64};
 use edr_transaction::{BlockDataForTransaction, TransactionAndBlock};
 use edr_utils::CastArcFrom;
@@ -26,6 +26,16 @@ pub enum HashOrTransaction<RpcTransactionT> {
     Transaction(RpcTransactionT),
 }
 
+pub fn handle_get_blob_sidecars_request<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    block_hash: B256,
+) -> Result<Option<Vec<BlobSidecar>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.blob_sidecars_by_block_hash(&block_hash)
+}
+
 // The result type can not be meaningfully simplified further without reducing
 // readability.
 #[allow(clippy::type_complexity)]
@@ -117,6 +127,31 @@ pub fn handle_get_block_transaction_count_by_block_number<
         .map(|BlockByNumberResult { block, .. }| U64::from(block.transactions().len())))
 }
 
+pub fn handle_get_block_receipts_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    block_spec: BlockSpec,
+) -> Result<Option<Vec<ChainSpecT::RpcReceipt>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let Some(BlockByNumberResult { block, .. }) = block_by_number(data, &block_spec)? else {
+        return Ok(None);
+    };
+
+    let hardfork = data.hardfork();
+    let receipts = block
+        .fetch_transaction_receipts()
+        .map_err(ProviderError::FetchReceipt)?
+        .iter()
+        .map(|receipt| ChainSpecT::RpcReceipt::rpc_type_from(receipt, hardfork))
+        .collect();
+
+    Ok(Some(receipts))
+}
+
 /// Helper type for a chain-specific [`BlockByNumberResult`].
 type BlockByNumberResultForChainSpec<ChainSpecT> =
     BlockByNumberResult<Arc<<ChainSpecT as BlockChainSpec>::Block>>;
@@ -158,8 +193,7 @@ fn block_by_number<
         }
         // Pending block
         Ok(None) => {
-            let result = data.mine_pending_block()?;
-            let pending_block = Arc::new(result.block);
+            let pending_block = data.pending_block()?.block;
 
             let last_block = data.last_block()?;
             let previous_total_difficulty = data
@@ -179,7 +213,7 @@ fn block_by_number<
     }
 }
 
-fn block_to_rpc_output<ChainSpecT: ProviderChainSpec>(
+pub(crate) fn block_to_rpc_output<ChainSpecT: ProviderChainSpec>(
     hardfork: ChainSpecT::Hardfork,
     block: Arc<ChainSpecT::Block>,
     is_pending: bool,
@@ -48,7 +48,7 @@ pub fn handle_call_request<
     {
         return Err(ProviderError::TransactionFailed(Box::new(
             TransactionFailureWithTraces {
-                failure,
+                failure: failure.with_custom_error(data.contract_decoder()),
                 traces: vec![result.trace],
             },
         )));
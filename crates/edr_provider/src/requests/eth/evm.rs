@@ -35,16 +35,21 @@ pub fn handle_mine_request<
     data: &mut ProviderData<ChainSpecT, TimerT>,
     timestamp: Option<Timestamp>,
 ) -> ProviderResultWithTraces<String, ChainSpecT> {
-    let mine_block_result = data.mine_and_commit_block(HeaderOverrides {
+    let mut mine_block_results = vec![data.mine_and_commit_block(HeaderOverrides {
         timestamp: timestamp.map(Into::into),
         ..HeaderOverrides::default()
-    })?;
+    })?];
+    // Transactions whose inclusion delay expired are auto-mined
+    mine_block_results.extend(data.mine_includable_transactions()?);
 
-    let traces = mine_block_result.transaction_traces.clone();
+    let traces = mine_block_results
+        .iter()
+        .flat_map(|result| result.transaction_traces.clone())
+        .collect();
 
     let hardfork = data.hardfork();
     data.logger_mut()
-        .log_mined_block(hardfork, &[mine_block_result])
+        .log_mined_block(hardfork, &mine_block_results)
         .map_err(ProviderError::Logger)?;
 
     let result = String::from("0");
@@ -55,7 +60,7 @@ pub fn handle_revert_request<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + T
     data: &mut ProviderData<ChainSpecT, TimerT>,
     snapshot_id: U64,
 ) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
-    Ok(data.revert_to_snapshot(snapshot_id.as_limbs()[0]))
+    data.revert_to_snapshot(snapshot_id.as_limbs()[0])
 }
 
 pub fn handle_set_automine_request<
@@ -98,6 +103,19 @@ pub fn handle_set_next_block_timestamp_request<
     Ok(new_timestamp.to_string())
 }
 
+pub fn handle_set_time_request<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    timestamp: Timestamp,
+) -> Result<String, ProviderErrorForChainSpec<ChainSpecT>> {
+    let new_block_time = data.set_time(timestamp.into())?;
+
+    // Similar to `evm_increaseTime`, this returns a number as a string decimal
+    Ok(new_block_time.to_string())
+}
+
 pub fn handle_snapshot_request<
     ChainSpecT: SyncProviderSpec<TimerT>,
     TimerT: Clone + TimeSinceEpoch,
@@ -109,6 +109,7 @@ pub fn handle_subscribe_request<
         SubscriptionType::NewPendingTransactions => {
             Ok(data.add_pending_transaction_filter::<true>())
         }
+        SubscriptionType::DroppedTransactions => Ok(data.add_dropped_transaction_filter::<true>()),
     }
 }
 
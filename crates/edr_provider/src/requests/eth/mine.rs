@@ -21,3 +21,21 @@ pub fn handle_set_interval_mining<
 
     Ok(true)
 }
+
+/// Pauses or resumes interval mining. Returns `false` if interval mining is
+/// disabled.
+pub fn handle_set_interval_mining_paused<
+    ChainSpecT: SyncProviderSpec<TimerT, SignedTransaction: Default>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    interval_miner: &Option<IntervalMiner<ChainSpecT, TimerT>>,
+    paused: bool,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let Some(interval_miner) = interval_miner else {
+        return Ok(false);
+    };
+
+    interval_miner.set_paused(paused);
+
+    Ok(true)
+}
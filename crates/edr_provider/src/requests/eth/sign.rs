@@ -2,7 +2,8 @@ use alloy_dyn_abi::eip712::TypedData;
 use edr_primitives::{Address, Bytes};
 
 use crate::{
-    data::ProviderData, spec::ProviderSpec, time::TimeSinceEpoch, ProviderErrorForChainSpec,
+    data::ProviderData, requests::validation::validate_eip712_domain, spec::ProviderSpec,
+    time::TimeSinceEpoch, ProviderErrorForChainSpec,
 };
 
 pub fn handle_sign_request<ChainSpecT: ProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
@@ -21,5 +22,7 @@ pub fn handle_sign_typed_data_v4<
     address: Address,
     message: TypedData,
 ) -> Result<Bytes, ProviderErrorForChainSpec<ChainSpecT>> {
+    validate_eip712_domain::<ChainSpecT, TimerT>(&message)?;
+
     Ok((&data.sign_typed_data_v4(&address, &message)?).into())
 }
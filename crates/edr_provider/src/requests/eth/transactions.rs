@@ -1,17 +1,19 @@
-use std::sync::Arc;
-
 use edr_block_api::Block;
 use edr_blockchain_api::r#dyn::DynBlockchainError;
+use edr_chain_l1::rpc::TransactionRequest;
 use edr_chain_spec::{ExecutableTransaction as _, TransactionValidation};
 use edr_chain_spec_rpc::RpcTypeFrom as _;
-use edr_eth::PreEip1898BlockSpec;
+use edr_eth::{BlockSpec, PreEip1898BlockSpec};
 use edr_primitives::{Bytes, B256, U256};
 use edr_runtime::transaction;
+use edr_signer::FakeSign as _;
 use edr_transaction::{
-    request::TransactionRequestAndSender, BlockDataForTransaction, IsEip155, IsEip4844,
-    TransactionAndBlock, TransactionType, INVALID_TX_TYPE_ERROR_MESSAGE,
+    pooled::eip4844::BlobTransactionSidecar, request::TransactionRequestAndSender,
+    BlockDataForTransaction, IsEip155, IsEip4844, TransactionAndBlock, TransactionMut,
+    TransactionType, INVALID_TX_TYPE_ERROR_MESSAGE,
 };
 use edr_utils::CastArcInto as _;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     data::ProviderData,
@@ -20,7 +22,10 @@ use crate::{
         validate_eip3860_max_initcode_size, validate_post_merge_block_tags,
         validate_transaction_and_call_request,
     },
-    spec::{FromRpcType, Sender as _, SyncProviderSpec, TransactionContext},
+    spec::{
+        FromRpcType, MaybeGasLimit as _, PooledBlobSidecar as _, Sender as _, SyncProviderSpec,
+        TransactionContext,
+    },
     time::TimeSinceEpoch,
     ProviderError, ProviderResultWithTraces, ProviderSpec, TransactionFailure,
 };
@@ -64,8 +69,7 @@ pub fn handle_get_transaction_by_block_spec_and_index<
         Ok(Some(block)) => Some((block, false)),
         // Pending block requested
         Ok(None) => {
-            let result = data.mine_pending_block()?;
-            let pending_block = Arc::new(result.block);
+            let pending_block = data.pending_block()?.block;
             Some((pending_block.cast_arc_into(), true))
         }
         // Matching Hardhat behavior in returning None for invalid block hash or number.
@@ -177,8 +181,111 @@ pub fn handle_send_transaction_request<
 
     let request = TransactionRequestAndSender { request, sender };
     let signed_transaction = data.sign_transaction_request(request)?;
+    data.sponsor_transaction(&signed_transaction)?;
+    let previous_sender_balance = data.top_up_impersonated_sender(&signed_transaction)?;
 
-    send_raw_transaction_and_log(data, signed_transaction)
+    send_raw_transaction_and_log(data, signed_transaction, None, previous_sender_balance)
+}
+
+/// Signs the transaction request without sending it, returning the
+/// transaction's EIP-2718 encoding. Impersonated accounts can't be used, as
+/// their transactions can't be sent using `eth_sendRawTransaction`.
+pub fn handle_sign_transaction_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    request: ChainSpecT::RpcTransactionRequest,
+) -> Result<Bytes, ProviderErrorForChainSpec<ChainSpecT>> {
+    let sender = *request.sender();
+
+    let context = TransactionContext { data };
+    let request = ChainSpecT::TransactionRequest::from_rpc_type(request, context)?;
+
+    let request = TransactionRequestAndSender { request, sender };
+    let signed_transaction = data.sign_transaction_request_with_secret_key(request)?;
+
+    Ok(signed_transaction.rlp_encoding().clone())
+}
+
+/// Result of `eth_fillTransaction`, following Geth.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FillTransactionResult {
+    /// The RLP encoding of the filled transaction, with a placeholder
+    /// signature.
+    pub raw: Bytes,
+    /// The filled transaction.
+    pub tx: TransactionRequest,
+}
+
+/// Populates the missing nonce, gas limit, fee, and chain ID fields of a
+/// transaction request, returning the resulting unsigned transaction. Unless
+/// provided, the gas limit is estimated against the pending block.
+pub fn handle_fill_transaction_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionMut
+                               + TransactionType
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    request: ChainSpecT::RpcTransactionRequest,
+) -> Result<FillTransactionResult, ProviderErrorForChainSpec<ChainSpecT>> {
+    let sender = *request.sender();
+    let gas_limit = request.maybe_gas_limit();
+
+    let context = TransactionContext { data };
+    let request = ChainSpecT::TransactionRequest::from_rpc_type(request, context)?;
+
+    // The transaction is only signed to be able to execute it for gas estimation
+    let mut transaction = request.fake_sign(sender);
+    if gas_limit.is_none() {
+        let result = data.estimate_gas(transaction.clone(), &BlockSpec::pending())?;
+        transaction.set_gas_limit(result.estimation);
+    }
+
+    let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match transaction.max_fee_per_gas()
+    {
+        Some(max_fee_per_gas) => (
+            None,
+            Some(*max_fee_per_gas),
+            transaction.max_priority_fee_per_gas().copied(),
+        ),
+        None => (Some(*transaction.gas_price()), None, None),
+    };
+
+    let tx = TransactionRequest {
+        from: sender,
+        to: transaction.kind().to().copied(),
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        gas: Some(transaction.gas_limit()),
+        value: Some(*transaction.value()),
+        data: Some(transaction.data().clone()),
+        nonce: Some(transaction.nonce()),
+        chain_id: transaction.chain_id(),
+        access_list: transaction.access_list().map(<[_]>::to_vec),
+        transaction_type: Some(transaction.transaction_type().into()),
+        // Requests with blobs are rejected, as blob transactions can only be sent
+        // raw, so the filled transaction never has blobs.
+        blobs: None,
+        blob_hashes: None,
+        authorization_list: transaction.authorization_list().map(<[_]>::to_vec),
+    };
+
+    Ok(FillTransactionResult {
+        raw: transaction.rlp_encoding().clone(),
+        tx,
+    })
 }
 
 pub fn handle_send_raw_transaction_request<
@@ -194,25 +301,64 @@ pub fn handle_send_raw_transaction_request<
     data: &mut ProviderData<ChainSpecT, TimerT>,
     raw_transaction: Bytes,
 ) -> ProviderResultWithTraces<B256, ChainSpecT> {
-    use alloy_rlp::Decodable as _;
+    let pooled_transaction = decode_raw_transaction::<ChainSpecT, TimerT>(&raw_transaction)?;
+    let (signed_transaction, blob_sidecar) = validate_raw_transaction(data, pooled_transaction)?;
 
-    let mut raw_transaction: &[u8] = raw_transaction.as_ref();
-    let pooled_transaction =
-    ChainSpecT::PooledTransaction::decode(&mut raw_transaction).map_err(|err| match err {
-            alloy_rlp::Error::Custom(INVALID_TX_TYPE_ERROR_MESSAGE) => {
-                let type_id = *raw_transaction.first().expect("We already validated that the transaction is not empty if it's an invalid transaction type error.");
-                ProviderError::InvalidTransactionType(type_id)
-            }
-            err => ProviderError::InvalidArgument(err.to_string()),
-        })?;
+    data.sponsor_transaction(&signed_transaction)?;
+
+    send_raw_transaction_and_log(data, signed_transaction, blob_sidecar, None)
+}
 
+/// Validates a decoded, signed transaction, as sent to
+/// `eth_sendRawTransaction`. Returns the transaction and, for an EIP-4844
+/// transaction, its blob sidecar, which should only be stored once the
+/// transaction was accepted.
+pub(crate) fn validate_raw_transaction<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+        PooledTransaction: IsEip155,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    pooled_transaction: ChainSpecT::PooledTransaction,
+) -> Result<
+    (
+        ChainSpecT::SignedTransaction,
+        Option<BlobTransactionSidecar>,
+    ),
+    ProviderErrorForChainSpec<ChainSpecT>,
+> {
     validate_send_raw_transaction_request(data, &pooled_transaction)?;
+    let blob_sidecar = pooled_transaction.blob_sidecar();
     let signed_transaction = pooled_transaction.into();
 
     let signed_transaction = transaction::validate(signed_transaction, data.evm_spec_id())
         .map_err(ProviderError::TransactionCreationError)?;
 
-    send_raw_transaction_and_log(data, signed_transaction)
+    Ok((signed_transaction, blob_sidecar))
+}
+
+/// Decodes an RLP-encoded, signed transaction.
+pub(crate) fn decode_raw_transaction<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    raw_transaction: &Bytes,
+) -> Result<ChainSpecT::PooledTransaction, ProviderErrorForChainSpec<ChainSpecT>> {
+    use alloy_rlp::Decodable as _;
+
+    let mut raw_transaction: &[u8] = raw_transaction.as_ref();
+    ChainSpecT::PooledTransaction::decode(&mut raw_transaction).map_err(|err| match err {
+        alloy_rlp::Error::Custom(INVALID_TX_TYPE_ERROR_MESSAGE) => {
+            let type_id = *raw_transaction.first().expect("We already validated that the transaction is not empty if it's an invalid transaction type error.");
+            ProviderError::InvalidTransactionType(type_id)
+        }
+        err => ProviderError::InvalidArgument(err.to_string()),
+    })
 }
 
 pub fn calculate_eip1559_fee_parameters<
@@ -273,7 +419,9 @@ pub fn calculate_eip1559_fee_parameters<
     Ok(parameters)
 }
 
-fn send_raw_transaction_and_log<
+/// Sends a validated, signed transaction and logs the result. If sending
+/// fails, the sender's top-up and the transaction's sponsorship are undone.
+pub(crate) fn send_raw_transaction_and_log<
     ChainSpecT: SyncProviderSpec<
         TimerT,
         SignedTransaction: Default
@@ -284,8 +432,26 @@ fn send_raw_transaction_and_log<
 >(
     data: &mut ProviderData<ChainSpecT, TimerT>,
     signed_transaction: ChainSpecT::SignedTransaction,
+    blob_sidecar: Option<BlobTransactionSidecar>,
+    previous_sender_balance: Option<U256>,
 ) -> ProviderResultWithTraces<B256, ChainSpecT> {
-    let result = data.send_transaction(signed_transaction.clone())?;
+    let result = match data.send_transaction(signed_transaction.clone()) {
+        Ok(result) => result,
+        Err(error) => {
+            // The transaction won't be mined, so the sender's top-up is undone
+            // and the sponsor is refunded
+            if let Some(balance) = previous_sender_balance {
+                data.set_balance(*signed_transaction.caller(), balance)?;
+            }
+            data.cancel_transaction_sponsorship(signed_transaction.transaction_hash())?;
+            return Err(error);
+        }
+    };
+
+    // The blob sidecar is only stored once the transaction was accepted
+    if let Some(blob_sidecar) = blob_sidecar {
+        data.add_blob_sidecar(result.transaction_hash, blob_sidecar);
+    }
 
     let hardfork = data.hardfork();
     data.logger_mut()
@@ -302,6 +468,7 @@ fn send_raw_transaction_and_log<
                         Some(&result.transaction_hash),
                         trace,
                     )
+                    .map(|failure| failure.with_custom_error(data.contract_decoder()))
                 });
 
         if let Some(failure) = transaction_failure {
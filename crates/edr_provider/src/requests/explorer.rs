@@ -0,0 +1,113 @@
+use edr_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::ProviderData, error::ProviderErrorForChainSpec, spec::SyncProviderSpec,
+    time::TimeSinceEpoch, ProviderError,
+};
+
+/// The number of transactions per page, if not specified.
+pub const DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 25;
+
+/// The maximum number of transactions per page.
+pub const MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 1000;
+
+/// The way in which a transaction involves an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressRelation {
+    /// The address sent the transaction.
+    Sent,
+    /// The address is the recipient of the transaction or the contract that
+    /// it created.
+    Received,
+    /// The address was the caller, callee, or executed code of an internal
+    /// call; was created by an internal create; or was involved in a
+    /// self-destruct.
+    Internal,
+}
+
+/// A transaction that involves an address.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransaction {
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    pub transaction_hash: B256,
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    pub sent: bool,
+    pub received: bool,
+    pub internal: bool,
+}
+
+impl AddressTransaction {
+    /// Whether the transaction involves the address in the provided way.
+    pub fn has_relation(&self, relation: AddressRelation) -> bool {
+        match relation {
+            AddressRelation::Sent => self.sent,
+            AddressRelation::Received => self.received,
+            AddressRelation::Internal => self.internal,
+        }
+    }
+}
+
+/// Options of `explorer_getAddressTransactions`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsOptions {
+    /// If present, only transactions that involve the address in this way are
+    /// returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relation: Option<AddressRelation>,
+    /// The zero-based index of the page. Defaults to the first page.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub page: Option<u64>,
+    /// The number of transactions per page. Defaults to
+    /// [`DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub page_size: Option<u64>,
+}
+
+/// Result of `explorer_getAddressTransactions`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsPage {
+    /// The page's transactions, from the most to the least recent.
+    pub transactions: Vec<AddressTransaction>,
+    /// The total number of matching transactions, across all pages.
+    #[serde(with = "alloy_serde::quantity")]
+    pub total: u64,
+}
+
+pub fn handle_get_address_transactions<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    options: Option<AddressTransactionsOptions>,
+) -> Result<AddressTransactionsPage, ProviderErrorForChainSpec<ChainSpecT>> {
+    let AddressTransactionsOptions {
+        relation,
+        page,
+        page_size,
+    } = options.unwrap_or_default();
+
+    let page_size = page_size.unwrap_or(DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE);
+    if page_size == 0 || page_size > MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE {
+        return Err(ProviderError::InvalidInput(format!(
+            "pageSize must be between 1 and {MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE}, but is {page_size}"
+        )));
+    }
+
+    data.address_transactions(&address, relation, page.unwrap_or(0), page_size)
+}
@@ -1,9 +1,17 @@
 mod accounts;
+mod bundle;
 mod config;
+mod contracts;
+mod deployment;
 mod log;
 mod miner;
 pub mod rpc_types;
+mod sign;
 mod state;
+mod tokens;
 mod transactions;
 
-pub use self::{accounts::*, config::*, log::*, miner::*, state::*, transactions::*};
+pub use self::{
+    accounts::*, bundle::*, config::*, contracts::*, deployment::*, log::*, miner::*, sign::*,
+    state::*, tokens::*, transactions::*,
+};
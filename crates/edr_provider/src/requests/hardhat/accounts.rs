@@ -1,9 +1,36 @@
-use edr_primitives::Address;
+use edr_primitives::{Address, U64};
 
 use crate::{
-    data::ProviderData, spec::ProviderSpec, time::TimeSinceEpoch, ProviderErrorForChainSpec,
+    data::ProviderData,
+    spec::{ProviderSpec, SyncProviderSpec},
+    time::TimeSinceEpoch,
+    ProviderErrorForChainSpec,
 };
 
+pub fn handle_auto_impersonate_account_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    enabled: bool,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_auto_impersonate(enabled);
+
+    Ok(true)
+}
+
+/// Retrieves the nonce of the next transaction of the account, taking into
+/// account its transactions in the mem pool.
+pub fn handle_get_next_nonce_request<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+) -> Result<U64, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.account_next_nonce(&address).map(U64::from)
+}
+
 pub fn handle_impersonate_account_request<
     ChainSpecT: ProviderSpec<TimerT>,
     TimerT: Clone + TimeSinceEpoch,
@@ -0,0 +1,101 @@
+use edr_chain_spec::TransactionValidation;
+use edr_eth::BlockSpec;
+use edr_primitives::Bytes;
+use edr_runtime::{overrides::StateOverrides, transaction};
+
+use crate::{
+    data::{BundleBlockOverrides, BundleTransaction, ProviderData},
+    error::ProviderErrorForChainSpec,
+    requests::{
+        eth::{decode_raw_transaction, resolve_block_spec_for_call_request, resolve_call_request},
+        hardhat::rpc_types::{BundleSimulation, BundleTransactionRequest, CallBundleRequest},
+    },
+    spec::SyncProviderSpec,
+    time::TimeSinceEpoch,
+    ProviderError,
+};
+
+pub fn handle_call_bundle_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    request: CallBundleRequest,
+) -> Result<BundleSimulation, ProviderErrorForChainSpec<ChainSpecT>> {
+    let CallBundleRequest {
+        txs,
+        block_number,
+        state_block_number,
+        timestamp,
+        coinbase,
+    } = request;
+
+    let block_spec = resolve_block_spec_for_call_request(state_block_number);
+
+    let transactions = txs
+        .iter()
+        .map(|raw_transaction| {
+            resolve_signed_transaction(data, raw_transaction, &block_spec)
+                .map(BundleTransaction::Signed)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let block_overrides = BundleBlockOverrides {
+        number: block_number,
+        timestamp,
+        coinbase,
+    };
+
+    data.simulate_bundle(transactions, &block_spec, block_overrides)
+}
+
+pub fn handle_simulate_bundle_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transactions: Vec<BundleTransactionRequest<ChainSpecT::RpcCallRequest>>,
+    block_spec: Option<BlockSpec>,
+) -> Result<BundleSimulation, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block_spec = resolve_block_spec_for_call_request(block_spec);
+
+    let transactions = transactions
+        .into_iter()
+        .map(|transaction| match transaction {
+            BundleTransactionRequest::Signed(raw_transaction) => {
+                resolve_signed_transaction(data, &raw_transaction, &block_spec)
+                    .map(BundleTransaction::Signed)
+            }
+            BundleTransactionRequest::Unsigned(request) => {
+                resolve_call_request(data, request, &block_spec, &StateOverrides::default())
+                    .map(BundleTransaction::Unsigned)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    data.simulate_bundle(transactions, &block_spec, BundleBlockOverrides::default())
+}
+
+fn resolve_signed_transaction<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    raw_transaction: &Bytes,
+    block_spec: &BlockSpec,
+) -> Result<ChainSpecT::SignedTransaction, ProviderErrorForChainSpec<ChainSpecT>> {
+    let pooled_transaction = decode_raw_transaction::<ChainSpecT, TimerT>(raw_transaction)?;
+
+    let hardfork = data.hardfork_at_block_spec(block_spec)?;
+    transaction::validate(pooled_transaction.into(), hardfork.into())
+        .map_err(ProviderError::TransactionCreationError)
+}
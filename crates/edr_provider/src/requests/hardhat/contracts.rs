@@ -0,0 +1,26 @@
+use edr_solidity::external_artifact::{create_contract_metadata_from_artifact, ExternalArtifact};
+
+use crate::{
+    data::ProviderData, spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderError,
+    ProviderErrorForChainSpec,
+};
+
+/// Registers the ABI and bytecode of a contract that wasn't compiled by
+/// Hardhat, to decode its traces.
+pub fn handle_add_contract_artifact<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    artifact: ExternalArtifact,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let metadata = create_contract_metadata_from_artifact(artifact)
+        .map_err(|error| ProviderError::InvalidInput(error.to_string()))?;
+
+    let contract_decoder = data.contract_decoder();
+    for metadata in metadata {
+        contract_decoder.add_contract_metadata(metadata);
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,32 @@
+use edr_primitives::{Address, Bytes, B256};
+
+use crate::{
+    data::ProviderData, spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderErrorForChainSpec,
+};
+
+pub fn handle_predict_create_address<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    deployer: Address,
+    nonce: Option<u64>,
+) -> Result<Address, ProviderErrorForChainSpec<ChainSpecT>> {
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => data.account_next_nonce(&deployer)?,
+    };
+
+    Ok(deployer.create(nonce))
+}
+
+pub fn handle_predict_create2_address<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    deployer: Address,
+    salt: B256,
+    init_code: Bytes,
+) -> Result<Address, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(deployer.create2_from_code(salt, init_code))
+}
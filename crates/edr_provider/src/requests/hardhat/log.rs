@@ -1,7 +1,18 @@
 use crate::{
-    data::ProviderData, spec::ProviderSpec, time::TimeSinceEpoch, ProviderErrorForChainSpec,
+    data::ProviderData, hardhat_rpc_types::LoggingOptions, log_filter, spec::ProviderSpec,
+    time::TimeSinceEpoch, ProviderError, ProviderErrorForChainSpec,
 };
 
+pub fn handle_set_log_filter_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    filter: &str,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    log_filter::set_log_filter(filter)?;
+    Ok(true)
+}
+
 pub fn handle_set_logging_enabled_request<
     ChainSpecT: ProviderSpec<TimerT>,
     TimerT: Clone + TimeSinceEpoch,
@@ -12,3 +23,17 @@ pub fn handle_set_logging_enabled_request<
     data.logger_mut().set_is_enabled(is_enabled);
     Ok(true)
 }
+
+pub fn handle_set_logging_options_request<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    options: LoggingOptions,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.logger_mut()
+        .set_options(options)
+        .map_err(|error| ProviderError::InvalidInput(error.to_string()))?;
+
+    Ok(true)
+}
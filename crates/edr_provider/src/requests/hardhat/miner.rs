@@ -1,8 +1,9 @@
 use edr_chain_spec::TransactionValidation;
+use edr_primitives::HashSet;
 
 use crate::{
-    data::ProviderData, spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderError,
-    ProviderResultWithTraces,
+    data::ProviderData, requests::hardhat::rpc_types::ReorgOptions, spec::SyncProviderSpec,
+    time::TimeSinceEpoch, ProviderError, ProviderResultWithTraces,
 };
 
 pub fn handle_mine<
@@ -19,7 +20,43 @@ pub fn handle_mine<
     let number_of_blocks = number_of_blocks.unwrap_or(1);
     let interval = interval.unwrap_or(1);
 
-    let mined_block_results = data.mine_and_commit_blocks(number_of_blocks, interval)?;
+    let mut mined_block_results = data.mine_and_commit_blocks(number_of_blocks, interval)?;
+    // Transactions whose inclusion delay expired are auto-mined
+    mined_block_results.extend(data.mine_includable_transactions()?);
+
+    let hardfork = data.hardfork();
+    data.logger_mut()
+        .log_mined_block(hardfork, &mined_block_results)
+        .map_err(ProviderError::Logger)?;
+
+    let traces = mined_block_results
+        .into_iter()
+        .flat_map(|result| result.transaction_traces)
+        .collect();
+
+    Ok((true, traces))
+}
+
+pub fn handle_reorg<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    depth: u64,
+    options: Option<ReorgOptions>,
+) -> ProviderResultWithTraces<bool, ChainSpecT> {
+    let ReorgOptions {
+        new_blocks,
+        drop_transactions,
+    } = options.unwrap_or_default();
+
+    let new_blocks = new_blocks.map_or(depth, |new_blocks| new_blocks.to());
+    let dropped_transactions: HashSet<_> = drop_transactions.into_iter().flatten().collect();
+
+    let mined_block_results = data.reorg(depth, new_blocks, &dropped_transactions)?;
 
     let hardfork = data.hardfork();
     data.logger_mut()
@@ -1,5 +1,44 @@
+mod authorization;
+mod bundle;
+mod fee_checks;
+mod fixture;
+mod flood;
+mod gas_profile;
+mod header;
+mod inclusion;
+mod logging;
+mod mempool;
 mod metadata;
+mod node_info;
+mod reorg;
+mod replay;
 mod state;
+mod storage_layout;
+mod token;
+mod transaction_import;
+mod user_operation;
 
+pub use authorization::AuthorizationRequest;
+pub use bundle::{
+    BundleSimulation, BundleTransactionRequest, BundleTransactionResult, CallBundleRequest,
+};
+pub use fee_checks::FeeChecksConfig;
+pub use fixture::FixtureResult;
+pub use flood::{FloodOptions, FloodResult};
+pub use gas_profile::{FrameGasProfile, FunctionGasProfile, GasProfile, LineGasProfile};
+pub use header::NextBlockHeaderOverrides;
+pub use inclusion::{InclusionAction, InclusionPolicy, InclusionRule};
+pub use logging::{LogVerbosity, LoggingOptions};
+pub use mempool::MempoolConfig;
 pub use metadata::{ForkMetadata, Metadata};
+pub use node_info::{NodeEnvironment, NodeForkConfig, NodeInfo};
+pub use reorg::ReorgOptions;
+pub use replay::{
+    AccountDiff, AccountMismatch, Mismatch, ReplayLog, ReplayReceipt, ReplayedBlock,
+    ReplayedTransaction, TransactionMismatch, ValueChange,
+};
 pub use state::{StateAccount, StateDump};
+pub use storage_layout::{StorageEncoding, StorageLayout, StorageLayoutEntry, StorageLayoutType};
+pub use token::{TokenBalance, TokenStandard, TokenTransfer, TokenTransferFilter};
+pub use transaction_import::{ImportedTransaction, TransactionImportSource};
+pub use user_operation::UserOperation;
@@ -0,0 +1,21 @@
+use edr_primitives::{Address, U256, U64};
+
+/// EIP-7702 authorization to sign, as provided to
+/// `hardhat_signAuthorization`.
+///
+/// Omitted fields are filled in by the provider.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationRequest {
+    /// The chain ID for which the authorization is valid. Defaults to the
+    /// provider's chain ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U256>,
+    /// The address of the code that the authorizing account delegates to.
+    pub address: Address,
+    /// The nonce of the authorizing account. Defaults to the account's next
+    /// nonce, which is only valid if the authorizing account doesn't also send
+    /// the transaction that carries the authorization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+}
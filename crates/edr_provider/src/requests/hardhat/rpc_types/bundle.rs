@@ -0,0 +1,104 @@
+//! RPC types for the `eth_callBundle` and `hardhat_simulateBundle` methods.
+
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+
+use super::ReplayLog;
+
+/// Parameters of `eth_callBundle`, following the format of Flashbots'
+/// relay.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleRequest {
+    /// The signed, RLP-encoded transactions of the bundle.
+    pub txs: Vec<Bytes>,
+    /// The number of the block in which the bundle is simulated. Defaults to
+    /// the number following that of the state block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub block_number: Option<u64>,
+    /// The block on top of whose state the bundle is simulated. Defaults to
+    /// the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_block_number: Option<BlockSpec>,
+    /// The timestamp of the block in which the bundle is simulated. Defaults
+    /// to one second after that of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// The coinbase of the block in which the bundle is simulated. Defaults
+    /// to that of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+}
+
+/// A transaction of a bundle, as provided to `hardhat_simulateBundle`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum BundleTransactionRequest<CallRequestT> {
+    /// A signed, RLP-encoded transaction, which is fully validated.
+    Signed(Bytes),
+    /// An unsigned transaction, which is executed like an `eth_call`.
+    Unsigned(CallRequestT),
+}
+
+/// The outcome of a simulated bundle, as returned by `eth_callBundle` and
+/// `hardhat_simulateBundle`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSimulation {
+    /// The Keccak-256 hash of the concatenated transaction hashes.
+    pub bundle_hash: B256,
+    /// The total payment to the coinbase divided by the total gas used.
+    pub bundle_gas_price: U256,
+    /// The increase in the coinbase's balance.
+    pub coinbase_diff: U256,
+    /// The part of the coinbase difference that was paid by transferring
+    /// ether, rather than through fees.
+    pub eth_sent_to_coinbase: U256,
+    /// The part of the coinbase difference that was paid through priority
+    /// fees.
+    pub gas_fees: U256,
+    /// The number of the block on top of whose state the bundle was
+    /// simulated.
+    pub state_block_number: u64,
+    /// The gas used by all transactions.
+    pub total_gas_used: u64,
+    /// The outcomes of the individual transactions, in order.
+    pub results: Vec<BundleTransactionResult>,
+}
+
+/// The outcome of a transaction of a simulated bundle.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleTransactionResult {
+    /// The hash of the transaction. Unsigned transactions are hashed with a
+    /// fake signature.
+    pub tx_hash: B256,
+    /// The sender of the transaction.
+    pub from_address: Address,
+    /// The recipient of the transaction, or `None` for contract creations.
+    pub to_address: Option<Address>,
+    /// The gas used by the transaction, including the intrinsic gas.
+    pub gas_used: u64,
+    /// The effective gas price of the transaction.
+    pub gas_price: U256,
+    /// The priority fees paid to the coinbase.
+    pub gas_fees: U256,
+    /// The increase in the coinbase's balance.
+    pub coinbase_diff: U256,
+    /// The ether transferred to the coinbase, excluding fees.
+    pub eth_sent_to_coinbase: U256,
+    /// The return data of the transaction.
+    pub value: Bytes,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReplayLog>,
+    /// The reason why the transaction failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The decoded revert reason, if the transaction reverted with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+}
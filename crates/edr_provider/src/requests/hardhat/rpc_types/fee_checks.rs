@@ -0,0 +1,20 @@
+/// Which fee and balance checks are disabled, as provided to
+/// `hardhat_setFeeChecks`.
+///
+/// Omitted fields retain their current values.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeChecksConfig {
+    /// Whether to accept and execute transactions regardless of whether the
+    /// sender's balance covers their upfront cost.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_balance_check: Option<bool>,
+    /// Whether to accept and execute transactions whose gas price - or max
+    /// fee per gas - is below the base fee of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_base_fee_check: Option<bool>,
+    /// Whether to accept and mine transactions whose priority fee is below
+    /// the minimum gas price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_priority_fee_check: Option<bool>,
+}
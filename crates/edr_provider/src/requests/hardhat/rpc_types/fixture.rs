@@ -0,0 +1,17 @@
+use edr_primitives::B256;
+
+/// The result of a `hardhat_runFixture` call.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureResult {
+    /// The hash of the base block, its state overrides, the chain
+    /// configuration, and the fixture's transactions that identifies the
+    /// cached fixture.
+    pub key: B256,
+    /// Whether the fixture was restored from the cache, instead of executing
+    /// its transactions.
+    pub cache_hit: bool,
+    /// The hashes of the fixture's transactions, in the order in which they
+    /// were mined.
+    pub transaction_hashes: Vec<B256>,
+}
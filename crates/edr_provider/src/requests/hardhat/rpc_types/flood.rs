@@ -0,0 +1,47 @@
+use edr_primitives::{Address, Bytes, U256, U64};
+
+/// Options for generating synthetic load, as provided to `hardhat_flood`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FloodOptions {
+    /// The number of transactions to send.
+    pub count: U64,
+    /// The sender of the transactions. Defaults to the first local account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    /// The receiver of the transactions. Transactions without a receiver
+    /// create contracts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The value sent with each transaction. Defaults to zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// The calldata sent with each transaction. Defaults to empty calldata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+    /// The gas limit of each transaction. Defaults to the transaction gas cap
+    /// or block gas limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<U64>,
+}
+
+/// Throughput statistics of a `hardhat_flood` call.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FloodResult {
+    /// The number of mined transactions.
+    pub transactions: u64,
+    /// The number of mined transactions that failed.
+    pub failed: u64,
+    /// The number of mined blocks.
+    pub blocks: u64,
+    /// The total gas used by the mined transactions.
+    pub gas_used: u64,
+    /// The wall-clock duration of creating and mining the transactions, in
+    /// milliseconds.
+    pub duration_ms: f64,
+    /// The number of mined transactions per second.
+    pub transactions_per_second: f64,
+    /// The amount of gas used per second.
+    pub gas_per_second: f64,
+}
@@ -0,0 +1,59 @@
+/// Gas usage of a transaction, as returned by `hardhat_getGasProfile`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfile {
+    /// The total gas used by the transaction, including the intrinsic gas.
+    pub gas_used: u64,
+    /// The gas usage of each call frame, in execution order.
+    pub frames: Vec<FrameGasProfile>,
+    /// The gas usage of each internal function, ordered from most to least
+    /// expensive. Only contains functions of recognized contracts.
+    pub functions: Vec<FunctionGasProfile>,
+    /// The gas usage of each source line. Only contains lines of recognized
+    /// contracts.
+    pub lines: Vec<LineGasProfile>,
+}
+
+/// Gas usage of a single call frame.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameGasProfile {
+    /// The depth of the call frame, starting at 0 for the top-level call.
+    pub depth: u64,
+    /// The name of the called contract.
+    pub contract: String,
+    /// The name of the called function, if it's known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The gas used by the call frame, including its child frames.
+    pub gas_used: u64,
+    /// The gas used by the call frame's own instructions.
+    pub self_gas: u64,
+}
+
+/// Gas usage of an internal function, aggregated over all call frames.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionGasProfile {
+    /// The source name of the file that contains the function.
+    pub source_name: String,
+    /// The name of the contract that contains the function, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    /// The name of the function.
+    pub function: String,
+    /// The gas used by the function's instructions, excluding external calls.
+    pub gas: u64,
+}
+
+/// Gas usage of a source line, aggregated over all call frames.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineGasProfile {
+    /// The source name of the file that contains the line.
+    pub source_name: String,
+    /// The 1-based line number.
+    pub line: u32,
+    /// The gas used by the line's instructions, excluding external calls.
+    pub gas: u64,
+}
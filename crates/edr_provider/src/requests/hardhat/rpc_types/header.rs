@@ -0,0 +1,23 @@
+use edr_primitives::{Address, Bytes, B256, U64};
+
+/// Overrides for fields of the next block's header, as provided to
+/// `hardhat_setNextBlockHeaderOverrides`.
+///
+/// The overrides only apply to the next mined block. Omitted fields use their
+/// regular values.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NextBlockHeaderOverrides {
+    /// The block's beneficiary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// The block's extra data, which is at most 32 bytes long.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_data: Option<Bytes>,
+    /// The block's gas limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    /// The block's mix hash, which is the prevrandao value after the merge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mix_hash: Option<B256>,
+}
@@ -0,0 +1,67 @@
+use edr_primitives::{Address, B256, U64};
+
+/// Policy that simulates adverse network conditions by delaying, dropping, or
+/// reordering the inclusion of transactions, as provided to
+/// `hardhat_setInclusionPolicy`.
+///
+/// All pseudo-random decisions are derived from the `seed`, making them
+/// reproducible.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionPolicy {
+    /// Rules that are applied to transactions when they're sent. Only the
+    /// first rule that applies to a transaction takes effect.
+    #[serde(default)]
+    pub rules: Vec<InclusionRule>,
+    /// Whether to mine pending transactions in a pseudo-random order, instead
+    /// of the mem pool's order. A sender's transactions are always mined in
+    /// nonce order.
+    #[serde(default)]
+    pub shuffle: bool,
+    /// Seed for the policy's pseudo-random decisions. Defaults to zero.
+    #[serde(default)]
+    pub seed: U64,
+}
+
+impl InclusionPolicy {
+    /// Whether the policy affects any transactions.
+    pub fn is_active(&self) -> bool {
+        !self.rules.is_empty() || self.shuffle
+    }
+}
+
+/// A rule of an [`InclusionPolicy`].
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionRule {
+    /// Hashes of the transactions that the rule applies to. If omitted, the
+    /// rule applies to transactions with any hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_hashes: Option<Vec<B256>>,
+    /// Senders of the transactions that the rule applies to. If omitted, the
+    /// rule applies to transactions of any sender.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub senders: Option<Vec<Address>>,
+    /// Probability between 0 and 1 that the rule applies to a matching
+    /// transaction. Defaults to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probability: Option<f64>,
+    /// The action to take for transactions that the rule applies to.
+    pub action: InclusionAction,
+}
+
+/// The action of an [`InclusionRule`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum InclusionAction {
+    /// Keeps the transaction out of the provided number of blocks, after
+    /// which it can be mined like any other pending transaction. Delayed
+    /// transactions aren't automined.
+    Delay {
+        /// The number of blocks
+        blocks: U64,
+    },
+    /// Accepts the transaction without adding it to the mem pool, as if it
+    /// was lost by the network.
+    Drop,
+}
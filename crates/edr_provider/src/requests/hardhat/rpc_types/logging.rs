@@ -0,0 +1,35 @@
+use std::{collections::HashMap, path::PathBuf};
+
+/// The level of detail with which the execution of a method is logged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LogVerbosity {
+    /// Nothing is logged.
+    Silent,
+    /// Only the method name and errors are logged.
+    Summary,
+    /// The method name is logged, along with block and transaction details.
+    #[default]
+    Full,
+}
+
+/// Options of the execution logger, as provided to
+/// `hardhat_setLoggingOptions`.
+///
+/// Omitted fields leave the corresponding option unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggingOptions {
+    /// The verbosity of methods without a method-specific verbosity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<LogVerbosity>,
+    /// Method-specific verbosities, keyed by method name, e.g.
+    /// `eth_sendTransaction`. Replaces previously provided method-specific
+    /// verbosities.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method_verbosity: Option<HashMap<String, LogVerbosity>>,
+    /// The output of the logger. A file path to which logs are appended, or an
+    /// empty string to restore the default output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<PathBuf>,
+}
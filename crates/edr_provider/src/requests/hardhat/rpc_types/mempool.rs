@@ -0,0 +1,32 @@
+use edr_primitives::U64;
+
+/// Configuration of the mem pool's policy, as provided to
+/// `hardhat_setMempoolConfig`.
+///
+/// Omitted fields revert to their default values.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolConfig {
+    /// The minimum percentage by which the fees of a replacement transaction
+    /// need to exceed the fees of the transaction it replaces. Defaults to
+    /// 10%.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_bump_percentage: Option<U64>,
+    /// The maximum number of transactions in the mem pool. Unlimited if
+    /// omitted or zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_transactions: Option<U64>,
+    /// The maximum number of transactions per sender in the mem pool.
+    /// Unlimited if omitted or zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_transactions_per_sender: Option<U64>,
+    /// The number of seconds that a transaction can remain in the mem pool,
+    /// before it's dropped. Unlimited if omitted or zero.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_lifetime: Option<U64>,
+    /// Whether transactions with a nonce higher than the sender's next nonce
+    /// are queued while automining, instead of being rejected. Disabled by
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_future_transactions: Option<bool>,
+}
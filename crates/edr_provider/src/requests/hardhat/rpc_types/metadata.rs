@@ -19,6 +19,11 @@ pub struct Metadata {
     pub latest_block_number: u64,
     /// The latest block's hash in Hardhat Network
     pub latest_block_hash: B256,
+    /// The name of the hardfork that is used to mine new blocks.
+    pub hardfork: String,
+    /// The timestamp of the block on top of which local blocks are mined, i.e.
+    /// the genesis block or - when forking - the fork block.
+    pub genesis_timestamp: u64,
     /// This field is only present when Hardhat Network is forking another
     /// chain.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,4 +40,8 @@ pub struct ForkMetadata {
     pub fork_block_number: u64,
     /// The hash of the block that the network forked from.
     pub fork_block_hash: B256,
+    /// The Keccak-256 hash of the URL of the forked network's JSON-RPC
+    /// endpoint. Identifies the endpoint without exposing credentials that
+    /// might be part of the URL.
+    pub fork_url_hash: B256,
 }
@@ -0,0 +1,47 @@
+use edr_primitives::B256;
+
+/// Information about the node, as returned by `anvil_nodeInfo`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// The latest block's number.
+    pub current_block_number: u64,
+    /// The latest block's timestamp.
+    pub current_block_timestamp: u64,
+    /// The latest block's hash.
+    pub current_block_hash: B256,
+    /// The name of the hardfork that is used to mine new blocks.
+    pub hard_fork: String,
+    /// The order in which pending transactions are mined, i.e. `fifo` or
+    /// `fees`.
+    pub transaction_order: String,
+    /// The environment of the next block.
+    pub environment: NodeEnvironment,
+    /// The fork configuration of the node.
+    pub fork_config: NodeForkConfig,
+}
+
+/// The environment of the next block, as returned by `anvil_nodeInfo`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeEnvironment {
+    /// The base fee per gas of the next block. Zero for pre-London hardforks.
+    pub base_fee: u128,
+    /// The chain's id.
+    pub chain_id: u64,
+    /// The gas limit of the next block.
+    pub gas_limit: u64,
+    /// The suggested gas price, as returned by `eth_gasPrice`.
+    pub gas_price: u128,
+}
+
+/// The fork configuration of the node, as returned by `anvil_nodeInfo`.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeForkConfig {
+    /// The origin of the forked network's JSON-RPC endpoint. The credentials,
+    /// path, and query of the URL are omitted, as they may contain API keys.
+    pub fork_url: Option<String>,
+    /// The number of the block that the network forked from.
+    pub fork_block_number: Option<u64>,
+}
@@ -0,0 +1,16 @@
+use edr_primitives::{B256, U64};
+
+/// Options for simulating a chain reorganisation, as provided to
+/// `hardhat_reorg`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgOptions {
+    /// The number of replacement blocks to mine. Defaults to the reorg depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_blocks: Option<U64>,
+    /// Hashes of transactions in the removed blocks that should not be
+    /// re-included in the replacement blocks. By default, all transactions are
+    /// returned to the mem pool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drop_transactions: Option<Vec<B256>>,
+}
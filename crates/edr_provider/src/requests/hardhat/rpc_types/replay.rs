@@ -0,0 +1,190 @@
+//! RPC types for the hardhat_replayBlock and hardhat_replayTransaction
+//! methods.
+
+use std::collections::BTreeMap;
+
+use edr_primitives::{Address, Bloom, Bytes, B256, U256};
+
+use crate::debug_trace::DebugTraceResult;
+
+/// The outcome of a replayed transaction, as returned by
+/// `hardhat_replayTransaction`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedTransaction {
+    /// The receipt of the replayed transaction.
+    pub receipt: ReplayReceipt,
+    /// The EIP-3155 trace of the replayed transaction.
+    pub trace: DebugTraceResult,
+    /// The changes that the transaction made to each account it modified.
+    pub state_diff: BTreeMap<Address, AccountDiff>,
+}
+
+/// The receipt of a replayed transaction.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReceipt {
+    /// Whether the transaction was executed successfully.
+    pub status: bool,
+    /// The gas used by the transaction, including the intrinsic gas.
+    pub gas_used: u64,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReplayLog>,
+    /// The address of the created contract, if the transaction created one.
+    pub contract_address: Option<Address>,
+}
+
+/// A log emitted by a replayed transaction.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ReplayLog {
+    /// The address of the contract that emitted the log.
+    pub address: Address,
+    /// The topics of the log.
+    pub topics: Vec<B256>,
+    /// The data of the log.
+    pub data: Bytes,
+}
+
+/// The changes made to an account by a replayed transaction. Only fields
+/// that changed are present.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AccountDiff {
+    /// The change in balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<ValueChange<U256>>,
+    /// The change in nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<ValueChange<u64>>,
+    /// The change in code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<ValueChange<Bytes>>,
+    /// The changes in storage, keyed by storage slot.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, ValueChange<U256>>,
+}
+
+impl AccountDiff {
+    /// Whether the account was left unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// A value before and after a replayed transaction.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ValueChange<T> {
+    /// The value before the transaction.
+    pub from: T,
+    /// The value after the transaction.
+    pub to: T,
+}
+
+impl<T: PartialEq> ValueChange<T> {
+    /// Constructs a new instance if the value changed.
+    pub fn new(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// The outcome of a replayed block, as returned by `hardhat_replayBlock`.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedBlock {
+    /// The number of the replayed block.
+    pub block_number: u64,
+    /// Whether the replay matched the block's receipts and post-state.
+    pub matches: bool,
+    /// The mismatch in the total gas used by the block, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<Mismatch<u64>>,
+    /// The mismatch in the state root of the block, if any. Only compared
+    /// for local blockchains, as the state root of forked state cannot be
+    /// computed locally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<Mismatch<B256>>,
+    /// The mismatch between the number of receipts of the block and the
+    /// number of replayed transactions, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_count: Option<Mismatch<usize>>,
+    /// The transactions whose replayed receipt differs from the block's
+    /// receipt, in block order.
+    pub transactions: Vec<TransactionMismatch>,
+    /// The accounts whose replayed state differs from the block's post-state.
+    ///
+    /// The state root of forked state cannot be computed locally, so the
+    /// post-state is compared account by account instead. Only accounts that
+    /// were modified by the replay are compared.
+    pub accounts: BTreeMap<Address, AccountMismatch>,
+}
+
+/// The differences between a replayed transaction's receipt and the block's
+/// receipt. Only fields that differ are present.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMismatch {
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The mismatch in status. Only compared for post-Byzantium receipts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Mismatch<bool>>,
+    /// The mismatch in gas used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<Mismatch<u64>>,
+    /// The mismatch in logs bloom.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs_bloom: Option<Mismatch<Bloom>>,
+}
+
+impl TransactionMismatch {
+    /// Whether the replayed receipt matched the block's receipt.
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.gas_used.is_none() && self.logs_bloom.is_none()
+    }
+}
+
+/// The differences between an account's replayed state and the block's
+/// post-state. Only fields that differ are present.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct AccountMismatch {
+    /// The mismatch in balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Mismatch<U256>>,
+    /// The mismatch in nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Mismatch<u64>>,
+    /// The mismatch in code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Mismatch<Bytes>>,
+    /// The mismatches in storage, keyed by storage slot.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, Mismatch<U256>>,
+}
+
+impl AccountMismatch {
+    /// Whether the account's replayed state matched the block's post-state.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// An expected value and the differing value that was produced by a replay.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Mismatch<T> {
+    /// The value recorded by the chain.
+    pub expected: T,
+    /// The value produced by the replay.
+    pub actual: T,
+}
+
+impl<T: PartialEq> Mismatch<T> {
+    /// Constructs a new instance if the values differ.
+    pub fn new(expected: T, actual: T) -> Option<Self> {
+        (expected != actual).then_some(Self { expected, actual })
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+/// The storage layout of a contract, as produced by the `storageLayout`
+/// output of solc.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct StorageLayout {
+    /// The state variables of the contract, in order of declaration.
+    pub storage: Vec<StorageLayoutEntry>,
+    /// The types of the state variables, keyed by type identifier.
+    #[serde(default)]
+    pub types: BTreeMap<String, StorageLayoutType>,
+}
+
+/// A state variable or struct member in a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct StorageLayoutEntry {
+    /// The name of the variable or member.
+    pub label: String,
+    /// The offset in bytes within the storage slot.
+    pub offset: u64,
+    /// The storage slot as a decimal string. For struct members, the slot is
+    /// relative to the slot of the struct.
+    pub slot: String,
+    /// The identifier of the type, which is a key of
+    /// [`StorageLayout::types`].
+    #[serde(rename = "type")]
+    pub type_id: String,
+}
+
+/// A type in a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageLayoutType {
+    /// How the type is encoded in storage.
+    pub encoding: StorageEncoding,
+    /// The canonical name of the type, e.g. `uint256` or `struct
+    /// Vault.Position`.
+    pub label: String,
+    /// The number of bytes used by the type as a decimal string.
+    pub number_of_bytes: String,
+    /// The type identifier of the keys of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The type identifier of the values of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The type identifier of the elements of an array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// The members of a struct.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageLayoutEntry>>,
+}
+
+/// The encoding of a [`StorageLayoutType`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncoding {
+    /// The value is stored contiguously, starting at its slot.
+    Inplace,
+    /// The value of a key is stored at the hash of the key and the slot.
+    Mapping,
+    /// The length is stored at the slot and the elements are stored
+    /// contiguously, starting at the hash of the slot.
+    DynamicArray,
+    /// A `string` or `bytes` value, which is stored at the slot if it's
+    /// shorter than 32 bytes, and otherwise starting at the hash of the slot.
+    Bytes,
+}
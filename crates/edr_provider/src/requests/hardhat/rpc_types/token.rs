@@ -0,0 +1,82 @@
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The standard that a token contract implements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenStandard {
+    Erc20,
+    Erc721,
+}
+
+/// A `Transfer` event of an ERC-20 or ERC-721 token, as returned by
+/// `hardhat_tokenTransfers`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    pub transaction_hash: B256,
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub log_index: u64,
+    /// The address of the token contract.
+    pub token: Address,
+    pub standard: TokenStandard,
+    pub from: Address,
+    pub to: Address,
+    /// The transferred amount of an ERC-20 token, or the ID of an ERC-721
+    /// token.
+    pub value: U256,
+}
+
+/// The filter of `hardhat_tokenTransfers`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransferFilter {
+    /// If present, only transfers of this token contract are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<Address>,
+    /// If present, only transfers of tokens of this standard are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard: Option<TokenStandard>,
+    /// If present, only transfers from or to this address are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The first block to include. Defaults to the earliest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockSpec>,
+    /// The last block to include. Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockSpec>,
+}
+
+impl TokenTransferFilter {
+    /// Whether the transfer matches the filter's token, standard, and
+    /// address.
+    pub fn matches(&self, transfer: &TokenTransfer) -> bool {
+        self.token.is_none_or(|token| token == transfer.token)
+            && self
+                .standard
+                .is_none_or(|standard| standard == transfer.standard)
+            && self
+                .address
+                .is_none_or(|address| address == transfer.from || address == transfer.to)
+    }
+}
+
+/// The balance of a token, as returned by `hardhat_tokenBalances`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance {
+    /// The address of the token contract.
+    pub token: Address,
+    pub standard: TokenStandard,
+    /// The amount of an ERC-20 token, or the number of owned ERC-721 tokens.
+    pub balance: U256,
+    /// The IDs of the owned ERC-721 tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_ids: Option<Vec<U256>>,
+}
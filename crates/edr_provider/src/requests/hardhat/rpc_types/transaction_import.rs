@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use edr_primitives::{Bytes, B256};
+
+/// The raw transactions to add to the mem pool, as provided to
+/// `hardhat_importTransactions`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum TransactionImportSource {
+    /// RLP-encoded, signed transactions.
+    Transactions(Vec<Bytes>),
+    /// The path of a file that contains one hex-encoded, RLP-encoded, signed
+    /// transaction per line. Empty lines are ignored.
+    File(PathBuf),
+}
+
+/// The result of adding a single transaction to the mem pool using
+/// `hardhat_importTransactions`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedTransaction {
+    /// The index of the transaction in the request or, for a file, among the
+    /// file's transactions.
+    pub index: usize,
+    /// The hash of the transaction, if it could be decoded.
+    pub transaction_hash: Option<B256>,
+    /// The reason why the transaction was not added to the mem pool, if it
+    /// was rejected.
+    pub error: Option<String>,
+}
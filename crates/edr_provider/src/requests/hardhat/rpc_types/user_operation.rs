@@ -0,0 +1,34 @@
+use edr_primitives::{Address, Bytes, U128, U256};
+
+/// ERC-4337 user operation in the unpacked EntryPoint v0.7 format, as
+/// provided to `hardhat_validateUserOperation`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The smart contract account that sends the user operation.
+    pub sender: Address,
+    pub nonce: U256,
+    /// The factory that deploys the account, if it doesn't exist yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    /// The call data for the factory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U128,
+    pub verification_gas_limit: U128,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U128,
+    pub max_priority_fee_per_gas: U128,
+    /// The paymaster that sponsors the user operation, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U128>,
+    /// The call data for the paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
@@ -0,0 +1,33 @@
+use edr_primitives::{Address, U256};
+
+use crate::{
+    data::ProviderData, requests::hardhat::rpc_types::AuthorizationRequest, spec::SyncProviderSpec,
+    time::TimeSinceEpoch, ProviderErrorForChainSpec,
+};
+
+pub fn handle_sign_authorization<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    request: AuthorizationRequest,
+) -> Result<edr_eip7702::SignedAuthorization, ProviderErrorForChainSpec<ChainSpecT>> {
+    let chain_id = request
+        .chain_id
+        .unwrap_or_else(|| U256::from(data.chain_id()));
+
+    let nonce = if let Some(nonce) = request.nonce {
+        nonce.to()
+    } else {
+        data.account_next_nonce(&address)?
+    };
+
+    let authorization = edr_eip7702::Authorization {
+        chain_id,
+        address: request.address,
+        nonce,
+    };
+
+    data.sign_authorization(&address, authorization)
+}
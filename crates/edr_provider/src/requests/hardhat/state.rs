@@ -1,10 +1,27 @@
-use edr_primitives::{Address, Bytes, U256};
+use std::collections::BTreeMap;
 
+use edr_block_api::{Block as _, EthBlockData, FetchBlockReceipts as _};
+use edr_chain_spec::{ExecutableTransaction as _, TransactionValidation};
+use edr_chain_spec_rpc::RpcTypeFrom as _;
+use edr_eth::BlockSpec;
+use edr_primitives::{hex, keccak256, Address, Bytes, B256, U256};
+use edr_tracing::Trace;
+use edr_transaction::{IsEip4844, TransactionType};
+use serde::de::IgnoredAny;
+
+use super::rpc_types::{FixtureResult, StateDump, StorageLayout};
 use crate::{
-    data::ProviderData, spec::SyncProviderSpec, time::TimeSinceEpoch, ProviderErrorForChainSpec,
+    chain_export::{ChainExport, ChainImportError, ExportedBlock},
+    checkpoint::{self, CheckpointError},
+    data::ProviderData,
+    requests::eth::{self, block_to_rpc_output},
+    spec::SyncProviderSpec,
+    time::TimeSinceEpoch,
+    ProviderError, ProviderErrorForChainSpec, ProviderResultWithTraces,
 };
 
-use super::rpc_types::StateDump;
+/// The subdirectory of the checkpoint directory in which fixtures are cached.
+const FIXTURE_DIR: &str = "fixtures";
 
 pub fn handle_set_balance<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
     data: &mut ProviderData<ChainSpecT, TimerT>,
@@ -16,6 +33,19 @@ pub fn handle_set_balance<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone +
     Ok(true)
 }
 
+pub fn handle_set_beacon_root<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    timestamp: u64,
+    root: B256,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_beacon_root(timestamp, root)?;
+
+    Ok(true)
+}
+
 pub fn handle_set_code<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
     data: &mut ProviderData<ChainSpecT, TimerT>,
     address: Address,
@@ -50,6 +80,72 @@ pub fn handle_set_storage_at<
     Ok(true)
 }
 
+pub fn handle_set_storage_layout<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    layout: StorageLayout,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_storage_layout(address, layout);
+
+    Ok(true)
+}
+
+pub fn handle_set_storage_variable<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    variable: String,
+    value: serde_json::Value,
+    keys: Vec<String>,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_storage_variable(address, &variable, &keys, &value)?;
+
+    Ok(true)
+}
+
+pub fn handle_read_storage_variable<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    variable: String,
+    keys: Vec<String>,
+    block_spec: Option<BlockSpec>,
+) -> Result<serde_json::Value, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.read_storage_variable(address, &variable, &keys, block_spec.as_ref())
+}
+
+pub fn handle_set_account_storage_root<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    storage_root: Option<B256>,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_account_storage_root(address, storage_root)?;
+
+    Ok(true)
+}
+
+pub fn handle_set_state_root<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    state_root: B256,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.set_next_block_state_root(state_root);
+
+    Ok(true)
+}
+
 pub fn handle_dump_state<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
     data: &mut ProviderData<ChainSpecT, TimerT>,
 ) -> Result<StateDump, ProviderErrorForChainSpec<ChainSpecT>> {
@@ -63,3 +159,320 @@ pub fn handle_load_state<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + T
     data.load_state(state_dump)?;
     Ok(true)
 }
+
+pub fn handle_export_chain<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+) -> Result<Bytes, ProviderErrorForChainSpec<ChainSpecT>> {
+    let base_block_number = base_block_number(data);
+    let base_block = data
+        .block_by_block_spec(&BlockSpec::Number(base_block_number))?
+        .expect("Block numbers cannot be pending");
+
+    let hardfork = data.hardfork();
+    let blocks = (base_block_number + 1..=data.last_block_number())
+        .map(|block_number| {
+            let block = data
+                .block_by_block_spec(&BlockSpec::Number(block_number))?
+                .expect("Block numbers cannot be pending");
+
+            let receipts = block
+                .fetch_transaction_receipts()
+                .map_err(ProviderError::FetchReceipt)?
+                .iter()
+                .map(|receipt| ChainSpecT::RpcReceipt::rpc_type_from(receipt, hardfork))
+                .collect();
+
+            let total_difficulty = data.total_difficulty_by_hash(block.block_hash())?;
+            let block =
+                block_to_rpc_output::<ChainSpecT>(hardfork, block, false, total_difficulty, true)?;
+
+            Ok(ExportedBlock {
+                block,
+                receipts,
+                state_overrides: data.state_overrides_dump(block_number),
+            })
+        })
+        .collect::<Result<Vec<_>, ProviderErrorForChainSpec<ChainSpecT>>>()?;
+
+    let chain = ChainExport {
+        chain_id: data.chain_id(),
+        base_block_number,
+        base_block_hash: *base_block.block_hash(),
+        base_state_overrides: data.state_overrides_dump(base_block_number),
+        blocks,
+        state: data.dump_state()?,
+    };
+
+    chain.encode().map_err(ProviderError::Serialization)
+}
+
+pub fn handle_import_chain<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    chain: Bytes,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    // The receipts are covered by the receipts root of the imported blocks
+    let chain =
+        ChainExport::<ChainSpecT::RpcBlock<ChainSpecT::RpcTransaction>, IgnoredAny>::decode(
+            &chain,
+        )?;
+
+    if chain.chain_id != data.chain_id() {
+        return Err(ChainImportError::ChainIdMismatch {
+            expected: chain.chain_id,
+            actual: data.chain_id(),
+        }
+        .into());
+    }
+
+    let base_block_number = base_block_number(data);
+    let latest_block_number = data.last_block_number();
+    if latest_block_number != base_block_number {
+        return Err(ChainImportError::LocalBlocksExist {
+            base_block_number,
+            latest_block_number,
+        }
+        .into());
+    }
+
+    let base_block_hash = *data
+        .block_by_block_spec(&BlockSpec::Number(base_block_number))?
+        .expect("Block numbers cannot be pending")
+        .block_hash();
+
+    if chain.base_block_number != base_block_number || chain.base_block_hash != base_block_hash {
+        return Err(ChainImportError::BaseBlockMismatch {
+            expected_number: chain.base_block_number,
+            expected_hash: chain.base_block_hash,
+            actual_number: base_block_number,
+            actual_hash: base_block_hash,
+        }
+        .into());
+    }
+
+    let blocks = (chain.base_block_number + 1..)
+        .zip(chain.blocks)
+        .map(|(block_number, exported)| {
+            let block: EthBlockData<ChainSpecT::SignedTransaction> = exported
+                .block
+                .try_into()
+                .map_err(|error| ChainImportError::InvalidBlock {
+                    block_number,
+                    message: error.to_string(),
+                })?;
+
+            Ok((block, exported.state_overrides))
+        })
+        .collect::<Result<Vec<_>, ChainImportError>>()?;
+
+    data.import_chain(chain.base_state_overrides, blocks, chain.state)?;
+
+    Ok(true)
+}
+
+pub fn handle_save_checkpoint<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    name: &str,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let chain = handle_export_chain(data)?;
+    checkpoint::write_checkpoint(data.checkpoint_dir(), name, &chain)?;
+
+    Ok(true)
+}
+
+pub fn handle_load_checkpoint<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    name: &str,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let chain = checkpoint::read_checkpoint(data.checkpoint_dir(), name)?;
+
+    handle_import_chain(data, Bytes::from(chain))
+}
+
+/// Restores the chain that results from mining the provided transactions on
+/// top of the base block from the fixture cache, if available. Otherwise, the
+/// transactions are sent and mined - including any transactions that remain
+/// pending if automining is disabled - and the resulting chain is cached.
+///
+/// A cached fixture that cannot be imported, e.g. because the provider's
+/// configuration results in different blocks, is replaced.
+pub fn handle_run_fixture<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transactions: Vec<ChainSpecT::RpcTransactionRequest>,
+) -> ProviderResultWithTraces<FixtureResult, ChainSpecT> {
+    let base_block_number = base_block_number(data);
+    let latest_block_number = data.last_block_number();
+    if latest_block_number != base_block_number {
+        return Err(ChainImportError::LocalBlocksExist {
+            base_block_number,
+            latest_block_number,
+        }
+        .into());
+    }
+
+    let base_block_hash = *data
+        .block_by_block_spec(&BlockSpec::Number(base_block_number))?
+        .expect("Block numbers cannot be pending")
+        .block_hash();
+
+    let key = {
+        let mut preimage = base_block_hash.to_vec();
+        preimage.extend_from_slice(&data.chain_id().to_be_bytes());
+        preimage.extend_from_slice(<&'static str>::from(data.hardfork()).as_bytes());
+
+        // State overrides of the base block, e.g. using `hardhat_setBalance`, are
+        // sorted to make the key deterministic
+        let base_state_overrides = data
+            .state_overrides_dump(base_block_number)
+            .map(|dump| dump.accounts.into_iter().collect::<BTreeMap<_, _>>())
+            .unwrap_or_default();
+
+        for (address, account) in base_state_overrides {
+            preimage.extend_from_slice(address.as_slice());
+            preimage.extend_from_slice(&account.balance.to_be_bytes::<32>());
+            preimage.extend_from_slice(&account.nonce.to_be_bytes::<32>());
+            preimage.extend_from_slice(keccak256(&account.code).as_slice());
+
+            let storage = account.storage.into_iter().collect::<BTreeMap<_, _>>();
+            for (index, value) in storage {
+                preimage.extend_from_slice(&index.to_be_bytes::<32>());
+                preimage.extend_from_slice(&value.to_be_bytes::<32>());
+            }
+        }
+
+        serde_json::to_writer(&mut preimage, &transactions)
+            .map_err(ProviderError::Serialization)?;
+
+        keccak256(preimage)
+    };
+
+    let fixture_dir = data.checkpoint_dir().join(FIXTURE_DIR);
+    let name = hex::encode(key);
+
+    match checkpoint::read_checkpoint(&fixture_dir, &name) {
+        Ok(chain) => match handle_import_chain(data, Bytes::from(chain)) {
+            Ok(_) => {
+                let result = FixtureResult {
+                    key,
+                    cache_hit: true,
+                    transaction_hashes: local_transaction_hashes(data, base_block_number)?,
+                };
+
+                return Ok((result, Vec::new()));
+            }
+            Err(ProviderError::ChainImport(_)) => (),
+            Err(error) => return Err(error),
+        },
+        Err(CheckpointError::NotFound { .. }) => (),
+        Err(error) => return Err(error.into()),
+    }
+
+    // Don't leave a partially executed fixture behind, including transactions
+    // that remain in the mem pool and balance top-ups of impersonated senders
+    let traces = data.revert_on_error(|data| send_fixture_transactions(data, transactions))?;
+
+    let chain = handle_export_chain(data)?;
+    checkpoint::write_checkpoint(&fixture_dir, &name, &chain)?;
+
+    let result = FixtureResult {
+        key,
+        cache_hit: false,
+        transaction_hashes: local_transaction_hashes(data, base_block_number)?,
+    };
+
+    Ok((result, traces))
+}
+
+/// Sends the provided transactions and mines any that remain pending,
+/// returning the traces of the mined transactions.
+fn send_fixture_transactions<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transactions: Vec<ChainSpecT::RpcTransactionRequest>,
+) -> Result<Vec<Trace<ChainSpecT::HaltReason>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let mut traces = Vec::new();
+    for transaction in transactions {
+        let (_transaction_hash, transaction_traces) =
+            eth::handle_send_transaction_request(data, transaction)?;
+
+        traces.extend(transaction_traces);
+    }
+
+    if data.pending_transactions().next().is_some() {
+        let (_result, mine_traces) = eth::handle_mine_request(data, None)?;
+        traces.extend(mine_traces);
+    }
+
+    Ok(traces)
+}
+
+/// Returns the hashes of the transactions in the blocks after the base block.
+fn local_transaction_hashes<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    base_block_number: u64,
+) -> Result<Vec<B256>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let mut transaction_hashes = Vec::new();
+    for block_number in base_block_number + 1..=data.last_block_number() {
+        let block = data
+            .block_by_block_spec(&BlockSpec::Number(block_number))?
+            .expect("Block numbers cannot be pending");
+
+        transaction_hashes.extend(
+            block
+                .transactions()
+                .iter()
+                .map(|transaction| *transaction.transaction_hash()),
+        );
+    }
+
+    Ok(transaction_hashes)
+}
+
+/// Returns the number of the block on top of which local blocks are mined.
+fn base_block_number<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+) -> u64 {
+    data.fork_metadata()
+        .map_or(0, |fork_metadata| fork_metadata.fork_block_number)
+}
@@ -0,0 +1,134 @@
+use edr_block_api::Block as _;
+use edr_chain_l1::rpc::call::L1CallRequest;
+use edr_chain_spec::TransactionValidation;
+use edr_chain_spec_evm::result::ExecutionResult;
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, HashMap, U256};
+use edr_runtime::overrides::StateOverrides;
+
+use super::rpc_types::{TokenBalance, TokenTransfer, TokenTransferFilter};
+use crate::{
+    data::ProviderData, error::ProviderErrorForChainSpec, requests::eth::resolve_call_request,
+    spec::SyncProviderSpec, time::TimeSinceEpoch,
+};
+
+/// The selector of `balanceOf(address)`.
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+pub fn handle_token_balances<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    block_spec: Option<BlockSpec>,
+) -> Result<Vec<TokenBalance>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block_number = block_number(data, block_spec.as_ref())?;
+    let holdings = data.token_holdings(&address, block_number)?;
+
+    // Transfers before the first local block aren't indexed, so the ERC-20
+    // balances of the fork or genesis block are used as a starting point.
+    let first_block_number = data
+        .fork_metadata()
+        .map_or(0, |fork_metadata| fork_metadata.fork_block_number);
+
+    let initial_erc20_balances = holdings
+        .erc20_tokens()
+        .map(|token| {
+            let balance = erc20_balance(data, *token, address, first_block_number)?;
+
+            Ok((*token, balance))
+        })
+        .collect::<Result<HashMap<_, _>, ProviderErrorForChainSpec<ChainSpecT>>>()?;
+
+    Ok(holdings.into_balances(&initial_erc20_balances))
+}
+
+pub fn handle_token_transfers<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    filter: Option<TokenTransferFilter>,
+) -> Result<Vec<TokenTransfer>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let filter = filter.unwrap_or_default();
+
+    let from_block = match filter.from_block.as_ref() {
+        Some(block_spec) => block_number(data, Some(block_spec))?,
+        None => 0,
+    };
+    let to_block = block_number(data, filter.to_block.as_ref())?;
+
+    let transfers = data
+        .token_transfers(to_block)?
+        .iter()
+        .filter(|transfer| transfer.block_number >= from_block && filter.matches(transfer))
+        .cloned()
+        .collect();
+
+    Ok(transfers)
+}
+
+/// Resolves the block number of a block spec, defaulting to the latest block.
+///
+/// The pending block's transfers aren't indexed, so it resolves to the latest
+/// block.
+fn block_number<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    block_spec: Option<&BlockSpec>,
+) -> Result<u64, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block = match block_spec {
+        Some(block_spec) => data.block_by_block_spec(block_spec)?,
+        None => None,
+    };
+
+    Ok(block.map_or_else(
+        || data.last_block_number(),
+        |block| block.block_header().number,
+    ))
+}
+
+/// Calls `balanceOf(owner)` of an ERC-20 token at the provided block number.
+///
+/// Tokens that don't exist at the block or don't return a balance are
+/// considered to have a zero balance.
+fn erc20_balance<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    token: Address,
+    owner: Address,
+    block_number: u64,
+) -> Result<U256, ProviderErrorForChainSpec<ChainSpecT>> {
+    let mut input = BALANCE_OF_SELECTOR.to_vec();
+    input.extend_from_slice(owner.into_word().as_slice());
+
+    let request = L1CallRequest {
+        to: Some(token),
+        data: Some(Bytes::from(input)),
+        ..L1CallRequest::default()
+    };
+
+    let block_spec = BlockSpec::Number(block_number);
+    let state_overrides = StateOverrides::default();
+
+    let transaction = resolve_call_request(data, request.into(), &block_spec, &state_overrides)?;
+    let result = data.run_call(transaction, &block_spec, &state_overrides)?;
+
+    let balance = match result.execution_result {
+        ExecutionResult::Success { output, .. } => output
+            .data()
+            .get(..32)
+            .map_or(U256::ZERO, U256::from_be_slice),
+        ExecutionResult::Revert { .. } | ExecutionResult::Halt { .. } => U256::ZERO,
+    };
+
+    Ok(balance)
+}
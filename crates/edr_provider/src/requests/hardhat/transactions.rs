@@ -1,10 +1,61 @@
-use edr_primitives::B256;
+use std::{collections::BTreeMap, str::FromStr as _, time::Instant};
+
+use edr_chain_l1::rpc::call::L1CallRequest;
+use edr_chain_spec::{ExecutableTransaction as _, TransactionValidation};
+use edr_chain_spec_evm::result::ExecutionResult;
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_runtime::overrides::StateOverrides;
+use edr_transaction::{
+    request::TransactionRequestAndSender, IsEip155, IsEip4844, TransactionMut, TransactionType,
+};
 
 use crate::{
-    data::ProviderData, error::ProviderErrorForChainSpec, spec::SyncProviderSpec,
-    time::TimeSinceEpoch, ProviderError,
+    data::ProviderData,
+    erc4337::{decode_failed_op, encode_handle_ops, UserOperationEntities},
+    error::ProviderErrorForChainSpec,
+    replay::ReplayedTransactionWithTraces,
+    requests::{
+        eth::{
+            decode_raw_transaction, handle_estimate_gas, resolve_call_request,
+            send_raw_transaction_and_log, validate_raw_transaction,
+        },
+        hardhat::rpc_types::{
+            AccountDiff, FloodOptions, FloodResult, GasProfile, ImportedTransaction, ReplayedBlock,
+            ReplayedTransaction, TransactionImportSource, UserOperation,
+        },
+    },
+    spec::{CallContext, FromRpcType as _, SyncProviderSpec},
+    time::TimeSinceEpoch,
+    ProviderError, ProviderResultWithTraces,
 };
 
+/// Removes all transactions from the mem pool.
+pub fn handle_drop_all_transactions<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+    data.remove_all_pending_transactions()?;
+
+    Ok(())
+}
+
+/// Removes a transaction from the mem pool, returning its hash if it was
+/// removed. Like Anvil, mined and unknown transactions return `None`.
+pub fn handle_anvil_drop_transaction<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Option<B256>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let removed_transaction = data.remove_pending_transaction(&transaction_hash)?;
+
+    Ok(removed_transaction.map(|_transaction| transaction_hash))
+}
+
 pub fn handle_drop_transaction<
     ChainSpecT: SyncProviderSpec<TimerT>,
     TimerT: Clone + TimeSinceEpoch,
@@ -12,7 +63,9 @@ pub fn handle_drop_transaction<
     data: &mut ProviderData<ChainSpecT, TimerT>,
     transaction_hash: B256,
 ) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
-    let was_removed = data.remove_pending_transaction(&transaction_hash).is_some();
+    let was_removed = data
+        .remove_pending_transaction(&transaction_hash)?
+        .is_some();
     if was_removed {
         return Ok(true);
     }
@@ -24,3 +77,351 @@ pub fn handle_drop_transaction<
         Ok(false)
     }
 }
+
+/// Enqueues an RLP-encoded deposit transaction to be force-included at the top
+/// of the next block, simulating an L1 to L2 deposit.
+pub fn handle_enqueue_deposit<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    raw_transaction: Bytes,
+) -> Result<B256, ProviderErrorForChainSpec<ChainSpecT>> {
+    let pooled_transaction = decode_raw_transaction::<ChainSpecT, TimerT>(&raw_transaction)?;
+
+    data.enqueue_deposit(pooled_transaction.into())
+}
+
+/// Creates and mines `count` synthetic transactions, without serializing
+/// them individually. Transactions are added to the mem pool in batches that
+/// fit in a single block, after which the pending transactions are mined.
+///
+/// Mined blocks are not logged and their traces are not collected.
+pub fn handle_flood<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionMut
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    options: FloodOptions,
+) -> Result<FloodResult, ProviderErrorForChainSpec<ChainSpecT>> {
+    let FloodOptions {
+        count,
+        from,
+        to,
+        value,
+        data: input,
+        gas,
+    } = options;
+
+    let count: u64 = count.to();
+    let sender = from.unwrap_or_else(|| data.default_caller());
+    let template = L1CallRequest {
+        from: Some(sender),
+        to,
+        value,
+        data: input,
+        ..L1CallRequest::default()
+    };
+
+    // Reserving the maximum gas for each transaction would result in one
+    // transaction per block, so the gas limit is estimated once instead.
+    let gas_limit: u64 = if let Some(gas) = gas {
+        gas.to()
+    } else {
+        let (estimation, _traces) = handle_estimate_gas(data, template.clone().into(), None)?;
+        estimation.to()
+    };
+
+    // The pending block spec ensures that nonces account for the transactions
+    // that were already added to the mem pool.
+    let block_spec = BlockSpec::pending();
+    let state_overrides = StateOverrides::default();
+
+    let mut result = FloodResult {
+        transactions: 0,
+        failed: 0,
+        blocks: 0,
+        gas_used: 0,
+        duration_ms: 0.0,
+        transactions_per_second: 0.0,
+        gas_per_second: 0.0,
+    };
+
+    let start = Instant::now();
+
+    let mut remaining = count;
+    while remaining > 0 {
+        let batch_size = remaining.min((data.block_gas_limit() / gas_limit.max(1)).max(1));
+        for index in 0..batch_size {
+            let request = L1CallRequest {
+                gas: Some(gas_limit),
+                ..template.clone()
+            };
+
+            let context = CallContext {
+                data,
+                block_spec: &block_spec,
+                state_overrides: &state_overrides,
+                default_gas_price_fn: |data| Ok(data.next_gas_price()?),
+                max_fees_fn: |data, _block_spec, _max_fee_per_gas, _max_priority_fee_per_gas| {
+                    const MAX_PRIORITY_FEE_PER_GAS: u128 = 1_000_000_000;
+
+                    // Leave room for the base fee to increase while the
+                    // batch is mined
+                    let base_fee_per_gas = data.next_block_base_fee_per_gas()?.unwrap_or(0);
+                    Ok((
+                        2 * base_fee_per_gas + MAX_PRIORITY_FEE_PER_GAS,
+                        MAX_PRIORITY_FEE_PER_GAS,
+                    ))
+                },
+            };
+
+            let request = ChainSpecT::TransactionRequest::from_rpc_type(request.into(), context)?;
+            let transaction =
+                data.sign_transaction_request(TransactionRequestAndSender { request, sender })?;
+
+            // All transactions of a batch have the same upfront cost, so the
+            // sender is topped up once per block.
+            if index == 0 {
+                let batch_cost = edr_transaction::upfront_cost(&transaction)
+                    .saturating_mul(U256::from(batch_size));
+
+                data.top_up_impersonated_account(sender, batch_cost)?;
+            }
+
+            data.add_pending_transaction(transaction)?;
+        }
+
+        remaining -= batch_size;
+
+        for mining_result in data.mine_pending_transactions()? {
+            result.blocks += 1;
+
+            for transaction_result in mining_result.transaction_results {
+                result.transactions += 1;
+                result.gas_used += transaction_result.gas_used();
+
+                if !transaction_result.is_success() {
+                    result.failed += 1;
+                }
+            }
+        }
+    }
+
+    let duration = start.elapsed().as_secs_f64();
+    result.duration_ms = duration * 1000.0;
+    if duration > 0.0 {
+        result.transactions_per_second = result.transactions as f64 / duration;
+        result.gas_per_second = result.gas_used as f64 / duration;
+    }
+
+    Ok(result)
+}
+
+pub fn handle_get_gas_profile_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<GasProfile, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.gas_profile_transaction(&transaction_hash)
+}
+
+pub fn handle_get_state_diff_request<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Option<BTreeMap<Address, AccountDiff>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.state_diff(&transaction_hash)
+}
+
+/// Returns the tags that the transaction firewall assigned to the transaction
+/// with the provided hash.
+pub fn handle_get_transaction_tags<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Vec<String>, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(data.transaction_tags(&transaction_hash).to_vec())
+}
+
+/// Validates raw, signed transactions and adds them to the mem pool, without
+/// mining them. Transactions are processed in order and an invalid transaction
+/// doesn't prevent the remaining ones from being added.
+pub fn handle_import_transactions<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default
+                               + TransactionType<Type: IsEip4844>
+                               + TransactionValidation<ValidationError: PartialEq>,
+        PooledTransaction: IsEip155,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    source: TransactionImportSource,
+) -> Result<Vec<ImportedTransaction>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let raw_transactions = match source {
+        TransactionImportSource::Transactions(raw_transactions) => raw_transactions,
+        TransactionImportSource::File(path) => {
+            let contents = std::fs::read_to_string(&path).map_err(|error| {
+                ProviderError::InvalidInput(format!(
+                    "Failed to read transactions from '{}': {error}",
+                    path.display()
+                ))
+            })?;
+
+            contents
+                .lines()
+                .enumerate()
+                .map(|(index, line)| (index, line.trim()))
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(index, line)| {
+                    Bytes::from_str(line).map_err(|error| {
+                        ProviderError::InvalidInput(format!(
+                            "Invalid transaction on line {} of '{}': {error}",
+                            index + 1,
+                            path.display()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+
+    // Imported transactions are only added to the mem pool, so automining is
+    // disabled while they're sent.
+    let is_auto_mining = data.is_auto_mining();
+    data.set_auto_mining(false);
+
+    let mut results = Vec::with_capacity(raw_transactions.len());
+    for (index, raw_transaction) in raw_transactions.into_iter().enumerate() {
+        let pooled_transaction =
+            match decode_raw_transaction::<ChainSpecT, TimerT>(&raw_transaction) {
+                Ok(pooled_transaction) => pooled_transaction,
+                Err(error) => {
+                    results.push(ImportedTransaction {
+                        index,
+                        transaction_hash: None,
+                        error: Some(error.to_string()),
+                    });
+                    continue;
+                }
+            };
+
+        let transaction_hash = *pooled_transaction.transaction_hash();
+        let error = validate_raw_transaction(data, pooled_transaction)
+            .and_then(|(signed_transaction, blob_sidecar)| {
+                data.sponsor_transaction(&signed_transaction)?;
+                send_raw_transaction_and_log(data, signed_transaction, blob_sidecar, None)
+            })
+            .err()
+            .map(|error| error.to_string());
+
+        results.push(ImportedTransaction {
+            index,
+            transaction_hash: Some(transaction_hash),
+            error,
+        });
+    }
+
+    data.set_auto_mining(is_auto_mining);
+
+    Ok(results)
+}
+
+pub fn handle_replay_block_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    block_number: u64,
+) -> Result<ReplayedBlock, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.replay_block(block_number)
+}
+
+pub fn handle_replay_transaction_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> ProviderResultWithTraces<ReplayedTransaction, ChainSpecT> {
+    let ReplayedTransactionWithTraces { result, traces } = data
+        .replay_transaction(&transaction_hash)
+        .map_err(|error| match error {
+            ProviderError::InvalidTransactionHash(tx_hash) => ProviderError::InvalidInput(format!(
+                "Unable to find a block containing transaction {tx_hash}"
+            )),
+            _ => error,
+        })?;
+
+    Ok((result, traces))
+}
+
+pub fn handle_validate_user_operation_request<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    user_operation: UserOperation,
+    entry_point: Address,
+) -> Result<bool, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block_spec = BlockSpec::latest();
+
+    let request = L1CallRequest {
+        to: Some(entry_point),
+        data: Some(encode_handle_ops(&user_operation, data.default_caller())),
+        ..L1CallRequest::default()
+    };
+    let transaction = resolve_call_request(
+        data,
+        request.into(),
+        &block_spec,
+        &StateOverrides::default(),
+    )?;
+
+    let (result, violations) = data.validate_user_operation(
+        transaction,
+        &block_spec,
+        UserOperationEntities::new(&user_operation, entry_point),
+    )?;
+
+    if !violations.is_empty() {
+        return Err(ProviderError::UserOperationRuleViolations { violations });
+    }
+
+    match result {
+        ExecutionResult::Success { .. } => Ok(true),
+        ExecutionResult::Revert { output, .. } => Err(ProviderError::UserOperationRejected {
+            reason: decode_failed_op(&output)
+                .unwrap_or_else(|| format!("EntryPoint reverted with {output}")),
+        }),
+        ExecutionResult::Halt { reason, .. } => Err(ProviderError::UserOperationRejected {
+            reason: format!("EntryPoint halted with {reason:?}"),
+        }),
+    }
+}
@@ -1,5 +1,6 @@
 use alloy_dyn_abi::eip712::TypedData;
 use derive_where::derive_where;
+use edr_block_header::Withdrawal;
 use edr_chain_spec_rpc::RpcChainSpec;
 use edr_eth::{
     filter::{LogFilterOptions, SubscriptionType},
@@ -8,10 +9,21 @@ use edr_eth::{
 };
 use edr_primitives::{Address, Bytes, B256, U128, U256, U64};
 use edr_rpc_eth::StateOverrideOptions;
+use edr_solidity::external_artifact::ExternalArtifact;
 use serde::{Deserialize, Serialize};
 
 use super::serde::{RpcAddress, Timestamp};
-use crate::requests::debug::DebugTraceConfig;
+use crate::requests::{
+    debug::DebugTraceConfig,
+    engine::{ExecutionPayloadV3, ForkchoiceState, PayloadAttributesV3},
+    explorer::AddressTransactionsOptions,
+    hardhat::rpc_types::{
+        AuthorizationRequest, BundleTransactionRequest, CallBundleRequest, FeeChecksConfig,
+        FloodOptions, InclusionPolicy, LoggingOptions, MempoolConfig, NextBlockHeaderOverrides,
+        ReorgOptions, StorageLayout, TokenTransferFilter, TransactionImportSource, UserOperation,
+    },
+    trace::TraceFilter,
+};
 
 mod optional_block_spec {
     use super::BlockSpec;
@@ -50,6 +62,9 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         Option<BlockSpec>,
         #[serde(default, skip_serializing_if = "Option::is_none")] Option<StateOverrideOptions>,
     ),
+    /// `eth_callBundle`
+    #[serde(rename = "eth_callBundle", with = "edr_eth::serde::sequence")]
+    CallBundle(CallBundleRequest),
     /// `eth_chainId`
     #[serde(rename = "eth_chainId", with = "edr_eth::serde::empty_params")]
     ChainId(()),
@@ -82,6 +97,9 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         /// reward percentiles
         Vec<f64>,
     ),
+    /// `eth_fillTransaction`
+    #[serde(rename = "eth_fillTransaction", with = "edr_eth::serde::sequence")]
+    FillTransaction(ChainSpecT::RpcTransactionRequest),
     /// `eth_gasPrice`
     #[serde(rename = "eth_gasPrice", with = "edr_eth::serde::empty_params")]
     GasPrice(()),
@@ -95,6 +113,9 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         )]
         Option<BlockSpec>,
     ),
+    /// `eth_getBlobSidecars`
+    #[serde(rename = "eth_getBlobSidecars", with = "edr_eth::serde::sequence")]
+    GetBlobSidecars(B256),
     /// `eth_getBlockByNumber`
     #[serde(rename = "eth_getBlockByNumber")]
     GetBlockByNumber(
@@ -110,6 +131,9 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         /// include transaction data
         bool,
     ),
+    /// `eth_getBlockReceipts`
+    #[serde(rename = "eth_getBlockReceipts", with = "edr_eth::serde::sequence")]
+    GetBlockReceipts(BlockSpec),
     /// `eth_getBlockTransactionCountByHash`
     #[serde(
         rename = "eth_getBlockTransactionCountByHash",
@@ -212,12 +236,36 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
     /// `eth_sendTransaction`
     #[serde(rename = "eth_sendTransaction", with = "edr_eth::serde::sequence")]
     SendTransaction(ChainSpecT::RpcTransactionRequest),
+    /// `personal_importRawKey`
+    #[serde(rename = "personal_importRawKey")]
+    PersonalImportRawKey(
+        /// secret key:
+        B256,
+        /// passphrase:
+        String,
+    ),
+    /// `personal_newAccount`
+    #[serde(rename = "personal_newAccount", with = "edr_eth::serde::sequence")]
+    PersonalNewAccount(String),
     /// `personal_sign`
     #[serde(rename = "personal_sign")]
     PersonalSign(
         Bytes,
         #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
     ),
+    /// `personal_unlockAccount`
+    #[serde(rename = "personal_unlockAccount")]
+    PersonalUnlockAccount(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// passphrase:
+        String,
+        /// duration in seconds:
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        Option<u64>,
+    ),
+    /// `eth_signTransaction`
+    #[serde(rename = "eth_signTransaction", with = "edr_eth::serde::sequence")]
+    SignTransaction(ChainSpecT::RpcTransactionRequest),
     /// `eth_signTypedData_v4`
     #[serde(rename = "eth_signTypedData_v4")]
     SignTypedDataV4(
@@ -258,6 +306,12 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
     /// `evm_revert`
     #[serde(rename = "evm_revert", with = "edr_eth::serde::sequence")]
     EvmRevert(U64),
+    /// `evm_setAccountStorageRoot`
+    #[serde(rename = "evm_setAccountStorageRoot")]
+    EvmSetAccountStorageRoot(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        #[serde(default)] Option<B256>,
+    ),
     /// `evm_setAutomine`
     #[serde(rename = "evm_setAutomine", with = "edr_eth::serde::sequence")]
     EvmSetAutomine(bool),
@@ -273,10 +327,20 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         with = "edr_eth::serde::sequence"
     )]
     EvmSetNextBlockTimestamp(Timestamp),
+    /// `evm_setTime`
+    #[serde(rename = "evm_setTime", with = "edr_eth::serde::sequence")]
+    EvmSetTime(Timestamp),
     /// `evm_snapshot`
     #[serde(rename = "evm_snapshot", with = "edr_eth::serde::empty_params")]
     EvmSnapshot(()),
 
+    /// `admin_importChain`
+    #[serde(rename = "admin_importChain", with = "edr_eth::serde::sequence")]
+    AdminImportChain(Bytes),
+
+    /// `debug_setHead`
+    #[serde(rename = "debug_setHead", with = "edr_eth::serde::sequence")]
+    DebugSetHead(U64),
     // `debug_traceTransaction`
     #[serde(rename = "debug_traceCall")]
     DebugTraceCall(
@@ -288,18 +352,178 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
     #[serde(rename = "debug_traceTransaction")]
     DebugTraceTransaction(B256, #[serde(default)] Option<DebugTraceConfig>),
 
+    /// `engine_forkchoiceUpdatedV3`
+    #[serde(rename = "engine_forkchoiceUpdatedV3")]
+    EngineForkchoiceUpdatedV3(
+        ForkchoiceState,
+        #[serde(default, skip_serializing_if = "Option::is_none")] Option<PayloadAttributesV3>,
+    ),
+    /// `engine_newPayloadV3`
+    #[serde(rename = "engine_newPayloadV3")]
+    EngineNewPayloadV3(
+        ExecutionPayloadV3,
+        /// expected blob versioned hashes:
+        Vec<B256>,
+        /// parent beacon block root:
+        B256,
+    ),
+
+    /// `explorer_getAddressTransactions`
+    #[serde(rename = "explorer_getAddressTransactions")]
+    ExplorerGetAddressTransactions(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// options:
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        Option<AddressTransactionsOptions>,
+    ),
+
+    /// `ots_getApiLevel`
+    #[serde(rename = "ots_getApiLevel", with = "edr_eth::serde::empty_params")]
+    OtsGetApiLevel(()),
+    /// `ots_getBlockDetails`
+    #[serde(rename = "ots_getBlockDetails", with = "edr_eth::serde::sequence")]
+    OtsGetBlockDetails(u64),
+    /// `ots_getTransactionError`
+    #[serde(rename = "ots_getTransactionError", with = "edr_eth::serde::sequence")]
+    OtsGetTransactionError(B256),
+    /// `ots_searchTransactionsAfter`
+    #[serde(rename = "ots_searchTransactionsAfter")]
+    OtsSearchTransactionsAfter(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// block number:
+        u64,
+        /// page size:
+        u64,
+    ),
+    /// `ots_searchTransactionsBefore`
+    #[serde(rename = "ots_searchTransactionsBefore")]
+    OtsSearchTransactionsBefore(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// block number:
+        u64,
+        /// page size:
+        u64,
+    ),
+    /// `ots_traceTransaction`
+    #[serde(rename = "ots_traceTransaction", with = "edr_eth::serde::sequence")]
+    OtsTraceTransaction(B256),
+
+    /// `trace_block`
+    #[serde(rename = "trace_block", with = "edr_eth::serde::sequence")]
+    TraceBlock(BlockSpec),
+    /// `trace_filter`
+    #[serde(rename = "trace_filter", with = "edr_eth::serde::sequence")]
+    TraceFilter(TraceFilter),
+    /// `trace_transaction`
+    #[serde(rename = "trace_transaction", with = "edr_eth::serde::sequence")]
+    TraceTransaction(B256),
+
+    /// `txpool_content`
+    #[serde(rename = "txpool_content", with = "edr_eth::serde::empty_params")]
+    TxpoolContent(()),
+    /// `txpool_inspect`
+    #[serde(rename = "txpool_inspect", with = "edr_eth::serde::empty_params")]
+    TxpoolInspect(()),
+    /// `txpool_status`
+    #[serde(rename = "txpool_status", with = "edr_eth::serde::empty_params")]
+    TxpoolStatus(()),
+
+    /// `anvil_dropAllTransactions`
+    #[serde(
+        rename = "anvil_dropAllTransactions",
+        with = "edr_eth::serde::empty_params"
+    )]
+    AnvilDropAllTransactions(()),
+    /// `anvil_dropTransaction`
+    #[serde(rename = "anvil_dropTransaction", with = "edr_eth::serde::sequence")]
+    AnvilDropTransaction(B256),
+    /// `anvil_mine`
+    #[serde(rename = "anvil_mine")]
+    AnvilMine(
+        /// block count:
+        #[serde(default, with = "alloy_serde::quantity::opt")]
+        Option<u64>,
+        /// interval:
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )]
+        Option<u64>,
+    ),
+    /// `anvil_nodeInfo`
+    #[serde(rename = "anvil_nodeInfo", with = "edr_eth::serde::empty_params")]
+    AnvilNodeInfo(()),
+    /// `anvil_setLoggingEnabled`
+    #[serde(rename = "anvil_setLoggingEnabled", with = "edr_eth::serde::sequence")]
+    AnvilSetLoggingEnabled(bool),
+    /// `anvil_setNextBlockBaseFeePerGas`
+    #[serde(
+        rename = "anvil_setNextBlockBaseFeePerGas",
+        with = "edr_eth::serde::sequence"
+    )]
+    AnvilSetNextBlockBaseFeePerGas(U128),
+
+    /// `hardhat_addContractArtifact`
+    #[serde(
+        rename = "hardhat_addContractArtifact",
+        with = "edr_eth::serde::sequence"
+    )]
+    AddContractArtifact(ExternalArtifact),
+    /// `hardhat_autoImpersonateAccount`
+    #[serde(
+        rename = "hardhat_autoImpersonateAccount",
+        with = "edr_eth::serde::sequence"
+    )]
+    AutoImpersonateAccount(bool),
     /// `hardhat_dropTransaction`
     #[serde(rename = "hardhat_dropTransaction", with = "edr_eth::serde::sequence")]
     DropTransaction(B256),
+    /// `hardhat_enqueueDeposit`
+    #[serde(rename = "hardhat_enqueueDeposit", with = "edr_eth::serde::sequence")]
+    EnqueueDeposit(Bytes),
+    /// `hardhat_exportChain`
+    #[serde(rename = "hardhat_exportChain", with = "edr_eth::serde::empty_params")]
+    ExportChain(()),
+    /// `hardhat_flood`
+    #[serde(rename = "hardhat_flood", with = "edr_eth::serde::sequence")]
+    Flood(FloodOptions),
     /// `hardhat_getAutomine`
     #[serde(rename = "hardhat_getAutomine", with = "edr_eth::serde::empty_params")]
     GetAutomine(()),
+    /// `hardhat_getGasProfile`
+    #[serde(rename = "hardhat_getGasProfile", with = "edr_eth::serde::sequence")]
+    GetGasProfile(B256),
+    /// `hardhat_getNextNonce`
+    #[serde(rename = "hardhat_getNextNonce", with = "edr_eth::serde::sequence")]
+    GetNextNonce(RpcAddress),
+    /// `hardhat_getStateDiff`
+    #[serde(rename = "hardhat_getStateDiff", with = "edr_eth::serde::sequence")]
+    GetStateDiff(B256),
+    /// `hardhat_getTransactionTags`
+    #[serde(
+        rename = "hardhat_getTransactionTags",
+        with = "edr_eth::serde::sequence"
+    )]
+    GetTransactionTags(B256),
     /// `hardhat_impersonateAccount`
     #[serde(
         rename = "hardhat_impersonateAccount",
         with = "edr_eth::serde::sequence"
     )]
     ImpersonateAccount(RpcAddress),
+    /// `hardhat_importChain`
+    #[serde(rename = "hardhat_importChain", with = "edr_eth::serde::sequence")]
+    ImportChain(Bytes),
+    /// `hardhat_importTransactions`
+    #[serde(
+        rename = "hardhat_importTransactions",
+        with = "edr_eth::serde::sequence"
+    )]
+    ImportTransactions(TransactionImportSource),
+    /// `hardhat_loadCheckpoint`
+    #[serde(rename = "hardhat_loadCheckpoint", with = "edr_eth::serde::sequence")]
+    LoadCheckpoint(String),
     /// `hardhat_metadata`
     #[serde(rename = "hardhat_metadata", with = "edr_eth::serde::empty_params")]
     Metadata(()),
@@ -317,12 +541,110 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         )]
         Option<u64>,
     ),
+    /// `hardhat_pauseIntervalMining`
+    #[serde(
+        rename = "hardhat_pauseIntervalMining",
+        with = "edr_eth::serde::empty_params"
+    )]
+    PauseIntervalMining(()),
+    /// `hardhat_predictCreate2Address`
+    #[serde(rename = "hardhat_predictCreate2Address")]
+    PredictCreate2Address(
+        /// deployer:
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")]
+        Address,
+        /// salt:
+        B256,
+        /// init code:
+        Bytes,
+    ),
+    /// `hardhat_predictCreateAddress`
+    #[serde(rename = "hardhat_predictCreateAddress")]
+    PredictCreateAddress(
+        /// deployer:
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")]
+        Address,
+        /// nonce:
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )]
+        Option<u64>,
+    ),
+    /// `hardhat_readStorageVariable`
+    #[serde(rename = "hardhat_readStorageVariable")]
+    ReadStorageVariable(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// variable:
+        String,
+        /// keys:
+        #[serde(default)]
+        Vec<String>,
+        #[serde(
+            skip_serializing_if = "Option::is_none",
+            default = "optional_block_spec::latest"
+        )]
+        Option<BlockSpec>,
+    ),
+    /// `hardhat_reorg`
+    #[serde(rename = "hardhat_reorg")]
+    Reorg(
+        /// depth:
+        #[serde(with = "alloy_serde::quantity")]
+        u64,
+        /// options:
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        Option<ReorgOptions>,
+    ),
+    /// `hardhat_replayBlock`
+    #[serde(rename = "hardhat_replayBlock", with = "edr_eth::serde::sequence")]
+    ReplayBlock(U64),
+    /// `hardhat_replayTransaction`
+    #[serde(
+        rename = "hardhat_replayTransaction",
+        with = "edr_eth::serde::sequence"
+    )]
+    ReplayTransaction(B256),
+    /// `hardhat_resumeIntervalMining`
+    #[serde(
+        rename = "hardhat_resumeIntervalMining",
+        with = "edr_eth::serde::empty_params"
+    )]
+    ResumeIntervalMining(()),
+    /// `hardhat_runFixture`
+    #[serde(rename = "hardhat_runFixture", with = "edr_eth::serde::sequence")]
+    RunFixture(Vec<ChainSpecT::RpcTransactionRequest>),
+    /// `hardhat_saveCheckpoint`
+    #[serde(rename = "hardhat_saveCheckpoint", with = "edr_eth::serde::sequence")]
+    SaveCheckpoint(String),
+    /// `hardhat_setAllowUnlimitedContractSize`
+    #[serde(
+        rename = "hardhat_setAllowUnlimitedContractSize",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetAllowUnlimitedContractSize(bool),
+    /// `hardhat_setAllowUnlimitedInitCodeSize`
+    #[serde(
+        rename = "hardhat_setAllowUnlimitedInitCodeSize",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetAllowUnlimitedInitCodeSize(bool),
     /// `hardhat_setBalance`
     #[serde(rename = "hardhat_setBalance")]
     SetBalance(
         #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
         #[serde(deserialize_with = "crate::requests::serde::deserialize_quantity")] U256,
     ),
+    /// `hardhat_setBeaconRoot`
+    #[serde(rename = "hardhat_setBeaconRoot")]
+    SetBeaconRoot(
+        /// timestamp:
+        #[serde(with = "alloy_serde::quantity")]
+        u64,
+        /// root:
+        B256,
+    ),
     /// `hardhat_setCode`
     #[serde(rename = "hardhat_setCode")]
     SetCode(
@@ -332,12 +654,33 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
     /// `hardhat_setCoinbase`
     #[serde(rename = "hardhat_setCoinbase", with = "edr_eth::serde::sequence")]
     SetCoinbase(#[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address),
+    /// `hardhat_setFeeChecks`
+    #[serde(rename = "hardhat_setFeeChecks", with = "edr_eth::serde::sequence")]
+    SetFeeChecks(FeeChecksConfig),
+    /// `hardhat_setInclusionPolicy`
+    #[serde(
+        rename = "hardhat_setInclusionPolicy",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetInclusionPolicy(InclusionPolicy),
+    /// `hardhat_setLogFilter`
+    #[serde(rename = "hardhat_setLogFilter", with = "edr_eth::serde::sequence")]
+    SetLogFilter(String),
     /// `hardhat_setLoggingEnabled`
     #[serde(
         rename = "hardhat_setLoggingEnabled",
         with = "edr_eth::serde::sequence"
     )]
     SetLoggingEnabled(bool),
+    /// `hardhat_setLoggingOptions`
+    #[serde(
+        rename = "hardhat_setLoggingOptions",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetLoggingOptions(LoggingOptions),
+    /// `hardhat_setMempoolConfig`
+    #[serde(rename = "hardhat_setMempoolConfig", with = "edr_eth::serde::sequence")]
+    SetMempoolConfig(MempoolConfig),
     /// `hardhat_setMinGasPrice`
     #[serde(rename = "hardhat_setMinGasPrice", with = "edr_eth::serde::sequence")]
     SetMinGasPrice(U128),
@@ -347,6 +690,18 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         with = "edr_eth::serde::sequence"
     )]
     SetNextBlockBaseFeePerGas(U128),
+    /// `hardhat_setNextBlockHeaderOverrides`
+    #[serde(
+        rename = "hardhat_setNextBlockHeaderOverrides",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetNextBlockHeaderOverrides(NextBlockHeaderOverrides),
+    /// `hardhat_setNextBlockWithdrawals`
+    #[serde(
+        rename = "hardhat_setNextBlockWithdrawals",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetNextBlockWithdrawals(Vec<Withdrawal>),
     /// `hardhat_setNonce`
     #[serde(rename = "hardhat_setNonce")]
     SetNonce(
@@ -360,6 +715,15 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
     /// `hardhat_setPrevRandao`
     #[serde(rename = "hardhat_setPrevRandao", with = "edr_eth::serde::sequence")]
     SetPrevRandao(B256),
+    /// `hardhat_setPrevRandaoSeed`
+    #[serde(
+        rename = "hardhat_setPrevRandaoSeed",
+        with = "edr_eth::serde::sequence"
+    )]
+    SetPrevRandaoSeed(String),
+    /// `hardhat_setStateRoot`
+    #[serde(rename = "hardhat_setStateRoot", with = "edr_eth::serde::sequence")]
+    SetStateRoot(B256),
     /// `hardhat_setStorageAt`
     #[serde(rename = "hardhat_setStorageAt")]
     SetStorageAt(
@@ -367,12 +731,78 @@ pub enum MethodInvocation<ChainSpecT: RpcChainSpec> {
         #[serde(deserialize_with = "crate::requests::serde::deserialize_storage_key")] U256,
         #[serde(with = "crate::requests::serde::storage_value")] U256,
     ),
+    /// `hardhat_setStorageLayout`
+    #[serde(rename = "hardhat_setStorageLayout")]
+    SetStorageLayout(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        StorageLayout,
+    ),
+    /// `hardhat_setStorageVariable`
+    #[serde(rename = "hardhat_setStorageVariable")]
+    SetStorageVariable(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        /// variable:
+        String,
+        /// value:
+        serde_json::Value,
+        /// keys:
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        Vec<String>,
+    ),
+    /// `hardhat_signAuthorization`
+    #[serde(rename = "hardhat_signAuthorization")]
+    SignAuthorization(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        AuthorizationRequest,
+    ),
+    /// `hardhat_simulateBundle`
+    #[serde(rename = "hardhat_simulateBundle")]
+    SimulateBundle(
+        Vec<BundleTransactionRequest<ChainSpecT::RpcCallRequest>>,
+        #[serde(
+            skip_serializing_if = "Option::is_none",
+            default = "optional_block_spec::latest"
+        )]
+        Option<BlockSpec>,
+    ),
     /// `hardhat_stopImpersonatingAccount`
     #[serde(
         rename = "hardhat_stopImpersonatingAccount",
         with = "edr_eth::serde::sequence"
     )]
     StopImpersonatingAccount(RpcAddress),
+    /// `hardhat_tokenBalances`
+    #[serde(rename = "hardhat_tokenBalances")]
+    TokenBalances(
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")] Address,
+        #[serde(
+            skip_serializing_if = "Option::is_none",
+            default = "optional_block_spec::latest"
+        )]
+        Option<BlockSpec>,
+    ),
+    /// `hardhat_tokenTransfers`
+    #[serde(
+        rename = "hardhat_tokenTransfers",
+        serialize_with = "optional_single_to_sequence",
+        deserialize_with = "sequence_to_optional_single"
+    )]
+    TokenTransfers(Option<TokenTransferFilter>),
+    /// `hardhat_updateFork`
+    #[serde(
+        rename = "hardhat_updateFork",
+        serialize_with = "optional_single_to_sequence",
+        deserialize_with = "sequence_to_optional_single"
+    )]
+    UpdateFork(Option<U64>),
+    /// `hardhat_validateUserOperation`
+    #[serde(rename = "hardhat_validateUserOperation")]
+    ValidateUserOperation(
+        UserOperation,
+        /// entry point:
+        #[serde(deserialize_with = "crate::requests::serde::deserialize_address")]
+        Address,
+    ),
     /// `hardhat_dumpState`
     #[serde(rename = "hardhat_dumpState", with = "edr_eth::serde::empty_params")]
     DumpState(()),
@@ -389,15 +819,19 @@ impl<ChainSpecT: RpcChainSpec> MethodInvocation<ChainSpecT> {
             MethodInvocation::BlobBaseFee(_) => "eth_blobBaseFee",
             MethodInvocation::BlockNumber(_) => "eth_blockNumber",
             MethodInvocation::Call(_, _, _) => "eth_call",
+            MethodInvocation::CallBundle(_) => "eth_callBundle",
             MethodInvocation::ChainId(_) => "eth_chainId",
             MethodInvocation::Coinbase(_) => "eth_coinbase",
             MethodInvocation::EstimateGas(_, _) => "eth_estimateGas",
             MethodInvocation::EthSign(_, _) => "eth_sign",
             MethodInvocation::FeeHistory(_, _, _) => "eth_feeHistory",
+            MethodInvocation::FillTransaction(_) => "eth_fillTransaction",
             MethodInvocation::GasPrice(_) => "eth_gasPrice",
             MethodInvocation::GetBalance(_, _) => "eth_getBalance",
+            MethodInvocation::GetBlobSidecars(_) => "eth_getBlobSidecars",
             MethodInvocation::GetBlockByNumber(_, _) => "eth_getBlockByNumber",
             MethodInvocation::GetBlockByHash(_, _) => "eth_getBlockByHash",
+            MethodInvocation::GetBlockReceipts(_) => "eth_getBlockReceipts",
             MethodInvocation::GetBlockTransactionCountByHash(_) => {
                 "eth_getBlockTransactionCountByHash"
             }
@@ -424,9 +858,13 @@ impl<ChainSpecT: RpcChainSpec> MethodInvocation<ChainSpecT> {
             MethodInvocation::NewFilter(_) => "eth_newFilter",
             MethodInvocation::NewPendingTransactionFilter(_) => "eth_newPendingTransactionFilter",
             MethodInvocation::PendingTransactions(_) => "eth_pendingTransactions",
+            MethodInvocation::PersonalImportRawKey(_, _) => "personal_importRawKey",
+            MethodInvocation::PersonalNewAccount(_) => "personal_newAccount",
             MethodInvocation::PersonalSign(_, _) => "personal_sign",
+            MethodInvocation::PersonalUnlockAccount(_, _, _) => "personal_unlockAccount",
             MethodInvocation::SendRawTransaction(_) => "eth_sendRawTransaction",
             MethodInvocation::SendTransaction(_) => "eth_sendTransaction",
+            MethodInvocation::SignTransaction(_) => "eth_signTransaction",
             MethodInvocation::SignTypedDataV4(_, _) => "eth_signTypedData_v4",
             MethodInvocation::Subscribe(_, _) => "eth_subscribe",
             MethodInvocation::Syncing(_) => "eth_syncing",
@@ -437,32 +875,268 @@ impl<ChainSpecT: RpcChainSpec> MethodInvocation<ChainSpecT> {
             MethodInvocation::EvmIncreaseTime(_) => "evm_increaseTime",
             MethodInvocation::EvmMine(_) => "evm_mine",
             MethodInvocation::EvmRevert(_) => "evm_revert",
+            MethodInvocation::EvmSetAccountStorageRoot(_, _) => "evm_setAccountStorageRoot",
             MethodInvocation::EvmSetAutomine(_) => "evm_setAutomine",
             MethodInvocation::EvmSetBlockGasLimit(_) => "evm_setBlockGasLimit",
             MethodInvocation::EvmSetIntervalMining(_) => "evm_setIntervalMining",
             MethodInvocation::EvmSetNextBlockTimestamp(_) => "evm_setNextBlockTimestamp",
+            MethodInvocation::EvmSetTime(_) => "evm_setTime",
             MethodInvocation::EvmSnapshot(_) => "evm_snapshot",
+            MethodInvocation::AdminImportChain(_) => "admin_importChain",
+            MethodInvocation::DebugSetHead(_) => "debug_setHead",
             MethodInvocation::DebugTraceCall(_, _, _) => "debug_traceCall",
             MethodInvocation::DebugTraceTransaction(_, _) => "debug_traceTransaction",
+            MethodInvocation::EngineForkchoiceUpdatedV3(_, _) => "engine_forkchoiceUpdatedV3",
+            MethodInvocation::EngineNewPayloadV3(_, _, _) => "engine_newPayloadV3",
+            MethodInvocation::ExplorerGetAddressTransactions(_, _) => {
+                "explorer_getAddressTransactions"
+            }
+            MethodInvocation::OtsGetApiLevel(_) => "ots_getApiLevel",
+            MethodInvocation::OtsGetBlockDetails(_) => "ots_getBlockDetails",
+            MethodInvocation::OtsGetTransactionError(_) => "ots_getTransactionError",
+            MethodInvocation::OtsSearchTransactionsAfter(_, _, _) => "ots_searchTransactionsAfter",
+            MethodInvocation::OtsSearchTransactionsBefore(_, _, _) => {
+                "ots_searchTransactionsBefore"
+            }
+            MethodInvocation::OtsTraceTransaction(_) => "ots_traceTransaction",
+            MethodInvocation::TraceBlock(_) => "trace_block",
+            MethodInvocation::TraceFilter(_) => "trace_filter",
+            MethodInvocation::TraceTransaction(_) => "trace_transaction",
+            MethodInvocation::TxpoolContent(_) => "txpool_content",
+            MethodInvocation::TxpoolInspect(_) => "txpool_inspect",
+            MethodInvocation::TxpoolStatus(_) => "txpool_status",
+            MethodInvocation::AnvilDropAllTransactions(_) => "anvil_dropAllTransactions",
+            MethodInvocation::AnvilDropTransaction(_) => "anvil_dropTransaction",
+            MethodInvocation::AnvilMine(_, _) => "anvil_mine",
+            MethodInvocation::AnvilNodeInfo(_) => "anvil_nodeInfo",
+            MethodInvocation::AnvilSetLoggingEnabled(_) => "anvil_setLoggingEnabled",
+            MethodInvocation::AnvilSetNextBlockBaseFeePerGas(_) => {
+                "anvil_setNextBlockBaseFeePerGas"
+            }
+            MethodInvocation::AddContractArtifact(_) => "hardhat_addContractArtifact",
+            MethodInvocation::AutoImpersonateAccount(_) => "hardhat_autoImpersonateAccount",
             MethodInvocation::DropTransaction(_) => "hardhat_dropTransaction",
+            MethodInvocation::EnqueueDeposit(_) => "hardhat_enqueueDeposit",
+            MethodInvocation::ExportChain(_) => "hardhat_exportChain",
+            MethodInvocation::Flood(_) => "hardhat_flood",
             MethodInvocation::GetAutomine(_) => "hardhat_getAutomine",
+            MethodInvocation::GetGasProfile(_) => "hardhat_getGasProfile",
+            MethodInvocation::GetNextNonce(_) => "hardhat_getNextNonce",
+            MethodInvocation::GetStateDiff(_) => "hardhat_getStateDiff",
+            MethodInvocation::GetTransactionTags(_) => "hardhat_getTransactionTags",
             MethodInvocation::ImpersonateAccount(_) => "hardhat_impersonateAccount",
+            MethodInvocation::ImportChain(_) => "hardhat_importChain",
+            MethodInvocation::ImportTransactions(_) => "hardhat_importTransactions",
+            MethodInvocation::LoadCheckpoint(_) => "hardhat_loadCheckpoint",
             MethodInvocation::Metadata(_) => "hardhat_metadata",
             MethodInvocation::Mine(_, _) => "hardhat_mine",
+            MethodInvocation::PauseIntervalMining(_) => "hardhat_pauseIntervalMining",
+            MethodInvocation::PredictCreate2Address(_, _, _) => "hardhat_predictCreate2Address",
+            MethodInvocation::PredictCreateAddress(_, _) => "hardhat_predictCreateAddress",
+            MethodInvocation::ReadStorageVariable(_, _, _, _) => "hardhat_readStorageVariable",
+            MethodInvocation::Reorg(_, _) => "hardhat_reorg",
+            MethodInvocation::ReplayBlock(_) => "hardhat_replayBlock",
+            MethodInvocation::ReplayTransaction(_) => "hardhat_replayTransaction",
+            MethodInvocation::ResumeIntervalMining(_) => "hardhat_resumeIntervalMining",
+            MethodInvocation::RunFixture(_) => "hardhat_runFixture",
+            MethodInvocation::SaveCheckpoint(_) => "hardhat_saveCheckpoint",
+            MethodInvocation::SetAllowUnlimitedContractSize(_) => {
+                "hardhat_setAllowUnlimitedContractSize"
+            }
+            MethodInvocation::SetAllowUnlimitedInitCodeSize(_) => {
+                "hardhat_setAllowUnlimitedInitCodeSize"
+            }
             MethodInvocation::SetBalance(_, _) => "hardhat_setBalance",
+            MethodInvocation::SetBeaconRoot(_, _) => "hardhat_setBeaconRoot",
             MethodInvocation::SetCode(_, _) => "hardhat_setCode",
             MethodInvocation::SetCoinbase(_) => "hardhat_setCoinbase",
+            MethodInvocation::SetFeeChecks(_) => "hardhat_setFeeChecks",
+            MethodInvocation::SetInclusionPolicy(_) => "hardhat_setInclusionPolicy",
+            MethodInvocation::SetLogFilter(_) => "hardhat_setLogFilter",
             MethodInvocation::SetLoggingEnabled(_) => "hardhat_setLoggingEnabled",
+            MethodInvocation::SetLoggingOptions(_) => "hardhat_setLoggingOptions",
+            MethodInvocation::SetMempoolConfig(_) => "hardhat_setMempoolConfig",
             MethodInvocation::SetMinGasPrice(_) => "hardhat_setMinGasPrice",
             MethodInvocation::SetNextBlockBaseFeePerGas(_) => "hardhat_setNextBlockBaseFeePerGas",
+            MethodInvocation::SetNextBlockHeaderOverrides(_) => {
+                "hardhat_setNextBlockHeaderOverrides"
+            }
+            MethodInvocation::SetNextBlockWithdrawals(_) => "hardhat_setNextBlockWithdrawals",
             MethodInvocation::SetNonce(_, _) => "hardhat_setNonce",
             MethodInvocation::SetPrevRandao(_) => "hardhat_setPrevRandao",
+            MethodInvocation::SetPrevRandaoSeed(_) => "hardhat_setPrevRandaoSeed",
+            MethodInvocation::SetStateRoot(_) => "hardhat_setStateRoot",
             MethodInvocation::SetStorageAt(_, _, _) => "hardhat_setStorageAt",
+            MethodInvocation::SetStorageLayout(_, _) => "hardhat_setStorageLayout",
+            MethodInvocation::SetStorageVariable(_, _, _, _) => "hardhat_setStorageVariable",
+            MethodInvocation::SignAuthorization(_, _) => "hardhat_signAuthorization",
+            MethodInvocation::SimulateBundle(_, _) => "hardhat_simulateBundle",
             MethodInvocation::StopImpersonatingAccount(_) => "hardhat_stopImpersonatingAccount",
+            MethodInvocation::TokenBalances(_, _) => "hardhat_tokenBalances",
+            MethodInvocation::TokenTransfers(_) => "hardhat_tokenTransfers",
+            MethodInvocation::UpdateFork(_) => "hardhat_updateFork",
+            MethodInvocation::ValidateUserOperation(_, _) => "hardhat_validateUserOperation",
             MethodInvocation::DumpState(_) => "hardhat_dumpState",
             MethodInvocation::LoadState(_) => "hardhat_loadState",
         }
     }
+
+    /// Returns whether the method leaves the blockchain, the state, the
+    /// mempool, the provider's configuration, and the file system unmodified,
+    /// and doesn't sign with the provider's accounts. Such methods are
+    /// permitted in read-only mode.
+    ///
+    /// Filters and subscriptions are considered read-only, as they only
+    /// observe the blockchain.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            MethodInvocation::Accounts(_)
+            | MethodInvocation::BlobBaseFee(_)
+            | MethodInvocation::BlockNumber(_)
+            | MethodInvocation::Call(_, _, _)
+            | MethodInvocation::CallBundle(_)
+            | MethodInvocation::ChainId(_)
+            | MethodInvocation::Coinbase(_)
+            | MethodInvocation::EstimateGas(_, _)
+            | MethodInvocation::FeeHistory(_, _, _)
+            | MethodInvocation::FillTransaction(_)
+            | MethodInvocation::GasPrice(_)
+            | MethodInvocation::GetBalance(_, _)
+            | MethodInvocation::GetBlobSidecars(_)
+            | MethodInvocation::GetBlockByNumber(_, _)
+            | MethodInvocation::GetBlockByHash(_, _)
+            | MethodInvocation::GetBlockReceipts(_)
+            | MethodInvocation::GetBlockTransactionCountByHash(_)
+            | MethodInvocation::GetBlockTransactionCountByNumber(_)
+            | MethodInvocation::GetCode(_, _)
+            | MethodInvocation::GetFilterChanges(_)
+            | MethodInvocation::GetFilterLogs(_)
+            | MethodInvocation::GetLogs(_)
+            | MethodInvocation::GetStorageAt(_, _, _)
+            | MethodInvocation::GetTransactionByBlockHashAndIndex(_, _)
+            | MethodInvocation::GetTransactionByBlockNumberAndIndex(_, _)
+            | MethodInvocation::GetTransactionByHash(_)
+            | MethodInvocation::GetTransactionCount(_, _)
+            | MethodInvocation::GetTransactionReceipt(_)
+            | MethodInvocation::MaxPriorityFeePerGas(_)
+            | MethodInvocation::NetVersion(_)
+            | MethodInvocation::NewBlockFilter(_)
+            | MethodInvocation::NewFilter(_)
+            | MethodInvocation::NewPendingTransactionFilter(_)
+            | MethodInvocation::PendingTransactions(_)
+            | MethodInvocation::Subscribe(_, _)
+            | MethodInvocation::Syncing(_)
+            | MethodInvocation::UninstallFilter(_)
+            | MethodInvocation::Unsubscribe(_)
+            | MethodInvocation::Web3ClientVersion(_)
+            | MethodInvocation::Web3Sha3(_)
+            | MethodInvocation::DebugTraceCall(_, _, _)
+            | MethodInvocation::DebugTraceTransaction(_, _)
+            | MethodInvocation::ExplorerGetAddressTransactions(_, _)
+            | MethodInvocation::OtsGetApiLevel(_)
+            | MethodInvocation::OtsGetBlockDetails(_)
+            | MethodInvocation::OtsGetTransactionError(_)
+            | MethodInvocation::OtsSearchTransactionsAfter(_, _, _)
+            | MethodInvocation::OtsSearchTransactionsBefore(_, _, _)
+            | MethodInvocation::OtsTraceTransaction(_)
+            | MethodInvocation::TraceBlock(_)
+            | MethodInvocation::TraceFilter(_)
+            | MethodInvocation::TraceTransaction(_)
+            | MethodInvocation::TxpoolContent(_)
+            | MethodInvocation::TxpoolInspect(_)
+            | MethodInvocation::TxpoolStatus(_)
+            | MethodInvocation::AnvilNodeInfo(_)
+            | MethodInvocation::ExportChain(_)
+            | MethodInvocation::GetAutomine(_)
+            | MethodInvocation::GetGasProfile(_)
+            | MethodInvocation::GetNextNonce(_)
+            | MethodInvocation::GetStateDiff(_)
+            | MethodInvocation::GetTransactionTags(_)
+            | MethodInvocation::Metadata(_)
+            | MethodInvocation::PredictCreate2Address(_, _, _)
+            | MethodInvocation::PredictCreateAddress(_, _)
+            | MethodInvocation::ReadStorageVariable(_, _, _, _)
+            | MethodInvocation::ReplayBlock(_)
+            | MethodInvocation::ReplayTransaction(_)
+            | MethodInvocation::SimulateBundle(_, _)
+            | MethodInvocation::TokenBalances(_, _)
+            | MethodInvocation::TokenTransfers(_)
+            | MethodInvocation::ValidateUserOperation(_, _)
+            | MethodInvocation::DumpState(_) => true,
+            // Signing methods use the provider's private keys, so they're not
+            // permitted in read-only mode.
+            MethodInvocation::EthSign(_, _)
+            | MethodInvocation::PersonalSign(_, _)
+            | MethodInvocation::SignAuthorization(_, _)
+            | MethodInvocation::SignTransaction(_)
+            | MethodInvocation::SignTypedDataV4(_, _)
+            | MethodInvocation::PersonalImportRawKey(_, _)
+            | MethodInvocation::PersonalNewAccount(_)
+            | MethodInvocation::PersonalUnlockAccount(_, _, _)
+            | MethodInvocation::SendRawTransaction(_)
+            | MethodInvocation::SendTransaction(_)
+            | MethodInvocation::EvmIncreaseTime(_)
+            | MethodInvocation::EvmMine(_)
+            | MethodInvocation::EvmRevert(_)
+            | MethodInvocation::EvmSetAccountStorageRoot(_, _)
+            | MethodInvocation::EvmSetAutomine(_)
+            | MethodInvocation::EvmSetBlockGasLimit(_)
+            | MethodInvocation::EvmSetIntervalMining(_)
+            | MethodInvocation::EvmSetNextBlockTimestamp(_)
+            | MethodInvocation::EvmSetTime(_)
+            | MethodInvocation::EvmSnapshot(_)
+            | MethodInvocation::AdminImportChain(_)
+            | MethodInvocation::DebugSetHead(_)
+            | MethodInvocation::EngineForkchoiceUpdatedV3(_, _)
+            | MethodInvocation::EngineNewPayloadV3(_, _, _)
+            | MethodInvocation::AnvilDropAllTransactions(_)
+            | MethodInvocation::AnvilDropTransaction(_)
+            | MethodInvocation::AnvilMine(_, _)
+            | MethodInvocation::AnvilSetLoggingEnabled(_)
+            | MethodInvocation::AnvilSetNextBlockBaseFeePerGas(_)
+            | MethodInvocation::AddContractArtifact(_)
+            | MethodInvocation::AutoImpersonateAccount(_)
+            | MethodInvocation::DropTransaction(_)
+            | MethodInvocation::EnqueueDeposit(_)
+            | MethodInvocation::Flood(_)
+            | MethodInvocation::ImpersonateAccount(_)
+            | MethodInvocation::ImportChain(_)
+            | MethodInvocation::ImportTransactions(_)
+            | MethodInvocation::LoadCheckpoint(_)
+            | MethodInvocation::Mine(_, _)
+            | MethodInvocation::PauseIntervalMining(_)
+            | MethodInvocation::Reorg(_, _)
+            | MethodInvocation::ResumeIntervalMining(_)
+            | MethodInvocation::RunFixture(_)
+            | MethodInvocation::SaveCheckpoint(_)
+            | MethodInvocation::SetAllowUnlimitedContractSize(_)
+            | MethodInvocation::SetAllowUnlimitedInitCodeSize(_)
+            | MethodInvocation::SetBalance(_, _)
+            | MethodInvocation::SetBeaconRoot(_, _)
+            | MethodInvocation::SetCode(_, _)
+            | MethodInvocation::SetCoinbase(_)
+            | MethodInvocation::SetFeeChecks(_)
+            | MethodInvocation::SetInclusionPolicy(_)
+            | MethodInvocation::SetLogFilter(_)
+            | MethodInvocation::SetLoggingEnabled(_)
+            | MethodInvocation::SetLoggingOptions(_)
+            | MethodInvocation::SetMempoolConfig(_)
+            | MethodInvocation::SetMinGasPrice(_)
+            | MethodInvocation::SetNextBlockBaseFeePerGas(_)
+            | MethodInvocation::SetNextBlockHeaderOverrides(_)
+            | MethodInvocation::SetNextBlockWithdrawals(_)
+            | MethodInvocation::SetNonce(_, _)
+            | MethodInvocation::SetPrevRandao(_)
+            | MethodInvocation::SetPrevRandaoSeed(_)
+            | MethodInvocation::SetStateRoot(_)
+            | MethodInvocation::SetStorageAt(_, _, _)
+            | MethodInvocation::SetStorageLayout(_, _)
+            | MethodInvocation::SetStorageVariable(_, _, _, _)
+            | MethodInvocation::StopImpersonatingAccount(_)
+            | MethodInvocation::UpdateFork(_)
+            | MethodInvocation::LoadState(_) => false,
+        }
+    }
 }
 
 /// an input that can be either a single `u64` or an array of two `u64` values
@@ -0,0 +1,536 @@
+use std::sync::Arc;
+
+use edr_block_api::{Block as _, FetchBlockReceipts as _};
+use edr_chain_l1::rpc::block::L1RpcBlock;
+use edr_chain_spec::{ExecutableTransaction as _, HaltReasonTrait, TransactionValidation};
+use edr_chain_spec_evm::{
+    interpreter::{CallScheme, CreateScheme},
+    result::{ExecutionResult, Output},
+};
+use edr_chain_spec_rpc::RpcTypeFrom as _;
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_receipt::ReceiptTrait as _;
+use edr_tracing::{Trace, TraceMessage};
+use edr_transaction::{BlockDataForTransaction, TransactionAndBlock};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::ProviderData,
+    error::ProviderErrorForChainSpec,
+    requests::{
+        eth::{block_to_rpc_output, HashOrTransaction},
+        explorer::AddressTransaction,
+    },
+    spec::SyncProviderSpec,
+    time::TimeSinceEpoch,
+    ProviderError,
+};
+
+/// The version of the Otterscan API that is fully implemented, as returned by
+/// `ots_getApiLevel`.
+///
+/// Only a subset of the Otterscan API is implemented - e.g. `ots_hasCode`,
+/// `ots_getInternalOperations`, `ots_getContractCreator`,
+/// `ots_getTransactionBySenderAndNonce`, and `erigon_getHeaderByNumber` are
+/// missing - so no API level is fully supported.
+pub const OTTERSCAN_API_LEVEL: u64 = 0;
+
+/// The maximum page size of `ots_searchTransactionsBefore` and
+/// `ots_searchTransactionsAfter`.
+pub const MAX_SEARCH_PAGE_SIZE: u64 = 1_000;
+
+/// Result of `ots_getBlockDetails`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockDetails<BlockT> {
+    pub block: BlockWithTransactionCount<BlockT>,
+    pub issuance: BlockIssuance,
+    /// The sum of the fees paid by the block's transactions.
+    pub total_fees: U256,
+}
+
+/// A block without its transactions, along with the number of transactions.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithTransactionCount<BlockT> {
+    #[serde(flatten)]
+    pub block: BlockT,
+    pub transaction_count: u64,
+}
+
+/// The ether that was issued by a block.
+///
+/// Always zero, as blocks mined by the provider don't pay rewards.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockIssuance {
+    pub block_reward: U256,
+    pub uncle_reward: U256,
+    pub issuance: U256,
+}
+
+/// Result of `ots_searchTransactionsBefore` and
+/// `ots_searchTransactionsAfter`.
+///
+/// Transactions are ordered from the most to the least recent. Pages are
+/// ordered in the same way, so the first page contains the most recent
+/// transactions.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSearchResult<TransactionT, ReceiptT> {
+    pub txs: Vec<TransactionT>,
+    pub receipts: Vec<ReceiptWithTimestamp<ReceiptT>>,
+    /// Whether the page contains the most recent transactions.
+    pub first_page: bool,
+    /// Whether the page contains the least recent transactions.
+    pub last_page: bool,
+}
+
+/// A receipt along with the timestamp of the block that contains it.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ReceiptWithTimestamp<ReceiptT> {
+    #[serde(flatten)]
+    pub receipt: ReceiptT,
+    pub timestamp: u64,
+}
+
+/// An entry of `ots_traceTransaction`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TraceEntry {
+    #[serde(rename = "type")]
+    pub entry_type: TraceEntryType,
+    /// The call depth, starting at zero for the transaction itself.
+    pub depth: u64,
+    pub from: Address,
+    /// The callee, the created contract, or the beneficiary of a
+    /// self-destruct.
+    pub to: Address,
+    /// `None` for delegate and static calls, which don't transfer value.
+    pub value: Option<U256>,
+    pub input: Bytes,
+    /// The returned or reverted data. `None` for halted calls and
+    /// self-destructs.
+    pub output: Option<Bytes>,
+}
+
+/// The type of a [`TraceEntry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TraceEntryType {
+    Call,
+    CallCode,
+    Create,
+    Create2,
+    DelegateCall,
+    SelfDestruct,
+    StaticCall,
+}
+
+pub fn handle_get_api_level<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>() -> Result<u64, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(OTTERSCAN_API_LEVEL)
+}
+
+// The result type can not be meaningfully simplified further without reducing
+// readability.
+#[allow(clippy::type_complexity)]
+pub fn handle_get_block_details<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    block_number: u64,
+) -> Result<
+    Option<BlockDetails<L1RpcBlock<HashOrTransaction<ChainSpecT::RpcTransaction>>>>,
+    ProviderErrorForChainSpec<ChainSpecT>,
+> {
+    let Some(block) = data.block_by_number(block_number)? else {
+        return Ok(None);
+    };
+
+    let receipts = block
+        .fetch_transaction_receipts()
+        .map_err(ProviderError::FetchReceipt)?;
+
+    let total_fees = block
+        .transactions()
+        .iter()
+        .zip(receipts.iter())
+        .map(|(transaction, receipt)| {
+            let gas_price = receipt
+                .effective_gas_price()
+                .unwrap_or_else(|| transaction.gas_price());
+
+            U256::from(receipt.gas_used()) * U256::from(*gas_price)
+        })
+        .sum::<U256>();
+
+    let transaction_count = block.transactions().len() as u64;
+    let total_difficulty = data.total_difficulty_by_hash(block.block_hash())?;
+
+    let mut block =
+        block_to_rpc_output::<ChainSpecT>(data.hardfork(), block, false, total_difficulty, false)?;
+    block.transactions = Vec::new();
+
+    Ok(Some(BlockDetails {
+        block: BlockWithTransactionCount {
+            block,
+            transaction_count,
+        },
+        issuance: BlockIssuance::default(),
+        total_fees,
+    }))
+}
+
+pub fn handle_get_transaction_error<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Option<Bytes>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let call_tree = data.transaction_call_tree(&transaction_hash)?;
+
+    Ok(call_tree.map(|call_tree| revert_output(&call_tree)))
+}
+
+/// Searches the transactions that involve the provided address in blocks after
+/// the provided block number, using the address index.
+///
+/// Transactions are matched if they were sent by or to the address, or if the
+/// address took part in one of their internal calls, contract creations, or
+/// self-destructs.
+// The result type can not be meaningfully simplified further without reducing
+// readability.
+#[allow(clippy::type_complexity)]
+pub fn handle_search_transactions_after<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    block_number: u64,
+    page_size: u64,
+) -> Result<
+    TransactionSearchResult<ChainSpecT::RpcTransaction, ChainSpecT::RpcReceipt>,
+    ProviderErrorForChainSpec<ChainSpecT>,
+> {
+    let page_size = validate_page_size::<ChainSpecT, TimerT>(page_size)?;
+    let (transactions, pruned_block_numbers) = data.retained_address_transactions(&address)?;
+
+    if pruned_block_numbers.is_some_and(|block_numbers| *block_numbers.end() > block_number) {
+        return Err(pruned_transactions_error(&address, block_number));
+    }
+
+    let start =
+        transactions.partition_point(|transaction| transaction.block_number <= block_number);
+    let mut end = transactions.len().min(start.saturating_add(page_size));
+    // Pages contain all matching transactions of their blocks
+    while end > start
+        && end < transactions.len()
+        && transactions[end].block_number == transactions[end - 1].block_number
+    {
+        end += 1;
+    }
+
+    let (txs, receipts) = search_result_entries(data, &transactions[start..end])?;
+    Ok(TransactionSearchResult {
+        txs,
+        receipts,
+        first_page: end == transactions.len(),
+        last_page: block_number == 0,
+    })
+}
+
+/// Searches the transactions that involve the provided address in blocks
+/// before the provided block number, using the address index. Zero indicates
+/// that the search starts at the latest block.
+///
+/// Transactions are matched in the same way as by
+/// [`handle_search_transactions_after`].
+// The result type can not be meaningfully simplified further without reducing
+// readability.
+#[allow(clippy::type_complexity)]
+pub fn handle_search_transactions_before<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    address: Address,
+    block_number: u64,
+    page_size: u64,
+) -> Result<
+    TransactionSearchResult<ChainSpecT::RpcTransaction, ChainSpecT::RpcReceipt>,
+    ProviderErrorForChainSpec<ChainSpecT>,
+> {
+    let page_size = validate_page_size::<ChainSpecT, TimerT>(page_size)?;
+    let (transactions, pruned_block_numbers) = data.retained_address_transactions(&address)?;
+
+    let last_searched_block_number = block_number.checked_sub(1).unwrap_or(u64::MAX);
+
+    let end = transactions
+        .partition_point(|transaction| transaction.block_number <= last_searched_block_number);
+    let mut start = end.saturating_sub(page_size);
+    // Pages contain all matching transactions of their blocks
+    while start > 0
+        && start < end
+        && transactions[start - 1].block_number == transactions[start].block_number
+    {
+        start -= 1;
+    }
+
+    if start == 0
+        && pruned_block_numbers
+            .is_some_and(|block_numbers| *block_numbers.start() <= last_searched_block_number)
+    {
+        return Err(pruned_transactions_error(&address, block_number));
+    }
+
+    let (txs, receipts) = search_result_entries(data, &transactions[start..end])?;
+    Ok(TransactionSearchResult {
+        txs,
+        receipts,
+        first_page: block_number == 0,
+        last_page: start == 0,
+    })
+}
+
+pub fn handle_trace_transaction<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Option<Vec<TraceEntry>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let call_tree = data.transaction_call_tree(&transaction_hash)?;
+
+    Ok(call_tree.map(|call_tree| trace_entries(&call_tree)))
+}
+
+/// Validates the page size of a transaction search, returning it as a `usize`.
+fn validate_page_size<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    page_size: u64,
+) -> Result<usize, ProviderErrorForChainSpec<ChainSpecT>> {
+    if page_size > MAX_SEARCH_PAGE_SIZE {
+        return Err(ProviderError::InvalidInput(format!(
+            "The page size {page_size} exceeds the maximum of {MAX_SEARCH_PAGE_SIZE}."
+        )));
+    }
+
+    // The maximum page size fits in a `usize`
+    Ok(page_size as usize)
+}
+
+fn pruned_transactions_error<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    address: &Address,
+    block_number: u64,
+) -> ProviderErrorForChainSpec<ChainSpecT> {
+    ProviderError::InvalidInput(format!(
+        "The transactions of {address} around block {block_number} are unavailable, as they include transactions of pruned blocks."
+    ))
+}
+
+/// Converts indexed transactions, in the order in which they were mined, into
+/// the transactions and receipts of a search result, from the most to the
+/// least recent.
+// The result type can not be meaningfully simplified further without reducing
+// readability.
+#[allow(clippy::type_complexity)]
+fn search_result_entries<ChainSpecT: SyncProviderSpec<TimerT>, TimerT: Clone + TimeSinceEpoch>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    transactions: &[AddressTransaction],
+) -> Result<
+    (
+        Vec<ChainSpecT::RpcTransaction>,
+        Vec<ReceiptWithTimestamp<ChainSpecT::RpcReceipt>>,
+    ),
+    ProviderErrorForChainSpec<ChainSpecT>,
+> {
+    let hardfork = data.hardfork();
+
+    let mut txs = Vec::with_capacity(transactions.len());
+    let mut receipts = Vec::with_capacity(transactions.len());
+
+    for block_transactions in transactions
+        .chunk_by(|first, second| first.block_number == second.block_number)
+        .rev()
+    {
+        let block_number = block_transactions[0].block_number;
+        let block = data.block_by_number(block_number)?.ok_or_else(|| {
+            ProviderError::InvalidBlockNumberOrHash {
+                block_spec: BlockSpec::Number(block_number),
+                latest_block_number: data.last_block_number(),
+            }
+        })?;
+
+        let block_receipts = block
+            .fetch_transaction_receipts()
+            .map_err(ProviderError::FetchReceipt)?;
+
+        let timestamp = block.block_header().timestamp;
+
+        for indexed_transaction in block_transactions.iter().rev() {
+            let transaction_index = indexed_transaction.transaction_index as usize;
+            let (Some(transaction), Some(receipt)) = (
+                block.transactions().get(transaction_index),
+                block_receipts.get(transaction_index),
+            ) else {
+                continue;
+            };
+
+            let transaction_and_block = TransactionAndBlock {
+                transaction: transaction.clone(),
+                block_data: Some(BlockDataForTransaction {
+                    block: Arc::clone(&block),
+                    transaction_index: indexed_transaction.transaction_index,
+                }),
+                is_pending: false,
+            };
+
+            txs.push(ChainSpecT::RpcTransaction::rpc_type_from(
+                &transaction_and_block,
+                hardfork,
+            ));
+            receipts.push(ReceiptWithTimestamp {
+                receipt: ChainSpecT::RpcReceipt::rpc_type_from(receipt, hardfork),
+                timestamp,
+            });
+        }
+    }
+
+    Ok((txs, receipts))
+}
+
+/// Returns the data that was returned by a reverted transaction, or empty
+/// bytes if the transaction didn't revert.
+fn revert_output<HaltReasonT: HaltReasonTrait>(call_tree: &[Trace<HaltReasonT>]) -> Bytes {
+    // The transaction's own call is the last one to finish
+    let execution_result = call_tree
+        .iter()
+        .flat_map(|trace| &trace.messages)
+        .rev()
+        .find_map(|message| match message {
+            TraceMessage::After(message) => Some(&message.execution_result),
+            _ => None,
+        });
+
+    match execution_result {
+        Some(ExecutionResult::Revert { output, .. }) => output.clone(),
+        _ => Bytes::new(),
+    }
+}
+
+/// Converts the call tree of a transaction into a flat list of entries, in
+/// depth-first order.
+fn trace_entries<HaltReasonT: HaltReasonTrait>(
+    call_tree: &[Trace<HaltReasonT>],
+) -> Vec<TraceEntry> {
+    let mut entries: Vec<TraceEntry> = Vec::new();
+    // Indices of the entries of the calls that haven't finished yet
+    let mut call_stack: Vec<usize> = Vec::new();
+
+    for message in call_tree.iter().flat_map(|trace| &trace.messages) {
+        match message {
+            TraceMessage::Before(message) => {
+                let (entry_type, from, to, value) = match (message.to, message.code_address) {
+                    (Some(to), Some(code_address)) if to != code_address => {
+                        let entry_type = if message.scheme == Some(CallScheme::CallCode) {
+                            TraceEntryType::CallCode
+                        } else {
+                            TraceEntryType::DelegateCall
+                        };
+
+                        (entry_type, to, code_address, None)
+                    }
+                    (Some(to), _) if message.is_static_call => {
+                        (TraceEntryType::StaticCall, message.caller, to, None)
+                    }
+                    (Some(to), _) => (
+                        TraceEntryType::Call,
+                        message.caller,
+                        to,
+                        Some(message.value),
+                    ),
+                    // The address of the created contract is only known once it finishes
+                    (None, _) => {
+                        let entry_type = if matches!(
+                            message.create_scheme,
+                            Some(CreateScheme::Create2 { .. })
+                        ) {
+                            TraceEntryType::Create2
+                        } else {
+                            TraceEntryType::Create
+                        };
+
+                        (
+                            entry_type,
+                            message.caller,
+                            Address::ZERO,
+                            Some(message.value),
+                        )
+                    }
+                };
+
+                call_stack.push(entries.len());
+                entries.push(TraceEntry {
+                    entry_type,
+                    depth: (call_stack.len() - 1) as u64,
+                    from,
+                    to,
+                    value,
+                    input: message.data.clone(),
+                    output: None,
+                });
+            }
+            TraceMessage::After(message) => {
+                let Some(index) = call_stack.pop() else {
+                    continue;
+                };
+
+                let entry = &mut entries[index];
+                if matches!(
+                    entry.entry_type,
+                    TraceEntryType::Create | TraceEntryType::Create2
+                ) && let Some(contract_address) = message.contract_address
+                {
+                    entry.to = contract_address;
+                }
+
+                entry.output = match &message.execution_result {
+                    ExecutionResult::Success { output, .. } => match output {
+                        Output::Call(output) => Some(output.clone()),
+                        Output::Create(code, _address) => Some(code.clone()),
+                    },
+                    ExecutionResult::Revert { output, .. } => Some(output.clone()),
+                    ExecutionResult::Halt { .. } => None,
+                };
+            }
+            TraceMessage::SelfDestruct(message) => {
+                entries.push(TraceEntry {
+                    entry_type: TraceEntryType::SelfDestruct,
+                    depth: call_stack.len() as u64,
+                    from: message.contract,
+                    to: message.target,
+                    value: Some(message.value),
+                    input: Bytes::new(),
+                    output: None,
+                });
+            }
+            TraceMessage::Step(_) => {}
+        }
+    }
+
+    entries
+}
@@ -0,0 +1,307 @@
+use edr_block_api::Block as _;
+use edr_chain_spec::TransactionValidation;
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::ProviderData, error::ProviderErrorForChainSpec, spec::SyncProviderSpec,
+    time::TimeSinceEpoch, ProviderError,
+};
+
+/// The maximum number of blocks that a single `trace_filter` request may
+/// span, as every block in the range is re-executed.
+pub const MAX_TRACE_FILTER_BLOCK_RANGE: u64 = 100;
+
+/// A trace of a single call, create, or self-destruct within a transaction,
+/// in the format of OpenEthereum's `trace` module.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    /// The action that was performed.
+    pub action: TraceAction,
+    /// The reason why the action failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The outcome of a successful call or create. `None` for failed actions
+    /// and self-destructs.
+    pub result: Option<TraceResult>,
+    /// The number of direct child traces.
+    pub subtraces: u64,
+    /// The path of the trace in the transaction's call tree, as the indices
+    /// of its ancestors among their siblings.
+    pub trace_address: Vec<u64>,
+    /// The type of the action.
+    #[serde(rename = "type")]
+    pub trace_type: TraceType,
+}
+
+/// A [`TransactionTrace`] along with the transaction and block it belongs to,
+/// as returned by `trace_transaction`, `trace_block`, and `trace_filter`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+    #[serde(flatten)]
+    pub trace: TransactionTrace,
+    /// The hash of the block that contains the transaction.
+    pub block_hash: B256,
+    /// The number of the block that contains the transaction.
+    pub block_number: u64,
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The index of the transaction in its block.
+    pub transaction_position: u64,
+}
+
+/// The type of a traced action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceType {
+    Call,
+    Create,
+    Suicide,
+}
+
+/// A traced action.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TraceAction {
+    Call(CallAction),
+    Create(CreateAction),
+    Suicide(SuicideAction),
+}
+
+/// The type of a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+}
+
+/// A message call.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+    pub call_type: CallType,
+    /// The address of the calling context.
+    pub from: Address,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub input: Bytes,
+    /// The address of the executed code.
+    pub to: Address,
+    pub value: U256,
+}
+
+/// A contract creation.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+    pub from: Address,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub init: Bytes,
+    pub value: U256,
+}
+
+/// A contract self-destruct.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuicideAction {
+    /// The address of the self-destructed contract.
+    pub address: Address,
+    /// The address that received the contract's balance.
+    pub refund_address: Address,
+    pub balance: U256,
+}
+
+/// The outcome of a successful call or create.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+    // Must precede `Call`, as untagged deserialization picks the first match
+    Create(CreateTraceResult),
+    Call(CallTraceResult),
+}
+
+/// The outcome of a successful call.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTraceResult {
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    pub output: Bytes,
+}
+
+/// The outcome of a successful create.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTraceResult {
+    /// The address of the created contract.
+    pub address: Address,
+    /// The deployed code.
+    pub code: Bytes,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+}
+
+/// The filter of `trace_filter`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// The first block to trace. Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockSpec>,
+    /// The last block to trace. Defaults to the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockSpec>,
+    /// If present, only traces with one of these senders are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Vec<Address>>,
+    /// If present, only traces with one of these recipients are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Vec<Address>>,
+    /// The number of matching traces to skip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    /// The maximum number of traces to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+impl TraceFilter {
+    /// Whether the trace matches the filter's addresses.
+    ///
+    /// The recipient of a create is the created contract and the recipient
+    /// of a self-destruct is the refund address.
+    pub fn matches(&self, trace: &TransactionTrace) -> bool {
+        let (from, to) = match &trace.action {
+            TraceAction::Call(action) => (action.from, Some(action.to)),
+            TraceAction::Create(action) => {
+                let to = match &trace.result {
+                    Some(TraceResult::Create(result)) => Some(result.address),
+                    _ => None,
+                };
+
+                (action.from, to)
+            }
+            TraceAction::Suicide(action) => (action.address, Some(action.refund_address)),
+        };
+
+        let from_matches = self
+            .from_address
+            .as_ref()
+            .is_none_or(|addresses| addresses.contains(&from));
+
+        let to_matches = self
+            .to_address
+            .as_ref()
+            .is_none_or(|addresses| to.is_some_and(|to| addresses.contains(&to)));
+
+        from_matches && to_matches
+    }
+}
+
+pub fn handle_trace_block<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    block_spec: BlockSpec,
+) -> Result<Vec<LocalizedTrace>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block_number = resolve_block_number(data, &block_spec)?;
+
+    data.trace_block(block_number)
+}
+
+pub fn handle_trace_filter<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    filter: TraceFilter,
+) -> Result<Vec<LocalizedTrace>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let latest = BlockSpec::latest();
+    let from_block = resolve_block_number(data, filter.from_block.as_ref().unwrap_or(&latest))?;
+    let to_block = resolve_block_number(data, filter.to_block.as_ref().unwrap_or(&latest))?;
+
+    if from_block > to_block {
+        return Err(ProviderError::InvalidInput(format!(
+            "fromBlock ({from_block}) cannot be greater than toBlock ({to_block})"
+        )));
+    }
+
+    if to_block - from_block >= MAX_TRACE_FILTER_BLOCK_RANGE {
+        return Err(ProviderError::InvalidInput(format!(
+            "Query exceeds max block range {MAX_TRACE_FILTER_BLOCK_RANGE}. Requested block range [{from_block:#x}, {to_block:#x}]."
+        )));
+    }
+
+    let mut remaining_skips = filter.after.unwrap_or(0);
+    let count = filter.count.unwrap_or(u64::MAX);
+
+    let mut traces = Vec::new();
+    for block_number in from_block..=to_block {
+        if traces.len() as u64 >= count {
+            break;
+        }
+
+        for trace in data.trace_block(block_number)? {
+            if !filter.matches(&trace.trace) {
+                continue;
+            }
+
+            if remaining_skips > 0 {
+                remaining_skips -= 1;
+                continue;
+            }
+
+            if traces.len() as u64 >= count {
+                break;
+            }
+
+            traces.push(trace);
+        }
+    }
+
+    Ok(traces)
+}
+
+pub fn handle_trace_transaction<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &mut ProviderData<ChainSpecT, TimerT>,
+    transaction_hash: B256,
+) -> Result<Option<Vec<LocalizedTrace>>, ProviderErrorForChainSpec<ChainSpecT>> {
+    data.trace_transaction(&transaction_hash)
+}
+
+fn resolve_block_number<
+    ChainSpecT: SyncProviderSpec<
+        TimerT,
+        SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+    >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+    block_spec: &BlockSpec,
+) -> Result<u64, ProviderErrorForChainSpec<ChainSpecT>> {
+    let block = data.block_by_block_spec(block_spec)?.ok_or_else(|| {
+        ProviderError::InvalidInput("The pending block cannot be traced".to_string())
+    })?;
+
+    Ok(block.block_header().number)
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use edr_chain_spec::ExecutableTransaction;
+use edr_chain_spec_rpc::RpcTypeFrom as _;
+use edr_primitives::{Address, U64};
+use edr_transaction::{TransactionAndBlock, TxKind};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::ProviderData, error::ProviderErrorForChainSpec, spec::SyncProviderSpec,
+    time::TimeSinceEpoch,
+};
+
+/// Transactions in the mem pool, grouped by sender and ordered by nonce.
+///
+/// Nonces are serialized as decimal strings, matching Geth.
+pub type TransactionsBySender<T> = BTreeMap<Address, BTreeMap<u64, T>>;
+
+/// Result of `txpool_content`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolContent<RpcTransactionT> {
+    /// Transactions that can be included in the next block
+    pub pending: TransactionsBySender<RpcTransactionT>,
+    /// Transactions whose nonces are too high to be included in the next
+    /// block
+    pub queued: TransactionsBySender<RpcTransactionT>,
+}
+
+/// Result of `txpool_inspect`.
+///
+/// Each transaction is summarized in Geth's textual format:
+/// `<to>: <value> wei + <gas limit> gas × <gas price> wei`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolInspect {
+    /// Transactions that can be included in the next block
+    pub pending: TransactionsBySender<String>,
+    /// Transactions whose nonces are too high to be included in the next
+    /// block
+    pub queued: TransactionsBySender<String>,
+}
+
+/// Result of `txpool_status`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TxpoolStatus {
+    /// The number of transactions that can be included in the next block
+    pub pending: U64,
+    /// The number of transactions whose nonces are too high to be included in
+    /// the next block
+    pub queued: U64,
+}
+
+pub fn handle_txpool_content<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+) -> Result<TxpoolContent<ChainSpecT::RpcTransaction>, ProviderErrorForChainSpec<ChainSpecT>> {
+    let to_rpc_transaction = |transaction: &ChainSpecT::SignedTransaction| {
+        let transaction_and_block = TransactionAndBlock {
+            transaction: transaction.clone(),
+            block_data: None,
+            is_pending: true,
+        };
+        ChainSpecT::RpcTransaction::rpc_type_from(&transaction_and_block, data.hardfork())
+    };
+
+    Ok(TxpoolContent {
+        pending: group_by_sender(data.executable_transactions(), to_rpc_transaction),
+        queued: group_by_sender(data.queued_transactions(), to_rpc_transaction),
+    })
+}
+
+pub fn handle_txpool_inspect<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+) -> Result<TxpoolInspect, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(TxpoolInspect {
+        pending: group_by_sender(data.executable_transactions(), summarize_transaction),
+        queued: group_by_sender(data.queued_transactions(), summarize_transaction),
+    })
+}
+
+pub fn handle_txpool_status<
+    ChainSpecT: SyncProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    data: &ProviderData<ChainSpecT, TimerT>,
+) -> Result<TxpoolStatus, ProviderErrorForChainSpec<ChainSpecT>> {
+    Ok(TxpoolStatus {
+        pending: U64::from(data.executable_transactions().count()),
+        queued: U64::from(data.queued_transactions().count()),
+    })
+}
+
+fn group_by_sender<'a, SignedTransactionT: ExecutableTransaction + 'a, T>(
+    transactions: impl Iterator<Item = &'a SignedTransactionT>,
+    mut map_fn: impl FnMut(&SignedTransactionT) -> T,
+) -> TransactionsBySender<T> {
+    let mut grouped = TransactionsBySender::new();
+    for transaction in transactions {
+        grouped
+            .entry(*transaction.caller())
+            .or_insert_with(BTreeMap::new)
+            .insert(transaction.nonce(), map_fn(transaction));
+    }
+
+    grouped
+}
+
+fn summarize_transaction(transaction: &impl ExecutableTransaction) -> String {
+    let to = match transaction.kind() {
+        TxKind::Call(to) => to.to_string(),
+        TxKind::Create => "contract creation".to_string(),
+    };
+
+    format!(
+        "{to}: {value} wei + {gas_limit} gas × {gas_price} wei",
+        value = transaction.value(),
+        gas_limit = transaction.gas_limit(),
+        gas_price = transaction.gas_price()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_chain_l1::L1ChainSpec;
+
+    use super::*;
+    use crate::test_utils::ProviderTestFixture;
+
+    #[test]
+    fn txpool_groups_by_sender_and_orders_by_nonce() -> anyhow::Result<()> {
+        let mut fixture = ProviderTestFixture::<L1ChainSpec>::new_local()?;
+        fixture.provider_data.set_auto_mining(false);
+
+        let sender = fixture.nth_local_account(0)?;
+        let other_sender = fixture.nth_local_account(1)?;
+
+        // Insert out of nonce order to verify ordering. Nonce 3 has a gap, so
+        // it's queued.
+        for (account_index, nonce) in [(0, 1), (0, 0), (0, 3), (1, 0)] {
+            let transaction = fixture.signed_dummy_transaction(account_index, Some(nonce))?;
+            fixture.provider_data.send_transaction(transaction)?;
+        }
+
+        let status = handle_txpool_status(&fixture.provider_data)?;
+        assert_eq!(
+            status,
+            TxpoolStatus {
+                pending: U64::from(3),
+                queued: U64::from(1),
+            }
+        );
+
+        let content = handle_txpool_content(&fixture.provider_data)?;
+        assert_eq!(content.pending.len(), 2);
+        assert_eq!(
+            content.pending[&sender].keys().copied().collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            content.pending[&other_sender]
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert_eq!(
+            content.queued[&sender].keys().copied().collect::<Vec<_>>(),
+            vec![3]
+        );
+
+        let inspect = handle_txpool_inspect(&fixture.provider_data)?;
+        assert_eq!(
+            inspect.queued[&sender][&3],
+            "0x0000000000000000000000000000000000000000: 1 wei + 30000 gas × 42000000000 wei"
+        );
+
+        let json = serde_json::to_value(&inspect)?;
+        assert!(json["pending"][sender.to_string().to_lowercase()]["1"].is_string());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use alloy_dyn_abi::eip712::TypedData;
 use edr_chain_l1::rpc::{call::L1CallRequest, TransactionRequest};
 use edr_chain_spec::{EvmSpecId, ExecutableTransaction};
 use edr_eth::{Blob, BlockSpec, BlockTag, PreEip1898BlockSpec};
@@ -406,6 +407,68 @@ pub(crate) fn validate_post_merge_block_tags<
     Ok(())
 }
 
+/// Validates that the `EIP712Domain` type, if declared, has the fields of the
+/// typed data's domain. The domain separator is computed from the provided
+/// fields in their canonical order, so the declared fields may be listed in
+/// any order, but a declaration with different fields would result in a
+/// signature that other signers wouldn't produce.
+pub(crate) fn validate_eip712_domain<
+    ChainSpecT: ProviderSpec<TimerT>,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    typed_data: &TypedData,
+) -> Result<(), ProviderErrorForChainSpec<ChainSpecT>> {
+    const EIP712_DOMAIN_TYPE_NAME: &str = "EIP712Domain";
+
+    if !typed_data
+        .resolver
+        .contains_type_name(EIP712_DOMAIN_TYPE_NAME)
+    {
+        return Ok(());
+    }
+
+    let declared = typed_data.resolver.encode_type(EIP712_DOMAIN_TYPE_NAME)?;
+
+    let domain = &typed_data.domain;
+    let expected_fields = [
+        domain.name.as_ref().map(|_| "string name"),
+        domain.version.as_ref().map(|_| "string version"),
+        domain.chain_id.map(|_| "uint256 chainId"),
+        domain
+            .verifying_contract
+            .map(|_| "address verifyingContract"),
+        domain.salt.map(|_| "bytes32 salt"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    let mut declared_fields = declared
+        .strip_prefix(EIP712_DOMAIN_TYPE_NAME)
+        .and_then(|fields| fields.strip_prefix('('))
+        .and_then(|fields| fields.strip_suffix(')'))
+        .map(|fields| {
+            fields
+                .split(',')
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut sorted_expected_fields = expected_fields.clone();
+    sorted_expected_fields.sort_unstable();
+    declared_fields.sort_unstable();
+
+    if declared_fields == sorted_expected_fields {
+        Ok(())
+    } else {
+        Err(ProviderError::InvalidEip712Domain {
+            declared,
+            expected: format!("{EIP712_DOMAIN_TYPE_NAME}({})", expected_fields.join(",")),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use edr_chain_l1::L1ChainSpec;
@@ -1,24 +1,34 @@
 use std::time::Instant;
 
+use edr_block_header::Withdrawal;
 use edr_chain_spec::ExecutableTransaction;
 use edr_mem_pool::MemPool;
-use edr_primitives::Address;
+use edr_primitives::{Address, HashMap, B256, U256};
 use edr_state_api::irregular::IrregularState;
+use edr_transaction::pooled::eip4844::BlobTransactionSidecar;
 use edr_utils::random::RandomHashGenerator;
-use rpds::HashTrieMapSync;
+use rpds::{HashTrieMapSync, RedBlackTreeMapSync};
 
-use crate::data::StateId;
+use crate::{data::StateId, requests::hardhat::rpc_types::NextBlockHeaderOverrides};
 
 pub(crate) struct Snapshot<SignedTransactionT: ExecutableTransaction> {
+    pub blob_sidecars: HashTrieMapSync<B256, BlobTransactionSidecar>,
     pub block_number: u64,
-    pub block_number_to_state_id: HashTrieMapSync<u64, StateId>,
+    pub block_number_to_state_id: RedBlackTreeMapSync<u64, StateId>,
     pub block_time_offset_seconds: i64,
     pub coinbase: Address,
     pub irregular_state: IrregularState,
     pub mem_pool: MemPool<SignedTransactionT>,
     pub next_block_base_fee_per_gas: Option<u128>,
+    pub next_block_difficulty: Option<U256>,
+    pub next_block_header_overrides: Option<NextBlockHeaderOverrides>,
+    pub next_block_state_root: Option<B256>,
     pub next_block_timestamp: Option<u64>,
+    pub next_block_withdrawals: Option<Vec<Withdrawal>>,
     pub parent_beacon_block_root_generator: RandomHashGenerator,
     pub prev_randao_generator: RandomHashGenerator,
+    pub queued_deposits: Vec<SignedTransactionT>,
+    pub sponsored_transactions: HashMap<B256, (Address, U256)>,
     pub time: Instant,
+    pub transaction_tags: HashMap<B256, Vec<String>>,
 }
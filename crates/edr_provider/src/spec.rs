@@ -17,7 +17,9 @@ use edr_eth::{Blob, BlockSpec};
 use edr_primitives::{Address, B256};
 use edr_runtime::overrides::StateOverrides;
 use edr_signer::{FakeSign, Sign};
-use edr_transaction::{IsSupported, TransactionAndBlock};
+use edr_transaction::{
+    pooled::eip4844::BlobTransactionSidecar, IsDeposit, IsSupported, TransactionAndBlock,
+};
 
 use crate::{
     data::ProviderData, error::ProviderErrorForChainSpec, time::TimeSinceEpoch,
@@ -117,13 +119,15 @@ pub type TransactionAndBlockForChainSpec<ChainSpecT> = TransactionAndBlock<
 
 pub trait ProviderSpec<TimerT: Clone + TimeSinceEpoch>:
     ProviderChainSpec<
+    HaltReason: serde::Serialize,
     RpcBlock<B256>: From<BlockAndTotalDifficulty<Arc<Self::Block>, Self::SignedTransaction>>,
-    RpcCallRequest: MaybeSender,
-    RpcTransactionRequest: Sender,
-    SignedTransaction: IsSupported,
+    RpcCallRequest: From<L1CallRequest> + MaybeSender,
+    RpcTransactionRequest: MaybeGasLimit + Sender,
+    SignedTransaction: alloy_rlp::Decodable + IsDeposit + IsSupported,
 >
 {
     type PooledTransaction: HardforkValidationData
+        + PooledBlobSidecar
         + Into<Self::SignedTransaction>
         + alloy_rlp::Decodable
         + ExecutableTransaction;
@@ -196,6 +200,22 @@ pub trait HardforkValidationData {
     fn authorization_list(&self) -> Option<&Vec<edr_eip7702::SignedAuthorization>>;
 }
 
+/// Trait for retrieving the blob sidecar of a pooled transaction.
+pub trait PooledBlobSidecar {
+    /// Returns the blobs, commitments, and proofs of the transaction, if it's
+    /// an EIP-4844 transaction.
+    fn blob_sidecar(&self) -> Option<BlobTransactionSidecar>;
+}
+
+impl PooledBlobSidecar for edr_chain_l1::L1PooledTransaction {
+    fn blob_sidecar(&self) -> Option<BlobTransactionSidecar> {
+        match self {
+            edr_chain_l1::L1PooledTransaction::Eip4844(tx) => Some(tx.to_sidecar()),
+            _ => None,
+        }
+    }
+}
+
 /// Trait for retrieving the sender of a request, if any.
 pub trait MaybeSender {
     /// Retrieves the sender of the request, if any.
@@ -208,6 +228,18 @@ impl MaybeSender for L1CallRequest {
     }
 }
 
+/// Trait for retrieving the gas limit of a request, if any.
+pub trait MaybeGasLimit {
+    /// Retrieves the gas limit of the request, if any.
+    fn maybe_gas_limit(&self) -> Option<u64>;
+}
+
+impl MaybeGasLimit for TransactionRequest {
+    fn maybe_gas_limit(&self) -> Option<u64> {
+        self.gas
+    }
+}
+
 /// Trait for retrieving the sender of a request.
 pub trait Sender {
     /// Retrieves the sender of the request.
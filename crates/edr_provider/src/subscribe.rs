@@ -18,6 +18,7 @@ pub enum SubscriptionEventData<BlockT: ?Sized, SignedTransactionT> {
     Logs(Vec<LogOutput>),
     NewHeads(BlockAndTotalDifficulty<Arc<BlockT>, SignedTransactionT>),
     NewPendingTransactions(B256),
+    DroppedTransactions(B256),
 }
 
 /// Supertrait for subscription callbacks.
@@ -1,5 +1,5 @@
 use core::fmt::Debug;
-use std::{num::NonZeroU64, sync::Arc, time::SystemTime};
+use std::{collections::BTreeMap, num::NonZeroU64, sync::Arc, time::SystemTime};
 
 use anyhow::anyhow;
 use edr_block_api::Block as _;
@@ -9,20 +9,26 @@ use edr_chain_l1::{
     L1ChainSpec,
 };
 use edr_chain_spec::TransactionValidation;
+use edr_chain_spec_block::BlockChainSpec;
 use edr_primitives::{Address, Bytes, HashMap, B256, KECCAK_NULL_RLP, U160, U256};
+use edr_rpc_eth::client::TransportConfig;
 use edr_signer::{public_key_to_address, secret_key_from_str, SignatureWithYParity};
 use edr_solidity::contract_decoder::ContractDecoder;
-use edr_transaction::{request::TransactionRequestAndSender, TxKind};
+use edr_transaction::{
+    request::TransactionRequestAndSender, IsEip155, IsEip4844, TransactionMut, TransactionType,
+    TxKind,
+};
 use k256::SecretKey;
+use serde::de::DeserializeOwned;
 use tokio::runtime;
 
 use crate::{
     config,
     error::ProviderErrorForChainSpec,
     observability,
-    time::{CurrentTime, TimeSinceEpoch},
+    time::{CurrentTime, TimeMode, TimeSinceEpoch},
     AccountOverride, ForkConfig, MethodInvocation, NoopLogger, Provider, ProviderConfig,
-    ProviderData, ProviderRequest, ProviderSpec, SyncProviderSpec,
+    ProviderData, ProviderRequest, ProviderSpec, SyncProviderSpec, SyncSubscriberCallback,
 };
 
 pub const TEST_SECRET_KEY: &str =
@@ -112,16 +118,31 @@ pub fn create_test_config_with_fork<HardforkT: Default>(
     ProviderConfig {
         allow_blocks_with_same_timestamp: false,
         allow_unlimited_contract_size: false,
+        allow_unlimited_init_code_size: false,
+        auto_fund: false,
+        auto_impersonate: false,
         bail_on_call_failure: false,
         bail_on_transaction_failure: false,
         base_fee_params: None,
         // SAFETY: literal is non-zero
         block_gas_limit: unsafe { NonZeroU64::new_unchecked(30_000_000) },
+        block_gas_limit_schedule: BTreeMap::new(),
         chain_id: 123,
+        checkpoint_dir: edr_defaults::CHECKPOINT_DIR.into(),
         coinbase: Address::from(U160::from(1)),
+        deploy_deterministic_deployment_proxy: false,
+        disable_balance_check: false,
+        disable_base_fee_adjustment: false,
+        disable_base_fee_check: false,
+        disable_priority_fee_check: false,
         fork,
+        gas_sponsor: None,
+        genesis_extra_data: None,
         genesis_state,
         hardfork: HardforkT::default(),
+        hardfork_activations: BTreeMap::new(),
+        index_address_transactions: false,
+        index_token_transfers: false,
         initial_base_fee_per_gas: Some(1000000000),
         initial_blob_gas: Some(BlobGas {
             gas_used: 0,
@@ -129,12 +150,20 @@ pub fn create_test_config_with_fork<HardforkT: Default>(
         }),
         initial_date: Some(SystemTime::now()),
         initial_parent_beacon_block_root: Some(KECCAK_NULL_RLP),
+        max_cached_states: None,
+        max_logs_block_range: None,
+        max_logs_results: None,
+        method_permissions: config::MethodPermissions::default(),
         min_gas_price: 0,
         mining: config::Mining::default(),
         network_id: 123,
         observability: observability::ObservabilityConfig::default(),
         owned_accounts,
         precompile_overrides: HashMap::default(),
+        prev_randao_seed: None,
+        record_state_diffs: false,
+        state_pruning: config::StatePruning::default(),
+        time_mode: TimeMode::default(),
         transaction_gas_cap: None,
     }
 }
@@ -156,6 +185,114 @@ pub fn pending_base_fee<
     Ok(base_fee)
 }
 
+/// Constructs an L1 provider with the provided config, using a no-op logger
+/// and subscriber. Must be called from within a multi-threaded Tokio runtime.
+pub fn create_provider(
+    config: ProviderConfig<edr_chain_l1::Hardfork>,
+) -> anyhow::Result<Provider<L1ChainSpec>> {
+    create_provider_with_timer(config, CurrentTime)
+}
+
+/// Constructs an L1 provider with the provided config and timer, using a no-op
+/// logger and subscriber. Must be called from within a multi-threaded Tokio
+/// runtime.
+pub fn create_provider_with_timer<TimerT: Clone + TimeSinceEpoch>(
+    config: ProviderConfig<edr_chain_l1::Hardfork>,
+    timer: TimerT,
+) -> anyhow::Result<Provider<L1ChainSpec, TimerT>> {
+    create_provider_with_subscriber(config, timer, Box::new(|_event| {}))
+}
+
+/// Constructs an L1 provider with the provided config, timer, and subscriber,
+/// using a no-op logger. Must be called from within a multi-threaded Tokio
+/// runtime.
+pub fn create_provider_with_subscriber<TimerT: Clone + TimeSinceEpoch>(
+    config: ProviderConfig<edr_chain_l1::Hardfork>,
+    timer: TimerT,
+    subscriber: Box<
+        dyn SyncSubscriberCallback<
+            <L1ChainSpec as BlockChainSpec>::Block,
+            edr_chain_l1::L1SignedTransaction,
+        >,
+    >,
+) -> anyhow::Result<Provider<L1ChainSpec, TimerT>> {
+    let logger = Box::new(NoopLogger::<L1ChainSpec, TimerT>::default());
+
+    let provider = Provider::new(
+        runtime::Handle::current(),
+        logger,
+        subscriber,
+        config,
+        Arc::<ContractDecoder>::default(),
+        timer,
+    )?;
+
+    Ok(provider)
+}
+
+/// Constructs a provider for the chain with the provided config, using a no-op
+/// logger and subscriber. Must be called from within a multi-threaded Tokio
+/// runtime.
+pub fn create_provider_for_chain_spec<
+    ChainSpecT: Debug
+        + SyncProviderSpec<
+            CurrentTime,
+            SignedTransaction: Default + TransactionValidation<ValidationError: PartialEq>,
+        >,
+>(
+    config: ProviderConfig<ChainSpecT::Hardfork>,
+) -> anyhow::Result<Provider<ChainSpecT>> {
+    let logger = Box::new(NoopLogger::<ChainSpecT>::default());
+    let subscriber = Box::new(|_event| {});
+
+    let provider = Provider::new(
+        runtime::Handle::current(),
+        logger,
+        subscriber,
+        config,
+        Arc::<ContractDecoder>::default(),
+        CurrentTime,
+    )?;
+
+    Ok(provider)
+}
+
+/// Handles a single request and deserializes its result.
+pub fn request<
+    ResultT: DeserializeOwned,
+    ChainSpecT: Debug
+        + SyncProviderSpec<
+            TimerT,
+            PooledTransaction: IsEip155,
+            SignedTransaction: Default
+                                   + TransactionMut
+                                   + TransactionType<Type: IsEip4844>
+                                   + TransactionValidation<ValidationError: PartialEq>,
+        >,
+    TimerT: Clone + TimeSinceEpoch,
+>(
+    provider: &Provider<ChainSpecT, TimerT>,
+    method: MethodInvocation<ChainSpecT>,
+) -> anyhow::Result<ResultT> {
+    let response = provider.handle_request(ProviderRequest::with_single(method))?;
+
+    Ok(serde_json::from_value(response.result)?)
+}
+
+/// Returns the address of the config's owned account at the provided index.
+///
+/// # Panics
+///
+/// Panics if the config doesn't have enough owned accounts.
+pub fn owned_account<HardforkT>(config: &ProviderConfig<HardforkT>, index: usize) -> Address {
+    let secret_key = config
+        .owned_accounts
+        .get(index)
+        .expect("the requested owned account does not exist");
+
+    public_key_to_address(secret_key.public_key())
+}
+
 /// Deploys a contract with the provided code. Returns the address of the
 /// contract.
 pub fn deploy_contract<TimerT>(
@@ -219,7 +356,10 @@ where
             cache_dir: edr_defaults::CACHE_DIR.into(),
             chain_overrides: HashMap::default(),
             http_headers: None,
+            live: false,
+            transport: TransportConfig::default(),
             url: json_rpc_url,
+            verified_sources: None,
         });
 
         let config = create_test_config_with_fork(fork);
@@ -1,4 +1,4 @@
-use std::time::{SystemTime, SystemTimeError};
+use std::time::{Instant, SystemTime, SystemTimeError};
 
 use auto_impl::auto_impl;
 #[cfg(any(test, feature = "test-utils"))]
@@ -33,6 +33,71 @@ impl TimeSinceEpoch for CurrentTime {
     }
 }
 
+/// The mode that determines how block timestamps advance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeMode {
+    /// Block timestamps follow the wall clock, shifted by an offset that is
+    /// changed by `evm_increaseTime`, `evm_setNextBlockTimestamp`, and
+    /// `evm_setTime`.
+    #[default]
+    Drift,
+    /// The clock is frozen at the time the provider is created. Block
+    /// timestamps only advance through `evm_increaseTime`,
+    /// `evm_setNextBlockTimestamp`, and `evm_setTime`, or by a single second
+    /// when a new block would otherwise have the same timestamp as its parent.
+    Frozen,
+}
+
+/// The clock used for block timestamps, according to a [`TimeMode`].
+#[derive(Clone, Copy, Debug)]
+pub struct BlockClock {
+    /// The time at which the clock was frozen, in seconds since the Unix
+    /// epoch. Only set in [`TimeMode::Frozen`].
+    frozen_at: Option<u64>,
+}
+
+impl BlockClock {
+    /// Constructs a new instance with the provided mode. A frozen clock is
+    /// frozen at the timer's current time.
+    pub fn new(mode: TimeMode, timer: &impl TimeSinceEpoch) -> Self {
+        let frozen_at = match mode {
+            TimeMode::Drift => None,
+            TimeMode::Frozen => Some(timer.since_epoch()),
+        };
+
+        Self { frozen_at }
+    }
+
+    /// Returns the clock's mode.
+    pub fn mode(&self) -> TimeMode {
+        if self.frozen_at.is_some() {
+            TimeMode::Frozen
+        } else {
+            TimeMode::Drift
+        }
+    }
+
+    /// Returns the current time in seconds since the Unix epoch.
+    ///
+    /// In [`TimeMode::Frozen`], this never changes.
+    pub fn now(&self, timer: &impl TimeSinceEpoch) -> u64 {
+        self.frozen_at.unwrap_or_else(|| timer.since_epoch())
+    }
+
+    /// Returns the number of seconds that elapsed on the clock since the
+    /// provided instant.
+    ///
+    /// In [`TimeMode::Frozen`], this is always zero.
+    pub fn elapsed_since(&self, instant: Instant) -> u64 {
+        if self.frozen_at.is_some() {
+            0
+        } else {
+            instant.elapsed().as_secs()
+        }
+    }
+}
+
 #[cfg(any(test, feature = "test-utils"))]
 mod test_utils {
     use std::{
@@ -108,4 +173,34 @@ mod tests {
         let mock_time = MockTime::with_seconds(start_time + ADDED_SECONDS);
         assert_eq!(mock_time.since_epoch(), start_time + ADDED_SECONDS);
     }
+
+    #[test]
+    fn block_clock_drift_follows_timer() {
+        const ADDED_SECONDS: u64 = 10;
+
+        let mock_time = MockTime::now();
+        let start_time = mock_time.since_epoch();
+
+        let clock = BlockClock::new(TimeMode::Drift, &mock_time);
+        assert_eq!(clock.mode(), TimeMode::Drift);
+        assert_eq!(clock.now(&mock_time), start_time);
+
+        mock_time.add_seconds(ADDED_SECONDS);
+        assert_eq!(clock.now(&mock_time), start_time + ADDED_SECONDS);
+    }
+
+    #[test]
+    fn block_clock_frozen_ignores_timer() {
+        const ADDED_SECONDS: u64 = 10;
+
+        let mock_time = MockTime::now();
+        let start_time = mock_time.since_epoch();
+
+        let clock = BlockClock::new(TimeMode::Frozen, &mock_time);
+        assert_eq!(clock.mode(), TimeMode::Frozen);
+        assert_eq!(clock.now(&mock_time), start_time);
+
+        mock_time.add_seconds(ADDED_SECONDS);
+        assert_eq!(clock.now(&mock_time), start_time);
+    }
 }
@@ -0,0 +1,220 @@
+//! Index of the ERC-20 and ERC-721 `Transfer` events of mined blocks, used by
+//! `hardhat_tokenTransfers` and `hardhat_tokenBalances`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use edr_primitives::{b256, Address, HashMap, B256, U256};
+use edr_receipt::log::FilterLog;
+
+use crate::requests::hardhat::rpc_types::{TokenBalance, TokenStandard, TokenTransfer};
+
+/// The topic of `Transfer(address,address,uint256)`, which ERC-20 and ERC-721
+/// share. ERC-721 indexes the third parameter, while ERC-20 doesn't.
+const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+#[derive(Debug, Default)]
+pub(crate) struct TokenTransferIndex {
+    /// Transfers in the order in which they occurred.
+    transfers: Vec<TokenTransfer>,
+    /// The holdings of each address that result from the transfers of pruned
+    /// blocks.
+    pruned_holdings: HashMap<Address, TokenHoldings>,
+    /// The number of the first block whose transfers haven't been pruned.
+    first_unpruned_block_number: u64,
+    /// Whether pruned blocks were removed from the blockchain, in which case
+    /// `pruned_holdings` include transfers that no longer occurred.
+    are_pruned_holdings_stale: bool,
+}
+
+impl TokenTransferIndex {
+    /// Indexes the transfers among the logs of a newly mined block.
+    ///
+    /// Any previously indexed blocks with the same or a higher number have
+    /// been removed from the blockchain - e.g. by `evm_revert` - so their
+    /// transfers are removed from the index.
+    pub fn insert_block<'log>(
+        &mut self,
+        block_number: u64,
+        logs: impl IntoIterator<Item = &'log FilterLog>,
+    ) {
+        if block_number < self.first_unpruned_block_number {
+            self.are_pruned_holdings_stale = true;
+        }
+
+        let first_stale_index = self
+            .transfers
+            .partition_point(|transfer| transfer.block_number < block_number);
+        self.transfers.truncate(first_stale_index);
+
+        self.transfers
+            .extend(logs.into_iter().filter_map(decode_transfer));
+    }
+
+    /// Removes the transfers in blocks before the provided block number,
+    /// retaining the holdings that result from them.
+    pub fn prune_blocks_before(&mut self, block_number: u64) {
+        if block_number <= self.first_unpruned_block_number {
+            return;
+        }
+
+        let first_retained_index = self
+            .transfers
+            .partition_point(|transfer| transfer.block_number < block_number);
+
+        for transfer in self.transfers.drain(..first_retained_index) {
+            self.pruned_holdings
+                .entry(transfer.from)
+                .or_default()
+                .apply(&transfer, &transfer.from);
+            self.pruned_holdings
+                .entry(transfer.to)
+                .or_default()
+                .apply(&transfer, &transfer.to);
+        }
+
+        self.first_unpruned_block_number = block_number;
+    }
+
+    /// Returns the transfers in blocks up to and including the provided block
+    /// number, in the order in which they occurred. Transfers of pruned blocks
+    /// are omitted.
+    pub fn transfers(&self, last_block_number: u64) -> &[TokenTransfer] {
+        let end = self
+            .transfers
+            .partition_point(|transfer| transfer.block_number <= last_block_number);
+
+        &self.transfers[..end]
+    }
+
+    /// Computes the holdings of the provided address that result from the
+    /// transfers up to and including the provided block number, or `None` if
+    /// they depend on transfers of pruned blocks that are no longer known.
+    pub fn holdings(&self, address: &Address, block_number: u64) -> Option<TokenHoldings> {
+        if self.are_pruned_holdings_stale
+            || block_number.saturating_add(1) < self.first_unpruned_block_number
+        {
+            return None;
+        }
+
+        let mut holdings = self
+            .pruned_holdings
+            .get(address)
+            .cloned()
+            .unwrap_or_default();
+
+        for transfer in self.transfers(block_number) {
+            holdings.apply(transfer, address);
+        }
+
+        Some(holdings)
+    }
+}
+
+/// The tokens that an address holds as a result of its transfers.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TokenHoldings {
+    /// The received and sent amounts per ERC-20 token.
+    amounts: BTreeMap<Address, (U256, U256)>,
+    /// The owned token IDs per ERC-721 token.
+    owned_token_ids: BTreeMap<Address, BTreeSet<U256>>,
+}
+
+impl TokenHoldings {
+    /// Applies a transfer to the holdings of the provided address.
+    fn apply(&mut self, transfer: &TokenTransfer, address: &Address) {
+        match transfer.standard {
+            TokenStandard::Erc20 => {
+                if transfer.to == *address {
+                    let (received, _sent) = self.amounts.entry(transfer.token).or_default();
+                    *received = received.saturating_add(transfer.value);
+                }
+                if transfer.from == *address {
+                    let (_received, sent) = self.amounts.entry(transfer.token).or_default();
+                    *sent = sent.saturating_add(transfer.value);
+                }
+            }
+            TokenStandard::Erc721 => {
+                let token_ids = self.owned_token_ids.entry(transfer.token).or_default();
+                if transfer.from == *address {
+                    token_ids.remove(&transfer.value);
+                }
+                if transfer.to == *address {
+                    token_ids.insert(transfer.value);
+                }
+            }
+        }
+    }
+
+    /// Returns the ERC-20 tokens that the address sent or received.
+    pub fn erc20_tokens(&self) -> impl Iterator<Item = &Address> {
+        self.amounts.keys()
+    }
+
+    /// Converts the holdings into balances, starting from the provided
+    /// initial ERC-20 balances. Tokens with a zero balance are omitted.
+    pub fn into_balances(
+        self,
+        initial_erc20_balances: &HashMap<Address, U256>,
+    ) -> Vec<TokenBalance> {
+        let erc20_balances = self
+            .amounts
+            .into_iter()
+            .map(|(token, (received, sent))| {
+                let initial_balance = initial_erc20_balances
+                    .get(&token)
+                    .copied()
+                    .unwrap_or(U256::ZERO);
+
+                TokenBalance {
+                    token,
+                    standard: TokenStandard::Erc20,
+                    balance: initial_balance
+                        .saturating_add(received)
+                        .saturating_sub(sent),
+                    token_ids: None,
+                }
+            })
+            .filter(|balance| balance.balance > U256::ZERO);
+
+        let erc721_balances = self
+            .owned_token_ids
+            .into_iter()
+            .filter(|(_token, token_ids)| !token_ids.is_empty())
+            .map(|(token, token_ids)| TokenBalance {
+                token,
+                standard: TokenStandard::Erc721,
+                balance: U256::from(token_ids.len()),
+                token_ids: Some(token_ids.into_iter().collect()),
+            });
+
+        erc20_balances.chain(erc721_balances).collect()
+    }
+}
+
+/// Decodes an ERC-20 or ERC-721 transfer from a log, if it is one.
+fn decode_transfer(log: &FilterLog) -> Option<TokenTransfer> {
+    let topics = log.topics();
+    if topics.first() != Some(&TRANSFER_EVENT_TOPIC) {
+        return None;
+    }
+
+    let data = &log.data.data;
+    let (standard, value) = match topics.len() {
+        3 if data.len() == 32 => (TokenStandard::Erc20, U256::from_be_slice(data)),
+        4 if data.is_empty() => (TokenStandard::Erc721, U256::from_be_bytes(topics[3].0)),
+        _ => return None,
+    };
+
+    Some(TokenTransfer {
+        block_number: log.block_number,
+        transaction_hash: log.transaction_hash,
+        transaction_index: log.transaction_index,
+        log_index: log.log_index,
+        token: log.address,
+        standard,
+        from: Address::from_word(topics[1]),
+        to: Address::from_word(topics[2]),
+        value,
+    })
+}
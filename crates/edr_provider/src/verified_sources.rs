@@ -0,0 +1,797 @@
+//! Fetching of the verified sources of forked contracts from Sourcify and
+//! Etherscan, to decode the traces of third-party contracts.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use alloy_json_abi::JsonAbi;
+use edr_chain_spec::HaltReasonTrait;
+use edr_primitives::{Address, Bytes, HashSet};
+use edr_solidity::{
+    contract_decoder::ContractDecoder,
+    external_artifact::{create_contract_metadata_from_artifact, ExternalArtifact},
+};
+use edr_tracing::{Trace, TraceMessage};
+use serde::{Deserialize, Serialize};
+use tokio::runtime;
+
+use crate::config::VerifiedSources;
+
+const DEFAULT_ETHERSCAN_URL: &str = "https://api.etherscan.io/v2/api";
+const DEFAULT_SOURCIFY_URL: &str = "https://sourcify.dev/server";
+
+/// The name of the directory within the fork's cache directory in which
+/// fetched sources are stored.
+const CACHE_DIR_NAME: &str = "verified_sources";
+
+/// The `ABI` value of Etherscan's response for an unverified contract.
+const ETHERSCAN_UNVERIFIED_ABI: &str = "Contract source code not verified";
+
+/// The maximum duration of a request to Sourcify or Etherscan.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The duration for which a contract is cached as unverified. Afterwards, its
+/// source is fetched again, as it might have been verified in the meantime.
+const UNVERIFIED_CACHE_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// An error that occurred while fetching a verified source.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum VerifiedSourceError {
+    /// The Etherscan API returned an error.
+    #[error("Etherscan request failed: {0}")]
+    Etherscan(String),
+    /// The cache couldn't be read or written.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A response or cache entry couldn't be (de)serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The HTTP request failed.
+    #[error(transparent)]
+    Request(reqwest::Error),
+}
+
+impl From<reqwest::Error> for VerifiedSourceError {
+    fn from(value: reqwest::Error) -> Self {
+        // The URL of Etherscan requests contains the API key, so it's removed to
+        // avoid leaking it in logs.
+        Self::Request(value.without_url())
+    }
+}
+
+/// The verified source of a contract, as stored in the cache.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VerifiedSource {
+    pub contract_name: String,
+    pub source_name: Option<String>,
+    pub abi: JsonAbi,
+    pub compiler_version: String,
+}
+
+/// The result of looking up the verified source of a contract, as stored in
+/// the cache.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+enum CacheEntry {
+    Verified {
+        source: VerifiedSource,
+    },
+    Unverified {
+        /// The time at which the contract was looked up, in seconds since the
+        /// Unix epoch.
+        checked_at: u64,
+    },
+}
+
+/// Tracks the forked contracts whose verified sources need to be fetched.
+#[derive(Debug)]
+pub(crate) struct VerifiedSourceTracker {
+    fetcher: Arc<VerifiedSourceFetcher>,
+    /// Addresses whose sources were already looked up in this session.
+    attempted: HashSet<Address>,
+    /// Addresses of contracts that were deployed locally, which don't have a
+    /// verified source.
+    local_contracts: HashSet<Address>,
+}
+
+impl VerifiedSourceTracker {
+    /// Creates a tracker that fetches sources using the provided fetcher.
+    pub fn new(fetcher: VerifiedSourceFetcher) -> Self {
+        Self {
+            fetcher: Arc::new(fetcher),
+            attempted: HashSet::default(),
+            local_contracts: HashSet::default(),
+        }
+    }
+
+    /// Marks the contract at the provided address as locally deployed.
+    pub fn add_local_contract(&mut self, address: Address) {
+        self.local_contracts.insert(address);
+    }
+
+    /// Marks the contracts created in the traces as locally deployed.
+    pub fn add_local_contracts<HaltReasonT: HaltReasonTrait>(
+        &mut self,
+        traces: &[Trace<HaltReasonT>],
+    ) {
+        let created_contracts =
+            traces
+                .iter()
+                .flat_map(|trace| &trace.messages)
+                .filter_map(|message| match message {
+                    TraceMessage::After(after) => after.contract_address,
+                    _ => None,
+                });
+
+        self.local_contracts.extend(created_contracts);
+    }
+
+    /// Returns a lookup of the verified sources of the called forked
+    /// contracts in the traces that the contract decoder doesn't recognize
+    /// yet, if any.
+    ///
+    /// Each contract is only looked up once per session.
+    pub fn lookup<HaltReasonT: HaltReasonTrait>(
+        &mut self,
+        contract_decoder: &Arc<ContractDecoder>,
+        traces: &[Trace<HaltReasonT>],
+    ) -> Option<VerifiedSourceLookup> {
+        self.add_local_contracts(traces);
+
+        let contracts = traces
+            .iter()
+            .flat_map(|trace| &trace.messages)
+            .filter_map(|message| match message {
+                TraceMessage::Before(before) => {
+                    let address = before.code_address?;
+                    let code = before.code.as_ref()?.original_bytes();
+
+                    Some((address, code))
+                }
+                _ => None,
+            })
+            .filter(|(address, code)| {
+                !code.is_empty()
+                    && !self.local_contracts.contains(address)
+                    && !self.attempted.contains(address)
+                    && contract_decoder
+                        .get_contract_metadata(code, false)
+                        .is_none()
+            })
+            .collect::<Vec<_>>();
+
+        // A contract can be called multiple times in the traces
+        let contracts = contracts
+            .into_iter()
+            .filter(|(address, _code)| self.attempted.insert(*address))
+            .collect::<Vec<_>>();
+
+        if contracts.is_empty() {
+            return None;
+        }
+
+        Some(VerifiedSourceLookup {
+            fetcher: Arc::clone(&self.fetcher),
+            contract_decoder: Arc::clone(contract_decoder),
+            contracts,
+        })
+    }
+}
+
+/// A pending lookup of the verified sources of forked contracts.
+#[derive(Debug)]
+pub(crate) struct VerifiedSourceLookup {
+    fetcher: Arc<VerifiedSourceFetcher>,
+    contract_decoder: Arc<ContractDecoder>,
+    /// The addresses and deployed bytecodes of the contracts.
+    contracts: Vec<(Address, Bytes)>,
+}
+
+impl VerifiedSourceLookup {
+    /// Fetches the verified sources of the contracts concurrently in the
+    /// background and registers them with the contract decoder as they
+    /// arrive.
+    ///
+    /// Returns without waiting for the sources, so they're only used to decode
+    /// the traces of subsequent requests. Failures are logged rather than
+    /// returned, as they shouldn't fail the request that produced the traces.
+    pub fn register(self, runtime: &runtime::Handle) {
+        let Self {
+            fetcher,
+            contract_decoder,
+            contracts,
+        } = self;
+
+        for (address, deployed_bytecode) in contracts {
+            let fetcher = Arc::clone(&fetcher);
+            let contract_decoder = Arc::clone(&contract_decoder);
+
+            runtime.spawn(async move {
+                let source = match fetcher.verified_source(&address).await {
+                    Ok(Some(source)) => source,
+                    Ok(None) => return,
+                    Err(error) => {
+                        log::warn!("Failed to fetch the verified source of {address}: {error}");
+                        return;
+                    }
+                };
+
+                if let Err(error) = register_source(&contract_decoder, source, deployed_bytecode) {
+                    log::debug!("Failed to register the verified source of {address}: {error}");
+                }
+            });
+        }
+    }
+}
+
+/// Fetches the verified sources of forked contracts from Sourcify and
+/// Etherscan.
+#[derive(Debug)]
+pub(crate) struct VerifiedSourceFetcher {
+    chain_id: u64,
+    client: reqwest::Client,
+    config: VerifiedSources,
+    cache_dir: PathBuf,
+}
+
+impl VerifiedSourceFetcher {
+    /// Creates a fetcher for the provided chain, caching sources in the
+    /// fork's cache directory.
+    pub fn new(
+        config: VerifiedSources,
+        fork_cache_dir: &Path,
+        chain_id: u64,
+    ) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            chain_id,
+            client,
+            config,
+            cache_dir: fork_cache_dir
+                .join(CACHE_DIR_NAME)
+                .join(chain_id.to_string()),
+        })
+    }
+
+    /// Returns the verified source of the contract at the provided address,
+    /// from the cache if possible.
+    async fn verified_source(
+        &self,
+        address: &Address,
+    ) -> Result<Option<VerifiedSource>, VerifiedSourceError> {
+        let cache_path = self.cache_dir.join(format!("{address:#x}.json"));
+        if let Some(entry) = read_cache_entry(&cache_path) {
+            match entry {
+                CacheEntry::Verified { source } => return Ok(Some(source)),
+                CacheEntry::Unverified { checked_at } => {
+                    let elapsed = unix_time().saturating_sub(checked_at);
+                    if elapsed < UNVERIFIED_CACHE_DURATION.as_secs() {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        let source = match self.fetch_from_sourcify(address).await {
+            Ok(Some(source)) => Some(source),
+            sourcify_result => {
+                if self.config.etherscan_api_key.is_some() {
+                    // Sourcify not knowing the contract or being unavailable is treated as a
+                    // miss, so Etherscan is queried instead.
+                    if let Err(error) = &sourcify_result {
+                        log::debug!(
+                            "Failed to fetch the verified source of {address} from Sourcify: {error}"
+                        );
+                    }
+
+                    self.fetch_from_etherscan(address).await?
+                } else {
+                    sourcify_result?
+                }
+            }
+        };
+
+        // Contracts that are unverified are only cached for a limited time, as they
+        // might be verified later.
+        let entry = match &source {
+            Some(source) => CacheEntry::Verified {
+                source: source.clone(),
+            },
+            None => CacheEntry::Unverified {
+                checked_at: unix_time(),
+            },
+        };
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(&cache_path, serde_json::to_string(&entry)?)?;
+
+        Ok(source)
+    }
+
+    async fn fetch_from_sourcify(
+        &self,
+        address: &Address,
+    ) -> Result<Option<VerifiedSource>, VerifiedSourceError> {
+        let base_url = self
+            .config
+            .sourcify_url
+            .as_deref()
+            .unwrap_or(DEFAULT_SOURCIFY_URL)
+            .trim_end_matches('/');
+
+        let response = self
+            .client
+            .get(format!(
+                "{base_url}/v2/contract/{}/{address:#x}?fields=abi,compilation",
+                self.chain_id
+            ))
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response: SourcifyResponse = response.error_for_status()?.json().await?;
+        Ok(Some(response.into()))
+    }
+
+    async fn fetch_from_etherscan(
+        &self,
+        address: &Address,
+    ) -> Result<Option<VerifiedSource>, VerifiedSourceError> {
+        let url = self
+            .config
+            .etherscan_url
+            .as_deref()
+            .unwrap_or(DEFAULT_ETHERSCAN_URL);
+        let api_key = self.config.etherscan_api_key.as_deref().unwrap_or_default();
+
+        let response: EtherscanResponse = self
+            .client
+            .get(url)
+            .query(&[
+                ("chainid", self.chain_id.to_string()),
+                ("module", "contract".to_string()),
+                ("action", "getsourcecode".to_string()),
+                ("address", format!("{address:#x}")),
+                ("apikey", api_key.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        response.into_verified_source()
+    }
+}
+
+/// Reads a cache entry, ignoring missing or malformed entries.
+fn read_cache_entry(path: &Path) -> Option<CacheEntry> {
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Registers a verified source with the contract decoder.
+fn register_source(
+    contract_decoder: &ContractDecoder,
+    source: VerifiedSource,
+    deployed_bytecode: Bytes,
+) -> Result<(), edr_solidity::external_artifact::ExternalArtifactError> {
+    let artifact = ExternalArtifact {
+        contract_name: source.contract_name,
+        source_name: source.source_name,
+        abi: source.abi,
+        bytecode: None,
+        deployed_bytecode: Some(deployed_bytecode),
+        link_references: HashMap::new(),
+        deployed_link_references: HashMap::new(),
+        immutable_references: HashMap::new(),
+        compiler_version: source.compiler_version,
+    };
+
+    for metadata in create_contract_metadata_from_artifact(artifact)? {
+        contract_decoder.add_contract_metadata(metadata);
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyCompilation {
+    compiler_version: String,
+    name: String,
+    fully_qualified_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SourcifyResponse {
+    abi: JsonAbi,
+    compilation: SourcifyCompilation,
+}
+
+impl From<SourcifyResponse> for VerifiedSource {
+    fn from(value: SourcifyResponse) -> Self {
+        let SourcifyResponse { abi, compilation } = value;
+
+        let source_name = compilation
+            .fully_qualified_name
+            .as_deref()
+            .and_then(|name| name.rsplit_once(':'))
+            .map(|(source_name, _contract_name)| source_name.to_string());
+
+        Self {
+            contract_name: compilation.name,
+            source_name,
+            abi,
+            compiler_version: compilation.compiler_version,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EtherscanSourceCode {
+    #[serde(rename = "ABI")]
+    abi: String,
+    contract_name: String,
+    compiler_version: String,
+}
+
+#[derive(Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    result: serde_json::Value,
+}
+
+impl EtherscanResponse {
+    fn into_verified_source(self) -> Result<Option<VerifiedSource>, VerifiedSourceError> {
+        if self.status != "1" {
+            let message = self
+                .result
+                .as_str()
+                .map_or_else(|| self.result.to_string(), str::to_string);
+
+            return Err(VerifiedSourceError::Etherscan(message));
+        }
+
+        let results: Vec<EtherscanSourceCode> = serde_json::from_value(self.result)?;
+        let Some(result) = results.into_iter().next() else {
+            return Ok(None);
+        };
+
+        if result.abi == ETHERSCAN_UNVERIFIED_ABI || result.contract_name.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(VerifiedSource {
+            contract_name: result.contract_name,
+            source_name: None,
+            abi: serde_json::from_str(&result.abi)?,
+            compiler_version: result.compiler_version,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sourcify_response() -> anyhow::Result<()> {
+        let response: SourcifyResponse = serde_json::from_value(serde_json::json!({
+            "abi": [{
+                "type": "function",
+                "name": "totalSupply",
+                "inputs": [],
+                "outputs": [{ "name": "", "type": "uint256", "internalType": "uint256" }],
+                "stateMutability": "view"
+            }],
+            "compilation": {
+                "language": "Solidity",
+                "compiler": "solc",
+                "compilerVersion": "v0.8.24+commit.e11b9ed9",
+                "name": "Token",
+                "fullyQualifiedName": "src/Token.sol:Token"
+            },
+            "match": "exact_match"
+        }))?;
+
+        let source = VerifiedSource::from(response);
+        assert_eq!(source.contract_name, "Token");
+        assert_eq!(source.source_name.as_deref(), Some("src/Token.sol"));
+        assert_eq!(source.compiler_version, "v0.8.24+commit.e11b9ed9");
+        assert_eq!(source.abi.functions().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_etherscan_response() -> anyhow::Result<()> {
+        let response: EtherscanResponse = serde_json::from_value(serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{
+                "SourceCode": "contract Token {}",
+                "ABI": "[{\"type\":\"function\",\"name\":\"totalSupply\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\"}]",
+                "ContractName": "Token",
+                "CompilerVersion": "v0.8.24+commit.e11b9ed9"
+            }]
+        }))?;
+
+        let source = response
+            .into_verified_source()?
+            .expect("contract is verified");
+        assert_eq!(source.contract_name, "Token");
+        assert_eq!(source.abi.functions().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unverified_etherscan_contract_has_no_source() -> anyhow::Result<()> {
+        let response: EtherscanResponse = serde_json::from_value(serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{
+                "SourceCode": "",
+                "ABI": ETHERSCAN_UNVERIFIED_ABI,
+                "ContractName": "",
+                "CompilerVersion": ""
+            }]
+        }))?;
+
+        assert_eq!(response.into_verified_source()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn etherscan_error_is_returned() -> anyhow::Result<()> {
+        let response: EtherscanResponse = serde_json::from_value(serde_json::json!({
+            "status": "0",
+            "message": "NOTOK",
+            "result": "Invalid API Key"
+        }))?;
+
+        let error = response.into_verified_source().expect_err("request failed");
+        assert!(
+            matches!(error, VerifiedSourceError::Etherscan(message) if message == "Invalid API Key")
+        );
+
+        Ok(())
+    }
+
+    const ETHERSCAN_API_KEY: &str = "secret-api-key";
+
+    fn token_source() -> VerifiedSource {
+        VerifiedSource {
+            contract_name: "Token".to_string(),
+            source_name: None,
+            abi: JsonAbi::default(),
+            compiler_version: "v0.8.24+commit.e11b9ed9".to_string(),
+        }
+    }
+
+    fn create_fetcher(
+        server: &mockito::Server,
+        cache_dir: &Path,
+    ) -> anyhow::Result<VerifiedSourceFetcher> {
+        let config = VerifiedSources {
+            sourcify_url: Some(format!("{}/sourcify", server.url())),
+            etherscan_url: Some(format!("{}/etherscan", server.url())),
+            etherscan_api_key: Some(ETHERSCAN_API_KEY.to_string()),
+        };
+
+        Ok(VerifiedSourceFetcher::new(config, cache_dir, 1)?)
+    }
+
+    fn sourcify_path(address: &Address) -> mockito::Matcher {
+        mockito::Matcher::Regex(format!("^/sourcify/v2/contract/1/{address:#x}"))
+    }
+
+    fn etherscan_body(source: &VerifiedSource) -> anyhow::Result<String> {
+        let body = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{
+                "SourceCode": "contract Token {}",
+                "ABI": serde_json::to_string(&source.abi)?,
+                "ContractName": source.contract_name,
+                "CompilerVersion": source.compiler_version,
+            }]
+        });
+
+        Ok(body.to_string())
+    }
+
+    fn write_cache_entry(
+        fetcher: &VerifiedSourceFetcher,
+        address: &Address,
+        entry: &CacheEntry,
+    ) -> anyhow::Result<()> {
+        fs::create_dir_all(&fetcher.cache_dir)?;
+        fs::write(
+            fetcher.cache_dir.join(format!("{address:#x}.json")),
+            serde_json::to_string(entry)?,
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn verified_source_is_read_from_cache() -> anyhow::Result<()> {
+        let server = mockito::Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new()?;
+        let address = Address::random();
+
+        let fetcher = create_fetcher(&server, cache_dir.path())?;
+
+        let source = token_source();
+        write_cache_entry(
+            &fetcher,
+            &address,
+            &CacheEntry::Verified {
+                source: source.clone(),
+            },
+        )?;
+
+        // No mocks are registered, so any request would fail.
+        assert_eq!(fetcher.verified_source(&address).await?, Some(source));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn etherscan_is_queried_when_sourcify_fails() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new()?;
+        let address = Address::random();
+
+        let source = token_source();
+
+        let sourcify_mock = server
+            .mock("GET", sourcify_path(&address))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let etherscan_mock = server
+            .mock("GET", "/etherscan")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "address".to_string(),
+                format!("{address:#x}"),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(etherscan_body(&source)?)
+            .create_async()
+            .await;
+
+        let fetcher = create_fetcher(&server, cache_dir.path())?;
+        assert_eq!(fetcher.verified_source(&address).await?, Some(source));
+
+        sourcify_mock.assert_async().await;
+        etherscan_mock.assert_async().await;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unverified_contract_is_refetched_after_cache_expires() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new()?;
+        let address = Address::random();
+
+        let fetcher = create_fetcher(&server, cache_dir.path())?;
+
+        // A recently checked contract is served from the cache
+        write_cache_entry(
+            &fetcher,
+            &address,
+            &CacheEntry::Unverified {
+                checked_at: unix_time(),
+            },
+        )?;
+        assert_eq!(fetcher.verified_source(&address).await?, None);
+
+        // An expired entry is refetched
+        write_cache_entry(
+            &fetcher,
+            &address,
+            &CacheEntry::Unverified {
+                checked_at: unix_time() - UNVERIFIED_CACHE_DURATION.as_secs() - 1,
+            },
+        )?;
+
+        let source = token_source();
+
+        let sourcify_mock = server
+            .mock("GET", sourcify_path(&address))
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let etherscan_mock = server
+            .mock("GET", "/etherscan")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(etherscan_body(&source)?)
+            .create_async()
+            .await;
+
+        assert_eq!(
+            fetcher.verified_source(&address).await?,
+            Some(source.clone())
+        );
+
+        sourcify_mock.assert_async().await;
+        etherscan_mock.assert_async().await;
+
+        // The verified source replaced the expired entry
+        let cache_path = fetcher.cache_dir.join(format!("{address:#x}.json"));
+        assert_eq!(
+            read_cache_entry(&cache_path),
+            Some(CacheEntry::Verified { source })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn failed_lookup_is_not_cached() -> anyhow::Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new()?;
+        let address = Address::random();
+
+        let sourcify_mock = server
+            .mock("GET", sourcify_path(&address))
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let etherscan_mock = server
+            .mock("GET", "/etherscan")
+            .match_query(mockito::Matcher::Any)
+            .with_status(502)
+            .create_async()
+            .await;
+
+        let fetcher = create_fetcher(&server, cache_dir.path())?;
+        let error = fetcher
+            .verified_source(&address)
+            .await
+            .expect_err("Etherscan request failed");
+
+        // The URL contains the API key, so it mustn't be part of the error.
+        assert!(!error.to_string().contains(ETHERSCAN_API_KEY));
+        assert!(!format!("{error:?}").contains(ETHERSCAN_API_KEY));
+
+        sourcify_mock.assert_async().await;
+        etherscan_mock.assert_async().await;
+
+        let cache_path = fetcher.cache_dir.join(format!("{address:#x}.json"));
+        assert!(!cache_path.exists());
+
+        Ok(())
+    }
+}
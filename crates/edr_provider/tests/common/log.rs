@@ -0,0 +1,48 @@
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes, B256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider,
+};
+
+/// Runtime code that emits a log with the first calldata word as its single
+/// topic: PUSH1 0x00 CALLDATALOAD PUSH1 0x00 PUSH1 0x00 LOG1 STOP
+pub const EMITTER_CODE: [u8; 9] = [0x60, 0x00, 0x35, 0x60, 0x00, 0x60, 0x00, 0xa1, 0x00];
+
+/// Creates a provider with an emitter contract, deployed at a random address.
+/// Returns the provider, an owned account, and the emitter's address.
+pub fn create_emitting_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address, Address)> {
+    let config = create_test_config();
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    let emitter = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(emitter, Bytes::from_static(&EMITTER_CODE)),
+    )?;
+
+    Ok((provider, sender, emitter))
+}
+
+/// Sends a transaction from the sender to the emitter that emits a log with
+/// the provided topic.
+pub fn emit_log(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    emitter: Address,
+    topic: B256,
+) -> anyhow::Result<()> {
+    request::<B256, _, _>(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(emitter),
+            data: Some(topic.to_vec().into()),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 /// Test utilities for blob transactions.
 #[allow(dead_code)]
 pub mod blob;
+/// Test utilities for contracts that emit logs.
+#[cfg(feature = "test-utils")]
+#[allow(dead_code)]
+pub mod log;
 
 pub fn help_test_method_invocation_serde<MethodInvocation>(call: MethodInvocation)
 where
@@ -0,0 +1,82 @@
+#![cfg(feature = "test-utils")]
+
+use edr_primitives::U64;
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    BatchRequest, MethodInvocation, ProviderRequest,
+};
+use edr_rpc_eth::jsonrpc;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_request_isolates_errors() -> anyhow::Result<()> {
+    let config = create_test_config();
+    let chain_id = config.chain_id;
+
+    let provider = create_provider(config)?;
+
+    let response = provider.handle_request(ProviderRequest::Batch(vec![
+        BatchRequest::new(jsonrpc::Id::Num(1), MethodInvocation::BlockNumber(())),
+        BatchRequest::new(
+            jsonrpc::Id::Str("gas-limit".to_owned()),
+            MethodInvocation::EvmSetBlockGasLimit(U64::ZERO),
+        ),
+        BatchRequest::new(jsonrpc::Id::Num(3), MethodInvocation::ChainId(())),
+    ]))?;
+
+    assert_eq!(
+        response.result,
+        json!([
+            { "jsonrpc": "2.0", "id": 1, "result": "0x0" },
+            {
+                "jsonrpc": "2.0",
+                "id": "gas-limit",
+                "error": {
+                    "code": -32000,
+                    "message": "Block gas limit must be greater than 0",
+                    "data": null,
+                }
+            },
+            { "jsonrpc": "2.0", "id": 3, "result": format!("{chain_id:#x}") },
+        ])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_request_omits_responses_to_notifications() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let request: ProviderRequest<edr_chain_l1::L1ChainSpec> = serde_json::from_value(json!([
+        { "jsonrpc": "2.0", "method": "evm_mine", "params": [] },
+        { "jsonrpc": "2.0", "id": 1, "method": "eth_blockNumber", "params": [] },
+    ]))?;
+
+    let response = provider.handle_request(request)?;
+
+    assert_eq!(
+        response.result,
+        json!([{ "jsonrpc": "2.0", "id": 1, "result": "0x1" }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_request_of_notifications_has_no_response() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let batch: ProviderRequest<edr_chain_l1::L1ChainSpec> = serde_json::from_value(json!([
+        { "jsonrpc": "2.0", "method": "evm_mine", "params": [] },
+        { "jsonrpc": "2.0", "method": "evm_mine", "params": [] },
+    ]))?;
+
+    let response = provider.handle_request(batch)?;
+    assert_eq!(response.result, serde_json::Value::Null);
+
+    let block_number: U64 = request(&provider, MethodInvocation::BlockNumber(()))?;
+    assert_eq!(block_number, U64::from(2));
+
+    Ok(())
+}
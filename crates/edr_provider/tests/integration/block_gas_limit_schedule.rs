@@ -0,0 +1,124 @@
+#![cfg(feature = "test-utils")]
+
+use std::{collections::BTreeMap, num::NonZeroU64};
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{B256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+const INITIAL_GAS_LIMIT: u64 = 30_000_000;
+const FIRST_SCHEDULED_GAS_LIMIT: u64 = 20_000_000;
+const SECOND_SCHEDULED_GAS_LIMIT: u64 = 25_000_000;
+
+fn create_scheduled_provider(
+    schedule: BTreeMap<u64, u64>,
+) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.block_gas_limit = NonZeroU64::new(INITIAL_GAS_LIMIT).expect("non-zero");
+    config.block_gas_limit_schedule = schedule
+        .into_iter()
+        .map(|(block_number, gas_limit)| {
+            (block_number, NonZeroU64::new(gas_limit).expect("non-zero"))
+        })
+        .collect();
+
+    create_provider(config)
+}
+
+fn block_gas_limit(provider: &Provider<L1ChainSpec>, block_number: u64) -> anyhow::Result<u64> {
+    let block: L1RpcBlock<B256> = request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::Number(block_number), false),
+    )?;
+    Ok(block.gas_limit)
+}
+
+fn mine_blocks(provider: &Provider<L1ChainSpec>, count: u64) -> anyhow::Result<()> {
+    request::<bool, _, _>(provider, MethodInvocation::Mine(Some(count), None))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applies_scheduled_gas_limits() -> anyhow::Result<()> {
+    let provider = create_scheduled_provider(BTreeMap::from([
+        (2, FIRST_SCHEDULED_GAS_LIMIT),
+        (4, SECOND_SCHEDULED_GAS_LIMIT),
+    ]))?;
+
+    mine_blocks(&provider, 3)?;
+
+    assert_eq!(block_gas_limit(&provider, 1)?, INITIAL_GAS_LIMIT);
+    assert_eq!(block_gas_limit(&provider, 2)?, FIRST_SCHEDULED_GAS_LIMIT);
+    assert_eq!(block_gas_limit(&provider, 3)?, FIRST_SCHEDULED_GAS_LIMIT);
+
+    // A runtime change is replaced by the next scheduled gas limit
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::EvmSetBlockGasLimit(U64::from(15_000_000)),
+    )?;
+    mine_blocks(&provider, 1)?;
+
+    assert_eq!(block_gas_limit(&provider, 4)?, SECOND_SCHEDULED_GAS_LIMIT);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runtime_change_persists() -> anyhow::Result<()> {
+    let provider = create_scheduled_provider(BTreeMap::from([(2, FIRST_SCHEDULED_GAS_LIMIT)]))?;
+
+    mine_blocks(&provider, 2)?;
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::EvmSetBlockGasLimit(U64::from(15_000_000)),
+    )?;
+    mine_blocks(&provider, 2)?;
+
+    assert_eq!(block_gas_limit(&provider, 3)?, 15_000_000);
+    assert_eq!(block_gas_limit(&provider, 4)?, 15_000_000);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_gas_limit_within_reserved_blocks() -> anyhow::Result<()> {
+    const SCHEDULED_BLOCK_NUMBER: u64 = 10;
+    const BLOCK_COUNT: u64 = 20;
+
+    let provider = create_scheduled_provider(BTreeMap::from([(
+        SCHEDULED_BLOCK_NUMBER,
+        FIRST_SCHEDULED_GAS_LIMIT,
+    )]))?;
+
+    // Mining many empty blocks reserves most of them
+    mine_blocks(&provider, BLOCK_COUNT)?;
+
+    assert_eq!(
+        block_gas_limit(&provider, BLOCK_COUNT)?,
+        FIRST_SCHEDULED_GAS_LIMIT
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_gas_limit_at_genesis() -> anyhow::Result<()> {
+    let provider = create_scheduled_provider(BTreeMap::from([
+        (0, FIRST_SCHEDULED_GAS_LIMIT),
+        (2, SECOND_SCHEDULED_GAS_LIMIT),
+    ]))?;
+
+    mine_blocks(&provider, 2)?;
+
+    assert_eq!(block_gas_limit(&provider, 0)?, FIRST_SCHEDULED_GAS_LIMIT);
+    assert_eq!(block_gas_limit(&provider, 1)?, FIRST_SCHEDULED_GAS_LIMIT);
+    assert_eq!(block_gas_limit(&provider, 2)?, SECOND_SCHEDULED_GAS_LIMIT);
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, call::L1CallRequest, TransactionRequest},
+    InvalidTransaction, L1ChainSpec,
+};
+use edr_chain_spec_evm::TransactionError;
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    hardhat_rpc_types::{BundleSimulation, BundleTransactionRequest, CallBundleRequest},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+fn create_bundle_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn sign_transaction(
+    provider: &Provider<L1ChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<Bytes> {
+    request(provider, MethodInvocation::SignTransaction(transaction))
+}
+
+fn balance(provider: &Provider<L1ChainSpec>, address: Address) -> anyhow::Result<U256> {
+    request(provider, MethodInvocation::GetBalance(address, None))
+}
+
+fn get_block(
+    provider: &Provider<L1ChainSpec>,
+    block_spec: PreEip1898BlockSpec,
+) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(block_spec, false),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simulates_unsigned_bundle_without_committing() -> anyhow::Result<()> {
+    let (provider, sender) = create_bundle_provider()?;
+    let recipient = Address::random();
+    let coinbase: Address = request(&provider, MethodInvocation::Coinbase(()))?;
+    let coinbase_balance = balance(&provider, coinbase)?;
+
+    let simulation: BundleSimulation = request(
+        &provider,
+        MethodInvocation::SimulateBundle(
+            vec![
+                BundleTransactionRequest::Unsigned(L1CallRequest {
+                    from: Some(sender),
+                    to: Some(recipient),
+                    value: Some(U256::from(100)),
+                    ..L1CallRequest::default()
+                }),
+                BundleTransactionRequest::Unsigned(L1CallRequest {
+                    from: Some(sender),
+                    to: Some(coinbase),
+                    value: Some(U256::from(1_000)),
+                    ..L1CallRequest::default()
+                }),
+            ],
+            None,
+        ),
+    )?;
+
+    assert_eq!(simulation.results.len(), 2);
+    assert_eq!(simulation.total_gas_used, 42_000);
+    assert!(simulation
+        .results
+        .iter()
+        .all(|result| result.error.is_none()));
+
+    // Unsigned transactions are executed with a gas price of zero
+    assert_eq!(simulation.gas_fees, U256::ZERO);
+    assert_eq!(simulation.coinbase_diff, U256::from(1_000));
+    assert_eq!(simulation.results[0].eth_sent_to_coinbase, U256::ZERO);
+    assert_eq!(
+        simulation.results[1].eth_sent_to_coinbase,
+        U256::from(1_000)
+    );
+
+    assert_eq!(balance(&provider, recipient)?, U256::ZERO);
+    assert_eq!(balance(&provider, coinbase)?, coinbase_balance);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_bundle_executes_signed_transactions_in_order() -> anyhow::Result<()> {
+    let (provider, sender) = create_bundle_provider()?;
+    let recipient = Address::random();
+    let coinbase: Address = request(&provider, MethodInvocation::Coinbase(()))?;
+
+    let sign = |nonce: u64, to: Address, value: u64| -> anyhow::Result<Bytes> {
+        sign_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                to: Some(to),
+                value: Some(U256::from(value)),
+                nonce: Some(nonce),
+                ..TransactionRequest::default()
+            },
+        )
+    };
+
+    let txs = vec![sign(0, recipient, 100)?, sign(1, coinbase, 1_000)?];
+
+    let simulation: BundleSimulation = request(
+        &provider,
+        MethodInvocation::CallBundle(CallBundleRequest {
+            txs,
+            block_number: None,
+            state_block_number: None,
+            timestamp: None,
+            coinbase: None,
+        }),
+    )?;
+
+    assert_eq!(simulation.state_block_number, 0);
+    assert_eq!(simulation.results.len(), 2);
+    assert!(simulation
+        .results
+        .iter()
+        .all(|result| result.error.is_none() && result.from_address == sender));
+
+    assert_eq!(
+        simulation.results[1].eth_sent_to_coinbase,
+        U256::from(1_000)
+    );
+    assert_eq!(
+        simulation.coinbase_diff,
+        simulation.gas_fees + U256::from(1_000)
+    );
+    assert_eq!(simulation.eth_sent_to_coinbase, U256::from(1_000));
+
+    assert_eq!(balance(&provider, recipient)?, U256::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_bundle_executes_in_child_block() -> anyhow::Result<()> {
+    let (provider, sender) = create_bundle_provider()?;
+
+    // NUMBER PUSH1 0x00 MSTORE TIMESTAMP PUSH1 0x20 MSTORE BASEFEE PUSH1 0x40
+    // MSTORE PUSH1 0x60 PUSH1 0x00 RETURN
+    let init_code = Bytes::from_static(&[
+        0x43, 0x60, 0x00, 0x52, 0x42, 0x60, 0x20, 0x52, 0x48, 0x60, 0x40, 0x52, 0x60, 0x60, 0x60,
+        0x00, 0xf3,
+    ]);
+
+    let txs = vec![sign_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(init_code),
+            nonce: Some(0),
+            ..TransactionRequest::default()
+        },
+    )?];
+
+    let simulation: BundleSimulation = request(
+        &provider,
+        MethodInvocation::CallBundle(CallBundleRequest {
+            txs,
+            block_number: None,
+            state_block_number: None,
+            timestamp: None,
+            coinbase: None,
+        }),
+    )?;
+    assert_eq!(simulation.state_block_number, 0);
+
+    let state_block = get_block(&provider, PreEip1898BlockSpec::Number(0))?;
+    let pending_block = get_block(&provider, PreEip1898BlockSpec::pending())?;
+
+    let returned = &simulation.results[0].value;
+    assert_eq!(U256::from_be_slice(&returned[..32]), U256::from(1));
+    assert_eq!(
+        U256::from_be_slice(&returned[32..64]),
+        U256::from(state_block.timestamp + 1)
+    );
+
+    // The base fee is that of the next block, which differs from the state
+    // block's as the latter is empty
+    let base_fee = U256::from_be_slice(&returned[64..]);
+    assert_eq!(
+        Some(base_fee),
+        pending_block.base_fee_per_gas.map(U256::from)
+    );
+    assert_ne!(Some(base_fee), state_block.base_fee_per_gas.map(U256::from));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_bundle_applies_block_overrides() -> anyhow::Result<()> {
+    const BLOCK_NUMBER: u64 = 100;
+    const TIMESTAMP: u64 = 2_000_000_000;
+
+    let (provider, sender) = create_bundle_provider()?;
+    let coinbase = Address::random();
+
+    // NUMBER PUSH1 0x00 MSTORE TIMESTAMP PUSH1 0x20 MSTORE PUSH1 0x40 PUSH1 0x00
+    // RETURN
+    let init_code = Bytes::from_static(&[
+        0x43, 0x60, 0x00, 0x52, 0x42, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00, 0xf3,
+    ]);
+
+    let txs = vec![
+        sign_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                data: Some(init_code),
+                nonce: Some(0),
+                ..TransactionRequest::default()
+            },
+        )?,
+        sign_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                to: Some(coinbase),
+                value: Some(U256::from(1_000)),
+                nonce: Some(1),
+                ..TransactionRequest::default()
+            },
+        )?,
+    ];
+
+    let simulation: BundleSimulation = request(
+        &provider,
+        MethodInvocation::CallBundle(CallBundleRequest {
+            txs,
+            block_number: Some(BLOCK_NUMBER),
+            state_block_number: None,
+            timestamp: Some(TIMESTAMP),
+            coinbase: Some(coinbase),
+        }),
+    )?;
+
+    // The state block isn't affected by the overrides
+    assert_eq!(simulation.state_block_number, 0);
+
+    let returned = &simulation.results[0].value;
+    assert_eq!(
+        U256::from_be_slice(&returned[..32]),
+        U256::from(BLOCK_NUMBER)
+    );
+    assert_eq!(U256::from_be_slice(&returned[32..]), U256::from(TIMESTAMP));
+
+    // Fees and transfers are paid to the overridden coinbase
+    assert_eq!(simulation.eth_sent_to_coinbase, U256::from(1_000));
+    assert_eq!(
+        simulation.coinbase_diff,
+        simulation.gas_fees + U256::from(1_000)
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_bundle_rejects_invalid_nonce() -> anyhow::Result<()> {
+    let (provider, sender) = create_bundle_provider()?;
+
+    let raw_transaction = sign_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            nonce: Some(1),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::CallBundle(CallBundleRequest {
+            txs: vec![raw_transaction],
+            block_number: None,
+            state_block_number: None,
+            timestamp: None,
+            coinbase: None,
+        }),
+    ));
+    assert!(
+        matches!(
+            result,
+            Err(ProviderError::RunTransaction(
+                TransactionError::InvalidTransaction(InvalidTransaction::NonceTooHigh {
+                    tx: 1,
+                    state: 0
+                })
+            ))
+        ),
+        "{result:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "test-utils")]
+
+use std::time::{Duration, SystemTime};
+
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    ChainImportError, MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+fn create_provider_with_fixed_genesis() -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    // Both providers need to have the same genesis block
+    config.initial_date = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+
+    create_provider(config)
+}
+
+fn latest_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+fn export_chain(provider: &Provider<L1ChainSpec>) -> anyhow::Result<Bytes> {
+    request(provider, MethodInvocation::ExportChain(()))
+}
+
+fn import_chain(
+    provider: &Provider<L1ChainSpec>,
+    chain: Bytes,
+) -> Result<(), edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::ImportChain(
+        chain,
+    )))?;
+
+    Ok(())
+}
+
+/// Mines a block with a transaction of an impersonated account and modifies
+/// the state outside of any transaction.
+fn create_chain(provider: &Provider<L1ChainSpec>) -> anyhow::Result<Address> {
+    let sender = Address::random();
+
+    request::<bool, _, _>(
+        provider,
+        MethodInvocation::ImpersonateAccount(sender.into()),
+    )?;
+    request::<bool, _, _>(
+        provider,
+        MethodInvocation::SetBalance(sender, U256::from(10).pow(U256::from(18))),
+    )?;
+    request::<B256, _, _>(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let modified = Address::random();
+    request::<bool, _, _>(
+        provider,
+        MethodInvocation::SetStorageAt(modified, U256::from(1), U256::from(2)),
+    )?;
+    request::<bool, _, _>(provider, MethodInvocation::Mine(Some(2), None))?;
+
+    Ok(modified)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn export_and_import_chain() -> anyhow::Result<()> {
+    let exporter = create_provider_with_fixed_genesis()?;
+    let modified = create_chain(&exporter)?;
+
+    let chain = export_chain(&exporter)?;
+
+    let importer = create_provider_with_fixed_genesis()?;
+    import_chain(&importer, chain)?;
+
+    let expected_block = latest_block(&exporter)?;
+    let imported_block = latest_block(&importer)?;
+    assert_eq!(imported_block.number, expected_block.number);
+    assert_eq!(imported_block.hash, expected_block.hash);
+
+    let value: U256 = request(
+        &importer,
+        MethodInvocation::GetStorageAt(modified, U256::from(1), None),
+    )?;
+    assert_eq!(value, U256::from(2));
+
+    // The imported chain can be resumed
+    request::<bool, _, _>(&importer, MethodInvocation::Mine(Some(1), None))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_chain_after_local_blocks() -> anyhow::Result<()> {
+    let exporter = create_provider_with_fixed_genesis()?;
+    create_chain(&exporter)?;
+
+    let chain = export_chain(&exporter)?;
+
+    let importer = create_provider_with_fixed_genesis()?;
+    request::<bool, _, _>(&importer, MethodInvocation::Mine(Some(1), None))?;
+
+    let result = import_chain(&importer, chain);
+    assert!(matches!(
+        result,
+        Err(ProviderError::ChainImport(
+            ChainImportError::LocalBlocksExist {
+                base_block_number: 0,
+                latest_block_number: 1,
+            }
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_chain_invalid_data() -> anyhow::Result<()> {
+    let importer = create_provider_with_fixed_genesis()?;
+
+    let result = import_chain(&importer, Bytes::from_static(b"not a chain"));
+    assert!(matches!(
+        result,
+        Err(ProviderError::ChainImport(ChainImportError::InvalidMagic))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "test-utils")]
+
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    CheckpointError, MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+fn create_checkpointing_provider(checkpoint_dir: &Path) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.checkpoint_dir = checkpoint_dir.to_path_buf();
+    // All providers need to have the same genesis block
+    config.initial_date = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+
+    create_provider(config)
+}
+
+fn latest_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn save_and_load_checkpoint() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+
+    let saver = create_checkpointing_provider(checkpoint_dir.path())?;
+
+    let modified = Address::random();
+    request::<bool, _, _>(
+        &saver,
+        MethodInvocation::SetStorageAt(modified, U256::from(1), U256::from(2)),
+    )?;
+    request::<bool, _, _>(&saver, MethodInvocation::Mine(Some(3), None))?;
+    request::<bool, _, _>(
+        &saver,
+        MethodInvocation::SaveCheckpoint(String::from("fixture")),
+    )?;
+
+    assert!(checkpoint_dir.path().join("fixture.edrchain").exists());
+
+    let loader = create_checkpointing_provider(checkpoint_dir.path())?;
+    request::<bool, _, _>(
+        &loader,
+        MethodInvocation::LoadCheckpoint(String::from("fixture")),
+    )?;
+
+    let expected_block = latest_block(&saver)?;
+    let loaded_block = latest_block(&loader)?;
+    assert_eq!(loaded_block.number, expected_block.number);
+    assert_eq!(loaded_block.hash, expected_block.hash);
+
+    let value: U256 = request(
+        &loader,
+        MethodInvocation::GetStorageAt(modified, U256::from(1), None),
+    )?;
+    assert_eq!(value, U256::from(2));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn load_missing_checkpoint() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let provider = create_checkpointing_provider(checkpoint_dir.path())?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::LoadCheckpoint(String::from("missing")),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::Checkpoint(CheckpointError::NotFound { .. }))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reject_checkpoint_names_outside_directory() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let provider = create_checkpointing_provider(checkpoint_dir.path())?;
+
+    for name in ["../fixture", "nested/fixture", ".hidden", ""] {
+        let result = provider.handle_request(ProviderRequest::with_single(
+            MethodInvocation::SaveCheckpoint(name.to_owned()),
+        ));
+        assert!(matches!(
+            result,
+            Err(ProviderError::Checkpoint(
+                CheckpointError::InvalidName { .. }
+            ))
+        ));
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_saves_of_same_checkpoint() -> anyhow::Result<()> {
+    const NUM_PROVIDERS: usize = 4;
+
+    let checkpoint_dir = tempfile::tempdir()?;
+    let providers = (0..NUM_PROVIDERS)
+        .map(|_| create_checkpointing_provider(checkpoint_dir.path()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    std::thread::scope(|scope| {
+        let handles = providers
+            .iter()
+            .map(|provider| {
+                scope.spawn(|| {
+                    request::<bool, _, _>(
+                        provider,
+                        MethodInvocation::SaveCheckpoint(String::from("fixture")),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            let result = handle.join().expect("thread should not panic");
+            assert!(result.is_ok(), "{result:?}");
+        }
+    });
+
+    // Temporary files don't remain
+    let file_names = std::fs::read_dir(checkpoint_dir.path())?
+        .map(|entry| Ok(entry?.file_name()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    assert_eq!(file_names, ["fixture.edrchain"]);
+
+    Ok(())
+}
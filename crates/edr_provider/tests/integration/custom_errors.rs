@@ -0,0 +1,144 @@
+#![cfg(feature = "test-utils")]
+
+use std::collections::HashMap;
+
+use alloy_sol_types::{sol, SolError as _};
+use edr_chain_l1::{rpc::call::L1CallRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account},
+    MethodInvocation, Provider, ProviderError, ProviderErrorForChainSpec, ProviderRequest,
+};
+use edr_solidity::external_artifact::ExternalArtifact;
+
+sol! {
+    error OwnableUnauthorizedAccount(address account);
+    error VaultLocked(address caller);
+}
+
+fn create_bailing_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.bail_on_call_failure = true;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+/// Init code that reverts with a custom error with the provided selector and
+/// the caller as its single argument.
+fn reverting_init_code(selector: [u8; 4]) -> Bytes {
+    // PUSH4 <selector> PUSH1 0xe0 SHL PUSH1 0x00 MSTORE
+    let mut code = vec![0x63];
+    code.extend_from_slice(&selector);
+    code.extend_from_slice(&[0x60, 0xe0, 0x1b, 0x60, 0x00, 0x52]);
+    // CALLER PUSH1 0x04 MSTORE PUSH1 0x24 PUSH1 0x00 REVERT
+    code.extend_from_slice(&[0x33, 0x60, 0x04, 0x52, 0x60, 0x24, 0x60, 0x00, 0xfd]);
+
+    code.into()
+}
+
+fn call_error(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    selector: [u8; 4],
+) -> ProviderErrorForChainSpec<L1ChainSpec> {
+    let result = provider.handle_request(ProviderRequest::with_single(MethodInvocation::Call(
+        L1CallRequest {
+            from: Some(sender),
+            data: Some(reverting_init_code(selector)),
+            ..L1CallRequest::default()
+        },
+        None,
+        None,
+    )));
+
+    result.expect_err("call should revert")
+}
+
+fn call_error_message(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    selector: [u8; 4],
+) -> String {
+    call_error(provider, sender, selector).to_string()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn built_in_custom_error_is_decoded() -> anyhow::Result<()> {
+    let (provider, sender) = create_bailing_provider()?;
+
+    let message = call_error_message(&provider, sender, OwnableUnauthorizedAccount::SELECTOR);
+    assert_eq!(
+        message,
+        format!(
+            "VM Exception while processing transaction: reverted with custom error 'OwnableUnauthorizedAccount(\"{sender}\")'"
+        )
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn registered_custom_error_is_decoded() -> anyhow::Result<()> {
+    let (provider, sender) = create_bailing_provider()?;
+
+    let message = call_error_message(&provider, sender, VaultLocked::SELECTOR);
+    assert!(message.contains("unrecognized custom error"));
+
+    let abi = serde_json::from_value(serde_json::json!([{
+        "type": "error",
+        "name": "VaultLocked",
+        "inputs": [{ "name": "caller", "type": "address", "internalType": "address" }]
+    }]))?;
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::AddContractArtifact(ExternalArtifact {
+            contract_name: "Vault".to_string(),
+            source_name: None,
+            abi,
+            bytecode: None,
+            deployed_bytecode: Some(Bytes::from_static(&[0x00])),
+            link_references: HashMap::new(),
+            deployed_link_references: HashMap::new(),
+            immutable_references: HashMap::new(),
+            compiler_version: "0.8.24".to_string(),
+        }),
+    ))?;
+
+    let message = call_error_message(&provider, sender, VaultLocked::SELECTOR);
+    assert_eq!(
+        message,
+        format!(
+            "VM Exception while processing transaction: reverted with custom error 'VaultLocked(\"{sender}\")'"
+        )
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn custom_error_is_included_in_failure_data() -> anyhow::Result<()> {
+    let (provider, sender) = create_bailing_provider()?;
+
+    let ProviderError::TransactionFailed(failure) =
+        call_error(&provider, sender, OwnableUnauthorizedAccount::SELECTOR)
+    else {
+        panic!("call should fail with a transaction failure");
+    };
+
+    let expected_custom_error = format!("OwnableUnauthorizedAccount(\"{sender}\")");
+    assert_eq!(
+        failure.failure.custom_error.as_deref(),
+        Some(expected_custom_error.as_str())
+    );
+    assert_eq!(
+        failure.failure.error_selector.as_deref(),
+        Some(OwnableUnauthorizedAccount::SELECTOR.as_slice())
+    );
+
+    let data = serde_json::to_value(&failure.failure)?;
+    assert_eq!(data["customError"], expected_custom_error);
+
+    Ok(())
+}
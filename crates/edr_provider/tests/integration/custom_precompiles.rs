@@ -0,0 +1,95 @@
+#![cfg(feature = "test-utils")]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use edr_chain_l1::{rpc::call::L1CallRequest, L1ChainSpec};
+use edr_precompile::{u64_to_address, CustomPrecompile, PrecompileError, PrecompileOutput};
+use edr_primitives::{Address, Bytes};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+const ECRECOVER_ADDRESS: Address = u64_to_address(0x01);
+const PRECOMPILE_GAS_COST: u64 = 100;
+
+/// Creates a precompile that echoes its input and counts how often it was
+/// called.
+fn counting_echo_precompile(counter: Arc<AtomicUsize>) -> CustomPrecompile {
+    CustomPrecompile::new(move |input, gas_limit| {
+        if gas_limit < PRECOMPILE_GAS_COST {
+            return Err(PrecompileError::OutOfGas);
+        }
+
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        Ok(PrecompileOutput::new(
+            PRECOMPILE_GAS_COST,
+            Bytes::copy_from_slice(input),
+        ))
+    })
+}
+
+fn call(provider: &Provider<L1ChainSpec>, to: Address, data: Bytes) -> anyhow::Result<Bytes> {
+    request(
+        provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                to: Some(to),
+                data: Some(data),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn closure_precompile_at_custom_address() -> anyhow::Result<()> {
+    let precompile_address = Address::random();
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let mut config = create_test_config();
+    config.precompile_overrides = [(
+        precompile_address,
+        counting_echo_precompile(counter.clone()),
+    )]
+    .into_iter()
+    .collect();
+
+    let provider = create_provider(config)?;
+
+    let input = Bytes::from_static(b"custom precompile");
+    let output = call(&provider, precompile_address, input.clone())?;
+
+    assert_eq!(output, input);
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn closure_precompile_overrides_builtin_precompile() -> anyhow::Result<()> {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let mut config = create_test_config();
+    config.precompile_overrides = [(ECRECOVER_ADDRESS, counting_echo_precompile(counter.clone()))]
+        .into_iter()
+        .collect();
+
+    let provider = create_provider(config)?;
+
+    // The built-in `ecrecover` precompile would return an empty output for
+    // invalid input
+    let input = Bytes::from_static(b"not a signature");
+    let output = call(&provider, ECRECOVER_ADDRESS, input.clone())?;
+
+    assert_eq!(output, input);
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    Ok(())
+}
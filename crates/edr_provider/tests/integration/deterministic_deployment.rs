@@ -0,0 +1,223 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{call::L1CallRequest, receipt::L1RpcTransactionReceipt, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_primitives::{address, bytes, Address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    AccountOverride, MethodInvocation, Provider, DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS,
+    DETERMINISTIC_DEPLOYMENT_PROXY_BYTECODE,
+};
+
+/// Init code that deploys the runtime code `0xfe`.
+const INIT_CODE: Bytes = bytes!("0x60fe60005360016000f3");
+
+fn create_provider_and_sender(
+    deploy_deterministic_deployment_proxy: bool,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.deploy_deterministic_deployment_proxy = deploy_deterministic_deployment_proxy;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn get_code(provider: &Provider<L1ChainSpec>, address: Address) -> anyhow::Result<Bytes> {
+    request(provider, MethodInvocation::GetCode(address, None))
+}
+
+/// Returns the calldata of a proxy deployment: the salt followed by the init
+/// code.
+fn proxy_calldata(salt: B256, init_code: &Bytes) -> Bytes {
+    [salt.as_slice(), init_code].concat().into()
+}
+
+fn call_proxy(provider: &Provider<L1ChainSpec>, data: Bytes) -> anyhow::Result<Bytes> {
+    request(
+        provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                to: Some(DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS),
+                data: Some(data),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deploys_proxy_at_genesis() -> anyhow::Result<()> {
+    let (provider, _sender) = create_provider_and_sender(true)?;
+
+    let code = get_code(&provider, DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS)?;
+    assert_eq!(code, DETERMINISTIC_DEPLOYMENT_PROXY_BYTECODE);
+
+    let (provider, _sender) = create_provider_and_sender(false)?;
+
+    let code = get_code(&provider, DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS)?;
+    assert!(code.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn genesis_state_takes_precedence_over_proxy() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.deploy_deterministic_deployment_proxy = true;
+    config.genesis_state.insert(
+        DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS,
+        AccountOverride {
+            balance: Some(U256::from(1_000)),
+            ..AccountOverride::default()
+        },
+    );
+
+    let provider = create_provider(config)?;
+
+    let code = get_code(&provider, DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS)?;
+    assert!(code.is_empty());
+
+    let balance: U256 = request(
+        &provider,
+        MethodInvocation::GetBalance(DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS, None),
+    )?;
+    assert_eq!(balance, U256::from(1_000));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicts_create2_address_of_eip1014_example() -> anyhow::Result<()> {
+    let (provider, _sender) = create_provider_and_sender(false)?;
+
+    // Example 0 of EIP-1014
+    let predicted_address: Address = request(
+        &provider,
+        MethodInvocation::PredictCreate2Address(Address::ZERO, B256::ZERO, bytes!("0x00")),
+    )?;
+    assert_eq!(
+        predicted_address,
+        address!("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicts_create2_address_of_proxy_deployment() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender(true)?;
+    let salt = B256::with_last_byte(1);
+
+    let predicted_address: Address = request(
+        &provider,
+        MethodInvocation::PredictCreate2Address(
+            DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS,
+            salt,
+            INIT_CODE,
+        ),
+    )?;
+
+    // The proxy returns the address of the created contract
+    let output = call_proxy(&provider, proxy_calldata(salt, &INIT_CODE))?;
+    assert_eq!(output.as_ref(), predicted_address.as_slice());
+
+    let _transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(DETERMINISTIC_DEPLOYMENT_PROXY_ADDRESS),
+            data: Some(proxy_calldata(salt, &INIT_CODE)),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let code = get_code(&provider, predicted_address)?;
+    assert_eq!(code, bytes!("0xfe"));
+
+    // Deploying to the same address again fails
+    let output = call_proxy(&provider, proxy_calldata(salt, &INIT_CODE))?;
+    assert_ne!(output.as_ref(), predicted_address.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicts_create_address_of_next_deployment() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender(false)?;
+
+    let predicted_address: Address = request(
+        &provider,
+        MethodInvocation::PredictCreateAddress(sender, None),
+    )?;
+    assert_eq!(predicted_address, sender.create(0));
+
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            data: Some(INIT_CODE),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let receipt: L1RpcTransactionReceipt = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    assert_eq!(receipt.contract_address, Some(predicted_address));
+
+    let predicted_address: Address = request(
+        &provider,
+        MethodInvocation::PredictCreateAddress(sender, Some(5)),
+    )?;
+    assert_eq!(predicted_address, sender.create(5));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicts_create_address_after_pending_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender(false)?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    // The pending transaction uses nonce 0
+    let predicted_address: Address = request(
+        &provider,
+        MethodInvocation::PredictCreateAddress(sender, None),
+    )?;
+    assert_eq!(predicted_address, sender.create(1));
+
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            data: Some(INIT_CODE),
+            ..TransactionRequest::default()
+        }),
+    )?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let receipt: Option<L1RpcTransactionReceipt> = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    let receipt = receipt.expect("transaction should be mined");
+    assert_eq!(receipt.contract_address, Some(predicted_address));
+
+    Ok(())
+}
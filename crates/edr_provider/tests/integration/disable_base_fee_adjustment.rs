@@ -0,0 +1,49 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{B256, U128};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+const INITIAL_BASE_FEE_PER_GAS: u128 = 1_000_000_000;
+
+fn mine_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request::<String, _, _>(provider, MethodInvocation::EvmMine(None))?;
+
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn base_fee_remains_constant() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.disable_base_fee_adjustment = true;
+    config.initial_base_fee_per_gas = Some(INITIAL_BASE_FEE_PER_GAS);
+
+    let provider = create_provider(config)?;
+
+    // Empty blocks would normally decrease the base fee
+    for _ in 0..2 {
+        let block = mine_block(&provider)?;
+        assert_eq!(block.base_fee_per_gas, Some(INITIAL_BASE_FEE_PER_GAS));
+    }
+
+    // An overridden base fee is used for subsequent blocks
+    let base_fee_per_gas = 2 * INITIAL_BASE_FEE_PER_GAS;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::AnvilSetNextBlockBaseFeePerGas(U128::from(base_fee_per_gas)),
+    )?;
+
+    for _ in 0..2 {
+        let block = mine_block(&provider)?;
+        assert_eq!(block.base_fee_per_gas, Some(base_fee_per_gas));
+    }
+
+    Ok(())
+}
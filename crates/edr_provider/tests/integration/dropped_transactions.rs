@@ -0,0 +1,194 @@
+#![cfg(feature = "test-utils")]
+
+use std::sync::{Arc, Mutex};
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::filter::SubscriptionType;
+use edr_primitives::{Address, B256, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::MempoolConfig,
+    test_utils::{create_provider_with_subscriber, create_test_config, owned_account, request},
+    time::MockTime,
+    MethodInvocation, Provider, SubscriptionEventData, TxpoolStatus,
+};
+
+struct Fixture {
+    provider: Provider<L1ChainSpec, Arc<MockTime>>,
+    sender: Address,
+    dropped_transactions: Arc<Mutex<Vec<B256>>>,
+    timer: Arc<MockTime>,
+}
+
+impl Fixture {
+    fn new() -> anyhow::Result<Self> {
+        let dropped_transactions = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = {
+            let dropped_transactions = dropped_transactions.clone();
+            Box::new(move |event: edr_provider::SubscriptionEvent<_, _>| {
+                if let SubscriptionEventData::DroppedTransactions(transaction_hash) = event.result {
+                    dropped_transactions
+                        .lock()
+                        .expect("lock is not poisoned")
+                        .push(transaction_hash);
+                }
+            })
+        };
+
+        let config = create_test_config();
+        let sender = owned_account(&config, 0);
+
+        let timer = Arc::new(MockTime::now());
+        let provider = create_provider_with_subscriber(config, timer.clone(), subscriber)?;
+
+        let fixture = Self {
+            provider,
+            sender,
+            dropped_transactions,
+            timer,
+        };
+
+        fixture.request::<bool>(MethodInvocation::EvmSetAutomine(false))?;
+        fixture.request::<U256>(MethodInvocation::Subscribe(
+            SubscriptionType::DroppedTransactions,
+            None,
+        ))?;
+
+        Ok(fixture)
+    }
+
+    fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: MethodInvocation<L1ChainSpec>,
+    ) -> anyhow::Result<T> {
+        request(&self.provider, method)
+    }
+
+    fn send_transaction(&self) -> anyhow::Result<B256> {
+        self.request(MethodInvocation::SendTransaction(TransactionRequest {
+            from: self.sender,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            ..TransactionRequest::default()
+        }))
+    }
+
+    fn dropped_transactions(&self) -> Vec<B256> {
+        let mut dropped_transactions = self
+            .dropped_transactions
+            .lock()
+            .expect("lock is not poisoned")
+            .clone();
+
+        dropped_transactions.sort();
+        dropped_transactions
+    }
+
+    fn txpool_status(&self) -> anyhow::Result<TxpoolStatus> {
+        self.request(MethodInvocation::TxpoolStatus(()))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drop_transaction() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+
+    let first_hash = fixture.send_transaction()?;
+    let second_hash = fixture.send_transaction()?;
+
+    let dropped_hash: Option<B256> =
+        fixture.request(MethodInvocation::AnvilDropTransaction(first_hash))?;
+    assert_eq!(dropped_hash, Some(first_hash));
+    assert_eq!(fixture.dropped_transactions(), vec![first_hash]);
+
+    // The sender's remaining transaction has a nonce gap
+    assert_eq!(
+        fixture.txpool_status()?,
+        TxpoolStatus {
+            pending: U64::from(0),
+            queued: U64::from(1),
+        }
+    );
+
+    let dropped_hash: Option<B256> =
+        fixture.request(MethodInvocation::AnvilDropTransaction(first_hash))?;
+    assert_eq!(dropped_hash, None);
+    assert_eq!(fixture.dropped_transactions(), vec![first_hash]);
+
+    let transaction: Option<serde_json::Value> =
+        fixture.request(MethodInvocation::GetTransactionByHash(second_hash))?;
+    assert!(transaction.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drop_all_transactions() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+
+    let mut transaction_hashes = vec![
+        fixture.send_transaction()?,
+        fixture.send_transaction()?,
+        fixture.send_transaction()?,
+    ];
+    transaction_hashes.sort();
+
+    let result: serde_json::Value =
+        fixture.request(MethodInvocation::AnvilDropAllTransactions(()))?;
+    assert_eq!(result, serde_json::Value::Null);
+
+    assert_eq!(fixture.dropped_transactions(), transaction_hashes);
+    assert_eq!(
+        fixture.txpool_status()?,
+        TxpoolStatus {
+            pending: U64::from(0),
+            queued: U64::from(0),
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn expired_transactions_are_dropped() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+
+    fixture.request::<bool>(MethodInvocation::SetMempoolConfig(MempoolConfig {
+        transaction_lifetime: Some(U64::from(1)),
+        ..MempoolConfig::default()
+    }))?;
+
+    let expired_hash = fixture.send_transaction()?;
+    fixture.timer.add_seconds(1);
+
+    // Expired transactions are dropped when the mem pool is modified
+    fixture.request::<bool>(MethodInvocation::Mine(None, None))?;
+
+    assert_eq!(fixture.dropped_transactions(), vec![expired_hash]);
+
+    let transaction: Option<serde_json::Value> =
+        fixture.request(MethodInvocation::GetTransactionByHash(expired_hash))?;
+    assert!(transaction.is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalidated_transactions_are_dropped() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+
+    let invalidated_hash = fixture.send_transaction()?;
+
+    // The sender can no longer pay for the pending transaction
+    fixture.request::<bool>(MethodInvocation::SetBalance(fixture.sender, U256::ZERO))?;
+
+    assert_eq!(fixture.dropped_transactions(), vec![invalidated_hash]);
+    assert_eq!(
+        fixture.txpool_status()?,
+        TxpoolStatus {
+            pending: U64::from(0),
+            queued: U64::from(0),
+        }
+    );
+
+    Ok(())
+}
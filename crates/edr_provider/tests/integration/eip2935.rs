@@ -0,0 +1,82 @@
+#![cfg(feature = "test-utils")]
+
+use alloy_eips::eip2935::HISTORY_STORAGE_ADDRESS;
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, call::L1CallRequest},
+    L1ChainSpec,
+};
+use edr_eth::{BlockSpec, PreEip1898BlockSpec};
+use edr_primitives::{Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+fn create_prague_provider() -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::PRAGUE;
+
+    create_provider(config)
+}
+
+fn get_block_hash(provider: &Provider<L1ChainSpec>, block_number: u64) -> anyhow::Result<B256> {
+    let block: L1RpcBlock<B256> = request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::Number(block_number), false),
+    )?;
+
+    Ok(block.hash.expect("Mined blocks have a hash"))
+}
+
+/// Calls the history storage contract to retrieve the hash of the block with
+/// the provided number.
+fn get_historical_block_hash(
+    provider: &Provider<L1ChainSpec>,
+    block_number: u64,
+) -> anyhow::Result<B256> {
+    let result: Bytes = request(
+        provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                to: Some(HISTORY_STORAGE_ADDRESS),
+                data: Some(Bytes::from(U256::from(block_number).to_be_bytes::<32>())),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )?;
+
+    Ok(B256::from_slice(&result))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mined_blocks_store_parent_block_hash() -> anyhow::Result<()> {
+    let provider = create_prague_provider()?;
+
+    for _ in 0..2 {
+        request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    }
+
+    for block_number in 0..2 {
+        assert_eq!(
+            get_historical_block_hash(&provider, block_number)?,
+            get_block_hash(&provider, block_number)?
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn history_storage_contract_is_deployed_with_nonce_one() -> anyhow::Result<()> {
+    let provider = create_prague_provider()?;
+
+    let nonce: U256 = request(
+        &provider,
+        MethodInvocation::GetTransactionCount(HISTORY_STORAGE_ADDRESS, Some(BlockSpec::Number(0))),
+    )?;
+    assert_eq!(nonce, U256::from(1));
+
+    Ok(())
+}
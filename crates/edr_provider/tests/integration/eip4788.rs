@@ -0,0 +1,136 @@
+#![cfg(feature = "test-utils")]
+
+use std::collections::BTreeMap;
+
+use alloy_eips::eip4788::BEACON_ROOTS_ADDRESS;
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, call::L1CallRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+fn create_provider_with_hardfork(
+    hardfork: edr_chain_l1::Hardfork,
+) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = hardfork;
+
+    create_provider(config)
+}
+
+/// Mines a block and asserts that its parent beacon block root was stored in
+/// the beacon roots contract.
+fn assert_mined_block_stores_parent_beacon_block_root(
+    provider: &Provider<L1ChainSpec>,
+) -> anyhow::Result<()> {
+    request::<String, _, _>(provider, MethodInvocation::EvmMine(None))?;
+
+    let block: L1RpcBlock<B256> = request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )?;
+
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("Cancun blocks have a parent beacon block root");
+
+    assert_eq!(
+        get_beacon_root(provider, block.timestamp)?,
+        parent_beacon_block_root
+    );
+
+    Ok(())
+}
+
+/// Calls the beacon roots contract to retrieve the beacon root of the provided
+/// timestamp.
+fn get_beacon_root(provider: &Provider<L1ChainSpec>, timestamp: u64) -> anyhow::Result<B256> {
+    let result: Bytes = request(
+        provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                to: Some(BEACON_ROOTS_ADDRESS),
+                data: Some(Bytes::from(U256::from(timestamp).to_be_bytes::<32>())),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )?;
+
+    Ok(B256::from_slice(&result))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mined_blocks_store_parent_beacon_block_root() -> anyhow::Result<()> {
+    let provider = create_provider_with_hardfork(edr_chain_l1::Hardfork::CANCUN)?;
+
+    assert_mined_block_stores_parent_beacon_block_root(&provider)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancun_activation_after_genesis_stores_parent_beacon_block_root() -> anyhow::Result<()> {
+    const CANCUN_ACTIVATION_BLOCK: u64 = 2;
+
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::SHANGHAI;
+    config.hardfork_activations =
+        BTreeMap::from([(CANCUN_ACTIVATION_BLOCK, edr_chain_l1::Hardfork::CANCUN)]);
+
+    let provider = create_provider(config)?;
+
+    for _ in 1..CANCUN_ACTIVATION_BLOCK {
+        request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    }
+
+    assert_mined_block_stores_parent_beacon_block_root(&provider)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_beacon_root() -> anyhow::Result<()> {
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    let provider = create_provider_with_hardfork(edr_chain_l1::Hardfork::CANCUN)?;
+
+    let root = B256::random();
+    request::<bool, _, _>(&provider, MethodInvocation::SetBeaconRoot(TIMESTAMP, root))?;
+
+    assert_eq!(get_beacon_root(&provider, TIMESTAMP)?, root);
+
+    // The ring buffer slots are overwritten by a timestamp that maps to the same
+    // index
+    let colliding_timestamp = TIMESTAMP + edr_chain_l1::block::BEACON_ROOTS_HISTORY_BUFFER_LENGTH;
+    let colliding_root = B256::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetBeaconRoot(colliding_timestamp, colliding_root),
+    )?;
+
+    assert_eq!(
+        get_beacon_root(&provider, colliding_timestamp)?,
+        colliding_root
+    );
+    assert!(get_beacon_root(&provider, TIMESTAMP).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_beacon_root_requires_cancun() -> anyhow::Result<()> {
+    let provider = create_provider_with_hardfork(edr_chain_l1::Hardfork::SHANGHAI)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SetBeaconRoot(1, B256::random()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::SetBeaconRootUnsupported { .. })
+    ));
+
+    Ok(())
+}
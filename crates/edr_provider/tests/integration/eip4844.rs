@@ -5,17 +5,19 @@ use std::{str::FromStr, sync::Arc};
 use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
 use edr_chain_l1::{
     rpc::{
-        block::L1RpcBlock, call::L1CallRequest, transaction::L1RpcTransactionWithSignature,
-        TransactionRequest,
+        block::L1RpcBlock, call::L1CallRequest, receipt::L1RpcTransactionReceipt,
+        transaction::L1RpcTransactionWithSignature, TransactionRequest,
     },
     L1ChainSpec,
 };
 use edr_chain_spec::ExecutableTransaction as _;
 use edr_defaults::SECRET_KEYS;
-use edr_eth::{Blob, PreEip1898BlockSpec};
-use edr_primitives::{Address, Bytes, B256, U256};
+use edr_eth::{
+    blob::BlobSidecar, fee_history::FeeHistoryResult, Blob, BlockSpec, PreEip1898BlockSpec,
+};
+use edr_primitives::{Address, Bytes, B256, U256, U64};
 use edr_provider::{
-    test_utils::{create_test_config, deploy_contract, one_ether},
+    test_utils::{create_provider, create_test_config, deploy_contract, one_ether, request},
     time::CurrentTime,
     AccountOverride, MethodInvocation, NoopLogger, Provider, ProviderError, ProviderRequest,
 };
@@ -92,6 +94,26 @@ fn fake_transaction_request() -> TransactionRequest {
     }
 }
 
+/// Creates a Cancun provider that uses the chain ID of the fake blob
+/// transaction and funds its sender.
+fn create_cancun_provider() -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.chain_id = fake_transaction()
+        .chain_id()
+        .expect("Blob transaction has chain ID");
+    config.hardfork = edr_chain_l1::Hardfork::CANCUN;
+
+    config.genesis_state.insert(
+        secret_key_to_address(SECRET_KEYS[0])?,
+        AccountOverride {
+            balance: Some(one_ether()),
+            ..AccountOverride::default()
+        },
+    );
+
+    create_provider(config)
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn call_unsupported() -> anyhow::Result<()> {
     let request = fake_call_request();
@@ -226,6 +248,128 @@ async fn send_raw_transaction() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn send_raw_transaction_without_auto_mining() -> anyhow::Result<()> {
+    let raw_eip4844_transaction = fake_raw_transaction();
+
+    let provider = create_cancun_provider()?;
+
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::EvmSetAutomine(false),
+    ))?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendRawTransaction(raw_eip4844_transaction),
+    ))?;
+
+    let transaction_hash: B256 = serde_json::from_value(result.result)?;
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::Mine(
+        None, None,
+    )))?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    ))?;
+
+    let receipt: L1RpcTransactionReceipt = serde_json::from_value(result.result)?;
+    assert_eq!(receipt.blob_gas_used, Some(DATA_GAS_PER_BLOB));
+    // The genesis block has 0 excess blobs, so the minimum blob base fee applies
+    assert_eq!(receipt.blob_gas_price, Some(1));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_blob_sidecars() -> anyhow::Result<()> {
+    let pooled_transaction = fake_pooled_transaction();
+    let raw_eip4844_transaction = fake_raw_transaction();
+
+    let provider = create_cancun_provider()?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendRawTransaction(raw_eip4844_transaction),
+    ))?;
+
+    let transaction_hash: B256 = serde_json::from_value(result.result)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    ))?;
+
+    let block: L1RpcBlock<B256> = serde_json::from_value(result.result)?;
+    let block_hash = block.hash.expect("Mined block has a hash");
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetBlobSidecars(block_hash),
+    ))?;
+
+    let blob_sidecars: Vec<BlobSidecar> = serde_json::from_value(result.result)?;
+    assert_eq!(blob_sidecars.len(), 1);
+
+    let blob_sidecar = &blob_sidecars[0];
+    assert_eq!(blob_sidecar.index, 0);
+    assert_eq!(blob_sidecar.block_hash, block_hash);
+    assert_eq!(blob_sidecar.transaction_hash, transaction_hash);
+    assert_eq!(
+        Some(&blob_sidecar.blob),
+        pooled_transaction.blobs().and_then(<[_]>::first)
+    );
+    assert_eq!(
+        Some(&blob_sidecar.kzg_commitment),
+        pooled_transaction.commitments().and_then(<[_]>::first)
+    );
+
+    // Unknown blocks return `null`
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetBlobSidecars(B256::ZERO),
+    ))?;
+    assert!(result.result.is_null());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverting_to_snapshot_restores_blob_sidecars() -> anyhow::Result<()> {
+    let raw_eip4844_transaction = fake_raw_transaction();
+
+    let provider = create_cancun_provider()?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendRawTransaction(raw_eip4844_transaction),
+    )?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+
+    // Dropping the transaction removes its blob sidecar
+    let was_dropped: bool = request(
+        &provider,
+        MethodInvocation::DropTransaction(transaction_hash),
+    )?;
+    assert!(was_dropped);
+
+    let was_reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(was_reverted);
+
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let block: L1RpcBlock<B256> = request(
+        &provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )?;
+    assert_eq!(block.transactions, vec![transaction_hash]);
+
+    let block_hash = block.hash.expect("Mined block has a hash");
+    let blob_sidecars: Vec<BlobSidecar> =
+        request(&provider, MethodInvocation::GetBlobSidecars(block_hash))?;
+    assert_eq!(blob_sidecars.len(), 1);
+    assert_eq!(blob_sidecars[0].transaction_hash, transaction_hash);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_transaction() -> anyhow::Result<()> {
     let raw_eip4844_transaction = fake_raw_transaction();
@@ -503,3 +647,31 @@ async fn blob_hash_opcode() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fee_history() -> anyhow::Result<()> {
+    let raw_eip4844_transaction = fake_raw_transaction();
+
+    let provider = create_cancun_provider()?;
+
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendRawTransaction(raw_eip4844_transaction),
+    ))?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::FeeHistory(U256::from(1), BlockSpec::latest(), Vec::new()),
+    ))?;
+
+    let fee_history: FeeHistoryResult = serde_json::from_value(result.result)?;
+    assert_eq!(fee_history.oldest_block, 1);
+
+    // The block and its successor have no excess blob gas, so they use the minimum
+    // base fee per blob gas.
+    assert_eq!(fee_history.base_fee_per_blob_gas, vec![1, 1]);
+
+    // Cancun allows a maximum of 6 blobs per block
+    assert_eq!(fee_history.blob_gas_used_ratio.len(), 1);
+    assert!((fee_history.blob_gas_used_ratio[0] - 1.0 / 6.0).abs() < f64::EPSILON);
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "test-utils")]
+
+use alloy_dyn_abi::eip712::TypedData;
+use edr_chain_l1::L1ChainSpec;
+use edr_primitives::{Address, Bytes};
+use edr_provider::{
+    test_utils::{create_test_config, owned_account},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+use edr_signer::{RecoveryMessage, SignatureWithRecoveryId};
+use serde_json::json;
+
+fn create_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+    let signer = owned_account(&config, 0);
+
+    let provider = edr_provider::test_utils::create_provider(config)?;
+
+    Ok((provider, signer))
+}
+
+fn sign_typed_data(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    typed_data: TypedData,
+) -> Result<Bytes, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SignTypedDataV4(address, typed_data),
+    ))?;
+
+    Ok(serde_json::from_value(response.result).expect("result should be bytes"))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn nested_structs_and_arrays() -> anyhow::Result<()> {
+    let (provider, signer) = create_provider()?;
+
+    let typed_data: TypedData = serde_json::from_value(json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallets", "type": "address[]" },
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person[]" },
+                { "name": "contents", "type": "string" },
+                { "name": "attachment", "type": "bytes" },
+                { "name": "tags", "type": "bytes32[2]" },
+            ],
+            "Group": [
+                { "name": "name", "type": "string" },
+                { "name": "members", "type": "Person[]" },
+            ],
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 123,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallets": [
+                    "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                    "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF",
+                ],
+            },
+            "to": [{
+                "name": "Bob",
+                "wallets": [
+                    "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                    "0xb0bdabea57b0bdabea57b0bdabea57b0bdabea57",
+                    "0xb0b0b0b0b0b0b000000000000000000000000000",
+                ],
+            }],
+            "contents": "Hello, Bob!",
+            "attachment": "0xabababababababababababababababababababababababababababababababababab",
+            "tags": [
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+            ],
+        },
+    }))?;
+
+    let signature = sign_typed_data(&provider, signer, typed_data.clone())?;
+    let signature = SignatureWithRecoveryId::try_from(signature.as_ref())?;
+
+    let hash = typed_data.eip712_signing_hash()?;
+    assert_eq!(signature.recover(RecoveryMessage::Hash(hash))?, signer);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mismatched_domain_type() -> anyhow::Result<()> {
+    let (provider, signer) = create_provider()?;
+
+    // The domain contains a `chainId`, which the declared type lacks
+    let typed_data: TypedData = serde_json::from_value(json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+            ],
+            "Message": [
+                { "name": "contents", "type": "string" },
+            ],
+        },
+        "primaryType": "Message",
+        "domain": {
+            "name": "Ether Mail",
+            "chainId": 123,
+        },
+        "message": {
+            "contents": "Hello, Bob!",
+        },
+    }))?;
+
+    let result = sign_typed_data(&provider, signer, typed_data);
+    assert!(matches!(
+        result,
+        Err(ProviderError::InvalidEip712Domain { declared, expected })
+            if declared == "EIP712Domain(string name)"
+                && expected == "EIP712Domain(string name,uint256 chainId)"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reordered_domain_type() -> anyhow::Result<()> {
+    let (provider, signer) = create_provider()?;
+
+    let typed_data = |domain_type: serde_json::Value| -> anyhow::Result<TypedData> {
+        Ok(serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": domain_type,
+                "Message": [
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Message",
+            "domain": {
+                "name": "Ether Mail",
+                "chainId": 123,
+            },
+            "message": {
+                "contents": "Hello, Bob!",
+            },
+        }))?)
+    };
+
+    let canonical = sign_typed_data(
+        &provider,
+        signer,
+        typed_data(json!([
+            { "name": "name", "type": "string" },
+            { "name": "chainId", "type": "uint256" },
+        ]))?,
+    )?;
+
+    // The declared fields are normalized to their canonical order
+    let reordered = sign_typed_data(
+        &provider,
+        signer,
+        typed_data(json!([
+            { "name": "chainId", "type": "uint256" },
+            { "name": "name", "type": "string" },
+        ]))?,
+    )?;
+
+    assert_eq!(reordered, canonical);
+
+    Ok(())
+}
@@ -15,8 +15,9 @@ use edr_chain_l1::{
     L1ChainSpec,
 };
 use edr_chain_spec::ExecutableTransaction as _;
-use edr_primitives::{address, Address, Bytes, B256, U256};
+use edr_primitives::{address, bytes, Address, Bytes, B256, U256, U64};
 use edr_provider::{
+    hardhat_rpc_types::AuthorizationRequest,
     test_utils::{
         create_test_config, one_ether, set_genesis_state_with_owned_accounts, sign_authorization,
     },
@@ -168,3 +169,63 @@ async fn get_transaction() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sign_authorization() -> anyhow::Result<()> {
+    let secret_key = secret_key_from_str(edr_defaults::SECRET_KEYS[0])?;
+    let sender = public_key_to_address(secret_key.public_key());
+
+    let mut config = create_test_config();
+    config.chain_id = CHAIN_ID;
+    config.hardfork = edr_chain_l1::Hardfork::PRAGUE;
+
+    let provider = new_provider(config, vec![secret_key.clone()])?;
+
+    let delegate = address!("0x1234567890123456789012345678901234567890");
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SignAuthorization(
+            sender,
+            AuthorizationRequest {
+                chain_id: None,
+                address: delegate,
+                // The sender also sends the transaction, so the authorization needs to use the
+                // nonce after the transaction's nonce.
+                nonce: Some(U64::from(1)),
+            },
+        ),
+    ))?;
+
+    let signed_authorization: edr_eip7702::SignedAuthorization =
+        serde_json::from_value(response.result)?;
+
+    let expected = sign_authorization(
+        edr_eip7702::Authorization {
+            chain_id: U256::from(CHAIN_ID),
+            address: delegate,
+            nonce: 0x1,
+        },
+        &secret_key,
+    )?;
+    assert_eq!(signed_authorization, expected);
+
+    let transaction_request = TransactionRequest {
+        chain_id: Some(CHAIN_ID),
+        nonce: Some(0),
+        from: sender,
+        to: Some(sender),
+        authorization_list: Some(vec![signed_authorization]),
+        ..TransactionRequest::default()
+    };
+
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(transaction_request),
+    ))?;
+
+    assert_code_at(
+        &provider,
+        sender,
+        &bytes!("ef01001234567890123456789012345678901234567890"),
+    );
+
+    Ok(())
+}
@@ -106,6 +106,45 @@ async fn send_transaction() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn send_transaction_without_auto_mining() -> anyhow::Result<()> {
+    let secret_key = secret_key_from_str(edr_defaults::SECRET_KEYS[0])?;
+    let sender = public_key_to_address(secret_key.public_key());
+    let authorized_address = sender;
+
+    let transaction_request = TransactionRequest {
+        chain_id: Some(CHAIN_ID),
+        nonce: Some(0),
+        from: sender,
+        to: Some(sender),
+        authorization_list: Some(vec![signed_authorization(&secret_key)?]),
+        ..TransactionRequest::default()
+    };
+
+    let provider = new_provider(secret_key)?;
+
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::EvmSetAutomine(false),
+    ))?;
+
+    let _response = provider
+        .handle_request(ProviderRequest::with_single(
+            MethodInvocation::SendTransaction(transaction_request),
+        ))
+        .expect("eth_sendTransaction should succeed");
+
+    // The delegation is only applied once the transaction is mined
+    assert_code_at(&provider, authorized_address, &Bytes::new());
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::Mine(
+        None, None,
+    )))?;
+
+    assert_code_at(&provider, authorized_address, &EXPECTED_CODE);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn trace_call() -> anyhow::Result<()> {
     let secret_key = secret_key_from_str(edr_defaults::SECRET_KEYS[0])?;
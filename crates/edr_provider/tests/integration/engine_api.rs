@@ -0,0 +1,337 @@
+#![cfg(feature = "test-utils")]
+
+use edr_block_header::Withdrawal;
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdatedResult, MethodInvocation, PayloadStatus,
+    PayloadValidity, Provider, ProviderError, ProviderRequest,
+};
+
+const WITHDRAWAL_AMOUNT_GWEI: u64 = 32_000_000_000;
+
+fn create_cancun_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::CANCUN;
+
+    let owned_account = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, owned_account))
+}
+
+fn latest_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+fn balance(provider: &Provider<L1ChainSpec>, address: Address) -> anyhow::Result<U256> {
+    request(provider, MethodInvocation::GetBalance(address, None))
+}
+
+/// Converts a block with the provided EIP-2718 encoded transactions into the
+/// corresponding execution payload.
+fn block_to_payload(block: &L1RpcBlock<B256>, transactions: Vec<Bytes>) -> ExecutionPayloadV3 {
+    ExecutionPayloadV3 {
+        parent_hash: block.parent_hash,
+        fee_recipient: block.miner.expect("mined block has a miner"),
+        state_root: block.state_root,
+        receipts_root: block.receipts_root,
+        logs_bloom: block.logs_bloom,
+        prev_randao: block.mix_hash.expect("post-merge block has a prevrandao"),
+        block_number: block.number.expect("mined block has a number"),
+        gas_limit: block.gas_limit,
+        gas_used: block.gas_used,
+        timestamp: block.timestamp,
+        extra_data: block.extra_data.clone(),
+        base_fee_per_gas: block
+            .base_fee_per_gas
+            .expect("post-London block has a base fee"),
+        block_hash: block.hash.expect("mined block has a hash"),
+        transactions,
+        withdrawals: block.withdrawals.clone().unwrap_or_default(),
+        blob_gas_used: block.blob_gas_used.expect("post-Cancun block has blob gas"),
+        excess_blob_gas: block
+            .excess_blob_gas
+            .expect("post-Cancun block has blob gas"),
+    }
+}
+
+fn new_payload(
+    provider: &Provider<L1ChainSpec>,
+    payload: ExecutionPayloadV3,
+    parent_beacon_block_root: B256,
+) -> anyhow::Result<PayloadStatus> {
+    request(
+        provider,
+        MethodInvocation::EngineNewPayloadV3(payload, Vec::new(), parent_beacon_block_root),
+    )
+}
+
+fn forkchoice_updated(
+    provider: &Provider<L1ChainSpec>,
+    head_block_hash: B256,
+) -> anyhow::Result<ForkchoiceUpdatedResult> {
+    request(
+        provider,
+        MethodInvocation::EngineForkchoiceUpdatedV3(
+            ForkchoiceState {
+                head_block_hash,
+                safe_block_hash: B256::ZERO,
+                finalized_block_hash: B256::ZERO,
+            },
+            None,
+        ),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_payload_imports_block_with_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_cancun_provider()?;
+    let receiver = Address::random();
+
+    let raw_transaction: Bytes = request(
+        &provider,
+        MethodInvocation::SignTransaction(TransactionRequest {
+            from: sender,
+            to: Some(receiver),
+            value: Some(U256::from(1_000)),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendRawTransaction(raw_transaction.clone()),
+    )?;
+
+    let block = latest_block(&provider)?;
+    assert_eq!(block.transactions.len(), 1);
+
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("post-Cancun block has a parent beacon block root");
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+    assert_eq!(balance(&provider, receiver)?, U256::ZERO);
+
+    let status = new_payload(
+        &provider,
+        block_to_payload(&block, vec![raw_transaction]),
+        parent_beacon_block_root,
+    )?;
+    assert_eq!(status.status, PayloadValidity::Valid);
+    assert_eq!(status.latest_valid_hash, block.hash);
+
+    assert_eq!(latest_block(&provider)?.hash, block.hash);
+    assert_eq!(balance(&provider, receiver)?, U256::from(1_000));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_payload_imports_block_with_withdrawals() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_cancun_provider()?;
+
+    let recipient = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetNextBlockWithdrawals(vec![Withdrawal {
+            index: 0,
+            validator_index: 1,
+            address: recipient,
+            amount: WITHDRAWAL_AMOUNT_GWEI,
+        }]),
+    )?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let block = latest_block(&provider)?;
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("post-Cancun block has a parent beacon block root");
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+    assert_eq!(balance(&provider, recipient)?, U256::ZERO);
+
+    let status = new_payload(
+        &provider,
+        block_to_payload(&block, Vec::new()),
+        parent_beacon_block_root,
+    )?;
+    assert_eq!(status.status, PayloadValidity::Valid);
+    assert_eq!(status.latest_valid_hash, block.hash);
+
+    assert_eq!(latest_block(&provider)?.hash, block.hash);
+    assert_eq!(
+        balance(&provider, recipient)?,
+        U256::from(WITHDRAWAL_AMOUNT_GWEI) * U256::from(1_000_000_000u64)
+    );
+
+    // Known payloads are valid
+    let status = new_payload(
+        &provider,
+        block_to_payload(&block, Vec::new()),
+        parent_beacon_block_root,
+    )?;
+    assert_eq!(status.status, PayloadValidity::Valid);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_payload_rejects_invalid_payloads() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_cancun_provider()?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let block = latest_block(&provider)?;
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("post-Cancun block has a parent beacon block root");
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    let mut payload = block_to_payload(&block, Vec::new());
+    payload.gas_used += 1;
+
+    let status = new_payload(&provider, payload, parent_beacon_block_root)?;
+    assert_eq!(status.status, PayloadValidity::Invalid);
+    assert_eq!(status.latest_valid_hash, Some(block.parent_hash));
+    assert!(status.validation_error.is_some());
+
+    let mut payload = block_to_payload(&block, Vec::new());
+    payload.parent_hash = B256::random();
+
+    let status = new_payload(&provider, payload, parent_beacon_block_root)?;
+    assert_eq!(status.status, PayloadValidity::Syncing);
+
+    assert_eq!(latest_block(&provider)?.hash, Some(block.parent_hash));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_payload_with_known_parent_that_is_not_latest_is_syncing() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_cancun_provider()?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let block = latest_block(&provider)?;
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("post-Cancun block has a parent beacon block root");
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    // The parent of the payload is an ancestor of the latest block
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    let latest_block_hash = latest_block(&provider)?.hash;
+
+    let status = new_payload(
+        &provider,
+        block_to_payload(&block, Vec::new()),
+        parent_beacon_block_root,
+    )?;
+    assert_eq!(status.status, PayloadValidity::Syncing);
+    assert_eq!(status.latest_valid_hash, None);
+
+    assert_eq!(latest_block(&provider)?.hash, latest_block_hash);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_payload_rejects_unsupported_fork() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::PRAGUE;
+
+    let provider = create_provider(config)?;
+
+    let block = latest_block(&provider)?;
+    let parent_beacon_block_root = block
+        .parent_beacon_block_root
+        .expect("post-Cancun block has a parent beacon block root");
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::EngineNewPayloadV3(
+            block_to_payload(&block, Vec::new()),
+            Vec::new(),
+            parent_beacon_block_root,
+        ),
+    ));
+
+    let error = result.expect_err("Prague payloads should be rejected");
+    assert!(matches!(error, ProviderError::UnsupportedFork { .. }));
+    assert_eq!(edr_rpc_eth::jsonrpc::Error::from(error).code, -38005);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forkchoice_updated_to_ancestor_keeps_chain() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_cancun_provider()?;
+
+    let ancestor = latest_block(&provider)?.hash.expect("block has a hash");
+
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let head = latest_block(&provider)?.hash;
+
+    let result = forkchoice_updated(&provider, ancestor)?;
+    assert_eq!(result.payload_status.status, PayloadValidity::Valid);
+    assert_eq!(result.payload_status.latest_valid_hash, Some(ancestor));
+    assert_eq!(result.payload_id, None);
+
+    assert_eq!(latest_block(&provider)?.hash, head);
+
+    let result = forkchoice_updated(&provider, B256::random())?;
+    assert_eq!(result.payload_status.status, PayloadValidity::Syncing);
+
+    assert_eq!(latest_block(&provider)?.hash, head);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forkchoice_updated_rejects_invalid_forkchoice_state() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_cancun_provider()?;
+
+    let ancestor = latest_block(&provider)?.hash.expect("block has a hash");
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    let head = latest_block(&provider)?.hash.expect("block has a hash");
+
+    // The finalized block is a descendant of the head block
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::EngineForkchoiceUpdatedV3(
+            ForkchoiceState {
+                head_block_hash: ancestor,
+                safe_block_hash: ancestor,
+                finalized_block_hash: head,
+            },
+            None,
+        ),
+    ));
+
+    let error = result.expect_err("forkchoice state should be invalid");
+    assert!(matches!(error, ProviderError::InvalidForkchoiceState(_)));
+    assert_eq!(edr_rpc_eth::jsonrpc::Error::from(error).code, -38002);
+
+    Ok(())
+}
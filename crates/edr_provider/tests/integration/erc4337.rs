@@ -0,0 +1,201 @@
+#![cfg(all(feature = "test-remote", feature = "test-utils"))]
+
+use edr_chain_l1::L1ChainSpec;
+use edr_primitives::{address, Address, Bytes, HashMap, U128, U256};
+use edr_provider::{
+    hardhat_rpc_types::UserOperation,
+    test_utils::{create_provider, create_test_config_with_fork, request},
+    ForkConfig, MethodInvocation, Provider, ProviderError, ProviderErrorForChainSpec,
+    ProviderRequest, UserOperationEntity, UserOperationRule, UserOperationViolation,
+};
+use edr_rpc_eth::client::TransportConfig;
+use edr_test_utils::env::get_alchemy_url;
+
+/// The EntryPoint v0.7 deployment on mainnet.
+const ENTRY_POINT: Address = address!("0x0000000071727de22e5e9d8baf0edac6f37da032");
+/// A mainnet block after the EntryPoint v0.7 deployment.
+const FORK_BLOCK_NUMBER: u64 = 20_000_000;
+
+const SENDER: Address = address!("0x1111111111111111111111111111111111111111");
+const OTHER_CONTRACT: Address = address!("0x2222222222222222222222222222222222222222");
+
+/// Returns 32 zero bytes from `validateUserOp`, signalling a valid signature
+/// without a time range: PUSH1 0x20 PUSH1 0x00 RETURN
+const RETURN_VALID: [u8; 5] = [0x60, 0x20, 0x60, 0x00, 0xf3];
+
+fn create_forked_provider() -> anyhow::Result<Provider<L1ChainSpec>> {
+    let config = create_test_config_with_fork(Some(ForkConfig {
+        block_number: Some(FORK_BLOCK_NUMBER),
+        cache_dir: edr_defaults::CACHE_DIR.into(),
+        chain_overrides: HashMap::default(),
+        http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
+        url: get_alchemy_url(),
+        verified_sources: None,
+    }));
+
+    create_provider(config)
+}
+
+/// Deploys an account at `SENDER` whose `validateUserOp` executes the
+/// provided code and then reports a valid signature.
+fn deploy_account(provider: &Provider<L1ChainSpec>, validation_code: &[u8]) -> anyhow::Result<()> {
+    let code = [validation_code, &RETURN_VALID].concat();
+
+    request::<bool, _, _>(provider, MethodInvocation::SetCode(SENDER, code.into()))?;
+
+    Ok(())
+}
+
+/// Returns code that calls the provided address without any data or value.
+fn call_code(address: Address) -> Vec<u8> {
+    // PUSH1 0x00 (x5) PUSH20 <address> GAS CALL POP
+    let mut code = [0x60, 0x00].repeat(5);
+    code.push(0x73);
+    code.extend_from_slice(address.as_slice());
+    code.extend_from_slice(&[0x5a, 0xf1, 0x50]);
+    code
+}
+
+/// A user operation without any gas fees, so the EntryPoint doesn't require
+/// the account to pay a prefund.
+fn user_operation() -> UserOperation {
+    UserOperation {
+        sender: SENDER,
+        nonce: U256::ZERO,
+        factory: None,
+        factory_data: None,
+        call_data: Bytes::new(),
+        call_gas_limit: U128::from(100_000),
+        verification_gas_limit: U128::from(100_000),
+        pre_verification_gas: U256::from(50_000),
+        max_fee_per_gas: U128::ZERO,
+        max_priority_fee_per_gas: U128::ZERO,
+        paymaster: None,
+        paymaster_verification_gas_limit: None,
+        paymaster_post_op_gas_limit: None,
+        paymaster_data: None,
+        signature: Bytes::new(),
+    }
+}
+
+fn validate_user_operation(
+    provider: &Provider<L1ChainSpec>,
+    user_operation: UserOperation,
+) -> Result<bool, ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::ValidateUserOperation(user_operation, ENTRY_POINT),
+    ))?;
+
+    Ok(serde_json::from_value(response.result).expect("result should be a boolean"))
+}
+
+fn assert_account_violation(
+    result: Result<bool, ProviderErrorForChainSpec<L1ChainSpec>>,
+    rule: UserOperationRule,
+) {
+    let error = result.expect_err("user operation should violate a rule");
+    assert!(matches!(
+        &error,
+        ProviderError::UserOperationRuleViolations { violations }
+            if *violations == vec![UserOperationViolation {
+                entity: UserOperationEntity::Account,
+                entity_address: SENDER,
+                rule,
+            }]
+    ));
+    assert_eq!(edr_rpc_eth::jsonrpc::Error::from(error).code, -32502);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn valid_user_operation() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+
+    // The account may access its own storage: PUSH1 0x00 SLOAD POP
+    deploy_account(&provider, &[0x60, 0x00, 0x54, 0x50])?;
+
+    assert!(validate_user_operation(&provider, user_operation())?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejected_user_operation() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+    deploy_account(&provider, &[])?;
+
+    // The account neither has a deposit nor pays the required prefund
+    let result = validate_user_operation(
+        &provider,
+        UserOperation {
+            max_fee_per_gas: U128::from(1_000_000_000),
+            max_priority_fee_per_gas: U128::from(1_000_000_000),
+            ..user_operation()
+        },
+    );
+
+    let error = result.expect_err("user operation should be rejected");
+    assert!(matches!(
+        &error,
+        ProviderError::UserOperationRejected { reason } if reason == "AA21 didn't pay prefund"
+    ));
+    assert_eq!(edr_rpc_eth::jsonrpc::Error::from(error).code, -32500);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn banned_opcode() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+
+    // TIMESTAMP POP
+    deploy_account(&provider, &[0x42, 0x50])?;
+
+    assert_account_violation(
+        validate_user_operation(&provider, user_operation()),
+        UserOperationRule::BannedOpcode {
+            opcode: "TIMESTAMP".to_owned(),
+        },
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn gas_not_followed_by_call() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+
+    // GAS POP
+    deploy_account(&provider, &[0x5a, 0x50])?;
+
+    assert_account_violation(
+        validate_user_operation(&provider, user_operation()),
+        UserOperationRule::GasNotFollowedByCall,
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unassociated_storage_access() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+
+    // The account calls another contract, which reads its own storage:
+    // PUSH1 0x00 SLOAD POP STOP
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(OTHER_CONTRACT, vec![0x60, 0x00, 0x54, 0x50, 0x00].into()),
+    )?;
+    deploy_account(&provider, &call_code(OTHER_CONTRACT))?;
+
+    assert_account_violation(
+        validate_user_operation(&provider, user_operation()),
+        UserOperationRule::StorageAccess {
+            address: OTHER_CONTRACT,
+            slot: U256::ZERO,
+        },
+    );
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::rpc::TransactionRequest;
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn eth_get_block_receipts() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let sender = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::ImpersonateAccount(sender.into()),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetBalance(sender, U256::from(10).pow(U256::from(18))),
+    )?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let mut transaction_hashes = Vec::new();
+    for _ in 0..2 {
+        let transaction_hash: B256 = request(
+            &provider,
+            MethodInvocation::SendTransaction(TransactionRequest {
+                from: sender,
+                to: Some(Address::random()),
+                value: Some(U256::from(1)),
+                ..TransactionRequest::default()
+            }),
+        )?;
+
+        transaction_hashes.push(transaction_hash);
+    }
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+
+    let block_receipts: Vec<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetBlockReceipts(BlockSpec::latest()),
+    )?;
+
+    let mut expected_receipts = Vec::new();
+    for transaction_hash in transaction_hashes {
+        let receipt: serde_json::Value = request(
+            &provider,
+            MethodInvocation::GetTransactionReceipt(transaction_hash),
+        )?;
+
+        expected_receipts.push(receipt);
+    }
+
+    assert_eq!(block_receipts, expected_receipts);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn eth_get_block_receipts_unknown_block() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let block_receipts: Option<Vec<serde_json::Value>> = request(
+        &provider,
+        MethodInvocation::GetBlockReceipts(BlockSpec::Number(100)),
+    )?;
+    assert!(block_receipts.is_none());
+
+    Ok(())
+}
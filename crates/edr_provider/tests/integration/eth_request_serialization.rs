@@ -7,8 +7,12 @@ use edr_eth::{
     filter::{LogFilterOptions, LogOutput, OneOrMore},
     Blob, BlockSpec, BlockTag, PreEip1898BlockSpec,
 };
-use edr_primitives::{Address, Bytes, B256, U160, U256};
-use edr_provider::{IntervalConfigRequest, MethodInvocation, Timestamp};
+use edr_primitives::{Address, Bloom, Bytes, B256, U160, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::CallBundleRequest, AddressRelation, AddressTransactionsOptions,
+    ExecutionPayloadV3, ForkchoiceState, IntervalConfigRequest, MethodInvocation,
+    PayloadAttributesV3, Timestamp, TraceFilter,
+};
 
 use crate::common::{
     help_test_method_invocation_serde, help_test_method_invocation_serde_with_expected,
@@ -61,6 +65,33 @@ fn test_serde_eth_call() {
     );
 }
 
+#[test]
+fn test_serde_eth_call_bundle() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::CallBundle(
+        CallBundleRequest {
+            txs: vec![Bytes::from(&b"first"[..]), Bytes::from(&b"second"[..])],
+            block_number: Some(101),
+            state_block_number: Some(BlockSpec::Number(100)),
+            timestamp: Some(1_700_000_000),
+            coinbase: Some(Address::random()),
+        },
+    ));
+
+    let json = r#"{"jsonrpc":"2.0","method":"eth_callBundle","params":[{"txs":["0x01"],"blockNumber":"0x65"}],"id":1}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(
+        MethodInvocation::CallBundle(CallBundleRequest {
+            txs: vec![Bytes::from_static(&[0x01])],
+            block_number: Some(101),
+            state_block_number: None,
+            timestamp: None,
+            coinbase: None,
+        }),
+        deserialized
+    );
+}
+
 #[test]
 fn test_serde_eth_chain_id() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ChainId(()));
@@ -116,6 +147,19 @@ fn test_serde_eth_fee_history() {
     ));
 }
 
+#[test]
+fn test_serde_eth_fill_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::FillTransaction(
+        TransactionRequest {
+            from: Address::from(U160::from(1)),
+            to: Some(Address::from(U160::from(2))),
+            value: Some(U256::from(123568919)),
+            data: Some(Bytes::from(&b"whatever"[..])),
+            ..TransactionRequest::default()
+        },
+    ));
+}
+
 #[test]
 fn test_serde_eth_gas_price() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GasPrice(()));
@@ -144,6 +188,16 @@ fn test_serde_eth_get_block_by_number() {
     ));
 }
 
+#[test]
+fn test_serde_eth_get_block_receipts() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetBlockReceipts(
+        BlockSpec::Number(100),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetBlockReceipts(
+        BlockSpec::latest(),
+    ));
+}
+
 #[test]
 fn test_serde_eth_get_block_by_tag() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetBlockByNumber(
@@ -152,6 +206,13 @@ fn test_serde_eth_get_block_by_tag() {
     ));
 }
 
+#[test]
+fn test_serde_eth_get_blob_sidecars() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetBlobSidecars(
+        B256::from(U256::from(1)),
+    ));
+}
+
 #[test]
 fn test_serde_eth_get_block_by_hash() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetBlockByHash(
@@ -401,6 +462,50 @@ fn test_serde_personal_sign() {
     ));
 }
 
+#[test]
+fn test_serde_personal_import_raw_key() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PersonalImportRawKey(
+        B256::from(U256::from(1)),
+        String::from("passphrase"),
+    ));
+}
+
+#[test]
+fn test_serde_personal_new_account() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PersonalNewAccount(
+        String::from("passphrase"),
+    ));
+}
+
+#[test]
+fn test_serde_personal_unlock_account() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PersonalUnlockAccount(
+        Address::from(U160::from(1)),
+        String::from("passphrase"),
+        Some(60),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PersonalUnlockAccount(
+        Address::from(U160::from(1)),
+        String::from("passphrase"),
+        None,
+    ));
+}
+
+#[test]
+fn test_serde_eth_sign_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SignTransaction(
+        TransactionRequest {
+            from: Address::from(U160::from(1)),
+            to: Some(Address::from(U160::from(2))),
+            gas: Some(3),
+            gas_price: Some(4),
+            value: Some(U256::from(123568919)),
+            data: Some(Bytes::from(&b"whatever"[..])),
+            ..TransactionRequest::default()
+        },
+    ));
+}
+
 #[test]
 fn test_serde_eth_sign() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EthSign(
@@ -443,6 +548,7 @@ impl_serde_eth_subscribe_tests! {
     logs => SubscriptionType::Logs,
     new_pending_transactions => SubscriptionType::NewPendingTransactions,
     new_heads => SubscriptionType::NewHeads,
+    dropped_transactions => SubscriptionType::DroppedTransactions,
 }
 
 #[test]
@@ -515,6 +621,18 @@ fn test_evm_mine() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EvmMine(None));
 }
 
+#[test]
+fn test_evm_set_account_storage_root() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EvmSetAccountStorageRoot(
+        Address::from(U160::from(1)),
+        Some(B256::from(U256::from(1))),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EvmSetAccountStorageRoot(
+        Address::from(U160::from(1)),
+        None,
+    ));
+}
+
 #[test]
 fn test_evm_set_next_block_timestamp() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EvmSetNextBlockTimestamp(
@@ -522,6 +640,191 @@ fn test_evm_set_next_block_timestamp() {
     ));
 }
 
+#[test]
+fn test_evm_set_time() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EvmSetTime(
+        Timestamp::from(12345),
+    ));
+}
+
+#[test]
+fn test_serde_admin_import_chain() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AdminImportChain(
+        Bytes::from(&b"whatever"[..]),
+    ));
+}
+
+#[test]
+fn test_serde_debug_set_head() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::DebugSetHead(U64::from(
+        12345,
+    )));
+}
+
+#[test]
+fn test_serde_engine_forkchoice_updated_v3() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EngineForkchoiceUpdatedV3(
+        ForkchoiceState {
+            head_block_hash: B256::from(U256::from(1)),
+            safe_block_hash: B256::from(U256::from(2)),
+            finalized_block_hash: B256::ZERO,
+        },
+        None,
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EngineForkchoiceUpdatedV3(
+        ForkchoiceState {
+            head_block_hash: B256::from(U256::from(1)),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        },
+        Some(PayloadAttributesV3 {
+            timestamp: 12345,
+            prev_randao: B256::from(U256::from(3)),
+            suggested_fee_recipient: Address::from(U160::from(1)),
+            withdrawals: Vec::new(),
+            parent_beacon_block_root: B256::from(U256::from(4)),
+        }),
+    ));
+}
+
+#[test]
+fn test_serde_engine_new_payload_v3() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EngineNewPayloadV3(
+        ExecutionPayloadV3 {
+            parent_hash: B256::from(U256::from(1)),
+            fee_recipient: Address::from(U160::from(1)),
+            state_root: B256::from(U256::from(2)),
+            receipts_root: B256::from(U256::from(3)),
+            logs_bloom: Bloom::default(),
+            prev_randao: B256::from(U256::from(4)),
+            block_number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            timestamp: 12345,
+            extra_data: Bytes::from(&b"whatever"[..]),
+            base_fee_per_gas: 7,
+            block_hash: B256::from(U256::from(5)),
+            transactions: vec![Bytes::from(&b"transaction"[..])],
+            withdrawals: Vec::new(),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        },
+        vec![B256::from(U256::from(6))],
+        B256::from(U256::from(7)),
+    ));
+}
+
+#[test]
+fn test_serde_explorer_get_address_transactions() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::ExplorerGetAddressTransactions(
+            Address::from(U160::from(1)),
+            None,
+        ),
+    );
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::ExplorerGetAddressTransactions(
+            Address::from(U160::from(1)),
+            Some(AddressTransactionsOptions {
+                relation: Some(AddressRelation::Internal),
+                page: Some(2),
+                page_size: Some(10),
+            }),
+        ),
+    );
+}
+
+#[test]
+fn test_serde_ots_get_api_level() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::OtsGetApiLevel(()));
+}
+
+#[test]
+fn test_serde_ots_get_block_details() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::OtsGetBlockDetails(100));
+}
+
+#[test]
+fn test_serde_ots_get_transaction_error() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::OtsGetTransactionError(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn test_serde_ots_search_transactions_after() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::OtsSearchTransactionsAfter(
+        Address::from(U160::from(1)),
+        100,
+        25,
+    ));
+}
+
+#[test]
+fn test_serde_ots_search_transactions_before() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::OtsSearchTransactionsBefore(
+            Address::from(U160::from(1)),
+            0,
+            25,
+        ),
+    );
+}
+
+#[test]
+fn test_serde_ots_trace_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::OtsTraceTransaction(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn test_serde_trace_block() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TraceBlock(
+        BlockSpec::Number(100),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TraceBlock(
+        BlockSpec::latest(),
+    ));
+}
+
+#[test]
+fn test_serde_trace_filter() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TraceFilter(
+        TraceFilter::default(),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TraceFilter(TraceFilter {
+        from_block: Some(BlockSpec::Number(1)),
+        to_block: Some(BlockSpec::latest()),
+        from_address: Some(vec![Address::from(U160::from(1))]),
+        to_address: Some(vec![Address::from(U160::from(2))]),
+        after: Some(2),
+        count: Some(10),
+    }));
+}
+
+#[test]
+fn test_serde_trace_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TraceTransaction(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn test_serde_txpool_content() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TxpoolContent(()));
+}
+
+#[test]
+fn test_serde_txpool_inspect() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TxpoolInspect(()));
+}
+
+#[test]
+fn test_serde_txpool_status() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TxpoolStatus(()));
+}
+
 #[test]
 fn test_serde_web3_client_version() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Web3ClientVersion(()));
@@ -0,0 +1,191 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    AddressRelation, AddressTransactionsOptions, AddressTransactionsPage, MethodInvocation,
+    Provider, ProviderError, ProviderRequest,
+};
+
+fn create_indexing_provider(
+    index_address_transactions: bool,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.index_address_transactions = index_address_transactions;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<B256> {
+    request(provider, MethodInvocation::SendTransaction(transaction))
+}
+
+fn transfer(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    receiver: Address,
+) -> anyhow::Result<B256> {
+    send_transaction(
+        provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(receiver),
+            value: Some(U256::from(1)),
+            ..TransactionRequest::default()
+        },
+    )
+}
+
+fn address_transactions(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    options: Option<AddressTransactionsOptions>,
+) -> anyhow::Result<AddressTransactionsPage> {
+    request(
+        provider,
+        MethodInvocation::ExplorerGetAddressTransactions(address, options),
+    )
+}
+
+fn transaction_hashes(page: &AddressTransactionsPage) -> Vec<B256> {
+    page.transactions
+        .iter()
+        .map(|transaction| transaction.transaction_hash)
+        .collect()
+}
+
+/// Init code that calls the provided address without any data or value.
+fn calling_init_code(callee: Address) -> Bytes {
+    // PUSH1 0x00 (x5) PUSH20 <callee> GAS CALL STOP
+    let mut init_code = [0x60, 0x00].repeat(5);
+    init_code.push(0x73);
+    init_code.extend_from_slice(callee.as_slice());
+    init_code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+
+    init_code.into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn indexes_sent_received_and_internal_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+    let receiver = Address::random();
+    let callee = Address::random();
+
+    let first_hash = transfer(&provider, sender, receiver)?;
+    let create_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(calling_init_code(callee)),
+            ..TransactionRequest::default()
+        },
+    )?;
+    let third_hash = transfer(&provider, sender, receiver)?;
+
+    let page = address_transactions(&provider, receiver, None)?;
+    assert_eq!(page.total, 2);
+    assert_eq!(transaction_hashes(&page), vec![third_hash, first_hash]);
+    assert!(page
+        .transactions
+        .iter()
+        .all(|transaction| transaction.received && !transaction.sent && !transaction.internal));
+
+    let page = address_transactions(&provider, callee, None)?;
+    assert_eq!(page.total, 1);
+    let transaction = &page.transactions[0];
+    assert_eq!(transaction.transaction_hash, create_hash);
+    assert_eq!(transaction.block_number, 2);
+    assert!(transaction.internal && !transaction.sent && !transaction.received);
+
+    let page = address_transactions(
+        &provider,
+        callee,
+        Some(AddressTransactionsOptions {
+            relation: Some(AddressRelation::Received),
+            ..AddressTransactionsOptions::default()
+        }),
+    )?;
+    assert_eq!(page.total, 0);
+
+    let page = address_transactions(
+        &provider,
+        sender,
+        Some(AddressTransactionsOptions {
+            relation: Some(AddressRelation::Sent),
+            page: Some(1),
+            page_size: Some(2),
+        }),
+    )?;
+    assert_eq!(page.total, 3);
+    assert_eq!(transaction_hashes(&page), vec![first_hash]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverted_blocks_are_removed_from_index() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+    let receiver = Address::random();
+
+    let first_hash = transfer(&provider, sender, receiver)?;
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    transfer(&provider, sender, receiver)?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    let page = address_transactions(&provider, receiver, None)?;
+    assert_eq!(transaction_hashes(&page), vec![first_hash]);
+
+    let other_receiver = Address::random();
+    let replacement_hash = transfer(&provider, sender, other_receiver)?;
+
+    let page = address_transactions(&provider, receiver, None)?;
+    assert_eq!(transaction_hashes(&page), vec![first_hash]);
+
+    let page = address_transactions(&provider, sender, None)?;
+    assert_eq!(
+        transaction_hashes(&page),
+        vec![replacement_hash, first_hash]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_invalid_page_size() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::ExplorerGetAddressTransactions(
+            sender,
+            Some(AddressTransactionsOptions {
+                page_size: Some(0),
+                ..AddressTransactionsOptions::default()
+            }),
+        ),
+    ));
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn address_transactions_require_indexing() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(false)?;
+    transfer(&provider, sender, Address::random())?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::ExplorerGetAddressTransactions(sender, None),
+    ));
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
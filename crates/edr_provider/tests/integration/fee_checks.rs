@@ -0,0 +1,190 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, B256, U128};
+use edr_provider::{
+    hardhat_rpc_types::FeeChecksConfig,
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderConfig, ProviderRequest,
+};
+
+fn create_impersonating_provider(
+    configure: impl FnOnce(&mut ProviderConfig<edr_chain_l1::Hardfork>),
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.auto_impersonate = true;
+    configure(&mut config);
+
+    let owned_account = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, owned_account))
+}
+
+fn set_fee_checks(provider: &Provider<L1ChainSpec>, config: FeeChecksConfig) -> anyhow::Result<()> {
+    let result: bool = request(provider, MethodInvocation::SetFeeChecks(config))?;
+    assert!(result);
+
+    Ok(())
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    request: TransactionRequest,
+) -> Result<B256, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(TransactionRequest {
+            to: Some(Address::random()),
+            ..request
+        }),
+    ))?;
+
+    Ok(serde_json::from_value(response.result).expect("should be a transaction hash"))
+}
+
+fn assert_mined(provider: &Provider<L1ChainSpec>, transaction_hash: B256) -> anyhow::Result<()> {
+    let receipt: serde_json::Value = request(
+        provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    assert_eq!(receipt["status"], "0x1");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn balance_check_can_be_disabled() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_impersonating_provider(|_config| {})?;
+
+    let sender = Address::random();
+    let transaction = TransactionRequest {
+        from: sender,
+        ..TransactionRequest::default()
+    };
+
+    assert!(send_transaction(&provider, transaction.clone()).is_err());
+
+    set_fee_checks(
+        &provider,
+        FeeChecksConfig {
+            disable_balance_check: Some(true),
+            ..FeeChecksConfig::default()
+        },
+    )?;
+
+    let transaction_hash = send_transaction(&provider, transaction)?;
+    assert_mined(&provider, transaction_hash)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn balance_check_can_be_disabled_without_auto_mining() -> anyhow::Result<()> {
+    let (provider, _owned_account) = create_impersonating_provider(|config| {
+        config.disable_balance_check = true;
+        config.mining.auto_mine = false;
+    })?;
+
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: Address::random(),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(None, None))?;
+
+    assert_mined(&provider, transaction_hash)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn priority_fee_check_can_be_disabled() -> anyhow::Result<()> {
+    let (provider, owned_account) = create_impersonating_provider(|_config| {})?;
+
+    let result: bool = request(
+        &provider,
+        MethodInvocation::SetMinGasPrice(U128::from(10_000_000_000u64)),
+    )?;
+    assert!(result);
+
+    let transaction = TransactionRequest {
+        from: owned_account,
+        max_fee_per_gas: Some(100_000_000_000),
+        max_priority_fee_per_gas: Some(1),
+        ..TransactionRequest::default()
+    };
+
+    assert!(send_transaction(&provider, transaction.clone()).is_err());
+
+    set_fee_checks(
+        &provider,
+        FeeChecksConfig {
+            disable_priority_fee_check: Some(true),
+            ..FeeChecksConfig::default()
+        },
+    )?;
+
+    let transaction_hash = send_transaction(&provider, transaction)?;
+    assert_mined(&provider, transaction_hash)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn base_fee_check_can_be_toggled() -> anyhow::Result<()> {
+    let (provider, owned_account) = create_impersonating_provider(|config| {
+        config.disable_base_fee_check = true;
+    })?;
+
+    let transaction = TransactionRequest {
+        from: owned_account,
+        max_fee_per_gas: Some(1),
+        max_priority_fee_per_gas: Some(0),
+        ..TransactionRequest::default()
+    };
+
+    let transaction_hash = send_transaction(&provider, transaction.clone())?;
+    assert_mined(&provider, transaction_hash)?;
+
+    set_fee_checks(
+        &provider,
+        FeeChecksConfig {
+            disable_base_fee_check: Some(false),
+            ..FeeChecksConfig::default()
+        },
+    )?;
+
+    assert!(send_transaction(&provider, transaction).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auto_mines_transactions_below_base_fee_with_min_gas_price() -> anyhow::Result<()> {
+    const MIN_GAS_PRICE: u128 = 1_000;
+
+    let (provider, owned_account) = create_impersonating_provider(|config| {
+        config.disable_base_fee_check = true;
+        config.disable_base_fee_adjustment = true;
+        config.initial_base_fee_per_gas = Some(1_000_000_000);
+        config.min_gas_price = MIN_GAS_PRICE;
+    })?;
+
+    // The priority fee satisfies the minimum gas price, but the max fee is below
+    // the base fee, so the transaction doesn't pay the miner
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: owned_account,
+            max_fee_per_gas: Some(MIN_GAS_PRICE),
+            max_priority_fee_per_gas: Some(MIN_GAS_PRICE),
+            ..TransactionRequest::default()
+        },
+    )?;
+    assert_mined(&provider, transaction_hash)?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "test-utils")]
+
+use alloy_eips::eip4844::BYTES_PER_BLOB;
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::Blob;
+use edr_primitives::{Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    FillTransactionResult, MethodInvocation, MiningConfig, Provider, ProviderError,
+    ProviderRequest,
+};
+
+fn create_fill_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.mining = MiningConfig {
+        auto_mine: false,
+        ..MiningConfig::default()
+    };
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn next_nonce_includes_pending_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_fill_provider()?;
+
+    let nonce: U64 = request(&provider, MethodInvocation::GetNextNonce(sender.into()))?;
+    assert_eq!(nonce, U64::from(0));
+
+    for _ in 0..2 {
+        request::<B256, _, _>(
+            &provider,
+            MethodInvocation::SendTransaction(TransactionRequest {
+                from: sender,
+                to: Some(Address::random()),
+                ..TransactionRequest::default()
+            }),
+        )?;
+    }
+
+    let nonce: U64 = request(&provider, MethodInvocation::GetNextNonce(sender.into()))?;
+    assert_eq!(nonce, U64::from(2));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fill_transaction_populates_missing_fields() -> anyhow::Result<()> {
+    let (provider, sender) = create_fill_provider()?;
+    let recipient = Address::random();
+
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let FillTransactionResult { raw, tx: filled } = request(
+        &provider,
+        MethodInvocation::FillTransaction(TransactionRequest {
+            from: sender,
+            to: Some(recipient),
+            value: Some(U256::from(100)),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let chain_id: U64 = request(&provider, MethodInvocation::ChainId(()))?;
+
+    assert_eq!(filled.from, sender);
+    assert_eq!(filled.to, Some(recipient));
+    assert_eq!(filled.value, Some(U256::from(100)));
+    assert_eq!(filled.data, Some(Bytes::new()));
+    assert_eq!(filled.nonce, Some(1));
+    assert_eq!(filled.gas, Some(21_000));
+    assert_eq!(filled.chain_id, Some(chain_id.to::<u64>()));
+    assert_eq!(filled.transaction_type, Some(2));
+    assert_eq!(filled.gas_price, None);
+    assert!(filled.max_fee_per_gas.is_some());
+    assert!(filled.max_priority_fee_per_gas.is_some());
+
+    // The raw transaction is an EIP-1559 envelope
+    assert_eq!(raw.first(), Some(&2));
+
+    // The filled transaction can be signed and sent as-is
+    let raw_transaction: Bytes = request(&provider, MethodInvocation::SignTransaction(filled))?;
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendRawTransaction(raw_transaction),
+    )?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fill_transaction_keeps_provided_fields() -> anyhow::Result<()> {
+    let (provider, sender) = create_fill_provider()?;
+
+    let FillTransactionResult { tx: filled, .. } = request(
+        &provider,
+        MethodInvocation::FillTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            gas: Some(50_000),
+            gas_price: Some(10_000_000_000),
+            nonce: Some(5),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    assert_eq!(filled.gas, Some(50_000));
+    assert_eq!(filled.gas_price, Some(10_000_000_000));
+    assert_eq!(filled.nonce, Some(5));
+    assert_eq!(filled.max_fee_per_gas, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fill_transaction_rejects_blobs() -> anyhow::Result<()> {
+    let (provider, sender) = create_fill_provider()?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::FillTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            blobs: Some(vec![Blob::new([0u8; BYTES_PER_BLOB])]),
+            ..TransactionRequest::default()
+        }),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::Eip4844TransactionUnsupported)
+    ));
+
+    Ok(())
+}
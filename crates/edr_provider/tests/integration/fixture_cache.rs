@@ -0,0 +1,150 @@
+#![cfg(feature = "test-utils")]
+
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, B256, U256};
+use edr_provider::{
+    hardhat_rpc_types::FixtureResult,
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderRequest,
+};
+
+fn create_fixture_provider(
+    checkpoint_dir: &Path,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.checkpoint_dir = checkpoint_dir.to_path_buf();
+    // All providers need to have the same genesis block
+    config.initial_date = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn run_fixture(
+    provider: &Provider<L1ChainSpec>,
+    transactions: Vec<TransactionRequest>,
+) -> anyhow::Result<FixtureResult> {
+    request(provider, MethodInvocation::RunFixture(transactions))
+}
+
+fn latest_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+fn transfers(sender: Address, receiver: Address, count: u64) -> Vec<TransactionRequest> {
+    (0..count)
+        .map(|value| TransactionRequest {
+            from: sender,
+            to: Some(receiver),
+            value: Some(U256::from(value + 1)),
+            ..TransactionRequest::default()
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restores_cached_fixture() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let receiver = Address::random();
+
+    let (first, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    let created = run_fixture(&first, transfers(sender, receiver, 2))?;
+    assert!(!created.cache_hit);
+    assert_eq!(created.transaction_hashes.len(), 2);
+
+    let (second, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    let restored = run_fixture(&second, transfers(sender, receiver, 2))?;
+    assert!(restored.cache_hit);
+    assert_eq!(restored.key, created.key);
+    assert_eq!(restored.transaction_hashes, created.transaction_hashes);
+
+    assert_eq!(latest_block(&second)?.hash, latest_block(&first)?.hash);
+
+    let balance: U256 = request(&second, MethodInvocation::GetBalance(receiver, None))?;
+    assert_eq!(balance, U256::from(3));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn different_transactions_miss_the_cache() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let receiver = Address::random();
+
+    let (first, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    let created = run_fixture(&first, transfers(sender, receiver, 2))?;
+
+    let (second, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    let other = run_fixture(&second, transfers(sender, receiver, 3))?;
+    assert!(!other.cache_hit);
+    assert_ne!(other.key, created.key);
+    assert_eq!(other.transaction_hashes.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn different_state_overrides_miss_the_cache() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let receiver = Address::random();
+
+    let (first, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    let created = run_fixture(&first, transfers(sender, receiver, 2))?;
+
+    let (second, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    request::<bool, _, _>(
+        &second,
+        MethodInvocation::SetBalance(receiver, U256::from(1_000)),
+    )?;
+
+    let other = run_fixture(&second, transfers(sender, receiver, 2))?;
+    assert!(!other.cache_hit);
+    assert_ne!(other.key, created.key);
+
+    let balance: U256 = request(&second, MethodInvocation::GetBalance(receiver, None))?;
+    assert_eq!(balance, U256::from(1_003));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_fixture_is_reverted() -> anyhow::Result<()> {
+    let checkpoint_dir = tempfile::tempdir()?;
+    let receiver = Address::random();
+
+    let (provider, sender) = create_fixture_provider(checkpoint_dir.path())?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let block_before = latest_block(&provider)?;
+
+    // Transactions of unknown senders are rejected
+    let mut transactions = transfers(sender, receiver, 1);
+    transactions.extend(transfers(Address::random(), receiver, 1));
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::RunFixture(transactions),
+    ));
+    assert!(result.is_err());
+
+    let pending_transactions: Vec<serde_json::Value> =
+        request(&provider, MethodInvocation::PendingTransactions(()))?;
+    assert!(pending_transactions.is_empty());
+
+    assert_eq!(latest_block(&provider)?.hash, block_before.hash);
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::rpc::{receipt::L1RpcTransactionReceipt, TransactionRequest};
+use edr_primitives::{Address, Bytes, B256};
+use edr_provider::{
+    hardhat_rpc_types::GasProfile,
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, ProviderError, ProviderRequest,
+};
+
+/// Runtime code that reads a cold storage slot: PUSH1 0x00 SLOAD POP STOP
+const CALLEE_CODE: [u8; 5] = [0x60, 0x00, 0x54, 0x50, 0x00];
+/// The gas used by `CALLEE_CODE`: PUSH1 (3), cold SLOAD (2100), and POP (2).
+const CALLEE_GAS: u64 = 2_105;
+
+/// Runtime code that calls the provided address without any data or value.
+fn calling_code(callee: Address) -> Bytes {
+    // PUSH1 0x00 (x5) PUSH20 <callee> GAS CALL STOP
+    let mut code = [0x60, 0x00].repeat(5);
+    code.push(0x73);
+    code.extend_from_slice(callee.as_slice());
+    code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+
+    code.into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn attributes_gas_to_call_frames() -> anyhow::Result<()> {
+    let config = create_test_config();
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    let caller = Address::random();
+    let callee = Address::random();
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(caller, calling_code(callee)),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(callee, Bytes::from_static(&CALLEE_CODE)),
+    )?;
+
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(caller),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let profile: GasProfile =
+        request(&provider, MethodInvocation::GetGasProfile(transaction_hash))?;
+
+    let receipt: Option<L1RpcTransactionReceipt> = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    let receipt = receipt.expect("transaction should be mined");
+    assert_eq!(profile.gas_used, receipt.gas_used);
+
+    let [caller_frame, callee_frame] = profile.frames.as_slice() else {
+        panic!("expected two call frames, got {:?}", profile.frames);
+    };
+
+    assert_eq!(caller_frame.depth, 0);
+    assert_eq!(callee_frame.depth, 1);
+
+    assert_eq!(callee_frame.gas_used, CALLEE_GAS);
+    assert_eq!(callee_frame.self_gas, CALLEE_GAS);
+
+    // The gas used by the callee isn't attributed to the caller's own
+    // instructions
+    assert_eq!(
+        caller_frame.gas_used,
+        caller_frame.self_gas + callee_frame.gas_used
+    );
+
+    // Neither contract is known to the contract decoder
+    assert!(profile.functions.is_empty());
+    assert!(profile.lines.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_transaction() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let transaction_hash = B256::random();
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetGasProfile(transaction_hash),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::InvalidTransactionHash(hash)) if hash == transaction_hash
+    ));
+
+    Ok(())
+}
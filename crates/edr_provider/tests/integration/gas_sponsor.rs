@@ -0,0 +1,228 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{call::L1CallRequest, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_primitives::{Address, Bytes, HashSet, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    GasSponsorConfig, MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+const GAS_LIMIT: u64 = 100_000;
+const GAS_PRICE: u128 = 1_000_000_000;
+
+fn create_sponsored_provider(
+    gas_sponsor: impl FnOnce(Address) -> GasSponsorConfig,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.auto_impersonate = true;
+
+    let owned_account = owned_account(&config, 0);
+    config.gas_sponsor = Some(gas_sponsor(owned_account));
+
+    let provider = create_provider(config)?;
+
+    Ok((provider, owned_account))
+}
+
+fn balance(provider: &Provider<L1ChainSpec>, address: Address) -> anyhow::Result<U256> {
+    request(provider, MethodInvocation::GetBalance(address, None))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    from: Address,
+) -> Result<B256, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from,
+            to: Some(Address::random()),
+            gas: Some(GAS_LIMIT),
+            gas_price: Some(GAS_PRICE),
+            ..TransactionRequest::default()
+        }),
+    ))?;
+
+    Ok(serde_json::from_value(response.result).expect("should be a transaction hash"))
+}
+
+fn prepaid_gas_cost() -> U256 {
+    U256::from(GAS_LIMIT) * U256::from(GAS_PRICE)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sponsor_pays_for_gas_used() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let (provider, sponsor) = create_sponsored_provider(|sponsor| GasSponsorConfig {
+        sponsor,
+        senders: HashSet::from_iter([sender]),
+    })?;
+
+    let sponsor_balance = balance(&provider, sponsor)?;
+
+    let transaction_hash = send_transaction(&provider, sender)?;
+
+    let receipt: serde_json::Value = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    let gas_used: U256 = serde_json::from_value(receipt["gasUsed"].clone())?;
+    let effective_gas_price: U256 = serde_json::from_value(receipt["effectiveGasPrice"].clone())?;
+
+    // The sender paid nothing, whereas the sponsor paid exactly for the gas used
+    assert_eq!(balance(&provider, sender)?, U256::ZERO);
+    assert_eq!(
+        balance(&provider, sponsor)?,
+        sponsor_balance - gas_used * effective_gas_price
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unsponsored_senders_pay_for_gas() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let (provider, sponsor) = create_sponsored_provider(|sponsor| GasSponsorConfig {
+        sponsor,
+        senders: HashSet::from_iter([Address::random()]),
+    })?;
+
+    let sponsor_balance = balance(&provider, sponsor)?;
+
+    let result = send_transaction(&provider, sender);
+    assert!(result.is_err());
+
+    assert_eq!(balance(&provider, sender)?, U256::ZERO);
+    assert_eq!(balance(&provider, sponsor)?, sponsor_balance);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_transactions_if_sponsor_has_insufficient_funds() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let sponsor = Address::random();
+    let (provider, _owned_account) =
+        create_sponsored_provider(|_owned_account| GasSponsorConfig {
+            sponsor,
+            senders: HashSet::from_iter([sender]),
+        })?;
+
+    let result = send_transaction(&provider, sender);
+    assert!(matches!(
+        result,
+        Err(ProviderError::GasSponsorInsufficientFunds { .. })
+    ));
+
+    assert_eq!(balance(&provider, sender)?, U256::ZERO);
+    assert_eq!(balance(&provider, sponsor)?, U256::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dropped_transactions_refund_sponsor() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let (provider, sponsor) = create_sponsored_provider(|sponsor| GasSponsorConfig {
+        sponsor,
+        senders: HashSet::from_iter([sender]),
+    })?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let sponsor_balance = balance(&provider, sponsor)?;
+
+    let transaction_hash = send_transaction(&provider, sender)?;
+    assert_eq!(balance(&provider, sender)?, prepaid_gas_cost());
+    assert_eq!(
+        balance(&provider, sponsor)?,
+        sponsor_balance - prepaid_gas_cost()
+    );
+
+    let was_dropped: bool = request(
+        &provider,
+        MethodInvocation::DropTransaction(transaction_hash),
+    )?;
+    assert!(was_dropped);
+
+    assert_eq!(balance(&provider, sender)?, U256::ZERO);
+    assert_eq!(balance(&provider, sponsor)?, sponsor_balance);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverting_to_snapshot_restores_sponsored_transactions() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let (provider, sponsor) = create_sponsored_provider(|sponsor| GasSponsorConfig {
+        sponsor,
+        senders: HashSet::from_iter([sender]),
+    })?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let sponsor_balance = balance(&provider, sponsor)?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    send_transaction(&provider, sender)?;
+
+    let was_reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(was_reverted);
+
+    assert_eq!(balance(&provider, sender)?, U256::ZERO);
+    assert_eq!(balance(&provider, sponsor)?, sponsor_balance);
+
+    // Mining an empty block doesn't refund the reverted sponsorship
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    assert_eq!(balance(&provider, sponsor)?, sponsor_balance);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn calls_are_sponsored() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let (provider, sponsor) = create_sponsored_provider(|sponsor| GasSponsorConfig {
+        sponsor,
+        senders: HashSet::from_iter([sender]),
+    })?;
+
+    let sponsor_balance = balance(&provider, sponsor)?;
+
+    // PUSH20 <sponsor> BALANCE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    let mut code = vec![0x73];
+    code.extend_from_slice(sponsor.as_slice());
+    code.extend_from_slice(&[0x31, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+    let contract = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(contract, Bytes::from(code)),
+    )?;
+
+    let result: Bytes = request(
+        &provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                from: Some(sender),
+                to: Some(contract),
+                gas: Some(GAS_LIMIT),
+                gas_price: Some(GAS_PRICE),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )?;
+
+    // The sponsor prepaid the gas cost of the call
+    assert_eq!(
+        U256::from_be_slice(&result),
+        sponsor_balance - prepaid_gas_cost()
+    );
+
+    // Calls don't modify the state
+    assert_eq!(balance(&provider, sponsor)?, sponsor_balance);
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "test-utils")]
+
+use std::time::UNIX_EPOCH;
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{address, bytes, Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    AccountOverride, GethGenesis, GethGenesisError, MethodInvocation, Provider,
+};
+
+const PREDEPLOY_ADDRESS: Address = address!("0x4200000000000000000000000000000000000015");
+
+const GENESIS_JSON: &str = r#"{
+    "config": {
+        "chainId": 901
+    },
+    "nonce": "0x0",
+    "timestamp": "0x65000000",
+    "extraData": "0x4544522067656e65736973",
+    "gasLimit": "0x1c9c380",
+    "difficulty": "0x0",
+    "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+    "coinbase": "0x0000000000000000000000000000000000000000",
+    "baseFeePerGas": "0x3b9aca00",
+    "alloc": {
+        "4200000000000000000000000000000000000015": {
+            "balance": "0x2",
+            "nonce": "0x1",
+            "code": "0x600160005260206000f3",
+            "storage": {
+                "0x0000000000000000000000000000000000000000000000000000000000000001": "0x000000000000000000000000000000000000000000000000000000000000002a"
+            }
+        }
+    }
+}"#;
+
+fn create_genesis_provider(genesis: GethGenesis) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    genesis.apply_to(&mut config);
+
+    create_provider(config)
+}
+
+#[test]
+fn parses_genesis_json() -> anyhow::Result<()> {
+    let genesis = GethGenesis::from_json(GENESIS_JSON)?;
+
+    assert_eq!(genesis.chain_id, 901);
+    assert_eq!(genesis.block_gas_limit.get(), 30_000_000);
+    assert_eq!(genesis.initial_base_fee_per_gas, Some(1_000_000_000));
+    assert_eq!(
+        genesis.initial_date.duration_since(UNIX_EPOCH)?.as_secs(),
+        0x6500_0000
+    );
+    assert_eq!(genesis.extra_data, bytes!("0x4544522067656e65736973"));
+
+    let account = genesis
+        .genesis_state
+        .get(&PREDEPLOY_ADDRESS)
+        .expect("should have the predeploy");
+    assert_eq!(account.balance, Some(U256::from(2)));
+    assert_eq!(account.nonce, Some(1));
+
+    let storage = account.storage.as_ref().expect("should have storage");
+    assert_eq!(
+        storage.get(&U256::from(1)).map(|slot| slot.present_value),
+        Some(U256::from(42))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_genesis_json() {
+    assert!(matches!(
+        GethGenesis::from_json("{"),
+        Err(GethGenesisError::InvalidJson(_))
+    ));
+
+    let zero_gas_limit = GENESIS_JSON.replace(r#""gasLimit": "0x1c9c380""#, r#""gasLimit": "0x0""#);
+    assert!(matches!(
+        GethGenesis::from_json(&zero_gas_limit),
+        Err(GethGenesisError::ZeroGasLimit)
+    ));
+
+    let long_extra_data = GENESIS_JSON.replace(
+        r#""extraData": "0x4544522067656e65736973""#,
+        &format!(r#""extraData": "0x{}""#, "00".repeat(33)),
+    );
+    assert!(matches!(
+        GethGenesis::from_json(&long_extra_data),
+        Err(GethGenesisError::ExtraDataTooLong(33))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn initializes_local_blockchain() -> anyhow::Result<()> {
+    let provider = create_genesis_provider(GethGenesis::from_json(GENESIS_JSON)?)?;
+
+    let chain_id: U64 = request(&provider, MethodInvocation::ChainId(()))?;
+    assert_eq!(chain_id, U64::from(901));
+
+    let balance: U256 = request(
+        &provider,
+        MethodInvocation::GetBalance(PREDEPLOY_ADDRESS, None),
+    )?;
+    assert_eq!(balance, U256::from(2));
+
+    let nonce: U256 = request(
+        &provider,
+        MethodInvocation::GetTransactionCount(PREDEPLOY_ADDRESS, None),
+    )?;
+    assert_eq!(nonce, U256::from(1));
+
+    let code: Bytes = request(
+        &provider,
+        MethodInvocation::GetCode(PREDEPLOY_ADDRESS, None),
+    )?;
+    assert_eq!(code, bytes!("0x600160005260206000f3"));
+
+    let value: B256 = request(
+        &provider,
+        MethodInvocation::GetStorageAt(PREDEPLOY_ADDRESS, U256::from(1), None),
+    )?;
+    assert_eq!(value, B256::from(U256::from(42)));
+
+    let genesis_block: L1RpcBlock<B256> = request(
+        &provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::Number(0), false),
+    )?;
+    assert_eq!(genesis_block.gas_limit, 30_000_000);
+    assert_eq!(genesis_block.timestamp, 0x6500_0000);
+    assert_eq!(genesis_block.extra_data, bytes!("0x4544522067656e65736973"));
+    assert_eq!(genesis_block.base_fee_per_gas, Some(1_000_000_000));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn uses_zero_genesis_timestamp() -> anyhow::Result<()> {
+    let genesis_json =
+        GENESIS_JSON.replace(r#""timestamp": "0x65000000""#, r#""timestamp": "0x0""#);
+    let provider = create_genesis_provider(GethGenesis::from_json(&genesis_json)?)?;
+
+    let genesis_block: L1RpcBlock<B256> = request(
+        &provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::Number(0), false),
+    )?;
+    assert_eq!(genesis_block.timestamp, 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn genesis_state_overrides_take_precedence() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.genesis_state.insert(
+        PREDEPLOY_ADDRESS,
+        AccountOverride {
+            balance: Some(U256::from(100)),
+            ..AccountOverride::default()
+        },
+    );
+
+    GethGenesis::from_json(GENESIS_JSON)?.apply_to(&mut config);
+    let provider = create_provider(config)?;
+
+    let balance: U256 = request(
+        &provider,
+        MethodInvocation::GetBalance(PREDEPLOY_ADDRESS, None),
+    )?;
+    assert_eq!(balance, U256::from(100));
+
+    // Fields that aren't overridden are taken from the `alloc` section
+    let code: Bytes = request(
+        &provider,
+        MethodInvocation::GetCode(PREDEPLOY_ADDRESS, None),
+    )?;
+    assert_eq!(code, bytes!("0x600160005260206000f3"));
+
+    Ok(())
+}
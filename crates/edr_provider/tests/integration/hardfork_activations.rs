@@ -0,0 +1,144 @@
+#![cfg(feature = "test-utils")]
+
+use std::collections::BTreeMap;
+
+use alloy_eips::eip7825::MAX_TX_GAS_LIMIT_OSAKA;
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_mem_pool::MemPoolAddTransactionError;
+use edr_primitives::{Address, B256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderError, ProviderErrorForChainSpec, ProviderRequest,
+};
+
+const CANCUN_ACTIVATION_BLOCK: u64 = 2;
+
+fn get_block_by_number(
+    provider: &Provider<L1ChainSpec>,
+    block_number: u64,
+) -> anyhow::Result<L1RpcBlock<B256>> {
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::Number(block_number), false),
+    )
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    gas_limit: u64,
+) -> Result<B256, ProviderErrorForChainSpec<L1ChainSpec>> {
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            gas: Some(gas_limit),
+            ..TransactionRequest::default()
+        }),
+    ))?;
+
+    Ok(serde_json::from_value(result.result).expect("should be a transaction hash"))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn activates_hardfork_at_block_number() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::SHANGHAI;
+    config.hardfork_activations =
+        BTreeMap::from([(CANCUN_ACTIVATION_BLOCK, edr_chain_l1::Hardfork::CANCUN)]);
+
+    let provider = create_provider(config)?;
+
+    for _ in 0..CANCUN_ACTIVATION_BLOCK {
+        request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    }
+
+    let shanghai_block = get_block_by_number(&provider, CANCUN_ACTIVATION_BLOCK - 1)?;
+    assert_eq!(shanghai_block.blob_gas_used, None);
+    assert_eq!(shanghai_block.parent_beacon_block_root, None);
+
+    let cancun_block = get_block_by_number(&provider, CANCUN_ACTIVATION_BLOCK)?;
+    assert_eq!(cancun_block.blob_gas_used, Some(0));
+    assert!(cancun_block.parent_beacon_block_root.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn activation_at_block_zero_determines_genesis_hardfork() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::SHANGHAI;
+    config.hardfork_activations = BTreeMap::from([(0, edr_chain_l1::Hardfork::CANCUN)]);
+
+    let provider = create_provider(config)?;
+
+    let genesis_block = get_block_by_number(&provider, 0)?;
+    assert_eq!(genesis_block.blob_gas_used, Some(0));
+
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let first_block = get_block_by_number(&provider, 1)?;
+    assert_eq!(first_block.blob_gas_used, Some(0));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reserved_blocks_activate_hardfork_at_block_number() -> anyhow::Result<()> {
+    const CANCUN_ACTIVATION_BLOCK: u64 = 50;
+
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::SHANGHAI;
+    config.hardfork_activations =
+        BTreeMap::from([(CANCUN_ACTIVATION_BLOCK, edr_chain_l1::Hardfork::CANCUN)]);
+
+    let provider = create_provider(config)?;
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::Mine(
+        Some(2 * CANCUN_ACTIVATION_BLOCK),
+        None,
+    )))?;
+
+    let shanghai_block = get_block_by_number(&provider, CANCUN_ACTIVATION_BLOCK - 1)?;
+    assert_eq!(shanghai_block.blob_gas_used, None);
+
+    let cancun_block = get_block_by_number(&provider, CANCUN_ACTIVATION_BLOCK)?;
+    assert_eq!(cancun_block.blob_gas_used, Some(0));
+    assert!(cancun_block.parent_beacon_block_root.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applies_transaction_gas_cap_of_activated_hardfork() -> anyhow::Result<()> {
+    const OSAKA_ACTIVATION_BLOCK: u64 = 2;
+    const EXCEEDS_TRANSACTION_GAS_CAP: u64 = MAX_TX_GAS_LIMIT_OSAKA + 1;
+
+    let mut config = create_test_config();
+    config.hardfork = edr_chain_l1::Hardfork::PRAGUE;
+    config.hardfork_activations =
+        BTreeMap::from([(OSAKA_ACTIVATION_BLOCK, edr_chain_l1::Hardfork::OSAKA)]);
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    // Block 1 is mined with Prague, so the transaction isn't capped
+    send_transaction(&provider, sender, EXCEEDS_TRANSACTION_GAS_CAP)?;
+
+    let result = send_transaction(&provider, sender, EXCEEDS_TRANSACTION_GAS_CAP);
+    assert!(matches!(
+        result,
+        Err(ProviderError::MemPoolAddTransaction(
+            MemPoolAddTransactionError::ExceedsTransactionGasCap {
+                transaction_gas_cap: MAX_TX_GAS_LIMIT_OSAKA,
+                transaction_gas_limit: EXCEEDS_TRANSACTION_GAS_CAP,
+            }
+        ))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "test-utils")]
+
+use std::num::NonZeroU64;
+
+use edr_chain_l1::L1ChainSpec;
+use edr_primitives::{Address, Bytes, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{FloodOptions, FloodResult},
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+fn flood(provider: &Provider<L1ChainSpec>, options: FloodOptions) -> anyhow::Result<FloodResult> {
+    request(provider, MethodInvocation::Flood(options))
+}
+
+fn block_number(provider: &Provider<L1ChainSpec>) -> anyhow::Result<u64> {
+    let block_number: U64 = request(provider, MethodInvocation::BlockNumber(()))?;
+
+    Ok(block_number.to())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn flood_transfers() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let receiver = Address::random();
+    let result = flood(
+        &provider,
+        FloodOptions {
+            count: U64::from(50),
+            to: Some(receiver),
+            value: Some(U256::from(1)),
+            gas: Some(U64::from(21_000)),
+            ..FloodOptions::default()
+        },
+    )?;
+
+    assert_eq!(result.transactions, 50);
+    assert_eq!(result.failed, 0);
+    assert_eq!(result.blocks, 1);
+    assert_eq!(result.gas_used, 50 * 21_000);
+    assert_eq!(block_number(&provider)?, 1);
+
+    let balance: U256 = request(&provider, MethodInvocation::GetBalance(receiver, None))?;
+    assert_eq!(balance, U256::from(50));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn flood_spans_multiple_blocks() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.block_gas_limit = NonZeroU64::new(3 * 21_000).expect("non-zero");
+
+    let provider = create_provider(config)?;
+
+    let result = flood(
+        &provider,
+        FloodOptions {
+            count: U64::from(10),
+            to: Some(Address::random()),
+            gas: Some(U64::from(21_000)),
+            ..FloodOptions::default()
+        },
+    )?;
+
+    assert_eq!(result.transactions, 10);
+    assert_eq!(result.blocks, 4);
+    assert_eq!(block_number(&provider)?, 4);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn flood_counts_failed_transactions() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    // Init code consisting of the INVALID opcode
+    let result = flood(
+        &provider,
+        FloodOptions {
+            count: U64::from(5),
+            data: Some(Bytes::from_static(&[0xfe])),
+            gas: Some(U64::from(100_000)),
+            ..FloodOptions::default()
+        },
+    )?;
+
+    assert_eq!(result.transactions, 5);
+    assert_eq!(result.failed, 5);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn flood_estimates_default_gas_limit() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let result = flood(
+        &provider,
+        FloodOptions {
+            count: U64::from(20),
+            to: Some(Address::random()),
+            ..FloodOptions::default()
+        },
+    )?;
+
+    // Transactions aren't limited to one per block
+    assert_eq!(result.transactions, 20);
+    assert_eq!(result.blocks, 1);
+    assert_eq!(result.gas_used, 20 * 21_000);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn flood_tops_up_impersonated_sender_per_block() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.auto_fund = true;
+
+    let provider = create_provider(config)?;
+
+    let sender = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::ImpersonateAccount(sender.into()),
+    )?;
+
+    let result = flood(
+        &provider,
+        FloodOptions {
+            count: U64::from(10),
+            from: Some(sender),
+            to: Some(Address::random()),
+            value: Some(U256::from(1_000)),
+            gas: Some(U64::from(21_000)),
+            ..FloodOptions::default()
+        },
+    )?;
+
+    assert_eq!(result.transactions, 10);
+    assert_eq!(result.failed, 0);
+    assert_eq!(result.blocks, 1);
+
+    Ok(())
+}
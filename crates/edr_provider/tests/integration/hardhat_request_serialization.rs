@@ -1,9 +1,55 @@
-use edr_chain_l1::L1ChainSpec;
-use edr_primitives::{Address, Bytes, B256, U128, U160, U256};
-use edr_provider::MethodInvocation;
+use std::{collections::HashMap, path::PathBuf};
+
+use edr_block_header::Withdrawal;
+use edr_chain_l1::{
+    rpc::{call::L1CallRequest, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U128, U160, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{
+        AuthorizationRequest, BundleTransactionRequest, FeeChecksConfig, FloodOptions,
+        InclusionAction, InclusionPolicy, InclusionRule, LogVerbosity, LoggingOptions,
+        MempoolConfig, NextBlockHeaderOverrides, ReorgOptions, StorageEncoding, StorageLayout,
+        StorageLayoutEntry, StorageLayoutType, TokenStandard, TokenTransferFilter,
+        TransactionImportSource, UserOperation,
+    },
+    MethodInvocation,
+};
+use edr_solidity::external_artifact::ExternalArtifact;
 
 use crate::common::help_test_method_invocation_serde;
 
+#[test]
+fn serde_hardhat_add_contract_artifact() {
+    let abi = serde_json::from_value(serde_json::json!([{
+        "type": "function",
+        "name": "transfer",
+        "inputs": [
+            { "name": "to", "type": "address", "internalType": "address" },
+            { "name": "amount", "type": "uint256", "internalType": "uint256" }
+        ],
+        "outputs": [{ "name": "", "type": "bool", "internalType": "bool" }],
+        "stateMutability": "nonpayable"
+    }]))
+    .expect("ABI should be valid");
+
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AddContractArtifact(
+        ExternalArtifact {
+            contract_name: "Token".to_string(),
+            source_name: Some("src/Token.sol".to_string()),
+            abi,
+            bytecode: None,
+            deployed_bytecode: Some(Bytes::from_static(&[0x60, 0x80, 0x60, 0x40, 0x52])),
+            link_references: HashMap::new(),
+            deployed_link_references: HashMap::new(),
+            immutable_references: HashMap::new(),
+            compiler_version: "0.8.24+commit.e11b9ed9".to_string(),
+        },
+    ));
+}
+
 #[test]
 fn serde_hardhat_drop_transaction() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::DropTransaction(
@@ -11,11 +57,67 @@ fn serde_hardhat_drop_transaction() {
     ));
 }
 
+#[test]
+fn serde_hardhat_enqueue_deposit() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::EnqueueDeposit(
+        Bytes::from_static(&[0x7e, 0xc0]),
+    ));
+}
+
+#[test]
+fn serde_hardhat_export_chain() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ExportChain(()));
+}
+
+#[test]
+fn serde_hardhat_flood() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Flood(FloodOptions {
+        count: U64::from(100),
+        from: Some(Address::from(U160::from(1))),
+        to: Some(Address::from(U160::from(2))),
+        value: Some(U256::from(3)),
+        data: Some(Bytes::from(&b"whatever"[..])),
+        gas: Some(U64::from(21_000)),
+    }));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Flood(FloodOptions {
+        count: U64::from(1),
+        ..FloodOptions::default()
+    }));
+}
+
 #[test]
 fn serde_hardhat_get_automine() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetAutomine(()));
 }
 
+#[test]
+fn serde_hardhat_get_gas_profile() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetGasProfile(B256::from(
+        U256::from(1),
+    )));
+}
+
+#[test]
+fn serde_hardhat_get_next_nonce() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetNextNonce(
+        Address::from(U160::from(1)).into(),
+    ));
+}
+
+#[test]
+fn serde_hardhat_get_state_diff() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetStateDiff(B256::from(
+        U256::from(1),
+    )));
+}
+
+#[test]
+fn serde_hardhat_auto_impersonate_account() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AutoImpersonateAccount(
+        true,
+    ));
+}
+
 #[test]
 fn serde_hardhat_impersonate_account() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ImpersonateAccount(
@@ -23,6 +125,37 @@ fn serde_hardhat_impersonate_account() {
     ));
 }
 
+#[test]
+fn serde_hardhat_get_transaction_tags() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::GetTransactionTags(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn serde_hardhat_import_chain() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ImportChain(Bytes::from(
+        &b"chain"[..],
+    )));
+}
+
+#[test]
+fn serde_hardhat_import_transactions() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ImportTransactions(
+        TransactionImportSource::Transactions(vec![Bytes::from(&b"transaction"[..])]),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ImportTransactions(
+        TransactionImportSource::File(PathBuf::from("transactions.txt")),
+    ));
+}
+
+#[test]
+fn serde_hardhat_load_checkpoint() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::LoadCheckpoint(
+        String::from("fixture"),
+    ));
+}
+
 #[test]
 fn serde_hardhat_metadata() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Metadata(()));
@@ -41,6 +174,116 @@ fn serde_hardhat_mine() {
     assert_eq!(MethodInvocation::Mine(None, None), deserialized);
 }
 
+#[test]
+fn serde_anvil_drop_all_transactions() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::AnvilDropAllTransactions(()),
+    );
+}
+
+#[test]
+fn serde_anvil_drop_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AnvilDropTransaction(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn serde_anvil_mine() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AnvilMine(
+        Some(1_000_000),
+        Some(12),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AnvilMine(None, None));
+
+    let json = r#"{"jsonrpc":"2.0","method":"anvil_mine","params":["0x3e8"],"id":2}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(MethodInvocation::AnvilMine(Some(1000), None), deserialized);
+}
+
+#[test]
+fn serde_anvil_node_info() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AnvilNodeInfo(()));
+}
+
+#[test]
+fn serde_anvil_set_logging_enabled() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::AnvilSetLoggingEnabled(
+        false,
+    ));
+}
+
+#[test]
+fn serde_anvil_set_next_block_base_fee_per_gas() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::AnvilSetNextBlockBaseFeePerGas(U128::from(1)),
+    );
+}
+
+#[test]
+fn serde_hardhat_pause_interval_mining() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PauseIntervalMining(()));
+}
+
+#[test]
+fn serde_hardhat_predict_create2_address() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PredictCreate2Address(
+        Address::from(U160::from(1)),
+        B256::from(U256::from(2)),
+        Bytes::from(&b"init code"[..]),
+    ));
+}
+
+#[test]
+fn serde_hardhat_predict_create_address() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PredictCreateAddress(
+        Address::from(U160::from(1)),
+        Some(2),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::PredictCreateAddress(
+        Address::from(U160::from(1)),
+        None,
+    ));
+}
+
+#[test]
+fn serde_hardhat_resume_interval_mining() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ResumeIntervalMining(()));
+}
+
+#[test]
+fn serde_hardhat_run_fixture() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::RunFixture(vec![
+        TransactionRequest {
+            from: Address::random(),
+            data: Some(Bytes::from(&b"deploy"[..])),
+            ..TransactionRequest::default()
+        },
+    ]));
+}
+
+#[test]
+fn serde_hardhat_save_checkpoint() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SaveCheckpoint(
+        String::from("fixture"),
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_allow_unlimited_contract_size() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::SetAllowUnlimitedContractSize(true),
+    );
+}
+
+#[test]
+fn serde_hardhat_set_allow_unlimited_init_code_size() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::SetAllowUnlimitedInitCodeSize(false),
+    );
+}
+
 #[test]
 fn serde_hardhat_set_balance() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetBalance(
@@ -49,6 +292,14 @@ fn serde_hardhat_set_balance() {
     ));
 }
 
+#[test]
+fn serde_hardhat_set_beacon_root() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetBeaconRoot(
+        1_700_000_000,
+        B256::random(),
+    ));
+}
+
 #[test]
 fn serde_hardhat_set_code() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetCode(
@@ -64,11 +315,176 @@ fn serde_hardhat_set_coinbase() {
     ));
 }
 
+#[test]
+fn serde_hardhat_set_inclusion_policy() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetInclusionPolicy(
+        InclusionPolicy {
+            rules: vec![
+                InclusionRule {
+                    transaction_hashes: Some(vec![B256::random()]),
+                    senders: None,
+                    probability: None,
+                    action: InclusionAction::Delay {
+                        blocks: U64::from(3),
+                    },
+                },
+                InclusionRule {
+                    transaction_hashes: None,
+                    senders: Some(vec![Address::random()]),
+                    probability: Some(0.5),
+                    action: InclusionAction::Drop,
+                },
+            ],
+            shuffle: true,
+            seed: U64::from(42),
+        },
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_log_filter() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetLogFilter(
+        "edr_provider=debug".to_owned(),
+    ));
+}
+
 #[test]
 fn serde_hardhat_set_logging_enabled() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetLoggingEnabled(true));
 }
 
+#[test]
+fn serde_hardhat_simulate_bundle() {
+    let call = L1CallRequest {
+        from: Some(Address::from(U160::from(1))),
+        to: Some(Address::from(U160::from(2))),
+        value: Some(U256::from(3)),
+        ..L1CallRequest::default()
+    };
+
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SimulateBundle(
+        vec![
+            BundleTransactionRequest::Signed(Bytes::from(&b"signed"[..])),
+            BundleTransactionRequest::Unsigned(call.clone()),
+        ],
+        Some(BlockSpec::Number(7)),
+    ));
+
+    let json = r#"{"jsonrpc":"2.0","method":"hardhat_simulateBundle","params":[["0x01",{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x3"}]],"id":1}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(
+        MethodInvocation::SimulateBundle(
+            vec![
+                BundleTransactionRequest::Signed(Bytes::from_static(&[0x01])),
+                BundleTransactionRequest::Unsigned(call),
+            ],
+            Some(BlockSpec::latest()),
+        ),
+        deserialized
+    );
+}
+
+#[test]
+fn serde_hardhat_set_logging_options() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetLoggingOptions(
+        LoggingOptions {
+            verbosity: Some(LogVerbosity::Summary),
+            method_verbosity: Some(HashMap::from([(
+                "eth_call".to_owned(),
+                LogVerbosity::Silent,
+            )])),
+            output_file: Some(PathBuf::from("edr.log")),
+        },
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetLoggingOptions(
+        LoggingOptions::default(),
+    ));
+
+    let json = r#"{"jsonrpc":"2.0","method":"hardhat_setLoggingOptions","params":[{"methodVerbosity":{"eth_sendTransaction":"full"}}],"id":1}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(
+        MethodInvocation::SetLoggingOptions(LoggingOptions {
+            method_verbosity: Some(HashMap::from([(
+                "eth_sendTransaction".to_owned(),
+                LogVerbosity::Full,
+            )])),
+            ..LoggingOptions::default()
+        }),
+        deserialized
+    );
+}
+
+#[test]
+fn serde_hardhat_read_storage_variable() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ReadStorageVariable(
+        Address::random(),
+        String::from("balances"),
+        vec![Address::random().to_string()],
+        Some(BlockSpec::latest()),
+    ));
+}
+
+#[test]
+fn serde_hardhat_reorg() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Reorg(
+        2,
+        Some(ReorgOptions {
+            new_blocks: Some(U64::from(3)),
+            drop_transactions: Some(vec![B256::from(U256::from(1))]),
+        }),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::Reorg(1, None));
+
+    let json = r#"{"jsonrpc":"2.0","method":"hardhat_reorg","params":["0x2"],"id":2}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(MethodInvocation::Reorg(2, None), deserialized);
+}
+
+#[test]
+fn serde_hardhat_replay_block() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ReplayBlock(U64::from(1)));
+}
+
+#[test]
+fn serde_hardhat_replay_transaction() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ReplayTransaction(
+        B256::from(U256::from(1)),
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_fee_checks() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetFeeChecks(
+        FeeChecksConfig {
+            disable_balance_check: Some(true),
+            disable_base_fee_check: Some(false),
+            disable_priority_fee_check: None,
+        },
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetFeeChecks(
+        FeeChecksConfig::default(),
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_mempool_config() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetMempoolConfig(
+        MempoolConfig {
+            price_bump_percentage: Some(U64::from(25)),
+            max_transactions: Some(U64::from(1000)),
+            max_transactions_per_sender: None,
+            transaction_lifetime: Some(U64::from(3600)),
+            queue_future_transactions: Some(true),
+        },
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetMempoolConfig(
+        MempoolConfig::default(),
+    ));
+}
+
 #[test]
 fn serde_hardhat_set_min_gas_price() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetMinGasPrice(U128::from(
@@ -83,6 +499,38 @@ fn serde_hardhat_set_next_block_base_fee_per_gas() {
     ));
 }
 
+#[test]
+fn serde_hardhat_set_next_block_header_overrides() {
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::SetNextBlockHeaderOverrides(NextBlockHeaderOverrides {
+            coinbase: Some(Address::random()),
+            extra_data: Some(Bytes::from_static(b"extra data")),
+            gas_limit: Some(U64::from(30_000_000)),
+            mix_hash: Some(B256::random()),
+        }),
+    );
+    help_test_method_invocation_serde(
+        MethodInvocation::<L1ChainSpec>::SetNextBlockHeaderOverrides(
+            NextBlockHeaderOverrides::default(),
+        ),
+    );
+}
+
+#[test]
+fn serde_hardhat_set_next_block_withdrawals() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetNextBlockWithdrawals(
+        vec![Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address::from(U160::from(1)),
+            amount: 32_000_000_000,
+        }],
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetNextBlockWithdrawals(
+        Vec::new(),
+    ));
+}
+
 #[test]
 fn serde_hardhat_set_nonce() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetNonce(
@@ -98,6 +546,20 @@ fn serde_hardhat_set_prev_randao() {
     ));
 }
 
+#[test]
+fn serde_hardhat_set_prev_randao_seed() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetPrevRandaoSeed(
+        String::from("seed"),
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_state_root() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetStateRoot(B256::from(
+        U256::from(1),
+    )));
+}
+
 #[test]
 fn serde_hardhat_set_storage_at() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetStorageAt(
@@ -107,6 +569,98 @@ fn serde_hardhat_set_storage_at() {
     ));
 }
 
+#[test]
+fn serde_hardhat_set_storage_layout() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetStorageLayout(
+        Address::random(),
+        StorageLayout {
+            storage: vec![StorageLayoutEntry {
+                label: String::from("balances"),
+                offset: 0,
+                slot: String::from("0"),
+                type_id: String::from("t_mapping(t_address,t_uint256)"),
+            }],
+            types: [
+                (
+                    String::from("t_address"),
+                    StorageLayoutType {
+                        encoding: StorageEncoding::Inplace,
+                        label: String::from("address"),
+                        number_of_bytes: String::from("20"),
+                        key: None,
+                        value: None,
+                        base: None,
+                        members: None,
+                    },
+                ),
+                (
+                    String::from("t_mapping(t_address,t_uint256)"),
+                    StorageLayoutType {
+                        encoding: StorageEncoding::Mapping,
+                        label: String::from("mapping(address => uint256)"),
+                        number_of_bytes: String::from("32"),
+                        key: Some(String::from("t_address")),
+                        value: Some(String::from("t_uint256")),
+                        base: None,
+                        members: None,
+                    },
+                ),
+                (
+                    String::from("t_uint256"),
+                    StorageLayoutType {
+                        encoding: StorageEncoding::Inplace,
+                        label: String::from("uint256"),
+                        number_of_bytes: String::from("32"),
+                        key: None,
+                        value: None,
+                        base: None,
+                        members: None,
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        },
+    ));
+}
+
+#[test]
+fn serde_hardhat_set_storage_variable() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetStorageVariable(
+        Address::random(),
+        String::from("balances"),
+        serde_json::json!("1000"),
+        vec![Address::random().to_string()],
+    ));
+
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SetStorageVariable(
+        Address::random(),
+        String::from("paused"),
+        serde_json::json!(true),
+        Vec::new(),
+    ));
+}
+
+#[test]
+fn serde_hardhat_sign_authorization() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SignAuthorization(
+        Address::random(),
+        AuthorizationRequest {
+            chain_id: Some(U256::from(1)),
+            address: Address::random(),
+            nonce: Some(U64::from(1)),
+        },
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::SignAuthorization(
+        Address::random(),
+        AuthorizationRequest {
+            chain_id: None,
+            address: Address::random(),
+            nonce: None,
+        },
+    ));
+}
+
 #[test]
 fn serde_hardhat_stop_impersonating_account() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::StopImpersonatingAccount(
@@ -114,6 +668,79 @@ fn serde_hardhat_stop_impersonating_account() {
     ));
 }
 
+#[test]
+fn serde_hardhat_token_balances() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TokenBalances(
+        Address::from(U160::from(1)),
+        Some(BlockSpec::Number(2)),
+    ));
+}
+
+#[test]
+fn serde_hardhat_token_transfers() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TokenTransfers(Some(
+        TokenTransferFilter {
+            token: Some(Address::from(U160::from(1))),
+            standard: Some(TokenStandard::Erc721),
+            address: Some(Address::from(U160::from(2))),
+            from_block: Some(BlockSpec::Number(3)),
+            to_block: Some(BlockSpec::latest()),
+        },
+    )));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::TokenTransfers(None));
+}
+
+#[test]
+fn serde_hardhat_update_fork() {
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::UpdateFork(Some(
+        U64::from(1),
+    )));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::UpdateFork(None));
+
+    let json = r#"{"jsonrpc":"2.0","method":"hardhat_updateFork","params":[],"id":1}"#;
+    let deserialized: MethodInvocation<L1ChainSpec> = serde_json::from_str(json)
+        .unwrap_or_else(|_| panic!("should have successfully deserialized json {json}"));
+    assert_eq!(MethodInvocation::UpdateFork(None), deserialized);
+}
+
+#[test]
+fn serde_hardhat_validate_user_operation() {
+    let user_operation = UserOperation {
+        sender: Address::random(),
+        nonce: U256::from(1),
+        factory: None,
+        factory_data: None,
+        call_data: Bytes::from(&b"call"[..]),
+        call_gas_limit: U128::from(100_000),
+        verification_gas_limit: U128::from(200_000),
+        pre_verification_gas: U256::from(50_000),
+        max_fee_per_gas: U128::from(2_000_000_000),
+        max_priority_fee_per_gas: U128::from(1_000_000_000),
+        paymaster: None,
+        paymaster_verification_gas_limit: None,
+        paymaster_post_op_gas_limit: None,
+        paymaster_data: None,
+        signature: Bytes::from(&b"signature"[..]),
+    };
+
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ValidateUserOperation(
+        user_operation.clone(),
+        Address::random(),
+    ));
+    help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::ValidateUserOperation(
+        UserOperation {
+            factory: Some(Address::random()),
+            factory_data: Some(Bytes::from(&b"factory"[..])),
+            paymaster: Some(Address::random()),
+            paymaster_verification_gas_limit: Some(U128::from(30_000)),
+            paymaster_post_op_gas_limit: Some(U128::from(10_000)),
+            paymaster_data: Some(Bytes::from(&b"paymaster"[..])),
+            ..user_operation
+        },
+        Address::random(),
+    ));
+}
+
 #[test]
 fn serde_hardhat_dump_state() {
     help_test_method_invocation_serde(MethodInvocation::<L1ChainSpec>::DumpState(()));
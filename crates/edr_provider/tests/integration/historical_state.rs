@@ -0,0 +1,64 @@
+#![cfg(feature = "test-utils")]
+
+use std::num::NonZeroUsize;
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider,
+};
+
+const NUM_BLOCKS: u64 = 10;
+
+fn create_caching_provider(
+    max_cached_states: usize,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.max_cached_states = NonZeroUsize::new(max_cached_states);
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn balance_at(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    block_number: u64,
+) -> anyhow::Result<U256> {
+    request(
+        provider,
+        MethodInvocation::GetBalance(address, Some(BlockSpec::Number(block_number))),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn balances_at_evicted_blocks_are_reconstructed() -> anyhow::Result<()> {
+    let (provider, sender) = create_caching_provider(2)?;
+    let recipient = Address::random();
+
+    // Every block transfers one wei to the recipient
+    for _ in 0..NUM_BLOCKS {
+        request::<serde_json::Value, _, _>(
+            &provider,
+            MethodInvocation::SendTransaction(TransactionRequest {
+                from: sender,
+                to: Some(recipient),
+                value: Some(U256::from(1)),
+                ..TransactionRequest::default()
+            }),
+        )?;
+    }
+
+    // Querying in descending order recomputes evicted states from scratch, whereas
+    // ascending order derives them from the preceding block's cached state.
+    for block_number in (0..=NUM_BLOCKS).rev().chain(0..=NUM_BLOCKS) {
+        let balance = balance_at(&provider, recipient, block_number)?;
+        assert_eq!(balance, U256::from(block_number));
+    }
+
+    Ok(())
+}
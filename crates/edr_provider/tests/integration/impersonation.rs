@@ -0,0 +1,132 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_mem_pool::MemPoolAddTransactionError;
+use edr_primitives::{bytes, Address, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    from: Address,
+) -> Result<(), edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            ..TransactionRequest::default()
+        }),
+    ))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auto_impersonate_and_auto_fund() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.auto_fund = true;
+    config.auto_impersonate = true;
+
+    let provider = create_provider(config)?;
+
+    // The sender is neither an owned account nor funded
+    send_transaction(&provider, Address::random())?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn auto_impersonate_account() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let sender = Address::random();
+    assert!(matches!(
+        send_transaction(&provider, sender),
+        Err(ProviderError::UnknownAddress { .. })
+    ));
+
+    request::<bool, _, _>(&provider, MethodInvocation::AutoImpersonateAccount(true))?;
+
+    // Without auto-funding, the impersonated sender needs a balance
+    assert!(matches!(
+        send_transaction(&provider, sender),
+        Err(ProviderError::MemPoolAddTransaction(
+            MemPoolAddTransactionError::InsufficientFunds { .. }
+        ))
+    ));
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetBalance(sender, U256::from(10).pow(U256::from(18))),
+    )?;
+    send_transaction(&provider, sender)?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::AutoImpersonateAccount(false))?;
+
+    assert!(matches!(
+        send_transaction(&provider, sender),
+        Err(ProviderError::UnknownAddress { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn impersonate_contract_account() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.auto_fund = true;
+
+    let provider = create_provider(config)?;
+
+    let contract = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(
+            contract,
+            // PUSH1 0x00 PUSH1 0x00 RETURN
+            bytes!("0x60006000f3"),
+        ),
+    )?;
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::ImpersonateAccount(contract.into()),
+    )?;
+
+    send_transaction(&provider, contract)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejected_transaction_reverts_auto_funding() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.auto_fund = true;
+    config.auto_impersonate = true;
+
+    let provider = create_provider(config)?;
+
+    let sender = Address::random();
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            // Transactions with a future nonce can't be auto-mined
+            nonce: Some(1),
+            ..TransactionRequest::default()
+        }),
+    ));
+    assert!(
+        matches!(result, Err(ProviderError::AutoMineNonceTooHigh { .. })),
+        "{result:?}"
+    );
+
+    let balance: U256 = request(&provider, MethodInvocation::GetBalance(sender, None))?;
+    assert_eq!(balance, U256::ZERO);
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    hardhat_rpc_types::{ImportedTransaction, TransactionImportSource},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider,
+};
+
+fn create_automining_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+    let sender = owned_account(&config, 0);
+
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn create_non_automining_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let (provider, sender) = create_automining_provider()?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    Ok((provider, sender))
+}
+
+fn sign_transfer(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    nonce: u64,
+) -> anyhow::Result<Bytes> {
+    request(
+        provider,
+        MethodInvocation::SignTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            nonce: Some(nonce),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn import_transactions(
+    provider: &Provider<L1ChainSpec>,
+    source: TransactionImportSource,
+) -> anyhow::Result<Vec<ImportedTransaction>> {
+    request(provider, MethodInvocation::ImportTransactions(source))
+}
+
+fn block_number(
+    provider: &Provider<L1ChainSpec>,
+    transaction_hash: B256,
+) -> anyhow::Result<Option<U256>> {
+    let transaction: serde_json::Value = request(
+        provider,
+        MethodInvocation::GetTransactionByHash(transaction_hash),
+    )?;
+
+    Ok(serde_json::from_value(transaction["blockNumber"].clone())?)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_transactions_without_automining() -> anyhow::Result<()> {
+    let (provider, sender) = create_non_automining_provider()?;
+
+    let first = sign_transfer(&provider, sender, 0)?;
+    let second = sign_transfer(&provider, sender, 1)?;
+
+    let results = import_transactions(
+        &provider,
+        TransactionImportSource::Transactions(vec![first, Bytes::from_static(b"invalid"), second]),
+    )?;
+    assert_eq!(results.len(), 3);
+
+    assert_eq!(results[0].index, 0);
+    let first_hash = results[0].transaction_hash.expect("is valid");
+    assert_eq!(results[0].error, None);
+
+    assert_eq!(results[1].index, 1);
+    assert_eq!(results[1].transaction_hash, None);
+    assert!(results[1].error.is_some());
+
+    assert_eq!(results[2].index, 2);
+    let second_hash = results[2].transaction_hash.expect("is valid");
+    assert_eq!(results[2].error, None);
+
+    assert_eq!(block_number(&provider, first_hash)?, None);
+    assert_eq!(block_number(&provider, second_hash)?, None);
+
+    // Importing the same transaction again fails, but reports its hash
+    let results = import_transactions(
+        &provider,
+        TransactionImportSource::Transactions(vec![sign_transfer(&provider, sender, 0)?]),
+    )?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].transaction_hash, Some(first_hash));
+    assert!(results[0].error.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_transactions_with_automining() -> anyhow::Result<()> {
+    let (provider, sender) = create_automining_provider()?;
+
+    let first = sign_transfer(&provider, sender, 0)?;
+    let second = sign_transfer(&provider, sender, 1)?;
+
+    let results = import_transactions(
+        &provider,
+        TransactionImportSource::Transactions(vec![first, second]),
+    )?;
+    assert_eq!(results.len(), 2);
+
+    // Imported transactions are added to the mem pool without being mined
+    for result in &results {
+        assert_eq!(result.error, None);
+
+        let transaction_hash = result.transaction_hash.expect("is valid");
+        assert_eq!(block_number(&provider, transaction_hash)?, None);
+    }
+
+    // Automining is still enabled afterwards, so the next transaction mines all
+    // pending transactions
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendRawTransaction(sign_transfer(&provider, sender, 2)?),
+    )?;
+
+    for result in results {
+        let transaction_hash = result.transaction_hash.expect("is valid");
+        assert_eq!(
+            block_number(&provider, transaction_hash)?,
+            Some(U256::from(1))
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_transactions_from_file() -> anyhow::Result<()> {
+    let (provider, sender) = create_non_automining_provider()?;
+
+    let first = sign_transfer(&provider, sender, 0)?;
+    let second = sign_transfer(&provider, sender, 1)?;
+
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("transactions.txt");
+    std::fs::write(&path, format!("{first}\n\n  {second}  \n"))?;
+
+    let results = import_transactions(&provider, TransactionImportSource::File(path))?;
+    assert_eq!(results.len(), 2);
+
+    for (index, result) in results.into_iter().enumerate() {
+        assert_eq!(result.index, index);
+        assert_eq!(result.error, None);
+
+        let transaction_hash = result.transaction_hash.expect("is valid");
+        assert_eq!(block_number(&provider, transaction_hash)?, None);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_transactions_from_invalid_file() -> anyhow::Result<()> {
+    let (provider, _sender) = create_non_automining_provider()?;
+
+    let directory = tempfile::tempdir()?;
+
+    let missing_path = directory.path().join("missing.txt");
+    assert!(import_transactions(&provider, TransactionImportSource::File(missing_path)).is_err());
+
+    let invalid_path = directory.path().join("invalid.txt");
+    std::fs::write(&invalid_path, "not hex\n")?;
+    assert!(import_transactions(&provider, TransactionImportSource::File(invalid_path)).is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_transaction_that_fails_validation() -> anyhow::Result<()> {
+    let (provider, sender) = create_non_automining_provider()?;
+
+    // The transaction can be decoded, but is signed for a different chain
+    let other_chain_provider = {
+        let mut config = create_test_config();
+        config.chain_id += 1;
+
+        create_provider(config)?
+    };
+    let raw_transaction = sign_transfer(&other_chain_provider, sender, 0)?;
+
+    let results = import_transactions(
+        &provider,
+        TransactionImportSource::Transactions(vec![raw_transaction]),
+    )?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].index, 0);
+    assert!(results[0].error.is_some());
+
+    let transaction_hash = results[0]
+        .transaction_hash
+        .expect("the hash of a decodable transaction is reported");
+
+    let transaction: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionByHash(transaction_hash),
+    )?;
+    assert!(transaction.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, B256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{InclusionAction, InclusionPolicy, InclusionRule},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderRequest,
+};
+
+fn create_inclusion_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn set_rule(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    action: InclusionAction,
+) -> anyhow::Result<()> {
+    request::<bool, _, _>(
+        provider,
+        MethodInvocation::SetInclusionPolicy(InclusionPolicy {
+            rules: vec![InclusionRule {
+                transaction_hashes: None,
+                senders: Some(vec![sender]),
+                probability: None,
+                action,
+            }],
+            ..InclusionPolicy::default()
+        }),
+    )?;
+
+    Ok(())
+}
+
+fn send_transaction(provider: &Provider<L1ChainSpec>, sender: Address) -> anyhow::Result<B256> {
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn mine_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<()> {
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::EvmMine(
+        None,
+    )))?;
+
+    Ok(())
+}
+
+fn receipt_block_number(
+    provider: &Provider<L1ChainSpec>,
+    transaction_hash: B256,
+) -> anyhow::Result<Option<U64>> {
+    let receipt: Option<serde_json::Value> = request(
+        provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+
+    receipt
+        .map(|receipt| Ok(serde_json::from_value(receipt["blockNumber"].clone())?))
+        .transpose()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delayed_transaction_is_mined_after_delay() -> anyhow::Result<()> {
+    let (provider, sender) = create_inclusion_provider()?;
+
+    set_rule(
+        &provider,
+        sender,
+        InclusionAction::Delay {
+            blocks: U64::from(2),
+        },
+    )?;
+
+    // The delayed transaction isn't automined
+    let transaction_hash = send_transaction(&provider, sender)?;
+    assert_eq!(receipt_block_number(&provider, transaction_hash)?, None);
+
+    let transaction: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionByHash(transaction_hash),
+    )?;
+    assert!(transaction.is_some());
+
+    mine_block(&provider)?;
+    assert_eq!(receipt_block_number(&provider, transaction_hash)?, None);
+
+    // Once the delay expires, the transaction is automined right after the mined
+    // block
+    mine_block(&provider)?;
+    assert_eq!(
+        receipt_block_number(&provider, transaction_hash)?,
+        Some(U64::from(3))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transaction_behind_delayed_transaction_is_not_automined() -> anyhow::Result<()> {
+    let (provider, sender) = create_inclusion_provider()?;
+
+    set_rule(
+        &provider,
+        sender,
+        InclusionAction::Delay {
+            blocks: U64::from(2),
+        },
+    )?;
+    let delayed_hash = send_transaction(&provider, sender)?;
+
+    // Later transactions of the same sender aren't affected by the rule, but can't
+    // be mined before the delayed transaction
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetInclusionPolicy(InclusionPolicy::default()),
+    )?;
+    let queued_hash = send_transaction(&provider, sender)?;
+    assert_eq!(receipt_block_number(&provider, queued_hash)?, None);
+
+    let block_number: U64 = request(&provider, MethodInvocation::BlockNumber(()))?;
+    assert_eq!(block_number, U64::ZERO);
+
+    mine_block(&provider)?;
+    mine_block(&provider)?;
+    assert_eq!(
+        receipt_block_number(&provider, delayed_hash)?,
+        Some(U64::from(3))
+    );
+    assert_eq!(
+        receipt_block_number(&provider, queued_hash)?,
+        Some(U64::from(3))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dropped_transaction_is_never_mined() -> anyhow::Result<()> {
+    let (provider, sender) = create_inclusion_provider()?;
+
+    set_rule(&provider, sender, InclusionAction::Drop)?;
+
+    let transaction_hash = send_transaction(&provider, sender)?;
+    mine_block(&provider)?;
+
+    let transaction: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionByHash(transaction_hash),
+    )?;
+    assert!(transaction.is_none());
+    assert_eq!(receipt_block_number(&provider, transaction_hash)?, None);
+
+    // Clearing the policy allows the retried transaction to be mined
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetInclusionPolicy(InclusionPolicy::default()),
+    )?;
+
+    let transaction_hash = send_transaction(&provider, sender)?;
+    assert!(receipt_block_number(&provider, transaction_hash)?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_probability_is_rejected() -> anyhow::Result<()> {
+    let (provider, _sender) = create_inclusion_provider()?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SetInclusionPolicy(InclusionPolicy {
+            rules: vec![InclusionRule {
+                transaction_hashes: None,
+                senders: None,
+                probability: Some(1.5),
+                action: InclusionAction::Drop,
+            }],
+            ..InclusionPolicy::default()
+        }),
+    ));
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dropped_transaction_does_not_replace_pending_transaction() -> anyhow::Result<()> {
+    let (provider, sender) = create_inclusion_provider()?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let pending_hash = send_transaction(&provider, sender)?;
+
+    set_rule(&provider, sender, InclusionAction::Drop)?;
+
+    // A valid replacement, were it not dropped
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            nonce: Some(0),
+            max_fee_per_gas: Some(100_000_000_000),
+            max_priority_fee_per_gas: Some(100_000_000_000),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let transaction: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionByHash(pending_hash),
+    )?;
+    assert!(transaction.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "test-utils")]
+
+use std::time::Duration;
+
+use edr_chain_l1::L1ChainSpec;
+use edr_primitives::U64;
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    IntervalConfigRequest, MethodInvocation, Provider,
+};
+use tokio::time::{sleep, timeout};
+
+const INTERVAL_MILLIS: u64 = 50;
+/// How long to wait for a block to be mined, before failing the test.
+const MINING_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn block_number(provider: &Provider<L1ChainSpec>) -> anyhow::Result<u64> {
+    let block_number: U64 = request(provider, MethodInvocation::BlockNumber(()))?;
+    Ok(block_number.to())
+}
+
+/// Waits until a block is mined after `block_number`.
+async fn wait_for_block_after(
+    provider: &Provider<L1ChainSpec>,
+    block_number: u64,
+) -> anyhow::Result<u64> {
+    timeout(MINING_TIMEOUT, async {
+        loop {
+            let current = self::block_number(provider)?;
+            if current > block_number {
+                return Ok(current);
+            }
+
+            sleep(Duration::from_millis(INTERVAL_MILLIS)).await;
+        }
+    })
+    .await?
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pause_and_resume_interval_mining() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::EvmSetIntervalMining(IntervalConfigRequest::FixedOrDisabled(
+            INTERVAL_MILLIS,
+        )),
+    )?;
+    wait_for_block_after(&provider, 0).await?;
+
+    let paused: bool = request(&provider, MethodInvocation::PauseIntervalMining(()))?;
+    assert!(paused);
+
+    // Allow a block that was being mined while pausing to be committed
+    sleep(Duration::from_millis(2 * INTERVAL_MILLIS)).await;
+    let paused_block_number = block_number(&provider)?;
+
+    sleep(Duration::from_millis(10 * INTERVAL_MILLIS)).await;
+    assert_eq!(block_number(&provider)?, paused_block_number);
+
+    let resumed: bool = request(&provider, MethodInvocation::ResumeIntervalMining(()))?;
+    assert!(resumed);
+
+    wait_for_block_after(&provider, paused_block_number).await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pause_without_interval_mining() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let paused: bool = request(&provider, MethodInvocation::PauseIntervalMining(()))?;
+    assert!(!paused);
+
+    let resumed: bool = request(&provider, MethodInvocation::ResumeIntervalMining(()))?;
+    assert!(!resumed);
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use edr_provider::{
     test_utils::create_test_config_with_fork, time::CurrentTime, ForkConfig, MethodInvocation,
     NoopLogger, Provider, ProviderRequest,
 };
+use edr_rpc_eth::client::TransportConfig;
 use edr_solidity::contract_decoder::ContractDecoder;
 use edr_test_utils::env::get_alchemy_url;
 use tokio::runtime;
@@ -29,7 +30,10 @@ async fn issue_324() -> anyhow::Result<()> {
         cache_dir: edr_defaults::CACHE_DIR.into(),
         chain_overrides: HashMap::default(),
         http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
         url: get_alchemy_url().replace("mainnet", "sepolia"),
+        verified_sources: None,
     }));
     config.hardfork = edr_chain_l1::Hardfork::CANCUN;
 
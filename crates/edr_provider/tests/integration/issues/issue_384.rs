@@ -6,6 +6,7 @@ use edr_provider::{
     test_utils::create_test_config_with_fork, time::CurrentTime, ForkConfig, MethodInvocation,
     NoopLogger, Provider, ProviderRequest,
 };
+use edr_rpc_eth::client::TransportConfig;
 use edr_solidity::contract_decoder::ContractDecoder;
 use edr_test_utils::env::get_infura_url;
 use tokio::runtime;
@@ -22,7 +23,10 @@ async fn avalanche_chain_mine_local_block() -> anyhow::Result<()> {
         cache_dir: edr_defaults::CACHE_DIR.into(),
         chain_overrides: HashMap::default(),
         http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
         url: get_infura_url().replace("mainnet", "avalanche-mainnet"),
+        verified_sources: None,
     }));
 
     let provider = Provider::new(
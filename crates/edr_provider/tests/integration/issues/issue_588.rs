@@ -9,6 +9,7 @@ use edr_primitives::HashMap;
 use edr_provider::{
     test_utils::create_test_config_with_fork, time::MockTime, ForkConfig, NoopLogger, Provider,
 };
+use edr_rpc_eth::client::TransportConfig;
 use edr_solidity::contract_decoder::ContractDecoder;
 use edr_test_utils::env::get_alchemy_url;
 use tokio::runtime;
@@ -23,7 +24,10 @@ async fn issue_588() -> anyhow::Result<()> {
         cache_dir: edr_defaults::CACHE_DIR.into(),
         chain_overrides: HashMap::default(),
         http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
         url: get_alchemy_url(),
+        verified_sources: None,
     }));
 
     let current_time_is_1970 = Arc::new(MockTime::with_seconds(0));
@@ -0,0 +1,100 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::L1ChainSpec;
+use edr_eth::{
+    filter::{LogFilterOptions, OneOrMore},
+    BlockSpec,
+};
+use edr_primitives::{Address, B256, U64};
+use edr_provider::{test_utils::request, MethodInvocation, Provider};
+
+use crate::common::log::{create_emitting_provider, emit_log};
+
+/// The number of blocks that is mined between logs, such that the logs end up
+/// in different bloom sections.
+const BLOCKS_BETWEEN_LOGS: u64 = 2_048;
+
+fn mine_blocks(provider: &Provider<L1ChainSpec>, count: u64) -> anyhow::Result<()> {
+    request::<bool, _, _>(provider, MethodInvocation::Mine(Some(count), None))?;
+
+    Ok(())
+}
+
+/// Returns the block numbers of all logs that match the provided address and
+/// topic.
+fn log_block_numbers(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    topic: B256,
+) -> anyhow::Result<Vec<u64>> {
+    let logs: Vec<serde_json::Value> = request(
+        provider,
+        MethodInvocation::GetLogs(LogFilterOptions {
+            from_block: Some(BlockSpec::Number(0)),
+            to_block: Some(BlockSpec::latest()),
+            address: Some(OneOrMore::One(address)),
+            topics: Some(vec![Some(OneOrMore::One(topic))]),
+            ..LogFilterOptions::default()
+        }),
+    )?;
+
+    logs.into_iter()
+        .map(|log| {
+            let block_number: U64 = serde_json::from_value(log["blockNumber"].clone())?;
+            Ok(block_number.to())
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn finds_logs_across_bloom_sections() -> anyhow::Result<()> {
+    let (provider, sender, emitter) = create_emitting_provider()?;
+    let first_topic = B256::random();
+    let second_topic = B256::random();
+
+    // Block 1
+    emit_log(&provider, sender, emitter, first_topic)?;
+    mine_blocks(&provider, BLOCKS_BETWEEN_LOGS)?;
+    // Block 2 + BLOCKS_BETWEEN_LOGS
+    emit_log(&provider, sender, emitter, second_topic)?;
+
+    assert_eq!(log_block_numbers(&provider, emitter, first_topic)?, vec![1]);
+    assert_eq!(
+        log_block_numbers(&provider, emitter, second_topic)?,
+        vec![2 + BLOCKS_BETWEEN_LOGS]
+    );
+
+    // Neither the topic nor the address is in any of the blooms
+    assert!(log_block_numbers(&provider, emitter, B256::random())?.is_empty());
+    assert!(log_block_numbers(&provider, Address::random(), first_topic)?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverted_logs_are_removed_from_blooms() -> anyhow::Result<()> {
+    let (provider, sender, emitter) = create_emitting_provider()?;
+    let first_topic = B256::random();
+    let reverted_topic = B256::random();
+    let replacement_topic = B256::random();
+
+    emit_log(&provider, sender, emitter, first_topic)?;
+    mine_blocks(&provider, BLOCKS_BETWEEN_LOGS)?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    emit_log(&provider, sender, emitter, reverted_topic)?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    emit_log(&provider, sender, emitter, replacement_topic)?;
+
+    assert!(log_block_numbers(&provider, emitter, reverted_topic)?.is_empty());
+    assert_eq!(
+        log_block_numbers(&provider, emitter, replacement_topic)?,
+        vec![2 + BLOCKS_BETWEEN_LOGS]
+    );
+    assert_eq!(log_block_numbers(&provider, emitter, first_topic)?, vec![1]);
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "test-utils")]
+
+use edr_provider::{
+    log_filter::LogFilterError,
+    test_utils::{create_provider, create_test_config},
+    MethodInvocation, ProviderError, ProviderRequest,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_log_filter_without_handler() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SetLogFilter("edr_provider=debug".to_owned()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::LogFilter(LogFilterError::Unsupported))
+    ));
+
+    Ok(())
+}
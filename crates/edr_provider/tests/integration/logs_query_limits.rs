@@ -0,0 +1,118 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::{
+    filter::{LogFilterOptions, OneOrMore},
+    BlockSpec,
+};
+use edr_primitives::{bytes, Address, Bytes, B256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+// PUSH1 0x00 PUSH1 0x00 LOG0 STOP
+const EMIT_LOG_INIT_CODE: Bytes = bytes!("0x60006000a000");
+
+fn get_logs(
+    provider: &Provider<L1ChainSpec>,
+    from_block: u64,
+    to_block: u64,
+) -> Result<usize, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetLogs(LogFilterOptions {
+            from_block: Some(BlockSpec::Number(from_block)),
+            to_block: Some(BlockSpec::Number(to_block)),
+            ..LogFilterOptions::default()
+        }),
+    ))?;
+
+    let logs: Vec<serde_json::Value> =
+        serde_json::from_value(response.result).expect("Failed to deserialize logs");
+
+    Ok(logs.len())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_range_exceeded() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.max_logs_block_range = Some(2);
+
+    let provider = create_provider(config)?;
+    for _ in 0..3 {
+        request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    }
+
+    assert_eq!(get_logs(&provider, 1, 2)?, 0);
+
+    let result = get_logs(&provider, 0, 2);
+    assert!(matches!(
+        result,
+        Err(ProviderError::LogsBlockRangeExceeded {
+            from_block: 0,
+            to_block: 2,
+            max_block_range: 2,
+        })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn results_exceeded_returns_continuation_range() -> anyhow::Result<()> {
+    let mut config = create_test_config();
+    config.max_logs_results = Some(2);
+
+    let from = owned_account(&config, 0);
+
+    let provider = create_provider(config)?;
+
+    // Each transaction is auto-mined into its own block, emitting a single log
+    for _ in 0..3 {
+        request::<B256, _, _>(
+            &provider,
+            MethodInvocation::SendTransaction(TransactionRequest {
+                from,
+                data: Some(EMIT_LOG_INIT_CODE),
+                ..TransactionRequest::default()
+            }),
+        )?;
+    }
+
+    assert_eq!(get_logs(&provider, 1, 2)?, 2);
+
+    let result = get_logs(&provider, 1, 3);
+    let Err(error) = result else {
+        panic!("Expected the query to exceed the maximum number of results");
+    };
+
+    assert!(matches!(
+        error,
+        ProviderError::LogsResultsExceeded {
+            from_block: 1,
+            to_block: 2,
+            max_results: 2,
+        }
+    ));
+
+    let error = edr_rpc_eth::jsonrpc::Error::from(error);
+    assert_eq!(error.code, -32005);
+    assert_eq!(
+        error.data,
+        Some(serde_json::json!({ "fromBlock": "0x1", "toBlock": "0x2" }))
+    );
+
+    // Logs that don't match the filter don't count towards the limit
+    let logs: Vec<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetLogs(LogFilterOptions {
+            from_block: Some(BlockSpec::Number(1)),
+            to_block: Some(BlockSpec::Number(3)),
+            address: Some(OneOrMore::One(Address::random())),
+            ..LogFilterOptions::default()
+        }),
+    )?;
+    assert!(logs.is_empty());
+
+    Ok(())
+}
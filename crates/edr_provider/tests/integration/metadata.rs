@@ -0,0 +1,62 @@
+#![cfg(feature = "test-utils")]
+
+use std::time::{Duration, SystemTime};
+
+use edr_provider::{
+    hardhat_rpc_types::{Metadata, NodeInfo},
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, ProviderConfig,
+};
+
+const GENESIS_TIMESTAMP: u64 = 1_700_000_000;
+
+fn create_genesis_timestamp_config() -> ProviderConfig<edr_chain_l1::Hardfork> {
+    let mut config = create_test_config();
+    config.initial_date = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(GENESIS_TIMESTAMP));
+
+    config
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metadata_includes_instance_identity() -> anyhow::Result<()> {
+    let provider = create_provider(create_genesis_timestamp_config())?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(Some(2), None))?;
+
+    let metadata: Metadata = request(&provider, MethodInvocation::Metadata(()))?;
+
+    let config = create_test_config::<edr_chain_l1::Hardfork>();
+    assert_eq!(metadata.chain_id, config.chain_id);
+    assert_eq!(metadata.hardfork, <&'static str>::from(config.hardfork));
+    assert_eq!(metadata.genesis_timestamp, GENESIS_TIMESTAMP);
+    assert_eq!(metadata.latest_block_number, 2);
+    assert!(metadata.forked_network.is_none());
+
+    // Another instance has a different id
+    let other_provider = create_provider(create_genesis_timestamp_config())?;
+    let other_metadata: Metadata = request(&other_provider, MethodInvocation::Metadata(()))?;
+    assert_ne!(metadata.instance_id, other_metadata.instance_id);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn node_info() -> anyhow::Result<()> {
+    let provider = create_provider(create_genesis_timestamp_config())?;
+
+    let node_info: NodeInfo = request(&provider, MethodInvocation::AnvilNodeInfo(()))?;
+
+    let config = create_test_config::<edr_chain_l1::Hardfork>();
+    assert_eq!(node_info.current_block_number, 0);
+    assert_eq!(node_info.current_block_timestamp, GENESIS_TIMESTAMP);
+    assert_eq!(node_info.hard_fork, <&'static str>::from(config.hardfork));
+    assert_eq!(node_info.environment.chain_id, config.chain_id);
+    assert_eq!(
+        node_info.environment.gas_limit,
+        config.block_gas_limit.get()
+    );
+    assert!(node_info.fork_config.fork_url.is_none());
+    assert!(node_info.fork_config.fork_block_number.is_none());
+
+    Ok(())
+}
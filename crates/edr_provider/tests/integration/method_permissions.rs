@@ -0,0 +1,156 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::L1ChainSpec;
+use edr_primitives::{Bytes, HashSet, B256};
+use edr_provider::{
+    test_utils::{create_test_config, owned_account},
+    BatchRequest, MethodInvocation, MethodPermissionsConfig, Provider, ProviderError,
+    ProviderRequest, StructLogStream,
+};
+use edr_rpc_eth::jsonrpc;
+use serde_json::json;
+
+fn create_provider(
+    method_permissions: MethodPermissionsConfig,
+) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.method_permissions = method_permissions;
+
+    edr_provider::test_utils::create_provider(config)
+}
+
+fn is_not_permitted(
+    provider: &Provider<L1ChainSpec>,
+    request: MethodInvocation<L1ChainSpec>,
+) -> bool {
+    let expected_method = request.method_name();
+
+    let result = provider.handle_request(ProviderRequest::with_single(request));
+    matches!(result, Err(ProviderError::MethodNotPermitted { method }) if method == expected_method)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_rejects_state_mutating_methods() -> anyhow::Result<()> {
+    let provider = create_provider(MethodPermissionsConfig {
+        read_only: true,
+        ..MethodPermissionsConfig::default()
+    })?;
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::BlockNumber(
+        (),
+    )))?;
+
+    assert!(is_not_permitted(&provider, MethodInvocation::EvmMine(None)));
+    assert!(is_not_permitted(
+        &provider,
+        MethodInvocation::SetLoggingEnabled(true)
+    ));
+
+    let error = provider
+        .handle_request(ProviderRequest::with_single(MethodInvocation::EvmSnapshot(
+            (),
+        )))
+        .err()
+        .map(edr_rpc_eth::jsonrpc::Error::from);
+
+    assert_eq!(error.map(|error| error.code), Some(-32004));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn denied_methods_take_precedence_over_allowed_methods() -> anyhow::Result<()> {
+    let provider = create_provider(MethodPermissionsConfig {
+        allowed_methods: Some(HashSet::from_iter([
+            "eth_blockNumber".to_owned(),
+            "eth_chainId".to_owned(),
+        ])),
+        denied_methods: HashSet::from_iter(["eth_chainId".to_owned()]),
+        read_only: false,
+    })?;
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::BlockNumber(
+        (),
+    )))?;
+
+    assert!(is_not_permitted(&provider, MethodInvocation::ChainId(())));
+    assert!(is_not_permitted(
+        &provider,
+        MethodInvocation::NetVersion(())
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batch_rejects_only_denied_requests() -> anyhow::Result<()> {
+    let provider = create_provider(MethodPermissionsConfig {
+        denied_methods: HashSet::from_iter(["evm_mine".to_owned()]),
+        ..MethodPermissionsConfig::default()
+    })?;
+
+    let response = provider.handle_request(ProviderRequest::Batch(vec![
+        BatchRequest::new(jsonrpc::Id::Num(1), MethodInvocation::EvmMine(None)),
+        BatchRequest::new(jsonrpc::Id::Num(2), MethodInvocation::BlockNumber(())),
+    ]))?;
+
+    assert_eq!(
+        response.result,
+        json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {
+                    "code": -32004,
+                    "message": "Method evm_mine is not permitted by the provider's configuration",
+                    "data": null,
+                }
+            },
+            { "jsonrpc": "2.0", "id": 2, "result": "0x0" },
+        ])
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_rejects_signing_methods() -> anyhow::Result<()> {
+    let provider = create_provider(MethodPermissionsConfig {
+        read_only: true,
+        ..MethodPermissionsConfig::default()
+    })?;
+
+    let account = owned_account(&create_test_config(), 0);
+
+    assert!(is_not_permitted(
+        &provider,
+        MethodInvocation::EthSign(account, Bytes::from_static(b"message"))
+    ));
+    assert!(is_not_permitted(
+        &provider,
+        MethodInvocation::PersonalSign(Bytes::from_static(b"message"), account)
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn streamed_debug_trace_transaction_is_not_permitted_if_denied() -> anyhow::Result<()> {
+    let provider = create_provider(MethodPermissionsConfig {
+        denied_methods: HashSet::from_iter(["debug_traceTransaction".to_owned()]),
+        ..MethodPermissionsConfig::default()
+    })?;
+
+    let result = provider.handle_streamed_debug_trace_transaction(
+        B256::ZERO,
+        None,
+        StructLogStream::new(1, |_struct_logs| {}),
+    );
+
+    assert!(matches!(
+        result,
+        Err(ProviderError::MethodNotPermitted { method }) if method == "debug_traceTransaction"
+    ));
+
+    Ok(())
+}
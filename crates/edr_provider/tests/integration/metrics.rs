@@ -0,0 +1,39 @@
+#![cfg(feature = "test-utils")]
+
+use edr_primitives::U64;
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metrics_record_requests_and_mined_blocks() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    request::<U64, _, _>(&provider, MethodInvocation::BlockNumber(()))?;
+    request::<bool, _, _>(&provider, MethodInvocation::Mine(Some(2), None))?;
+    // A block gas limit of zero is invalid
+    let result = request::<bool, _, _>(&provider, MethodInvocation::EvmSetBlockGasLimit(U64::ZERO));
+    assert!(result.is_err());
+
+    let metrics = provider.metrics();
+
+    let block_number = &metrics.methods["eth_blockNumber"];
+    assert_eq!(block_number.requests, 1);
+    assert_eq!(block_number.errors, 0);
+
+    let set_block_gas_limit = &metrics.methods["evm_setBlockGasLimit"];
+    assert_eq!(set_block_gas_limit.requests, 1);
+    assert_eq!(set_block_gas_limit.errors, 1);
+
+    assert_eq!(metrics.blocks_mined, 2);
+    assert_eq!(metrics.transactions_mined, 0);
+    assert_eq!(metrics.state.block_number, 2);
+    assert_eq!(metrics.fork_cache_hits, None);
+
+    let prometheus = metrics.to_prometheus();
+    assert!(prometheus.contains("edr_rpc_requests_total{method=\"hardhat_mine\"} 1\n"));
+    assert!(prometheus.contains("edr_block_number 2\n"));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, B256, U128};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider,
+};
+
+const GWEI: u128 = 1_000_000_000;
+const BASE_FEE: u128 = 10 * GWEI;
+const MIN_GAS_PRICE: u128 = GWEI;
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    max_fee_per_gas: u128,
+) -> anyhow::Result<B256> {
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            max_fee_per_gas: Some(max_fee_per_gas),
+            max_priority_fee_per_gas: Some(2 * MIN_GAS_PRICE),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn min_gas_price_applies_to_effective_priority_fee() -> anyhow::Result<()> {
+    let config = create_test_config();
+    let first_sender = owned_account(&config, 0);
+    let second_sender = owned_account(&config, 1);
+    let provider = create_provider(config)?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetMinGasPrice(U128::from(MIN_GAS_PRICE)),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetNextBlockBaseFeePerGas(U128::from(BASE_FEE)),
+    )?;
+
+    // The effective priority fee is capped by the max fee at half the minimum
+    let underpaying_hash = send_transaction(&provider, first_sender, BASE_FEE + MIN_GAS_PRICE / 2)?;
+    let paying_hash = send_transaction(&provider, second_sender, BASE_FEE + 2 * MIN_GAS_PRICE)?;
+
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    let block: L1RpcBlock<B256> = request(
+        &provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )?;
+    assert_eq!(block.transactions, vec![paying_hash]);
+
+    let pending_transactions: Vec<serde_json::Value> =
+        request(&provider, MethodInvocation::PendingTransactions(()))?;
+    assert_eq!(pending_transactions.len(), 1);
+    assert_eq!(
+        pending_transactions[0]["hash"],
+        serde_json::to_value(underpaying_hash)?
+    );
+
+    Ok(())
+}
@@ -1,14 +1,66 @@
+mod batch_request;
+mod block_gas_limit_schedule;
+mod bundle_simulation;
+mod chain_export;
+mod checkpoint;
 mod coverage;
+mod custom_errors;
+mod custom_precompiles;
+mod deterministic_deployment;
 mod disable_balance_check;
+mod disable_base_fee_adjustment;
+mod dropped_transactions;
 mod eip2537;
+mod eip2935;
+mod eip4788;
 mod eip4844;
+mod eip712;
 mod eip7623;
 mod eip7691;
 mod eip7702;
 mod eip7825;
+mod engine_api;
+mod erc4337;
+mod eth_get_block_receipts;
 mod eth_max_priority_fee_per_gas;
 mod eth_request_serialization;
+mod explorer;
+mod fee_checks;
+mod fill_transaction;
+mod fixture_cache;
+mod gas_profile;
+mod gas_sponsor;
+mod geth_genesis;
+mod hardfork_activations;
+mod hardhat_flood;
 mod hardhat_request_serialization;
+mod historical_state;
+mod impersonation;
+mod import_transactions;
+mod inclusion_policy;
+mod interval_mining;
 mod issues;
+mod log_blooms;
+mod log_filter;
+mod logs_query_limits;
+mod metadata;
+mod method_permissions;
+mod metrics;
+mod min_gas_price;
+mod next_block_header_overrides;
+mod otterscan;
+mod pending_block;
+mod personal;
+mod polling_filters;
+mod prev_randao_seed;
+mod replay_transaction;
 mod rip7212;
+mod state_diff;
+mod state_pruning;
+mod storage_variable;
 mod timestamp;
+mod token_index;
+mod trace;
+mod transaction_firewall;
+mod unlimited_contract_size;
+mod withdrawals;
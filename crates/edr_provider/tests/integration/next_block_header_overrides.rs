@@ -0,0 +1,111 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U64};
+use edr_provider::{
+    hardhat_rpc_types::NextBlockHeaderOverrides,
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+fn set_overrides(
+    provider: &Provider<L1ChainSpec>,
+    overrides: NextBlockHeaderOverrides,
+) -> anyhow::Result<()> {
+    let result: bool = request(
+        provider,
+        MethodInvocation::SetNextBlockHeaderOverrides(overrides),
+    )?;
+    assert!(result);
+
+    Ok(())
+}
+
+fn mine_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request::<bool, _, _>(provider, MethodInvocation::Mine(None, None))?;
+
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn overrides_apply_to_next_block_only() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let coinbase = Address::random();
+    let extra_data = Bytes::from_static(b"overridden");
+    let mix_hash = B256::random();
+
+    set_overrides(
+        &provider,
+        NextBlockHeaderOverrides {
+            coinbase: Some(coinbase),
+            extra_data: Some(extra_data.clone()),
+            gas_limit: Some(U64::from(12_345_678)),
+            mix_hash: Some(mix_hash),
+        },
+    )?;
+
+    let block = mine_block(&provider)?;
+    assert_eq!(block.miner, Some(coinbase));
+    assert_eq!(block.extra_data, extra_data);
+    assert_eq!(block.gas_limit, 12_345_678);
+    assert_eq!(block.mix_hash, Some(mix_hash));
+
+    let block = mine_block(&provider)?;
+    assert_ne!(block.miner, Some(coinbase));
+    assert_ne!(block.extra_data, extra_data);
+    assert_ne!(block.gas_limit, 12_345_678);
+    assert_ne!(block.mix_hash, Some(mix_hash));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn omitted_fields_use_regular_values() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let expected = mine_block(&provider)?;
+
+    set_overrides(
+        &provider,
+        NextBlockHeaderOverrides {
+            extra_data: Some(Bytes::from_static(b"overridden")),
+            ..NextBlockHeaderOverrides::default()
+        },
+    )?;
+
+    let block = mine_block(&provider)?;
+    assert_eq!(block.miner, expected.miner);
+    assert_eq!(block.gas_limit, expected.gas_limit);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_overrides_are_rejected() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    assert!(set_overrides(
+        &provider,
+        NextBlockHeaderOverrides {
+            extra_data: Some(Bytes::from(vec![0u8; 33])),
+            ..NextBlockHeaderOverrides::default()
+        },
+    )
+    .is_err());
+
+    assert!(set_overrides(
+        &provider,
+        NextBlockHeaderOverrides {
+            gas_limit: Some(U64::ZERO),
+            ..NextBlockHeaderOverrides::default()
+        },
+    )
+    .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    BlockDetails, MethodInvocation, Provider, ProviderError, ProviderRequest, TraceEntry,
+    TraceEntryType, TransactionSearchResult, MAX_SEARCH_PAGE_SIZE,
+};
+
+fn create_provider_and_sender() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.index_address_transactions = true;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<B256> {
+    request(provider, MethodInvocation::SendTransaction(transaction))
+}
+
+type SearchResult = TransactionSearchResult<serde_json::Value, serde_json::Value>;
+
+fn transaction_hashes(result: &SearchResult) -> Vec<B256> {
+    result
+        .txs
+        .iter()
+        .map(|transaction| {
+            serde_json::from_value(transaction["hash"].clone()).expect("valid transaction hash")
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+    let first_receiver = Address::random();
+    let second_receiver = Address::random();
+
+    // Each transaction is mined in its own block
+    let mut hashes = Vec::new();
+    for receiver in [first_receiver, second_receiver, first_receiver] {
+        hashes.push(send_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                to: Some(receiver),
+                value: Some(U256::from(1)),
+                ..TransactionRequest::default()
+            },
+        )?);
+    }
+
+    let result: SearchResult = request(
+        &provider,
+        MethodInvocation::OtsSearchTransactionsBefore(first_receiver, 0, 10),
+    )?;
+    assert_eq!(transaction_hashes(&result), vec![hashes[2], hashes[0]]);
+    assert_eq!(result.receipts.len(), 2);
+    assert!(result.first_page);
+    assert!(result.last_page);
+
+    let result: SearchResult = request(
+        &provider,
+        MethodInvocation::OtsSearchTransactionsBefore(first_receiver, 0, 1),
+    )?;
+    assert_eq!(transaction_hashes(&result), vec![hashes[2]]);
+    assert!(result.first_page);
+    assert!(!result.last_page);
+
+    let result: SearchResult = request(
+        &provider,
+        MethodInvocation::OtsSearchTransactionsAfter(first_receiver, 0, 1),
+    )?;
+    assert_eq!(transaction_hashes(&result), vec![hashes[0]]);
+    assert!(!result.first_page);
+    assert!(result.last_page);
+
+    let result: SearchResult = request(
+        &provider,
+        MethodInvocation::OtsSearchTransactionsAfter(sender, 1, 10),
+    )?;
+    assert_eq!(transaction_hashes(&result), vec![hashes[2], hashes[1]]);
+    assert!(result.first_page);
+    assert!(!result.last_page);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_transactions_matches_internal_contract_creations() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    // PUSH1 0x00 (x4) CREATE2 STOP
+    let factory_init_code =
+        Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf5, 0x00]);
+
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(factory_init_code),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let factory = sender.create(0);
+    let created = factory.create2_from_code(B256::ZERO, Bytes::new());
+
+    let result: SearchResult = request(
+        &provider,
+        MethodInvocation::OtsSearchTransactionsBefore(created, 0, 10),
+    )?;
+    assert_eq!(transaction_hashes(&result), vec![transaction_hash]);
+    assert!(result.first_page);
+    assert!(result.last_page);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_transactions_rejects_large_pages() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::OtsSearchTransactionsBefore(sender, 0, MAX_SEARCH_PAGE_SIZE + 1),
+    ));
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn search_transactions_requires_address_index() -> anyhow::Result<()> {
+    let config = create_test_config();
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::OtsSearchTransactionsAfter(sender, 0, 10),
+    ));
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_block_details() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let details: Option<BlockDetails<serde_json::Value>> =
+        request(&provider, MethodInvocation::OtsGetBlockDetails(1))?;
+    let details = details.expect("block should exist");
+
+    assert_eq!(details.block.transaction_count, 1);
+    assert_eq!(details.block.block["transactions"], serde_json::json!([]));
+    assert!(details.total_fees > U256::ZERO);
+    assert_eq!(details.issuance.issuance, U256::ZERO);
+
+    let details: Option<BlockDetails<serde_json::Value>> =
+        request(&provider, MethodInvocation::OtsGetBlockDetails(2))?;
+    assert!(details.is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn transaction_error_and_trace() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    // PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 REVERT
+    let reverting_init_code =
+        Bytes::from_static(&[0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd]);
+
+    let reverted_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(reverting_init_code.clone()),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let revert_data = Bytes::copy_from_slice(&U256::from(0x2a).to_be_bytes::<32>());
+
+    let error: Option<Bytes> = request(
+        &provider,
+        MethodInvocation::OtsGetTransactionError(reverted_hash),
+    )?;
+    assert_eq!(error, Some(revert_data.clone()));
+
+    let entries: Option<Vec<TraceEntry>> = request(
+        &provider,
+        MethodInvocation::OtsTraceTransaction(reverted_hash),
+    )?;
+    assert_eq!(
+        entries,
+        Some(vec![TraceEntry {
+            entry_type: TraceEntryType::Create,
+            depth: 0,
+            from: sender,
+            to: Address::ZERO,
+            value: Some(U256::ZERO),
+            input: reverting_init_code,
+            output: Some(revert_data),
+        }])
+    );
+
+    let successful_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(Address::random()),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let error: Option<Bytes> = request(
+        &provider,
+        MethodInvocation::OtsGetTransactionError(successful_hash),
+    )?;
+    assert_eq!(error, Some(Bytes::new()));
+
+    let error: Option<Bytes> = request(
+        &provider,
+        MethodInvocation::OtsGetTransactionError(B256::random()),
+    )?;
+    assert_eq!(error, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_distinguishes_create2() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    // PUSH1 0x00 (x4) CREATE2 STOP
+    let factory_init_code =
+        Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf5, 0x00]);
+
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(factory_init_code.clone()),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let factory = sender.create(0);
+    let entries: Option<Vec<TraceEntry>> = request(
+        &provider,
+        MethodInvocation::OtsTraceTransaction(transaction_hash),
+    )?;
+    assert_eq!(
+        entries,
+        Some(vec![
+            TraceEntry {
+                entry_type: TraceEntryType::Create,
+                depth: 0,
+                from: sender,
+                to: factory,
+                value: Some(U256::ZERO),
+                input: factory_init_code,
+                output: Some(Bytes::new()),
+            },
+            TraceEntry {
+                entry_type: TraceEntryType::Create2,
+                depth: 1,
+                from: factory,
+                to: factory.create2_from_code(B256::ZERO, Bytes::new()),
+                value: Some(U256::ZERO),
+                input: Bytes::new(),
+                output: Some(Bytes::new()),
+            },
+        ])
+    );
+
+    Ok(())
+}
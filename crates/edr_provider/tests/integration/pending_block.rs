@@ -0,0 +1,207 @@
+#![cfg(feature = "test-utils")]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use edr_chain_l1::{
+    rpc::{block::L1RpcBlock, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::{BlockSpec, PreEip1898BlockSpec};
+use edr_primitives::{Address, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider_with_timer, create_test_config, owned_account, request},
+    time::MockTime,
+    MethodInvocation, MiningConfig, PendingTransactionSummary, Provider,
+};
+
+struct PendingBlockFixture {
+    provider: Provider<L1ChainSpec, Arc<MockTime>>,
+    mock_timer: Arc<MockTime>,
+    sender: Address,
+}
+
+impl PendingBlockFixture {
+    fn new() -> anyhow::Result<Self> {
+        let mut config = create_test_config();
+        config.mining = MiningConfig {
+            auto_mine: false,
+            ..MiningConfig::default()
+        };
+
+        let sender = owned_account(&config, 0);
+        let mock_timer = Arc::new(MockTime::now());
+        let provider = create_provider_with_timer(config, mock_timer.clone())?;
+
+        Ok(Self {
+            provider,
+            mock_timer,
+            sender,
+        })
+    }
+
+    fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: MethodInvocation<L1ChainSpec>,
+    ) -> anyhow::Result<T> {
+        request(&self.provider, method)
+    }
+
+    fn send_transaction(&self, to: Address, value: u64) -> anyhow::Result<B256> {
+        self.request(MethodInvocation::SendTransaction(TransactionRequest {
+            from: self.sender,
+            to: Some(to),
+            value: Some(U256::from(value)),
+            ..TransactionRequest::default()
+        }))
+    }
+
+    fn pending_block(&self) -> anyhow::Result<L1RpcBlock<B256>> {
+        self.request(MethodInvocation::GetBlockByNumber(
+            PreEip1898BlockSpec::pending(),
+            false,
+        ))
+    }
+
+    /// Counts how often the pending block is mined from the mem pool, by
+    /// observing the mine ordering callback.
+    fn count_mined_pending_blocks(&self) -> Arc<AtomicUsize> {
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let callback_count = count.clone();
+        self.provider.set_mine_ordering_callback(Some(Arc::new(
+            move |transactions: &[PendingTransactionSummary]| {
+                callback_count.fetch_add(1, Ordering::SeqCst);
+
+                Ok(transactions
+                    .iter()
+                    .map(|transaction| transaction.transaction_hash)
+                    .collect())
+            },
+        )));
+
+        count
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pending_block_reflects_mem_pool() -> anyhow::Result<()> {
+    let fixture = PendingBlockFixture::new()?;
+    let recipient = Address::random();
+
+    let empty_pending_block = fixture.pending_block()?;
+    assert!(empty_pending_block.transactions.is_empty());
+
+    let first_transaction = fixture.send_transaction(recipient, 100)?;
+
+    let pending_block = fixture.pending_block()?;
+    assert_eq!(pending_block.transactions, vec![first_transaction]);
+    assert_ne!(pending_block.state_root, empty_pending_block.state_root);
+
+    let pending_balance: U256 = fixture.request(MethodInvocation::GetBalance(
+        recipient,
+        Some(BlockSpec::pending()),
+    ))?;
+    assert_eq!(pending_balance, U256::from(100));
+
+    let latest_balance: U256 = fixture.request(MethodInvocation::GetBalance(
+        recipient,
+        Some(BlockSpec::latest()),
+    ))?;
+    assert_eq!(latest_balance, U256::ZERO);
+
+    let second_transaction = fixture.send_transaction(recipient, 200)?;
+
+    let pending_block = fixture.pending_block()?;
+    assert_eq!(
+        pending_block.transactions,
+        vec![first_transaction, second_transaction]
+    );
+
+    let pending_balance: U256 = fixture.request(MethodInvocation::GetBalance(
+        recipient,
+        Some(BlockSpec::pending()),
+    ))?;
+    assert_eq!(pending_balance, U256::from(300));
+
+    fixture.request::<String>(MethodInvocation::EvmMine(None))?;
+
+    let pending_block = fixture.pending_block()?;
+    assert!(pending_block.transactions.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pending_block_follows_block_timestamp() -> anyhow::Result<()> {
+    let fixture = PendingBlockFixture::new()?;
+    fixture.send_transaction(Address::random(), 100)?;
+
+    let pending_block = fixture.pending_block()?;
+    assert_eq!(fixture.pending_block()?, pending_block);
+
+    fixture.mock_timer.add_seconds(10);
+
+    let next_pending_block = fixture.pending_block()?;
+    assert_eq!(next_pending_block.timestamp, pending_block.timestamp + 10);
+    assert_eq!(next_pending_block.transactions, pending_block.transactions);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pending_block_is_mined_once_until_invalidated() -> anyhow::Result<()> {
+    let fixture = PendingBlockFixture::new()?;
+    let recipient = Address::random();
+    let mined_pending_blocks = fixture.count_mined_pending_blocks();
+
+    fixture.send_transaction(recipient, 100)?;
+    let mined_before_requests = mined_pending_blocks.load(Ordering::SeqCst);
+
+    // Different requests for the pending block share the cached block
+    let pending_block = fixture.pending_block()?;
+    assert_eq!(fixture.pending_block()?, pending_block);
+    let pending_balance: U256 = fixture.request(MethodInvocation::GetBalance(
+        recipient,
+        Some(BlockSpec::pending()),
+    ))?;
+    assert_eq!(pending_balance, U256::from(100));
+    assert_eq!(
+        mined_pending_blocks.load(Ordering::SeqCst),
+        mined_before_requests + 1
+    );
+
+    // Changing the mem pool invalidates the cached block
+    fixture.send_transaction(recipient, 200)?;
+    let mined_before_requests = mined_pending_blocks.load(Ordering::SeqCst);
+
+    let pending_block = fixture.pending_block()?;
+    assert_eq!(pending_block.transactions.len(), 2);
+    assert_eq!(fixture.pending_block()?, pending_block);
+    assert_eq!(
+        mined_pending_blocks.load(Ordering::SeqCst),
+        mined_before_requests + 1
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn setting_mine_ordering_callback_invalidates_pending_block() -> anyhow::Result<()> {
+    let fixture = PendingBlockFixture::new()?;
+    fixture.send_transaction(Address::random(), 100)?;
+
+    // The pending block is cached before the callback is set
+    let pending_block = fixture.pending_block()?;
+
+    let mined_pending_blocks = fixture.count_mined_pending_blocks();
+    assert_eq!(
+        fixture.pending_block()?.transactions,
+        pending_block.transactions
+    );
+    assert_eq!(mined_pending_blocks.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    KeystoreError, MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+use edr_signer::public_key_to_address;
+
+const PASSPHRASE: &str = "passphrase";
+
+fn sign(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+) -> Result<serde_json::Value, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::EthSign(address, Bytes::from_static(b"message")),
+    ))?;
+
+    Ok(response.result)
+}
+
+fn sign_transaction(
+    provider: &Provider<L1ChainSpec>,
+    from: Address,
+) -> Result<Bytes, edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    let response = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SignTransaction(TransactionRequest {
+            from,
+            to: Some(Address::random()),
+            value: Some(U256::from(1)),
+            ..TransactionRequest::default()
+        }),
+    ))?;
+
+    Ok(serde_json::from_value(response.result).expect("result should be bytes"))
+}
+
+fn unlock_account(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    passphrase: &str,
+) -> Result<(), edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+    provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::PersonalUnlockAccount(address, passphrase.to_owned(), None),
+    ))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn new_account_requires_unlocking() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let address: Address = request(
+        &provider,
+        MethodInvocation::PersonalNewAccount(PASSPHRASE.to_owned()),
+    )?;
+
+    let accounts: Vec<Address> = request(&provider, MethodInvocation::Accounts(()))?;
+    assert_eq!(accounts.last(), Some(&address));
+
+    assert!(matches!(
+        sign(&provider, address),
+        Err(ProviderError::UnknownAddress { .. })
+    ));
+
+    assert!(matches!(
+        unlock_account(&provider, address, "wrong passphrase"),
+        Err(ProviderError::Keystore(
+            KeystoreError::InvalidPassphrase { .. }
+        ))
+    ));
+
+    unlock_account(&provider, address, PASSPHRASE)?;
+    sign(&provider, address)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_raw_key_and_sign_transaction() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let secret_key = B256::random();
+    let expected_address =
+        public_key_to_address(k256::SecretKey::from_slice(secret_key.as_slice())?.public_key());
+
+    let address: Address = request(
+        &provider,
+        MethodInvocation::PersonalImportRawKey(secret_key, PASSPHRASE.to_owned()),
+    )?;
+    assert_eq!(address, expected_address);
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::PersonalImportRawKey(secret_key, PASSPHRASE.to_owned()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::Keystore(
+            KeystoreError::AccountAlreadyExists { .. }
+        ))
+    ));
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetBalance(address, U256::from(10).pow(U256::from(18))),
+    )?;
+
+    assert!(matches!(
+        sign_transaction(&provider, address),
+        Err(ProviderError::UnknownAddress { .. })
+    ));
+
+    unlock_account(&provider, address, PASSPHRASE)?;
+    let raw_transaction = sign_transaction(&provider, address)?;
+
+    // Signing doesn't send the transaction
+    let nonce: U256 = request(
+        &provider,
+        MethodInvocation::GetTransactionCount(address, None),
+    )?;
+    assert_eq!(nonce, U256::ZERO);
+
+    request::<B256, _, _>(
+        &provider,
+        MethodInvocation::SendRawTransaction(raw_transaction),
+    )?;
+
+    let nonce: U256 = request(
+        &provider,
+        MethodInvocation::GetTransactionCount(address, None),
+    )?;
+    assert_eq!(nonce, U256::from(1));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sign_transaction_rejects_impersonated_accounts() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let impersonated = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::ImpersonateAccount(impersonated.into()),
+    )?;
+
+    assert!(matches!(
+        sign_transaction(&provider, impersonated),
+        Err(ProviderError::UnknownAddress { .. })
+    ));
+
+    Ok(())
+}
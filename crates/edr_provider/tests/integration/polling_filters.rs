@@ -0,0 +1,108 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::filter::{LogFilterOptions, LogOutput, OneOrMore};
+use edr_primitives::{B256, U256, U64};
+use edr_provider::{test_utils::request, MethodInvocation, Provider};
+
+use crate::common::log::{create_emitting_provider, emit_log};
+
+/// Returns the topic and `removed` flag of every log that changed since the
+/// filter was last polled.
+fn log_changes(
+    provider: &Provider<L1ChainSpec>,
+    filter_id: U256,
+) -> anyhow::Result<Vec<(B256, bool)>> {
+    let logs: Vec<LogOutput> = request(provider, MethodInvocation::GetFilterChanges(filter_id))?;
+
+    Ok(logs
+        .into_iter()
+        .map(|log| (log.topics[0], log.removed))
+        .collect())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn log_filter_reports_removed_logs_after_revert() -> anyhow::Result<()> {
+    let (provider, sender, emitter) = create_emitting_provider()?;
+    let kept_topic = B256::random();
+    let delivered_topic = B256::random();
+    let undelivered_topic = B256::random();
+    let replacement_topic = B256::random();
+
+    let filter_id: U256 = request(
+        &provider,
+        MethodInvocation::NewFilter(LogFilterOptions {
+            address: Some(OneOrMore::One(emitter)),
+            ..LogFilterOptions::default()
+        }),
+    )?;
+
+    emit_log(&provider, sender, emitter, kept_topic)?;
+    assert_eq!(
+        log_changes(&provider, filter_id)?,
+        vec![(kept_topic, false)]
+    );
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+
+    emit_log(&provider, sender, emitter, delivered_topic)?;
+    assert_eq!(
+        log_changes(&provider, filter_id)?,
+        vec![(delivered_topic, false)]
+    );
+
+    emit_log(&provider, sender, emitter, undelivered_topic)?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    // Only the delivered log is reported as removed, while the undelivered log
+    // is discarded
+    assert_eq!(
+        log_changes(&provider, filter_id)?,
+        vec![(delivered_topic, true)]
+    );
+
+    emit_log(&provider, sender, emitter, replacement_topic)?;
+    assert_eq!(
+        log_changes(&provider, filter_id)?,
+        vec![(replacement_topic, false)]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pending_transaction_filter_survives_revert() -> anyhow::Result<()> {
+    let (provider, sender, emitter) = create_emitting_provider()?;
+
+    let filter_id: U256 = request(&provider, MethodInvocation::NewPendingTransactionFilter(()))?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    emit_log(&provider, sender, emitter, B256::random())?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    // Consume the transaction that was sent before reverting
+    request::<Vec<B256>, _, _>(&provider, MethodInvocation::GetFilterChanges(filter_id))?;
+
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+    let transaction_hash: B256 = request(
+        &provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(emitter),
+            data: Some(B256::random().to_vec().into()),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let changes: Vec<B256> = request(&provider, MethodInvocation::GetFilterChanges(filter_id))?;
+    assert_eq!(changes, vec![transaction_hash]);
+
+    let changes: Vec<B256> = request(&provider, MethodInvocation::GetFilterChanges(filter_id))?;
+    assert!(changes.is_empty());
+
+    Ok(())
+}
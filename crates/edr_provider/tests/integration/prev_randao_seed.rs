@@ -0,0 +1,65 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{keccak256, B256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider,
+};
+
+fn create_seeded_provider(prev_randao_seed: Option<&str>) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.prev_randao_seed = prev_randao_seed.map(str::to_owned);
+
+    create_provider(config)
+}
+
+/// Mines the provided number of blocks and returns their prevrandao values.
+fn mine_prev_randaos(provider: &Provider<L1ChainSpec>, count: u64) -> anyhow::Result<Vec<B256>> {
+    let mut prev_randaos = Vec::new();
+    for _ in 0..count {
+        request::<bool, _, _>(provider, MethodInvocation::Mine(None, None))?;
+
+        let block: L1RpcBlock<B256> = request(
+            provider,
+            MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+        )?;
+        prev_randaos.push(block.mix_hash.expect("post-merge block has a prevrandao"));
+    }
+
+    Ok(prev_randaos)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn seed_determines_prev_randao_sequence() -> anyhow::Result<()> {
+    let first = mine_prev_randaos(&create_seeded_provider(Some("seed"))?, 3)?;
+    let second = mine_prev_randaos(&create_seeded_provider(Some("seed"))?, 3)?;
+    assert_eq!(first, second);
+
+    let other = mine_prev_randaos(&create_seeded_provider(Some("other seed"))?, 3)?;
+    assert_ne!(first, other);
+
+    let default = mine_prev_randaos(&create_seeded_provider(None)?, 3)?;
+    assert_ne!(first, default);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_prev_randao_seed_restarts_sequence() -> anyhow::Result<()> {
+    let provider = create_seeded_provider(None)?;
+    mine_prev_randaos(&provider, 2)?;
+
+    let result: bool = request(
+        &provider,
+        MethodInvocation::SetPrevRandaoSeed(String::from("seed")),
+    )?;
+    assert!(result);
+
+    let prev_randaos = mine_prev_randaos(&provider, 2)?;
+    assert_eq!(prev_randaos[0], keccak256("seed"));
+    assert_eq!(prev_randaos[1], keccak256(prev_randaos[0]));
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+#![cfg(all(feature = "test-remote", feature = "test-utils"))]
+
+use edr_chain_l1::{rpc::receipt::L1RpcTransactionReceipt, L1ChainSpec};
+use edr_primitives::{b256, Address, HashMap, B256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{ReplayedTransaction, ValueChange},
+    test_utils::{create_provider, create_test_config_with_fork, request},
+    ForkConfig, MethodInvocation, Provider,
+};
+use edr_rpc_eth::client::TransportConfig;
+use edr_test_utils::env::get_alchemy_url;
+
+const FORK_BLOCK_NUMBER: u64 = 20_384_300;
+
+/// A mainnet transaction that was mined before `FORK_BLOCK_NUMBER`.
+const REMOTE_TRANSACTION_HASH: B256 =
+    b256!("0x0537316f37627655b7fe5e50e23f71cd835b377d1cde4226443c94723d036e32");
+
+fn create_forked_provider() -> anyhow::Result<Provider<L1ChainSpec>> {
+    let config = create_test_config_with_fork(Some(ForkConfig {
+        block_number: Some(FORK_BLOCK_NUMBER),
+        cache_dir: edr_defaults::CACHE_DIR.into(),
+        chain_overrides: HashMap::default(),
+        http_headers: None,
+        live: false,
+        transport: TransportConfig::default(),
+        url: get_alchemy_url(),
+        verified_sources: None,
+    }));
+
+    create_provider(config)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn replays_remote_transaction() -> anyhow::Result<()> {
+    let provider = create_forked_provider()?;
+
+    let replayed: ReplayedTransaction = request(
+        &provider,
+        MethodInvocation::ReplayTransaction(REMOTE_TRANSACTION_HASH),
+    )?;
+
+    let receipt: Option<L1RpcTransactionReceipt> = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(REMOTE_TRANSACTION_HASH),
+    )?;
+    let receipt = receipt.expect("remote transaction should have a receipt");
+    assert!(receipt.block_number <= FORK_BLOCK_NUMBER);
+
+    // The replay reproduces the outcome of the original execution
+    assert_eq!(Some(replayed.receipt.status), receipt.status);
+    assert_eq!(replayed.receipt.gas_used, receipt.gas_used);
+    assert_eq!(replayed.receipt.logs.len(), receipt.logs.len());
+    assert_eq!(replayed.receipt.contract_address, receipt.contract_address);
+    assert_eq!(replayed.trace.pass, replayed.receipt.status);
+    assert_eq!(replayed.trace.gas_used, receipt.gas_used);
+
+    let transaction: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetTransactionByHash(REMOTE_TRANSACTION_HASH),
+    )?;
+    let transaction = transaction.expect("remote transaction should exist");
+    let sender: Address = serde_json::from_value(transaction["from"].clone())?;
+    let nonce: U64 = serde_json::from_value(transaction["nonce"].clone())?;
+    let nonce = nonce.to::<u64>();
+
+    // The replay executes on top of the remote state of the prior block
+    let sender_diff = replayed
+        .state_diff
+        .get(&sender)
+        .expect("sender should be modified");
+    assert_eq!(sender_diff.nonce, ValueChange::new(nonce, nonce + 1));
+
+    Ok(())
+}
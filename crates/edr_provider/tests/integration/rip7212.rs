@@ -3,7 +3,10 @@
 use std::sync::Arc;
 
 use edr_chain_l1::{rpc::call::L1CallRequest, L1ChainSpec};
-use edr_precompile::secp256r1::{self, P256VERIFY_BASE_GAS_FEE, P256VERIFY_BASE_GAS_FEE_OSAKA};
+use edr_precompile::{
+    secp256r1::{self, P256VERIFY_BASE_GAS_FEE, P256VERIFY_BASE_GAS_FEE_OSAKA},
+    CustomPrecompile,
+};
 use edr_primitives::{bytes, Bytes};
 use edr_provider::{
     test_utils::create_test_config, time::CurrentTime, MethodInvocation, NoopLogger, Provider,
@@ -57,7 +60,7 @@ async fn rip7212_enabled() -> anyhow::Result<()> {
     config.hardfork = edr_chain_l1::Hardfork::PRAGUE;
     config.precompile_overrides = [(
         *secp256r1::P256VERIFY.address(),
-        *secp256r1::P256VERIFY.precompile(),
+        CustomPrecompile::from(*secp256r1::P256VERIFY.precompile()),
     )]
     .into_iter()
     .collect();
@@ -0,0 +1,143 @@
+#![cfg(feature = "test-utils")]
+
+use std::collections::BTreeMap;
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{Address, B256, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{AccountDiff, ValueChange},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderError, ProviderErrorForChainSpec, ProviderRequest,
+};
+
+fn create_recording_provider(
+    record_state_diffs: bool,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.record_state_diffs = record_state_diffs;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn send_transfer(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    receiver: Address,
+) -> anyhow::Result<B256> {
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(receiver),
+            value: Some(U256::from(100)),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn get_state_diff(
+    provider: &Provider<L1ChainSpec>,
+    transaction_hash: B256,
+) -> Result<Option<BTreeMap<Address, AccountDiff>>, ProviderErrorForChainSpec<L1ChainSpec>> {
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::GetStateDiff(transaction_hash),
+    ))?;
+
+    Ok(serde_json::from_value(result.result).expect("valid state diff"))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn records_state_diff_of_mined_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_recording_provider(true)?;
+    let receiver = Address::random();
+
+    let first_hash = send_transfer(&provider, sender, receiver)?;
+    let second_hash = send_transfer(&provider, sender, receiver)?;
+
+    let state_diff = get_state_diff(&provider, second_hash)?.expect("diff should be recorded");
+
+    let receiver_diff = state_diff.get(&receiver).expect("receiver was modified");
+    assert_eq!(
+        receiver_diff.balance,
+        ValueChange::new(U256::from(100), U256::from(200))
+    );
+    assert_eq!(receiver_diff.nonce, None);
+    assert!(receiver_diff.storage.is_empty());
+
+    let sender_diff = state_diff.get(&sender).expect("sender was modified");
+    assert_eq!(sender_diff.nonce, ValueChange::new(1, 2));
+
+    let first_diff = get_state_diff(&provider, first_hash)?.expect("diff should be recorded");
+    assert_eq!(
+        first_diff
+            .get(&sender)
+            .and_then(|account_diff| account_diff.nonce.clone()),
+        ValueChange::new(0, 1)
+    );
+
+    assert_eq!(get_state_diff(&provider, B256::random())?, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn records_state_diffs_of_transactions_in_the_same_block() -> anyhow::Result<()> {
+    let (provider, sender) = create_recording_provider(true)?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let receiver = Address::random();
+    let first_hash = send_transfer(&provider, sender, receiver)?;
+    let second_hash = send_transfer(&provider, sender, receiver)?;
+
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+
+    // Each diff is relative to the state after the preceding transaction
+    let first_diff = get_state_diff(&provider, first_hash)?.expect("diff should be recorded");
+    assert_eq!(
+        first_diff
+            .get(&receiver)
+            .and_then(|account_diff| account_diff.balance.clone()),
+        ValueChange::new(U256::ZERO, U256::from(100))
+    );
+
+    let second_diff = get_state_diff(&provider, second_hash)?.expect("diff should be recorded");
+    assert_eq!(
+        second_diff
+            .get(&receiver)
+            .and_then(|account_diff| account_diff.balance.clone()),
+        ValueChange::new(U256::from(100), U256::from(200))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverting_to_snapshot_removes_state_diffs() -> anyhow::Result<()> {
+    let (provider, sender) = create_recording_provider(true)?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    let transaction_hash = send_transfer(&provider, sender, Address::random())?;
+    assert!(get_state_diff(&provider, transaction_hash)?.is_some());
+
+    let was_reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(was_reverted);
+
+    assert_eq!(get_state_diff(&provider, transaction_hash)?, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn state_diff_requires_recording() -> anyhow::Result<()> {
+    let (provider, sender) = create_recording_provider(false)?;
+
+    let transaction_hash = send_transfer(&provider, sender, Address::random())?;
+
+    let result = get_state_diff(&provider, transaction_hash);
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
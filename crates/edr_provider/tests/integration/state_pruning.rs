@@ -0,0 +1,266 @@
+#![cfg(feature = "test-utils")]
+
+use std::num::NonZeroU64;
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::BlockSpec;
+use edr_primitives::{b256, Address, Bytes, B256, U256};
+use edr_provider::{
+    hardhat_rpc_types::{TokenBalance, TokenTransfer},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    AddressTransactionsOptions, AddressTransactionsPage, MethodInvocation, Provider, ProviderError,
+    ProviderRequest, StatePruningConfig,
+};
+
+const NUM_BLOCKS: u64 = 10;
+const RETAINED_BLOCKS: u64 = 2;
+/// The maximum number of reconstructed states of pruned blocks that the
+/// provider caches.
+const MAX_RECONSTRUCTED_STATES: u64 = 4;
+
+const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+fn prune_config() -> StatePruningConfig {
+    StatePruningConfig::Prune {
+        retained_blocks: NonZeroU64::new(RETAINED_BLOCKS).expect("constant is non-zero"),
+    }
+}
+
+fn create_pruning_provider(
+    state_pruning: StatePruningConfig,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.state_pruning = state_pruning;
+    config.record_state_diffs = true;
+    config.index_address_transactions = true;
+    config.index_token_transfers = true;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<B256> {
+    request(provider, MethodInvocation::SendTransaction(transaction))
+}
+
+/// Mines `NUM_BLOCKS` blocks that each transfer one wei to the returned
+/// recipient, returning the hashes of the transfers as well.
+fn mine_transfers(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+) -> anyhow::Result<(Address, Vec<B256>)> {
+    let recipient = Address::random();
+
+    let transaction_hashes = (0..NUM_BLOCKS)
+        .map(|_| {
+            send_transaction(
+                provider,
+                TransactionRequest {
+                    from: sender,
+                    to: Some(recipient),
+                    value: Some(U256::from(1)),
+                    ..TransactionRequest::default()
+                },
+            )
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok((recipient, transaction_hashes))
+}
+
+/// Runtime code that emits an ERC-20 `Transfer` event with the `from`, `to`,
+/// and `value` words of the calldata.
+fn transfer_emitter_runtime_code() -> Bytes {
+    // PUSH1 0x40 CALLDATALOAD PUSH1 0x00 MSTORE PUSH1 0x20 CALLDATALOAD PUSH1 0x00
+    // CALLDATALOAD PUSH32 <topic>
+    let mut code = vec![
+        0x60, 0x40, 0x35, 0x60, 0x00, 0x52, 0x60, 0x20, 0x35, 0x60, 0x00, 0x35, 0x7f,
+    ];
+    code.extend_from_slice(TRANSFER_EVENT_TOPIC.as_slice());
+    // PUSH1 0x20 PUSH1 0x00 LOG3 STOP
+    code.extend_from_slice(&[0x60, 0x20, 0x60, 0x00, 0xa3, 0x00]);
+
+    code.into()
+}
+
+fn emit_transfer(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    token: Address,
+    from: Address,
+    to: Address,
+    value: u64,
+) -> anyhow::Result<B256> {
+    let mut data = Vec::new();
+    data.extend_from_slice(from.into_word().as_slice());
+    data.extend_from_slice(to.into_word().as_slice());
+    data.extend_from_slice(&U256::from(value).to_be_bytes::<32>());
+
+    send_transaction(
+        provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(token),
+            data: Some(data.into()),
+            ..TransactionRequest::default()
+        },
+    )
+}
+
+fn token_balance(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    block_spec: Option<BlockSpec>,
+) -> anyhow::Result<U256> {
+    let balances: Vec<TokenBalance> = request(
+        provider,
+        MethodInvocation::TokenBalances(address, block_spec),
+    )?;
+
+    Ok(balances
+        .first()
+        .map_or(U256::ZERO, |balance| balance.balance))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn archive_mode_retains_all_states() -> anyhow::Result<()> {
+    let (provider, sender) = create_pruning_provider(StatePruningConfig::Archive)?;
+    let (recipient, transaction_hashes) = mine_transfers(&provider, sender)?;
+
+    assert!(provider.metrics().state.cached_states > NUM_BLOCKS);
+
+    let state_diff: Option<serde_json::Value> = request(
+        &provider,
+        MethodInvocation::GetStateDiff(transaction_hashes[0]),
+    )?;
+    assert!(state_diff.is_some());
+
+    let page: AddressTransactionsPage = request(
+        &provider,
+        MethodInvocation::ExplorerGetAddressTransactions(recipient, None),
+    )?;
+    assert_eq!(page.total, NUM_BLOCKS);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pruned_states_are_reconstructed() -> anyhow::Result<()> {
+    let (provider, sender) = create_pruning_provider(prune_config())?;
+    let (recipient, _transaction_hashes) = mine_transfers(&provider, sender)?;
+
+    assert!(provider.metrics().state.cached_states <= RETAINED_BLOCKS);
+
+    for block_number in 0..=NUM_BLOCKS {
+        let balance: U256 = request(
+            &provider,
+            MethodInvocation::GetBalance(recipient, Some(BlockSpec::Number(block_number))),
+        )?;
+        assert_eq!(balance, U256::from(block_number));
+    }
+
+    // Only the most recently reconstructed states are cached
+    let cached_states = provider.metrics().state.cached_states;
+    assert!(cached_states > RETAINED_BLOCKS);
+    assert!(cached_states <= RETAINED_BLOCKS + MAX_RECONSTRUCTED_STATES);
+
+    let balance: U256 = request(
+        &provider,
+        MethodInvocation::GetBalance(recipient, Some(BlockSpec::Number(NUM_BLOCKS - 3))),
+    )?;
+    assert_eq!(balance, U256::from(NUM_BLOCKS - 3));
+    assert_eq!(provider.metrics().state.cached_states, cached_states);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prunes_state_diffs_and_address_transactions_of_pruned_blocks() -> anyhow::Result<()> {
+    let (provider, sender) = create_pruning_provider(prune_config())?;
+    let (recipient, transaction_hashes) = mine_transfers(&provider, sender)?;
+
+    let (pruned_hashes, retained_hashes) =
+        transaction_hashes.split_at(transaction_hashes.len() - RETAINED_BLOCKS as usize);
+
+    for transaction_hash in pruned_hashes {
+        let result = provider.handle_request(ProviderRequest::with_single(
+            MethodInvocation::GetStateDiff(*transaction_hash),
+        ));
+        assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+    }
+
+    for transaction_hash in retained_hashes {
+        let state_diff: Option<serde_json::Value> =
+            request(&provider, MethodInvocation::GetStateDiff(*transaction_hash))?;
+        assert!(state_diff.is_some());
+    }
+
+    // The first page includes transactions of pruned blocks
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::ExplorerGetAddressTransactions(recipient, None),
+    ));
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    // A page of only retained transactions is available
+    let page: AddressTransactionsPage = request(
+        &provider,
+        MethodInvocation::ExplorerGetAddressTransactions(
+            recipient,
+            Some(AddressTransactionsOptions {
+                page_size: Some(RETAINED_BLOCKS),
+                ..AddressTransactionsOptions::default()
+            }),
+        ),
+    )?;
+    assert_eq!(page.total, NUM_BLOCKS);
+
+    let mut page_hashes = page
+        .transactions
+        .iter()
+        .map(|transaction| transaction.transaction_hash)
+        .collect::<Vec<_>>();
+    page_hashes.reverse();
+    assert_eq!(page_hashes, retained_hashes);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn token_balances_include_pruned_transfers() -> anyhow::Result<()> {
+    let (provider, sender) = create_pruning_provider(prune_config())?;
+    let token = Address::random();
+    let alice = Address::random();
+    let bob = Address::random();
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(token, transfer_emitter_runtime_code()),
+    )?;
+
+    // Blocks 1 and 2
+    emit_transfer(&provider, sender, token, Address::ZERO, alice, 100)?;
+    emit_transfer(&provider, sender, token, alice, bob, 30)?;
+
+    for _ in 0..NUM_BLOCKS {
+        request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    }
+
+    let transfers: Vec<TokenTransfer> = request(&provider, MethodInvocation::TokenTransfers(None))?;
+    assert!(transfers.is_empty());
+
+    assert_eq!(token_balance(&provider, alice, None)?, U256::from(70));
+    assert_eq!(token_balance(&provider, bob, None)?, U256::from(30));
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::TokenBalances(alice, Some(BlockSpec::Number(1))),
+    ));
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "test-utils")]
+
+use edr_primitives::{keccak256, Address, B256, U256};
+use edr_provider::{
+    hardhat_rpc_types::StorageLayout,
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, ProviderRequest,
+};
+
+fn token_layout() -> anyhow::Result<StorageLayout> {
+    Ok(serde_json::from_value(serde_json::json!({
+        "storage": [
+            { "astId": 1, "contract": "Token", "label": "totalSupply", "offset": 0, "slot": "0", "type": "t_uint256" },
+            { "astId": 2, "contract": "Token", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)" }
+        ],
+        "types": {
+            "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+            "t_mapping(t_address,t_uint256)": { "encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)", "numberOfBytes": "32", "value": "t_uint256" },
+            "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" }
+        }
+    }))?)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reads_storage_variables_using_layout() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+    let token = Address::random();
+    let holder = Address::random();
+
+    let mut preimage = holder.into_word().to_vec();
+    preimage.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+    let balance_slot = U256::from_be_bytes(keccak256(preimage).0);
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetStorageAt(token, U256::ZERO, U256::from(1_000)),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetStorageAt(token, balance_slot, U256::from(250)),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetStorageLayout(token, token_layout()?),
+    )?;
+
+    let total_supply: String = request(
+        &provider,
+        MethodInvocation::ReadStorageVariable(token, String::from("totalSupply"), Vec::new(), None),
+    )?;
+    assert_eq!(total_supply, "1000");
+
+    let balance: String = request(
+        &provider,
+        MethodInvocation::ReadStorageVariable(
+            token,
+            String::from("balances"),
+            vec![holder.to_string()],
+            None,
+        ),
+    )?;
+    assert_eq!(balance, "250");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn writes_storage_variables_using_layout() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+    let token = Address::random();
+    let holder = Address::random();
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetStorageLayout(token, token_layout()?),
+    )?;
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetStorageVariable(
+            token,
+            String::from("balances"),
+            serde_json::json!("500"),
+            vec![holder.to_string()],
+        ),
+    )?;
+
+    let mut preimage = holder.into_word().to_vec();
+    preimage.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+    let balance_slot = U256::from_be_bytes(keccak256(preimage).0);
+
+    let balance: B256 = request(
+        &provider,
+        MethodInvocation::GetStorageAt(token, balance_slot, None),
+    )?;
+    assert_eq!(balance, B256::from(U256::from(500)));
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SetStorageVariable(
+            token,
+            String::from("totalSupply"),
+            serde_json::json!(true),
+            Vec::new(),
+        ),
+    ));
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_reads_without_layout() -> anyhow::Result<()> {
+    let provider = create_provider(create_test_config())?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::ReadStorageVariable(
+            Address::random(),
+            String::from("totalSupply"),
+            Vec::new(),
+            None,
+        ),
+    ));
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -7,8 +7,8 @@ use edr_eth::PreEip1898BlockSpec;
 use edr_primitives::B256;
 use edr_provider::{
     test_utils::create_test_config,
-    time::{MockTime, TimeSinceEpoch},
-    MethodInvocation, NoopLogger, Provider, ProviderRequest, Timestamp,
+    time::{MockTime, TimeMode, TimeSinceEpoch},
+    MethodInvocation, NoopLogger, Provider, ProviderError, ProviderRequest, Timestamp,
 };
 use edr_solidity::contract_decoder::ContractDecoder;
 use tokio::runtime;
@@ -20,11 +20,19 @@ struct TimestampFixture {
 
 impl TimestampFixture {
     fn new(allow_blocks_with_same_timestamp: bool) -> anyhow::Result<Self> {
+        Self::with_time_mode(allow_blocks_with_same_timestamp, TimeMode::Drift)
+    }
+
+    fn with_time_mode(
+        allow_blocks_with_same_timestamp: bool,
+        time_mode: TimeMode,
+    ) -> anyhow::Result<Self> {
         let logger = Box::<NoopLogger<L1ChainSpec, Arc<MockTime>>>::default();
         let subscription_callback_noop = Box::new(|_| ());
 
         let mut config = create_test_config();
         config.allow_blocks_with_same_timestamp = allow_blocks_with_same_timestamp;
+        config.time_mode = time_mode;
 
         let mock_timer = Arc::new(MockTime::now());
 
@@ -85,6 +93,17 @@ impl TimestampFixture {
 
         Ok(())
     }
+
+    fn set_time(
+        &self,
+        timestamp: u64,
+    ) -> Result<(), edr_provider::ProviderErrorForChainSpec<L1ChainSpec>> {
+        self.provider.handle_request(ProviderRequest::with_single(
+            MethodInvocation::EvmSetTime(Timestamp::from(timestamp)),
+        ))?;
+
+        Ok(())
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -283,3 +302,126 @@ async fn block_timestamp_with_allow_same_timestamp_includes_elapsed_time() -> an
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_timestamp_with_set_time_drifts() -> anyhow::Result<()> {
+    const DELTA: u64 = 100;
+    const INCREMENT: u64 = 5;
+
+    let fixture = TimestampFixture::new(false)?;
+    let now = fixture.mock_timer.since_epoch();
+
+    fixture.set_time(now + DELTA)?;
+    fixture.mock_timer.add_seconds(INCREMENT);
+    fixture.mine_block()?;
+
+    let latest_block_timestamp = fixture.latest_block_timestamp()?;
+    assert_eq!(latest_block_timestamp, now + DELTA + INCREMENT);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_time_lower_than_previous_block() -> anyhow::Result<()> {
+    let fixture = TimestampFixture::new(false)?;
+    let latest_block_timestamp = fixture.latest_block_timestamp()?;
+
+    let result = fixture.set_time(latest_block_timestamp - 1);
+    assert!(matches!(
+        result,
+        Err(ProviderError::TimestampLowerThanPrevious { proposed, previous })
+            if proposed == latest_block_timestamp - 1 && previous == latest_block_timestamp
+    ));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_time_too_large() -> anyhow::Result<()> {
+    let fixture = TimestampFixture::new(false)?;
+
+    let result = fixture.set_time(u64::MAX);
+    assert!(matches!(result, Err(ProviderError::InvalidInput(_))));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_timestamp_with_set_time_overrides_set_next_block_timestamp() -> anyhow::Result<()> {
+    const DELTA: u64 = 100;
+
+    let fixture = TimestampFixture::new(false)?;
+    let now = fixture.mock_timer.since_epoch();
+
+    fixture.set_next_block_timestamp(now + 2 * DELTA)?;
+    fixture.set_time(now + DELTA)?;
+    fixture.mine_block()?;
+
+    assert_eq!(fixture.latest_block_timestamp()?, now + DELTA);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_timestamp_with_frozen_time_ignores_elapsed_time() -> anyhow::Result<()> {
+    const INCREMENT: u64 = 15;
+
+    let fixture = TimestampFixture::with_time_mode(true, TimeMode::Frozen)?;
+    let now = fixture.mock_timer.since_epoch();
+
+    for _ in 0..3 {
+        fixture.mock_timer.add_seconds(INCREMENT);
+        fixture.mine_block()?;
+
+        let latest_block_timestamp = fixture.latest_block_timestamp()?;
+        assert_eq!(latest_block_timestamp, now);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_timestamp_with_frozen_time_advances_by_one_second() -> anyhow::Result<()> {
+    const INCREMENT: u64 = 15;
+
+    let fixture = TimestampFixture::with_time_mode(false, TimeMode::Frozen)?;
+    let now = fixture.mock_timer.since_epoch();
+
+    fixture.mock_timer.add_seconds(INCREMENT);
+    fixture.mine_block()?;
+    assert_eq!(fixture.latest_block_timestamp()?, now + 1);
+
+    fixture.mock_timer.add_seconds(INCREMENT);
+    fixture.mine_block()?;
+    assert_eq!(fixture.latest_block_timestamp()?, now + 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn block_timestamp_with_frozen_time_and_explicit_changes() -> anyhow::Result<()> {
+    const DELTA: u64 = 30;
+    const INCREMENT: u64 = 15;
+
+    let fixture = TimestampFixture::with_time_mode(false, TimeMode::Frozen)?;
+    let now = fixture.mock_timer.since_epoch();
+
+    fixture.increase_time(DELTA)?;
+    fixture.mock_timer.add_seconds(INCREMENT);
+    fixture.mine_block()?;
+    assert_eq!(fixture.latest_block_timestamp()?, now + DELTA);
+
+    let timestamp = now + 2 * DELTA;
+    fixture.set_next_block_timestamp(timestamp)?;
+    fixture.mine_block()?;
+    assert_eq!(fixture.latest_block_timestamp()?, timestamp);
+
+    // The clock stays frozen at the explicitly set time
+    let timestamp = now + 3 * DELTA;
+    fixture.set_time(timestamp)?;
+    fixture.mock_timer.add_seconds(INCREMENT);
+    fixture.mine_block()?;
+    assert_eq!(fixture.latest_block_timestamp()?, timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{receipt::L1RpcTransactionReceipt, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_eth::BlockSpec;
+use edr_primitives::{b256, Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    hardhat_rpc_types::{TokenBalance, TokenStandard, TokenTransfer, TokenTransferFilter},
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderRequest,
+};
+
+const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+fn create_indexing_provider(
+    index_token_transfers: bool,
+) -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let mut config = create_test_config();
+    config.index_token_transfers = index_token_transfers;
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+/// Runtime code that emits a `Transfer` event with the `from`, `to`, and
+/// `value` words of the calldata. ERC-20 events have a non-indexed value, while
+/// ERC-721 events index the token ID.
+fn transfer_emitter_runtime_code(standard: TokenStandard) -> Vec<u8> {
+    let mut code = Vec::new();
+    match standard {
+        // PUSH1 0x40 CALLDATALOAD PUSH1 0x00 MSTORE
+        TokenStandard::Erc20 => code.extend_from_slice(&[0x60, 0x40, 0x35, 0x60, 0x00, 0x52]),
+        // PUSH1 0x40 CALLDATALOAD
+        TokenStandard::Erc721 => code.extend_from_slice(&[0x60, 0x40, 0x35]),
+    }
+
+    // PUSH1 0x20 CALLDATALOAD PUSH1 0x00 CALLDATALOAD PUSH32 <topic>
+    code.extend_from_slice(&[0x60, 0x20, 0x35, 0x60, 0x00, 0x35, 0x7f]);
+    code.extend_from_slice(TRANSFER_EVENT_TOPIC.as_slice());
+
+    match standard {
+        // PUSH1 0x20 PUSH1 0x00 LOG3 STOP
+        TokenStandard::Erc20 => code.extend_from_slice(&[0x60, 0x20, 0x60, 0x00, 0xa3, 0x00]),
+        // PUSH1 0x00 PUSH1 0x00 LOG4 STOP
+        TokenStandard::Erc721 => code.extend_from_slice(&[0x60, 0x00, 0x60, 0x00, 0xa4, 0x00]),
+    }
+
+    code
+}
+
+fn deploy_token(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    standard: TokenStandard,
+) -> anyhow::Result<Address> {
+    let runtime_code = transfer_emitter_runtime_code(standard);
+    let runtime_code_len = u8::try_from(runtime_code.len())?;
+
+    // PUSH1 <len> DUP1 PUSH1 0x0b PUSH1 0x00 CODECOPY PUSH1 0x00 RETURN
+    let mut init_code = vec![
+        0x60,
+        runtime_code_len,
+        0x80,
+        0x60,
+        0x0b,
+        0x60,
+        0x00,
+        0x39,
+        0x60,
+        0x00,
+        0xf3,
+    ];
+    init_code.extend(runtime_code);
+
+    let transaction_hash: B256 = request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            data: Some(init_code.into()),
+            ..TransactionRequest::default()
+        }),
+    )?;
+
+    let receipt: L1RpcTransactionReceipt = request(
+        provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+
+    Ok(receipt
+        .contract_address
+        .expect("should have deployed a contract"))
+}
+
+fn emit_transfer(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    token: Address,
+    from: Address,
+    to: Address,
+    value: u64,
+) -> anyhow::Result<B256> {
+    let mut data = Vec::new();
+    data.extend_from_slice(from.into_word().as_slice());
+    data.extend_from_slice(to.into_word().as_slice());
+    data.extend_from_slice(&U256::from(value).to_be_bytes::<32>());
+
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(token),
+            data: Some(Bytes::from(data)),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn token_balances(
+    provider: &Provider<L1ChainSpec>,
+    address: Address,
+    block_spec: Option<BlockSpec>,
+) -> anyhow::Result<Vec<TokenBalance>> {
+    request(
+        provider,
+        MethodInvocation::TokenBalances(address, block_spec),
+    )
+}
+
+fn token_transfers(
+    provider: &Provider<L1ChainSpec>,
+    filter: Option<TokenTransferFilter>,
+) -> anyhow::Result<Vec<TokenTransfer>> {
+    request(provider, MethodInvocation::TokenTransfers(filter))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn indexes_erc20_and_erc721_transfers() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+    let alice = Address::random();
+    let bob = Address::random();
+
+    let erc20 = deploy_token(&provider, sender, TokenStandard::Erc20)?;
+    let erc721 = deploy_token(&provider, sender, TokenStandard::Erc721)?;
+
+    // Block 3
+    let mint_hash = emit_transfer(&provider, sender, erc20, Address::ZERO, alice, 100)?;
+    // Block 4
+    emit_transfer(&provider, sender, erc20, alice, bob, 30)?;
+    // Blocks 5 and 6
+    emit_transfer(&provider, sender, erc721, Address::ZERO, alice, 7)?;
+    emit_transfer(&provider, sender, erc721, Address::ZERO, alice, 8)?;
+    // Block 7
+    emit_transfer(&provider, sender, erc721, alice, bob, 7)?;
+
+    let transfers = token_transfers(&provider, None)?;
+    assert_eq!(transfers.len(), 5);
+    assert_eq!(
+        transfers[0],
+        TokenTransfer {
+            block_number: 3,
+            transaction_hash: mint_hash,
+            transaction_index: 0,
+            log_index: 0,
+            token: erc20,
+            standard: TokenStandard::Erc20,
+            from: Address::ZERO,
+            to: alice,
+            value: U256::from(100),
+        }
+    );
+
+    let nft_transfers = token_transfers(
+        &provider,
+        Some(TokenTransferFilter {
+            token: Some(erc721),
+            ..TokenTransferFilter::default()
+        }),
+    )?;
+    assert_eq!(nft_transfers.len(), 3);
+    assert!(nft_transfers
+        .iter()
+        .all(|transfer| transfer.standard == TokenStandard::Erc721));
+
+    let bob_transfers = token_transfers(
+        &provider,
+        Some(TokenTransferFilter {
+            address: Some(bob),
+            from_block: Some(BlockSpec::Number(5)),
+            ..TokenTransferFilter::default()
+        }),
+    )?;
+    assert_eq!(bob_transfers.len(), 1);
+    assert_eq!(bob_transfers[0].block_number, 7);
+
+    let balances = token_balances(&provider, alice, None)?;
+    assert_eq!(
+        balances,
+        vec![
+            TokenBalance {
+                token: erc20,
+                standard: TokenStandard::Erc20,
+                balance: U256::from(70),
+                token_ids: None,
+            },
+            TokenBalance {
+                token: erc721,
+                standard: TokenStandard::Erc721,
+                balance: U256::from(1),
+                token_ids: Some(vec![U256::from(8)]),
+            },
+        ]
+    );
+
+    let balances = token_balances(&provider, alice, Some(BlockSpec::Number(3)))?;
+    assert_eq!(
+        balances,
+        vec![TokenBalance {
+            token: erc20,
+            standard: TokenStandard::Erc20,
+            balance: U256::from(100),
+            token_ids: None,
+        }]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverted_blocks_are_removed_from_index() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+    let alice = Address::random();
+
+    let erc20 = deploy_token(&provider, sender, TokenStandard::Erc20)?;
+    emit_transfer(&provider, sender, erc20, Address::ZERO, alice, 100)?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    emit_transfer(&provider, sender, erc20, Address::ZERO, alice, 50)?;
+
+    let reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(reverted);
+
+    let balances = token_balances(&provider, alice, None)?;
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance, U256::from(100));
+
+    emit_transfer(&provider, sender, erc20, Address::ZERO, alice, 1)?;
+
+    let balances = token_balances(&provider, alice, None)?;
+    assert_eq!(balances[0].balance, U256::from(101));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_queries_when_disabled() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(false)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::TokenBalances(sender, None),
+    ));
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn erc20_balances_include_initial_balances() -> anyhow::Result<()> {
+    let (provider, sender) = create_indexing_provider(true)?;
+    let alice = Address::random();
+    let bob = Address::random();
+
+    // Replace the final STOP to return a balance of 100 for any call, including
+    // `balanceOf`: PUSH1 0x64 PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+    let mut runtime_code = transfer_emitter_runtime_code(TokenStandard::Erc20);
+    runtime_code.pop();
+    runtime_code.extend_from_slice(&[0x60, 0x64, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+    // The token exists in the genesis block, without any `Transfer` events
+    let token = Address::random();
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetCode(token, runtime_code.into()),
+    )?;
+
+    emit_transfer(&provider, sender, token, alice, bob, 30)?;
+
+    let balances = token_balances(&provider, alice, None)?;
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance, U256::from(70));
+
+    let balances = token_balances(&provider, bob, None)?;
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance, U256::from(130));
+
+    Ok(())
+}
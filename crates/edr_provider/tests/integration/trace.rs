@@ -0,0 +1,253 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_eth::BlockSpec;
+use edr_primitives::{Address, Bytes, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    CallType, LocalizedTrace, MethodInvocation, Provider, ProviderError, ProviderRequest,
+    TraceAction, TraceFilter, TraceResult, TraceType, MAX_TRACE_FILTER_BLOCK_RANGE,
+};
+
+fn create_tracing_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+    let sender = owned_account(&config, 0);
+
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    transaction: TransactionRequest,
+) -> anyhow::Result<B256> {
+    request(provider, MethodInvocation::SendTransaction(transaction))
+}
+
+/// Init code that self-destructs, sending the contract's balance to the
+/// provided beneficiary.
+fn self_destructing_init_code(beneficiary: Address) -> Bytes {
+    // PUSH20 <beneficiary> SELFDESTRUCT
+    let mut init_code = vec![0x73];
+    init_code.extend_from_slice(beneficiary.as_slice());
+    init_code.push(0xff);
+
+    init_code.into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_transaction_of_transfer() -> anyhow::Result<()> {
+    let (provider, sender) = create_tracing_provider()?;
+    let receiver = Address::random();
+
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            to: Some(receiver),
+            value: Some(U256::from(100)),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let traces: Vec<LocalizedTrace> = request(
+        &provider,
+        MethodInvocation::TraceTransaction(transaction_hash),
+    )?;
+    assert_eq!(traces.len(), 1);
+
+    let trace = &traces[0];
+    assert_eq!(trace.transaction_hash, transaction_hash);
+    assert_eq!(trace.transaction_position, 0);
+    assert_eq!(trace.block_number, 1);
+    assert_eq!(trace.trace.trace_type, TraceType::Call);
+    assert!(trace.trace.trace_address.is_empty());
+    assert_eq!(trace.trace.subtraces, 0);
+    assert_eq!(trace.trace.error, None);
+
+    let TraceAction::Call(action) = &trace.trace.action else {
+        panic!("expected a call action");
+    };
+    assert_eq!(action.call_type, CallType::Call);
+    assert_eq!(action.from, sender);
+    assert_eq!(action.to, receiver);
+    assert_eq!(action.value, U256::from(100));
+
+    let unknown: Option<Vec<LocalizedTrace>> = request(
+        &provider,
+        MethodInvocation::TraceTransaction(B256::random()),
+    )?;
+    assert_eq!(unknown, None);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_block_with_self_destruct() -> anyhow::Result<()> {
+    let (provider, sender) = create_tracing_provider()?;
+    let beneficiary = Address::random();
+
+    let transaction_hash = send_transaction(
+        &provider,
+        TransactionRequest {
+            from: sender,
+            data: Some(self_destructing_init_code(beneficiary)),
+            ..TransactionRequest::default()
+        },
+    )?;
+
+    let traces: Vec<LocalizedTrace> =
+        request(&provider, MethodInvocation::TraceBlock(BlockSpec::latest()))?;
+    assert_eq!(traces.len(), 2);
+    assert!(traces
+        .iter()
+        .all(|trace| trace.transaction_hash == transaction_hash));
+
+    let create = &traces[0].trace;
+    assert_eq!(create.trace_type, TraceType::Create);
+    assert!(create.trace_address.is_empty());
+    assert_eq!(create.subtraces, 1);
+    assert!(matches!(create.action, TraceAction::Create(_)));
+    let Some(TraceResult::Create(result)) = &create.result else {
+        panic!("expected a create result");
+    };
+
+    let suicide = &traces[1].trace;
+    assert_eq!(suicide.trace_type, TraceType::Suicide);
+    assert_eq!(suicide.trace_address, vec![0]);
+    let TraceAction::Suicide(action) = &suicide.action else {
+        panic!("expected a suicide action");
+    };
+    assert_eq!(action.address, result.address);
+    assert_eq!(action.refund_address, beneficiary);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_filter_by_address() -> anyhow::Result<()> {
+    let (provider, sender) = create_tracing_provider()?;
+    let first_receiver = Address::random();
+    let second_receiver = Address::random();
+
+    let mut transaction_hashes = Vec::new();
+    for receiver in [first_receiver, second_receiver, first_receiver] {
+        transaction_hashes.push(send_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                to: Some(receiver),
+                value: Some(U256::from(1)),
+                ..TransactionRequest::default()
+            },
+        )?);
+    }
+
+    let traces: Vec<LocalizedTrace> = request(
+        &provider,
+        MethodInvocation::TraceFilter(TraceFilter {
+            from_block: Some(BlockSpec::Number(0)),
+            to_address: Some(vec![first_receiver]),
+            ..TraceFilter::default()
+        }),
+    )?;
+    let traced_hashes: Vec<B256> = traces.iter().map(|trace| trace.transaction_hash).collect();
+    assert_eq!(
+        traced_hashes,
+        vec![transaction_hashes[0], transaction_hashes[2]]
+    );
+
+    let traces: Vec<LocalizedTrace> = request(
+        &provider,
+        MethodInvocation::TraceFilter(TraceFilter {
+            from_block: Some(BlockSpec::Number(0)),
+            from_address: Some(vec![sender]),
+            after: Some(1),
+            count: Some(1),
+            ..TraceFilter::default()
+        }),
+    )?;
+    let traced_hashes: Vec<B256> = traces.iter().map(|trace| trace.transaction_hash).collect();
+    assert_eq!(traced_hashes, vec![transaction_hashes[1]]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_transaction_distinguishes_call_code_from_delegate_call() -> anyhow::Result<()> {
+    let (provider, sender) = create_tracing_provider()?;
+
+    let library = Address::random();
+    for (contract, opcode, expected_call_type) in [
+        (Address::random(), 0xf2, CallType::CallCode),
+        (Address::random(), 0xf4, CallType::DelegateCall),
+    ] {
+        // CALLCODE takes a value argument, whereas DELEGATECALL doesn't
+        let value_argument: &[u8] = if opcode == 0xf2 { &[0x60, 0x00] } else { &[] };
+
+        // PUSH1 0 (x4) [PUSH1 0] PUSH20 <library> GAS <opcode> STOP
+        let mut code = [0x60, 0x00].repeat(4);
+        code.extend_from_slice(value_argument);
+        code.push(0x73);
+        code.extend_from_slice(library.as_slice());
+        code.extend_from_slice(&[0x5a, opcode, 0x00]);
+
+        request::<bool, _, _>(
+            &provider,
+            MethodInvocation::SetCode(contract, Bytes::from(code)),
+        )?;
+
+        let transaction_hash = send_transaction(
+            &provider,
+            TransactionRequest {
+                from: sender,
+                to: Some(contract),
+                ..TransactionRequest::default()
+            },
+        )?;
+
+        let traces: Vec<LocalizedTrace> = request(
+            &provider,
+            MethodInvocation::TraceTransaction(transaction_hash),
+        )?;
+        assert_eq!(traces.len(), 2);
+
+        let TraceAction::Call(action) = &traces[1].trace.action else {
+            panic!("expected a call action");
+        };
+        assert_eq!(action.call_type, expected_call_type);
+        assert_eq!(action.from, contract);
+        assert_eq!(action.to, library);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_filter_rejects_large_block_ranges() -> anyhow::Result<()> {
+    let (provider, _sender) = create_tracing_provider()?;
+
+    provider.handle_request(ProviderRequest::with_single(MethodInvocation::Mine(
+        Some(MAX_TRACE_FILTER_BLOCK_RANGE),
+        None,
+    )))?;
+
+    let trace_filter = |to_block| {
+        provider.handle_request(ProviderRequest::with_single(MethodInvocation::TraceFilter(
+            TraceFilter {
+                from_block: Some(BlockSpec::Number(0)),
+                to_block: Some(BlockSpec::Number(to_block)),
+                ..TraceFilter::default()
+            },
+        )))
+    };
+
+    assert!(trace_filter(MAX_TRACE_FILTER_BLOCK_RANGE - 1).is_ok());
+    assert!(matches!(
+        trace_filter(MAX_TRACE_FILTER_BLOCK_RANGE),
+        Err(ProviderError::InvalidInput(_))
+    ));
+
+    Ok(())
+}
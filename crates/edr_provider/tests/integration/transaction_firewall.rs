@@ -0,0 +1,173 @@
+#![cfg(feature = "test-utils")]
+
+use std::sync::Arc;
+
+use edr_chain_l1::{rpc::TransactionRequest, L1ChainSpec};
+use edr_primitives::{address, Address, Bytes, B256, U256, U64};
+use edr_provider::{
+    test_utils::{create_test_config, owned_account, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest, TransactionFirewallRequest,
+    TransactionFirewallVerdict,
+};
+
+const SANCTIONED: Address = address!("0x000000000000000000000000000000000000dead");
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+fn create_provider() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+    let sender = owned_account(&config, 0);
+
+    let provider = edr_provider::test_utils::create_provider(config)?;
+
+    provider.set_transaction_firewall(Some(Arc::new(|request: &TransactionFirewallRequest| {
+        if request.to == Some(SANCTIONED) {
+            TransactionFirewallVerdict::Reject {
+                reason: "sanctioned recipient".to_string(),
+            }
+        } else if request.selector == Some(TRANSFER_SELECTOR) {
+            TransactionFirewallVerdict::Accept {
+                tags: vec!["erc20-transfer".to_string()],
+            }
+        } else {
+            TransactionFirewallVerdict::Accept { tags: Vec::new() }
+        }
+    })));
+
+    Ok((provider, sender))
+}
+
+fn send_transaction(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    to: Address,
+    data: Option<Bytes>,
+) -> anyhow::Result<B256> {
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            to: Some(to),
+            data,
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn send_transfer(provider: &Provider<L1ChainSpec>, sender: Address) -> anyhow::Result<B256> {
+    let mut calldata = TRANSFER_SELECTOR.to_vec();
+    calldata.extend_from_slice(&[0u8; 64]);
+
+    send_transaction(
+        provider,
+        sender,
+        Address::random(),
+        Some(Bytes::from(calldata)),
+    )
+}
+
+fn transaction_tags(
+    provider: &Provider<L1ChainSpec>,
+    transaction_hash: B256,
+) -> anyhow::Result<Vec<String>> {
+    request(
+        provider,
+        MethodInvocation::GetTransactionTags(transaction_hash),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firewall_rejects_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider()?;
+
+    let block_number: U256 = request(&provider, MethodInvocation::BlockNumber(()))?;
+
+    let error = provider
+        .handle_request(ProviderRequest::with_single(
+            MethodInvocation::SendTransaction(TransactionRequest {
+                from: sender,
+                to: Some(SANCTIONED),
+                ..TransactionRequest::default()
+            }),
+        ))
+        .expect_err("transaction should be rejected");
+
+    assert!(matches!(
+        error,
+        ProviderError::TransactionRejected { reason, .. } if reason == "sanctioned recipient"
+    ));
+
+    // The rejected transaction was not mined
+    let new_block_number: U256 = request(&provider, MethodInvocation::BlockNumber(()))?;
+    assert_eq!(new_block_number, block_number);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firewall_tags_transactions() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider()?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let tagged_hash = send_transfer(&provider, sender)?;
+    assert_eq!(
+        transaction_tags(&provider, tagged_hash)?,
+        vec!["erc20-transfer".to_string()]
+    );
+
+    let untagged_hash = send_transaction(&provider, sender, Address::random(), None)?;
+    assert!(transaction_tags(&provider, untagged_hash)?.is_empty());
+
+    // Removing the firewall admits all transactions
+    provider.set_transaction_firewall(None);
+    send_transaction(&provider, sender, SANCTIONED, None)?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tags_are_retained_when_transactions_are_mined() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider()?;
+
+    // The transaction is automined
+    let transaction_hash = send_transfer(&provider, sender)?;
+    assert_eq!(
+        transaction_tags(&provider, transaction_hash)?,
+        vec!["erc20-transfer".to_string()]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tags_are_removed_with_their_blocks() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider()?;
+
+    let transaction_hash = send_transfer(&provider, sender)?;
+    request::<(), _, _>(&provider, MethodInvocation::DebugSetHead(U64::ZERO))?;
+
+    assert!(transaction_tags(&provider, transaction_hash)?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reverting_to_snapshot_restores_tags() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider()?;
+    request::<bool, _, _>(&provider, MethodInvocation::EvmSetAutomine(false))?;
+
+    let transaction_hash = send_transfer(&provider, sender)?;
+
+    let snapshot_id: U64 = request(&provider, MethodInvocation::EvmSnapshot(()))?;
+    request::<String, _, _>(&provider, MethodInvocation::EvmMine(None))?;
+    assert!(transaction_tags(&provider, transaction_hash)?.is_empty());
+
+    let was_reverted: bool = request(&provider, MethodInvocation::EvmRevert(snapshot_id))?;
+    assert!(was_reverted);
+
+    assert_eq!(
+        transaction_tags(&provider, transaction_hash)?,
+        vec!["erc20-transfer".to_string()]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "test-utils")]
+
+use edr_chain_l1::{
+    rpc::{call::L1CallRequest, TransactionRequest},
+    L1ChainSpec,
+};
+use edr_primitives::{Address, Bytes, B256, MAX_INITCODE_SIZE};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, owned_account, request},
+    MethodInvocation, Provider,
+};
+
+/// The size of the deployed code, which exceeds the EIP-170 limit of 24576
+/// bytes.
+const CODE_SIZE: u16 = 25_000;
+
+fn create_provider_and_sender() -> anyhow::Result<(Provider<L1ChainSpec>, Address)> {
+    let config = create_test_config();
+
+    let sender = owned_account(&config, 0);
+    let provider = create_provider(config)?;
+
+    Ok((provider, sender))
+}
+
+/// Init code that deploys `CODE_SIZE` zero bytes.
+fn large_contract_init_code() -> Bytes {
+    let [high, low] = CODE_SIZE.to_be_bytes();
+
+    // PUSH2 CODE_SIZE PUSH1 0 RETURN
+    Bytes::from(vec![0x61, high, low, 0x60, 0x00, 0xf3])
+}
+
+/// Init code that exceeds the EIP-3860 limit and deploys an empty contract.
+fn large_init_code() -> Bytes {
+    // STOP
+    Bytes::from(vec![0x00; MAX_INITCODE_SIZE + 1])
+}
+
+fn call_deployment(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    init_code: Bytes,
+) -> anyhow::Result<Bytes> {
+    request(
+        provider,
+        MethodInvocation::Call(
+            L1CallRequest {
+                from: Some(sender),
+                data: Some(init_code),
+                ..L1CallRequest::default()
+            },
+            None,
+            None,
+        ),
+    )
+}
+
+fn send_deployment(
+    provider: &Provider<L1ChainSpec>,
+    sender: Address,
+    init_code: Bytes,
+) -> anyhow::Result<B256> {
+    request(
+        provider,
+        MethodInvocation::SendTransaction(TransactionRequest {
+            from: sender,
+            data: Some(init_code),
+            gas: Some(10_000_000),
+            ..TransactionRequest::default()
+        }),
+    )
+}
+
+fn set_allow(
+    provider: &Provider<L1ChainSpec>,
+    method: MethodInvocation<L1ChainSpec>,
+) -> anyhow::Result<()> {
+    let result: bool = request(provider, method)?;
+    assert!(result);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn contract_size_limit_can_be_disabled_at_runtime() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    let deployed_code = call_deployment(&provider, sender, large_contract_init_code())?;
+    assert!(deployed_code.is_empty());
+
+    let transaction_hash = send_deployment(&provider, sender, large_contract_init_code())?;
+    let receipt: serde_json::Value = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    assert_eq!(receipt["status"], "0x0");
+
+    set_allow(
+        &provider,
+        MethodInvocation::SetAllowUnlimitedContractSize(true),
+    )?;
+
+    let deployed_code = call_deployment(&provider, sender, large_contract_init_code())?;
+    assert_eq!(deployed_code.len(), usize::from(CODE_SIZE));
+
+    let transaction_hash = send_deployment(&provider, sender, large_contract_init_code())?;
+    let receipt: serde_json::Value = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    assert_eq!(receipt["status"], "0x1");
+
+    let contract_address: Address = serde_json::from_value(receipt["contractAddress"].clone())?;
+    let code: Bytes = request(&provider, MethodInvocation::GetCode(contract_address, None))?;
+    assert_eq!(code.len(), usize::from(CODE_SIZE));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn init_code_size_limit_can_be_disabled_at_runtime() -> anyhow::Result<()> {
+    let (provider, sender) = create_provider_and_sender()?;
+
+    assert!(call_deployment(&provider, sender, large_init_code()).is_err());
+    assert!(send_deployment(&provider, sender, large_init_code()).is_err());
+
+    set_allow(
+        &provider,
+        MethodInvocation::SetAllowUnlimitedInitCodeSize(true),
+    )?;
+
+    call_deployment(&provider, sender, large_init_code())?;
+
+    let transaction_hash = send_deployment(&provider, sender, large_init_code())?;
+    let receipt: serde_json::Value = request(
+        &provider,
+        MethodInvocation::GetTransactionReceipt(transaction_hash),
+    )?;
+    assert_eq!(receipt["status"], "0x1");
+
+    // The contract size limit remains in effect
+    let deployed_code = call_deployment(&provider, sender, large_contract_init_code())?;
+    assert!(deployed_code.is_empty());
+
+    set_allow(
+        &provider,
+        MethodInvocation::SetAllowUnlimitedInitCodeSize(false),
+    )?;
+
+    assert!(send_deployment(&provider, sender, large_init_code()).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "test-utils")]
+
+use edr_block_header::Withdrawal;
+use edr_chain_l1::{rpc::block::L1RpcBlock, L1ChainSpec};
+use edr_eth::PreEip1898BlockSpec;
+use edr_primitives::{Address, B256, U256};
+use edr_provider::{
+    test_utils::{create_provider, create_test_config, request},
+    MethodInvocation, Provider, ProviderError, ProviderRequest,
+};
+
+const WITHDRAWAL_AMOUNT_GWEI: u64 = 32_000_000_000;
+
+fn create_provider_with_hardfork(
+    hardfork: edr_chain_l1::Hardfork,
+) -> anyhow::Result<Provider<L1ChainSpec>> {
+    let mut config = create_test_config();
+    config.hardfork = hardfork;
+
+    create_provider(config)
+}
+
+fn mine_block(provider: &Provider<L1ChainSpec>) -> anyhow::Result<L1RpcBlock<B256>> {
+    request::<String, _, _>(provider, MethodInvocation::EvmMine(None))?;
+
+    request(
+        provider,
+        MethodInvocation::GetBlockByNumber(PreEip1898BlockSpec::latest(), false),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn withdrawals_are_included_and_credited() -> anyhow::Result<()> {
+    let provider = create_provider_with_hardfork(edr_chain_l1::Hardfork::CANCUN)?;
+
+    let recipient = Address::random();
+    let withdrawals = vec![Withdrawal {
+        index: 0,
+        validator_index: 1,
+        address: recipient,
+        amount: WITHDRAWAL_AMOUNT_GWEI,
+    }];
+
+    request::<bool, _, _>(
+        &provider,
+        MethodInvocation::SetNextBlockWithdrawals(withdrawals.clone()),
+    )?;
+
+    let block = mine_block(&provider)?;
+    assert_eq!(block.withdrawals, Some(withdrawals));
+    assert_ne!(
+        block.withdrawals_root,
+        Some(edr_primitives::KECCAK_NULL_RLP)
+    );
+
+    let balance: U256 = request(&provider, MethodInvocation::GetBalance(recipient, None))?;
+    assert_eq!(
+        balance,
+        U256::from(WITHDRAWAL_AMOUNT_GWEI) * U256::from(1_000_000_000u64)
+    );
+
+    // Scheduled withdrawals only apply to a single block
+    let block = mine_block(&provider)?;
+    assert_eq!(block.withdrawals, Some(Vec::new()));
+    assert_eq!(
+        block.withdrawals_root,
+        Some(edr_primitives::KECCAK_NULL_RLP)
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_next_block_withdrawals_requires_shanghai() -> anyhow::Result<()> {
+    let provider = create_provider_with_hardfork(edr_chain_l1::Hardfork::MERGE)?;
+
+    let result = provider.handle_request(ProviderRequest::with_single(
+        MethodInvocation::SetNextBlockWithdrawals(Vec::new()),
+    ));
+    assert!(matches!(
+        result,
+        Err(ProviderError::SetNextBlockWithdrawalsUnsupported { .. })
+    ));
+
+    Ok(())
+}
@@ -28,6 +28,12 @@ pub struct TransactionReceipt<ExecutionReceiptT: ExecutionReceipt> {
     /// maxPriorityFeePerGas) after EIP-1559. Following Hardhat, only present if
     /// the hardfork is at least London.
     pub effective_gas_price: Option<u128>,
+    /// Blob gas used by this transaction. Only present for EIP-4844
+    /// transactions.
+    pub blob_gas_used: Option<u64>,
+    /// The price per unit of blob gas paid by this transaction. Only present
+    /// for EIP-4844 transactions.
+    pub blob_gas_price: Option<u128>,
 }
 
 impl<ExecutionReceiptT: ExecutionReceipt> AsExecutionReceipt
@@ -49,13 +55,17 @@ impl<ExecutionReceiptT: ExecutionReceipt> TransactionReceipt<ExecutionReceiptT>
 
 impl<ExecutionReceiptT: ExecutionReceipt> TransactionReceipt<ExecutionReceiptT> {
     /// Constructs a new instance using the provided execution receipt an
-    /// transaction
+    /// transaction.
+    ///
+    /// The `block_blob_gas_price` is the base fee per blob gas of the block
+    /// that includes the transaction, if the hardfork is at least Cancun.
     pub fn new<HaltReasonT: HaltReasonTrait, HardforkT: Into<EvmSpecId>>(
         execution_receipt: ExecutionReceiptT,
         transaction: &impl ExecutableTransaction,
         result: &ExecutionResult<HaltReasonT>,
         transaction_index: u64,
         block_base_fee: u128,
+        block_blob_gas_price: Option<u128>,
         hardfork: HardforkT,
     ) -> Self {
         let contract_address = if let ExecutionResult::Success {
@@ -78,6 +88,9 @@ impl<ExecutionReceiptT: ExecutionReceipt> TransactionReceipt<ExecutionReceiptT>
             None
         };
 
+        let blob_gas_used = transaction.total_blob_gas();
+        let blob_gas_price = blob_gas_used.and(block_blob_gas_price);
+
         Self {
             inner: execution_receipt,
             transaction_hash: *transaction.transaction_hash(),
@@ -87,6 +100,8 @@ impl<ExecutionReceiptT: ExecutionReceipt> TransactionReceipt<ExecutionReceiptT>
             contract_address,
             gas_used: result.gas_used(),
             effective_gas_price,
+            blob_gas_used,
+            blob_gas_price,
         }
     }
 }
@@ -112,6 +127,8 @@ where
             contract_address: self.contract_address,
             gas_used: self.gas_used,
             effective_gas_price: self.effective_gas_price,
+            blob_gas_used: self.blob_gas_used,
+            blob_gas_price: self.blob_gas_price,
         }
     }
 }
@@ -39,6 +39,15 @@ pub trait CacheableMethod: Into<Option<Self::MethodWithResolvableBlockTag>> {
     fn write_cache_key(self) -> Option<WriteCacheKey<Self>>;
 }
 
+/// Statistics about the responses that were served from the cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of calls that were served from the cache.
+    pub hits: u64,
+    /// The number of calls that were sent to the remote node.
+    pub misses: u64,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct CachedBlockNumber {
     pub block_number: u64,
@@ -4,7 +4,7 @@ use std::{
     marker::PhantomData,
     path::{Path, PathBuf},
     sync::atomic::{AtomicU64, Ordering},
-    time::Duration,
+    time::Instant,
 };
 
 use edr_eth::block::{block_time, is_safe_block_number, IsSafeBlockNumberArgs};
@@ -29,19 +29,18 @@ use crate::{
             CacheKeyForUncheckedBlockNumber, CacheKeyForUnresolvedBlockTag, ReadCacheKey,
             ResolvedSymbolicTag, WriteCacheKey,
         },
-        remove_from_cache, CacheableMethod, CachedBlockNumber,
+        remove_from_cache, CacheStats, CacheableMethod, CachedBlockNumber,
     },
-    error::{MiddlewareError, ReqwestError},
+    error::{MiddlewareError, ReqwestError, WebSocketError},
     jsonrpc,
+    transport::{
+        faults::FaultInjectionMiddleware, ws::WebSocketTransport, RateLimiter, Subscription,
+        TransportConfig,
+    },
 };
 
 const RPC_CACHE_DIR: &str = "rpc_cache";
 const TMP_DIR: &str = "tmp";
-// Retry parameters for rate limited requests.
-const EXPONENT_BASE: u32 = 2;
-const MIN_RETRY_INTERVAL: Duration = Duration::from_secs(1);
-const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(32);
-const MAX_RETRIES: u32 = 9;
 
 /// Specialized error types
 #[derive(Debug, thiserror::Error)]
@@ -88,6 +87,10 @@ pub enum RpcClientError {
     #[error(transparent)]
     InvalidUrl(#[from] url::ParseError),
 
+    /// The transport configuration is invalid.
+    #[error("Invalid transport configuration: {0}")]
+    InvalidTransportConfig(String),
+
     /// The JSON-RPC returned an error.
     #[error("{error}. Request: {request}")]
     JsonRpcError {
@@ -108,6 +111,15 @@ pub enum RpcClientError {
         error: cache::Error,
     },
 
+    /// Failed to communicate with the remote node over a WebSocket.
+    #[error(transparent)]
+    WebSocket(WebSocketError),
+
+    /// Subscriptions are only supported when the primary URL is a WebSocket
+    /// URL.
+    #[error("Subscriptions require a WebSocket URL ('ws://' or 'wss://')")]
+    SubscriptionsUnsupported,
+
     /// Failed to join a tokio task.
     #[error(transparent)]
     JoinError(#[from] tokio::task::JoinError),
@@ -142,9 +154,16 @@ pub trait RpcMethod {
 /// client only caches responses for finalized blocks.
 #[derive(Debug)]
 pub struct RpcClient<MethodT: RpcMethod + Serialize> {
+    /// The primary URL, which is used for identifying the remote node in the
+    /// cache.
     url: url::Url,
+    /// The endpoints to send requests to, in order of preference. The first
+    /// endpoint is always the primary URL.
+    endpoints: Vec<Endpoint>,
     chain_id: OnceCell<u64>,
     cached_block_number: RwLock<Option<CachedBlockNumber>>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
     client: ClientWithMiddleware,
     next_id: AtomicU64,
     rpc_cache_dir: PathBuf,
@@ -161,10 +180,43 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
         cache_dir: PathBuf,
         extra_headers: Option<HeaderMap>,
     ) -> Result<Self, RpcClientError> {
+        Self::with_transport_config(url, cache_dir, extra_headers, TransportConfig::default())
+    }
+
+    /// Create a new instance, given a remote node URL and a policy for
+    /// retries, rate limiting, timeouts, and fallback endpoints.
+    ///
+    /// The cache directory is the global EDR cache directory configured by the
+    /// user.
+    pub fn with_transport_config(
+        url: &str,
+        cache_dir: PathBuf,
+        extra_headers: Option<HeaderMap>,
+        transport_config: TransportConfig,
+    ) -> Result<Self, RpcClientError> {
+        let TransportConfig {
+            backoff_exponent_base,
+            max_retries,
+            min_retry_interval,
+            max_retry_interval,
+            request_timeout,
+            max_requests_per_second,
+            fallback_urls,
+            fault_injection,
+        } = &transport_config;
+
+        if min_retry_interval > max_retry_interval {
+            return Err(RpcClientError::InvalidTransportConfig(
+                "the minimum retry interval is greater than the maximum retry interval".to_string(),
+            ));
+        }
+
+        // The backoff policy applies full jitter to every interval, to avoid
+        // retrying in lockstep with other clients.
         let retry_policy = ExponentialBackoff::builder()
-            .retry_bounds(MIN_RETRY_INTERVAL, MAX_RETRY_INTERVAL)
-            .base(EXPONENT_BASE)
-            .build_with_max_retries(MAX_RETRIES);
+            .retry_bounds(*min_retry_interval, *max_retry_interval)
+            .base(*backoff_exponent_base)
+            .build_with_max_retries(*max_retries);
 
         let mut headers = extra_headers.unwrap_or_default();
         headers.append(
@@ -177,20 +229,28 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
                 .expect("Version string is valid header value"),
         );
 
-        let client = HttpClient::builder()
-            .default_headers(headers)
+        let client = HttpClient::builder().default_headers(headers.clone());
+        let client = if let Some(request_timeout) = request_timeout {
+            client.timeout(*request_timeout)
+        } else {
+            client
+        };
+        let client = client
             .build()
             .expect("Default construction nor setting default headers can cause an error");
 
+        let client = HttpClientBuilder::new(client);
         #[cfg(feature = "tracing")]
-        let client = HttpClientBuilder::new(client)
-            .with(TracingMiddleware::default())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
-        #[cfg(not(feature = "tracing"))]
-        let client = HttpClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
+        let client = client.with(TracingMiddleware::default());
+        let client = client.with(RetryTransientMiddleware::new_with_policy(retry_policy));
+        // Faults are injected below the retry middleware, so they're retried
+        // like any other transient failure.
+        let client = if fault_injection.is_active() {
+            client.with(FaultInjectionMiddleware::new(fault_injection.clone()))
+        } else {
+            client
+        };
+        let client = client.build();
 
         let rpc_cache_dir = cache_dir.join(RPC_CACHE_DIR);
         // We aren't using the system temporary directories as they may be on a
@@ -198,10 +258,34 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
         // fail.
         let tmp_dir = rpc_cache_dir.join(TMP_DIR);
 
+        let url: url::Url = url.parse()?;
+        let endpoints = std::iter::once(Ok(url.clone()))
+            .chain(fallback_urls.iter().map(|url| url.parse()))
+            .map(|url| {
+                url.map(|url| {
+                    let transport = match url.scheme() {
+                        "ws" | "wss" => EndpointTransport::WebSocket(Box::new(
+                            WebSocketTransport::new(url.clone(), &headers, &transport_config),
+                        )),
+                        _ => EndpointTransport::Http,
+                    };
+
+                    Endpoint {
+                        url,
+                        rate_limiter: max_requests_per_second.map(RateLimiter::new),
+                        transport,
+                    }
+                })
+            })
+            .collect::<Result<_, url::ParseError>>()?;
+
         Ok(RpcClient {
-            url: url.parse()?,
+            url,
+            endpoints,
             chain_id: OnceCell::new(),
             cached_block_number: RwLock::new(None),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
             client,
             next_id: AtomicU64::new(0),
             rpc_cache_dir: cache_dir.join(RPC_CACHE_DIR),
@@ -483,22 +567,83 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
         .await
     }
 
+    /// Sends the request to the endpoints in order of preference, falling back
+    /// to the next endpoint when a request fails after exhausting its retries.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     async fn send_request_body(
         &self,
         request_body: &SerializedRequest,
     ) -> Result<String, RpcClientError> {
-        self.client
-            .post(self.url.clone())
-            .body(request_body.to_json_string())
-            .send()
-            .await
-            .map_err(|err| RpcClientError::FailedToSend(err.into()))?
-            .error_for_status()
-            .map_err(|err| RpcClientError::HttpStatus(err.into()))?
-            .text()
-            .await
-            .map_err(|err| RpcClientError::CorruptedResponse(err.into()))
+        let (last_endpoint, fallback_endpoints) = self
+            .endpoints
+            .split_last()
+            .expect("There is always at least one endpoint");
+
+        for endpoint in fallback_endpoints {
+            match self.send_request_body_to(endpoint, request_body).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    log::warn!(
+                        "Request to '{}' failed, trying the next endpoint. error: '{error}'",
+                        endpoint.url.host_str().unwrap_or("<unknown host>")
+                    );
+                }
+            }
+        }
+
+        self.send_request_body_to(last_endpoint, request_body).await
+    }
+
+    async fn send_request_body_to(
+        &self,
+        endpoint: &Endpoint,
+        request_body: &SerializedRequest,
+    ) -> Result<String, RpcClientError> {
+        if let Some(rate_limiter) = &endpoint.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        if let EndpointTransport::WebSocket(websocket) = &endpoint.transport {
+            let result = if let Some(id) = request_body.id()? {
+                websocket
+                    .send_request(id, request_body.to_json_string())
+                    .await
+            } else {
+                // Notifications don't have a response
+                websocket
+                    .send_notification(request_body.to_json_string())
+                    .await
+                    .map(|()| String::new())
+            };
+
+            return result.map_err(RpcClientError::WebSocket);
+        }
+
+        let start = Instant::now();
+        let result = async {
+            self.client
+                .post(endpoint.url.clone())
+                .body(request_body.to_json_string())
+                .send()
+                .await
+                .map_err(|err| RpcClientError::FailedToSend(err.into()))?
+                .error_for_status()
+                .map_err(|err| RpcClientError::HttpStatus(err.into()))?
+                .text()
+                .await
+                .map_err(|err| RpcClientError::CorruptedResponse(err.into()))
+        }
+        .await;
+
+        tracing::debug!(
+            host = endpoint.url.host_str().unwrap_or("<unknown host>"),
+            method = request_body.method().unwrap_or("<unknown method>"),
+            duration = ?start.elapsed(),
+            success = result.is_ok(),
+            "Sent remote request"
+        );
+
+        result
     }
 
     fn serialize_request(&self, input: &MethodT) -> Result<SerializedRequest, RpcClientError> {
@@ -520,6 +665,46 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
         Ok(SerializedRequest(request))
     }
 
+    /// Subscribes to notifications of the remote node using `eth_subscribe`
+    /// with the provided parameters, e.g. `["newHeads"]`.
+    ///
+    /// Only supported when the primary URL is a WebSocket URL.
+    pub async fn subscribe(&self, params: impl Serialize) -> Result<Subscription, RpcClientError> {
+        let Some(Endpoint {
+            transport: EndpointTransport::WebSocket(websocket),
+            ..
+        }) = self.endpoints.first()
+        else {
+            return Err(RpcClientError::SubscriptionsUnsupported);
+        };
+
+        let params = serde_json::to_value(params).map_err(RpcClientError::InvalidJsonRequest)?;
+
+        let id = jsonrpc::Id::Num(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let request = serde_json::json!({
+            "jsonrpc": jsonrpc::Version::V2_0,
+            "id": id,
+            "method": "eth_subscribe",
+            "params": params,
+        })
+        .to_string();
+
+        let (response, notifications) = websocket
+            .subscribe(id, request.clone())
+            .await
+            .map_err(RpcClientError::WebSocket)?;
+
+        let id = Self::parse_response_str::<String>(response)?
+            .data
+            .into_result()
+            .map_err(|error| RpcClientError::JsonRpcError {
+                error: Box::new(error),
+                request,
+            })?;
+
+        Ok(Subscription { id, notifications })
+    }
+
     /// Calls the provided JSON-RPC method and returns the result.
     pub async fn call<SuccessT: DeserializeOwned + Serialize>(
         &self,
@@ -545,6 +730,7 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
                 Ok(result) => {
                     #[cfg(feature = "tracing")]
                     tracing::trace!("Cache hit: {}", method.name());
+                    self.cache_hits.fetch_add(1, Ordering::Relaxed);
                     return Ok(result);
                 }
                 Err(error) => match error {
@@ -567,6 +753,7 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
 
         #[cfg(feature = "tracing")]
         tracing::trace!("Cache miss: {}", method.name());
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
 
         let result: SuccessT = self.send_request_and_extract_result(request).await?;
 
@@ -588,6 +775,15 @@ impl<MethodT: RpcMethod + Serialize> RpcClient<MethodT> {
         self.send_request_and_extract_result(request).await
     }
 
+    /// Returns the number of calls that were served from the cache and the
+    /// number of calls that were sent to the remote node.
+    pub fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.cache_hits.load(Ordering::Relaxed),
+            misses: self.cache_misses.load(Ordering::Relaxed),
+        }
+    }
+
     /// Calls `eth_blockNumber` and returns the block number.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub async fn block_number(&self) -> Result<u64, RpcClientError> {
@@ -654,12 +850,44 @@ async fn ensure_cache_directory(
         })
 }
 
+/// A remote JSON-RPC endpoint with its own rate limit.
+#[derive(Debug)]
+struct Endpoint {
+    url: url::Url,
+    rate_limiter: Option<RateLimiter>,
+    transport: EndpointTransport,
+}
+
+/// The protocol used to communicate with an [`Endpoint`], based on the scheme
+/// of its URL.
+#[derive(Debug)]
+enum EndpointTransport {
+    Http,
+    WebSocket(Box<WebSocketTransport>),
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[repr(transparent)]
 #[serde(transparent)]
 struct SerializedRequest(serde_json::Value);
 
 impl SerializedRequest {
+    /// Returns the id of the request, or `None` if the request is a
+    /// notification.
+    fn id(&self) -> Result<Option<jsonrpc::Id>, RpcClientError> {
+        self.0
+            .get("id")
+            .filter(|id| !id.is_null())
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(RpcClientError::InvalidJsonRequest)
+    }
+
+    fn method(&self) -> Option<&str> {
+        self.0.get("method").and_then(serde_json::Value::as_str)
+    }
+
     fn to_json_string(&self) -> String {
         self.0.to_string()
     }
@@ -677,6 +905,7 @@ mod tests {
         KeyHasher,
     };
     use super::*;
+    use crate::transport::{ErrorRate, FaultInjectionConfig};
 
     #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
     #[serde(tag = "method", content = "params")]
@@ -839,6 +1068,56 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn injected_faults_fail_requests() -> anyhow::Result<()> {
+        let cache_dir = tempfile::TempDir::new()?;
+
+        let client = RpcClient::<TestMethod>::with_transport_config(
+            "http://localhost:1",
+            cache_dir.path().into(),
+            None,
+            TransportConfig {
+                max_retries: 0,
+                fault_injection: FaultInjectionConfig {
+                    error_rate: ErrorRate::try_from(1.0)?,
+                    ..FaultInjectionConfig::default()
+                },
+                ..TransportConfig::default()
+            },
+        )?;
+
+        let error = client
+            .block_number()
+            .await
+            .expect_err("request should fail");
+        assert!(matches!(
+            error,
+            RpcClientError::HttpStatus(error)
+                if reqwest::Error::from(error).status()
+                    == Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialized_notification_has_no_id() -> anyhow::Result<()> {
+        let request = SerializedRequest(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_chainId",
+        }));
+        assert_eq!(request.id()?, Some(jsonrpc::Id::Num(1)));
+
+        let notification = SerializedRequest(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_chainId",
+        }));
+        assert_eq!(notification.id()?, None);
+
+        Ok(())
+    }
+
     #[cfg(feature = "test-remote")]
     mod alchemy {
         use std::ops::Deref;
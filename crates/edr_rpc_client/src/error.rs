@@ -91,6 +91,28 @@ impl std::fmt::Display for MiddlewareError {
     }
 }
 
+/// Errors that occur when communicating with a remote node over a WebSocket.
+///
+/// Only the host of the URL is reported, as the URL could contain sensitive
+/// information such as API keys.
+#[derive(thiserror::Error, Debug)]
+pub enum WebSocketError {
+    /// The connection could not be established.
+    #[error("Failed to connect to host ({host}): {error}")]
+    Connect {
+        /// The host of the remote node
+        host: String,
+        /// The underlying error
+        error: Box<tokio_tungstenite::tungstenite::Error>,
+    },
+    /// The connection was closed before a response was received.
+    #[error("The WebSocket connection was closed before a response was received")]
+    ConnectionClosed,
+    /// No response was received within the configured request timeout.
+    #[error("The WebSocket request timed out")]
+    Timeout,
+}
+
 lazy_static! {
     static ref URL_REGEX: Regex = Regex::new(r"(http(s)?:\/\/.)?(www\.)?[-a-zA-Z0-9@:%._\+~#=]{2,256}\.[a-z]{2,6}\b([-a-zA-Z0-9@:%_\+.~#?&//=]*)").expect("Test checks panic");
 }
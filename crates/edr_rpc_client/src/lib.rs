@@ -10,5 +10,10 @@ mod client;
 pub mod error;
 /// Types specific to JSON-RPC
 pub mod jsonrpc;
+/// Types for configuring the transport to remote nodes.
+pub mod transport;
 
-pub use self::client::*;
+pub use self::{
+    client::*,
+    transport::{ErrorRate, FaultInjectionConfig, InvalidErrorRate, Subscription, TransportConfig},
+};
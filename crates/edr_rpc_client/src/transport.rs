@@ -0,0 +1,138 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Mutex, time::Instant};
+
+pub use self::{
+    faults::{ErrorRate, FaultInjectionConfig, InvalidErrorRate},
+    ws::Subscription,
+};
+
+pub(crate) mod faults;
+pub(crate) mod ws;
+
+// Default retry parameters for rate limited requests.
+const DEFAULT_EXPONENT_BASE: u32 = 2;
+const DEFAULT_MIN_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RETRY_INTERVAL: Duration = Duration::from_secs(32);
+const DEFAULT_MAX_RETRIES: u32 = 9;
+
+/// Policy for sending requests to remote JSON-RPC endpoints.
+///
+/// The defaults match the behaviour of the client before the policy was
+/// configurable: nine retries with exponential backoff between one and 32
+/// seconds, no request timeout, no client-side rate limit, no fallback
+/// endpoints, and no fault injection.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TransportConfig {
+    /// The base of the exponential backoff between retries.
+    pub backoff_exponent_base: u32,
+    /// The maximum number of retries of a transient failure per endpoint.
+    pub max_retries: u32,
+    /// The interval before the first retry, which grows exponentially for
+    /// subsequent retries. Random jitter is applied to every interval.
+    pub min_retry_interval: Duration,
+    /// The maximum interval between retries.
+    pub max_retry_interval: Duration,
+    /// The maximum duration of a single request, including reading the
+    /// response body. If `None`, requests never time out.
+    pub request_timeout: Option<Duration>,
+    /// The maximum number of requests per second sent to each endpoint. If
+    /// `None`, requests are not rate limited on the client side.
+    pub max_requests_per_second: Option<NonZeroU32>,
+    /// An ordered list of JSON-RPC URLs that are tried, in order, when a
+    /// request to the primary URL fails after exhausting its retries. Both
+    /// HTTP and WebSocket URLs are supported.
+    ///
+    /// Fallback endpoints are assumed to serve the same chain as the primary
+    /// URL.
+    pub fallback_urls: Vec<String>,
+    /// Faults that are injected into requests, to test how failures of remote
+    /// nodes are handled. By default, no faults are injected.
+    pub fault_injection: FaultInjectionConfig,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            backoff_exponent_base: DEFAULT_EXPONENT_BASE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            min_retry_interval: DEFAULT_MIN_RETRY_INTERVAL,
+            max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
+            request_timeout: None,
+            max_requests_per_second: None,
+            fallback_urls: Vec::new(),
+            fault_injection: FaultInjectionConfig::default(),
+        }
+    }
+}
+
+/// A rate limiter that spaces out requests evenly, such that no more than the
+/// configured number of requests per second is started.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a new instance with the provided maximum number of requests per
+    /// second.
+    pub fn new(max_requests_per_second: NonZeroU32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / max_requests_per_second.get(),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until a request is allowed to be sent.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+
+            slot
+        };
+
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(NonZeroU32::new(50).expect("non-zero"));
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+
+        // The first request is sent immediately, the remaining four are spaced
+        // out by 20 ms each.
+        assert!(start.elapsed() >= Duration::from_millis(80));
+    }
+
+    #[test]
+    fn deserialize_partial_config() -> anyhow::Result<()> {
+        let config: TransportConfig =
+            serde_json::from_str(r#"{ "maxRetries": 3, "fallbackUrls": ["http://localhost"] }"#)?;
+
+        assert_eq!(
+            config,
+            TransportConfig {
+                max_retries: 3,
+                fallback_urls: vec!["http://localhost".to_string()],
+                ..TransportConfig::default()
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use std::{sync::Mutex, time::Duration};
+
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+use reqwest::{Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use serde::{Deserialize, Serialize};
+use task_local_extensions::Extensions;
+
+/// Configuration for injecting faults into requests to remote JSON-RPC
+/// endpoints, to verify that tests and tooling degrade gracefully when remote
+/// nodes are unreliable.
+///
+/// Faults are only injected into requests to HTTP endpoints.
+///
+/// The default configuration doesn't inject any faults.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FaultInjectionConfig {
+    /// The probability that a request to an endpoint fails without being
+    /// sent. Injected failures result in a `503 Service Unavailable` response,
+    /// which is retried like any other transient failure.
+    pub error_rate: ErrorRate,
+    /// The latency that is added to every attempt of a request.
+    pub latency: Duration,
+    /// The maximum random latency that is added to every attempt of a
+    /// request, on top of `latency`.
+    pub latency_jitter: Duration,
+    /// The maximum number of blocks that the remote node lags behind the
+    /// actual head of the chain, to simulate nodes that serve stale data.
+    ///
+    /// The block numbers returned by `eth_blockNumber` lag behind by up to
+    /// this number of blocks, but never decrease. Blocks, transactions,
+    /// receipts, and logs after the last reported block number are not
+    /// returned.
+    pub stale_block_window: u64,
+    /// The seed of the random number generator that decides which faults to
+    /// inject. If `None`, a random seed is used.
+    pub seed: Option<u64>,
+}
+
+impl FaultInjectionConfig {
+    /// Whether any faults are injected.
+    pub fn is_active(&self) -> bool {
+        self.error_rate.0 > 0.0
+            || !self.latency.is_zero()
+            || !self.latency_jitter.is_zero()
+            || self.stale_block_window > 0
+    }
+}
+
+/// A probability between 0 and 1 that a request fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct ErrorRate(f64);
+
+// The error rate is never NaN, so equality is reflexive.
+impl Eq for ErrorRate {}
+
+impl From<ErrorRate> for f64 {
+    fn from(value: ErrorRate) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<f64> for ErrorRate {
+    type Error = InvalidErrorRate;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(InvalidErrorRate(value))
+        }
+    }
+}
+
+/// An error that occurs when an error rate is not between 0 and 1.
+#[derive(Debug, thiserror::Error)]
+#[error("The error rate must be between 0 and 1, but got {0}")]
+pub struct InvalidErrorRate(pub f64);
+
+/// HTTP middleware that injects faults into requests, according to a
+/// [`FaultInjectionConfig`].
+///
+/// The middleware is placed below the retry middleware, so injected failures
+/// and latencies apply to every attempt of a request.
+#[derive(Debug)]
+pub(crate) struct FaultInjectionMiddleware {
+    config: FaultInjectionConfig,
+    state: Mutex<FaultState>,
+}
+
+#[derive(Debug)]
+struct FaultState {
+    rng: StdRng,
+    /// The last block number that was reported by `eth_blockNumber`.
+    stale_head: Option<u64>,
+}
+
+impl FaultInjectionMiddleware {
+    /// Creates a new instance with the provided configuration.
+    pub fn new(config: FaultInjectionConfig) -> Self {
+        let rng = config
+            .seed
+            .map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64);
+
+        Self {
+            config,
+            state: Mutex::new(FaultState {
+                rng,
+                stale_head: None,
+            }),
+        }
+    }
+
+    /// Returns the latency to add to a request and whether it should fail.
+    fn sample_request_faults(&self) -> (Duration, bool) {
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+
+        let jitter = if self.config.latency_jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            self.config
+                .latency_jitter
+                .mul_f64(state.rng.random_range(0.0..=1.0))
+        };
+
+        let should_fail = state.rng.random_bool(self.config.error_rate.0);
+
+        (self.config.latency + jitter, should_fail)
+    }
+
+    /// Modifies the JSON-RPC response body of a request with the provided
+    /// method to be stale. Returns `None` if the response is unmodified.
+    fn make_stale(&self, method: &str, body: &[u8]) -> Option<Vec<u8>> {
+        if self.config.stale_block_window == 0 {
+            return None;
+        }
+
+        let mut response: serde_json::Value = serde_json::from_slice(body).ok()?;
+        let result = response.get_mut("result")?;
+
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+        if method == "eth_blockNumber" {
+            let latest_block_number = parse_quantity(result)?;
+            let lag = state.rng.random_range(0..=self.config.stale_block_window);
+
+            // The reported block number must not decrease, as the RPC client
+            // caches data of blocks that it considers safe based on previously
+            // reported block numbers. Omitting such data would poison the
+            // cache.
+            let stale_head = latest_block_number
+                .saturating_sub(lag)
+                .max(state.stale_head.unwrap_or(0));
+            state.stale_head = Some(stale_head);
+
+            *result = serde_json::Value::String(format!("{stale_head:#x}"));
+        } else {
+            let stale_head = state.stale_head?;
+            let is_after_stale_head = |value: &serde_json::Value| {
+                value
+                    .get("number")
+                    .or_else(|| value.get("blockNumber"))
+                    .and_then(parse_quantity)
+                    .is_some_and(|block_number| block_number > stale_head)
+            };
+
+            match result {
+                serde_json::Value::Array(items) => items.retain(|item| !is_after_stale_head(item)),
+                item if is_after_stale_head(item) => *item = serde_json::Value::Null,
+                _ => return None,
+            }
+        }
+
+        serde_json::to_vec(&response).ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for FaultInjectionMiddleware {
+    async fn handle(
+        &self,
+        request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let (latency, should_fail) = self.sample_request_faults();
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        if should_fail {
+            let response = http::Response::builder()
+                .status(StatusCode::SERVICE_UNAVAILABLE)
+                .body("Injected fault")
+                .expect("Status code is valid");
+
+            return Ok(Response::from(response));
+        }
+
+        let method = request_method(&request);
+        let response = next.run(request, extensions).await?;
+
+        let Some(method) = method else {
+            return Ok(response);
+        };
+
+        if self.config.stale_block_window == 0 || !response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        let body = self.make_stale(&method, &body).map_or(body, Into::into);
+
+        let mut builder = http::Response::builder().status(status);
+        if let Some(builder_headers) = builder.headers_mut() {
+            builder_headers.extend(headers);
+        }
+
+        let response = builder
+            .body(body)
+            .map_err(|error| reqwest_middleware::Error::Middleware(error.into()))?;
+
+        Ok(Response::from(response))
+    }
+}
+
+/// Returns the method of the JSON-RPC request, if it isn't a batch request.
+fn request_method(request: &Request) -> Option<String> {
+    let body = request.body()?.as_bytes()?;
+    let request: serde_json::Value = serde_json::from_slice(body).ok()?;
+
+    request.get("method")?.as_str().map(str::to_owned)
+}
+
+fn parse_quantity(value: &serde_json::Value) -> Option<u64> {
+    let hex = value.as_str()?.strip_prefix("0x")?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn make_stale(
+        middleware: &FaultInjectionMiddleware,
+        method: &str,
+        result: serde_json::Value,
+    ) -> serde_json::Value {
+        let body = serde_json::to_vec(&json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+            .expect("response should serialize");
+
+        middleware.make_stale(method, &body).map_or(result, |body| {
+            let response: serde_json::Value =
+                serde_json::from_slice(&body).expect("response should deserialize");
+
+            response["result"].clone()
+        })
+    }
+
+    #[test]
+    fn error_rate_must_be_between_zero_and_one() {
+        assert!(ErrorRate::try_from(0.5).is_ok());
+        assert!(ErrorRate::try_from(1.5).is_err());
+        assert!(ErrorRate::try_from(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn inactive_config_injects_no_faults() {
+        let middleware = FaultInjectionMiddleware::new(FaultInjectionConfig::default());
+
+        for _ in 0..100 {
+            assert_eq!(middleware.sample_request_faults(), (Duration::ZERO, false));
+        }
+        assert_eq!(
+            make_stale(&middleware, "eth_blockNumber", json!("0x64")),
+            json!("0x64")
+        );
+    }
+
+    #[test]
+    fn injects_faults_deterministically() -> anyhow::Result<()> {
+        let config = FaultInjectionConfig {
+            error_rate: ErrorRate::try_from(0.5)?,
+            stale_block_window: 10,
+            seed: Some(1),
+            ..FaultInjectionConfig::default()
+        };
+
+        let first = FaultInjectionMiddleware::new(config.clone());
+        let second = FaultInjectionMiddleware::new(config);
+
+        let mut failures = 0;
+        for _ in 0..100 {
+            let (_latency, should_fail) = first.sample_request_faults();
+            assert_eq!(should_fail, second.sample_request_faults().1);
+
+            if should_fail {
+                failures += 1;
+            }
+
+            let block_number = make_stale(&first, "eth_blockNumber", json!("0x64"));
+            assert_eq!(
+                block_number,
+                make_stale(&second, "eth_blockNumber", json!("0x64"))
+            );
+
+            let block_number = parse_quantity(&block_number).expect("should be a quantity");
+            assert!((90..=100).contains(&block_number));
+        }
+
+        assert!(failures > 0 && failures < 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stale_head_never_decreases() {
+        let middleware = FaultInjectionMiddleware::new(FaultInjectionConfig {
+            stale_block_window: 10,
+            seed: Some(1),
+            ..FaultInjectionConfig::default()
+        });
+
+        let mut previous_stale_head = 0;
+        for _ in 0..100 {
+            let stale_head = make_stale(&middleware, "eth_blockNumber", json!("0x64"));
+            let stale_head = parse_quantity(&stale_head).expect("should be a quantity");
+
+            assert!(stale_head >= previous_stale_head);
+            assert!((90..=100).contains(&stale_head));
+
+            previous_stale_head = stale_head;
+        }
+    }
+
+    #[test]
+    fn data_after_stale_head_is_omitted() {
+        let middleware = FaultInjectionMiddleware::new(FaultInjectionConfig {
+            stale_block_window: 10,
+            seed: Some(1),
+            ..FaultInjectionConfig::default()
+        });
+
+        let stale_head = make_stale(&middleware, "eth_blockNumber", json!("0x64"));
+        let stale_head = parse_quantity(&stale_head).expect("should be a quantity");
+
+        let block = |number: u64| json!({ "number": format!("{number:#x}") });
+        assert_eq!(
+            make_stale(&middleware, "eth_getBlockByHash", block(stale_head)),
+            block(stale_head)
+        );
+        assert_eq!(
+            make_stale(&middleware, "eth_getBlockByHash", block(stale_head + 1)),
+            serde_json::Value::Null
+        );
+
+        let log = |block_number: u64| json!({ "blockNumber": format!("{block_number:#x}") });
+        assert_eq!(
+            make_stale(
+                &middleware,
+                "eth_getLogs",
+                json!([log(stale_head), log(stale_head + 1)])
+            ),
+            json!([log(stale_head)])
+        );
+    }
+}
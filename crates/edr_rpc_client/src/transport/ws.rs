@@ -0,0 +1,548 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex as StdMutex},
+    time::Duration,
+};
+
+use futures::{SinkExt as _, StreamExt as _};
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest as _,
+        http::{HeaderName, HeaderValue},
+        Message,
+    },
+};
+
+use super::TransportConfig;
+use crate::{error::WebSocketError, jsonrpc};
+
+/// A subscription to notifications of a remote node.
+///
+/// The subscription ends when the WebSocket connection is closed, in which
+/// case the receiver of notifications yields `None`. Subscriptions are not
+/// re-established automatically after a reconnect.
+#[derive(Debug)]
+pub struct Subscription {
+    /// The subscription id returned by the remote node.
+    pub id: String,
+    /// The receiver of the `result` field of notifications.
+    pub notifications: mpsc::UnboundedReceiver<serde_json::Value>,
+}
+
+/// A JSON-RPC transport over a WebSocket, which multiplexes concurrent
+/// requests over a single connection by correlating the ids of requests and
+/// responses.
+///
+/// The connection is established lazily and re-established when it was
+/// closed.
+#[derive(Debug)]
+pub(crate) struct WebSocketTransport {
+    url: url::Url,
+    headers: tokio_tungstenite::tungstenite::http::HeaderMap,
+    backoff_exponent_base: u32,
+    max_retries: u32,
+    min_retry_interval: Duration,
+    max_retry_interval: Duration,
+    request_timeout: Option<Duration>,
+    connection: Mutex<Option<Arc<Connection>>>,
+}
+
+impl WebSocketTransport {
+    /// Creates a new instance for the provided URL. Reconnects and request
+    /// timeouts follow the provided transport configuration.
+    pub fn new(url: url::Url, headers: &HeaderMap, config: &TransportConfig) -> Self {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let name = HeaderName::from_bytes(name.as_str().as_bytes())
+                    .expect("Header names are valid across HTTP versions");
+                let value = HeaderValue::from_bytes(value.as_bytes())
+                    .expect("Header values are valid across HTTP versions");
+
+                (name, value)
+            })
+            .collect();
+
+        Self {
+            url,
+            headers,
+            backoff_exponent_base: config.backoff_exponent_base,
+            max_retries: config.max_retries,
+            min_retry_interval: config.min_retry_interval,
+            max_retry_interval: config.max_retry_interval,
+            request_timeout: config.request_timeout,
+            connection: Mutex::new(None),
+        }
+    }
+
+    /// Sends the request with the provided id and returns the text of the
+    /// corresponding response.
+    pub async fn send_request(
+        &self,
+        id: jsonrpc::Id,
+        request_body: String,
+    ) -> Result<String, WebSocketError> {
+        self.with_reconnect(|connection| {
+            let (sender, receiver) = oneshot::channel();
+            let result = connection.send(id.clone(), Pending::Request(sender), &request_body);
+
+            async move {
+                let _pending_guard = result?;
+                receiver
+                    .await
+                    .map_err(|_error| WebSocketError::ConnectionClosed)
+            }
+        })
+        .await
+    }
+
+    /// Sends the notification without awaiting a response, as notifications
+    /// don't have one.
+    pub async fn send_notification(&self, request_body: String) -> Result<(), WebSocketError> {
+        self.with_reconnect(|connection| std::future::ready(connection.send_text(&request_body)))
+            .await
+    }
+
+    /// Sends the `eth_subscribe` request with the provided id and returns the
+    /// text of the corresponding response, together with the receiver of
+    /// notifications.
+    ///
+    /// The receiver only yields notifications if the response was successful.
+    pub async fn subscribe(
+        &self,
+        id: jsonrpc::Id,
+        request_body: String,
+    ) -> Result<(String, mpsc::UnboundedReceiver<serde_json::Value>), WebSocketError> {
+        self.with_reconnect(|connection| {
+            let (sender, receiver) = oneshot::channel();
+            let (notification_sender, notification_receiver) = mpsc::unbounded_channel();
+            let result = connection.send(
+                id.clone(),
+                Pending::Subscription(sender, notification_sender),
+                &request_body,
+            );
+
+            async move {
+                let _pending_guard = result?;
+                let response = receiver
+                    .await
+                    .map_err(|_error| WebSocketError::ConnectionClosed)?;
+
+                Ok((response, notification_receiver))
+            }
+        })
+        .await
+    }
+
+    /// Runs the provided request on the current connection, reconnecting with
+    /// exponential backoff when the connection is closed or cannot be
+    /// established.
+    async fn with_reconnect<FutureT, SuccessT>(
+        &self,
+        request: impl Fn(&Connection) -> FutureT,
+    ) -> Result<SuccessT, WebSocketError>
+    where
+        FutureT: std::future::Future<Output = Result<SuccessT, WebSocketError>>,
+    {
+        let mut past_retries = 0;
+        loop {
+            let result = match self.connection().await {
+                Ok(connection) => {
+                    let response = request(&connection);
+                    if let Some(request_timeout) = self.request_timeout {
+                        tokio::time::timeout(request_timeout, response)
+                            .await
+                            .unwrap_or(Err(WebSocketError::Timeout))
+                    } else {
+                        response.await
+                    }
+                }
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Err(WebSocketError::ConnectionClosed | WebSocketError::Connect { .. })
+                    if past_retries < self.max_retries =>
+                {
+                    tokio::time::sleep(self.retry_interval(past_retries)).await;
+                    past_retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn retry_interval(&self, past_retries: u32) -> Duration {
+        self.backoff_exponent_base
+            .checked_pow(past_retries)
+            .and_then(|factor| self.min_retry_interval.checked_mul(factor))
+            .map_or(self.max_retry_interval, |interval| {
+                interval.min(self.max_retry_interval)
+            })
+    }
+
+    /// Returns the current connection, establishing a new one if there is no
+    /// open connection.
+    async fn connection(&self) -> Result<Arc<Connection>, WebSocketError> {
+        let mut connection = self.connection.lock().await;
+
+        if let Some(connection) = connection
+            .as_ref()
+            .filter(|connection| !connection.is_closed())
+        {
+            return Ok(connection.clone());
+        }
+
+        let new_connection = Arc::new(self.connect().await?);
+        *connection = Some(new_connection.clone());
+
+        Ok(new_connection)
+    }
+
+    async fn connect(&self) -> Result<Connection, WebSocketError> {
+        let connect_error = |error| WebSocketError::Connect {
+            host: self.url.host_str().unwrap_or("<unknown host>").to_string(),
+            error: Box::new(error),
+        };
+
+        let mut request = self
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(connect_error)?;
+        request.headers_mut().extend(self.headers.clone());
+
+        let (stream, _response) = connect_async(request).await.map_err(connect_error)?;
+
+        Ok(Connection::spawn(stream))
+    }
+}
+
+/// A request that is awaiting its response.
+#[derive(Debug)]
+enum Pending {
+    Request(oneshot::Sender<String>),
+    /// An `eth_subscribe` request, whose notifications are routed to the
+    /// provided sender once the response was received.
+    Subscription(
+        oneshot::Sender<String>,
+        mpsc::UnboundedSender<serde_json::Value>,
+    ),
+}
+
+type PendingRequests = Arc<StdMutex<HashMap<jsonrpc::Id, Pending>>>;
+type Subscriptions = Arc<StdMutex<HashMap<String, mpsc::UnboundedSender<serde_json::Value>>>>;
+
+/// An open WebSocket connection, whose messages are sent and received by a
+/// background task.
+#[derive(Debug)]
+struct Connection {
+    outgoing: mpsc::UnboundedSender<Message>,
+    pending: PendingRequests,
+}
+
+impl Connection {
+    fn spawn<StreamT>(stream: StreamT) -> Self
+    where
+        StreamT: futures::Sink<Message>
+            + futures::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+            + Send
+            + 'static,
+    {
+        let (outgoing, mut outgoing_receiver) = mpsc::unbounded_channel();
+        let pending = PendingRequests::default();
+        let subscriptions = Subscriptions::default();
+
+        let task_pending = pending.clone();
+        tokio::spawn(async move {
+            let (mut sink, mut stream) = stream.split();
+
+            loop {
+                tokio::select! {
+                    message = outgoing_receiver.recv() => {
+                        let Some(message) = message else {
+                            break;
+                        };
+
+                        if sink.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                    message = stream.next() => match message {
+                        Some(Ok(Message::Text(text))) => {
+                            dispatch(&task_pending, &subscriptions, text.as_str());
+                        }
+                        Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                        // Pings are answered by `tungstenite` itself.
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+
+            // Close the channel before dropping the pending requests, to ensure
+            // that no request can be added after the connection was closed.
+            outgoing_receiver.close();
+            task_pending.lock().expect("Lock is not poisoned").clear();
+            subscriptions.lock().expect("Lock is not poisoned").clear();
+        });
+
+        Self { outgoing, pending }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.outgoing.is_closed()
+    }
+
+    /// Sends the request with the provided id, registering it as pending
+    /// until the returned guard is dropped or its response is received.
+    fn send(
+        &self,
+        id: jsonrpc::Id,
+        pending: Pending,
+        request_body: &str,
+    ) -> Result<PendingGuard, WebSocketError> {
+        self.pending
+            .lock()
+            .expect("Lock is not poisoned")
+            .insert(id.clone(), pending);
+
+        // If sending fails, the guard is dropped immediately
+        let guard = PendingGuard {
+            pending: self.pending.clone(),
+            id,
+        };
+        self.send_text(request_body)?;
+
+        Ok(guard)
+    }
+
+    fn send_text(&self, text: &str) -> Result<(), WebSocketError> {
+        self.outgoing
+            .send(Message::text(text))
+            .map_err(|_error| WebSocketError::ConnectionClosed)
+    }
+}
+
+/// Removes a pending request when dropped, such that requests that time out or
+/// are cancelled don't leak their entries.
+#[derive(Debug)]
+struct PendingGuard {
+    pending: PendingRequests,
+    id: jsonrpc::Id,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        self.pending
+            .lock()
+            .expect("Lock is not poisoned")
+            .remove(&self.id);
+    }
+}
+
+/// Routes an incoming message to the pending request with the same id or to
+/// the subscription it belongs to.
+fn dispatch(pending: &PendingRequests, subscriptions: &Subscriptions, text: &str) {
+    #[derive(Deserialize)]
+    struct IncomingMessage {
+        id: Option<jsonrpc::Id>,
+        method: Option<String>,
+        params: Option<SubscriptionParams>,
+    }
+
+    #[derive(Deserialize)]
+    struct SubscriptionParams {
+        subscription: String,
+        result: serde_json::Value,
+    }
+
+    let Ok(message) = serde_json::from_str::<IncomingMessage>(text) else {
+        log::debug!("Ignoring unrecognized WebSocket message: '{text}'");
+        return;
+    };
+
+    if let Some(id) = message.id {
+        let Some(request) = pending.lock().expect("Lock is not poisoned").remove(&id) else {
+            log::debug!("Ignoring WebSocket response with unknown id: '{id:?}'");
+            return;
+        };
+
+        match request {
+            Pending::Request(sender) => {
+                let _ = sender.send(text.to_string());
+            }
+            Pending::Subscription(sender, notification_sender) => {
+                // Register the subscription before replying, such that no
+                // notifications are missed.
+                if let Ok(jsonrpc::ResponseData::Success {
+                    result: subscription_id,
+                }) = serde_json::from_str::<jsonrpc::Response<String>>(text)
+                    .map(|response| response.data)
+                {
+                    subscriptions
+                        .lock()
+                        .expect("Lock is not poisoned")
+                        .insert(subscription_id, notification_sender);
+                }
+
+                let _ = sender.send(text.to_string());
+            }
+        }
+    } else if message.method.as_deref() == Some("eth_subscription") {
+        let Some(SubscriptionParams {
+            subscription,
+            result,
+        }) = message.params
+        else {
+            return;
+        };
+
+        let mut subscriptions = subscriptions.lock().expect("Lock is not poisoned");
+        let receiver_dropped = subscriptions
+            .get(&subscription)
+            .is_some_and(|sender| sender.send(result).is_err());
+
+        if receiver_dropped {
+            subscriptions.remove(&subscription);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Starts a server that replies to every pair of requests in reverse
+    /// order, and returns its URL.
+    async fn start_reversing_server() -> anyhow::Result<url::Url> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("ws://{}", listener.local_addr()?).parse()?;
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.expect("Accepts connection");
+            let mut stream = tokio_tungstenite::accept_async(stream)
+                .await
+                .expect("Handshake succeeds");
+
+            let mut requests = Vec::new();
+            while let Some(Ok(Message::Text(text))) = stream.next().await {
+                let request: serde_json::Value =
+                    serde_json::from_str(text.as_str()).expect("Valid JSON");
+                requests.push(request);
+
+                if requests.len() == 2 {
+                    for request in requests.drain(..).rev() {
+                        let response = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "result": request["params"][0],
+                        });
+                        stream
+                            .send(Message::text(response.to_string()))
+                            .await
+                            .expect("Sends response");
+                    }
+                }
+            }
+        });
+
+        Ok(url)
+    }
+
+    fn request(id: u64) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "eth_echo",
+            "params": [id],
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn multiplexes_concurrent_requests() -> anyhow::Result<()> {
+        let url = start_reversing_server().await?;
+        let transport =
+            WebSocketTransport::new(url, &HeaderMap::new(), &TransportConfig::default());
+
+        let (first, second) = tokio::join!(
+            transport.send_request(jsonrpc::Id::Num(1), request(1)),
+            transport.send_request(jsonrpc::Id::Num(2), request(2)),
+        );
+
+        let first: jsonrpc::Response<u64> = serde_json::from_str(&first?)?;
+        let second: jsonrpc::Response<u64> = serde_json::from_str(&second?)?;
+
+        assert_eq!(first.id, jsonrpc::Id::Num(1));
+        assert_eq!(first.data.into_result()?, 1);
+        assert_eq!(second.id, jsonrpc::Id::Num(2));
+        assert_eq!(second.data.into_result()?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sends_notifications_without_awaiting_a_response() -> anyhow::Result<()> {
+        let url = start_reversing_server().await?;
+        let transport =
+            WebSocketTransport::new(url, &HeaderMap::new(), &TransportConfig::default());
+
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_echo",
+            "params": [0],
+        })
+        .to_string();
+        transport.send_notification(notification).await?;
+
+        // The server only replies once it received both messages
+        let response = transport
+            .send_request(jsonrpc::Id::Num(1), request(1))
+            .await?;
+        let response: jsonrpc::Response<u64> = serde_json::from_str(&response)?;
+
+        assert_eq!(response.id, jsonrpc::Id::Num(1));
+        assert_eq!(response.data.into_result()?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn removes_pending_requests_that_time_out() -> anyhow::Result<()> {
+        // The server only replies once it received two requests
+        let url = start_reversing_server().await?;
+        let transport = WebSocketTransport::new(
+            url,
+            &HeaderMap::new(),
+            &TransportConfig {
+                request_timeout: Some(Duration::from_millis(100)),
+                ..TransportConfig::default()
+            },
+        );
+
+        let result = transport
+            .send_request(jsonrpc::Id::Num(1), request(1))
+            .await;
+        assert!(matches!(result, Err(WebSocketError::Timeout)));
+
+        let connection = transport
+            .connection
+            .lock()
+            .await
+            .clone()
+            .expect("Connection was established");
+        assert!(connection
+            .pending
+            .lock()
+            .expect("Lock is not poisoned")
+            .is_empty());
+
+        Ok(())
+    }
+}
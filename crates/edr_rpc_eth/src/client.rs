@@ -11,7 +11,9 @@ use edr_eth::{
 use edr_primitives::{Address, Bytecode, Bytes, B256, KECCAK_EMPTY, U256, U64};
 use edr_receipt::log::FilterLog;
 use edr_rpc_client::RpcClient;
-pub use edr_rpc_client::{header, HeaderMap, RpcClientError};
+pub use edr_rpc_client::{
+    cache::CacheStats, header, HeaderMap, RpcClientError, Subscription, TransportConfig,
+};
 use edr_state_api::account::AccountInfo;
 use futures::StreamExt;
 use serde::{de::DeserializeOwned, Serialize};
@@ -55,7 +57,22 @@ impl<
         cache_dir: PathBuf,
         extra_headers: Option<HeaderMap>,
     ) -> Result<Self, RpcClientError> {
-        let inner = RpcClient::new(url, cache_dir, extra_headers)?;
+        Self::with_transport_config(url, cache_dir, extra_headers, TransportConfig::default())
+    }
+
+    /// Creates a new instance, given a remote node URL and a policy for
+    /// retries, rate limiting, timeouts, and fallback endpoints.
+    ///
+    /// The cache directory is the global EDR cache directory configured by the
+    /// user.
+    pub fn with_transport_config(
+        url: &str,
+        cache_dir: PathBuf,
+        extra_headers: Option<HeaderMap>,
+        transport_config: TransportConfig,
+    ) -> Result<Self, RpcClientError> {
+        let inner =
+            RpcClient::with_transport_config(url, cache_dir, extra_headers, transport_config)?;
         Ok(Self {
             inner,
             phantom: PhantomData,
@@ -68,6 +85,12 @@ impl<
         self.inner.block_number().await
     }
 
+    /// Returns the number of calls that were served from the cache and the
+    /// number of calls that were sent to the remote node.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.inner.cache_stats()
+    }
+
     /// Calls `eth_chainId` and returns the chain ID.
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
     pub async fn chain_id(&self) -> Result<u64, RpcClientError> {
@@ -108,6 +131,14 @@ impl<
         })
     }
 
+    /// Subscribes to notifications of new block headers of the remote node.
+    ///
+    /// Only supported when the remote node URL is a WebSocket URL.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    pub async fn subscribe_new_heads(&self) -> Result<Subscription, RpcClientError> {
+        self.inner.subscribe(["newHeads"]).await
+    }
+
     /// Submits three concurrent RPC method invocations in order to obtain
     /// the set of data contained in [`AccountInfo`].
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
@@ -5,14 +5,22 @@
 /// into logs.
 pub mod old;
 
-use std::{num::NonZeroU64, path::PathBuf, time::SystemTime};
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::SystemTime,
+};
 
 use chrono::{DateTime, Utc};
 use edr_block_header::BlobGas;
 use edr_chain_config::ChainOverride;
 use edr_napi_core::provider::Config as ProviderConfig;
 use edr_primitives::{Address, ChainId, HashMap, B256};
-use edr_provider::{AccountOverride, ForkConfig, MiningConfig};
+use edr_provider::{
+    time::TimeMode, AccountOverride, ForkConfig, GasSponsorConfig, MethodPermissionsConfig,
+    MiningConfig, StatePruningConfig,
+};
 use edr_test_utils::secret_key::{secret_key_from_str, secret_key_to_str};
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +32,33 @@ pub struct ScenarioConfig {
     pub provider_config: ScenarioProviderConfig,
 }
 
+/// The first line of a request journal, which records the configuration of
+/// the provider and the time at which it was created.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalHeader {
+    /// The time at which the provider was created, in milliseconds since the
+    /// Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub config: ScenarioConfig,
+}
+
+/// A request that was handled by the provider and its response, as recorded
+/// in a request journal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    /// The time at which the request was received, in milliseconds since the
+    /// Unix epoch.
+    pub timestamp: u64,
+    /// The JSON-RPC request, as it was received.
+    pub request: String,
+    /// The JSON-RPC response data, i.e. an object with either a `result` or an
+    /// `error` field.
+    pub response: serde_json::Value,
+}
+
 /// Custom configuration for the provider that supports serde as we don't want a
 /// serde implementation for secret keys.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -31,28 +66,70 @@ pub struct ScenarioConfig {
 pub struct ScenarioProviderConfig {
     pub allow_blocks_with_same_timestamp: bool,
     pub allow_unlimited_contract_size: bool,
+    #[serde(default)]
+    pub allow_unlimited_init_code_size: bool,
+    #[serde(default)]
+    pub auto_fund: bool,
+    #[serde(default)]
+    pub auto_impersonate: bool,
     /// Whether to return an `Err` when `eth_call` fails
     pub bail_on_call_failure: bool,
     /// Whether to return an `Err` when a `eth_sendTransaction` fails
     pub bail_on_transaction_failure: bool,
     pub block_gas_limit: NonZeroU64,
+    #[serde(default)]
+    pub block_gas_limit_schedule: BTreeMap<u64, NonZeroU64>,
     pub chain_id: ChainId,
     pub chain_overrides: HashMap<ChainId, ChainOverride<String>>,
     pub coinbase: Address,
+    #[serde(default)]
+    pub deploy_deterministic_deployment_proxy: bool,
+    #[serde(default)]
+    pub disable_balance_check: bool,
+    #[serde(default)]
+    pub disable_base_fee_adjustment: bool,
+    #[serde(default)]
+    pub disable_base_fee_check: bool,
+    #[serde(default)]
+    pub disable_priority_fee_check: bool,
     pub fork: Option<ForkConfig<String>>,
+    #[serde(default)]
+    pub gas_sponsor: Option<GasSponsorConfig>,
     pub genesis_state: HashMap<Address, AccountOverride>,
     pub hardfork: String,
+    #[serde(default)]
+    pub hardfork_activations: BTreeMap<u64, String>,
+    #[serde(default)]
+    pub index_address_transactions: bool,
+    #[serde(default)]
+    pub index_token_transfers: bool,
     #[serde(with = "alloy_serde::quantity::opt")]
     pub initial_base_fee_per_gas: Option<u128>,
     pub initial_blob_gas: Option<BlobGas>,
     /// The initial date of the blockchain, in ISO 8601 format.
     pub initial_date: Option<DateTime<Utc>>,
     pub initial_parent_beacon_block_root: Option<B256>,
+    #[serde(default)]
+    pub max_cached_states: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub max_logs_block_range: Option<u64>,
+    #[serde(default)]
+    pub max_logs_results: Option<u64>,
+    #[serde(default)]
+    pub method_permissions: MethodPermissionsConfig,
     #[serde(with = "alloy_serde::quantity")]
     pub min_gas_price: u128,
     pub mining: MiningConfig,
     pub network_id: u64,
     pub owned_accounts: Vec<SerializableSecretKey>,
+    #[serde(default)]
+    pub prev_randao_seed: Option<String>,
+    #[serde(default)]
+    pub record_state_diffs: bool,
+    #[serde(default)]
+    pub state_pruning: StatePruningConfig,
+    #[serde(default)]
+    pub time_mode: TimeMode,
     /// Transaction gas cap, introduced in [EIP-7825].
     ///
     /// When not set, will default to value defined by the used hardfork
@@ -76,19 +153,41 @@ impl From<ScenarioProviderConfig> for ProviderConfig {
         Self {
             allow_blocks_with_same_timestamp: value.allow_blocks_with_same_timestamp,
             allow_unlimited_contract_size: value.allow_unlimited_contract_size,
+            allow_unlimited_init_code_size: value.allow_unlimited_init_code_size,
+            auto_fund: value.auto_fund,
+            auto_impersonate: value.auto_impersonate,
             bail_on_call_failure: value.bail_on_call_failure,
             bail_on_transaction_failure: value.bail_on_transaction_failure,
             base_fee_params: None,
             block_gas_limit: value.block_gas_limit,
+            block_gas_limit_schedule: value.block_gas_limit_schedule,
             chain_id: value.chain_id,
+            // Like cache directories, custom checkpoint directories aren't supported for replaying
+            // scenarios.
+            checkpoint_dir: PathBuf::from(edr_defaults::CHECKPOINT_DIR),
             coinbase: value.coinbase,
+            deploy_deterministic_deployment_proxy: value.deploy_deterministic_deployment_proxy,
+            disable_balance_check: value.disable_balance_check,
+            disable_base_fee_adjustment: value.disable_base_fee_adjustment,
+            disable_base_fee_check: value.disable_base_fee_check,
+            disable_priority_fee_check: value.disable_priority_fee_check,
             fork,
+            gas_sponsor: value.gas_sponsor,
             genesis_state: value.genesis_state,
+            // Genesis JSONs are not supported in scenarios
+            geth_genesis: None,
             hardfork: value.hardfork,
+            hardfork_activations: value.hardfork_activations,
+            index_address_transactions: value.index_address_transactions,
+            index_token_transfers: value.index_token_transfers,
             initial_base_fee_per_gas: value.initial_base_fee_per_gas,
             initial_blob_gas: value.initial_blob_gas,
             initial_date: value.initial_date.map(SystemTime::from),
             initial_parent_beacon_block_root: value.initial_parent_beacon_block_root,
+            max_cached_states: value.max_cached_states,
+            max_logs_block_range: value.max_logs_block_range,
+            max_logs_results: value.max_logs_results,
+            method_permissions: value.method_permissions,
             min_gas_price: value.min_gas_price,
             mining: value.mining,
             network_id: value.network_id,
@@ -100,6 +199,10 @@ impl From<ScenarioProviderConfig> for ProviderConfig {
                 .collect::<Vec<_>>(),
             // Overriding precompiles is not supported in scenarios
             precompile_overrides: HashMap::default(),
+            prev_randao_seed: value.prev_randao_seed,
+            record_state_diffs: value.record_state_diffs,
+            state_pruning: value.state_pruning,
+            time_mode: value.time_mode,
             transaction_gas_cap: value.transaction_gas_cap,
         }
     }
@@ -115,24 +218,47 @@ impl TryFrom<ProviderConfig> for ScenarioProviderConfig {
             ));
         }
 
+        if value.geth_genesis.is_some() {
+            return Err(anyhow::anyhow!(
+                "Genesis JSONs are not supported in scenarios"
+            ));
+        }
+
         Ok(Self {
             allow_blocks_with_same_timestamp: value.allow_blocks_with_same_timestamp,
             allow_unlimited_contract_size: value.allow_unlimited_contract_size,
+            allow_unlimited_init_code_size: value.allow_unlimited_init_code_size,
+            auto_fund: value.auto_fund,
+            auto_impersonate: value.auto_impersonate,
             bail_on_call_failure: value.bail_on_call_failure,
             bail_on_transaction_failure: value.bail_on_transaction_failure,
             block_gas_limit: value.block_gas_limit,
+            block_gas_limit_schedule: value.block_gas_limit_schedule,
             chain_id: value.chain_id,
             // Chain overrides are not supported for newly recorded scenarios. We merely maintain it
             // for backwards compatibility for Hardhat 2.
             chain_overrides: HashMap::default(),
             coinbase: value.coinbase,
+            deploy_deterministic_deployment_proxy: value.deploy_deterministic_deployment_proxy,
+            disable_balance_check: value.disable_balance_check,
+            disable_base_fee_adjustment: value.disable_base_fee_adjustment,
+            disable_base_fee_check: value.disable_base_fee_check,
+            disable_priority_fee_check: value.disable_priority_fee_check,
             fork: value.fork,
+            gas_sponsor: value.gas_sponsor,
             genesis_state: value.genesis_state,
             hardfork: value.hardfork,
+            hardfork_activations: value.hardfork_activations,
+            index_address_transactions: value.index_address_transactions,
+            index_token_transfers: value.index_token_transfers,
             initial_base_fee_per_gas: value.initial_base_fee_per_gas,
             initial_blob_gas: value.initial_blob_gas,
             initial_date: value.initial_date.map(DateTime::from),
             initial_parent_beacon_block_root: value.initial_parent_beacon_block_root,
+            max_cached_states: value.max_cached_states,
+            max_logs_block_range: value.max_logs_block_range,
+            max_logs_results: value.max_logs_results,
+            method_permissions: value.method_permissions,
             min_gas_price: value.min_gas_price,
             mining: value.mining,
             network_id: value.network_id,
@@ -141,6 +267,10 @@ impl TryFrom<ProviderConfig> for ScenarioProviderConfig {
                 .into_iter()
                 .map(SerializableSecretKey::from)
                 .collect(),
+            prev_randao_seed: value.prev_randao_seed,
+            record_state_diffs: value.record_state_diffs,
+            state_pruning: value.state_pruning,
+            time_mode: value.time_mode,
             transaction_gas_cap: value.transaction_gas_cap,
         })
     }
@@ -7,6 +7,7 @@
 //! Ethereum signature types
 
 mod fakeable;
+mod mnemonic;
 mod recovery_id;
 pub mod utils;
 mod y_parity;
@@ -18,6 +19,7 @@ use sha3::{Digest, Keccak256};
 
 pub use self::{
     fakeable::FakeableSignature,
+    mnemonic::{secret_keys_from_mnemonic, MnemonicError, DEFAULT_DERIVATION_PATH},
     recovery_id::SignatureWithRecoveryId,
     y_parity::{Args as SignatureWithYParityArgs, SignatureWithYParity},
 };
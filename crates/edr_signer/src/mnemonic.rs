@@ -0,0 +1,139 @@
+use alloy_signer_local::{coins_bip39::English, MnemonicBuilder};
+use k256::SecretKey;
+
+/// The default BIP-32 derivation path of accounts, excluding the account
+/// index.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0";
+
+/// An error that occurs when deriving accounts from a mnemonic.
+///
+/// The errors are deliberately opaque, to avoid leaking (parts of) the
+/// mnemonic to logs.
+#[derive(Debug, thiserror::Error)]
+pub enum MnemonicError {
+    /// The derivation path is not a valid BIP-32 path.
+    #[error("Invalid derivation path: {path}")]
+    InvalidDerivationPath { path: String },
+    /// The mnemonic is not a valid BIP-39 mnemonic.
+    #[error("Invalid mnemonic")]
+    InvalidMnemonic,
+}
+
+/// Derives the secret keys of `count` accounts from the provided mnemonic,
+/// starting at `initial_index`. Each account's derivation path is the
+/// provided path, followed by its index.
+pub fn secret_keys_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+    path: &str,
+    initial_index: u32,
+    count: u32,
+) -> Result<Vec<SecretKey>, MnemonicError> {
+    let path = path.trim_end_matches('/');
+
+    (initial_index..initial_index.saturating_add(count))
+        .map(|index| {
+            let derivation_path = format!("{path}/{index}");
+
+            let signer = MnemonicBuilder::<English>::default()
+                .phrase(mnemonic)
+                .password(passphrase)
+                .derivation_path(&derivation_path)
+                .map_err(|_error| MnemonicError::InvalidDerivationPath {
+                    path: derivation_path,
+                })?
+                .build()
+                .map_err(|_error| MnemonicError::InvalidMnemonic)?;
+
+            Ok(SecretKey::from(*signer.credential().as_nonzero_scalar()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_primitives::{address, Address};
+
+    use super::*;
+    use crate::public_key_to_address;
+
+    const MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    fn addresses(secret_keys: Vec<SecretKey>) -> Vec<Address> {
+        secret_keys
+            .into_iter()
+            .map(|secret_key| public_key_to_address(secret_key.public_key()))
+            .collect()
+    }
+
+    #[test]
+    fn default_derivation_path() -> anyhow::Result<()> {
+        let secret_keys = secret_keys_from_mnemonic(MNEMONIC, "", DEFAULT_DERIVATION_PATH, 0, 2)?;
+
+        assert_eq!(
+            addresses(secret_keys),
+            vec![
+                address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+                address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn initial_index() -> anyhow::Result<()> {
+        let secret_keys = secret_keys_from_mnemonic(MNEMONIC, "", DEFAULT_DERIVATION_PATH, 1, 1)?;
+
+        assert_eq!(
+            addresses(secret_keys),
+            vec![address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn passphrase_and_path_change_accounts() -> anyhow::Result<()> {
+        let default_accounts = addresses(secret_keys_from_mnemonic(
+            MNEMONIC,
+            "",
+            DEFAULT_DERIVATION_PATH,
+            0,
+            1,
+        )?);
+
+        let with_passphrase = addresses(secret_keys_from_mnemonic(
+            MNEMONIC,
+            "passphrase",
+            DEFAULT_DERIVATION_PATH,
+            0,
+            1,
+        )?);
+        assert_ne!(default_accounts, with_passphrase);
+
+        let with_path = addresses(secret_keys_from_mnemonic(
+            MNEMONIC,
+            "",
+            "m/44'/60'/1'/0",
+            0,
+            1,
+        )?);
+        assert_ne!(default_accounts, with_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            secret_keys_from_mnemonic("not a mnemonic", "", DEFAULT_DERIVATION_PATH, 0, 1),
+            Err(MnemonicError::InvalidMnemonic)
+        ));
+
+        assert!(matches!(
+            secret_keys_from_mnemonic(MNEMONIC, "", "m/44'/invalid", 0, 1),
+            Err(MnemonicError::InvalidDerivationPath { .. })
+        ));
+    }
+}
@@ -297,6 +297,11 @@ impl CustomError {
         })
     }
 
+    /// Returns the JSON ABI definition of the custom error.
+    pub fn definition(&self) -> &alloy_json_abi::Error {
+        &self.def
+    }
+
     /// Decodes the error data (*with* selector).
     pub fn decode_error_data(
         &self,
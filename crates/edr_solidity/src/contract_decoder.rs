@@ -18,6 +18,7 @@ use crate::{
     build_model::{ContractFunctionType, ContractMetadata},
     compiler::create_models_and_decode_bytecodes,
     contracts_identifier::ContractsIdentifier,
+    custom_error_registry::CustomErrorRegistry,
     nested_trace::{NestedTrace, NestedTraceStep},
 };
 
@@ -55,25 +56,53 @@ where
 #[derive(Debug, Default)]
 pub struct ContractDecoder {
     contracts_identifier: RwLock<ContractsIdentifier>,
+    custom_errors: RwLock<CustomErrorRegistry>,
 }
 
 impl ContractDecoder {
     /// Creates a new [`ContractDecoder`].
     pub fn new(config: &BuildInfoConfig) -> Result<Self, ContractDecoderError> {
-        let contracts_identifier = initialize_contracts_identifier(config)
+        let mut custom_errors = CustomErrorRegistry::default();
+        let contracts_identifier = initialize_contracts_identifier(config, &mut custom_errors)
             .map_err(|err| ContractDecoderError::Initialization(err.to_string()))?;
         Ok(Self {
             contracts_identifier: RwLock::new(contracts_identifier),
+            custom_errors: RwLock::new(custom_errors),
         })
     }
 
     /// Adds contract metadata to the decoder.
     pub fn add_contract_metadata(&self, bytecode: ContractMetadata) {
+        self.custom_errors
+            .write()
+            .register_contract(&bytecode.contract.read());
+
         self.contracts_identifier
             .write()
             .add_bytecode(Arc::new(bytecode));
     }
 
+    /// Decodes the return data of a revert into a human-readable custom
+    /// error, e.g. `OwnableUnauthorizedAccount("0x...")`, if the custom error
+    /// is known.
+    ///
+    /// Known custom errors are those of added contracts and a built-in list
+    /// of commonly used custom errors.
+    pub fn decode_custom_error(&self, return_data: &[u8]) -> Option<String> {
+        self.custom_errors.read().decode(return_data)
+    }
+
+    /// Returns the contract metadata of the provided code, if it's recognized.
+    pub fn get_contract_metadata(
+        &self,
+        code: &Bytes,
+        is_create: bool,
+    ) -> Option<Arc<ContractMetadata>> {
+        self.contracts_identifier
+            .write()
+            .get_bytecode_for_call(code.as_ref(), is_create)
+    }
+
     /// Returns the contract and function names for the provided calldata.
     pub fn get_contract_and_function_names_for_call(
         &self,
@@ -232,6 +261,11 @@ impl<HaltReasonT: HaltReasonTrait> NestedTraceDecoder<HaltReasonT> for ContractD
                     .collect::<Result<Vec<_>, _>>()?;
 
                 call.contract_meta = contract_meta;
+                call.custom_error = if call.exit.is_error() {
+                    self.decode_custom_error(&call.return_data)
+                } else {
+                    None
+                };
                 call.steps = steps;
 
                 Ok(NestedTrace::Call(call))
@@ -271,6 +305,11 @@ impl<HaltReasonT: HaltReasonTrait> NestedTraceDecoder<HaltReasonT> for ContractD
                     .collect::<Result<Vec<_>, _>>()?;
 
                 create.contract_meta = contract_meta;
+                create.custom_error = if create.exit.is_error() {
+                    self.decode_custom_error(&create.return_data)
+                } else {
+                    None
+                };
                 create.steps = steps;
 
                 Ok(NestedTrace::Create(create))
@@ -297,6 +336,7 @@ pub struct ContractIdentifierAndFunctionSignature {
 
 fn initialize_contracts_identifier(
     config: &BuildInfoConfig,
+    custom_errors: &mut CustomErrorRegistry,
 ) -> anyhow::Result<ContractsIdentifier> {
     let mut contracts_identifier = ContractsIdentifier::default();
 
@@ -314,6 +354,7 @@ fn initialize_contracts_identifier(
                 continue;
             }
 
+            custom_errors.register_contract(&bytecode.contract.read());
             contracts_identifier.add_bytecode(Arc::new(bytecode));
         }
     }
@@ -0,0 +1,188 @@
+//! Registry of custom errors, used to render human-readable messages for
+//! reverts with custom errors, even if the reverting contract isn't
+//! recognized.
+
+use std::collections::HashMap;
+
+use alloy_dyn_abi::ErrorExt as _;
+use alloy_json_abi::Error;
+
+use crate::{build_model::Contract, error_inferrer::format_dyn_sol_value};
+
+/// Signatures of commonly used custom errors, e.g. those of OpenZeppelin
+/// Contracts v5.
+const BUILT_IN_ERRORS: &[&str] = &[
+    // ERC-20
+    "error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed)",
+    "error ERC20InvalidSender(address sender)",
+    "error ERC20InvalidReceiver(address receiver)",
+    "error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed)",
+    "error ERC20InvalidApprover(address approver)",
+    "error ERC20InvalidSpender(address spender)",
+    "error ERC2612ExpiredSignature(uint256 deadline)",
+    "error ERC2612InvalidSigner(address signer, address owner)",
+    // ERC-721
+    "error ERC721InvalidOwner(address owner)",
+    "error ERC721NonexistentToken(uint256 tokenId)",
+    "error ERC721IncorrectOwner(address sender, uint256 tokenId, address owner)",
+    "error ERC721InvalidSender(address sender)",
+    "error ERC721InvalidReceiver(address receiver)",
+    "error ERC721InsufficientApproval(address operator, uint256 tokenId)",
+    "error ERC721InvalidApprover(address approver)",
+    "error ERC721InvalidOperator(address operator)",
+    // ERC-1155
+    "error ERC1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 tokenId)",
+    "error ERC1155InvalidSender(address sender)",
+    "error ERC1155InvalidReceiver(address receiver)",
+    "error ERC1155MissingApprovalForAll(address operator, address owner)",
+    "error ERC1155InvalidApprover(address approver)",
+    "error ERC1155InvalidOperator(address operator)",
+    "error ERC1155InvalidArrayLength(uint256 idsLength, uint256 valuesLength)",
+    // Access control
+    "error OwnableUnauthorizedAccount(address account)",
+    "error OwnableInvalidOwner(address owner)",
+    "error AccessControlUnauthorizedAccount(address account, bytes32 neededRole)",
+    "error AccessControlBadConfirmation()",
+    // Security
+    "error ReentrancyGuardReentrantCall()",
+    "error EnforcedPause()",
+    "error ExpectedPause()",
+    "error InvalidInitialization()",
+    "error NotInitializing()",
+    // Utilities
+    "error AddressEmptyCode(address target)",
+    "error FailedCall()",
+    "error FailedInnerCall()",
+    "error InsufficientBalance(uint256 balance, uint256 needed)",
+    "error SafeERC20FailedOperation(address token)",
+    "error SafeERC20FailedDecreaseAllowance(address spender, uint256 currentAllowance, uint256 requestedDecrease)",
+    "error ECDSAInvalidSignature()",
+    "error ECDSAInvalidSignatureLength(uint256 length)",
+    "error ECDSAInvalidSignatureS(bytes32 s)",
+    "error SafeCastOverflowedUintDowncast(uint8 bits, uint256 value)",
+    "error SafeCastOverflowedIntDowncast(uint8 bits, int256 value)",
+    "error SafeCastOverflowedUintToInt(uint256 value)",
+    "error SafeCastOverflowedIntToUint(int256 value)",
+];
+
+/// Maps the 4-byte selectors of custom errors to their definitions.
+#[derive(Clone, Debug)]
+pub struct CustomErrorRegistry {
+    errors: HashMap<[u8; 4], Error>,
+}
+
+impl CustomErrorRegistry {
+    /// Creates a registry without any custom errors.
+    pub fn empty() -> Self {
+        Self {
+            errors: HashMap::new(),
+        }
+    }
+
+    /// Registers a custom error, replacing any previously registered error
+    /// with the same selector.
+    pub fn register(&mut self, error: Error) {
+        self.errors.insert(error.selector().0, error);
+    }
+
+    /// Registers the custom errors of a contract.
+    pub fn register_contract(&mut self, contract: &Contract) {
+        for custom_error in &contract.custom_errors {
+            self.register(custom_error.definition().clone());
+        }
+    }
+
+    /// Returns the custom error with the provided selector, if registered.
+    pub fn get(&self, selector: &[u8; 4]) -> Option<&Error> {
+        self.errors.get(selector)
+    }
+
+    /// Decodes the return data of a revert into a human-readable message,
+    /// e.g. `ERC20InsufficientBalance("0x...", 0, 100)`, if it contains a
+    /// registered custom error.
+    pub fn decode(&self, return_data: &[u8]) -> Option<String> {
+        let selector: [u8; 4] = return_data.get(..4)?.try_into().ok()?;
+        let error = self.errors.get(&selector)?;
+
+        let decoded = error.decode_error(return_data).ok()?;
+        let params = decoded
+            .body
+            .iter()
+            .map(format_dyn_sol_value)
+            .collect::<Vec<_>>();
+
+        Some(format!("{}({})", error.name, params.join(", ")))
+    }
+}
+
+impl Default for CustomErrorRegistry {
+    /// Creates a registry with the built-in custom errors.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for signature in BUILT_IN_ERRORS {
+            let error = Error::parse(signature).expect("built-in error signatures are valid");
+            registry.register(error);
+        }
+
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::{sol, SolError as _};
+    use edr_primitives::{address, U256};
+
+    use super::*;
+
+    sol! {
+        error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+        error Unknown();
+    }
+
+    #[test]
+    fn decodes_built_in_error() {
+        let registry = CustomErrorRegistry::default();
+
+        let return_data = ERC20InsufficientBalance {
+            sender: address!("0x1111111111111111111111111111111111111111"),
+            balance: U256::from(1),
+            needed: U256::from(100),
+        }
+        .abi_encode();
+
+        assert_eq!(
+            registry.decode(&return_data).as_deref(),
+            Some(
+                "ERC20InsufficientBalance(\"0x1111111111111111111111111111111111111111\", 1, 100)"
+            )
+        );
+    }
+
+    #[test]
+    fn decodes_registered_error() {
+        let mut registry = CustomErrorRegistry::empty();
+
+        let return_data = Unknown {}.abi_encode();
+        assert_eq!(registry.decode(&return_data), None);
+
+        registry.register(Error::parse("error Unknown()").expect("valid signature"));
+        assert_eq!(registry.decode(&return_data).as_deref(), Some("Unknown()"));
+    }
+
+    #[test]
+    fn ignores_malformed_return_data() {
+        let registry = CustomErrorRegistry::default();
+
+        let mut return_data = ERC20InsufficientBalance {
+            sender: address!("0x1111111111111111111111111111111111111111"),
+            balance: U256::from(1),
+            needed: U256::from(100),
+        }
+        .abi_encode();
+        return_data.truncate(36);
+
+        assert_eq!(registry.decode(&return_data), None);
+        assert_eq!(registry.decode(&[0x01, 0x02]), None);
+    }
+}
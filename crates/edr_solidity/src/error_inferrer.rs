@@ -426,9 +426,15 @@ fn check_custom_errors<HaltReasonT: HaltReasonTrait>(
         return Ok(Heuristic::Miss(stacktrace));
     }
 
-    let raw_return_data = hex::encode(return_data.value);
-    let mut error_message =
-        format!("reverted with an unrecognized custom error (return data: 0x{raw_return_data})",);
+    // Fall back to the contract decoder's registry, which also contains custom
+    // errors that aren't defined by the called contract
+    let mut error_message = trace.custom_error().map_or_else(
+        || {
+            let raw_return_data = hex::encode(return_data.value);
+            format!("reverted with an unrecognized custom error (return data: 0x{raw_return_data})")
+        },
+        |custom_error| format!("reverted with custom error '{custom_error}'"),
+    );
 
     for custom_error in &contract.custom_errors {
         if return_data.matches_selector(custom_error.selector) {
@@ -1007,7 +1013,7 @@ fn fix_initial_modifier<HaltReasonT: HaltReasonTrait>(
 }
 
 // Rewrite of `AbiHelpers.formatValues` from Hardhat
-fn format_dyn_sol_value(val: &DynSolValue) -> String {
+pub(crate) fn format_dyn_sol_value(val: &DynSolValue) -> String {
     match val {
         // print nested values as [value1, value2, ...]
         DynSolValue::Array(items)
@@ -0,0 +1,458 @@
+//! Creates the source model of contracts that weren't compiled by Hardhat, e.g.
+//! from Foundry artifacts or from metadata fetched from Etherscan.
+//!
+//! Such artifacts only provide the ABI and bytecode of a contract, so the
+//! created model lacks source locations. It suffices to resolve contract and
+//! function names and to decode arguments and custom errors.
+
+use std::{collections::HashMap, sync::Arc};
+
+use alloy_json_abi::{JsonAbi, Param, StateMutability};
+use edr_primitives::{bytecode::opcode::OpCode, Address, Bytes};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    artifacts::{ContractAbiEntry, ImmutableReference, LinkReference},
+    build_model::{
+        BuildModelSources, Contract, ContractFunction, ContractFunctionType,
+        ContractFunctionVisibility, ContractKind, ContractMetadata, ContractMetadataError,
+        CustomError, Instruction, JumpType, SourceFile, SourceLocation,
+    },
+};
+
+/// The file ID of the placeholder source file of an external artifact.
+const SOURCE_FILE_ID: u32 = 0;
+
+/// The positions of library addresses in a bytecode, by source name and
+/// library name.
+pub type LinkReferences = HashMap<String, HashMap<String, Vec<LinkReference>>>;
+
+/// The artifact of a contract that wasn't compiled by Hardhat.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalArtifact {
+    /// The name of the contract.
+    pub contract_name: String,
+    /// The name of the contract's source file. Defaults to the contract name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    /// The JSON ABI of the contract.
+    pub abi: JsonAbi,
+    /// The deployment bytecode of the contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode: Option<Bytes>,
+    /// The runtime bytecode of the contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_bytecode: Option<Bytes>,
+    /// The positions of library addresses in the deployment bytecode, by
+    /// source name and library name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub link_references: LinkReferences,
+    /// The positions of library addresses in the runtime bytecode, by source
+    /// name and library name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub deployed_link_references: LinkReferences,
+    /// The positions of immutable values in the runtime bytecode, by AST ID.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub immutable_references: HashMap<String, Vec<ImmutableReference>>,
+    /// The version of the compiler that produced the bytecode, e.g.
+    /// `0.8.24+commit.e11b9ed9`. A leading `v` is ignored.
+    pub compiler_version: String,
+}
+
+/// An error that occurred while creating the source model of an
+/// [`ExternalArtifact`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExternalArtifactError {
+    /// The compiler version isn't a valid semantic version.
+    #[error("Invalid compiler version '{version}': {source}")]
+    InvalidCompilerVersion {
+        /// The invalid compiler version.
+        version: String,
+        /// The parsing error.
+        source: semver::Error,
+    },
+    /// The artifact has neither a deployment nor a runtime bytecode.
+    #[error("The artifact of contract '{contract_name}' has no bytecode")]
+    MissingBytecode {
+        /// The name of the contract.
+        contract_name: String,
+    },
+    /// A parameter of the ABI couldn't be converted to JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The source model couldn't be created.
+    #[error(transparent)]
+    ContractMetadata(#[from] ContractMetadataError),
+    /// A custom error of the ABI is invalid.
+    #[error("Invalid custom error: {0}")]
+    CustomError(Box<str>),
+}
+
+/// Creates the [`ContractMetadata`] of the deployment and runtime bytecodes
+/// of an external artifact.
+pub fn create_contract_metadata_from_artifact(
+    artifact: ExternalArtifact,
+) -> Result<Vec<ContractMetadata>, ExternalArtifactError> {
+    let ExternalArtifact {
+        contract_name,
+        source_name,
+        abi,
+        bytecode,
+        deployed_bytecode,
+        link_references,
+        deployed_link_references,
+        immutable_references,
+        compiler_version,
+    } = artifact;
+
+    if bytecode.is_none() && deployed_bytecode.is_none() {
+        return Err(ExternalArtifactError::MissingBytecode { contract_name });
+    }
+
+    let compiler_version = compiler_version
+        .strip_prefix('v')
+        .unwrap_or(&compiler_version)
+        .to_owned();
+    if let Err(source) = semver::Version::parse(&compiler_version) {
+        return Err(ExternalArtifactError::InvalidCompilerVersion {
+            version: compiler_version,
+            source,
+        });
+    }
+
+    let source_name = source_name.unwrap_or_else(|| contract_name.clone());
+    let sources: Arc<BuildModelSources> = Arc::new(HashMap::from([(
+        SOURCE_FILE_ID,
+        Arc::new(RwLock::new(SourceFile::new(source_name, String::new()))),
+    )]));
+    let location = Arc::new(SourceLocation::new(
+        Arc::clone(&sources),
+        SOURCE_FILE_ID,
+        0,
+        0,
+    ));
+
+    let mut contract = Contract::new(
+        contract_name.clone(),
+        ContractKind::Contract,
+        Arc::clone(&location),
+    );
+
+    let new_function = |name: String,
+                        r#type: ContractFunctionType,
+                        inputs: &[Param],
+                        state_mutability: StateMutability,
+                        selector: Option<Vec<u8>>|
+     -> Result<Arc<ContractFunction>, ExternalArtifactError> {
+        let param_types = inputs
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Arc::new(ContractFunction {
+            name,
+            r#type,
+            location: Arc::clone(&location),
+            contract_name: Some(contract_name.clone()),
+            visibility: Some(ContractFunctionVisibility::Public),
+            is_payable: Some(state_mutability == StateMutability::Payable),
+            selector: RwLock::new(selector),
+            param_types: Some(param_types),
+        }))
+    };
+
+    if let Some(constructor) = &abi.constructor {
+        contract.add_local_function(new_function(
+            "constructor".to_string(),
+            ContractFunctionType::Constructor,
+            &constructor.inputs,
+            constructor.state_mutability,
+            None,
+        )?)?;
+    }
+
+    if let Some(fallback) = &abi.fallback {
+        contract.add_local_function(new_function(
+            String::new(),
+            ContractFunctionType::Fallback,
+            &[],
+            fallback.state_mutability,
+            None,
+        )?)?;
+    }
+
+    if let Some(receive) = &abi.receive {
+        contract.add_local_function(new_function(
+            String::new(),
+            ContractFunctionType::Receive,
+            &[],
+            receive.state_mutability,
+            None,
+        )?)?;
+    }
+
+    for function in abi.functions() {
+        contract.add_local_function(new_function(
+            function.name.clone(),
+            ContractFunctionType::Function,
+            &function.inputs,
+            function.state_mutability,
+            Some(function.selector().to_vec()),
+        )?)?;
+    }
+
+    for error in abi.errors() {
+        let entry = ContractAbiEntry {
+            name: Some(error.name.clone()),
+            r#type: Some("error".to_string()),
+            inputs: Some(
+                error
+                    .inputs
+                    .iter()
+                    .map(serde_json::to_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        contract.add_custom_error(
+            CustomError::from_abi(entry).map_err(ExternalArtifactError::CustomError)?,
+        );
+    }
+
+    let contract = Arc::new(RwLock::new(contract));
+
+    let immutable_references = immutable_references
+        .into_values()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let metadata = [
+        (true, bytecode, link_references, Vec::new()),
+        (
+            false,
+            deployed_bytecode,
+            deployed_link_references,
+            immutable_references,
+        ),
+    ]
+    .into_iter()
+    .filter_map(
+        |(is_deployment, code, link_references, immutable_references)| {
+            let code = code?;
+            let library_address_positions = library_address_positions(&link_references);
+            let normalized_code = normalize_library_addresses(&code, &library_address_positions);
+            let instructions = decode_unmapped_instructions(&normalized_code);
+
+            Some(ContractMetadata::new(
+                Arc::clone(&sources),
+                Arc::clone(&contract),
+                is_deployment,
+                normalized_code,
+                instructions,
+                library_address_positions,
+                immutable_references,
+                compiler_version.clone(),
+            ))
+        },
+    )
+    .collect();
+
+    Ok(metadata)
+}
+
+/// Returns the positions of the library addresses of the provided link
+/// references.
+fn library_address_positions(link_references: &LinkReferences) -> Vec<u32> {
+    link_references
+        .values()
+        .flat_map(HashMap::values)
+        .flatten()
+        .map(|reference| reference.start)
+        .collect()
+}
+
+/// Replaces the library addresses at the provided positions with zeros, like
+/// the bytecode of contracts compiled by Hardhat. Positions that are out of
+/// bounds are ignored.
+fn normalize_library_addresses(bytecode: &[u8], positions: &[u32]) -> Vec<u8> {
+    let mut normalized = bytecode.to_vec();
+    for &position in positions {
+        let start = position as usize;
+        if let Some(address) = normalized.get_mut(start..start + Address::len_bytes()) {
+            address.fill(0);
+        }
+    }
+
+    normalized
+}
+
+/// Decodes the instructions of a bytecode without source maps, until the
+/// first invalid opcode or truncated push data, which mark the start of the
+/// metadata region.
+fn decode_unmapped_instructions(bytecode: &[u8]) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+
+    let mut pc = 0;
+    while let Some(opcode) = bytecode.get(pc).copied().and_then(OpCode::new) {
+        if opcode == OpCode::INVALID {
+            break;
+        }
+
+        let immediate_size = usize::from(opcode.info().immediate_size());
+
+        let push_data = if opcode.is_push() {
+            let Some(push_data) = bytecode.get(pc..=pc + immediate_size) else {
+                break;
+            };
+
+            Some(push_data.to_vec())
+        } else {
+            None
+        };
+
+        let jump_type = if matches!(opcode, OpCode::JUMP | OpCode::JUMPI) {
+            JumpType::InternalJump
+        } else {
+            JumpType::NotJump
+        };
+
+        instructions.push(Instruction {
+            pc: pc as u32,
+            opcode,
+            jump_type,
+            push_data,
+            location: None,
+        });
+
+        pc += 1 + immediate_size;
+    }
+
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract_decoder::ContractDecoder;
+
+    fn create_artifact() -> ExternalArtifact {
+        serde_json::from_value(serde_json::json!({
+            "contractName": "Token",
+            "sourceName": "src/Token.sol",
+            "abi": [
+                {
+                    "type": "function",
+                    "name": "transfer",
+                    "inputs": [
+                        { "name": "to", "type": "address", "internalType": "address" },
+                        { "name": "amount", "type": "uint256", "internalType": "uint256" }
+                    ],
+                    "outputs": [{ "name": "", "type": "bool", "internalType": "bool" }],
+                    "stateMutability": "nonpayable"
+                },
+                {
+                    "type": "error",
+                    "name": "InsufficientBalance",
+                    "inputs": [{ "name": "needed", "type": "uint256", "internalType": "uint256" }]
+                }
+            ],
+            "deployedBytecode": "0x6080604052600080fdfea2646970667358",
+            "compilerVersion": "v0.8.24+commit.e11b9ed9"
+        }))
+        .expect("artifact should be valid")
+    }
+
+    #[test]
+    fn resolves_function_names_of_external_artifact() -> anyhow::Result<()> {
+        let artifact = create_artifact();
+        let deployed_bytecode = artifact
+            .deployed_bytecode
+            .clone()
+            .expect("artifact has deployed bytecode");
+
+        let metadata = create_contract_metadata_from_artifact(artifact)?;
+        assert_eq!(metadata.len(), 1);
+        assert!(!metadata[0].is_deployment);
+        assert_eq!(metadata[0].compiler_version, "0.8.24+commit.e11b9ed9");
+
+        let decoder = ContractDecoder::default();
+        for metadata in metadata {
+            decoder.add_contract_metadata(metadata);
+        }
+
+        let calldata = Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb]);
+        let names =
+            decoder.get_contract_and_function_names_for_call(&deployed_bytecode, Some(&calldata));
+        assert_eq!(names.contract_name, "Token");
+        assert_eq!(names.function_name.as_deref(), Some("transfer"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_contract_with_linked_library_and_immutable() -> anyhow::Result<()> {
+        const LIBRARY_POSITION: usize = 1;
+        const IMMUTABLE_POSITION: usize = 23;
+
+        // PUSH20 <library> POP PUSH32 <immutable> POP STOP INVALID <metadata>
+        let mut deployed_bytecode = vec![0x73];
+        deployed_bytecode.extend_from_slice(&[0u8; 20]);
+        deployed_bytecode.extend_from_slice(&[0x50, 0x7f]);
+        deployed_bytecode.extend_from_slice(&[0u8; 32]);
+        deployed_bytecode.extend_from_slice(&[0x50, 0x00, 0xfe, 0xa2, 0x64]);
+
+        let mut artifact = create_artifact();
+        artifact.deployed_bytecode = Some(Bytes::from(deployed_bytecode.clone()));
+        artifact.deployed_link_references = serde_json::from_value(serde_json::json!({
+            "src/Library.sol": {
+                "Library": [{ "start": LIBRARY_POSITION, "length": 20 }]
+            }
+        }))?;
+        artifact.immutable_references = serde_json::from_value(serde_json::json!({
+            "42": [{ "start": IMMUTABLE_POSITION, "length": 32 }]
+        }))?;
+
+        let decoder = ContractDecoder::default();
+        for metadata in create_contract_metadata_from_artifact(artifact)? {
+            decoder.add_contract_metadata(metadata);
+        }
+
+        let mut runtime_code = deployed_bytecode;
+        runtime_code[LIBRARY_POSITION..LIBRARY_POSITION + 20].fill(0x11);
+        runtime_code[IMMUTABLE_POSITION..IMMUTABLE_POSITION + 32].fill(0x22);
+
+        let calldata = Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb]);
+        let names = decoder
+            .get_contract_and_function_names_for_call(&Bytes::from(runtime_code), Some(&calldata));
+        assert_eq!(names.contract_name, "Token");
+        assert_eq!(names.function_name.as_deref(), Some("transfer"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stops_decoding_at_metadata_region() {
+        let instructions = decode_unmapped_instructions(&[0x60, 0x80, 0x00, 0xfe, 0x60]);
+
+        let pcs: Vec<_> = instructions
+            .iter()
+            .map(|instruction| instruction.pc)
+            .collect();
+        assert_eq!(pcs, vec![0, 2]);
+        assert_eq!(instructions[0].push_data, Some(vec![0x60, 0x80]));
+    }
+
+    #[test]
+    fn rejects_invalid_compiler_version() {
+        let mut artifact = create_artifact();
+        artifact.compiler_version = "latest".to_string();
+
+        let result = create_contract_metadata_from_artifact(artifact);
+        assert!(matches!(
+            result,
+            Err(ExternalArtifactError::InvalidCompilerVersion { .. })
+        ));
+    }
+}
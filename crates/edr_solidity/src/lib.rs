@@ -11,8 +11,11 @@ pub mod utils;
 pub mod artifacts;
 pub mod compiler;
 pub mod contract_decoder;
+pub mod custom_error_registry;
 pub mod exit_code;
+pub mod external_artifact;
 pub mod library_utils;
+pub mod line_coverage;
 pub mod linker;
 pub mod nested_trace;
 pub mod nested_tracer;
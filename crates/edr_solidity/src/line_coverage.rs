@@ -0,0 +1,285 @@
+//! Line and branch coverage derived from executed program counters.
+//!
+//! Every executed program counter of a recognised contract is mapped through
+//! the contract's source map to a source file and line. This allows
+//! collecting coverage without instrumenting the Solidity source code.
+
+use std::collections::BTreeMap;
+
+use edr_chain_spec::HaltReasonTrait;
+use edr_primitives::bytecode::opcode::OpCode;
+
+use crate::{
+    build_model::ContractMetadata,
+    nested_trace::{CreateOrCallMessageRef, NestedTrace, NestedTraceStep},
+};
+
+/// Hit counts of a conditional jump.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// The 1-based line number of the branch.
+    pub line: u32,
+    /// The number of times the jump was taken.
+    pub taken: u64,
+    /// The number of times the jump was not taken.
+    pub not_taken: u64,
+}
+
+/// Coverage of a single source file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// Maps 1-based line numbers to the number of times the line was executed.
+    pub lines: BTreeMap<u32, u64>,
+    /// Maps the byte offset of a branch's source location to its hit counts.
+    pub branches: BTreeMap<u32, BranchCoverage>,
+}
+
+/// Line and branch coverage, grouped by source name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineCoverage {
+    files: BTreeMap<String, FileCoverage>,
+}
+
+impl LineCoverage {
+    /// Returns the coverage of all source files, keyed by source name.
+    pub fn files(&self) -> &BTreeMap<String, FileCoverage> {
+        &self.files
+    }
+
+    /// Converts the instance into the coverage of all source files, keyed by
+    /// source name.
+    pub fn into_files(self) -> BTreeMap<String, FileCoverage> {
+        self.files
+    }
+
+    /// Whether no coverage has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Records the coverage of a decoded [`NestedTrace`].
+    ///
+    /// Messages of contracts that weren't recognised by the contract decoder
+    /// are ignored.
+    pub fn record_trace<HaltReasonT: HaltReasonTrait>(&mut self, trace: &NestedTrace<HaltReasonT>) {
+        match trace {
+            NestedTrace::Create(create) => self.record_message(create.into()),
+            NestedTrace::Call(call) => self.record_message(call.into()),
+            NestedTrace::Precompile(_) => {}
+        }
+    }
+
+    fn record_message<HaltReasonT: HaltReasonTrait>(
+        &mut self,
+        message: CreateOrCallMessageRef<'_, HaltReasonT>,
+    ) {
+        let contract_meta = message.contract_meta();
+        let steps = message.steps();
+
+        // The file ID and line of the previously executed instruction
+        let mut previous_line: Option<(u32, u32)> = None;
+        for (idx, step) in steps.iter().enumerate() {
+            let evm_step = match step {
+                NestedTraceStep::Create(create) => {
+                    self.record_message(create.into());
+                    continue;
+                }
+                NestedTraceStep::Call(call) => {
+                    self.record_message(call.into());
+                    continue;
+                }
+                NestedTraceStep::Precompile(_) => continue,
+                NestedTraceStep::Evm(evm_step) => evm_step,
+            };
+
+            let Some(step_location) = contract_meta
+                .as_deref()
+                .and_then(|contract_meta| StepLocation::resolve(contract_meta, evm_step.pc))
+            else {
+                continue;
+            };
+
+            let file = self.files.entry(step_location.source_name).or_default();
+
+            // Only count a line once per consecutive execution
+            let current_line = (step_location.file_id, step_location.line);
+            if previous_line != Some(current_line) {
+                *file.lines.entry(step_location.line).or_default() += 1;
+            }
+            previous_line = Some(current_line);
+
+            if step_location.opcode == OpCode::JUMPI {
+                // The next step in the same message determines whether the jump was taken
+                if let Some(NestedTraceStep::Evm(next_step)) = steps.get(idx + 1) {
+                    let branch =
+                        file.branches
+                            .entry(step_location.offset)
+                            .or_insert(BranchCoverage {
+                                line: step_location.line,
+                                ..BranchCoverage::default()
+                            });
+
+                    if next_step.pc == evm_step.pc + 1 {
+                        branch.not_taken += 1;
+                    } else {
+                        branch.taken += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The source location of an executed instruction.
+struct StepLocation {
+    source_name: String,
+    file_id: u32,
+    line: u32,
+    offset: u32,
+    opcode: OpCode,
+}
+
+impl StepLocation {
+    fn resolve(contract_meta: &ContractMetadata, pc: u32) -> Option<Self> {
+        let instruction = contract_meta.get_instruction(pc).ok()?;
+        let location = instruction.location.as_ref()?;
+
+        let source_name = location.file().ok()?.read().source_name.clone();
+        let line = location.get_starting_line_number().ok()?;
+
+        Some(Self {
+            source_name,
+            file_id: location.file_id,
+            line,
+            offset: location.offset,
+            opcode: instruction.opcode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use edr_chain_spec::EvmHaltReason;
+    use edr_primitives::{Address, Bytes, U256};
+    use parking_lot::RwLock;
+
+    use super::*;
+    use crate::{
+        build_model::{Contract, ContractKind, Instruction, JumpType, SourceFile, SourceLocation},
+        exit_code::ExitCode,
+        nested_trace::{CallMessage, EvmStep},
+    };
+
+    const SOURCE_NAME: &str = "contracts/Test.sol";
+
+    fn create_contract_metadata() -> Arc<ContractMetadata> {
+        let mut sources = HashMap::new();
+        sources.insert(
+            0,
+            Arc::new(RwLock::new(SourceFile::new(
+                SOURCE_NAME.to_string(),
+                "a\nb\nc".to_string(),
+            ))),
+        );
+        let sources = Arc::new(sources);
+
+        let contract = Arc::new(RwLock::new(Contract::new(
+            "Test".to_string(),
+            ContractKind::Contract,
+            Arc::new(SourceLocation::new(sources.clone(), 0, 0, 5)),
+        )));
+
+        // (pc, opcode, source offset)
+        let instructions = [
+            (0, OpCode::PUSH1, 0),
+            (2, OpCode::JUMPI, 2),
+            (3, OpCode::STOP, 4),
+            (4, OpCode::JUMPDEST, 4),
+        ]
+        .into_iter()
+        .map(|(pc, opcode, offset)| Instruction {
+            pc,
+            opcode,
+            jump_type: JumpType::NotJump,
+            push_data: None,
+            location: Some(Arc::new(SourceLocation::new(sources.clone(), 0, offset, 1))),
+        })
+        .collect();
+
+        Arc::new(ContractMetadata::new(
+            sources,
+            contract,
+            false,
+            Vec::new(),
+            instructions,
+            Vec::new(),
+            Vec::new(),
+            "<dummy-version>".to_string(),
+        ))
+    }
+
+    fn create_call_trace(
+        contract_meta: Option<Arc<ContractMetadata>>,
+        pcs: &[u32],
+    ) -> NestedTrace<EvmHaltReason> {
+        NestedTrace::Call(CallMessage {
+            number_of_subtraces: 0,
+            steps: pcs
+                .iter()
+                .map(|pc| NestedTraceStep::Evm(EvmStep { pc: *pc }))
+                .collect(),
+            contract_meta,
+            custom_error: None,
+            calldata: Bytes::new(),
+            address: Address::ZERO,
+            code_address: Address::ZERO,
+            code: Bytes::new(),
+            value: U256::ZERO,
+            return_data: Bytes::new(),
+            exit: ExitCode::Success,
+            gas_used: 0,
+            depth: 0,
+        })
+    }
+
+    #[test]
+    fn records_lines_and_branches() {
+        let contract_meta = create_contract_metadata();
+
+        let mut coverage = LineCoverage::default();
+        coverage.record_trace(&create_call_trace(Some(contract_meta.clone()), &[0, 2, 3]));
+        coverage.record_trace(&create_call_trace(Some(contract_meta), &[0, 2, 4, 3]));
+
+        let file = coverage
+            .files()
+            .get(SOURCE_NAME)
+            .expect("file should have coverage");
+
+        assert_eq!(
+            file.lines,
+            BTreeMap::from([(1, 2), (2, 2), (3, 2)]),
+            "consecutive instructions on the same line should count once"
+        );
+        assert_eq!(
+            file.branches,
+            BTreeMap::from([(
+                2,
+                BranchCoverage {
+                    line: 2,
+                    taken: 1,
+                    not_taken: 1,
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn ignores_unrecognized_contracts() {
+        let mut coverage = LineCoverage::default();
+        coverage.record_trace(&create_call_trace(None, &[0, 2, 3]));
+
+        assert!(coverage.is_empty());
+    }
+}
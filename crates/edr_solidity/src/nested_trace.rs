@@ -79,6 +79,9 @@ pub struct CreateMessage<HaltReasonT> {
     pub steps: Vec<NestedTraceStep<HaltReasonT>>,
     /// Resolved metadata of the contract that is being executed.
     pub contract_meta: Option<Arc<ContractMetadata>>,
+    /// The human-readable custom error of the return data, if the contract
+    /// decoder's registry contains it.
+    pub custom_error: Option<String>,
     /// Address of the deployed contract.
     pub deployed_contract: Option<Bytes>,
     /// Code of the contract that is being executed.
@@ -112,6 +115,7 @@ impl<HaltReasonT> CreateMessage<HaltReasonT> {
                 .map(|step| step.map_halt_reason(conversion_fn))
                 .collect(),
             contract_meta: self.contract_meta,
+            custom_error: self.custom_error,
             deployed_contract: self.deployed_contract,
             code: self.code,
             value: self.value,
@@ -135,6 +139,9 @@ pub struct CallMessage<HaltReasonT> {
     pub steps: Vec<NestedTraceStep<HaltReasonT>>,
     /// Resolved metadata of the contract that is being executed.
     pub contract_meta: Option<Arc<ContractMetadata>>,
+    /// The human-readable custom error of the return data, if the contract
+    /// decoder's registry contains it.
+    pub custom_error: Option<String>,
     /// Calldata buffer
     pub calldata: Bytes,
     /// Address of the contract that is being executed.
@@ -172,6 +179,7 @@ impl<HaltReasonT> CallMessage<HaltReasonT> {
                 .map(|step| step.map_halt_reason(conversion_fn))
                 .collect(),
             contract_meta: self.contract_meta,
+            custom_error: self.custom_error,
             calldata: self.calldata,
             address: self.address,
             code_address: self.code_address,
@@ -228,6 +236,13 @@ impl<'a, HaltReasonT: HaltReasonTrait> CreateOrCallMessageRef<'a, HaltReasonT> {
         }
     }
 
+    pub fn custom_error(&self) -> Option<&'a str> {
+        match self {
+            CreateOrCallMessageRef::Create(create) => create.custom_error.as_deref(),
+            CreateOrCallMessageRef::Call(call) => call.custom_error.as_deref(),
+        }
+    }
+
     pub fn exit_code(&self) -> &ExitCode<HaltReasonT> {
         match self {
             CreateOrCallMessageRef::Create(create) => &create.exit,
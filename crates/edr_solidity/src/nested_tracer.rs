@@ -96,6 +96,8 @@ impl<HaltReasonT: HaltReasonTrait> NestedTracer<HaltReasonT> {
                 edr_tracing::TraceMessage::After(after) => {
                     self.add_after_message(after.execution_result)?;
                 }
+                // Self-destructs are not part of nested traces
+                edr_tracing::TraceMessage::SelfDestruct(_) => {}
             }
         }
         Ok(())
@@ -397,6 +399,7 @@ fn convert_to_external_trace<HaltReasonT: HaltReasonTrait>(
                 number_of_subtraces,
                 steps: steps.into_iter().map(convert_to_external_step).collect(),
                 contract_meta: None,
+                custom_error: None,
                 deployed_contract,
                 code,
                 value,
@@ -424,6 +427,7 @@ fn convert_to_external_trace<HaltReasonT: HaltReasonTrait>(
                 number_of_subtraces,
                 steps: steps.into_iter().map(convert_to_external_step).collect(),
                 contract_meta: None,
+                custom_error: None,
                 calldata,
                 address,
                 code_address,
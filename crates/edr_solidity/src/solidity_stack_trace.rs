@@ -101,11 +101,17 @@ pub enum StackTraceEntry {
     },
     UnrecognizedCreateError {
         return_data: Bytes,
+        /// The human-readable custom error of the return data, if the
+        /// contract decoder's registry contains it.
+        custom_error: Option<String>,
         is_invalid_opcode_error: bool,
     },
     UnrecognizedContractError {
         address: Address,
         return_data: Bytes,
+        /// The human-readable custom error of the return data, if the
+        /// contract decoder's registry contains it.
+        custom_error: Option<String>,
         is_invalid_opcode_error: bool,
     },
     OtherExecutionError {
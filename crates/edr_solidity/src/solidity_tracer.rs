@@ -186,12 +186,14 @@ fn get_unrecognized_message_stack_trace<HaltReasonT: HaltReasonTrait>(
             Ok(vec![StackTraceEntry::UnrecognizedContractError {
                 address: call.address,
                 return_data: call.return_data.clone(),
+                custom_error: call.custom_error.clone(),
                 is_invalid_opcode_error,
             }])
         }
         CreateOrCallMessageRef::Create(call) => {
             Ok(vec![StackTraceEntry::UnrecognizedCreateError {
                 return_data: call.return_data.clone(),
+                custom_error: call.custom_error.clone(),
                 is_invalid_opcode_error,
             }])
         }
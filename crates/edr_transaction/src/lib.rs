@@ -198,6 +198,15 @@ pub trait IsEip155 {
     fn is_eip155(&self) -> bool;
 }
 
+/// Trait for determining whether a transaction is a deposit transaction.
+///
+/// Deposit transactions are created by a chain's sequencer, rather than signed
+/// by a user.
+pub trait IsDeposit {
+    /// Whether the transaction is a deposit transaction.
+    fn is_deposit(&self) -> bool;
+}
+
 /// Trait for determining whether a transaction is an EIP-4844 transaction.
 pub trait IsEip4844 {
     /// Whether the transaction is an EIP-4844 transaction.
@@ -19,6 +19,18 @@ pub struct Eip4844 {
     rlp_encoding: OnceLock<Bytes>,
 }
 
+/// The blobs, commitments, and proofs of an EIP-4844 transaction, which are
+/// not part of the transaction's signed payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobTransactionSidecar {
+    /// The blobs of the transaction
+    pub blobs: Vec<Blob>,
+    /// The KZG commitments of the blobs
+    pub commitments: Vec<Bytes48>,
+    /// The KZG proofs of the blobs
+    pub proofs: Vec<Bytes48>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CreationError {
     #[error(
@@ -147,6 +159,16 @@ impl Eip4844 {
         &self.proofs
     }
 
+    /// Returns a copy of the blobs, commitments, and proofs of the pooled
+    /// transaction.
+    pub fn to_sidecar(&self) -> BlobTransactionSidecar {
+        BlobTransactionSidecar {
+            blobs: self.blobs.clone(),
+            commitments: self.commitments.clone(),
+            proofs: self.proofs.clone(),
+        }
+    }
+
     /// Converts the pooled transaction into its inner components.
     pub fn into_inner(self) -> (signed::Eip4844, Vec<Blob>, Vec<Bytes48>, Vec<Bytes48>) {
         (self.payload, self.blobs, self.commitments, self.proofs)
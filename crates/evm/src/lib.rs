@@ -9,7 +9,7 @@ use edr_chain_spec_evm::{
     TransactionError,
 };
 use edr_database_components::{DatabaseComponents, WrapDatabaseRef};
-use edr_precompile::{OverriddenPrecompileProvider, PrecompileFn};
+use edr_precompile::{CustomPrecompile, OverriddenPrecompileProvider};
 use edr_primitives::{Address, HashMap};
 use edr_state_api::{State, StateCommit};
 
@@ -33,7 +33,7 @@ pub fn dry_run<
     cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     transaction: EvmChainSpecT::SignedTransaction,
     block: BlockT,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
 ) -> Result<
     ExecutionResultAndState<EvmChainSpecT::HaltReason>,
     TransactionError<
@@ -79,7 +79,7 @@ pub fn dry_run_with_inspector<
     cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     transaction: EvmChainSpecT::SignedTransaction,
     block: BlockT,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
     inspector: &mut InspectorT,
 ) -> Result<
     ExecutionResultAndState<EvmChainSpecT::HaltReason>,
@@ -126,7 +126,7 @@ pub fn guaranteed_dry_run<
     mut cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     transaction: EvmChainSpecT::SignedTransaction,
     block: BlockT,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
 ) -> Result<
     ExecutionResultAndState<EvmChainSpecT::HaltReason>,
     TransactionError<
@@ -175,7 +175,7 @@ pub fn guaranteed_dry_run_with_inspector<
     mut cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     transaction: EvmChainSpecT::SignedTransaction,
     block: BlockT,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
     inspector: &mut InspectorT,
 ) -> Result<
     ExecutionResultAndState<EvmChainSpecT::HaltReason>,
@@ -217,7 +217,7 @@ pub fn run<
     cfg: CfgEnv<EvmChainSpecT::Hardfork>,
     transaction: EvmChainSpecT::SignedTransaction,
     block: BlockT,
-    custom_precompiles: &HashMap<Address, PrecompileFn>,
+    custom_precompiles: &HashMap<Address, CustomPrecompile>,
 ) -> Result<
     ExecutionResult<EvmChainSpecT::HaltReason>,
     TransactionError<
@@ -214,6 +214,7 @@ fn convert_node_to_nested_trace<HaltReasonT: HaltReasonTr>(
             number_of_subtraces: node.children.len() as u32,
             steps,
             contract_meta: None, // This will be populated by the nested trace decoder
+            custom_error: None,
             deployed_contract: Some(trace.output.clone()),
             code: address_to_creation_code
                 .get(&trace.address)
@@ -241,6 +242,7 @@ fn convert_node_to_nested_trace<HaltReasonT: HaltReasonTr>(
             number_of_subtraces: node.children.len() as u32,
             steps,
             contract_meta: None, // This will be populated by the nested trace decoder
+            custom_error: None,
             calldata: trace.data.clone(),
             address: trace.address,
             code_address: trace.address,
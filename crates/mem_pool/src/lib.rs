@@ -1,14 +1,23 @@
 //! Types for managing Ethereum mem pool
 #![warn(missing_docs)]
 
-use std::{cmp::Ordering, fmt::Debug, num::NonZeroU64};
+mod policy;
+
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    num::{NonZeroU64, NonZeroUsize},
+    time::Duration,
+};
 
 use edr_chain_spec::ExecutableTransaction;
-use edr_primitives::{Address, HashMap, B256, U256};
+use edr_primitives::{Address, HashMap, HashSet, B256, U256};
 use edr_state_api::{account::AccountInfo, State};
 use edr_transaction::upfront_cost;
 use indexmap::{map::Entry, IndexMap};
 
+pub use self::policy::{MemPoolPolicy, DEFAULT_PRICE_BUMP_PERCENTAGE};
+
 /// An iterator over pending transactions.
 pub struct PendingTransactions<SignedTransactionT: ExecutableTransaction, ComparatorT>
 where
@@ -37,6 +46,25 @@ where
     ) -> Option<Vec<OrderedTransaction<SignedTransactionT>>> {
         self.transactions.shift_remove(caller)
     }
+
+    /// Removes the pending transactions corresponding to the provided
+    /// transaction hashes. As nonces need to be consecutive, the subsequent
+    /// transactions of their callers are removed as well.
+    pub fn remove_transactions(&mut self, transaction_hashes: &HashSet<B256>) {
+        if transaction_hashes.is_empty() {
+            return;
+        }
+
+        self.transactions.retain(|_caller, transactions| {
+            if let Some(index) = transactions.iter().position(|transaction| {
+                transaction_hashes.contains(transaction.pending().transaction_hash())
+            }) {
+                transactions.truncate(index);
+            }
+
+            !transactions.is_empty()
+        });
+    }
 }
 
 impl<SignedTransactionT, ComparatorT> Debug for PendingTransactions<SignedTransactionT, ComparatorT>
@@ -129,6 +157,15 @@ pub enum MemPoolAddTransactionError<SE> {
         /// The sender's balance
         sender_balance: U256,
     },
+    /// The mem pool is full and the transaction's gas price is too low to
+    /// evict another transaction.
+    #[error(
+        "Mem pool is full. The transaction's gas price is too low to evict any of the {max_transactions} transactions in the mem pool."
+    )]
+    MemPoolFull {
+        /// The maximum number of transactions in the mem pool
+        max_transactions: NonZeroUsize,
+    },
     /// Transaction nonce is too low.
     #[error(
         "Transaction nonce too low. Expected nonce to be at least {sender_nonce} but got {transaction_nonce}."
@@ -145,6 +182,17 @@ pub enum MemPoolAddTransactionError<SE> {
         /// The transaction hash
         transaction_hash: B256,
     },
+    /// The sender already has the maximum number of transactions in the mem
+    /// pool.
+    #[error(
+        "Sender {sender} already has the maximum of {max_transactions_per_sender} transactions in the mem pool."
+    )]
+    SenderTransactionLimitReached {
+        /// The sender of the transaction
+        sender: Address,
+        /// The maximum number of transactions per sender
+        max_transactions_per_sender: NonZeroUsize,
+    },
     /// State error
     #[error(transparent)]
     State(#[from] SE),
@@ -168,12 +216,23 @@ pub enum MemPoolAddTransactionError<SE> {
         /// The transaction nonce
         transaction_nonce: u64,
     },
+    /// Replacement transaction has underpriced max fee per blob gas.
+    #[error(
+        "Replacement transaction underpriced. A maxFeePerBlobGas of at least {min_new_max_fee_per_blob_gas} is necessary to replace the existing blob transaction with nonce {transaction_nonce}."
+    )]
+    ReplacementMaxFeePerBlobGasTooLow {
+        /// The minimum new max fee per blob gas
+        min_new_max_fee_per_blob_gas: u128,
+        /// The transaction nonce
+        transaction_nonce: u64,
+    },
 }
 
 /// A pending transaction with an order ID.
 #[derive(Clone, Debug)]
 pub struct OrderedTransaction<SignedTransactionT: ExecutableTransaction> {
     order_id: usize,
+    inserted_at: u64,
     transaction: SignedTransactionT,
 }
 
@@ -188,6 +247,12 @@ impl<SignedTransactionT: ExecutableTransaction> OrderedTransaction<SignedTransac
         &self.transaction
     }
 
+    /// Retrieves the moment at which the transaction was added to the mem
+    /// pool, in seconds since the Unix epoch.
+    pub fn inserted_at(&self) -> u64 {
+        self.inserted_at
+    }
+
     fn caller(&self) -> &Address {
         self.transaction.caller()
     }
@@ -214,24 +279,45 @@ pub struct MemPool<SignedTransactionT: ExecutableTransaction> {
     /// (pending) nonce is high enough
     future_transactions: IndexMap<Address, Vec<OrderedTransaction<SignedTransactionT>>>,
     next_order_id: usize,
+    /// Policy for replacing, limiting, and expiring transactions
+    policy: MemPoolPolicy,
     /// Transaction gas cap, introduced in [EIP-7825].
     ///
     /// When not set, there is no cap on transaction gas.
     ///
     /// [EIP-7825]: https://eips.ethereum.org/EIPS/eip-7825
     transaction_gas_cap: Option<u64>,
+    /// Whether transactions are accepted regardless of whether the sender's
+    /// balance covers their upfront cost.
+    disable_balance_check: bool,
 }
 
 impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
     /// Constructs a new [`MemPool`] with the specified block gas limit.
     pub fn new(block_gas_limit: NonZeroU64, transaction_gas_cap: Option<u64>) -> Self {
+        Self::with_policy(
+            block_gas_limit,
+            transaction_gas_cap,
+            MemPoolPolicy::default(),
+        )
+    }
+
+    /// Constructs a new [`MemPool`] with the specified block gas limit and
+    /// policy.
+    pub fn with_policy(
+        block_gas_limit: NonZeroU64,
+        transaction_gas_cap: Option<u64>,
+        policy: MemPoolPolicy,
+    ) -> Self {
         Self {
             block_gas_limit,
             pending_transactions: IndexMap::new(),
             hash_to_transaction: HashMap::default(),
             future_transactions: IndexMap::new(),
             next_order_id: 0,
+            policy,
             transaction_gas_cap,
+            disable_balance_check: false,
         }
     }
 
@@ -240,13 +326,89 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
         self.block_gas_limit
     }
 
+    /// Retrieves the instance's policy.
+    pub fn policy(&self) -> &MemPoolPolicy {
+        &self.policy
+    }
+
+    /// Sets the instance's policy, removing transactions that exceed its
+    /// limits or lifetime at the provided moment, in seconds since the Unix
+    /// epoch.
+    ///
+    /// Returns the removed transactions.
+    pub fn set_policy(
+        &mut self,
+        policy: MemPoolPolicy,
+        now: u64,
+    ) -> Vec<OrderedTransaction<SignedTransactionT>> {
+        self.policy = policy;
+
+        let mut removed = self.remove_expired_transactions(now);
+
+        if let Some(max_transactions_per_sender) = self.policy.max_transactions_per_sender {
+            let senders = self
+                .pending_transactions
+                .keys()
+                .chain(self.future_transactions.keys())
+                .copied()
+                .collect::<Vec<_>>();
+
+            for sender in senders {
+                while self.sender_transaction_count(&sender) > max_transactions_per_sender.get() {
+                    // Remove the sender's transactions with the highest nonces first, to avoid
+                    // nonce gaps.
+                    let hash = *self
+                        .future_transactions
+                        .get(&sender)
+                        .or_else(|| self.pending_transactions.get(&sender))
+                        .and_then(|transactions| {
+                            transactions
+                                .iter()
+                                .max_by_key(|transaction| transaction.nonce())
+                        })
+                        .expect("Sender has transactions")
+                        .hash();
+
+                    removed.extend(self.remove_transaction(&hash));
+                }
+            }
+        }
+
+        if let Some(max_transactions) = self.policy.max_transactions {
+            while self.hash_to_transaction.len() > max_transactions.get() {
+                removed.extend(self.evict_lowest_priority_transaction());
+            }
+        }
+
+        removed
+    }
+
     /// Retrieves the instance's transaction gas cap.
     pub fn transaction_gas_cap(&self) -> Option<u64> {
         self.transaction_gas_cap
     }
 
+    /// Retrieves whether the check that the sender's balance covers the
+    /// upfront cost of a transaction is disabled.
+    pub fn is_balance_check_disabled(&self) -> bool {
+        self.disable_balance_check
+    }
+
+    /// Sets whether the check that the sender's balance covers the upfront
+    /// cost of a transaction is disabled.
+    pub fn set_balance_check_disabled(&mut self, disabled: bool) {
+        self.disable_balance_check = disabled;
+    }
+
     /// Sets the instance's block gas limit.
-    pub fn set_block_gas_limit<S>(&mut self, state: &S, limit: NonZeroU64) -> Result<(), S::Error>
+    ///
+    /// Returns the transactions that were removed, as their gas limit exceeds
+    /// the new block gas limit.
+    pub fn set_block_gas_limit<S>(
+        &mut self,
+        state: &S,
+        limit: NonZeroU64,
+    ) -> Result<Vec<OrderedTransaction<SignedTransactionT>>, S::Error>
     where
         S: State + ?Sized,
         S::Error: Debug,
@@ -256,6 +418,24 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
         self.update(state)
     }
 
+    /// Sets the instance's transaction gas cap.
+    ///
+    /// Returns the transactions that were removed, as their gas limit exceeds
+    /// the new transaction gas cap.
+    pub fn set_transaction_gas_cap<S>(
+        &mut self,
+        state: &S,
+        cap: Option<u64>,
+    ) -> Result<Vec<OrderedTransaction<SignedTransactionT>>, S::Error>
+    where
+        S: State + ?Sized,
+        S::Error: Debug,
+    {
+        self.transaction_gas_cap = cap;
+
+        self.update(state)
+    }
+
     /// Retrieves the nonce of the last pending transaction of the account
     /// corresponding to the specified address, if it exists.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
@@ -327,12 +507,14 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
                     self.pending_transactions.shift_remove(caller);
                 }
 
-                self.future_transactions
-                    .entry(*caller)
-                    .and_modify(|transactions| {
-                        transactions.append(&mut invalidated_transactions);
-                    })
-                    .or_insert(invalidated_transactions);
+                if !invalidated_transactions.is_empty() {
+                    self.future_transactions
+                        .entry(*caller)
+                        .and_modify(|transactions| {
+                            transactions.append(&mut invalidated_transactions);
+                        })
+                        .or_insert(invalidated_transactions);
+                }
 
                 return Some(removed);
             }
@@ -356,10 +538,58 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
         None
     }
 
+    /// Removes all transactions.
+    ///
+    /// Returns the removed transactions.
+    pub fn remove_all_transactions(&mut self) -> Vec<OrderedTransaction<SignedTransactionT>> {
+        self.pending_transactions.clear();
+        self.future_transactions.clear();
+
+        self.hash_to_transaction
+            .drain()
+            .map(|(_, transaction)| transaction)
+            .collect()
+    }
+
+    /// Removes all transactions that have exceeded the policy's transaction
+    /// lifetime at the provided moment, in seconds since the Unix epoch.
+    ///
+    /// Returns the removed transactions.
+    pub fn remove_expired_transactions(
+        &mut self,
+        now: u64,
+    ) -> Vec<OrderedTransaction<SignedTransactionT>> {
+        let Some(transaction_lifetime) = self.policy.transaction_lifetime else {
+            return Vec::new();
+        };
+
+        let expired_hashes = self
+            .hash_to_transaction
+            .values()
+            .filter(|transaction| {
+                Duration::from_secs(now.saturating_sub(transaction.inserted_at))
+                    >= transaction_lifetime
+            })
+            .map(|transaction| *transaction.hash())
+            .collect::<Vec<_>>();
+
+        expired_hashes
+            .iter()
+            .filter_map(|hash| self.remove_transaction(hash))
+            .collect()
+    }
+
     /// Updates the [`MemPool`], moving any future transactions to the pending
     /// status, if their nonces are high enough.
+    ///
+    /// Returns the transactions that were removed, as they're no longer
+    /// valid; e.g. because they were mined or their sender can no longer
+    /// afford them.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn update<S>(&mut self, state: &S) -> Result<(), S::Error>
+    pub fn update<S>(
+        &mut self,
+        state: &S,
+    ) -> Result<Vec<OrderedTransaction<SignedTransactionT>>, S::Error>
     where
         S: State + ?Sized,
         S::Error: Debug,
@@ -367,25 +597,35 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
         fn is_valid_tx(
             transaction: &impl ExecutableTransaction,
             block_gas_limit: NonZeroU64,
+            transaction_gas_cap: Option<u64>,
+            disable_balance_check: bool,
             sender: &AccountInfo,
         ) -> bool {
             transaction.gas_limit() <= block_gas_limit.get()
-                && upfront_cost(transaction) <= sender.balance
+                && transaction_gas_cap.is_none_or(|cap| transaction.gas_limit() <= cap)
+                && (disable_balance_check || upfront_cost(transaction) <= sender.balance)
                 // Remove all mined transactions
                 && transaction.nonce() >= sender.nonce
         }
 
+        let mut removed = Vec::new();
+
         for entry in self.pending_transactions.iter_mut() {
             let (caller, transactions) = entry;
             let sender = state.basic(*caller)?.unwrap_or_default();
 
             // Remove invalidated transactions
             transactions.retain(|transaction| {
-                let should_retain =
-                    is_valid_tx(transaction.pending(), self.block_gas_limit, &sender);
+                let should_retain = is_valid_tx(
+                    transaction.pending(),
+                    self.block_gas_limit,
+                    self.transaction_gas_cap,
+                    self.disable_balance_check,
+                    &sender,
+                );
 
                 if !should_retain {
-                    self.hash_to_transaction.remove(transaction.hash());
+                    removed.extend(self.hash_to_transaction.remove(transaction.hash()));
                 }
 
                 should_retain
@@ -417,11 +657,16 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
             let sender = state.basic(*caller)?.unwrap_or_default();
 
             transactions.retain(|transaction| {
-                let should_retain =
-                    is_valid_tx(&transaction.transaction, self.block_gas_limit, &sender);
+                let should_retain = is_valid_tx(
+                    &transaction.transaction,
+                    self.block_gas_limit,
+                    self.transaction_gas_cap,
+                    self.disable_balance_check,
+                    &sender,
+                );
 
                 if !should_retain {
-                    self.hash_to_transaction.remove(transaction.hash());
+                    removed.extend(self.hash_to_transaction.remove(transaction.hash()));
                 }
 
                 should_retain
@@ -432,7 +677,7 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
         self.future_transactions
             .retain(|_, transactions| !transactions.is_empty());
 
-        Ok(())
+        Ok(removed)
     }
 
     /// Returns the transaction corresponding to the provided hash, if it
@@ -444,16 +689,61 @@ impl<SignedTransactionT: ExecutableTransaction> MemPool<SignedTransactionT> {
     ) -> Option<&OrderedTransaction<SignedTransactionT>> {
         self.hash_to_transaction.get(hash)
     }
+
+    /// Evicts the transaction with the lowest priority. Future transactions
+    /// are evicted before pending transactions. Of a sender's transactions,
+    /// only the one with the highest nonce is considered, to avoid nonce gaps.
+    fn evict_lowest_priority_transaction(
+        &mut self,
+    ) -> Option<OrderedTransaction<SignedTransactionT>> {
+        // Lowest gas price first and, in case of a tie, the most recent transaction
+        let lowest_priority =
+            |transactions: &IndexMap<Address, Vec<OrderedTransaction<SignedTransactionT>>>| {
+                transactions
+                    .values()
+                    .filter_map(|transactions| {
+                        // Future transactions aren't sorted by nonce
+                        transactions
+                            .iter()
+                            .max_by_key(|transaction| transaction.nonce())
+                    })
+                    .min_by(|lhs, rhs| {
+                        lhs.transaction
+                            .gas_price()
+                            .cmp(rhs.transaction.gas_price())
+                            .then_with(|| rhs.order_id.cmp(&lhs.order_id))
+                    })
+                    .map(|transaction| *transaction.hash())
+            };
+
+        let hash = lowest_priority(&self.future_transactions)
+            .or_else(|| lowest_priority(&self.pending_transactions))?;
+
+        self.remove_transaction(&hash)
+    }
+
+    fn sender_transaction_count(&self, sender: &Address) -> usize {
+        self.pending_transactions.get(sender).map_or(0, Vec::len)
+            + self.future_transactions.get(sender).map_or(0, Vec::len)
+    }
 }
 
 impl<SignedTransactionT: Clone + ExecutableTransaction> MemPool<SignedTransactionT> {
-    /// Tries to add the provided transaction to the [`MemPool`].
+    /// Tries to add the provided transaction to the [`MemPool`] at the
+    /// provided moment, in seconds since the Unix epoch.
+    ///
+    /// Returns the transactions that were removed as a result; i.e. expired,
+    /// replaced, or evicted transactions.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn add_transaction<S: State + ?Sized>(
         &mut self,
         state: &S,
         transaction: SignedTransactionT,
-    ) -> Result<(), MemPoolAddTransactionError<S::Error>> {
+        now: u64,
+    ) -> Result<Vec<OrderedTransaction<SignedTransactionT>>, MemPoolAddTransactionError<S::Error>>
+    {
+        let mut removed = self.remove_expired_transactions(now);
+
         let transaction_gas_limit = transaction.gas_limit();
 
         if let Some(transaction_gas_cap) = self.transaction_gas_cap
@@ -491,38 +781,78 @@ impl<SignedTransactionT: Clone + ExecutableTransaction> MemPool<SignedTransactio
 
         // We need to validate funds at this stage to avoid DOS
         let max_upfront_cost = upfront_cost(&transaction);
-        if max_upfront_cost > sender.balance {
+        if !self.disable_balance_check && max_upfront_cost > sender.balance {
             return Err(MemPoolAddTransactionError::InsufficientFunds {
                 max_upfront_cost,
                 sender_balance: sender.balance,
             });
         }
 
+        if let Some(max_transactions_per_sender) = self.policy.max_transactions_per_sender {
+            let sender = *transaction.caller();
+            let is_replacement = self
+                .pending_transactions
+                .get(&sender)
+                .into_iter()
+                .chain(self.future_transactions.get(&sender))
+                .flatten()
+                .any(|existing| existing.nonce() == transaction.nonce());
+
+            if !is_replacement
+                && self.sender_transaction_count(&sender) >= max_transactions_per_sender.get()
+            {
+                return Err(MemPoolAddTransactionError::SenderTransactionLimitReached {
+                    sender,
+                    max_transactions_per_sender,
+                });
+            }
+        }
+
         let next_nonce = account_next_nonce(self, state, transaction.caller())?;
         let transaction = OrderedTransaction {
             order_id: self.next_order_id,
+            inserted_at: now,
             transaction,
         };
 
-        if transaction.nonce() > next_nonce {
-            self.insert_future_transaction(transaction.clone())?;
+        let replaced = if transaction.nonce() > next_nonce {
+            self.insert_future_transaction(transaction.clone())?
         } else {
-            self.insert_pending_transaction(transaction.clone())?;
-        }
+            self.insert_pending_transaction(transaction.clone())?
+        };
+        removed.extend(replaced);
 
         self.next_order_id += 1;
 
+        let transaction_hash = *transaction.hash();
         self.hash_to_transaction
-            .insert(*transaction.hash(), transaction);
+            .insert(transaction_hash, transaction);
+
+        if let Some(max_transactions) = self.policy.max_transactions {
+            while self.hash_to_transaction.len() > max_transactions.get() {
+                let evicted = self
+                    .evict_lowest_priority_transaction()
+                    .expect("Mem pool is not empty");
+
+                if *evicted.hash() == transaction_hash {
+                    return Err(MemPoolAddTransactionError::MemPoolFull { max_transactions });
+                }
+
+                removed.push(evicted);
+            }
+        }
 
-        Ok(())
+        Ok(removed)
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn insert_pending_transaction<StateError>(
         &mut self,
         transaction: OrderedTransaction<SignedTransactionT>,
-    ) -> Result<(), MemPoolAddTransactionError<StateError>> {
+    ) -> Result<
+        Option<OrderedTransaction<SignedTransactionT>>,
+        MemPoolAddTransactionError<StateError>,
+    > {
         let mut pending_transactions = self.pending_transactions.entry(*transaction.caller());
 
         // Check whether an existing transaction can be replaced
@@ -534,15 +864,17 @@ impl<SignedTransactionT: Clone + ExecutableTransaction> MemPool<SignedTransactio
 
             if let Some(replaced_transaction) = replaced_transaction {
                 validate_replacement_transaction(
+                    &self.policy,
                     &replaced_transaction.transaction,
                     &transaction.transaction,
                 )?;
 
                 self.hash_to_transaction.remove(replaced_transaction.hash());
 
-                *replaced_transaction = transaction.clone();
+                let replaced_transaction =
+                    std::mem::replace(replaced_transaction, transaction.clone());
 
-                return Ok(());
+                return Ok(Some(replaced_transaction));
             }
         }
 
@@ -569,14 +901,17 @@ impl<SignedTransactionT: Clone + ExecutableTransaction> MemPool<SignedTransactio
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn insert_future_transaction<StateError>(
         &mut self,
         transaction: OrderedTransaction<SignedTransactionT>,
-    ) -> Result<(), MemPoolAddTransactionError<StateError>> {
+    ) -> Result<
+        Option<OrderedTransaction<SignedTransactionT>>,
+        MemPoolAddTransactionError<StateError>,
+    > {
         let mut future_transactions = self.future_transactions.entry(*transaction.caller());
 
         // Check whether an existing transaction can be replaced
@@ -588,20 +923,22 @@ impl<SignedTransactionT: Clone + ExecutableTransaction> MemPool<SignedTransactio
 
             if let Some(replaced_transaction) = replaced_transaction {
                 validate_replacement_transaction(
+                    &self.policy,
                     &replaced_transaction.transaction,
                     &transaction.transaction,
                 )?;
 
                 self.hash_to_transaction.remove(replaced_transaction.hash());
 
-                *replaced_transaction = transaction.clone();
+                let replaced_transaction =
+                    std::mem::replace(replaced_transaction, transaction.clone());
 
-                return Ok(());
+                return Ok(Some(replaced_transaction));
             }
         }
 
         future_transactions.or_default().push(transaction);
-        Ok(())
+        Ok(None)
     }
 
     /// Creates an iterator for all pending transactions; i.e. for which the
@@ -647,10 +984,11 @@ pub fn has_transactions<SignedTransactionT: ExecutableTransaction>(
 }
 
 fn validate_replacement_transaction<StateError>(
+    policy: &MemPoolPolicy,
     old_transaction: &impl ExecutableTransaction,
     new_transaction: &impl ExecutableTransaction,
 ) -> Result<(), MemPoolAddTransactionError<StateError>> {
-    let min_new_max_fee_per_gas = min_new_fee(*old_transaction.gas_price());
+    let min_new_max_fee_per_gas = policy.min_replacement_fee(*old_transaction.gas_price());
     if *new_transaction.gas_price() < min_new_max_fee_per_gas {
         return Err(MemPoolAddTransactionError::ReplacementMaxFeePerGasTooLow {
             min_new_max_fee_per_gas,
@@ -658,7 +996,7 @@ fn validate_replacement_transaction<StateError>(
         });
     }
 
-    let min_new_max_priority_fee_per_gas = min_new_fee(
+    let min_new_max_priority_fee_per_gas = policy.min_replacement_fee(
         *old_transaction
             .max_priority_fee_per_gas()
             .unwrap_or_else(|| old_transaction.gas_price()),
@@ -677,16 +1015,23 @@ fn validate_replacement_transaction<StateError>(
         );
     }
 
-    Ok(())
-}
+    // A blob transaction can only be replaced by a blob transaction that also
+    // bumps the max fee per blob gas.
+    if let Some(old_max_fee_per_blob_gas) = old_transaction.max_fee_per_blob_gas() {
+        let min_new_max_fee_per_blob_gas = policy.min_replacement_fee(*old_max_fee_per_blob_gas);
 
-fn min_new_fee(fee: u128) -> u128 {
-    let min_new_priority_fee = fee * 110u128;
-
-    let one_hundred = 100u128;
-    if min_new_priority_fee % one_hundred == 0u128 {
-        min_new_priority_fee / one_hundred
-    } else {
-        min_new_priority_fee / one_hundred + 1u128
+        if new_transaction
+            .max_fee_per_blob_gas()
+            .is_none_or(|max_fee_per_blob_gas| *max_fee_per_blob_gas < min_new_max_fee_per_blob_gas)
+        {
+            return Err(
+                MemPoolAddTransactionError::ReplacementMaxFeePerBlobGasTooLow {
+                    min_new_max_fee_per_blob_gas,
+                    transaction_nonce: old_transaction.nonce(),
+                },
+            );
+        }
     }
+
+    Ok(())
 }
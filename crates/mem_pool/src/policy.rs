@@ -0,0 +1,58 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// The default minimum percentage by which the fees of a replacement
+/// transaction need to exceed the fees of the transaction it replaces.
+pub const DEFAULT_PRICE_BUMP_PERCENTAGE: u64 = 10;
+
+/// Policy for replacing, limiting, and expiring transactions in the
+/// [`MemPool`](crate::MemPool).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MemPoolPolicy {
+    /// The minimum percentage by which the gas price - or max fee per gas and
+    /// max priority fee per gas - of a replacement transaction need to
+    /// exceed those of the transaction it replaces.
+    pub price_bump_percentage: u64,
+    /// The maximum number of transactions in the mem pool. When exceeded, the
+    /// transactions with the lowest gas price are evicted, starting with
+    /// future transactions.
+    pub max_transactions: Option<NonZeroUsize>,
+    /// The maximum number of transactions per sender in the mem pool.
+    pub max_transactions_per_sender: Option<NonZeroUsize>,
+    /// The maximum duration that a transaction can remain in the mem pool,
+    /// before it's dropped.
+    pub transaction_lifetime: Option<Duration>,
+    /// Whether transactions with a nonce higher than the sender's next nonce
+    /// are queued while automining, instead of being rejected. Queued
+    /// transactions are promoted and mined once the nonce gap is filled.
+    pub queue_future_transactions: bool,
+}
+
+impl MemPoolPolicy {
+    /// Calculates the minimum fee that a replacement transaction needs to pay
+    /// to replace a transaction that pays the provided fee.
+    pub fn min_replacement_fee(&self, fee: u128) -> u128 {
+        let min_new_fee = fee.saturating_mul(100 + u128::from(self.price_bump_percentage));
+
+        let one_hundred = 100u128;
+        if min_new_fee % one_hundred == 0u128 {
+            min_new_fee / one_hundred
+        } else {
+            min_new_fee / one_hundred + 1u128
+        }
+    }
+}
+
+impl Default for MemPoolPolicy {
+    fn default() -> Self {
+        Self {
+            price_bump_percentage: DEFAULT_PRICE_BUMP_PERCENTAGE,
+            max_transactions: None,
+            max_transactions_per_sender: None,
+            transaction_lifetime: None,
+            queue_future_transactions: false,
+        }
+    }
+}
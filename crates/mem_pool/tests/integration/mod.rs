@@ -1,13 +1,17 @@
-use core::num::NonZeroU64;
+use core::{
+    num::{NonZeroU64, NonZeroUsize},
+    time::Duration,
+};
 
 use edr_chain_spec::ExecutableTransaction;
-use edr_mem_pool::{MemPoolAddTransactionError, OrderedTransaction};
+use edr_mem_pool::{MemPoolAddTransactionError, MemPoolPolicy, OrderedTransaction};
 use edr_primitives::{Address, U256};
 use edr_state_api::{account::AccountInfo, AccountModifierFn, StateDebug};
 use edr_test_mem_pool::MemPoolTestFixture;
 use edr_test_transaction::{
     dummy_eip1559_transaction, dummy_eip155_transaction, dummy_eip155_transaction_with_limit,
     dummy_eip155_transaction_with_price, dummy_eip155_transaction_with_price_limit_and_value,
+    dummy_eip4844_transaction,
 };
 
 #[test]
@@ -441,6 +445,25 @@ fn add_transaction_insufficient_funds() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn add_transaction_insufficient_funds_with_disabled_balance_check() -> anyhow::Result<()> {
+    let sender = Address::random();
+    let mut fixture = MemPoolTestFixture::with_accounts(&[(sender, AccountInfo::default())]);
+    fixture.mem_pool.set_balance_check_disabled(true);
+
+    let transaction =
+        dummy_eip155_transaction_with_price_limit_and_value(sender, 0, 900, 21_000, U256::from(5))?;
+    fixture.add_transaction(transaction.clone())?;
+
+    fixture.update()?;
+
+    let pending_transactions = fixture.mem_pool.pending_transactions().collect::<Vec<_>>();
+    assert_eq!(pending_transactions.len(), 1);
+    assert_eq!(*pending_transactions[0].pending(), transaction);
+
+    Ok(())
+}
+
 #[test]
 fn add_transaction_ordering() -> anyhow::Result<()> {
     let sender = Address::random();
@@ -707,6 +730,36 @@ fn set_block_gas_limit_removes_invalid_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn set_transaction_gas_cap_removes_invalid_transactions() -> anyhow::Result<()> {
+    const TRANSACTION_GAS_CAP: u64 = 5_000_000;
+
+    let sender = Address::random();
+
+    let mut fixture = MemPoolTestFixture::with_accounts(&[(sender, AccountInfo::default())]);
+
+    let transaction1 = dummy_eip155_transaction_with_limit(sender, 0, TRANSACTION_GAS_CAP)?;
+    fixture.add_transaction(transaction1.clone())?;
+
+    let transaction2 = dummy_eip155_transaction_with_limit(sender, 1, TRANSACTION_GAS_CAP + 1)?;
+    fixture.add_transaction(transaction2)?;
+
+    let removed = fixture
+        .mem_pool
+        .set_transaction_gas_cap(&fixture.state, Some(TRANSACTION_GAS_CAP))?;
+    assert_eq!(removed.len(), 1);
+    assert_eq!(
+        fixture.mem_pool.transaction_gas_cap(),
+        Some(TRANSACTION_GAS_CAP)
+    );
+
+    let pending_transactions = fixture.mem_pool.pending_transactions().collect::<Vec<_>>();
+    assert_eq!(pending_transactions.len(), 1);
+    assert_eq!(*pending_transactions[0].pending(), transaction1);
+
+    Ok(())
+}
+
 #[test]
 fn set_block_gas_limit_moves_future_to_pending_queue() -> anyhow::Result<()> {
     let sender = Address::random();
@@ -833,3 +886,306 @@ fn update_removes_transactions_with_insufficient_balance() -> anyhow::Result<()>
 
     Ok(())
 }
+
+fn funded_fixture(senders: &[Address]) -> MemPoolTestFixture {
+    let accounts = senders
+        .iter()
+        .map(|sender| {
+            (
+                *sender,
+                AccountInfo {
+                    balance: U256::from(10_000_000u64),
+                    ..AccountInfo::default()
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    MemPoolTestFixture::with_accounts(&accounts)
+}
+
+#[test]
+fn replace_transaction_with_custom_price_bump() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+    fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            price_bump_percentage: 50,
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+
+    let transaction1 = dummy_eip155_transaction_with_price(sender, 0, 20)?;
+    fixture.add_transaction(transaction1)?;
+
+    let transaction2 = dummy_eip155_transaction_with_price(sender, 0, 29)?;
+    let result = fixture.add_transaction(transaction2);
+    assert!(matches!(
+        result,
+        Err(MemPoolAddTransactionError::ReplacementMaxFeePerGasTooLow {
+            min_new_max_fee_per_gas: 30,
+            transaction_nonce: 0,
+        })
+    ));
+
+    let transaction3 = dummy_eip155_transaction_with_price(sender, 0, 30)?;
+    fixture.add_transaction(transaction3.clone())?;
+
+    let pending_transactions = fixture.mem_pool.pending_transactions().collect::<Vec<_>>();
+    assert_eq!(pending_transactions.len(), 1);
+    assert_eq!(*pending_transactions[0].pending(), transaction3);
+
+    Ok(())
+}
+
+#[test]
+fn replace_blob_transaction_max_fee_per_blob_gas_too_low() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+
+    let transaction1 = dummy_eip4844_transaction(sender, 0, 20, 20, 20)?;
+    fixture.add_transaction(transaction1)?;
+
+    // Bumping only the fees per gas is insufficient
+    let transaction2 = dummy_eip4844_transaction(sender, 0, 22, 22, 21)?;
+    let result = fixture.add_transaction(transaction2);
+    assert!(matches!(
+        result,
+        Err(
+            MemPoolAddTransactionError::ReplacementMaxFeePerBlobGasTooLow {
+                min_new_max_fee_per_blob_gas: 22,
+                transaction_nonce: 0,
+            }
+        )
+    ));
+
+    // A transaction without blobs can't replace a blob transaction
+    let transaction3 = dummy_eip1559_transaction(sender, 0, 22, 22)?;
+    let result = fixture.add_transaction(transaction3);
+    assert!(matches!(
+        result,
+        Err(
+            MemPoolAddTransactionError::ReplacementMaxFeePerBlobGasTooLow {
+                min_new_max_fee_per_blob_gas: 22,
+                transaction_nonce: 0,
+            }
+        )
+    ));
+
+    let transaction4 = dummy_eip4844_transaction(sender, 0, 22, 22, 22)?;
+    fixture.add_transaction(transaction4.clone())?;
+
+    let pending_transactions = fixture.mem_pool.pending_transactions().collect::<Vec<_>>();
+    assert_eq!(pending_transactions.len(), 1);
+    assert_eq!(*pending_transactions[0].pending(), transaction4);
+
+    Ok(())
+}
+
+#[test]
+fn max_transactions_evicts_lowest_gas_price() -> anyhow::Result<()> {
+    let sender1 = Address::random();
+    let sender2 = Address::random();
+    let sender3 = Address::random();
+    let sender4 = Address::random();
+
+    let mut fixture = funded_fixture(&[sender1, sender2, sender3, sender4]);
+    fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            max_transactions: NonZeroUsize::new(2),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+
+    let transaction1 = dummy_eip155_transaction_with_price(sender1, 0, 10)?;
+    fixture.add_transaction(transaction1.clone())?;
+
+    let transaction2 = dummy_eip155_transaction_with_price(sender2, 0, 5)?;
+    fixture.add_transaction(transaction2)?;
+
+    let transaction3 = dummy_eip155_transaction_with_price(sender3, 0, 20)?;
+    fixture.add_transaction(transaction3.clone())?;
+
+    let pending_transactions = fixture.mem_pool.transactions().cloned().collect::<Vec<_>>();
+    assert_eq!(pending_transactions, vec![transaction1, transaction3]);
+
+    // A transaction with the lowest gas price is rejected
+    let transaction4 = dummy_eip155_transaction_with_price(sender4, 0, 1)?;
+    let result = fixture.add_transaction(transaction4);
+    assert!(matches!(
+        result,
+        Err(MemPoolAddTransactionError::MemPoolFull { max_transactions }) if max_transactions.get() == 2
+    ));
+    assert_eq!(fixture.mem_pool.transactions().count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn max_transactions_evicts_future_transactions_first() -> anyhow::Result<()> {
+    let sender1 = Address::random();
+    let sender2 = Address::random();
+
+    let mut fixture = funded_fixture(&[sender1, sender2]);
+    fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            max_transactions: NonZeroUsize::new(2),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+
+    let transaction1 = dummy_eip155_transaction_with_price(sender1, 0, 5)?;
+    fixture.add_transaction(transaction1)?;
+
+    let transaction2 = dummy_eip155_transaction_with_price(sender1, 2, 100)?;
+    fixture.add_transaction(transaction2)?;
+
+    let transaction3 = dummy_eip155_transaction_with_price(sender2, 0, 1)?;
+    fixture.add_transaction(transaction3)?;
+
+    assert!(!fixture.mem_pool.has_future_transactions());
+    assert_eq!(fixture.mem_pool.pending_transactions().count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn max_transactions_per_sender() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+    fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            max_transactions_per_sender: NonZeroUsize::new(2),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 0, 10)?)?;
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 3, 10)?)?;
+
+    let result = fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 1, 10)?);
+    assert!(matches!(
+        result,
+        Err(MemPoolAddTransactionError::SenderTransactionLimitReached {
+            sender: limited_sender,
+            max_transactions_per_sender,
+        }) if limited_sender == sender && max_transactions_per_sender.get() == 2
+    ));
+
+    // Replacements are still allowed
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 3, 20)?)?;
+
+    Ok(())
+}
+
+#[test]
+fn set_policy_removes_transactions_exceeding_sender_limit() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+
+    let transaction1 = dummy_eip155_transaction_with_price(sender, 0, 10)?;
+    fixture.add_transaction(transaction1.clone())?;
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 1, 10)?)?;
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 3, 10)?)?;
+
+    let removed = fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            max_transactions_per_sender: NonZeroUsize::new(1),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+    assert_eq!(removed.len(), 2);
+
+    let transactions = fixture.mem_pool.transactions().cloned().collect::<Vec<_>>();
+    assert_eq!(transactions, vec![transaction1]);
+
+    Ok(())
+}
+
+#[test]
+fn set_policy_removes_future_transactions_with_highest_nonce_first() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+
+    let transaction1 = dummy_eip155_transaction_with_price(sender, 0, 10)?;
+    fixture.add_transaction(transaction1.clone())?;
+    // Future transactions are added out of nonce order
+    fixture.add_transaction(dummy_eip155_transaction_with_price(sender, 3, 10)?)?;
+    let transaction2 = dummy_eip155_transaction_with_price(sender, 2, 10)?;
+    fixture.add_transaction(transaction2.clone())?;
+
+    let removed = fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            max_transactions_per_sender: NonZeroUsize::new(2),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].pending().nonce(), 3);
+
+    let transactions = fixture.mem_pool.transactions().cloned().collect::<Vec<_>>();
+    assert_eq!(transactions, vec![transaction1, transaction2]);
+
+    Ok(())
+}
+
+#[test]
+fn remove_expired_transactions() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+    let transaction_lifetime = Duration::from_secs(60);
+    fixture.mem_pool.set_policy(
+        MemPoolPolicy {
+            transaction_lifetime: Some(transaction_lifetime),
+            ..MemPoolPolicy::default()
+        },
+        fixture.now,
+    );
+
+    fixture.add_transaction(dummy_eip155_transaction(sender, 0)?)?;
+    fixture.add_transaction(dummy_eip155_transaction(sender, 2)?)?;
+
+    let removed = fixture
+        .mem_pool
+        .remove_expired_transactions(fixture.now + 59);
+    assert!(removed.is_empty());
+
+    let removed = fixture
+        .mem_pool
+        .remove_expired_transactions(fixture.now + transaction_lifetime.as_secs());
+    assert_eq!(removed.len(), 2);
+    assert!(!fixture.mem_pool.has_pending_transactions());
+    assert!(!fixture.mem_pool.has_future_transactions());
+
+    Ok(())
+}
+
+#[test]
+fn remove_all_transactions() -> anyhow::Result<()> {
+    let sender = Address::random();
+
+    let mut fixture = funded_fixture(&[sender]);
+
+    fixture.add_transaction(dummy_eip155_transaction(sender, 0)?)?;
+    fixture.add_transaction(dummy_eip155_transaction(sender, 2)?)?;
+
+    let removed = fixture.mem_pool.remove_all_transactions();
+    assert_eq!(removed.len(), 2);
+    assert!(!fixture.mem_pool.has_pending_transactions());
+    assert!(!fixture.mem_pool.has_future_transactions());
+    assert_eq!(fixture.mem_pool.transactions().count(), 0);
+
+    Ok(())
+}
@@ -1,17 +1,50 @@
 //! Types for EVM precompiles.
 #![warn(missing_docs)]
 
-use std::marker::PhantomData;
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 use edr_primitives::{Address, Bytes, HashMap, HashSet};
 use revm_context_interface::{Cfg, ContextTr as ContextTrait, JournalTr as _, LocalContextTr as _};
 pub use revm_handler::{EthPrecompiles, PrecompileProvider};
 use revm_interpreter::{CallInput, CallInputs, Gas, InstructionResult, InterpreterResult};
 pub use revm_precompile::{
-    secp256r1, u64_to_address, Precompile, PrecompileError, PrecompileFn, PrecompileSpecId,
-    Precompiles,
+    secp256r1, u64_to_address, Precompile, PrecompileError, PrecompileFn, PrecompileOutput,
+    PrecompileResult, PrecompileSpecId, Precompiles,
 };
 
+/// A custom precompile, which is implemented either by a native precompile
+/// function or by a closure. Closures allow embedders to define precompiles
+/// that capture state.
+#[derive(Clone)]
+pub struct CustomPrecompile(Arc<dyn Fn(&[u8], u64) -> PrecompileResult + Send + Sync>);
+
+impl CustomPrecompile {
+    /// Creates a new instance from the provided closure, which receives the
+    /// input and gas limit of a call.
+    pub fn new(
+        precompile: impl Fn(&[u8], u64) -> PrecompileResult + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(precompile))
+    }
+
+    /// Executes the precompile with the provided input and gas limit.
+    pub fn call(&self, input: &[u8], gas_limit: u64) -> PrecompileResult {
+        (self.0)(input, gas_limit)
+    }
+}
+
+impl Debug for CustomPrecompile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomPrecompile").finish_non_exhaustive()
+    }
+}
+
+impl From<PrecompileFn> for CustomPrecompile {
+    fn from(value: PrecompileFn) -> Self {
+        Self::new(value)
+    }
+}
+
 /// A precompile provider that allows adding custom or overwriting existing
 /// precompiles.
 #[derive(Clone)]
@@ -20,7 +53,7 @@ pub struct OverriddenPrecompileProvider<
     ContextT: ContextTrait,
 > {
     base: BaseProviderT,
-    custom_precompiles: HashMap<Address, PrecompileFn>,
+    custom_precompiles: HashMap<Address, CustomPrecompile>,
     // Cache of unique addresses to avoid reporting duplicates between `base` and
     // `custom_precompiles`. This speeds up the `warm_addresses` method.
     unique_addresses: HashSet<Address>,
@@ -40,7 +73,7 @@ impl<
     /// Creates a new custom precompile provider with custom precompiles.
     pub fn with_precompiles(
         base: BaseProviderT,
-        custom_precompiles: HashMap<Address, PrecompileFn>,
+        custom_precompiles: HashMap<Address, CustomPrecompile>,
     ) -> Self {
         let unique_addresses = custom_precompiles
             .keys()
@@ -57,7 +90,7 @@ impl<
     }
 
     /// Adds a custom precompile.
-    pub fn set_precompile(&mut self, address: Address, precompile: PrecompileFn) {
+    pub fn set_precompile(&mut self, address: Address, precompile: CustomPrecompile) {
         self.custom_precompiles.insert(address, precompile);
         self.unique_addresses.insert(address);
     }
@@ -112,7 +145,7 @@ impl<
                 }
                 CallInput::Bytes(bytes) => bytes.0.iter().as_slice(),
             };
-            (*precompile)(input_bytes, inputs.gas_limit)
+            precompile.call(input_bytes, inputs.gas_limit)
         };
 
         match exec_result {
@@ -1,4 +1,7 @@
-use edr_primitives::{Address, HashMap, KECCAK_EMPTY, U256};
+use std::{collections::BTreeMap, fmt};
+
+use edr_primitives::{hash_map::Entry, Address, HashMap, B256, KECCAK_EMPTY, U256, U64};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     account::{Account, AccountInfo, AccountStatus},
@@ -10,6 +13,9 @@ use crate::{
 #[derive(Clone, Debug, Default)]
 pub struct StateDiff {
     inner: HashMap<Address, Account>,
+    /// The account information before the first change, if it was recorded.
+    /// `None` if the account didn't exist.
+    original_infos: HashMap<Address, Option<AccountInfo>>,
 }
 
 /// Checks if the account info has code (non-empty code hash).
@@ -93,15 +99,353 @@ impl StateDiff {
         }
     }
 
+    /// Applies the diff of the transaction with the provided id to this
+    /// instance, combining with any and all existing changes, and records the
+    /// transaction id for every change.
+    ///
+    /// In contrast to [`StateDiff::apply_diff`], the original values of
+    /// storage slots that were already changed are preserved, such that the
+    /// resulting diff spans all applied transactions.
+    ///
+    /// If the transaction created or self-destructed the account, the account's
+    /// earlier changes are replaced, as its storage was cleared.
+    ///
+    /// Returns the storage slots that the transaction wrote without having
+    /// observed the value written by an earlier transaction. Account
+    /// information is not checked for conflicts, as diffs don't record the
+    /// account information that a transaction observed.
+    pub fn apply_transaction_diff(
+        &mut self,
+        transaction_id: usize,
+        diff: HashMap<Address, Account>,
+    ) -> Vec<StorageConflict> {
+        let mut conflicts = Vec::new();
+
+        for (address, mut account_diff) in diff {
+            account_diff.transaction_id = transaction_id;
+            account_diff
+                .storage
+                .values_mut()
+                .for_each(|slot| slot.transaction_id = transaction_id);
+
+            match self.inner.entry(address) {
+                Entry::Occupied(mut entry)
+                    if account_diff.is_created() || account_diff.is_selfdestructed() =>
+                {
+                    // The account's storage was cleared, so earlier changes no longer apply
+                    entry.insert(account_diff);
+                }
+                Entry::Occupied(mut entry) => {
+                    let account = entry.get_mut();
+                    account.info = account_diff.info;
+                    account.status.insert(account_diff.status);
+                    account.transaction_id = transaction_id;
+
+                    for (index, slot) in account_diff.storage {
+                        match account.storage.entry(index) {
+                            Entry::Occupied(mut entry) => {
+                                let previous = entry.get_mut();
+                                if previous.transaction_id != transaction_id
+                                    && previous.present_value() != slot.original_value()
+                                {
+                                    conflicts.push(StorageConflict {
+                                        address,
+                                        index,
+                                        previous_transaction_id: previous.transaction_id,
+                                        previous_value: previous.present_value(),
+                                        transaction_id,
+                                        observed_value: slot.original_value(),
+                                    });
+                                }
+
+                                previous.present_value = slot.present_value();
+                                previous.transaction_id = transaction_id;
+                            }
+                            Entry::Vacant(entry) => {
+                                entry.insert(slot);
+                            }
+                        }
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(account_diff);
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Squashes a sequence of per-transaction diffs into a single diff, e.g.
+    /// of a block, using the position of each diff in the sequence as its
+    /// transaction id.
+    ///
+    /// Returns the squashed diff and the storage slots that were written by
+    /// transactions that didn't observe the value written by an earlier
+    /// transaction. See [`StateDiff::apply_transaction_diff`].
+    pub fn squash(
+        diffs: impl IntoIterator<Item = HashMap<Address, Account>>,
+    ) -> (Self, Vec<StorageConflict>) {
+        let mut squashed = Self::default();
+        let conflicts = diffs
+            .into_iter()
+            .enumerate()
+            .flat_map(|(transaction_id, diff)| {
+                squashed.apply_transaction_diff(transaction_id, diff)
+            })
+            .collect();
+
+        (squashed, conflicts)
+    }
+
+    /// Records the account information of the provided address before it
+    /// was changed, which is used to display the account's changes. Only the
+    /// first recorded account information is retained, such that the diff
+    /// spans all applied changes.
+    pub fn record_original_account_info(
+        &mut self,
+        address: Address,
+        account_info: Option<AccountInfo>,
+    ) {
+        self.original_infos.entry(address).or_insert(account_info);
+    }
+
     /// Retrieves the inner hash map.
     pub fn as_inner(&self) -> &HashMap<Address, Account> {
         &self.inner
     }
 }
 
+/// A write to a storage slot by a transaction that didn't observe the value
+/// written to the same slot by an earlier transaction, e.g. because the
+/// transactions were executed in parallel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageConflict {
+    /// The address of the account
+    pub address: Address,
+    /// The index of the storage slot
+    pub index: U256,
+    /// The id of the earlier transaction
+    pub previous_transaction_id: usize,
+    /// The value written by the earlier transaction
+    pub previous_value: U256,
+    /// The id of the conflicting transaction
+    pub transaction_id: usize,
+    /// The value observed by the conflicting transaction
+    pub observed_value: U256,
+}
+
+/// Formats the diff with one account per line, followed by its changed
+/// storage slots. Accounts and storage slots are sorted, to produce a stable
+/// representation.
+///
+/// Account fields are formatted as `before -> after` if the account's
+/// original information was recorded, and as their new value otherwise.
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accounts = self.to_serializable();
+        if accounts.is_empty() {
+            return f.write_str("no changes");
+        }
+
+        for (idx, (address, account)) in accounts.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            if let Some(original) = &account.original {
+                write!(
+                    f,
+                    "{address}: balance {} -> {}, nonce {} -> {}, code hash {} -> {}",
+                    original.balance,
+                    account.balance,
+                    original.nonce,
+                    account.nonce,
+                    original.code_hash,
+                    account.code_hash
+                )?;
+            } else {
+                write!(
+                    f,
+                    "{address}: balance {}, nonce {}, code hash {}",
+                    account.balance, account.nonce, account.code_hash
+                )?;
+            }
+
+            if account.created {
+                f.write_str(" (created)")?;
+            }
+            if account.self_destructed {
+                f.write_str(" (self-destructed)")?;
+            }
+
+            for (index, slot) in &account.storage {
+                write!(
+                    f,
+                    "\n  storage[{index:#x}]: {:#x} -> {:#x}",
+                    slot.from, slot.to
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Serializes the diff as a map of addresses to the accounts' new balance,
+/// nonce, and code hash, together with the original and present values of
+/// changed storage slots. The original balance, nonce, and code hash are
+/// included if they were recorded. Accounts and storage slots are sorted, to
+/// produce a stable representation.
+impl Serialize for StateDiff {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_serializable().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StateDiff {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let accounts = BTreeMap::<Address, SerializableAccount>::deserialize(deserializer)?;
+
+        let original_infos = accounts
+            .iter()
+            .filter_map(|(address, account)| {
+                let original = account.original.as_ref()?;
+
+                let account_info = AccountInfo {
+                    balance: original.balance,
+                    nonce: original.nonce.to(),
+                    code_hash: original.code_hash,
+                    code: None,
+                };
+
+                Some((*address, Some(account_info)))
+            })
+            .collect();
+
+        let inner = accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let mut status = AccountStatus::Touched;
+                if account.created {
+                    status.insert(AccountStatus::Created);
+                }
+                if account.self_destructed {
+                    status.insert(AccountStatus::SelfDestructed);
+                }
+
+                let storage = account
+                    .storage
+                    .into_iter()
+                    .map(|(index, slot)| {
+                        (index, EvmStorageSlot::new_changed(slot.from, slot.to, 0))
+                    })
+                    .collect();
+
+                let account = Account {
+                    info: AccountInfo {
+                        balance: account.balance,
+                        nonce: account.nonce.to(),
+                        code_hash: account.code_hash,
+                        code: None,
+                    },
+                    storage,
+                    status,
+                    transaction_id: 0,
+                };
+
+                (address, account)
+            })
+            .collect();
+
+        Ok(Self {
+            inner,
+            original_infos,
+        })
+    }
+}
+
+impl StateDiff {
+    fn to_serializable(&self) -> BTreeMap<Address, SerializableAccount> {
+        self.inner
+            .iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(index, slot)| {
+                        (
+                            *index,
+                            SerializableSlotChange {
+                                from: slot.original_value(),
+                                to: slot.present_value(),
+                            },
+                        )
+                    })
+                    .collect();
+
+                let original = self.original_infos.get(address).map(|original| {
+                    let original = original.clone().unwrap_or_default();
+
+                    SerializableAccountInfo {
+                        balance: original.balance,
+                        nonce: U64::from(original.nonce),
+                        code_hash: original.code_hash,
+                    }
+                });
+
+                let account = SerializableAccount {
+                    balance: account.info.balance,
+                    nonce: U64::from(account.info.nonce),
+                    code_hash: account.info.code_hash,
+                    created: account.is_created(),
+                    self_destructed: account.is_selfdestructed(),
+                    storage,
+                    original,
+                };
+
+                (*address, account)
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableAccount {
+    balance: U256,
+    nonce: U64,
+    code_hash: B256,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    created: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    self_destructed: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    storage: BTreeMap<U256, SerializableSlotChange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original: Option<SerializableAccountInfo>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableAccountInfo {
+    balance: U256,
+    nonce: U64,
+    code_hash: B256,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SerializableSlotChange {
+    from: U256,
+    to: U256,
+}
+
 impl From<HashMap<Address, Account>> for StateDiff {
     fn from(value: HashMap<Address, Account>) -> Self {
-        Self { inner: value }
+        Self {
+            inner: value,
+            original_infos: HashMap::default(),
+        }
     }
 }
 
@@ -241,6 +585,237 @@ mod tests {
         assert_eq!(account.info.nonce, 2);
     }
 
+    fn diff_with_storage_change() -> (StateDiff, Address) {
+        let mut diff = StateDiff::default();
+        let address = Address::with_last_byte(1);
+
+        diff.apply_account_change(address, account_info_without_code(U256::from(1000), 2));
+        diff.apply_storage_change(
+            address,
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::from(2), U256::from(42), 0),
+            None,
+        );
+
+        (diff, address)
+    }
+
+    #[test]
+    fn display_is_sorted_and_human_readable() {
+        let (mut diff, _address) = diff_with_storage_change();
+        diff.apply_account_change(Address::ZERO, account_info_without_code(U256::from(5), 0));
+
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "0x0000000000000000000000000000000000000000: balance 5, nonce 0, code hash {KECCAK_EMPTY}\n\
+                 0x0000000000000000000000000000000000000001: balance 1000, nonce 2, code hash {KECCAK_EMPTY}\n  \
+                 storage[0x1]: 0x2 -> 0x2a"
+            )
+        );
+        assert_eq!(StateDiff::default().to_string(), "no changes");
+    }
+
+    #[test]
+    fn display_and_serialize_recorded_original_values() -> anyhow::Result<()> {
+        let (mut diff, address) = diff_with_storage_change();
+        diff.record_original_account_info(
+            address,
+            Some(account_info_without_code(U256::from(500), 1)),
+        );
+        // Later recordings don't replace the original account information
+        diff.record_original_account_info(address, None);
+
+        let created = Address::with_last_byte(2);
+        diff.apply_account_change(created, account_info_without_code(U256::from(5), 0));
+        diff.record_original_account_info(created, None);
+
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "0x0000000000000000000000000000000000000001: balance 500 -> 1000, nonce 1 -> 2, code hash {KECCAK_EMPTY} -> {KECCAK_EMPTY}\n  \
+                 storage[0x1]: 0x2 -> 0x2a\n\
+                 0x0000000000000000000000000000000000000002: balance 0 -> 5, nonce 0 -> 0, code hash {KECCAK_EMPTY} -> {KECCAK_EMPTY}"
+            )
+        );
+
+        let json = serde_json::to_value(&diff)?;
+        assert_eq!(
+            json[address.to_string()]["original"],
+            serde_json::json!({
+                "balance": "0x1f4",
+                "nonce": "0x1",
+                "codeHash": KECCAK_EMPTY,
+            })
+        );
+
+        let deserialized: StateDiff = serde_json::from_value(json)?;
+        assert_eq!(deserialized.to_string(), diff.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn serde_roundtrip() -> anyhow::Result<()> {
+        let (diff, address) = diff_with_storage_change();
+
+        let json = serde_json::to_value(&diff)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "0x0000000000000000000000000000000000000001": {
+                    "balance": "0x3e8",
+                    "nonce": "0x2",
+                    "codeHash": KECCAK_EMPTY,
+                    "storage": {
+                        "0x1": { "from": "0x2", "to": "0x2a" }
+                    }
+                }
+            })
+        );
+
+        let deserialized: StateDiff = serde_json::from_value(json)?;
+        let account = deserialized
+            .as_inner()
+            .get(&address)
+            .expect("account should exist");
+        assert_eq!(account.info.balance, U256::from(1000));
+        assert_eq!(account.info.nonce, 2);
+
+        let slot = account
+            .storage
+            .get(&U256::from(1))
+            .expect("slot should exist");
+        assert_eq!(slot.original_value(), U256::from(2));
+        assert_eq!(slot.present_value(), U256::from(42));
+
+        Ok(())
+    }
+
+    fn transaction_diff(
+        address: Address,
+        balance: u64,
+        storage: impl IntoIterator<Item = (u64, u64, u64)>,
+    ) -> HashMap<Address, Account> {
+        let storage = storage
+            .into_iter()
+            .map(|(index, original, present)| {
+                (
+                    U256::from(index),
+                    EvmStorageSlot::new_changed(U256::from(original), U256::from(present), 0),
+                )
+            })
+            .collect();
+
+        let account = Account {
+            info: account_info_without_code(U256::from(balance), 0),
+            storage,
+            status: AccountStatus::Touched,
+            transaction_id: 0,
+        };
+
+        std::iter::once((address, account)).collect()
+    }
+
+    #[test]
+    fn squash_preserves_original_values_and_records_transaction_ids() {
+        let address = Address::random();
+
+        let (diff, conflicts) = StateDiff::squash([
+            transaction_diff(address, 100, [(0, 1, 2), (1, 0, 5)]),
+            transaction_diff(address, 200, [(0, 2, 3)]),
+        ]);
+        assert!(conflicts.is_empty());
+
+        let account = diff.as_inner().get(&address).expect("account should exist");
+        assert_eq!(account.info.balance, U256::from(200));
+        assert_eq!(account.transaction_id, 1);
+
+        let slot = account.storage.get(&U256::ZERO).expect("slot should exist");
+        assert_eq!(slot.original_value(), U256::from(1));
+        assert_eq!(slot.present_value(), U256::from(3));
+        assert_eq!(slot.transaction_id, 1);
+
+        let slot = account
+            .storage
+            .get(&U256::from(1))
+            .expect("slot should exist");
+        assert_eq!(slot.present_value(), U256::from(5));
+        assert_eq!(slot.transaction_id, 0);
+    }
+
+    #[test]
+    fn squash_detects_stale_storage_writes() {
+        let address = Address::random();
+
+        let (diff, conflicts) = StateDiff::squash([
+            transaction_diff(address, 100, [(0, 1, 2)]),
+            // Didn't observe the write of the first transaction
+            transaction_diff(address, 100, [(0, 1, 4)]),
+        ]);
+
+        assert_eq!(
+            conflicts,
+            vec![StorageConflict {
+                address,
+                index: U256::ZERO,
+                previous_transaction_id: 0,
+                previous_value: U256::from(2),
+                transaction_id: 1,
+                observed_value: U256::from(1),
+            }]
+        );
+
+        let slot = diff.as_inner()[&address]
+            .storage
+            .get(&U256::ZERO)
+            .expect("slot should exist");
+        assert_eq!(slot.original_value(), U256::from(1));
+        assert_eq!(slot.present_value(), U256::from(4));
+    }
+
+    #[test]
+    fn squash_clears_storage_of_recreated_account() {
+        let address = Address::random();
+
+        let mut self_destructed = transaction_diff(address, 0, []);
+        self_destructed
+            .get_mut(&address)
+            .expect("account should exist")
+            .mark_selfdestruct();
+
+        let mut created = transaction_diff(address, 300, [(1, 0, 7)]);
+        created
+            .get_mut(&address)
+            .expect("account should exist")
+            .mark_created();
+
+        let (diff, conflicts) =
+            StateDiff::squash([transaction_diff(address, 100, [(0, 1, 2)]), self_destructed]);
+        assert!(conflicts.is_empty());
+
+        let account = &diff.as_inner()[&address];
+        assert!(account.is_selfdestructed());
+        assert!(account.storage.is_empty());
+
+        let (diff, conflicts) =
+            StateDiff::squash([transaction_diff(address, 100, [(0, 1, 2)]), created]);
+        assert!(conflicts.is_empty());
+
+        let account = &diff.as_inner()[&address];
+        assert!(account.is_created());
+        assert_eq!(account.info.balance, U256::from(300));
+        assert_eq!(account.transaction_id, 1);
+        assert_eq!(account.storage.len(), 1);
+        assert_eq!(
+            account
+                .storage
+                .get(&U256::from(1))
+                .map(EvmStorageSlot::present_value),
+            Some(U256::from(7))
+        );
+    }
+
     /// Test the full load_state simulation: balance -> nonce -> code -> storage
     #[test]
     fn simulate_load_state_with_contract() {
@@ -24,4 +24,9 @@ impl IrregularState {
     pub fn state_overrides(&self) -> &BTreeMap<u64, StateOverride> {
         &self.block_number_to_override
     }
+
+    /// Removes the state overrides of blocks after the specified block number.
+    pub fn remove_state_overrides_after(&mut self, block_number: u64) {
+        self.block_number_to_override.split_off(&(block_number + 1));
+    }
 }
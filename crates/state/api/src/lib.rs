@@ -15,7 +15,12 @@ use edr_trie::sec_trie_root;
 pub use revm_database_interface::DatabaseCommit as StateCommit;
 pub use revm_state::{EvmState, EvmStorage, EvmStorageSlot};
 
-pub use self::{diff::StateDiff, error::StateError, r#dyn::DynState, r#override::StateOverride};
+pub use self::{
+    diff::{StateDiff, StorageConflict},
+    error::StateError,
+    r#dyn::DynState,
+    r#override::StateOverride,
+};
 use crate::account::{AccountInfo, BasicAccount};
 
 /// Account storage mapping of indices to values.
@@ -88,6 +93,28 @@ pub trait StateDebug {
         account_info: AccountInfo,
     ) -> Result<(), Self::Error>;
 
+    /// Returns an iterator over the addresses and information of all accounts
+    /// in the state, in no particular order. The code of accounts is not
+    /// included.
+    ///
+    /// States that are backed by a remote node only include accounts that are
+    /// known locally, i.e. accounts that were modified or already fetched.
+    #[allow(clippy::type_complexity)]
+    fn iter_accounts(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = (Address, AccountInfo)> + '_>, Self::Error>;
+
+    /// Returns an iterator over the non-zero storage slots of the account at
+    /// the specified address, as pairs of indices and values, in no particular
+    /// order.
+    ///
+    /// States that are backed by a remote node only include storage slots that
+    /// are known locally, i.e. slots that were modified or already fetched.
+    fn iter_storage(
+        &self,
+        address: &Address,
+    ) -> Result<Box<dyn Iterator<Item = (U256, U256)> + '_>, Self::Error>;
+
     /// Modifies the account at the specified address using the provided
     /// function.
     ///
@@ -115,8 +142,26 @@ pub trait StateDebug {
         value: U256,
     ) -> Result<U256, Self::Error>;
 
+    /// Overrides the storage root of the account at the specified address,
+    /// which is used instead of the storage's actual root when calculating
+    /// the state root. Pass `None` to remove the override.
+    fn set_account_storage_root(
+        &mut self,
+        address: Address,
+        storage_root: Option<B256>,
+    ) -> Result<(), Self::Error>;
+
     /// Retrieves the storage root of the database.
     fn state_root(&self) -> Result<B256, Self::Error>;
+
+    /// Overrides the state root that is reported until the state is modified.
+    ///
+    /// Only states whose state root isn't derived from their contents, like
+    /// states that are backed by a remote node, support overrides. Returns
+    /// whether the override was applied.
+    fn override_state_root(&mut self, _state_root: B256) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
 }
 
 /// Trait for reading state information.
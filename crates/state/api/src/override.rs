@@ -1,4 +1,4 @@
-use edr_primitives::B256;
+use edr_primitives::{Address, HashMap, B256};
 
 use crate::StateDiff;
 
@@ -8,6 +8,9 @@ use crate::StateDiff;
 pub struct StateOverride {
     /// The diff to be applied.
     pub diff: StateDiff,
+    /// The storage root overrides to be applied after the diff. A `None` value
+    /// removes a previous override.
+    pub storage_roots: HashMap<Address, Option<B256>>,
     /// The resulting state root.
     pub state_root: B256,
 }
@@ -17,6 +20,7 @@ impl StateOverride {
     pub fn with_state_root(state_root: B256) -> Self {
         Self {
             diff: StateDiff::default(),
+            storage_roots: HashMap::default(),
             state_root,
         }
     }
@@ -22,6 +22,37 @@ pub type ForkedStateForChainSpec<ChainSpecT> = ForkedState<
     <ChainSpecT as RpcChainSpec>::RpcTransaction,
 >;
 
+/// Configuration for lazily backfilling the storage of an EIP-2935 history
+/// storage contract that was deployed at the fork block, using the block hashes
+/// of the remote chain.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockHashHistory {
+    /// The address of the history storage contract
+    pub address: Address,
+    /// The number of block hashes served by the history storage contract
+    pub serve_window: u64,
+    /// The block number of the fork
+    pub fork_block_number: u64,
+}
+
+impl BlockHashHistory {
+    /// Returns the number of the remote block whose hash is stored at the
+    /// provided storage index, if any.
+    fn block_number_at(&self, index: U256) -> Option<u64> {
+        let index = u64::try_from(index).ok()?;
+        if index >= self.serve_window {
+            return None;
+        }
+
+        // The hash of the fork block is only stored when the next block is mined
+        let last_block_number = self.fork_block_number.checked_sub(1)?;
+        let offset =
+            (last_block_number % self.serve_window + self.serve_window - index) % self.serve_window;
+
+        last_block_number.checked_sub(offset)
+    }
+}
+
 /// A database integrating the state from a remote node and the state from a
 /// local layered database.
 #[derive_where(Debug)]
@@ -37,6 +68,7 @@ pub struct ForkedState<
     current_state: RwLock<(B256, B256)>,
     hash_generator: Arc<Mutex<RandomHashGenerator>>,
     removed_remote_accounts: HashSet<Address>,
+    block_hash_history: Option<BlockHashHistory>,
 }
 
 impl<
@@ -67,9 +99,16 @@ impl<
             current_state: RwLock::new((state_root, local_root)),
             hash_generator,
             removed_remote_accounts: HashSet::default(),
+            block_hash_history: None,
         }
     }
 
+    /// Sets the configuration for backfilling the storage of the history
+    /// storage contract with remote block hashes.
+    pub fn set_block_hash_history(&mut self, block_hash_history: BlockHashHistory) {
+        self.block_hash_history = Some(block_hash_history);
+    }
+
     /// Overrides the state root of the fork state.
     pub fn set_state_root(&mut self, state_root: B256) {
         let local_root = self.local_state.state_root().unwrap();
@@ -93,6 +132,7 @@ impl<
             current_state: RwLock::new(*self.current_state.read()),
             hash_generator: self.hash_generator.clone(),
             removed_remote_accounts: self.removed_remote_accounts.clone(),
+            block_hash_history: self.block_hash_history,
         }
     }
 }
@@ -127,6 +167,16 @@ impl<
         let local = self.local_state.storage(address, index)?;
         if local != U256::ZERO || self.removed_storage_slots.contains(&(address, index)) {
             Ok(local)
+        } else if let Some(block_hash_history) = &self.block_hash_history
+            && block_hash_history.address == address
+        {
+            block_hash_history
+                .block_number_at(index)
+                .map_or(Ok(U256::ZERO), |block_number| {
+                    let block_hash = self.remote_state.lock().block_hash_mut(block_number)?;
+                    Ok(block_hash
+                        .map_or(U256::ZERO, |block_hash| U256::from_be_bytes(block_hash.0)))
+                })
         } else {
             self.remote_state.lock().storage_mut(address, index)
         }
@@ -175,6 +225,44 @@ impl<
         self.local_state.insert_account(address, account_info)
     }
 
+    fn iter_accounts(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = (Address, AccountInfo)> + '_>, Self::Error> {
+        let mut accounts: HashMap<Address, AccountInfo> =
+            self.local_state.iter_accounts()?.collect();
+
+        for (address, account_info) in self.remote_state.lock().cached_accounts() {
+            if !self.removed_remote_accounts.contains(address) {
+                accounts
+                    .entry(*address)
+                    .or_insert_with(|| account_info.clone());
+            }
+        }
+
+        Ok(Box::new(accounts.into_iter()))
+    }
+
+    fn iter_storage(
+        &self,
+        address: &Address,
+    ) -> Result<Box<dyn Iterator<Item = (U256, U256)> + '_>, Self::Error> {
+        let mut storage: HashMap<U256, U256> = self.local_state.iter_storage(address)?.collect();
+
+        for (index, value) in self.remote_state.lock().cached_storage(address) {
+            if !value.is_zero() && !self.removed_storage_slots.contains(&(*address, *index)) {
+                storage.entry(*index).or_insert(*value);
+            }
+        }
+
+        Ok(Box::new(storage.into_iter()))
+    }
+
+    fn override_state_root(&mut self, state_root: B256) -> Result<bool, Self::Error> {
+        self.set_state_root(state_root);
+
+        Ok(true)
+    }
+
     fn modify_account(
         &mut self,
         address: Address,
@@ -241,6 +329,25 @@ impl<
             })
     }
 
+    fn set_account_storage_root(
+        &mut self,
+        address: Address,
+        storage_root: Option<B256>,
+    ) -> Result<(), Self::Error> {
+        self.local_state
+            .set_account_storage_root_or_else(address, storage_root, &|| {
+                self.remote_state.lock().basic_mut(address)?.map_or_else(
+                    || {
+                        Ok(AccountInfo {
+                            code: None,
+                            ..AccountInfo::default()
+                        })
+                    },
+                    Ok,
+                )
+            })
+    }
+
     fn state_root(&self) -> Result<B256, Self::Error> {
         let local_root = self.local_state.state_root().unwrap();
 
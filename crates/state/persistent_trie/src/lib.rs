@@ -135,6 +135,22 @@ impl PersistentStateTrie {
         // If there is no old value, return zero to signal that the slot was empty
         Ok(old_value.unwrap_or(U256::ZERO))
     }
+
+    /// Overrides the storage root of the account at the specified address. If
+    /// the account does not exist, it is created using the provided
+    /// `default_account_fn`, unless the override is removed.
+    pub fn set_account_storage_root_or_else(
+        &mut self,
+        address: Address,
+        storage_root: Option<B256>,
+        default_account_fn: &dyn Fn() -> Result<AccountInfo, StateError>,
+    ) -> Result<(), StateError> {
+        self.accounts_and_storage.set_account_storage_root(
+            &address,
+            storage_root,
+            default_account_fn,
+        )
+    }
 }
 
 impl Default for PersistentStateTrie {
@@ -224,6 +240,26 @@ impl StateDebug for PersistentStateTrie {
         Ok(())
     }
 
+    fn iter_accounts(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = (Address, AccountInfo)> + '_>, Self::Error> {
+        Ok(Box::new(self.accounts_and_storage.accounts().map(
+            |(address, account)| (address, AccountInfo::from(account)),
+        )))
+    }
+
+    fn iter_storage(
+        &self,
+        address: &Address,
+    ) -> Result<Box<dyn Iterator<Item = (U256, U256)> + '_>, Self::Error> {
+        Ok(Box::new(
+            self.accounts_and_storage
+                .account_storage_slots(address)
+                .unwrap_or_default()
+                .into_iter(),
+        ))
+    }
+
     fn modify_account(
         &mut self,
         address: Address,
@@ -276,6 +312,19 @@ impl StateDebug for PersistentStateTrie {
         })
     }
 
+    fn set_account_storage_root(
+        &mut self,
+        address: Address,
+        storage_root: Option<B256>,
+    ) -> Result<(), Self::Error> {
+        self.set_account_storage_root_or_else(address, storage_root, &|| {
+            Ok(AccountInfo {
+                code: None,
+                ..AccountInfo::default()
+            })
+        })
+    }
+
     fn state_root(&self) -> Result<B256, Self::Error> {
         Ok(self.accounts_and_storage.state_root())
     }
@@ -342,4 +391,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn iterates_accounts_and_storage() -> anyhow::Result<()> {
+        let mut state = PersistentStateTrie::default();
+
+        let address1 = Address::random();
+        let address2 = Address::random();
+
+        state.insert_account(
+            address1,
+            AccountInfo {
+                balance: U256::from(10),
+                code: None,
+                ..AccountInfo::default()
+            },
+        )?;
+        state.set_account_storage_slot(address2, U256::from(1), U256::from(100))?;
+        state.set_account_storage_slot(address2, U256::from(2), U256::from(200))?;
+        state.set_account_storage_slot(address2, U256::from(3), U256::from(300))?;
+        state.set_account_storage_slot(address2, U256::from(2), U256::ZERO)?;
+
+        let mut accounts = state
+            .iter_accounts()?
+            .map(|(address, account_info)| (address, account_info.balance))
+            .collect::<Vec<_>>();
+        accounts.sort();
+
+        let mut expected_accounts = vec![(address1, U256::from(10)), (address2, U256::ZERO)];
+        expected_accounts.sort();
+        assert_eq!(accounts, expected_accounts);
+
+        let mut storage = state.iter_storage(&address2)?.collect::<Vec<_>>();
+        storage.sort();
+        assert_eq!(
+            storage,
+            vec![
+                (U256::from(1), U256::from(100)),
+                (U256::from(3), U256::from(300))
+            ]
+        );
+
+        assert_eq!(state.iter_storage(&address1)?.count(), 0);
+        assert_eq!(state.iter_storage(&Address::random())?.count(), 0);
+
+        Ok(())
+    }
 }
@@ -5,15 +5,24 @@ use edr_state_api::account::{Account, AccountInfo, BasicAccount};
 use hasher::{Hasher, HasherKeccak};
 use rpds::HashTrieMapSync;
 
-use crate::{account::AccountTrieMutation, storage::StorageTrie, PersistentAccountTrie};
+use crate::{
+    account::AccountTrieMutation,
+    storage::{StoragePreimages, StorageTrie},
+    PersistentAccountTrie,
+};
 
 type StorageTries = HashTrieMapSync<Address, StorageTrie>;
+type StorageRootOverrides = HashTrieMapSync<Address, B256>;
 
 /// A trie for maintaining the state of accounts and their storage.
 #[derive(Clone, Debug, Default)]
 pub struct PersistentAccountAndStorageTrie {
     account_trie: PersistentAccountTrie,
     storage_tries: StorageTries,
+    /// Storage roots that are used instead of the storage tries' roots when
+    /// calculating the state root.
+    storage_root_overrides: StorageRootOverrides,
+    storage_preimages: StoragePreimages,
 }
 
 impl PersistentAccountAndStorageTrie {
@@ -40,6 +49,29 @@ impl PersistentAccountAndStorageTrie {
         self.account_trie.account(address)
     }
 
+    /// Returns an iterator over the addresses and accounts in the state, in no
+    /// particular order.
+    pub fn accounts(&self) -> impl Iterator<Item = (Address, BasicAccount)> + '_ {
+        // A storage trie is stored for every account
+        self.storage_tries.keys().map(|address| {
+            let account = self
+                .account_trie
+                .account(address)
+                .expect("Account must exist in state, if a storage trie is stored for it");
+
+            (*address, account)
+        })
+    }
+
+    /// Retrieves the non-zero storage slots of the account at the specified
+    /// address, as pairs of indices and values, if the account exists.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn account_storage_slots(&self, address: &Address) -> Option<Vec<(U256, U256)>> {
+        self.storage_tries
+            .get(address)
+            .map(|storage_trie| storage_trie.storage_slots(&self.storage_preimages))
+    }
+
     /// Retrieves the storage corresponding to the account at the
     /// specified address and the specified index, if they exist.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
@@ -120,7 +152,7 @@ impl PersistentAccountAndStorageTrie {
                         code_hash: account.code_hash,
                         nonce: account.nonce,
                         storage: storage_trie.storage(),
-                        storage_root: storage_trie.root(),
+                        storage_root: account.storage_root,
                     };
 
                     Some((*address, account))
@@ -149,6 +181,45 @@ impl PersistentAccountAndStorageTrie {
             .insert_storage_slot(address, index, value, default_account_fn)
     }
 
+    /// Overrides the storage root of the account at the specified address,
+    /// creating the account if it doesn't exist. The override is used instead
+    /// of the account's storage trie root until it's removed by passing
+    /// `None`, or the account's storage is reset.
+    ///
+    /// Removing an override doesn't create the account.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self, default_account_fn))
+    )]
+    pub fn set_account_storage_root<ErrorT>(
+        &mut self,
+        address: &Address,
+        storage_root: Option<B256>,
+        default_account_fn: &dyn Fn() -> Result<AccountInfo, ErrorT>,
+    ) -> Result<(), ErrorT> {
+        let account_info = if let Some(storage_root) = storage_root {
+            self.storage_root_overrides
+                .insert_mut(*address, storage_root);
+
+            match self.account_trie.account(address) {
+                Some(account) => AccountInfo::from(account),
+                None => default_account_fn()?,
+            }
+        } else {
+            self.storage_root_overrides.remove_mut(address);
+
+            let Some(account) = self.account_trie.account(address) else {
+                return Ok(());
+            };
+
+            AccountInfo::from(account)
+        };
+
+        self.mutate().insert_account_info(address, &account_info);
+
+        Ok(())
+    }
+
     /// Retrieves the trie's state root.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn state_root(&self) -> B256 {
@@ -158,13 +229,18 @@ impl PersistentAccountAndStorageTrie {
     /// Retrieves the storage root of the account at the specified address.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn storage_root(&self, address: &Address) -> Option<B256> {
-        self.storage_tries.get(address).map(StorageTrie::root)
+        self.storage_root_overrides
+            .get(address)
+            .copied()
+            .or_else(|| self.storage_tries.get(address).map(StorageTrie::root))
     }
 
     fn mutate(&mut self) -> AccountAndStorageTrieMutation<'_> {
         AccountAndStorageTrieMutation {
             account_trie_mut: self.account_trie.mutate(),
             storage_tries: &mut self.storage_tries,
+            storage_root_overrides: &mut self.storage_root_overrides,
+            storage_preimages: &self.storage_preimages,
         }
     }
 }
@@ -174,13 +250,24 @@ impl PersistentAccountAndStorageTrie {
 struct AccountAndStorageTrieMutation<'a> {
     account_trie_mut: AccountTrieMutation<'a>,
     storage_tries: &'a mut StorageTries,
+    storage_root_overrides: &'a mut StorageRootOverrides,
+    storage_preimages: &'a StoragePreimages,
 }
 
 impl AccountAndStorageTrieMutation<'_> {
+    /// Returns the overridden storage root of the account at the specified
+    /// address, if any. Otherwise, returns the provided storage trie root.
+    fn storage_root_or_override(&self, address: &Address, storage_trie_root: B256) -> B256 {
+        self.storage_root_overrides
+            .get(address)
+            .copied()
+            .unwrap_or(storage_trie_root)
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn init_account(&mut self, address: &Address, account_info: &AccountInfo) {
         let storage_trie = StorageTrie::default();
-        let storage_root = storage_trie.root();
+        let storage_root = self.storage_root_or_override(address, storage_trie.root());
 
         self.storage_tries.insert_mut(*address, storage_trie);
 
@@ -202,6 +289,7 @@ impl AccountAndStorageTrieMutation<'_> {
             self.storage_tries.insert_mut(*address, storage_trie);
             root
         };
+        let storage_root = self.storage_root_or_override(address, storage_root);
 
         self.account_trie_mut.insert_account_info_with_storage_root(
             address,
@@ -216,7 +304,7 @@ impl AccountAndStorageTrieMutation<'_> {
         let storage_root = if let Some(storage_trie) = self.storage_tries.get_mut(address) {
             // Dropping the mutation will update the storage trie root
             if !account.storage.is_empty() {
-                let mut storage_trie_mutation = storage_trie.mutate();
+                let mut storage_trie_mutation = storage_trie.mutate(self.storage_preimages);
                 storage_trie_mutation.set_storage_slots(&account.storage);
             }
 
@@ -225,7 +313,7 @@ impl AccountAndStorageTrieMutation<'_> {
             let mut storage_trie = StorageTrie::default();
 
             if !account.storage.is_empty() {
-                let mut storage_trie_mutation = storage_trie.mutate();
+                let mut storage_trie_mutation = storage_trie.mutate(self.storage_preimages);
                 storage_trie_mutation.set_storage_slots(&account.storage);
             }
 
@@ -233,6 +321,7 @@ impl AccountAndStorageTrieMutation<'_> {
             self.storage_tries.insert_mut(*address, storage_trie);
             root
         };
+        let storage_root = self.storage_root_or_override(address, storage_root);
 
         self.account_trie_mut.insert_account_info_with_storage_root(
             address,
@@ -252,17 +341,26 @@ impl AccountAndStorageTrieMutation<'_> {
     ) -> Result<Option<U256>, ErrorT> {
         let (storage_root, old_value) =
             if let Some(storage_trie) = self.storage_tries.get_mut(address) {
-                let old_value = { storage_trie.mutate().set_storage_slot(index, value) };
+                let old_value = {
+                    storage_trie
+                        .mutate(self.storage_preimages)
+                        .set_storage_slot(index, value)
+                };
                 (storage_trie.root(), old_value)
             } else {
                 let mut storage_trie = StorageTrie::default();
 
-                let old_value = { storage_trie.mutate().set_storage_slot(index, value) };
+                let old_value = {
+                    storage_trie
+                        .mutate(self.storage_preimages)
+                        .set_storage_slot(index, value)
+                };
 
                 let storage_root = storage_trie.root();
                 self.storage_tries.insert_mut(*address, storage_trie);
                 (storage_root, old_value)
             };
+        let storage_root = self.storage_root_or_override(address, storage_root);
 
         let account = if let Some(mut account) = self.account_trie_mut.account(address) {
             account.storage_root = storage_root;
@@ -300,6 +398,7 @@ impl AccountAndStorageTrieMutation<'_> {
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn remove_account_storage(&mut self, address: &Address) {
         self.storage_tries.remove_mut(address);
+        self.storage_root_overrides.remove_mut(address);
     }
 }
 
@@ -383,4 +482,58 @@ mod tests {
 
         assert_eq!(state.state_root(), old);
     }
+
+    #[test]
+    fn storage_root_override() {
+        let address = Address::random();
+        let accounts = [(address, AccountInfo::default())].into_iter().collect();
+
+        let mut state = PersistentAccountAndStorageTrie::with_accounts(&accounts);
+        let original_state_root = state.state_root();
+
+        let storage_root = B256::random();
+        state
+            .set_account_storage_root::<()>(&address, Some(storage_root), &|| {
+                Ok(AccountInfo::default())
+            })
+            .unwrap();
+
+        assert_eq!(state.storage_root(&address), Some(storage_root));
+        assert_ne!(state.state_root(), original_state_root);
+
+        // The override persists when the storage changes
+        state
+            .set_account_storage_slot::<()>(&address, &U256::from(1), &U256::from(1), &|| {
+                Ok(AccountInfo::default())
+            })
+            .unwrap();
+
+        assert_eq!(state.storage_root(&address), Some(storage_root));
+
+        state
+            .set_account_storage_slot::<()>(&address, &U256::from(1), &U256::ZERO, &|| {
+                Ok(AccountInfo::default())
+            })
+            .unwrap();
+        state
+            .set_account_storage_root::<()>(&address, None, &|| Ok(AccountInfo::default()))
+            .unwrap();
+
+        assert_eq!(state.storage_root(&address), Some(KECCAK_NULL_RLP));
+        assert_eq!(state.state_root(), original_state_root);
+    }
+
+    #[test]
+    fn removing_storage_root_override_does_not_create_account() {
+        let mut state = PersistentAccountAndStorageTrie::default();
+        let original_state_root = state.state_root();
+
+        let address = Address::random();
+        state
+            .set_account_storage_root::<()>(&address, None, &|| Ok(AccountInfo::default()))
+            .unwrap();
+
+        assert_eq!(state.account(&address), None);
+        assert_eq!(state.state_root(), original_state_root);
+    }
 }
@@ -1,12 +1,21 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use alloy_rlp::Decodable;
-use edr_primitives::{B256, U256};
+use edr_primitives::{HashMap, B256, U256};
 use hasher::{Hasher, HasherKeccak};
+use parking_lot::RwLock;
 use revm_state::EvmStorage;
 
 use crate::{persistent_db::PersistentMemoryDB, query::TrieQuery};
 
+/// Mapping of hashed storage indices to their preimages, as storage tries only
+/// store the hashes.
+///
+/// The mapping is shared by all storage tries of a state and its clones. A
+/// preimage is valid regardless of the trie that it was recorded for, so
+/// preimages are never removed and each index is only stored once.
+pub(super) type StoragePreimages = Arc<RwLock<HashMap<B256, U256>>>;
+
 #[derive(Debug)]
 pub(super) struct StorageTrie {
     db: Arc<PersistentMemoryDB>,
@@ -14,11 +23,12 @@ pub(super) struct StorageTrie {
 }
 
 impl<'a> StorageTrie {
-    pub fn mutate(&'a mut self) -> StorageTrieMutation<'a> {
+    pub fn mutate(&'a mut self, preimages: &'a StoragePreimages) -> StorageTrieMutation<'a> {
         let trie_query = self.trie_query();
         StorageTrieMutation {
             storage_trie: self,
             trie_query,
+            preimages,
         }
     }
 
@@ -39,6 +49,23 @@ impl<'a> StorageTrie {
             .collect()
     }
 
+    /// Returns the non-zero storage slots as pairs of indices and values.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(preimages)))]
+    pub fn storage_slots(&self, preimages: &StoragePreimages) -> Vec<(U256, U256)> {
+        let preimages = preimages.read();
+
+        self.trie_query()
+            .iter()
+            .map(|(hashed_index, encoded_value)| {
+                let index = preimages
+                    .get(&B256::from_slice(&hashed_index))
+                    .expect("The preimages of all storage indices are stored");
+
+                (*index, decode_u256(encoded_value))
+            })
+            .collect()
+    }
+
     pub fn root(&self) -> B256 {
         self.root
     }
@@ -71,6 +98,7 @@ impl Default for StorageTrie {
 pub(super) struct StorageTrieMutation<'a> {
     storage_trie: &'a mut StorageTrie,
     trie_query: TrieQuery,
+    preimages: &'a StoragePreimages,
 }
 
 impl StorageTrieMutation<'_> {
@@ -95,6 +123,9 @@ impl StorageTrieMutation<'_> {
                 self.trie_query.remove_hashed_key(&hashed_index);
             }
         } else {
+            self.preimages
+                .write()
+                .insert(B256::from_slice(&hashed_index), *index);
             self.trie_query.insert_hashed_key(hashed_index, value);
         }
 
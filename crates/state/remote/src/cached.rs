@@ -33,6 +33,8 @@ pub struct CachedRemoteState<
     account_cache: HashMap<u64, HashMap<Address, AccountAndStorage>>,
     /// Mapping of block numbers to cached code
     code_cache: HashMap<u64, HashMap<B256, Bytecode>>,
+    /// Mapping of block numbers to cached block hashes
+    block_hash_cache: HashMap<u64, B256>,
 }
 
 impl<
@@ -47,8 +49,57 @@ impl<
             remote,
             account_cache: HashMap::default(),
             code_cache: HashMap::default(),
+            block_hash_cache: HashMap::default(),
         }
     }
+
+    /// Returns an iterator over the cached accounts of the state's block
+    /// number.
+    pub fn cached_accounts(&self) -> impl Iterator<Item = (&Address, &AccountInfo)> {
+        self.account_cache
+            .get(&self.remote.block_number())
+            .into_iter()
+            .flat_map(|block_accounts| {
+                block_accounts
+                    .iter()
+                    .map(|(address, account)| (address, &account.info))
+            })
+    }
+
+    /// Returns an iterator over the cached storage slots of the account at the
+    /// provided address, for the state's block number.
+    pub fn cached_storage(&self, address: &Address) -> impl Iterator<Item = (&U256, &U256)> {
+        self.account_cache
+            .get(&self.remote.block_number())
+            .and_then(|block_accounts| block_accounts.get(address))
+            .into_iter()
+            .flat_map(|account| account.storage.iter())
+    }
+}
+
+impl<
+        RpcBlockT: RpcBlockChainSpec<RpcBlock<B256>: RpcEthBlock>,
+        RpcReceiptT: DeserializeOwned + Serialize,
+        RpcTransactionT: DeserializeOwned + Serialize,
+    > CachedRemoteState<RpcBlockT, RpcReceiptT, RpcTransactionT>
+{
+    /// Retrieves the hash of the remote block with the provided number, if it
+    /// exists.
+    pub fn block_hash_mut(&mut self, block_number: u64) -> Result<Option<B256>, StateError> {
+        if let Some(block_hash) = self.block_hash_cache.get(&block_number) {
+            return Ok(Some(*block_hash));
+        }
+
+        let block_hash = self.remote.block_hash(block_number)?;
+
+        if let Some(block_hash) = block_hash
+            && self.remote.is_cacheable()?
+        {
+            self.block_hash_cache.insert(block_number, block_hash);
+        }
+
+        Ok(block_hash)
+    }
 }
 
 impl<
@@ -82,6 +82,17 @@ impl<
         })?
         .map(|block| *block.state_root()))
     }
+
+    /// Retrieve the hash of the given block, if it exists.
+    pub fn block_hash(&self, block_number: u64) -> Result<Option<B256>, RpcClientError> {
+        Ok(tokio::task::block_in_place(move || {
+            self.runtime.block_on(
+                self.client
+                    .get_block_by_number(PreEip1898BlockSpec::Number(block_number)),
+            )
+        })?
+        .and_then(|block| block.hash().copied()))
+    }
 }
 
 impl<
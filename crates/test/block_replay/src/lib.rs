@@ -176,8 +176,11 @@ pub async fn run_full_block<
 
     let evm_config = EvmConfig {
         chain_id: prior_blockchain.chain_id(),
+        disable_balance_check: false,
+        disable_base_fee: false,
         disable_eip3607: true,
         limit_contract_code_size: None,
+        limit_contract_initcode_size: None,
         transaction_gas_cap: None,
     };
 
@@ -387,8 +390,11 @@ pub async fn assert_replay_header<
 
     let evm_config = EvmConfig {
         chain_id: prior_blockchain.chain_id(),
+        disable_balance_check: false,
+        disable_base_fee: false,
         disable_eip3607: true,
         limit_contract_code_size: None,
+        limit_contract_initcode_size: None,
         transaction_gas_cap: None,
     };
 
@@ -203,6 +203,7 @@ pub fn insert_dummy_block_with_transaction<
         &execution_result,
         0,
         0,
+        None,
         blockchain.hardfork(),
     );
 
@@ -15,6 +15,9 @@ pub struct MemPoolTestFixture {
     pub mem_pool: MemPool<L1SignedTransaction>,
     /// The state.
     pub state: PersistentStateTrie,
+    /// The current time in seconds since the Unix epoch, used when adding
+    /// transactions.
+    pub now: u64,
 }
 
 impl MemPoolTestFixture {
@@ -27,6 +30,7 @@ impl MemPoolTestFixture {
             // SAFETY: literal is non-zero
             mem_pool: MemPool::new(unsafe { NonZeroU64::new_unchecked(10_000_000u64) }, None),
             state: PersistentStateTrie::with_accounts_and_storage(trie),
+            now: 0,
         }
     }
 
@@ -41,6 +45,7 @@ impl MemPoolTestFixture {
             state: PersistentStateTrie::with_accounts_and_storage(
                 PersistentAccountAndStorageTrie::default(),
             ),
+            now: 0,
         }
     }
 
@@ -49,17 +54,20 @@ impl MemPoolTestFixture {
         &mut self,
         transaction: L1SignedTransaction,
     ) -> Result<(), MemPoolAddTransactionError<StateError>> {
-        self.mem_pool.add_transaction(&self.state, transaction)
+        self.mem_pool
+            .add_transaction(&self.state, transaction, self.now)
+            .map(|_removed| ())
     }
 
     /// Sets the block gas limit.
     pub fn set_block_gas_limit(&mut self, block_gas_limit: NonZeroU64) -> Result<(), StateError> {
         self.mem_pool
             .set_block_gas_limit(&self.state, block_gas_limit)
+            .map(|_removed| ())
     }
 
     /// Updates the mem pool.
     pub fn update(&mut self) -> Result<(), StateError> {
-        self.mem_pool.update(&self.state)
+        self.mem_pool.update(&self.state).map(|_removed| ())
     }
 }
@@ -60,6 +60,8 @@ macro_rules! impl_execution_receipt_serde_tests {
                         contract_address: Some($crate::Address::random()),
                         gas_used: 100,
                         effective_gas_price: Some(100),
+                        blob_gas_used: None,
+                        blob_gas_price: None,
                     };
 
                     // ASSUMPTION: The transaction data doesn't matter for this test, so we can use a default transaction.
@@ -2,7 +2,7 @@
 #![warn(missing_docs)]
 
 use edr_chain_spec::EvmSpecId;
-use edr_primitives::{Address, Bytes, U256};
+use edr_primitives::{Address, Bytes, B256, U256};
 use edr_runtime::transaction;
 use edr_transaction::TxKind;
 
@@ -97,3 +97,34 @@ pub fn dummy_eip1559_transaction(
 
     transaction::validate(transaction, EvmSpecId::default())
 }
+
+/// Creates a dummy EIP-4844 transaction with a single blob.
+pub fn dummy_eip4844_transaction(
+    caller: Address,
+    nonce: u64,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    max_fee_per_blob_gas: u128,
+) -> Result<edr_chain_l1::L1SignedTransaction, transaction::CreationError> {
+    // Versioned hashes start with the KZG version byte
+    let mut blob_hash = B256::ZERO;
+    blob_hash[0] = 0x01;
+
+    let request = edr_chain_l1::request::Eip4844 {
+        chain_id: 123,
+        nonce,
+        max_priority_fee_per_gas,
+        max_fee_per_gas,
+        gas_limit: 30_000,
+        to: Address::random(),
+        value: U256::ZERO,
+        input: Bytes::new(),
+        access_list: Vec::new(),
+        max_fee_per_blob_gas,
+        blob_hashes: vec![blob_hash],
+    };
+    let transaction = request.fake_sign(caller);
+    let transaction = edr_chain_l1::L1SignedTransaction::from(transaction);
+
+    transaction::validate(transaction, EvmSpecId::default())
+}
@@ -0,0 +1,291 @@
+use std::{
+    fs::File,
+    io::{BufRead as _, BufReader},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use anyhow::Context as _;
+use edr_generic::GenericChainSpec;
+use edr_provider::{time::TimeSinceEpoch, NoopLogger, ProviderRequest};
+use edr_rpc_eth::jsonrpc;
+use edr_scenarios::{JournalEntry, JournalHeader};
+use edr_solidity::contract_decoder::ContractDecoder;
+use tokio::{runtime, task};
+
+/// A timer that returns the time at which the journaled request that is
+/// currently being replayed was received, to make block timestamps
+/// deterministic.
+#[derive(Clone, Debug, Default)]
+struct JournalTime {
+    seconds_since_epoch: Arc<AtomicU64>,
+}
+
+impl JournalTime {
+    fn set_timestamp_millis(&self, timestamp: u64) {
+        self.seconds_since_epoch
+            .store(timestamp / 1000, Ordering::Relaxed);
+    }
+}
+
+impl TimeSinceEpoch for JournalTime {
+    fn since(&self, other: SystemTime) -> Result<u64, std::time::SystemTimeError> {
+        let other = other
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        Ok(self.since_epoch().saturating_sub(other))
+    }
+
+    fn since_epoch(&self) -> u64 {
+        self.seconds_since_epoch.load(Ordering::Relaxed)
+    }
+}
+
+/// Re-executes the requests of a journal against a fresh provider and reports
+/// the responses that differ from the journaled responses.
+pub async fn replay(journal_path: &Path) -> anyhow::Result<()> {
+    let (header, entries) = load_journal(journal_path)?;
+
+    if let Some(chain_type) = &header.config.chain_type {
+        anyhow::ensure!(
+            chain_type == edr_generic::CHAIN_TYPE,
+            "Unsupported chain type: {chain_type}"
+        );
+    }
+
+    let provider_config = edr_provider::ProviderConfig::<edr_chain_l1::Hardfork>::try_from(
+        edr_napi_core::provider::Config::from(header.config.provider_config),
+    )?;
+
+    println!("Replaying {} requests", entries.len());
+
+    let mismatches = replay_entries(provider_config, header.timestamp, entries).await?;
+
+    anyhow::ensure!(
+        mismatches == 0,
+        "{mismatches} replayed responses differ from the journal"
+    );
+
+    println!("All responses match the journal");
+
+    Ok(())
+}
+
+/// Re-executes the journaled requests against a fresh provider that is created
+/// at the provided time, returning the number of responses that differ from
+/// the journaled responses.
+async fn replay_entries(
+    provider_config: edr_provider::ProviderConfig<edr_chain_l1::Hardfork>,
+    timestamp: u64,
+    entries: Vec<JournalEntry>,
+) -> anyhow::Result<usize> {
+    let timer = JournalTime::default();
+    timer.set_timestamp_millis(timestamp);
+
+    let provider = create_provider(provider_config, timer.clone()).await?;
+
+    let mut mismatches: usize = 0;
+    for (index, entry) in entries.into_iter().enumerate() {
+        timer.set_timestamp_millis(entry.timestamp);
+
+        let request =
+            match serde_json::from_str::<ProviderRequest<GenericChainSpec>>(&entry.request) {
+                Ok(request) => request,
+                Err(error) => {
+                    // The error responses of requests that cannot be deserialized are produced by
+                    // the N-API layer, so only whether the request failed is compared.
+                    if entry.response.get("error").is_none() {
+                        mismatches += 1;
+
+                        println!("Request #{index} could not be deserialized: {error}");
+                        println!("  request:  {}", entry.request);
+                        println!("  expected: {}", entry.response);
+                    }
+
+                    continue;
+                }
+            };
+
+        let actual = handle_request(&provider, request).await?;
+
+        if actual != entry.response {
+            mismatches += 1;
+
+            println!("Request #{index} has a different response:");
+            println!("  request:  {}", entry.request);
+            println!("  expected: {}", entry.response);
+            println!("  actual:   {actual}");
+        }
+    }
+
+    Ok(mismatches)
+}
+
+async fn create_provider(
+    provider_config: edr_provider::ProviderConfig<edr_chain_l1::Hardfork>,
+    timer: JournalTime,
+) -> anyhow::Result<Arc<edr_provider::Provider<GenericChainSpec, JournalTime>>> {
+    let provider = task::spawn_blocking(move || {
+        edr_provider::Provider::new(
+            runtime::Handle::current(),
+            Box::<NoopLogger<GenericChainSpec, JournalTime>>::default(),
+            Box::new(|_| ()),
+            provider_config,
+            Arc::new(ContractDecoder::default()),
+            timer,
+        )
+    })
+    .await??;
+
+    Ok(Arc::new(provider))
+}
+
+/// Handles the request, returning the JSON-RPC response data in the format in
+/// which it's journaled.
+async fn handle_request(
+    provider: &Arc<edr_provider::Provider<GenericChainSpec, JournalTime>>,
+    request: ProviderRequest<GenericChainSpec>,
+) -> anyhow::Result<serde_json::Value> {
+    // The N-API layer doesn't return a response to a batch that only consists of
+    // notifications, which is journaled as `null`
+    let is_notification_batch = matches!(
+        &request,
+        ProviderRequest::Batch(requests) if requests.iter().all(|request| request.id.is_none())
+    );
+
+    let response = {
+        let provider = provider.clone();
+        task::spawn_blocking(move || provider.handle_request(request))
+            .await?
+            .map(|response| response.result)
+    };
+
+    if is_notification_batch && response.is_ok() {
+        return Ok(serde_json::Value::Null);
+    }
+
+    Ok(serde_json::to_value(jsonrpc::ResponseData::from(response))?)
+}
+
+fn load_journal(journal_path: &Path) -> anyhow::Result<(JournalHeader, Vec<JournalEntry>)> {
+    println!("Loading journal from {journal_path:?}");
+
+    let reader = BufReader::new(File::open(journal_path)?);
+    let mut lines = reader.lines();
+
+    let first_line = lines.next().context("Journal file is empty")??;
+    let header: JournalHeader = serde_json::from_str(&first_line)?;
+
+    let entries = lines
+        .map(|line| Ok(serde_json::from_str(&line?)?))
+        .collect::<anyhow::Result<Vec<JournalEntry>>>()?;
+
+    Ok((header, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use edr_provider::test_utils::create_test_config;
+    use serde_json::json;
+
+    use super::*;
+
+    /// The time at which the journaled provider was created.
+    const PROVIDER_TIMESTAMP: u64 = 1_700_000_000_000;
+
+    const REQUESTS: [&str; 3] = [
+        r#"{"jsonrpc":"2.0","method":"evm_mine","params":[],"id":1}"#,
+        r#"{"jsonrpc":"2.0","method":"evm_mine","params":[],"id":2}"#,
+        r#"{"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["latest",false],"id":3}"#,
+    ];
+
+    fn provider_config() -> edr_provider::ProviderConfig<edr_chain_l1::Hardfork> {
+        let mut config = create_test_config();
+        // Derive the genesis timestamp from the timer, instead of the wall clock
+        config.initial_date = None;
+        config
+    }
+
+    /// Journals `REQUESTS`, which are received a minute apart.
+    async fn record_journal() -> anyhow::Result<Vec<JournalEntry>> {
+        let timer = JournalTime::default();
+        timer.set_timestamp_millis(PROVIDER_TIMESTAMP);
+
+        let provider = create_provider(provider_config(), timer.clone()).await?;
+
+        let mut entries = Vec::new();
+        for (minutes, request) in (1..).zip(REQUESTS) {
+            let timestamp = PROVIDER_TIMESTAMP + minutes * 60_000;
+            timer.set_timestamp_millis(timestamp);
+
+            let response = handle_request(&provider, serde_json::from_str(request)?).await?;
+            entries.push(JournalEntry {
+                timestamp,
+                request: request.to_owned(),
+                response,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    #[test]
+    fn journal_time_uses_journaled_seconds() {
+        let timer = JournalTime::default();
+        timer.set_timestamp_millis(PROVIDER_TIMESTAMP + 999);
+
+        assert_eq!(timer.since_epoch(), PROVIDER_TIMESTAMP / 1000);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replay_reproduces_journaled_responses() -> anyhow::Result<()> {
+        let entries = record_journal().await?;
+
+        let mismatches = replay_entries(provider_config(), PROVIDER_TIMESTAMP, entries).await?;
+        assert_eq!(mismatches, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replay_reports_different_responses() -> anyhow::Result<()> {
+        let mut entries = record_journal().await?;
+
+        // Mining the first block at a different time changes the hash of the latest
+        // block, but not the responses of the `evm_mine` requests.
+        entries[0].timestamp += 1_000;
+
+        let mismatches = replay_entries(provider_config(), PROVIDER_TIMESTAMP, entries).await?;
+        assert_eq!(mismatches, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn replay_only_compares_failure_of_undeserializable_requests() -> anyhow::Result<()> {
+        let request = r#"{"jsonrpc":"2.0","method":"eth_unknownMethod","params":[],"id":1}"#;
+        let entries = vec![
+            JournalEntry {
+                timestamp: PROVIDER_TIMESTAMP,
+                request: request.to_owned(),
+                response: json!({ "error": { "code": -32601, "message": "Method not found" } }),
+            },
+            JournalEntry {
+                timestamp: PROVIDER_TIMESTAMP,
+                request: request.to_owned(),
+                response: json!({ "result": null }),
+            },
+        ];
+
+        let mismatches = replay_entries(provider_config(), PROVIDER_TIMESTAMP, entries).await?;
+        assert_eq!(mismatches, 1);
+
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use clap::{Parser, Subcommand};
 mod benchmark;
 mod compare_test_runs;
 mod execution_api;
+mod journal;
 mod remote_block;
 mod scenario;
 mod update;
@@ -46,6 +47,12 @@ enum Command {
     ConvertScenario { path: PathBuf },
     /// Generate Ethereum execution API
     GenExecutionApi,
+    /// Replays a request journal against a fresh provider and compares the
+    /// responses to the journaled responses.
+    ReplayJournal {
+        /// The path to the journal file (JSON lines)
+        path: PathBuf,
+    },
     /// Replays a block from a remote node and compares it to the mined block.
     ReplayBlock {
         #[clap(long, short, value_enum)]
@@ -84,6 +91,7 @@ async fn main() -> anyhow::Result<()> {
         } => benchmark::run(working_directory, &test_command, iterations),
         Command::ConvertScenario { path } => scenario::convert(path).await,
         Command::GenExecutionApi => execution_api::generate(Mode::Overwrite),
+        Command::ReplayJournal { path } => journal::replay(&path).await,
         Command::ReplayBlock {
             chain_type,
             url,
@@ -9,7 +9,8 @@ use anyhow::Context;
 use derive_where::derive_where;
 use edr_generic::GenericChainSpec;
 use edr_provider::{
-    time::CurrentTime, Logger, ProviderErrorForChainSpec, ProviderRequest, ProviderSpec,
+    hardhat_rpc_types::LoggingOptions, time::CurrentTime, Logger, ProviderErrorForChainSpec,
+    ProviderRequest, ProviderSpec,
 };
 use edr_rpc_eth::jsonrpc;
 use edr_scenarios::ScenarioConfig;
@@ -275,6 +276,13 @@ impl<ChainSpecT: ProviderSpec<CurrentTime>> Logger<ChainSpecT, CurrentTime>
 
     fn set_is_enabled(&mut self, _is_enabled: bool) {}
 
+    fn set_options(
+        &mut self,
+        _options: LoggingOptions,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
     fn print_method_logs(
         &mut self,
         _method: &str,
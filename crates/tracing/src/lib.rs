@@ -9,8 +9,8 @@ use edr_blockchain_api::BlockHashByNumber;
 use edr_chain_spec::HaltReasonTrait;
 use edr_chain_spec_evm::{
     interpreter::{
-        return_revert, CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome,
-        EthInterpreter, Interpreter, Jumps as _, SuccessOrHalt,
+        return_revert, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
+        CreateScheme, EthInterpreter, Interpreter, Jumps as _, SuccessOrHalt,
     },
     result::{ExecutionResult, Output},
     ContextTrait, Inspector, JournalTrait,
@@ -29,6 +29,8 @@ pub enum TraceMessage<HaltReasonT: HaltReasonTrait> {
     Step(Step),
     /// Event that occurs after a call or create message.
     After(AfterMessage<HaltReasonT>),
+    /// Event that occurs when a contract self-destructs.
+    SelfDestruct(SelfDestructMessage),
 }
 
 /// Temporary before message type for handling traces
@@ -42,6 +44,10 @@ pub struct BeforeMessage {
     pub to: Option<Address>,
     /// Whether the call is a static call
     pub is_static_call: bool,
+    /// The scheme of the call, or `None` if it's a create
+    pub scheme: Option<CallScheme>,
+    /// The scheme of the create, or `None` if it's a call
+    pub create_scheme: Option<CreateScheme>,
     /// Transaction gas limit
     pub gas_limit: u64,
     /// Input data
@@ -64,6 +70,17 @@ pub struct AfterMessage<HaltReasonT: HaltReasonTrait> {
     pub contract_address: Option<Address>,
 }
 
+/// Event that occurs when a contract self-destructs.
+#[derive(Clone, Debug)]
+pub struct SelfDestructMessage {
+    /// The address of the self-destructed contract
+    pub contract: Address,
+    /// The address that receives the contract's balance
+    pub target: Address,
+    /// The balance that is transferred to the target
+    pub value: U256,
+}
+
 /// A trace for an EVM call.
 #[derive(Clone, Debug)]
 #[derive_where(Default)]
@@ -142,6 +159,11 @@ impl<HaltReasonT: HaltReasonTrait> Trace<HaltReasonT> {
     pub fn add_step(&mut self, step: Step) {
         self.messages.push(TraceMessage::Step(step));
     }
+
+    /// Adds a self-destruct message
+    pub fn add_self_destruct(&mut self, message: SelfDestructMessage) {
+        self.messages.push(TraceMessage::SelfDestruct(message));
+    }
 }
 
 /// Object that gathers trace information during EVM execution and can be turned
@@ -249,6 +271,8 @@ impl<HaltReasonT: HaltReasonTrait> TraceCollector<HaltReasonT> {
             caller: inputs.caller,
             to: Some(inputs.target_address),
             is_static_call: inputs.is_static,
+            scheme: Some(inputs.scheme),
+            create_scheme: None,
             gas_limit: inputs.gas_limit,
             data: input_data,
             value: match inputs.value {
@@ -353,6 +377,8 @@ impl<HaltReasonT: HaltReasonTrait> TraceCollector<HaltReasonT> {
             to: None,
             gas_limit: inputs.gas_limit,
             is_static_call: false,
+            scheme: None,
+            create_scheme: Some(inputs.scheme),
             data: inputs.init_code.clone(),
             value: inputs.value,
             code_address: None,
@@ -470,6 +496,18 @@ impl<HaltReasonT: HaltReasonTrait> TraceCollector<HaltReasonT> {
             });
         }
     }
+
+    /// Notifies the trace collector that a contract self-destructed.
+    pub fn notify_self_destruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.validate_before_message();
+
+        self.current_trace_mut()
+            .add_self_destruct(SelfDestructMessage {
+                contract,
+                target,
+                value,
+            });
+    }
 }
 
 impl<
@@ -520,4 +558,8 @@ impl<
     fn step(&mut self, interpreter: &mut Interpreter<EthInterpreter>, context: &mut ContextT) {
         self.notify_step_start(interpreter, context.journal());
     }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.notify_self_destruct(contract, target, value);
+    }
 }
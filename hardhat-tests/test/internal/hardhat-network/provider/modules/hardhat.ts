@@ -2661,12 +2661,23 @@ describe("Hardhat module", function () {
           describe(`When EIP-1559 is active (${hardfork})`, function () {
             useProvider({ hardfork });
 
-            it("Should be disabled", async function () {
+            it("Should make txs below the min priority fee fail", async function () {
+              await this.provider.send("hardhat_setMinGasPrice", [
+                numberToRpcQuantity(20),
+              ]);
+
               await assertInvalidInputError(
                 this.provider,
-                "hardhat_setMinGasPrice",
-                [numberToRpcQuantity(1)],
-                "hardhat_setMinGasPrice is not supported when EIP-1559 is active"
+                "eth_sendTransaction",
+                [
+                  {
+                    from: DEFAULT_ACCOUNTS_ADDRESSES[0],
+                    to: DEFAULT_ACCOUNTS_ADDRESSES[1],
+                    maxFeePerGas: numberToRpcQuantity(10_000_000_000),
+                    maxPriorityFeePerGas: numberToRpcQuantity(10),
+                  },
+                ],
+                "Transaction gas price is 10, which is below the minimum of 20"
               );
             });
           });
@@ -2859,15 +2870,17 @@ describe("Hardhat module", function () {
         describe("in hardforks before the merge", function () {
           useProvider({ hardfork: "london" });
 
-          it("should throw", async function () {
-            await assertInvalidInputError(
-              this.provider,
-              "hardhat_setPrevRandao",
-              [
-                "0x1234567812345678123456781234567812345678123456781234567812345678",
-              ],
-              "hardhat_setPrevRandao is only available in post-merge hardforks"
+          it("should set the difficulty of the next block", async function () {
+            await this.provider.send("hardhat_setPrevRandao", [
+              "0x0000000000000000000000000000000000000000000000000000000000001234",
+            ]);
+            await this.provider.send("evm_mine");
+
+            const latestBlock = await this.provider.send(
+              "eth_getBlockByNumber",
+              ["latest", false]
             );
+            assert.equal(latestBlock.difficulty, "0x1234");
           });
         });
 